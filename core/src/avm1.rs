@@ -43,7 +43,7 @@ pub use object::{Object, ObjectPtr, TObject};
 use scope::Scope;
 use smallvec::alloc::borrow::Cow;
 pub use string::AvmString;
-pub use timer::Timers;
+pub use timer::{TimerCallback, Timers};
 pub use value::Value;
 
 macro_rules! avm_debug {
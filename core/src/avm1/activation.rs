@@ -625,17 +625,15 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
 
         // TODO(Herschel):
         if let Value::String(a) = a {
-            let mut s = b.coerce_to_string(self)?.to_string();
-            s.push_str(&a);
+            let b = b.coerce_to_string(self)?;
             self.context
                 .avm1
-                .push(AvmString::new(self.context.gc_context, s));
+                .push(AvmString::concat(self.context.gc_context, b, a));
         } else if let Value::String(b) = b {
-            let mut b = b.to_string();
-            b.push_str(&a.coerce_to_string(self)?);
+            let a = a.coerce_to_string(self)?;
             self.context
                 .avm1
-                .push(AvmString::new(self.context.gc_context, b));
+                .push(AvmString::concat(self.context.gc_context, b, a));
         } else {
             let result = b.coerce_to_f64(self)? + a.coerce_to_f64(self)?;
             self.context.avm1.push(result);
@@ -1250,6 +1248,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                             fetch,
                             url,
                             None,
+                            None,
                         );
                         self.context.navigator.spawn_future(process);
                     }
@@ -1348,6 +1347,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                         fetch,
                         url.to_string(),
                         None,
+                        None,
                     );
                     self.context.navigator.spawn_future(process);
                 }
@@ -1366,6 +1366,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                         fetch,
                         url.to_string(),
                         None,
+                        None,
                     );
                     self.context.navigator.spawn_future(process);
                 }
@@ -2038,11 +2039,11 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         // SWFv4 string concatenation
         // TODO(Herschel): Result with non-string operands?
         let a = self.context.avm1.pop();
-        let mut b = self.context.avm1.pop().coerce_to_string(self)?.to_string();
-        b.push_str(&a.coerce_to_string(self)?);
+        let b = self.context.avm1.pop().coerce_to_string(self)?;
+        let a = a.coerce_to_string(self)?;
         self.context
             .avm1
-            .push(AvmString::new(self.context.gc_context, b));
+            .push(AvmString::concat(self.context.gc_context, b, a));
         Ok(FrameControl::Continue)
     }
 
@@ -2410,6 +2411,8 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                             "application/x-www-form-urlencoded".to_string(),
                         ))),
                     ),
+                    // AVM1 only ever sends variables with GET or POST.
+                    method => (url, RequestOptions::with_method(method, None)),
                 }
             }
             None => (url, RequestOptions::get()),
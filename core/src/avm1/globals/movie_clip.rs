@@ -221,6 +221,7 @@ pub fn create_proto<'gc>(
         "focusEnabled" => [focus_enabled, set_focus_enabled],
         "_lockroot" => [lock_root, set_lock_root],
         "useHandCursor" => [use_hand_cursor, set_use_hand_cursor],
+        "hitArea" => [hit_area, set_hit_area],
     );
 
     object.into()
@@ -1228,6 +1229,7 @@ fn load_movie<'gc>(
         fetch,
         url.to_string(),
         None,
+        None,
     );
 
     activation.context.navigator.spawn_future(process);
@@ -1357,3 +1359,97 @@ fn set_use_hand_cursor<'gc>(
     this.set_use_hand_cursor(&mut activation.context, use_hand_cursor);
     Ok(())
 }
+
+fn hit_area<'gc>(
+    this: MovieClip<'gc>,
+    _activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this
+        .hit_area()
+        .map(|hit_area| hit_area.object())
+        .unwrap_or(Value::Undefined))
+}
+
+fn set_hit_area<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let hit_area = match value {
+        Value::Object(object) => object
+            .as_display_object()
+            .and_then(|hit_area| hit_area.as_movie_clip()),
+        _ => None,
+    };
+    this.set_hit_area(&mut activation.context, hit_area);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm1::test_utils::with_avm;
+
+    #[test]
+    fn clicks_register_through_an_off_screen_hit_area() {
+        with_avm(19, |activation, root| -> Result<(), Error> {
+            let owner = root.call_method(
+                "createEmptyMovieClip",
+                &["owner".into(), 1.into()],
+                activation,
+            )?;
+            let owner_object = owner.coerce_to_object(activation);
+            owner_object.set("onRelease", true.into(), activation)?;
+            let owner = owner_object.as_display_object().unwrap();
+
+            // The hit area is never added to the display list.
+            let swf_movie = activation.base_clip().movie().unwrap();
+            let hit_area =
+                MovieClip::new(SwfSlice::empty(swf_movie), activation.context.gc_context);
+            hit_area.post_instantiation(
+                &mut activation.context,
+                hit_area.into(),
+                None,
+                Instantiator::Avm1,
+                true,
+            );
+            let hit_area_object = hit_area.object().coerce_to_object(activation);
+            hit_area_object.set("onRelease", true.into(), activation)?;
+            hit_area_object.call_method("beginFill", &[0xFF0000.into()], activation)?;
+            hit_area_object.call_method("moveTo", &[100.into(), 100.into()], activation)?;
+            for (x, y) in &[(200, 100), (200, 200), (100, 200), (100, 100)] {
+                hit_area_object.call_method("lineTo", &[(*x).into(), (*y).into()], activation)?;
+            }
+            hit_area_object.call_method("endFill", &[], activation)?;
+
+            let inside = (Twips::from_pixels(150.0), Twips::from_pixels(150.0));
+            let outside = (Twips::from_pixels(50.0), Twips::from_pixels(50.0));
+            assert!(owner
+                .mouse_pick(&mut activation.context, owner, inside)
+                .is_none());
+
+            owner_object.set("hitArea", hit_area_object.into(), activation)?;
+            assert_eq!(
+                owner_object.get("hitArea", activation)?,
+                Value::Object(hit_area_object)
+            );
+            assert!(DisplayObject::ptr_eq(
+                owner
+                    .mouse_pick(&mut activation.context, owner, inside)
+                    .unwrap(),
+                owner
+            ));
+            assert!(owner
+                .mouse_pick(&mut activation.context, owner, outside)
+                .is_none());
+
+            // The hit area no longer responds to the mouse itself.
+            let hit_area: DisplayObject<'_> = hit_area.into();
+            assert!(hit_area
+                .mouse_pick(&mut activation.context, hit_area, inside)
+                .is_none());
+
+            Ok(())
+        });
+    }
+}
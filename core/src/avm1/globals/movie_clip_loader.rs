@@ -55,6 +55,7 @@ pub fn load_clip<'gc>(
                 fetch,
                 url.to_string(),
                 Some(this),
+                None,
             );
 
             activation.context.navigator.spawn_future(process);
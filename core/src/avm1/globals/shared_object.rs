@@ -152,36 +152,29 @@ fn deserialize_array<'gc>(
     }
 }
 
-pub fn get_local<'gc>(
-    activation: &mut Activation<'_, 'gc, '_>,
-    _this: Object<'gc>,
-    args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error<'gc>> {
-    let name = args
-        .get(0)
-        .unwrap_or(&Value::Undefined)
-        .coerce_to_string(activation)?
-        .to_string();
-
+/// Get the name that a local shared object is stored under, or `None` if the
+/// movie at `movie_url` isn't allowed to open it.
+///
+/// This is shared with AVM2's `SharedObject.getLocal`, so that both kinds of
+/// movie agree on where a shared object lives.
+pub fn full_name(
+    movie_url: Option<&str>,
+    name: &str,
+    local_path: Option<&str>,
+    secure: bool,
+) -> Option<String> {
     const INVALID_CHARS: &str = "~%&\\;:\"',<>?# ";
     if name.contains(|c| INVALID_CHARS.contains(c)) {
         log::error!("SharedObject::get_local: Invalid character in name");
-        return Ok(Value::Null);
+        return None;
     }
 
-    let movie = if let Some(movie) = activation.base_clip().movie() {
-        movie
-    } else {
-        log::error!("SharedObject::get_local: Movie was None");
-        return Ok(Value::Null);
-    };
-
-    let mut movie_url = if let Some(url) = movie.url() {
+    let mut movie_url = if let Some(url) = movie_url {
         if let Ok(url) = url::Url::parse(url) {
             url
         } else {
             log::error!("SharedObject::get_local: Unable to parse movie URL");
-            return Ok(Value::Null);
+            return None;
         }
     } else {
         // No URL (loading local data). Use a dummy URL to allow SharedObjects to work.
@@ -190,17 +183,12 @@ pub fn get_local<'gc>(
     movie_url.set_query(None);
     movie_url.set_fragment(None);
 
-    let secure = args
-        .get(2)
-        .unwrap_or(&Value::Undefined)
-        .as_bool(activation.current_swf_version());
-
     // Secure parameter disallows using the shared object from non-HTTPS.
     if secure && movie_url.scheme() != "https" {
         log::warn!(
             "SharedObject.get_local: Tried to load a secure shared object from non-HTTPS origin"
         );
-        return Ok(Value::Null);
+        return None;
     }
 
     // Shared objects are sandboxed per-domain.
@@ -220,14 +208,14 @@ pub fn get_local<'gc>(
         movie_url.host_str().unwrap_or_default()
     };
 
-    let local_path = if let Some(Value::String(local_path)) = args.get(1) {
+    let local_path = if let Some(local_path) = local_path {
         // Empty local path always fails.
         if local_path.is_empty() {
-            return Ok(Value::Null);
+            return None;
         }
 
         // Remove leading/trailing slashes.
-        let mut local_path = local_path.as_str().strip_prefix("/").unwrap_or(local_path);
+        let mut local_path = local_path.strip_prefix("/").unwrap_or(local_path);
         local_path = local_path.strip_suffix("/").unwrap_or(local_path);
 
         // Verify that local_path is a prefix of the SWF path.
@@ -239,14 +227,48 @@ pub fn get_local<'gc>(
             local_path
         } else {
             log::warn!("SharedObject.get_local: localPath parameter does not match SWF path");
-            return Ok(Value::Null);
+            return None;
         }
     } else {
         movie_path
     };
 
     // Final SO path: foo.com/folder/game.swf/SOName
-    let full_name = format!("{}/{}/{}", movie_host, local_path, name);
+    Some(format!("{}/{}/{}", movie_host, local_path, name))
+}
+
+pub fn get_local<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?
+        .to_string();
+
+    let movie = if let Some(movie) = activation.base_clip().movie() {
+        movie
+    } else {
+        log::error!("SharedObject::get_local: Movie was None");
+        return Ok(Value::Null);
+    };
+
+    let secure = args
+        .get(2)
+        .unwrap_or(&Value::Undefined)
+        .as_bool(activation.current_swf_version());
+    let local_path = match args.get(1) {
+        Some(Value::String(local_path)) => Some(local_path.as_str()),
+        _ => None,
+    };
+
+    let full_name = if let Some(full_name) = full_name(movie.url(), &name, local_path, secure) {
+        full_name
+    } else {
+        return Ok(Value::Null);
+    };
 
     // Check if this is referencing an existing shared object
     if let Some(so) = activation.context.shared_objects.get(&full_name) {
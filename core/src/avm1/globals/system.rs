@@ -3,14 +3,24 @@ use crate::avm1::error::Error;
 use crate::avm1::function::{Executable, FunctionObject};
 use crate::avm1::object::Object;
 use crate::avm1::property::Attribute;
-use crate::avm1::{Avm1, ScriptObject, TObject, Value};
+use crate::avm1::{ScriptObject, TObject, Value};
 use crate::avm_warn;
 use bitflags::bitflags;
 use core::fmt;
+use encoding_rs::{UTF_8, WINDOWS_1252};
 use gc_arena::MutationContext;
 use num_enum::TryFromPrimitive;
 use std::convert::TryFrom;
 
+/// Characters that are percent-encoded in `serverString` values.
+///
+/// Flash Player leaves the dots and dashes in values like `AR=1.0` and
+/// `L=zh-CN` alone.
+const SERVER_STRING_ENCODE_SET: &percent_encoding::AsciiSet = &percent_encoding::NON_ALPHANUMERIC
+    .remove(b'.')
+    .remove(b'-')
+    .remove(b'_');
+
 /// Available cpu architectures
 pub enum CpuArchitecture {
     PowerPc,
@@ -275,11 +285,11 @@ pub struct SystemProperties {
 }
 
 impl SystemProperties {
-    pub fn get_version_string(&self, avm: &mut Avm1) -> String {
+    pub fn get_version_string(&self, player_version: u8) -> String {
         format!(
             "{} {},0,0,0",
             self.manufacturer.get_platform_name(),
-            avm.player_version
+            player_version
         )
     }
 
@@ -287,6 +297,21 @@ impl SystemProperties {
         self.capabilities.contains(cap)
     }
 
+    /// Decode a text file loaded from outside the movie.
+    ///
+    /// Text files are Unicode unless `useCodePage` is set, in which case they
+    /// use the system code page (always WINDOWS-1252 for now). A byte order
+    /// mark overrides either.
+    pub fn decode_text(&self, data: &[u8]) -> String {
+        let encoding = if self.use_codepage {
+            WINDOWS_1252
+        } else {
+            UTF_8
+        };
+
+        encoding.decode(data).0.into_owned()
+    }
+
     fn encode_capability(&self, cap: SystemCapabilities) -> &str {
         if self.has_capability(cap) {
             "t"
@@ -303,76 +328,103 @@ impl SystemProperties {
         }
     }
 
-    fn encode_string(&self, s: &str) -> String {
-        percent_encoding::utf8_percent_encode(s, percent_encoding::NON_ALPHANUMERIC).to_string()
-    }
+    /// Build the URL-encoded `serverString`, with keys in the order Flash
+    /// Player lists them.
+    pub fn get_server_string(&self, player_version: u8) -> String {
+        let mut server_string = String::new();
+        let mut append = |key: &str, value: &str| {
+            if !server_string.is_empty() {
+                server_string.push('&');
+            }
+            server_string.push_str(key);
+            server_string.push('=');
+            server_string.extend(percent_encoding::utf8_percent_encode(
+                value,
+                SERVER_STRING_ENCODE_SET,
+            ));
+        };
 
-    pub fn get_server_string(&self, avm: &mut Avm1) -> String {
-        url::form_urlencoded::Serializer::new(String::new())
-            .append_pair("A", self.encode_capability(SystemCapabilities::AUDIO))
-            .append_pair(
-                "SA",
-                self.encode_capability(SystemCapabilities::STREAMING_AUDIO),
-            )
-            .append_pair(
-                "SV",
-                self.encode_capability(SystemCapabilities::STREAMING_VIDEO),
-            )
-            .append_pair(
-                "EV",
-                self.encode_capability(SystemCapabilities::EMBEDDED_VIDEO),
-            )
-            .append_pair("MP3", self.encode_capability(SystemCapabilities::MP3))
-            .append_pair(
-                "AE",
-                self.encode_capability(SystemCapabilities::AUDIO_ENCODER),
-            )
-            .append_pair(
-                "VE",
-                self.encode_capability(SystemCapabilities::VIDEO_ENCODER),
-            )
-            .append_pair(
-                "ACC",
-                self.encode_not_capability(SystemCapabilities::ACCESSIBILITY),
-            )
-            .append_pair("PR", self.encode_capability(SystemCapabilities::PRINTING))
-            .append_pair(
-                "SP",
-                self.encode_capability(SystemCapabilities::SCREEN_PLAYBACK),
-            )
-            .append_pair(
-                "SB",
-                self.encode_capability(SystemCapabilities::SCREEN_BROADCAST),
-            )
-            .append_pair("DEB", self.encode_capability(SystemCapabilities::DEBUGGER))
-            .append_pair(
-                "M",
-                &self.encode_string(
-                    self.manufacturer
-                        .get_manufacturer_string(avm.player_version)
-                        .as_str(),
-                ),
-            )
-            .append_pair(
-                "R",
-                &format!("{}x{}", self.screen_resolution.0, self.screen_resolution.1),
-            )
-            .append_pair("COL", &self.screen_color.to_string())
-            .append_pair("AR", &self.aspect_ratio.to_string())
-            .append_pair("OS", &self.encode_string(&self.os.to_string()))
-            .append_pair("L", self.language.get_language_code(avm.player_version))
-            .append_pair("IME", self.encode_capability(SystemCapabilities::IME))
-            .append_pair("PT", &self.player_type.to_string())
-            .append_pair(
-                "AVD",
-                self.encode_not_capability(SystemCapabilities::AV_HARDWARE),
-            )
-            .append_pair(
-                "LFD",
-                self.encode_not_capability(SystemCapabilities::LOCAL_FILE_READ),
-            )
-            .append_pair("DP", &self.dpi.to_string())
-            .finish()
+        append("A", self.encode_capability(SystemCapabilities::AUDIO));
+        append(
+            "SA",
+            self.encode_capability(SystemCapabilities::STREAMING_AUDIO),
+        );
+        append(
+            "SV",
+            self.encode_capability(SystemCapabilities::STREAMING_VIDEO),
+        );
+        append(
+            "EV",
+            self.encode_capability(SystemCapabilities::EMBEDDED_VIDEO),
+        );
+        append("MP3", self.encode_capability(SystemCapabilities::MP3));
+        append(
+            "AE",
+            self.encode_capability(SystemCapabilities::AUDIO_ENCODER),
+        );
+        append(
+            "VE",
+            self.encode_capability(SystemCapabilities::VIDEO_ENCODER),
+        );
+        append(
+            "ACC",
+            self.encode_capability(SystemCapabilities::ACCESSIBILITY),
+        );
+        append("PR", self.encode_capability(SystemCapabilities::PRINTING));
+        append(
+            "SP",
+            self.encode_capability(SystemCapabilities::SCREEN_PLAYBACK),
+        );
+        append(
+            "SB",
+            self.encode_capability(SystemCapabilities::SCREEN_BROADCAST),
+        );
+        append("DEB", self.encode_capability(SystemCapabilities::DEBUGGER));
+        append("V", &self.get_version_string(player_version));
+        append(
+            "M",
+            &self.manufacturer.get_manufacturer_string(player_version),
+        );
+        append(
+            "R",
+            &format!("{}x{}", self.screen_resolution.0, self.screen_resolution.1),
+        );
+        append("COL", &self.screen_color.to_string());
+        if self.aspect_ratio.fract() == 0.0 {
+            append("AR", &format!("{:.1}", self.aspect_ratio));
+        } else {
+            append("AR", &self.aspect_ratio.to_string());
+        }
+        append("OS", &self.os.to_string());
+        append("ARCH", &self.cpu_architecture.to_string());
+        append("L", self.language.get_language_code(player_version));
+        append("IME", self.encode_capability(SystemCapabilities::IME));
+        append(
+            "PR32",
+            self.encode_capability(SystemCapabilities::PROCESS_32_BIT),
+        );
+        append(
+            "PR64",
+            self.encode_capability(SystemCapabilities::PROCESS_64_BIT),
+        );
+        append("PT", &self.player_type.to_string());
+        append(
+            "AVD",
+            self.encode_not_capability(SystemCapabilities::AV_HARDWARE),
+        );
+        append(
+            "LFD",
+            self.encode_not_capability(SystemCapabilities::LOCAL_FILE_READ),
+        );
+        append(
+            "WD",
+            self.encode_not_capability(SystemCapabilities::WINDOW_LESS),
+        );
+        append("TLS", self.encode_capability(SystemCapabilities::TLS));
+        append("ML", &self.idc_level);
+        append("DP", &self.dpi.to_string());
+
+        server_string
     }
 }
 
@@ -383,7 +435,16 @@ impl Default for SystemProperties {
             exact_settings: true,
             //TODO: default to false on fp>=7, true <= 6
             use_codepage: false,
-            capabilities: SystemCapabilities::empty(),
+            // MP3 support depends on the audio backend, and is set by the player.
+            capabilities: SystemCapabilities::AUDIO
+                | SystemCapabilities::STREAMING_AUDIO
+                | SystemCapabilities::TLS
+                | SystemCapabilities::PROCESS_32_BIT
+                | if cfg!(target_pointer_width = "64") {
+                    SystemCapabilities::PROCESS_64_BIT
+                } else {
+                    SystemCapabilities::empty()
+                },
             player_type: PlayerType::StandAlone,
             screen_color: ScreenColor::Color,
             // TODO: note for fp <7 this should be the locale and the ui lang for >= 7, on windows
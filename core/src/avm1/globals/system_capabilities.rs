@@ -187,7 +187,7 @@ pub fn get_version<'gc>(
         activation
             .context
             .system
-            .get_version_string(activation.context.avm1),
+            .get_version_string(activation.context.player_version),
     )
     .into())
 }
@@ -200,7 +200,7 @@ pub fn get_server_string<'gc>(
     let server_string = activation
         .context
         .system
-        .get_server_string(activation.context.avm1);
+        .get_server_string(activation.context.player_version);
     Ok(AvmString::new(activation.context.gc_context, server_string).into())
 }
 
@@ -1,4 +1,5 @@
-use gc_arena::{Collect, Gc, MutationContext};
+use gc_arena::{Collect, Gc, GcCell, MutationContext};
+use once_cell::unsync::OnceCell;
 use std::cmp::{Eq, Ord, Ordering, PartialOrd};
 use std::fmt;
 use std::hash::{Hash, Hasher};
@@ -9,6 +10,87 @@ use std::ops::Deref;
 enum Source<'gc> {
     Owned(Gc<'gc, String>),
     Static(&'static str),
+    Concat(Gc<'gc, Concat<'gc>>),
+}
+
+impl Source<'_> {
+    fn as_str(&self) -> &str {
+        match self {
+            Source::Owned(str) => str.deref(),
+            Source::Static(str) => str,
+            Source::Concat(concat) => concat.as_str(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Source::Owned(str) => str.len(),
+            Source::Static(str) => str.len(),
+            Source::Concat(concat) => concat.len,
+        }
+    }
+}
+
+/// A string made by appending one string to another.
+///
+/// The halves are only copied into one buffer when the string is first read,
+/// so a loop of `s += piece` copies each piece once rather than the whole
+/// string on every pass.
+#[derive(Collect)]
+#[collect(no_drop)]
+struct Concat<'gc> {
+    /// The strings this was made from, until it has been read and something
+    /// else is built on it.
+    parts: GcCell<'gc, Option<(AvmString<'gc>, AvmString<'gc>)>>,
+
+    /// The length of the string in bytes.
+    len: usize,
+
+    /// The text of the string, once it has been read.
+    #[collect(require_static)]
+    flat: OnceCell<String>,
+}
+
+impl<'gc> Concat<'gc> {
+    fn as_str(&self) -> &str {
+        self.flat.get_or_init(|| {
+            let mut flat = String::with_capacity(self.len);
+
+            // Long chains of appends are walked without recursing, so they
+            // can't overflow the stack.
+            let mut pending = vec![];
+            self.push_halves(&mut pending);
+            while let Some(string) = pending.pop() {
+                match string.source {
+                    Source::Concat(concat) if concat.flat.get().is_none() => {
+                        concat.push_halves(&mut pending)
+                    }
+                    source => flat.push_str(source.as_str()),
+                }
+            }
+
+            flat
+        })
+    }
+
+    /// Push the halves of this string onto a stack of strings to copy, if it
+    /// still has them.
+    fn push_halves(&self, pending: &mut Vec<AvmString<'gc>>) {
+        if let Some((left, right)) = *self.parts.read() {
+            pending.push(right);
+            pending.push(left);
+        }
+    }
+
+    /// Let go of the halves of this string if it no longer needs them.
+    ///
+    /// Otherwise a loop that reads its string as it appends to it would keep
+    /// every earlier string alive.
+    fn release_halves(&self, gc_context: MutationContext<'gc, '_>) {
+        if self.flat.get().is_some() && self.parts.read().is_some() {
+            *self.parts.write(gc_context) = None;
+        }
+    }
 }
 
 impl fmt::Debug for Source<'_> {
@@ -16,6 +98,7 @@ impl fmt::Debug for Source<'_> {
         match self {
             Source::Owned(str) => f.debug_tuple("Owned").field(str.deref()).finish(),
             Source::Static(str) => f.debug_tuple("Static").field(str).finish(),
+            Source::Concat(concat) => f.debug_tuple("Concat").field(&concat.as_str()).finish(),
         }
     }
 }
@@ -33,6 +116,50 @@ impl<'gc> AvmString<'gc> {
         }
     }
 
+    /// Append `right` to `left`.
+    ///
+    /// Neither string is copied until the result is read.
+    pub fn concat(
+        gc_context: MutationContext<'gc, '_>,
+        left: AvmString<'gc>,
+        right: AvmString<'gc>,
+    ) -> Self {
+        if right.is_empty() {
+            return left;
+        }
+
+        if left.is_empty() {
+            return right;
+        }
+
+        for string in &[left, right] {
+            if let Source::Concat(concat) = string.source {
+                concat.release_halves(gc_context);
+            }
+        }
+
+        let concat = Concat {
+            parts: GcCell::allocate(gc_context, Some((left, right))),
+            len: left.len() + right.len(),
+            flat: OnceCell::new(),
+        };
+
+        Self {
+            source: Source::Concat(Gc::allocate(gc_context, concat)),
+        }
+    }
+
+    /// The length of the string in bytes.
+    ///
+    /// Unlike `str::len`, this doesn't need to flatten a concatenation.
+    pub fn len(&self) -> usize {
+        self.source.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
     pub fn as_str(&self) -> &str {
         self
     }
@@ -65,20 +192,14 @@ impl Deref for AvmString<'_> {
 
     #[inline]
     fn deref(&self) -> &str {
-        match &self.source {
-            Source::Owned(str) => str.deref(),
-            Source::Static(str) => str,
-        }
+        self.source.as_str()
     }
 }
 
 impl AsRef<str> for AvmString<'_> {
     #[inline]
     fn as_ref(&self) -> &str {
-        match &self.source {
-            Source::Owned(str) => str,
-            Source::Static(str) => str,
-        }
+        self
     }
 }
 
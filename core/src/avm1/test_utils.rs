@@ -9,8 +9,9 @@ use crate::backend::log::NullLogBackend;
 use crate::backend::navigator::NullNavigatorBackend;
 use crate::backend::render::NullRenderer;
 use crate::backend::storage::MemoryStorageBackend;
-use crate::backend::ui::NullUiBackend;
+use crate::backend::ui::{MouseCursor, NullUiBackend};
 use crate::backend::video::NullVideoBackend;
+use crate::config::StageScaleMode;
 use crate::context::ActionQueue;
 use crate::display_object::{MovieClip, TDisplayObject};
 use crate::focus_tracker::FocusTracker;
@@ -63,8 +64,14 @@ where
             video: &mut NullVideoBackend::new(),
             mouse_hovered_object: None,
             mouse_position: &(Twips::new(0), Twips::new(0)),
+            mouse_cursor: &mut MouseCursor::Arrow,
+            forced_mouse_cursor: &mut None,
             drag_object: &mut None,
+            current_context_menu: &mut None,
             stage_size: (Twips::from_pixels(550.0), Twips::from_pixels(400.0)),
+            viewport_size: (550, 400),
+            scale_mode: &mut StageScaleMode::default(),
+            contents_scale_factor: 1.0,
             player: None,
             load_manager: &mut LoadManager::new(),
             system: &mut SystemProperties::default(),
@@ -4,10 +4,13 @@
 //! The timers are stored in a priority queue, where we check if the nearest timer
 //! is ready to tick each frame.
 //!
-//! TODO: Could we use this for AVM2 timers as well?
+//! AVM2's `flash.utils.setInterval` and `setTimeout` share these timers.
 
 use crate::avm1::object::search_prototype;
 use crate::avm1::{Activation, ActivationIdentifier, Object, TObject, Value};
+use crate::avm2::{
+    Activation as Avm2Activation, Object as Avm2Object, TObject as _, Value as Avm2Value,
+};
 use crate::context::UpdateContext;
 use gc_arena::Collect;
 use std::collections::{binary_heap::PeekMut, BinaryHeap};
@@ -101,6 +104,15 @@ impl<'gc> Timers<'gc> {
                         None
                     }
                 }
+                TimerCallback::Avm2Callback { closure, params } => {
+                    let mut avm2_activation =
+                        Avm2Activation::from_nothing(activation.context.reborrow());
+                    if let Err(e) = closure.call(None, &params, &mut avm2_activation, None) {
+                        log::error!("Unhandled AVM2 exception in timer callback: {}", e);
+                    }
+
+                    None
+                }
             };
 
             if let Some((this, base_proto, function)) = callback {
@@ -230,7 +242,7 @@ struct Timer<'gc> {
     /// A callback is either a function object, or a parent object with a method name.
     callback: TimerCallback<'gc>,
 
-    /// The parameters to pass to the callback function.
+    /// The parameters to pass to an AVM1 callback function.
     params: Vec<Value<'gc>>,
 
     /// The time when this timer should fire.
@@ -278,4 +290,14 @@ pub enum TimerCallback<'gc> {
         this: Object<'gc>,
         method_name: String,
     },
+
+    /// An AVM2 closure, called with the arguments that were given to
+    /// `setInterval`/`setTimeout` after the delay.
+    ///
+    /// These are the same values each time, so objects among them are
+    /// shared with the caller rather than copied.
+    Avm2Callback {
+        closure: Avm2Object<'gc>,
+        params: Vec<Avm2Value<'gc>>,
+    },
 }
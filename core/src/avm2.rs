@@ -2,12 +2,16 @@
 
 use crate::avm2::globals::SystemPrototypes;
 use crate::avm2::method::Method;
+use crate::avm2::object::ScriptObject;
 use crate::avm2::script::{Script, TranslationUnit};
+use crate::backend::ui::StageOrientation;
 use crate::context::UpdateContext;
+use crate::display_object::{DisplayObject, TDisplayObject, TDisplayObjectContainer};
 use crate::tag_utils::SwfSlice;
 use gc_arena::{Collect, MutationContext};
 use std::rc::Rc;
 use swf::avm2::read::Reader;
+use swf::Twips;
 
 #[macro_export]
 macro_rules! avm_debug {
@@ -19,7 +23,9 @@ macro_rules! avm_debug {
 }
 
 mod activation;
+mod amf;
 mod array;
+mod bytearray;
 mod class;
 mod domain;
 mod events;
@@ -29,27 +35,69 @@ mod method;
 mod names;
 mod object;
 mod property;
+mod property_cache;
 mod property_map;
+mod remoting;
 mod return_value;
 mod scope;
 mod script;
 mod slot;
 mod string;
+#[cfg(test)]
+pub(crate) mod test_utils;
 mod traits;
 mod value;
 
 pub use crate::avm2::activation::Activation;
 pub use crate::avm2::domain::Domain;
+pub use crate::avm2::events::{Event, EventData};
 pub use crate::avm2::names::{Namespace, QName};
-pub use crate::avm2::object::{Object, StageObject, TObject};
+pub use crate::avm2::object::{EventObject, Object, StageObject, TObject};
 pub use crate::avm2::value::Value;
 
+#[cfg(test)]
+pub use crate::avm2::object::FunctionObject;
+
 /// Boxed error alias.
 ///
 /// As AVM2 is a far stricter VM than AVM1, this may eventually be replaced
 /// with a proper Avm2Error enum.
 pub type Error = Box<dyn std::error::Error>;
 
+/// How touches on the stage are reported to content, as chosen by
+/// `Multitouch.inputMode`.
+#[derive(Clone, Collect, Copy, Debug, PartialEq, Eq)]
+#[collect(require_static)]
+pub enum MultitouchInputMode {
+    /// Touches are only seen as the mouse.
+    None,
+
+    /// Touches are recognized as gestures.
+    Gesture,
+
+    /// Every touch point dispatches its own `TouchEvent`s.
+    TouchPoint,
+}
+
+impl MultitouchInputMode {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "none" => Some(Self::None),
+            "gesture" => Some(Self::Gesture),
+            "touchPoint" => Some(Self::TouchPoint),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Gesture => "gesture",
+            Self::TouchPoint => "touchPoint",
+        }
+    }
+}
+
 /// The state of an AVM2 interpreter.
 #[derive(Collect)]
 #[collect(no_drop)]
@@ -63,10 +111,101 @@ pub struct Avm2<'gc> {
     /// System prototypes.
     system_prototypes: Option<SystemPrototypes<'gc>>,
 
+    /// The `Stage` instance shared by every display object on the stage.
+    stage: Option<Object<'gc>>,
+
+    /// Arrays that are partway through being joined into a string.
+    ///
+    /// Flash Player stringifies an array that contains itself, directly or
+    /// through other arrays, by skipping over the cycle. This is how we spot
+    /// one.
+    join_stack: Vec<Object<'gc>>,
+
+    /// The `Worker` that the player's own movie runs in, once content has
+    /// asked for it.
+    primordial_worker: Option<Object<'gc>>,
+
+    /// The `Worker` whose code is currently running, if it is not the
+    /// primordial worker.
+    current_worker: Option<Object<'gc>>,
+
+    /// Every `Worker` that has been started and not yet terminated, along
+    /// with the root of its movie.
+    ///
+    /// Workers share the player's thread: each one runs a frame of its
+    /// movie after the stage runs its own.
+    workers: Vec<(Object<'gc>, DisplayObject<'gc>)>,
+
+    /// `MessageChannel`s that have been sent messages which have not been
+    /// announced to their receivers yet.
+    pending_channels: Vec<Object<'gc>>,
+
+    /// Whether the stage went full screen with keyboard input allowed, as
+    /// requested by setting `displayState` to `fullScreenInteractive`.
+    full_screen_interactive: bool,
+
+    /// Whether the player's window has focus, as last told by the host.
+    has_focus: bool,
+
+    /// How touches are reported, as set by `Multitouch.inputMode`.
+    multitouch_input_mode: MultitouchInputMode,
+
+    /// The touch point that began while no others were down, if it is still
+    /// down.
+    primary_touch_point: Option<i32>,
+
+    /// Whether the player's own items are offered in the context menu, as
+    /// set by `Stage.showDefaultContextMenu`.
+    show_default_context_menu: bool,
+
+    /// Whether focused objects get a focus rectangle by default, as set by
+    /// `Stage.stageFocusRect`.
+    stage_focus_rect: bool,
+
     #[cfg(feature = "avm_debug")]
     pub debug_output: bool,
 }
 
+/// Find the top-most interactive object under `point`, in stage twips,
+/// within the interactive object `dobj`.
+///
+/// Objects that are not interactive, such as shapes, are never the target of
+/// a mouse event, so a hit on one goes to the interactive object holding it.
+fn interactive_object_under<'gc>(
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    dobj: DisplayObject<'gc>,
+    point: (Twips, Twips),
+) -> Option<DisplayObject<'gc>> {
+    fn is_interactive(dobj: DisplayObject<'_>) -> bool {
+        dobj.as_movie_clip().is_some()
+            || dobj.as_edit_text().is_some()
+            || dobj.as_button().is_some()
+    }
+
+    if !dobj.visible() {
+        return None;
+    }
+
+    if let Some(ctr) = dobj.as_container() {
+        let children: Vec<DisplayObject<'gc>> = ctr.iter_render_list().collect();
+        for child in children.into_iter().rev() {
+            if is_interactive(child) {
+                if let Some(hit) = interactive_object_under(context, child, point) {
+                    return Some(hit);
+                }
+            } else if child.visible() && child.hit_test_shape(context, point) {
+                return Some(dobj);
+            }
+        }
+    }
+
+    if dobj.hit_test_shape(context, point) {
+        Some(dobj)
+    } else {
+        None
+    }
+}
+
 impl<'gc> Avm2<'gc> {
     /// Construct a new AVM interpreter.
     pub fn new(mc: MutationContext<'gc, '_>) -> Self {
@@ -76,6 +215,18 @@ impl<'gc> Avm2<'gc> {
             stack: Vec::new(),
             globals,
             system_prototypes: None,
+            stage: None,
+            join_stack: Vec::new(),
+            primordial_worker: None,
+            current_worker: None,
+            workers: Vec::new(),
+            pending_channels: Vec::new(),
+            full_screen_interactive: false,
+            has_focus: true,
+            multitouch_input_mode: MultitouchInputMode::Gesture,
+            primary_touch_point: None,
+            show_default_context_menu: true,
+            stage_focus_rect: true,
 
             #[cfg(feature = "avm_debug")]
             debug_output: false,
@@ -95,6 +246,418 @@ impl<'gc> Avm2<'gc> {
         self.system_prototypes.as_ref().unwrap()
     }
 
+    /// Return the `Stage` instance, if player globals have been loaded.
+    pub fn stage(&self) -> Option<Object<'gc>> {
+        self.stage
+    }
+
+    /// Every `Worker` that is running, along with the root of its movie.
+    pub fn workers(&self) -> Vec<(Object<'gc>, DisplayObject<'gc>)> {
+        self.workers.clone()
+    }
+
+    /// Set the `Worker` whose code is running, yielding the previous one.
+    ///
+    /// `None` stands for the primordial worker.
+    pub fn set_current_worker(&mut self, worker: Option<Object<'gc>>) -> Option<Object<'gc>> {
+        std::mem::replace(&mut self.current_worker, worker)
+    }
+
+    /// Announce every message sent over a `MessageChannel` since the last
+    /// time this was called with a `channelMessage` event.
+    ///
+    /// Each event runs as the channel's receiving worker. Messages sent while
+    /// handling these events are announced the next time around.
+    pub fn deliver_channel_messages(context: &mut UpdateContext<'_, 'gc, '_>) {
+        let channels = std::mem::take(&mut context.avm2.pending_channels);
+
+        for mut channel in channels {
+            let mut activation = Activation::from_nothing(context.reborrow());
+            let receiver = match channel.get_property(
+                channel,
+                &QName::new(Namespace::Private("ruffle".into()), "receiver"),
+                &mut activation,
+            ) {
+                Ok(Value::Object(receiver)) => Some(receiver),
+                _ => None,
+            };
+
+            let previous = context.avm2.set_current_worker(receiver);
+            if let Err(e) = Self::dispatch_event(context, Event::new("channelMessage"), channel) {
+                log::error!("Unhandled AVM2 exception in channelMessage handler: {}", e);
+            }
+            context.avm2.set_current_worker(previous);
+        }
+    }
+
+    /// Dispatch an event on an object.
+    ///
+    /// The event object is constructed from the class matching the event's
+    /// data. Returns `true` if the event's default action was not cancelled.
+    pub fn dispatch_event(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        event: Event<'gc>,
+        target: Object<'gc>,
+    ) -> Result<bool, Error> {
+        let event_proto = match event.event_data() {
+            EventData::Empty => context.avm2.prototypes().event,
+            EventData::Text { .. } => context.avm2.prototypes().text_event,
+            EventData::Data { .. } => context.avm2.prototypes().data_event,
+            EventData::Error { .. } => context.avm2.prototypes().error_event,
+            EventData::NetStatus { .. } => context.avm2.prototypes().net_status_event,
+            EventData::Sync { .. } => context.avm2.prototypes().sync_event,
+            EventData::Progress { .. } => context.avm2.prototypes().progress_event,
+            EventData::HttpStatus { .. } => context.avm2.prototypes().http_status_event,
+            EventData::ContextMenu { .. } => context.avm2.prototypes().context_menu_event,
+            EventData::Mouse { .. } => context.avm2.prototypes().mouse_event,
+            EventData::FullScreen { .. } => context.avm2.prototypes().full_screen_event,
+            EventData::Touch { .. } => context.avm2.prototypes().touch_event,
+            EventData::Gesture { .. } => context.avm2.prototypes().gesture_event,
+            EventData::StageOrientation { .. } => context.avm2.prototypes().stage_orientation_event,
+        };
+        let event_object = EventObject::from_event(context.gc_context, Some(event_proto), event);
+        let mut activation = Activation::from_nothing(context.reborrow());
+
+        events::dispatch_event(&mut activation, target, event_object)
+    }
+
+    /// Dispatch a `netStatus` event on an object.
+    ///
+    /// The event's `info` object is populated with the given `code` and
+    /// `level`, as Flash Player does for its own status notifications.
+    pub fn dispatch_net_status(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        target: Object<'gc>,
+        code: &'static str,
+        level: &'static str,
+    ) -> Result<bool, Error> {
+        let mut activation = Activation::from_nothing(context.reborrow());
+        let mut info = ScriptObject::object(
+            activation.context.gc_context,
+            activation.avm2().prototypes().object,
+        );
+
+        info.set_property(
+            info,
+            &QName::new(Namespace::public(), "code"),
+            code.into(),
+            &mut activation,
+        )?;
+        info.set_property(
+            info,
+            &QName::new(Namespace::public(), "level"),
+            level.into(),
+            &mut activation,
+        )?;
+
+        let mut event = Event::new("netStatus");
+        event.set_event_data(EventData::NetStatus { info });
+
+        Self::dispatch_event(&mut activation.context, event, target)
+    }
+
+    /// Hand the reply to a `NetConnection.call` to its `Responder`.
+    ///
+    /// A successful call is answered with `onResult` and a fault with
+    /// `onStatus`, each of which runs the matching function given to the
+    /// responder. Replies that can't be read are announced on the connection
+    /// with a `NetConnection.Call.BadVersion` status instead.
+    pub fn dispatch_remoting_response(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        connection: Object<'gc>,
+        mut responder: Object<'gc>,
+        response: &str,
+        data: &[u8],
+    ) -> Result<(), Error> {
+        let mut activation = Activation::from_nothing(context.reborrow());
+        let messages = match remoting::read_response(&mut activation, data) {
+            Ok(messages) => messages,
+            Err(e) => {
+                log::warn!("Could not read remoting response: {}", e);
+                Self::dispatch_net_status(
+                    &mut activation.context,
+                    connection,
+                    "NetConnection.Call.BadVersion",
+                    "error",
+                )?;
+
+                return Ok(());
+            }
+        };
+
+        for (target, value) in messages {
+            let handler = match target.strip_prefix(response) {
+                Some("/onResult") => "result",
+                Some("/onStatus") => "status",
+                _ => continue,
+            };
+
+            if let Value::Object(handler) = responder.get_property(
+                responder,
+                &QName::new(Namespace::Private("ruffle".into()), handler),
+                &mut activation,
+            )? {
+                handler.call(None, &[value], &mut activation, None)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Dispatch a `fullScreen` event on the stage, announcing that it has
+    /// entered or left full screen.
+    pub fn dispatch_full_screen(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        full_screen: bool,
+        interactive: bool,
+    ) -> Result<(), Error> {
+        context.avm2.full_screen_interactive = full_screen && interactive;
+
+        if let Some(stage) = context.avm2.stage {
+            let mut event = Event::new("fullScreen");
+            event.set_event_data(EventData::FullScreen {
+                full_screen,
+                interactive: full_screen && interactive,
+            });
+
+            Self::dispatch_event(context, event, stage)?;
+        }
+
+        Ok(())
+    }
+
+    /// Dispatch an `orientationChange` event on the stage, announcing that it
+    /// has turned from one orientation to another.
+    pub fn dispatch_orientation_change(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        before: StageOrientation,
+        after: StageOrientation,
+    ) -> Result<(), Error> {
+        if let Some(stage) = context.avm2.stage {
+            let mut event = Event::new("orientationChange");
+            event.set_event_data(EventData::StageOrientation {
+                before_orientation: Some(before.as_str().into()),
+                after_orientation: Some(after.as_str().into()),
+            });
+
+            Self::dispatch_event(context, event, stage)?;
+        }
+
+        Ok(())
+    }
+
+    /// Find the object that a mouse or touch event at `position`, in stage
+    /// pixels, is dispatched to, along with `position` in that object's own
+    /// coordinates.
+    ///
+    /// That is the top-most interactive object under `position`, or the
+    /// stage if there is none.
+    fn pointer_target(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        position: (f64, f64),
+    ) -> Option<(Object<'gc>, (f64, f64))> {
+        let point = (
+            Twips::from_pixels(position.0),
+            Twips::from_pixels(position.1),
+        );
+        let levels: Vec<DisplayObject<'gc>> = context.levels.values().copied().collect();
+
+        for level in levels.into_iter().rev() {
+            if let Some(hit) = interactive_object_under(context, level, point) {
+                if let Value::Object(object) = hit.object2() {
+                    let local = hit.global_to_local(point);
+                    return Some((object, (local.0.to_pixels(), local.1.to_pixels())));
+                }
+            }
+        }
+
+        context.avm2.stage.map(|stage| (stage, position))
+    }
+
+    /// Dispatch a `mouseMove` event on the interactive object under the
+    /// mouse, from where it bubbles up to the stage.
+    ///
+    /// `position` is in stage pixels, and `movement` is how far the mouse
+    /// moved since the last `mouseMove` while it was locked.
+    pub fn dispatch_mouse_move(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        position: (f64, f64),
+        movement: (f64, f64),
+    ) -> Result<(), Error> {
+        if let Some((target, local)) = Self::pointer_target(context, position) {
+            let mut event = Event::new("mouseMove");
+            event.set_bubbles(true);
+            event.set_event_data(EventData::Mouse {
+                local_x: local.0,
+                local_y: local.1,
+                movement_x: movement.0,
+                movement_y: movement.1,
+            });
+
+            Self::dispatch_event(context, event, target)?;
+        }
+
+        Ok(())
+    }
+
+    /// Dispatch a frame event, such as `enterFrame`, to the stage and then to
+    /// every display object on it.
+    ///
+    /// Objects that are off the display list get nothing until they are added
+    /// back. Listeners may rearrange the display list, so every object is
+    /// checked again just before it is dispatched to: one removed by an
+    /// earlier listener misses the rest of this event.
+    pub fn dispatch_frame_event(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        event_type: &'static str,
+    ) -> Result<(), Error> {
+        fn collect<'gc>(dobj: DisplayObject<'gc>, targets: &mut Vec<DisplayObject<'gc>>) {
+            targets.push(dobj);
+
+            if let Some(ctr) = dobj.as_container() {
+                for child in ctr.iter_render_list() {
+                    collect(child, targets);
+                }
+            }
+        }
+
+        if let Some(stage) = context.avm2.stage {
+            Self::dispatch_event(context, Event::new(event_type), stage)?;
+        }
+
+        let mut targets = Vec::new();
+        for level in context.levels.values() {
+            collect(*level, &mut targets);
+        }
+
+        for target in targets {
+            if !target.is_on_stage(context) {
+                continue;
+            }
+
+            if let Value::Object(object) = target.object2() {
+                Self::dispatch_event(context, Event::new(event_type), object)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Dispatch `resize` on the stage after the viewport changed size.
+    pub fn dispatch_resize(context: &mut UpdateContext<'_, 'gc, '_>) -> Result<(), Error> {
+        if let Some(stage) = context.avm2.stage {
+            Self::dispatch_event(context, Event::new("resize"), stage)?;
+        }
+
+        Ok(())
+    }
+
+    /// Dispatch `activate` or `deactivate` on the stage as the player's window
+    /// gains or loses focus.
+    ///
+    /// Hosts may report the same state more than once, so nothing is
+    /// dispatched unless the focus actually changed. This keeps every
+    /// `deactivate` paired with an `activate`.
+    pub fn dispatch_focus(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        has_focus: bool,
+    ) -> Result<(), Error> {
+        if context.avm2.has_focus == has_focus {
+            return Ok(());
+        }
+
+        context.avm2.has_focus = has_focus;
+
+        if let Some(stage) = context.avm2.stage {
+            let event = if has_focus {
+                Event::new("activate")
+            } else {
+                Event::new("deactivate")
+            };
+
+            Self::dispatch_event(context, event, stage)?;
+        }
+
+        Ok(())
+    }
+
+    /// Dispatch a touch event, such as `touchBegin`, on the interactive
+    /// object under the touch point, from where it bubbles up to the stage.
+    ///
+    /// `position` is in stage pixels. Nothing is dispatched unless content
+    /// asked for touch points with `Multitouch.inputMode`, but the primary
+    /// touch point is tracked regardless.
+    pub fn dispatch_touch(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        event_type: &'static str,
+        touch_point_id: i32,
+        position: (f64, f64),
+        pressure: f64,
+    ) -> Result<(), Error> {
+        if event_type == "touchBegin" && context.avm2.primary_touch_point.is_none() {
+            context.avm2.primary_touch_point = Some(touch_point_id);
+        }
+        let is_primary_touch_point = context.avm2.primary_touch_point == Some(touch_point_id);
+        if event_type == "touchEnd" && is_primary_touch_point {
+            context.avm2.primary_touch_point = None;
+        }
+
+        if context.avm2.multitouch_input_mode != MultitouchInputMode::TouchPoint {
+            return Ok(());
+        }
+
+        if let Some((target, local)) = Self::pointer_target(context, position) {
+            let mut event = Event::new(event_type);
+            event.set_bubbles(true);
+            event.set_event_data(EventData::Touch {
+                touch_point_id,
+                is_primary_touch_point,
+                local_x: local.0,
+                local_y: local.1,
+                stage_x: position.0,
+                stage_y: position.1,
+                pressure,
+            });
+
+            Self::dispatch_event(context, event, target)?;
+        }
+
+        Ok(())
+    }
+
+    /// How touches are reported to content.
+    pub fn multitouch_input_mode(&self) -> MultitouchInputMode {
+        self.multitouch_input_mode
+    }
+
+    pub fn set_multitouch_input_mode(&mut self, input_mode: MultitouchInputMode) {
+        self.multitouch_input_mode = input_mode;
+    }
+
+    /// Whether the stage is in full screen with keyboard input allowed.
+    pub fn is_full_screen_interactive(&self) -> bool {
+        self.full_screen_interactive
+    }
+
+    /// Whether the player's own items are offered in the context menu.
+    pub fn show_default_context_menu(&self) -> bool {
+        self.show_default_context_menu
+    }
+
+    pub fn set_show_default_context_menu(&mut self, show: bool) {
+        self.show_default_context_menu = show;
+    }
+
+    /// Whether focused objects that don't set `focusRect` get a focus
+    /// rectangle.
+    pub fn stage_focus_rect(&self) -> bool {
+        self.stage_focus_rect
+    }
+
+    pub fn set_stage_focus_rect(&mut self, value: bool) {
+        self.stage_focus_rect = value;
+    }
+
     /// Run a script's initializer method.
     pub fn run_script_initializer(
         script: Script<'gc>,
@@ -2,11 +2,15 @@
 
 use crate::avm2::array::ArrayStorage;
 use crate::avm2::class::Class;
+use crate::avm2::domain::MIN_DOMAIN_MEMORY_LENGTH;
 use crate::avm2::method::BytecodeMethod;
 use crate::avm2::method::Method;
 use crate::avm2::names::{Multiname, Namespace, QName};
-use crate::avm2::object::{ArrayObject, FunctionObject, NamespaceObject, ScriptObject};
+use crate::avm2::object::{
+    ArrayObject, ByteArrayObject, FunctionObject, NamespaceObject, ScriptObject,
+};
 use crate::avm2::object::{Object, TObject};
+use crate::avm2::property_cache::{CachedProperty, PropertyCache};
 use crate::avm2::scope::Scope;
 use crate::avm2::script::Script;
 use crate::avm2::string::AvmString;
@@ -15,6 +19,7 @@ use crate::avm2::{value, Avm2, Error};
 use crate::context::UpdateContext;
 use gc_arena::{Gc, GcCell, MutationContext};
 use smallvec::SmallVec;
+use std::convert::TryFrom;
 use std::io::Cursor;
 use swf::avm2::read::Reader;
 use swf::avm2::types::{
@@ -104,6 +109,12 @@ pub struct Activation<'a, 'gc: 'a, 'gc_context: 'a> {
     /// This will not be available if this is not a method call.
     base_proto: Option<Object<'gc>>,
 
+    /// How many values were on the operand stack when this activation began.
+    ///
+    /// Values below this belong to callers, and may not be touched by this
+    /// activation's code.
+    stack_base: usize,
+
     pub context: UpdateContext<'a, 'gc, 'gc_context>,
 }
 
@@ -128,6 +139,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             local_scope: ScriptObject::bare_object(context.gc_context),
             scope: None,
             base_proto: None,
+            stack_base: context.avm2.stack.len(),
             context,
         }
     }
@@ -167,6 +179,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             local_scope: ScriptObject::bare_object(context.gc_context),
             scope,
             base_proto: None,
+            stack_base: context.avm2.stack.len(),
             context,
         })
     }
@@ -198,11 +211,19 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             let mut write = local_registers.write(context.gc_context);
             *write.get_mut(0).unwrap() = this.map(|t| t.into()).unwrap_or(Value::Null);
 
-            for i in 0..num_declared_arguments {
-                *write.get_mut(1 + i).unwrap() = arguments
-                    .get(i as usize)
-                    .cloned()
-                    .unwrap_or(Value::Undefined);
+            for (i, param) in method.method().params.iter().enumerate() {
+                *write.get_mut(1 + i as u32).unwrap() = match arguments.get(i) {
+                    Some(arg) => arg.clone(),
+                    None => match &param.default_value {
+                        Some(default) => value::abc_default_value(
+                            method.translation_unit(),
+                            default,
+                            context.avm2,
+                            context.gc_context,
+                        )?,
+                        None => Value::Undefined,
+                    },
+                };
             }
         }
 
@@ -215,6 +236,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             local_scope: ScriptObject::bare_object(context.gc_context),
             scope,
             base_proto,
+            stack_base: context.avm2.stack.len(),
             context,
         };
 
@@ -288,6 +310,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             local_scope: ScriptObject::bare_object(context.gc_context),
             scope,
             base_proto,
+            stack_base: context.avm2.stack.len(),
             context,
         })
     }
@@ -478,6 +501,57 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Multiname::from_abc_multiname_static(method.translation_unit(), index, mc)
     }
 
+    /// Pop the receiver of a property access off the stack.
+    ///
+    /// Primitives are boxed. `null` and `undefined` have no properties to
+    /// access, and throw the same errors they do in Flash Player.
+    fn pop_receiver(&mut self) -> Result<Object<'gc>, Error> {
+        self.context.avm2.pop().coerce_to_object(self)
+    }
+
+    /// Retrieve what the property access at `offset` resolved to the last
+    /// time it ran, if it was on a receiver of the same class.
+    fn cached_property(
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        offset: u64,
+        receiver: Object<'gc>,
+    ) -> Option<CachedProperty<'gc>> {
+        let class = PropertyCache::receiver_class(receiver)?;
+
+        method.property_cache().read().get(offset, class).cloned()
+    }
+
+    /// Remember what the property access at `offset` resolved to.
+    ///
+    /// Only accesses with a static multiname may be cached, as a runtime
+    /// multiname can resolve differently every time.
+    fn cache_property(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        offset: u64,
+        receiver: Object<'gc>,
+        name: QName<'gc>,
+        base_proto: Option<Object<'gc>>,
+    ) {
+        if let Some(class) = PropertyCache::receiver_class(receiver) {
+            let slot_id = receiver.get_own_slot_id(&name);
+
+            method
+                .property_cache()
+                .write(self.context.gc_context)
+                .insert(
+                    offset,
+                    receiver,
+                    CachedProperty {
+                        class,
+                        name,
+                        slot_id,
+                        base_proto,
+                    },
+                );
+        }
+    }
+
     /// Retrieve a method entry from the current ABC file's method table.
     fn table_method(
         &mut self,
@@ -532,6 +606,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             );
         }
 
+        let op_start = reader.seek(0)?;
         let op = reader.read_op();
         if let Ok(Some(op)) = op {
             avm_debug!(self.avm2(), "Opcode: {:?}", op);
@@ -557,7 +632,8 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                 Op::Call { num_args } => self.op_call(num_args),
                 Op::CallMethod { index, num_args } => self.op_call_method(index, num_args),
                 Op::CallProperty { index, num_args } => {
-                    self.op_call_property(method, index, num_args)
+                    let offset = reader.seek(0)?;
+                    self.op_call_property(method, index, num_args, offset)
                 }
                 Op::CallPropLex { index, num_args } => {
                     self.op_call_prop_lex(method, index, num_args)
@@ -572,7 +648,10 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                 }
                 Op::ReturnValue => self.op_return_value(),
                 Op::ReturnVoid => self.op_return_void(),
-                Op::GetProperty { index } => self.op_get_property(method, index),
+                Op::GetProperty { index } => {
+                    let offset = reader.seek(0)?;
+                    self.op_get_property(method, index, offset)
+                }
                 Op::SetProperty { index } => self.op_set_property(method, index),
                 Op::InitProperty { index } => self.op_init_property(method, index),
                 Op::DeleteProperty { index } => self.op_delete_property(method, index),
@@ -636,6 +715,10 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                 Op::Swap => self.op_swap(),
                 Op::URShift => self.op_urshift(),
                 Op::Jump { offset } => self.op_jump(offset, reader),
+                Op::LookupSwitch {
+                    default_offset,
+                    case_offsets,
+                } => self.op_lookup_switch(op_start, default_offset, &case_offsets, reader),
                 Op::IfTrue { offset } => self.op_if_true(offset, reader),
                 Op::IfFalse { offset } => self.op_if_false(offset, reader),
                 Op::IfStrictEq { offset } => self.op_if_strict_eq(offset, reader),
@@ -667,6 +750,9 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                 Op::IsType { index } => self.op_is_type(method, index),
                 Op::IsTypeLate => self.op_is_type_late(),
                 Op::InstanceOf => self.op_instance_of(),
+                Op::CheckFilter => self.op_check_filter(),
+                Op::EscXElem => self.op_esc_xelem(),
+                Op::EscXAttr => self.op_esc_xattr(),
                 Op::Label => Ok(FrameControl::Continue),
                 Op::Debug {
                     is_local_register,
@@ -676,6 +762,16 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                 Op::DebugFile { file_name } => self.op_debug_file(method, file_name),
                 Op::DebugLine { line_num } => self.op_debug_line(line_num),
                 Op::TypeOf => self.op_type_of(),
+                Op::Li8 => self.op_li8(),
+                Op::Li16 => self.op_li16(),
+                Op::Li32 => self.op_li32(),
+                Op::Lf32 => self.op_lf32(),
+                Op::Lf64 => self.op_lf64(),
+                Op::Si8 => self.op_si8(),
+                Op::Si16 => self.op_si16(),
+                Op::Si32 => self.op_si32(),
+                Op::Sf32 => self.op_sf32(),
+                Op::Sf64 => self.op_sf64(),
                 _ => self.unknown_op(op),
             };
 
@@ -701,8 +797,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     }
 
     fn op_push_byte(&mut self, value: u8) -> Result<FrameControl<'gc>, Error> {
-        //TODO: Adobe Animate CC appears to generate signed byte values, and
-        //JPEXS appears to take them.
+        // The operand is a signed byte, so values from 128 up are negative.
         self.context.avm2.push(value as i8 as i32);
         Ok(FrameControl::Continue)
     }
@@ -790,21 +885,28 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
+    /// Ensure that this activation has at least `depth` values of its own on
+    /// the operand stack.
+    fn check_stack_depth(&self, depth: usize) -> Result<(), Error> {
+        if self.context.avm2.stack.len() < self.stack_base + depth {
+            return Err("VerifyError: Error #1024: Stack underflow occurred.".into());
+        }
+
+        Ok(())
+    }
+
     fn op_pop(&mut self) -> Result<FrameControl<'gc>, Error> {
+        self.check_stack_depth(1)?;
         self.context.avm2.pop();
 
         Ok(FrameControl::Continue)
     }
 
     fn op_dup(&mut self) -> Result<FrameControl<'gc>, Error> {
-        self.context.avm2.push(
-            self.context
-                .avm2
-                .stack
-                .last()
-                .cloned()
-                .unwrap_or(Value::Undefined),
-        );
+        self.check_stack_depth(1)?;
+        let value = self.context.avm2.stack.last().cloned().unwrap();
+
+        self.context.avm2.push(value);
 
         Ok(FrameControl::Continue)
     }
@@ -863,18 +965,42 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
         arg_count: u32,
+        offset: u64,
     ) -> Result<FrameControl<'gc>, Error> {
         let args = self.context.avm2.pop_args(arg_count);
-        let multiname = self.pool_multiname(method, index)?;
-        let mut receiver = self.context.avm2.pop().coerce_to_object(self)?;
+        let is_static =
+            Multiname::is_abc_multiname_static(method.translation_unit(), index.clone());
+        let (multiname, mut receiver) = if is_static {
+            let mut receiver = self.pop_receiver()?;
+            if let Some(cached) = Self::cached_property(method, offset, receiver) {
+                let function = receiver
+                    .get_property(receiver, &cached.name, self)?
+                    .coerce_to_object(self)?;
+                let value = function.call(Some(receiver), &args, self, cached.base_proto)?;
+
+                self.context.avm2.push(value);
+
+                return Ok(FrameControl::Continue);
+            }
+
+            (self.pool_multiname(method, index)?, receiver)
+        } else {
+            let multiname = self.pool_multiname(method, index)?;
+            (multiname, self.pop_receiver()?)
+        };
         let name: Result<QName, Error> = receiver
-            .resolve_multiname(&multiname)?
+            .resolve_method_multiname(&multiname)?
             .ok_or_else(|| format!("Could not find method {:?}", multiname.local_name()).into());
         let name = name?;
         let base_proto = receiver.get_base_proto(&name)?;
         let function = receiver
             .get_property(receiver, &name, self)?
             .coerce_to_object(self)?;
+
+        if is_static {
+            self.cache_property(method, offset, receiver, name, base_proto);
+        }
+
         let value = function.call(Some(receiver), &args, self, base_proto)?;
 
         self.context.avm2.push(value);
@@ -890,9 +1016,9 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     ) -> Result<FrameControl<'gc>, Error> {
         let args = self.context.avm2.pop_args(arg_count);
         let multiname = self.pool_multiname(method, index)?;
-        let mut receiver = self.context.avm2.pop().coerce_to_object(self)?;
+        let mut receiver = self.pop_receiver()?;
         let name: Result<QName, Error> = receiver
-            .resolve_multiname(&multiname)?
+            .resolve_method_multiname(&multiname)?
             .ok_or_else(|| format!("Could not find method {:?}", multiname.local_name()).into());
         let function = receiver
             .get_property(receiver, &name?, self)?
@@ -912,9 +1038,9 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     ) -> Result<FrameControl<'gc>, Error> {
         let args = self.context.avm2.pop_args(arg_count);
         let multiname = self.pool_multiname(method, index)?;
-        let mut receiver = self.context.avm2.pop().coerce_to_object(self)?;
+        let mut receiver = self.pop_receiver()?;
         let name: Result<QName, Error> = receiver
-            .resolve_multiname(&multiname)?
+            .resolve_method_multiname(&multiname)?
             .ok_or_else(|| format!("Could not find method {:?}", multiname.local_name()).into());
         let name = name?;
         let base_proto = receiver.get_base_proto(&name)?;
@@ -1027,32 +1153,77 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
+        offset: u64,
     ) -> Result<FrameControl<'gc>, Error> {
-        let multiname = self.pool_multiname(method, index)?;
-        let mut object = self.context.avm2.pop().coerce_to_object(self)?;
+        let is_static =
+            Multiname::is_abc_multiname_static(method.translation_unit(), index.clone());
+        let (multiname, object) = if is_static {
+            let mut object = self.pop_receiver()?;
+            if let Some(cached) = Self::cached_property(method, offset, object) {
+                let value = match cached.slot_id {
+                    Some(slot_id) if object.has_instantiated_property(&cached.name) => {
+                        object.get_slot(slot_id)?
+                    }
+                    _ => object.get_property(object, &cached.name, self)?,
+                };
 
-        let name: Result<QName, Error> = object.resolve_multiname(&multiname)?.ok_or_else(|| {
-            format!("Could not resolve property {:?}", multiname.local_name()).into()
-        });
+                self.context.avm2.push(value);
 
-        // Special case for dynamic properties as scripts may attempt to get
-        // dynamic properties not yet set
-        if name.is_err()
-            && !object
-                .as_proto_class()
-                .map(|c| c.read().is_sealed())
-                .unwrap_or(false)
-        {
-            self.context.avm2.push(Value::Undefined);
-            return Ok(FrameControl::Continue);
+                return Ok(FrameControl::Continue);
+            }
+
+            (self.pool_multiname(method, index)?, object)
+        } else {
+            let multiname = self.pool_multiname(method, index)?;
+            (multiname, self.pop_receiver()?)
+        };
+
+        let (name, value) = self.get_resolved_property(object, &multiname)?;
+
+        if let (true, Some(name)) = (is_static, name) {
+            self.cache_property(method, offset, object, name, None);
         }
 
-        let value = object.get_property(object, &name?, self)?;
         self.context.avm2.push(value);
 
         Ok(FrameControl::Continue)
     }
 
+    /// Read the property of `object` that `multiname` resolves to, along with
+    /// the name it resolved to.
+    ///
+    /// A name that resolves to nothing reads as `undefined` on a dynamic
+    /// object, as scripts may read dynamic properties they have not set yet.
+    /// Instances of sealed classes throw instead.
+    fn get_resolved_property(
+        &mut self,
+        mut object: Object<'gc>,
+        multiname: &Multiname<'gc>,
+    ) -> Result<(Option<QName<'gc>>, Value<'gc>), Error> {
+        let name = match object.resolve_multiname(multiname)? {
+            Some(name) => name,
+            None => {
+                if let Some(class) = object.as_proto_class() {
+                    let class = class.read();
+
+                    if class.is_sealed() {
+                        return Err(format!(
+                            "ReferenceError: Error #1069: Property {} not found on {} and there is no default value.",
+                            multiname.local_name().unwrap_or_else(|| "*".into()),
+                            qualified_class_name(&class)
+                        )
+                        .into());
+                    }
+                }
+
+                return Ok((None, Value::Undefined));
+            }
+        };
+        let value = object.get_property(object, &name, self)?;
+
+        Ok((Some(name), value))
+    }
+
     fn op_set_property(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
@@ -1060,17 +1231,19 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     ) -> Result<FrameControl<'gc>, Error> {
         let value = self.context.avm2.pop();
         let multiname = self.pool_multiname(method, index)?;
-        let mut object = self.context.avm2.pop().coerce_to_object(self)?;
+        let mut object = self.pop_receiver()?;
 
         if let Some(name) = object.resolve_multiname(&multiname)? {
             object.set_property(object, &name, value, self)?;
         } else {
-            //TODO: Non-dynamic objects should fail
             //TODO: This should only work if the public namespace is present
             let local_name: Result<AvmString<'gc>, Error> = multiname
                 .local_name()
                 .ok_or_else(|| "Cannot set property using any name".into());
-            let name = QName::dynamic_name(local_name?);
+            let local_name = local_name?;
+            ensure_dynamic(object, local_name)?;
+
+            let name = QName::dynamic_name(local_name);
             object.set_property(object, &name, value, self)?;
         }
 
@@ -1084,17 +1257,19 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     ) -> Result<FrameControl<'gc>, Error> {
         let value = self.context.avm2.pop();
         let multiname = self.pool_multiname(method, index)?;
-        let mut object = self.context.avm2.pop().coerce_to_object(self)?;
+        let mut object = self.pop_receiver()?;
 
         if let Some(name) = object.resolve_multiname(&multiname)? {
             object.init_property(object, &name, value, self)?;
         } else {
-            //TODO: Non-dynamic objects should fail
             //TODO: This should only work if the public namespace is present
             let local_name: Result<AvmString<'gc>, Error> = multiname
                 .local_name()
                 .ok_or_else(|| "Cannot set property using any name".into());
-            let name = QName::dynamic_name(local_name?);
+            let local_name = local_name?;
+            ensure_dynamic(object, local_name)?;
+
+            let name = QName::dynamic_name(local_name);
             object.init_property(object, &name, value, self)?;
         }
 
@@ -1107,23 +1282,34 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         index: Index<AbcMultiname>,
     ) -> Result<FrameControl<'gc>, Error> {
         let multiname = self.pool_multiname(method, index)?;
-        let object = self.context.avm2.pop().coerce_to_object(self)?;
+        let object = self.pop_receiver()?;
+        let deleted = self.delete_multiname(object, &multiname)?;
 
-        if let Some(name) = object.resolve_multiname(&multiname)? {
-            self.context
-                .avm2
-                .push(object.delete_property(self.context.gc_context, &name))
+        self.context.avm2.push(deleted);
+
+        Ok(FrameControl::Continue)
+    }
+
+    /// Delete the property of `object` named by `multiname`, returning
+    /// whether it is now gone.
+    ///
+    /// Traits can never be deleted, whichever class in the chain declared
+    /// them. A dynamic property can't share a name with one, since setting it
+    /// would have set the trait instead.
+    fn delete_multiname(
+        &mut self,
+        object: Object<'gc>,
+        multiname: &Multiname<'gc>,
+    ) -> Result<bool, Error> {
+        if let Some(name) = object.resolve_multiname(multiname)? {
+            Ok(object.delete_property(self.context.gc_context, &name))
         } else {
             // Unknown properties on a dynamic class delete successfully.
-            self.context.avm2.push(
-                !object
-                    .as_proto_class()
-                    .map(|c| c.read().is_sealed())
-                    .unwrap_or(false),
-            )
+            Ok(!object
+                .as_proto_class()
+                .map(|c| c.read().is_sealed())
+                .unwrap_or(false))
         }
-
-        Ok(FrameControl::Continue)
     }
 
     fn op_get_super(
@@ -1216,8 +1402,10 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     }
 
     fn op_pop_scope(&mut self) -> Result<FrameControl<'gc>, Error> {
-        let scope_stack = self.scope();
-        let new_scope = scope_stack.and_then(|s| s.read().pop_scope());
+        let scope_stack = self
+            .scope()
+            .ok_or("VerifyError: Error #1018: Scope stack underflow occurred.")?;
+        let new_scope = scope_stack.read().pop_scope();
 
         self.set_scope(new_scope);
 
@@ -1354,6 +1542,13 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let args = self.context.avm2.pop_args(arg_count);
         let mut ctor = self.context.avm2.pop().coerce_to_object(self)?;
 
+        if ctor.as_class().is_none() {
+            let object = self.construct_with_function(ctor, &args)?;
+            self.context.avm2.push(object);
+
+            return Ok(FrameControl::Continue);
+        }
+
         let proto = ctor
             .get_property(ctor, &QName::new(Namespace::public(), "prototype"), self)?
             .coerce_to_object(self)?;
@@ -1366,6 +1561,32 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
+    /// Construct an object with a function that isn't a class constructor,
+    /// as ES3 does.
+    ///
+    /// The new object's prototype is the function's `prototype`, or
+    /// `Object.prototype` if that isn't an object. The function is run with
+    /// the new object as `this`; if it returns an object of its own, that is
+    /// what was constructed instead. Any other return value is ignored.
+    fn construct_with_function(
+        &mut self,
+        mut ctor: Object<'gc>,
+        args: &[Value<'gc>],
+    ) -> Result<Object<'gc>, Error> {
+        let proto =
+            match ctor.get_property(ctor, &QName::new(Namespace::public(), "prototype"), self)? {
+                Value::Object(proto) => proto,
+                _ => self.context.avm2.prototypes().object,
+            };
+
+        let object = ScriptObject::object(self.context.gc_context, proto);
+
+        match ctor.call(Some(object), args, self, None)? {
+            Value::Object(returned) => Ok(returned),
+            _ => Ok(object),
+        }
+    }
+
     fn op_construct_prop(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
@@ -1383,6 +1604,14 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let mut ctor = source
             .get_property(source, &ctor_name?, self)?
             .coerce_to_object(self)?;
+
+        if ctor.as_class().is_none() {
+            let object = self.construct_with_function(ctor, &args)?;
+            self.context.avm2.push(object);
+
+            return Ok(FrameControl::Continue);
+        }
+
         let proto = ctor
             .get_property(ctor, &QName::new(Namespace::public(), "prototype"), self)?
             .coerce_to_object(self)?;
@@ -1418,10 +1647,18 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             self.context.avm2.prototypes().object,
         );
 
+        let mut pairs = Vec::with_capacity(num_args as usize);
         for _ in 0..num_args {
             let value = self.context.avm2.pop();
             let name = self.context.avm2.pop();
 
+            pairs.push((name, value));
+        }
+
+        // The last pair is on top of the stack, so properties are added in
+        // reverse to be enumerated in the order they were written. A repeated
+        // name keeps its first position but takes its last value.
+        for (name, value) in pairs.into_iter().rev() {
             object.set_property(
                 object,
                 &QName::new(Namespace::public(), name.coerce_to_string(self)?),
@@ -1443,12 +1680,11 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let method_entry = self.table_method(method, index, self.context.gc_context)?;
         let scope = self.scope();
 
-        let mut new_fn = FunctionObject::from_method(
+        let mut new_fn = FunctionObject::from_function(
             self.context.gc_context,
             method_entry.into(),
             scope,
             self.context.avm2.prototypes().function,
-            None,
         );
         let es3_proto = ScriptObject::object(
             self.context.gc_context,
@@ -1534,7 +1770,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     fn op_convert_i(&mut self) -> Result<FrameControl<'gc>, Error> {
         let value = self.context.avm2.pop().coerce_to_i32(self)?;
 
-        self.context.avm2.push(Value::Number(value.into()));
+        self.context.avm2.push(Value::Integer(value));
 
         Ok(FrameControl::Continue)
     }
@@ -1558,7 +1794,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     fn op_convert_u(&mut self) -> Result<FrameControl<'gc>, Error> {
         let value = self.context.avm2.pop().coerce_to_u32(self)?;
 
-        self.context.avm2.push(Value::Number(value.into()));
+        self.context.avm2.push(Value::Unsigned(value));
 
         Ok(FrameControl::Continue)
     }
@@ -1575,20 +1811,24 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
+        if let Some(sum) = integer_arithmetic(&value1, &value2, i32::checked_add) {
+            self.context.avm2.push(sum);
+
+            return Ok(FrameControl::Continue);
+        }
+
         // TODO: Special handling required for `Date` and ECMA-357/E4X `XML`
         let sum_value = match (value1, value2) {
             (Value::Number(n1), Value::Number(n2)) => Value::Number(n1 + n2),
             (Value::String(s), value2) => {
-                let mut out_s = s.to_string();
-                out_s.push_str(&value2.coerce_to_string(self)?);
+                let s2 = value2.coerce_to_string(self)?;
 
-                Value::String(AvmString::new(self.context.gc_context, out_s))
+                Value::String(AvmString::concat(self.context.gc_context, s, s2))
             }
             (value1, Value::String(s)) => {
-                let mut out_s = value1.coerce_to_string(self)?.to_string();
-                out_s.push_str(&s);
+                let s1 = value1.coerce_to_string(self)?;
 
-                Value::String(AvmString::new(self.context.gc_context, out_s))
+                Value::String(AvmString::concat(self.context.gc_context, s1, s))
             }
             (value1, value2) => {
                 let prim_value1 = value1.coerce_to_primitive(None, self)?;
@@ -1596,16 +1836,14 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
 
                 match (prim_value1, prim_value2) {
                     (Value::String(s), value2) => {
-                        let mut out_s = s.to_string();
-                        out_s.push_str(&value2.coerce_to_string(self)?);
+                        let s2 = value2.coerce_to_string(self)?;
 
-                        Value::String(AvmString::new(self.context.gc_context, out_s))
+                        Value::String(AvmString::concat(self.context.gc_context, s, s2))
                     }
                     (value1, Value::String(s)) => {
-                        let mut out_s = value1.coerce_to_string(self)?.to_string();
-                        out_s.push_str(&s);
+                        let s1 = value1.coerce_to_string(self)?;
 
-                        Value::String(AvmString::new(self.context.gc_context, out_s))
+                        Value::String(AvmString::concat(self.context.gc_context, s1, s))
                     }
                     (value1, value2) => Value::Number(
                         value1.coerce_to_number(self)? + value2.coerce_to_number(self)?,
@@ -1623,7 +1861,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let value2 = self.context.avm2.pop().coerce_to_i32(self)?;
         let value1 = self.context.avm2.pop().coerce_to_i32(self)?;
 
-        self.context.avm2.push(value1 + value2);
+        self.context.avm2.push(value1.wrapping_add(value2));
 
         Ok(FrameControl::Continue)
     }
@@ -1664,7 +1902,15 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     }
 
     fn op_declocal(&mut self, index: u32) -> Result<FrameControl<'gc>, Error> {
-        let value = self.local_register(index)?.coerce_to_number(self)?;
+        let value = self.local_register(index)?;
+
+        if let Some(difference) = integer_arithmetic(&value, &1.into(), i32::checked_sub) {
+            self.set_local_register(index, difference, self.context.gc_context)?;
+
+            return Ok(FrameControl::Continue);
+        }
+
+        let value = value.coerce_to_number(self)?;
 
         self.set_local_register(index, value - 1.0, self.context.gc_context)?;
 
@@ -1674,13 +1920,21 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     fn op_declocal_i(&mut self, index: u32) -> Result<FrameControl<'gc>, Error> {
         let value = self.local_register(index)?.coerce_to_i32(self)?;
 
-        self.set_local_register(index, value - 1, self.context.gc_context)?;
+        self.set_local_register(index, value.wrapping_sub(1), self.context.gc_context)?;
 
         Ok(FrameControl::Continue)
     }
 
     fn op_decrement(&mut self) -> Result<FrameControl<'gc>, Error> {
-        let value = self.context.avm2.pop().coerce_to_number(self)?;
+        let value = self.context.avm2.pop();
+
+        if let Some(difference) = integer_arithmetic(&value, &1.into(), i32::checked_sub) {
+            self.context.avm2.push(difference);
+
+            return Ok(FrameControl::Continue);
+        }
+
+        let value = value.coerce_to_number(self)?;
 
         self.context.avm2.push(value - 1.0);
 
@@ -1690,7 +1944,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     fn op_decrement_i(&mut self) -> Result<FrameControl<'gc>, Error> {
         let value = self.context.avm2.pop().coerce_to_i32(self)?;
 
-        self.context.avm2.push(value - 1);
+        self.context.avm2.push(value.wrapping_sub(1));
 
         Ok(FrameControl::Continue)
     }
@@ -1705,7 +1959,15 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     }
 
     fn op_inclocal(&mut self, index: u32) -> Result<FrameControl<'gc>, Error> {
-        let value = self.local_register(index)?.coerce_to_number(self)?;
+        let value = self.local_register(index)?;
+
+        if let Some(sum) = integer_arithmetic(&value, &1.into(), i32::checked_add) {
+            self.set_local_register(index, sum, self.context.gc_context)?;
+
+            return Ok(FrameControl::Continue);
+        }
+
+        let value = value.coerce_to_number(self)?;
 
         self.set_local_register(index, value + 1.0, self.context.gc_context)?;
 
@@ -1715,13 +1977,21 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     fn op_inclocal_i(&mut self, index: u32) -> Result<FrameControl<'gc>, Error> {
         let value = self.local_register(index)?.coerce_to_i32(self)?;
 
-        self.set_local_register(index, value + 1, self.context.gc_context)?;
+        self.set_local_register(index, value.wrapping_add(1), self.context.gc_context)?;
 
         Ok(FrameControl::Continue)
     }
 
     fn op_increment(&mut self) -> Result<FrameControl<'gc>, Error> {
-        let value = self.context.avm2.pop().coerce_to_number(self)?;
+        let value = self.context.avm2.pop();
+
+        if let Some(sum) = integer_arithmetic(&value, &1.into(), i32::checked_add) {
+            self.context.avm2.push(sum);
+
+            return Ok(FrameControl::Continue);
+        }
+
+        let value = value.coerce_to_number(self)?;
 
         self.context.avm2.push(value + 1.0);
 
@@ -1731,7 +2001,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     fn op_increment_i(&mut self) -> Result<FrameControl<'gc>, Error> {
         let value = self.context.avm2.pop().coerce_to_i32(self)?;
 
-        self.context.avm2.push(value + 1);
+        self.context.avm2.push(value.wrapping_add(1));
 
         Ok(FrameControl::Continue)
     }
@@ -1746,8 +2016,17 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     }
 
     fn op_modulo(&mut self) -> Result<FrameControl<'gc>, Error> {
-        let value2 = self.context.avm2.pop().coerce_to_number(self)?;
-        let value1 = self.context.avm2.pop().coerce_to_number(self)?;
+        let value2 = self.context.avm2.pop();
+        let value1 = self.context.avm2.pop();
+
+        if let Some(remainder) = integer_arithmetic(&value1, &value2, checked_integer_rem) {
+            self.context.avm2.push(remainder);
+
+            return Ok(FrameControl::Continue);
+        }
+
+        let value2 = value2.coerce_to_number(self)?;
+        let value1 = value1.coerce_to_number(self)?;
 
         self.context.avm2.push(value1 % value2);
 
@@ -1755,8 +2034,17 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     }
 
     fn op_multiply(&mut self) -> Result<FrameControl<'gc>, Error> {
-        let value2 = self.context.avm2.pop().coerce_to_number(self)?;
-        let value1 = self.context.avm2.pop().coerce_to_number(self)?;
+        let value2 = self.context.avm2.pop();
+        let value1 = self.context.avm2.pop();
+
+        if let Some(product) = integer_arithmetic(&value1, &value2, checked_integer_mul) {
+            self.context.avm2.push(product);
+
+            return Ok(FrameControl::Continue);
+        }
+
+        let value2 = value2.coerce_to_number(self)?;
+        let value1 = value1.coerce_to_number(self)?;
 
         self.context.avm2.push(value1 * value2);
 
@@ -1767,13 +2055,26 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let value2 = self.context.avm2.pop().coerce_to_i32(self)?;
         let value1 = self.context.avm2.pop().coerce_to_i32(self)?;
 
-        self.context.avm2.push(value1 * value2);
+        self.context.avm2.push(value1.wrapping_mul(value2));
 
         Ok(FrameControl::Continue)
     }
 
     fn op_negate(&mut self) -> Result<FrameControl<'gc>, Error> {
-        let value1 = self.context.avm2.pop().coerce_to_number(self)?;
+        let value1 = self.context.avm2.pop();
+
+        // Negating zero yields `-0`, which only a `Number` can hold.
+        if let Some(negation) = value1
+            .as_integer()
+            .filter(|i| *i != 0)
+            .and_then(i32::checked_neg)
+        {
+            self.context.avm2.push(negation);
+
+            return Ok(FrameControl::Continue);
+        }
+
+        let value1 = value1.coerce_to_number(self)?;
 
         self.context.avm2.push(-value1);
 
@@ -1783,7 +2084,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     fn op_negate_i(&mut self) -> Result<FrameControl<'gc>, Error> {
         let value1 = self.context.avm2.pop().coerce_to_i32(self)?;
 
-        self.context.avm2.push(-value1);
+        self.context.avm2.push(value1.wrapping_neg());
 
         Ok(FrameControl::Continue)
     }
@@ -1798,8 +2099,17 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     }
 
     fn op_subtract(&mut self) -> Result<FrameControl<'gc>, Error> {
-        let value2 = self.context.avm2.pop().coerce_to_number(self)?;
-        let value1 = self.context.avm2.pop().coerce_to_number(self)?;
+        let value2 = self.context.avm2.pop();
+        let value1 = self.context.avm2.pop();
+
+        if let Some(difference) = integer_arithmetic(&value1, &value2, i32::checked_sub) {
+            self.context.avm2.push(difference);
+
+            return Ok(FrameControl::Continue);
+        }
+
+        let value2 = value2.coerce_to_number(self)?;
+        let value1 = value1.coerce_to_number(self)?;
 
         self.context.avm2.push(value1 - value2);
 
@@ -1810,12 +2120,13 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let value2 = self.context.avm2.pop().coerce_to_i32(self)?;
         let value1 = self.context.avm2.pop().coerce_to_i32(self)?;
 
-        self.context.avm2.push(value1 - value2);
+        self.context.avm2.push(value1.wrapping_sub(value2));
 
         Ok(FrameControl::Continue)
     }
 
     fn op_swap(&mut self) -> Result<FrameControl<'gc>, Error> {
+        self.check_stack_depth(2)?;
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
@@ -1844,6 +2155,33 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
+    /// Implements `lookupswitch`.
+    ///
+    /// Unlike the other branches, offsets are relative to the start of this
+    /// instruction. Indices that are not an integer naming one of the cases,
+    /// including `NaN`, take the default branch.
+    fn op_lookup_switch(
+        &mut self,
+        op_start: u64,
+        default_offset: i32,
+        case_offsets: &[i32],
+        reader: &mut Reader<Cursor<&[u8]>>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let index = self.context.avm2.pop().coerce_to_number(self)?;
+
+        let offset = if index >= 0.0 && index.fract() == 0.0 {
+            case_offsets.get(index as usize).copied()
+        } else {
+            None
+        }
+        .unwrap_or(default_offset);
+
+        let op_end = reader.seek(0)?;
+        reader.seek(op_start as i64 + offset as i64 - op_end as i64)?;
+
+        Ok(FrameControl::Continue)
+    }
+
     fn op_if_true(
         &mut self,
         offset: i32,
@@ -1880,7 +2218,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
-        if value1 == value2 {
+        if value1.strict_eq(&value2) {
             reader.seek(offset as i64)?;
         }
 
@@ -1895,7 +2233,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
-        if value1 != value2 {
+        if !value1.strict_eq(&value2) {
             reader.seek(offset as i64)?;
         }
 
@@ -2056,7 +2394,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
-        self.context.avm2.push(value1 == value2);
+        self.context.avm2.push(value1.strict_eq(&value2));
 
         Ok(FrameControl::Continue)
     }
@@ -2124,17 +2462,41 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
+    /// Call one of the enumeration traps of a `Proxy` with an enumerant
+    /// index, if `object` is a `Proxy`.
+    ///
+    /// Proxies are enumerated only through their traps: `nextNameIndex`
+    /// yields the index after the one given, or 0 once there are no more,
+    /// and `nextName` and `nextValue` yield the name and value at an index.
+    fn call_proxy_trap(
+        &mut self,
+        mut object: Object<'gc>,
+        trap: &'static str,
+        index: u32,
+    ) -> Result<Option<Value<'gc>>, Error> {
+        let proxy_proto = self.context.avm2.prototypes().proxy;
+        if !object.has_prototype_in_chain(proxy_proto, false)? {
+            return Ok(None);
+        }
+
+        let method = object
+            .get_property(object, &QName::new(Namespace::flash_proxy(), trap), self)?
+            .coerce_to_object(self)?;
+        let value = method.call(Some(object), &[index.into()], self, object.proto())?;
+
+        Ok(Some(value))
+    }
+
     fn op_has_next(&mut self) -> Result<FrameControl<'gc>, Error> {
         let cur_index = self.context.avm2.pop().coerce_to_u32(self)?;
         let object = self.context.avm2.pop().coerce_to_object(self)?;
 
-        let next_index = cur_index + 1;
+        let next_index = match self.call_proxy_trap(object, "nextNameIndex", cur_index)? {
+            Some(next_index) => next_index.coerce_to_u32(self)?,
+            None => next_enumerant_in_chain(object, cur_index)?.unwrap_or(0),
+        };
 
-        if object.get_enumerant_name(next_index).is_some() {
-            self.context.avm2.push(next_index);
-        } else {
-            self.context.avm2.push(0.0);
-        }
+        self.context.avm2.push(next_index);
 
         Ok(FrameControl::Continue)
     }
@@ -2145,33 +2507,24 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         index_register: u32,
     ) -> Result<FrameControl<'gc>, Error> {
         let cur_index = self.local_register(index_register)?.coerce_to_u32(self)?;
-        let mut object = Some(
-            self.local_register(object_register)?
-                .coerce_to_object(self)?,
-        );
-
-        let mut next_index = cur_index + 1;
-
-        while let Some(cur_object) = object {
-            if cur_object.get_enumerant_name(next_index).is_none() {
-                next_index = 1;
-                object = cur_object.proto();
-            } else {
-                break;
-            }
-        }
+        let object = self
+            .local_register(object_register)?
+            .coerce_to_object(self)?;
 
-        if object.is_none() {
-            next_index = 0;
-        }
+        let next_index = match self.call_proxy_trap(object, "nextNameIndex", cur_index)? {
+            Some(next_index) => Some(next_index.coerce_to_u32(self)?).filter(|i| *i != 0),
+            None => next_enumerant_in_chain(object, cur_index)?,
+        };
 
-        self.context.avm2.push(next_index != 0);
-        self.set_local_register(index_register, next_index, self.context.gc_context)?;
+        self.context.avm2.push(next_index.is_some());
         self.set_local_register(
-            object_register,
-            object.map(|v| v.into()).unwrap_or(Value::Null),
+            index_register,
+            next_index.unwrap_or(0),
             self.context.gc_context,
         )?;
+        if next_index.is_none() {
+            self.set_local_register(object_register, Value::Null, self.context.gc_context)?;
+        }
 
         Ok(FrameControl::Continue)
     }
@@ -2180,9 +2533,13 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let cur_index = self.context.avm2.pop().coerce_to_number(self)?;
         let object = self.context.avm2.pop().coerce_to_object(self)?;
 
-        let name = object
-            .get_enumerant_name(cur_index as u32)
-            .map(|n| n.local_name().into());
+        if let Some(name) = self.call_proxy_trap(object, "nextName", cur_index as u32)? {
+            self.context.avm2.push(name);
+
+            return Ok(FrameControl::Continue);
+        }
+
+        let name = enumerant_name_in_chain(object, cur_index as u32).map(|n| n.local_name().into());
 
         self.context.avm2.push(name.unwrap_or(Value::Undefined));
 
@@ -2193,7 +2550,13 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let cur_index = self.context.avm2.pop().coerce_to_number(self)?;
         let mut object = self.context.avm2.pop().coerce_to_object(self)?;
 
-        let name = object.get_enumerant_name(cur_index as u32);
+        if let Some(value) = self.call_proxy_trap(object, "nextValue", cur_index as u32)? {
+            self.context.avm2.push(value);
+
+            return Ok(FrameControl::Continue);
+        }
+
+        let name = enumerant_name_in_chain(object, cur_index as u32);
         let value = if let Some(name) = name {
             object.get_property(object, &name, self)?
         } else {
@@ -2205,12 +2568,47 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
+    /// Determine if `value` is of the type that `type_object` constructs.
+    ///
+    /// Numbers are `int` or `uint` whenever they hold an integer in that
+    /// type's range, regardless of how they are stored.
+    fn is_of_type(
+        &mut self,
+        value: Value<'gc>,
+        mut type_object: Object<'gc>,
+    ) -> Result<bool, Error> {
+        let type_proto = type_object
+            .get_property(type_object, &QName::dynamic_name("prototype"), self)?
+            .coerce_to_object(self)?;
+        let is_int = Object::ptr_eq(type_proto, self.avm2().prototypes().int);
+        let is_uint = Object::ptr_eq(type_proto, self.avm2().prototypes().uint);
+
+        if is_int || is_uint {
+            let (min, max) = if is_int {
+                (i32::MIN as f64, i32::MAX as f64)
+            } else {
+                (0.0, u32::MAX as f64)
+            };
+
+            match value {
+                Value::Number(n) => return Ok(n.fract() == 0.0 && n >= min && n <= max),
+                Value::Integer(i) => return Ok(i as f64 >= min),
+                Value::Unsigned(u) => return Ok(u as f64 <= max),
+                _ => {}
+            }
+        }
+
+        value
+            .coerce_to_object(self)?
+            .is_instance_of(self, type_object, true)
+    }
+
     fn op_is_type(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         type_name_index: Index<AbcMultiname>,
     ) -> Result<FrameControl<'gc>, Error> {
-        let value = self.context.avm2.pop().coerce_to_object(self)?;
+        let value = self.context.avm2.pop();
 
         let type_name =
             self.pool_multiname_static(method, type_name_index, self.context.gc_context)?;
@@ -2221,7 +2619,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         };
 
         if let Some(type_object) = type_object {
-            let is_instance_of = value.is_instance_of(self, type_object, true)?;
+            let is_instance_of = self.is_of_type(value, type_object)?;
             self.context.avm2.push(is_instance_of);
         } else {
             return Err(format!(
@@ -2236,18 +2634,38 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
 
     fn op_is_type_late(&mut self) -> Result<FrameControl<'gc>, Error> {
         let type_object = self.context.avm2.pop().coerce_to_object(self)?;
-        let value = self.context.avm2.pop().coerce_to_object(self)?;
+        let value = self.context.avm2.pop();
 
-        let is_instance_of = value.is_instance_of(self, type_object, true)?;
+        let is_instance_of = self.is_of_type(value, type_object)?;
 
         self.context.avm2.push(is_instance_of);
 
         Ok(FrameControl::Continue)
     }
 
+    /// Implements `instanceof`, which looks for the right operand's
+    /// `prototype` in the left operand's prototype chain.
+    ///
+    /// Unlike `istype`, this works for functions as well as classes, and
+    /// does not consider interfaces.
     fn op_instance_of(&mut self) -> Result<FrameControl<'gc>, Error> {
-        let type_object = self.context.avm2.pop().coerce_to_object(self)?;
-        let value = self.context.avm2.pop().coerce_to_object(self)?;
+        let type_object = match self.context.avm2.pop() {
+            Value::Object(type_object) if type_object.as_executable().is_some() => type_object,
+            _ => {
+                return Err(
+                    "TypeError: Error #1040: The right-hand side of instanceof must be a class or function."
+                        .into(),
+                )
+            }
+        };
+        let value = match self.context.avm2.pop() {
+            Value::Undefined | Value::Null => {
+                self.context.avm2.push(false);
+
+                return Ok(FrameControl::Continue);
+            }
+            value => value.coerce_to_object(self)?,
+        };
 
         let is_instance_of = value.is_instance_of(self, type_object, false)?;
 
@@ -2259,38 +2677,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     fn op_type_of(&mut self) -> Result<FrameControl<'gc>, Error> {
         let value = self.context.avm2.pop();
 
-        let type_name = match value {
-            Value::Undefined => "undefined",
-            Value::Null => "object",
-            Value::Bool(_) => "boolean",
-            Value::Number(_) | Value::Integer(_) | Value::Unsigned(_) => "number",
-            Value::Object(o) => {
-                // Subclasses always have a typeof = "object", must be a subclass if the prototype chain is > 2, or not a subclass if <=2
-                let is_not_subclass = matches!(
-                    o.proto().and_then(|p| p.proto()).and_then(|p| p.proto()),
-                    None
-                );
-
-                match o {
-                    Object::FunctionObject(_) => {
-                        if is_not_subclass {
-                            "function"
-                        } else {
-                            "object"
-                        }
-                    }
-                    Object::XmlObject(_) => {
-                        if is_not_subclass {
-                            "xml"
-                        } else {
-                            "object"
-                        }
-                    }
-                    _ => "object",
-                }
-            }
-            Value::String(_) => "string",
-        };
+        let type_name = value.type_of();
 
         self.context.avm2.push(Value::String(AvmString::new(
             self.context.gc_context,
@@ -2300,11 +2687,212 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    #[allow(unused_variables)]
-    #[cfg(avm_debug)]
-    fn op_debug(
-        &mut self,
-        method: Gc<'gc, BytecodeMethod<'gc>>,
+    /// Implements `checkfilter`, which E4X filtering expressions run before
+    /// evaluating their predicate.
+    ///
+    /// Only `XML` and `XMLList` values may be filtered. The value is left on
+    /// the stack.
+    fn op_check_filter(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let value = self.context.avm2.pop();
+        let xml_proto = self.context.avm2.prototypes().xml;
+        let xml_list_proto = self.context.avm2.prototypes().xml_list;
+
+        let type_name = match value {
+            Value::Object(object) => {
+                if object.has_prototype_in_chain(xml_proto, false)?
+                    || object.has_prototype_in_chain(xml_list_proto, false)?
+                {
+                    self.context.avm2.push(value);
+
+                    return Ok(FrameControl::Continue);
+                }
+
+                object
+                    .as_proto_class()
+                    .map(|class| qualified_class_name(&class.read()))
+                    .unwrap_or_else(|| "Object".to_string())
+            }
+            Value::Undefined => "void".to_string(),
+            Value::Null => "null".to_string(),
+            Value::Bool(_) => "Boolean".to_string(),
+            Value::Number(_) => "Number".to_string(),
+            Value::Unsigned(_) => "uint".to_string(),
+            Value::Integer(_) => "int".to_string(),
+            Value::String(_) => "String".to_string(),
+        };
+
+        Err(format!(
+            "TypeError: Error #1123: Filter operator not supported on type {}.",
+            type_name
+        )
+        .into())
+    }
+
+    /// Implements `esc_xelem`, which escapes a value to be placed in an XML
+    /// element's content.
+    fn op_esc_xelem(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let value = self.context.avm2.pop().coerce_to_string(self)?;
+        let escaped = escape_xml(&value, false);
+
+        self.context
+            .avm2
+            .push(AvmString::new(self.context.gc_context, escaped));
+
+        Ok(FrameControl::Continue)
+    }
+
+    /// Implements `esc_xattr`, which escapes a value to be placed in an XML
+    /// attribute's value.
+    fn op_esc_xattr(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let value = self.context.avm2.pop().coerce_to_string(self)?;
+        let escaped = escape_xml(&value, true);
+
+        self.context
+            .avm2
+            .push(AvmString::new(self.context.gc_context, escaped));
+
+        Ok(FrameControl::Continue)
+    }
+
+    /// Get the memory of the domain this code runs in, for the domain memory
+    /// opcodes.
+    ///
+    /// A domain that was never given a `domainMemory` gets a blank buffer of
+    /// `MIN_DOMAIN_MEMORY_LENGTH` bytes the first time it is needed.
+    fn domain_memory(&mut self) -> Object<'gc> {
+        let domain = self
+            .scope()
+            .and_then(|scope| scope.read().globals().as_application_domain())
+            .unwrap_or_else(|| self.context.avm2.global_domain());
+        if let Some(memory) = domain.domain_memory() {
+            return memory;
+        }
+
+        let mc = self.context.gc_context;
+        let memory = ByteArrayObject::construct(mc, Some(self.context.avm2.prototypes().bytearray));
+        if let Some(mut bytes) = memory.as_bytearray_mut(mc) {
+            bytes.set_length(MIN_DOMAIN_MEMORY_LENGTH);
+        }
+        domain.set_domain_memory(mc, Some(memory));
+
+        memory
+    }
+
+    /// Pop an address in domain memory off the stack, and read `N` bytes
+    /// there.
+    fn load_domain_memory<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        let address = self.context.avm2.pop().coerce_to_i32(self)?;
+        let memory = self.domain_memory();
+        let bytes = memory
+            .as_bytearray()
+            .ok_or("Domain memory is not a ByteArray")?;
+        let mut value = [0; N];
+        value.copy_from_slice(bytes.read_at(domain_memory_offset(address)?, N)?);
+
+        Ok(value)
+    }
+
+    /// Write `bytes` to domain memory at `address`.
+    fn store_domain_memory(&mut self, address: Value<'gc>, bytes: &[u8]) -> Result<(), Error> {
+        let address = address.coerce_to_i32(self)?;
+        let memory = self.domain_memory();
+        let mut memory = memory
+            .as_bytearray_mut(self.context.gc_context)
+            .ok_or("Domain memory is not a ByteArray")?;
+
+        memory.write_at(domain_memory_offset(address)?, bytes)
+    }
+
+    fn op_li8(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let [value] = self.load_domain_memory()?;
+
+        self.context.avm2.push(Value::Integer(value.into()));
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_li16(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let value = u16::from_le_bytes(self.load_domain_memory()?);
+
+        self.context.avm2.push(Value::Integer(value.into()));
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_li32(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let value = i32::from_le_bytes(self.load_domain_memory()?);
+
+        self.context.avm2.push(Value::Integer(value));
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_lf32(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let value = f32::from_le_bytes(self.load_domain_memory()?);
+
+        self.context.avm2.push(Value::Number(value.into()));
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_lf64(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let value = f64::from_le_bytes(self.load_domain_memory()?);
+
+        self.context.avm2.push(Value::Number(value));
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_si8(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let address = self.context.avm2.pop();
+        let value = self.context.avm2.pop().coerce_to_i32(self)?;
+
+        self.store_domain_memory(address, &[value as u8])?;
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_si16(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let address = self.context.avm2.pop();
+        let value = self.context.avm2.pop().coerce_to_i32(self)?;
+
+        self.store_domain_memory(address, &(value as u16).to_le_bytes())?;
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_si32(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let address = self.context.avm2.pop();
+        let value = self.context.avm2.pop().coerce_to_i32(self)?;
+
+        self.store_domain_memory(address, &value.to_le_bytes())?;
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_sf32(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let address = self.context.avm2.pop();
+        let value = self.context.avm2.pop().coerce_to_number(self)?;
+
+        self.store_domain_memory(address, &(value as f32).to_le_bytes())?;
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_sf64(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let address = self.context.avm2.pop();
+        let value = self.context.avm2.pop().coerce_to_number(self)?;
+
+        self.store_domain_memory(address, &value.to_le_bytes())?;
+
+        Ok(FrameControl::Continue)
+    }
+
+    #[allow(unused_variables)]
+    #[cfg(avm_debug)]
+    fn op_debug(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
         is_local_register: bool,
         register_name: Index<String>,
         register: u8,
@@ -2364,3 +2952,2249 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 }
+
+/// Perform an arithmetic operation on two integer operands without leaving
+/// the integers.
+///
+/// This yields `None` if either operand isn't an integer, or if `op` can't
+/// hold its result in an `int`, in which case the caller should redo the
+/// operation with `Number`s.
+fn integer_arithmetic<'gc>(
+    value1: &Value<'gc>,
+    value2: &Value<'gc>,
+    op: fn(i32, i32) -> Option<i32>,
+) -> Option<Value<'gc>> {
+    let result = op(value1.as_integer()?, value2.as_integer()?)?;
+
+    Some(result.into())
+}
+
+/// Multiply two integers, unless the product overflows or is `-0`.
+fn checked_integer_mul(n1: i32, n2: i32) -> Option<i32> {
+    let product = n1.checked_mul(n2)?;
+
+    if product == 0 && (n1 < 0 || n2 < 0) {
+        None
+    } else {
+        Some(product)
+    }
+}
+
+/// Take the remainder of two integers, unless it is `NaN` or `-0`.
+fn checked_integer_rem(n1: i32, n2: i32) -> Option<i32> {
+    let remainder = n1.checked_rem(n2)?;
+
+    if remainder == 0 && n1 < 0 {
+        None
+    } else {
+        Some(remainder)
+    }
+}
+
+/// Refuse to create a dynamic property on an instance of a sealed class.
+///
+/// Whether an instance is sealed is decided by the nearest class on its
+/// prototype chain, so dynamic subclasses of sealed classes stay writable.
+/// Prototypes and class objects are always dynamic.
+fn ensure_dynamic<'gc>(object: Object<'gc>, name: AvmString<'gc>) -> Result<(), Error> {
+    if object.as_class().is_some() {
+        return Ok(());
+    }
+
+    if let Some(class) = object.as_proto_class() {
+        let class = class.read();
+
+        if class.is_sealed() {
+            return Err(format!(
+                "ReferenceError: Error #1056: Cannot create property {} on {}.",
+                name,
+                qualified_class_name(&class)
+            )
+            .into());
+        }
+    }
+
+    Ok(())
+}
+
+/// The name of a class as error messages give it, e.g. `flash.display.Sprite`.
+fn qualified_class_name(class: &Class<'_>) -> String {
+    let class_name = class.name();
+    let uri = class_name.namespace().as_uri();
+    if uri.is_empty() {
+        class_name.local_name().to_string()
+    } else {
+        format!("{}.{}", uri, class_name.local_name())
+    }
+}
+
+/// Escape text for XML, as E4X's `EscapeElementValue` and
+/// `EscapeAttributeValue` do.
+///
+/// Element content only escapes `&`, `<` and `>`. Attribute values leave `>`
+/// alone, but also escape double quotes and whitespace that attribute value
+/// normalization would otherwise turn into spaces.
+fn escape_xml(text: &str, is_attribute: bool) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match (c, is_attribute) {
+            ('&', _) => escaped.push_str("&amp;"),
+            ('<', _) => escaped.push_str("&lt;"),
+            ('>', false) => escaped.push_str("&gt;"),
+            ('"', true) => escaped.push_str("&quot;"),
+            ('\n', true) => escaped.push_str("&#xA;"),
+            ('\r', true) => escaped.push_str("&#xD;"),
+            ('\t', true) => escaped.push_str("&#x9;"),
+            (c, _) => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// Check an address used by the domain memory opcodes.
+fn domain_memory_offset(address: i32) -> Result<usize, Error> {
+    usize::try_from(address)
+        .map_err(|_| "RangeError: Error #1506: The specified range is invalid.".into())
+}
+
+/// The number of enumerant indices `object` uses, holes included.
+fn enumerant_count(object: Object<'_>) -> u32 {
+    let mut count = 0;
+    while let Some(index) = object.get_next_enumerant(count) {
+        count = index;
+    }
+
+    count
+}
+
+/// Find the enumerant after `last_index` in `object` or its prototype chain.
+///
+/// `hasnext2` enumerates an object's own properties first, followed by those
+/// of each of its prototypes in turn. Each prototype's indices follow on from
+/// the ones before it, so `nextname` and `nextvalue` can use them on the
+/// object itself. Names that an object nearer the start of the chain already
+/// has are skipped, as they are shadowed.
+fn next_enumerant_in_chain<'gc>(
+    object: Object<'gc>,
+    last_index: u32,
+) -> Result<Option<u32>, Error> {
+    let mut chain = vec![];
+    let mut current = Some(object);
+    let mut base = 0;
+
+    while let Some(cur_object) = current {
+        if let Some(mut local_index) = last_index.checked_sub(base) {
+            while let Some(index) = cur_object.get_next_enumerant(local_index) {
+                let is_shadowed = match cur_object.get_enumerant_name(index) {
+                    Some(name) => is_shadowed(&chain, &name)?,
+                    None => true,
+                };
+
+                if !is_shadowed {
+                    return Ok(Some(base + index));
+                }
+
+                local_index = index;
+            }
+        }
+
+        base += enumerant_count(cur_object);
+        chain.push(cur_object);
+        current = cur_object.proto();
+    }
+
+    Ok(None)
+}
+
+/// Determine if any of the objects earlier in a prototype chain has `name`.
+fn is_shadowed<'gc>(chain: &[Object<'gc>], name: &QName<'gc>) -> Result<bool, Error> {
+    for object in chain {
+        if object.has_own_property(name)? {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+/// Get the name of an enumerant found by `next_enumerant_in_chain`.
+fn enumerant_name_in_chain<'gc>(object: Object<'gc>, index: u32) -> Option<QName<'gc>> {
+    let mut current = Some(object);
+    let mut index = index;
+
+    while let Some(cur_object) = current {
+        if let Some(name) = cur_object.get_enumerant_name(index) {
+            return Some(name);
+        }
+
+        let count = enumerant_count(cur_object);
+        if index <= count {
+            return None;
+        }
+
+        index -= count;
+        current = cur_object.proto();
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::array::ArrayStorage;
+    use crate::avm2::class::ClassAttributes;
+    use crate::avm2::method::NativeMethod;
+    use crate::avm2::script::TranslationUnit;
+    use crate::avm2::test_utils::{trace_with_avm2, with_avm2};
+    use crate::avm2::traits::{Trait, TraitAttributes};
+    use crate::avm2::value::Hint;
+
+    type Opcode<'a, 'gc, 'gc_context> =
+        fn(&mut Activation<'a, 'gc, 'gc_context>) -> Result<FrameControl<'gc>, Error>;
+
+    fn run_op<'a, 'gc, 'gc_context>(
+        activation: &mut Activation<'a, 'gc, 'gc_context>,
+        op: Opcode<'a, 'gc, 'gc_context>,
+        operands: &[Value<'gc>],
+    ) -> Value<'gc> {
+        for operand in operands {
+            activation.context.avm2.push(operand.clone());
+        }
+
+        op(activation).unwrap();
+
+        activation.context.avm2.pop()
+    }
+
+    /// Run one of the domain memory store opcodes.
+    fn run_store<'a, 'gc, 'gc_context>(
+        activation: &mut Activation<'a, 'gc, 'gc_context>,
+        op: Opcode<'a, 'gc, 'gc_context>,
+        value: Value<'gc>,
+        address: i32,
+    ) -> Result<FrameControl<'gc>, Error> {
+        activation.context.avm2.push(value);
+        activation.context.avm2.push(address);
+
+        op(activation)
+    }
+
+    /// A constructor function that sets `this.x` to its first argument, and
+    /// returns its second.
+    fn set_x<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let mut this = this.unwrap();
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), "x"),
+            args[0].clone(),
+            activation,
+        )?;
+
+        Ok(args.get(1).cloned().unwrap_or(Value::Undefined))
+    }
+
+    fn is_negative_zero(value: &Value<'_>) -> bool {
+        matches!(value, Value::Number(n) if *n == 0.0 && n.is_sign_negative())
+    }
+
+    #[test]
+    fn integer_accumulation_stays_integer() {
+        with_avm2(|activation| {
+            let mut total = Value::Integer(0);
+            let mut i = Value::Integer(0);
+
+            // The sum of every `i` below 65536 is just under `int.MAX_VALUE`.
+            while i.as_integer() != Some(65536) {
+                total = run_op(activation, Activation::op_add, &[total, i.clone()]);
+                i = run_op(activation, Activation::op_increment, &[i]);
+
+                assert!(matches!(total, Value::Integer(_)), "{:?}", total);
+                assert!(matches!(i, Value::Integer(_)), "{:?}", i);
+            }
+
+            assert!(matches!(total, Value::Integer(2_147_450_880)));
+
+            // One more step overflows, so it must fall back to `Number`.
+            let total = run_op(activation, Activation::op_add, &[total, i]);
+            assert!(matches!(total, Value::Number(n) if n == 2_147_516_416.0));
+        });
+    }
+
+    #[test]
+    fn integer_arithmetic_falls_back_to_number() {
+        with_avm2(|activation| {
+            let quotient = run_op(activation, Activation::op_divide, &[3.into(), 2.into()]);
+            assert!(matches!(quotient, Value::Number(n) if n == 1.5));
+
+            let difference = run_op(activation, Activation::op_subtract, &[3.into(), 5.into()]);
+            assert!(matches!(difference, Value::Integer(-2)));
+
+            let product = run_op(
+                activation,
+                Activation::op_multiply,
+                &[(-1).into(), 0.into()],
+            );
+            assert!(is_negative_zero(&product));
+
+            let remainder = run_op(activation, Activation::op_modulo, &[(-4).into(), 2.into()]);
+            assert!(is_negative_zero(&remainder));
+
+            let remainder = run_op(activation, Activation::op_modulo, &[4.into(), 0.into()]);
+            assert!(matches!(remainder, Value::Number(n) if n.is_nan()));
+
+            let negation = run_op(activation, Activation::op_negate, &[0.into()]);
+            assert!(is_negative_zero(&negation));
+
+            let sum = run_op(activation, Activation::op_increment, &[i32::MAX.into()]);
+            assert!(matches!(sum, Value::Number(n) if n == 2_147_483_648.0));
+        });
+    }
+
+    #[test]
+    fn switch_and_index_of_skip_nan() {
+        with_avm2(|activation| {
+            let nan = Value::Number(f64::NAN);
+
+            // A `switch` compares its subject against each case with
+            // `ifstricteq`, which never jumps for `NaN`.
+            let code = [0u8; 64];
+            let mut reader = Reader::new(Cursor::new(&code[..]));
+            reader.seek(8).unwrap();
+            activation.context.avm2.push(nan.clone());
+            activation.context.avm2.push(nan.clone());
+            activation.op_if_strict_eq(16, &mut reader).unwrap();
+            assert_eq!(reader.seek(0).unwrap(), 8);
+
+            // Nor does a `NaN` case index pick a case.
+            for (index, target) in &[(nan.clone(), 24), (1.into(), 44), ((-1).into(), 24)] {
+                let mut reader = Reader::new(Cursor::new(&code[..]));
+                reader.seek(10).unwrap();
+                activation.context.avm2.push(index.clone());
+                activation
+                    .op_lookup_switch(4, 20, &[30, 40], &mut reader)
+                    .unwrap();
+                assert_eq!(reader.seek(0).unwrap(), *target, "{:?}", index);
+            }
+
+            let mut array = ArrayObject::from_array(
+                ArrayStorage::from_args(&[nan.clone(), 0.into(), Value::Undefined, Value::Null]),
+                activation.avm2().prototypes().array,
+                activation.context.gc_context,
+            );
+            let index_of = array
+                .get_property(
+                    array,
+                    &QName::new(Namespace::as3_namespace(), "indexOf"),
+                    activation,
+                )
+                .unwrap()
+                .coerce_to_object(activation)
+                .unwrap();
+            for (value, index) in &[(nan, -1), ((-0.0).into(), 1), (Value::Null, 3)] {
+                let found = index_of
+                    .call(Some(array), std::slice::from_ref(value), activation, None)
+                    .unwrap();
+                assert_eq!(found, (*index).into(), "{:?}", value);
+            }
+        });
+    }
+
+    #[test]
+    fn arithmetic_result_types_follow_operands() {
+        with_avm2(|activation| {
+            let int_class = activation
+                .avm2()
+                .global_domain()
+                .get_defined_value(activation, QName::new(Namespace::public(), "int"))
+                .unwrap();
+            let uint_class = activation
+                .avm2()
+                .global_domain()
+                .get_defined_value(activation, QName::new(Namespace::public(), "uint"))
+                .unwrap();
+            // Each case lists whether the result is stored as an `int`, and
+            // whether it passes `is int`, as any integral `Number` in range
+            // does.
+            let cases: &[(Opcode<'_, '_, '_>, Value<'_>, Value<'_>, bool, bool)] = &[
+                (Activation::op_add, 2.into(), 3.into(), true, true),
+                (Activation::op_add, 2.0.into(), 3.into(), false, true),
+                (Activation::op_add, i32::MAX.into(), 1.into(), false, false),
+                (Activation::op_subtract, 2.into(), 3.into(), true, true),
+                (
+                    Activation::op_subtract,
+                    i32::MIN.into(),
+                    1.into(),
+                    false,
+                    false,
+                ),
+                (Activation::op_multiply, 6.into(), 7.into(), true, true),
+                (
+                    Activation::op_multiply,
+                    65536.into(),
+                    65536.into(),
+                    false,
+                    false,
+                ),
+                (Activation::op_divide, 6.into(), 3.into(), false, true),
+                (Activation::op_divide, 10.into(), 3.into(), false, false),
+                (Activation::op_modulo, 10.into(), 3.into(), true, true),
+                (Activation::op_modulo, 10.0.into(), 3.into(), false, true),
+            ];
+
+            for (op, value1, value2, stores_int, is_int) in cases.iter().cloned() {
+                let result = run_op(activation, op, &[value1.clone(), value2.clone()]);
+                assert_eq!(
+                    matches!(result, Value::Integer(_)),
+                    stores_int,
+                    "{:?}, {:?} gave {:?}",
+                    value1,
+                    value2,
+                    result
+                );
+
+                let type_name = run_op(
+                    activation,
+                    Activation::op_type_of,
+                    std::slice::from_ref(&result),
+                );
+                assert_eq!(type_name, "number".into());
+
+                let is_type = run_op(
+                    activation,
+                    Activation::op_is_type_late,
+                    &[result.clone(), int_class.clone()],
+                );
+                assert_eq!(is_type, is_int.into(), "{:?} is int", result);
+            }
+
+            // `uint` takes every integer from zero up, whatever holds it.
+            let uint_cases: &[(Value<'_>, bool)] = &[
+                (2.into(), true),
+                ((-1).into(), false),
+                (4_294_967_295.0.into(), true),
+                (4_294_967_296.0.into(), false),
+                (2.5.into(), false),
+                ("2".into(), false),
+            ];
+
+            for (value, is_uint) in uint_cases.iter().cloned() {
+                let is_type = run_op(
+                    activation,
+                    Activation::op_is_type_late,
+                    &[value.clone(), uint_class.clone()],
+                );
+                assert_eq!(is_type, is_uint.into(), "{:?} is uint", value);
+            }
+
+            // An `int` remainder can be masked without leaving `int`.
+            let remainder = run_op(activation, Activation::op_modulo, &[10.into(), 3.into()]);
+            let masked = run_op(activation, Activation::op_bitand, &[remainder, 0xFF.into()]);
+            assert!(matches!(masked, Value::Integer(1)));
+        });
+    }
+
+    #[test]
+    fn bitwise_operators_work_on_wrapped_integers() {
+        with_avm2(|activation| {
+            let not: &[(Value<'_>, i32)] = &[
+                (2.5.into(), -3),
+                ((-2.5).into(), 1),
+                (0.into(), -1),
+                (2_147_483_648.0.into(), i32::MAX),
+                (4_294_967_295.0.into(), 0),
+                (f64::NAN.into(), -1),
+                ("7".into(), -8),
+            ];
+            for (operand, result) in not {
+                let value = run_op(
+                    activation,
+                    Activation::op_bitnot,
+                    std::slice::from_ref(operand),
+                );
+                assert_eq!(value, Value::Integer(*result), "~{:?}", operand);
+            }
+
+            type BinaryOp<'a, 'gc, 'gc_context> = (
+                &'static str,
+                Opcode<'a, 'gc, 'gc_context>,
+                &'static [(f64, f64, i32)],
+            );
+            let binary: &[BinaryOp<'_, '_, '_>] = &[
+                (
+                    "&",
+                    Activation::op_bitand,
+                    &[
+                        (6.9, 3.1, 2),
+                        (-1.5, 255.0, 255),
+                        (4_294_967_295.0, 12.0, 12),
+                        (2_147_483_648.0, -1.0, i32::MIN),
+                    ],
+                ),
+                (
+                    "|",
+                    Activation::op_bitor,
+                    &[
+                        (4.5, 1.9, 5),
+                        (-0.5, 0.0, 0),
+                        (2_147_483_648.0, 1.0, i32::MIN + 1),
+                        (4_294_967_296.0, 3.0, 3),
+                    ],
+                ),
+                (
+                    "^",
+                    Activation::op_bitxor,
+                    &[
+                        (5.7, 3.2, 6),
+                        (-1.0, 0.5, -1),
+                        (4_294_967_295.0, 1.0, -2),
+                        (f64::INFINITY, 9.0, 9),
+                    ],
+                ),
+            ];
+            for (name, op, cases) in binary {
+                for (left, right, result) in cases.iter() {
+                    let value = run_op(activation, *op, &[(*left).into(), (*right).into()]);
+                    assert_eq!(
+                        value,
+                        Value::Integer(*result),
+                        "{} {} {}",
+                        left,
+                        name,
+                        right
+                    );
+                }
+            }
+        });
+    }
+
+    fn noop<'gc>(
+        _activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        Ok(Value::Undefined)
+    }
+
+    /// Build a class deriving from `base`, yielding its class object and
+    /// prototype.
+    fn test_class<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        name: &'static str,
+        base: (&'static str, Object<'gc>),
+        sealed: bool,
+    ) -> (Object<'gc>, Object<'gc>) {
+        let class = Class::new(
+            QName::new(Namespace::public(), name),
+            Some(QName::new(Namespace::public(), base.0).into()),
+            Method::from_builtin(noop),
+            Method::from_builtin(noop),
+            activation.context.gc_context,
+        );
+        if sealed {
+            class
+                .write(activation.context.gc_context)
+                .set_attributes(ClassAttributes::SEALED);
+        }
+
+        let (mut constr, _class_init) =
+            FunctionObject::from_class(activation, class, Some(base.1), None).unwrap();
+        let proto = constr
+            .get_property(
+                constr,
+                &QName::new(Namespace::public(), "prototype"),
+                activation,
+            )
+            .unwrap()
+            .coerce_to_object(activation)
+            .unwrap();
+
+        (constr, proto)
+    }
+
+    #[test]
+    fn unknown_writes_to_sealed_instances_throw() {
+        with_avm2(|activation| {
+            let object_class = activation
+                .avm2()
+                .global_domain()
+                .get_defined_value(activation, QName::new(Namespace::public(), "Object"))
+                .unwrap()
+                .coerce_to_object(activation)
+                .unwrap();
+            let (sealed_class, sealed_proto) =
+                test_class(activation, "Sealed", ("Object", object_class), true);
+            let (_open_class, open_proto) =
+                test_class(activation, "Open", ("Sealed", sealed_class), false);
+            let name = AvmString::new(activation.context.gc_context, "typo");
+
+            let sealed = sealed_proto.construct(activation, &[]).unwrap();
+            let error = ensure_dynamic(sealed, name).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "ReferenceError: Error #1056: Cannot create property typo on Sealed."
+            );
+
+            let open = open_proto.construct(activation, &[]).unwrap();
+            assert!(ensure_dynamic(open, name).is_ok());
+
+            assert!(ensure_dynamic(sealed_proto, name).is_ok());
+            assert!(ensure_dynamic(sealed_class, name).is_ok());
+        });
+    }
+
+    #[test]
+    fn missing_reads_throw_only_on_sealed_instances() {
+        with_avm2(|activation| {
+            let object_class = activation
+                .avm2()
+                .global_domain()
+                .get_defined_value(activation, QName::new(Namespace::public(), "Object"))
+                .unwrap()
+                .coerce_to_object(activation)
+                .unwrap();
+            let (sealed_class, sealed_proto) =
+                test_class(activation, "Sealed", ("Object", object_class), true);
+            let (_open_class, open_proto) =
+                test_class(activation, "Open", ("Sealed", sealed_class), false);
+            let typo = Multiname::from(QName::new(Namespace::public(), "typo"));
+
+            let sealed = sealed_proto.construct(activation, &[]).unwrap();
+            let error = activation.get_resolved_property(sealed, &typo).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "ReferenceError: Error #1069: Property typo not found on Sealed and there is no default value."
+            );
+
+            let open = open_proto.construct(activation, &[]).unwrap();
+            let (name, value) = activation.get_resolved_property(open, &typo).unwrap();
+            assert_eq!(name, None);
+            assert_eq!(value, Value::Undefined);
+
+            let object_proto = activation.avm2().prototypes().object;
+            let plain = object_proto.construct(activation, &[]).unwrap();
+            let (_name, value) = activation.get_resolved_property(plain, &typo).unwrap();
+            assert_eq!(value, Value::Undefined);
+        });
+
+        with_avm2(|activation| {
+            // `Base` is sealed, and never assigns its `int` field `count`.
+            let globals = load_field_initializer_abc(activation);
+            let base = construct_global_class(activation, globals, "Base");
+            let count = Multiname::from(QName::new(Namespace::public(), "count"));
+            let (name, value) = activation.get_resolved_property(base, &count).unwrap();
+            assert_eq!(name, Some(QName::new(Namespace::public(), "count")));
+            assert!(matches!(value, Value::Integer(0)));
+        });
+    }
+
+    #[test]
+    fn delete_spares_inherited_traits() {
+        with_avm2(|activation| {
+            let object_class = activation
+                .avm2()
+                .global_domain()
+                .get_defined_value(activation, QName::new(Namespace::public(), "Object"))
+                .unwrap()
+                .coerce_to_object(activation)
+                .unwrap();
+
+            let base = Class::new(
+                QName::new(Namespace::public(), "SealedBase"),
+                Some(QName::new(Namespace::public(), "Object").into()),
+                Method::from_builtin(noop),
+                Method::from_builtin(noop),
+                activation.context.gc_context,
+            );
+            let mut write = base.write(activation.context.gc_context);
+            write.set_attributes(ClassAttributes::SEALED);
+            write.define_instance_trait(Trait::from_method(
+                QName::new(Namespace::public(), "describe"),
+                Method::from_builtin(noop),
+            ));
+            write.define_instance_trait(Trait::from_slot(
+                QName::new(Namespace::public(), "label"),
+                QName::new(Namespace::public(), "String").into(),
+                None,
+            ));
+            drop(write);
+            let (base_class, _class_init) =
+                FunctionObject::from_class(activation, base, Some(object_class), None).unwrap();
+            let (_dynamic_class, dynamic_proto) =
+                test_class(activation, "DynamicSub", ("SealedBase", base_class), false);
+
+            let mut object = dynamic_proto.construct(activation, &[]).unwrap();
+            object
+                .set_property(
+                    object,
+                    &QName::new(Namespace::public(), "extra"),
+                    1.into(),
+                    activation,
+                )
+                .unwrap();
+
+            for (name, deleted) in &[
+                ("describe", false),
+                ("label", false),
+                ("extra", true),
+                ("missing", true),
+            ] {
+                let multiname = QName::new(Namespace::public(), *name).into();
+                assert_eq!(
+                    activation.delete_multiname(object, &multiname).unwrap(),
+                    *deleted,
+                    "delete {}",
+                    name
+                );
+            }
+
+            let extra = object
+                .get_property(
+                    object,
+                    &QName::new(Namespace::public(), "extra"),
+                    activation,
+                )
+                .unwrap();
+            assert_eq!(extra, Value::Undefined);
+            assert!(object
+                .has_property(&QName::new(Namespace::public(), "describe"))
+                .unwrap());
+        });
+    }
+
+    /// A base constructor that calls `this.describe()`.
+    fn describing_init<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let mut this = this.unwrap();
+        activation.super_init(this, &[])?;
+
+        this.get_property(
+            this,
+            &QName::new(Namespace::public(), "describe"),
+            activation,
+        )?
+        .coerce_to_object(activation)?
+        .call(Some(this), &[], activation, None)?;
+
+        Ok(Value::Undefined)
+    }
+
+    /// An override of `describe` that traces `this.count`.
+    fn describe_count<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let mut this = this.unwrap();
+        let count = this
+            .get_property(this, &QName::new(Namespace::public(), "count"), activation)?
+            .coerce_to_string(activation)?;
+
+        activation.context.log.avm_trace(&count);
+
+        Ok(Value::Undefined)
+    }
+
+    /// A subclass constructor that sets `this.count` once its base is built.
+    fn counting_init<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let mut this = this.unwrap();
+        activation.super_init(this, &[])?;
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), "count"),
+            5.into(),
+            activation,
+        )?;
+
+        Ok(Value::Undefined)
+    }
+
+    #[test]
+    fn base_constructors_dispatch_to_overrides() {
+        let traces = trace_with_avm2(|activation| {
+            let object_class = activation
+                .avm2()
+                .global_domain()
+                .get_defined_value(activation, QName::new(Namespace::public(), "Object"))
+                .unwrap()
+                .coerce_to_object(activation)
+                .unwrap();
+
+            let base = Class::new(
+                QName::new(Namespace::public(), "Describable"),
+                Some(QName::new(Namespace::public(), "Object").into()),
+                Method::from_builtin(describing_init),
+                Method::from_builtin(noop),
+                activation.context.gc_context,
+            );
+            base.write(activation.context.gc_context)
+                .define_instance_trait(Trait::from_method(
+                    QName::new(Namespace::public(), "describe"),
+                    Method::from_builtin(noop),
+                ));
+            let (base_class, _class_init) =
+                FunctionObject::from_class(activation, base, Some(object_class), None).unwrap();
+
+            let derived = Class::new(
+                QName::new(Namespace::public(), "Counter"),
+                Some(QName::new(Namespace::public(), "Describable").into()),
+                Method::from_builtin(counting_init),
+                Method::from_builtin(noop),
+                activation.context.gc_context,
+            );
+            let mut write = derived.write(activation.context.gc_context);
+            write.define_instance_trait(Trait::from_slot(
+                QName::new(Namespace::public(), "count"),
+                QName::new(Namespace::public(), "int").into(),
+                Some(0.into()),
+            ));
+            let mut describe = Trait::from_method(
+                QName::new(Namespace::public(), "describe"),
+                Method::from_builtin(describe_count),
+            );
+            describe.set_attributes(TraitAttributes::OVERRIDE);
+            write.define_instance_trait(describe);
+            drop(write);
+            let (mut derived_class, _class_init) =
+                FunctionObject::from_class(activation, derived, Some(base_class), None).unwrap();
+
+            let proto = derived_class
+                .get_property(
+                    derived_class,
+                    &QName::new(Namespace::public(), "prototype"),
+                    activation,
+                )
+                .unwrap()
+                .coerce_to_object(activation)
+                .unwrap();
+            let mut object = proto.construct(activation, &[]).unwrap();
+            derived_class
+                .call(Some(object), &[], activation, Some(proto))
+                .unwrap();
+
+            // The override ran from the base constructor, before `Counter`'s
+            // own constructor body set `count`.
+            let count = object
+                .get_property(
+                    object,
+                    &QName::new(Namespace::public(), "count"),
+                    activation,
+                )
+                .unwrap();
+            assert_eq!(count, 5.into());
+        });
+
+        assert_eq!(traces, vec!["0".to_string()]);
+    }
+
+    #[test]
+    fn new_array_keeps_authored_order() {
+        with_avm2(|activation| {
+            let array = run_op(
+                activation,
+                |activation| activation.op_new_array(3),
+                &["a".into(), "b".into(), "c".into()],
+            );
+            let storage = array
+                .coerce_to_object(activation)
+                .unwrap()
+                .as_array_storage()
+                .map(|storage| {
+                    storage
+                        .iter()
+                        .map(|value| value.unwrap_or(Value::Undefined))
+                        .collect::<Vec<_>>()
+                });
+            assert_eq!(storage, Some(vec!["a".into(), "b".into(), "c".into()]));
+
+            activation.context.avm2.push("below");
+            let empty = run_op(activation, |activation| activation.op_new_array(0), &[]);
+            let length = empty
+                .coerce_to_object(activation)
+                .unwrap()
+                .as_array_storage()
+                .map(|storage| storage.length());
+            assert_eq!(length, Some(0));
+            assert_eq!(activation.context.avm2.pop(), "below".into());
+        });
+    }
+
+    #[test]
+    fn domain_memory_round_trips() {
+        with_avm2(|activation| {
+            run_store(activation, Activation::op_si32, (-2).into(), 8).unwrap();
+            let int = run_op(activation, Activation::op_li32, &[8.into()]);
+            assert_eq!(int, Value::Integer(-2));
+            let low_byte = run_op(activation, Activation::op_li8, &[8.into()]);
+            assert_eq!(low_byte, Value::Integer(0xFE));
+
+            run_store(activation, Activation::op_si16, 0x1_2345.into(), 16).unwrap();
+            let short = run_op(activation, Activation::op_li16, &[16.into()]);
+            assert_eq!(short, Value::Integer(0x2345));
+
+            run_store(activation, Activation::op_sf64, 0.1.into(), 24).unwrap();
+            let double = run_op(activation, Activation::op_lf64, &[24.into()]);
+            assert_eq!(double, Value::Number(0.1));
+
+            run_store(activation, Activation::op_sf32, 0.5.into(), 1020).unwrap();
+            let float = run_op(activation, Activation::op_lf32, &[1020.into()]);
+            assert_eq!(float, Value::Number(0.5));
+
+            let error = run_store(activation, Activation::op_si32, 1.into(), 1021).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "RangeError: Error #1506: The specified range is invalid."
+            );
+            activation.context.avm2.push(Value::Integer(-1));
+            assert!(Activation::op_li8(activation).is_err());
+
+            let memory = activation.avm2().global_domain().domain_memory().unwrap();
+            assert_eq!(
+                memory.as_bytearray().map(|bytes| bytes.len()),
+                Some(MIN_DOMAIN_MEMORY_LENGTH)
+            );
+        });
+    }
+
+    /// Run a `lookupswitch` at byte 4 of a method with three cases, returning
+    /// the position it branches to.
+    fn run_lookup_switch<'gc>(activation: &mut Activation<'_, 'gc, '_>, index: Value<'gc>) -> u64 {
+        let code = [0; 64];
+        let mut reader = Reader::new(Cursor::new(&code[..]));
+        reader.seek(14).unwrap();
+
+        activation.context.avm2.push(index);
+        activation
+            .op_lookup_switch(4, 40, &[20, 24, 28], &mut reader)
+            .unwrap();
+
+        reader.seek(0).unwrap()
+    }
+
+    #[test]
+    fn lookup_switch_selects_integer_cases() {
+        with_avm2(|activation| {
+            assert_eq!(run_lookup_switch(activation, 0.into()), 24);
+            assert_eq!(run_lookup_switch(activation, 2.into()), 32);
+            assert_eq!(run_lookup_switch(activation, 1.0.into()), 28);
+            assert_eq!(run_lookup_switch(activation, "1".into()), 28);
+
+            // Anything else falls through to the default.
+            assert_eq!(run_lookup_switch(activation, 3.into()), 44);
+            assert_eq!(run_lookup_switch(activation, (-1).into()), 44);
+            assert_eq!(run_lookup_switch(activation, 1.5.into()), 44);
+            assert_eq!(run_lookup_switch(activation, f64::NAN.into()), 44);
+            assert_eq!(run_lookup_switch(activation, Value::Undefined), 44);
+        });
+    }
+
+    #[test]
+    fn construct_runs_plain_functions_on_their_prototype() {
+        with_avm2(|activation| {
+            let x = QName::new(Namespace::public(), "x");
+            let mut ctor = FunctionObject::from_builtin(
+                activation.context.gc_context,
+                set_x,
+                activation.avm2().prototypes().function,
+            );
+            let mut proto = ScriptObject::object(
+                activation.context.gc_context,
+                activation.avm2().prototypes().object,
+            );
+            ctor.set_property(
+                ctor,
+                &QName::new(Namespace::public(), "prototype"),
+                proto.into(),
+                activation,
+            )
+            .unwrap();
+            proto
+                .set_property(
+                    proto,
+                    &QName::new(Namespace::public(), "y"),
+                    2.into(),
+                    activation,
+                )
+                .unwrap();
+
+            // A non-object return value is ignored.
+            let mut object = run_op(
+                activation,
+                |activation| activation.op_construct(2),
+                &[ctor.into(), 1.into(), "ignored".into()],
+            )
+            .coerce_to_object(activation)
+            .unwrap();
+            assert!(object
+                .proto()
+                .map(|p| Object::ptr_eq(p, proto))
+                .unwrap_or(false));
+            assert_eq!(
+                object.get_property(object, &x, activation).unwrap(),
+                1.into()
+            );
+            assert_eq!(
+                object
+                    .get_property(object, &QName::new(Namespace::public(), "y"), activation)
+                    .unwrap(),
+                2.into()
+            );
+
+            // An object return value replaces the new instance.
+            let replacement = ScriptObject::object(
+                activation.context.gc_context,
+                activation.avm2().prototypes().object,
+            );
+            let constructed = run_op(
+                activation,
+                |activation| activation.op_construct(2),
+                &[ctor.into(), 1.into(), replacement.into()],
+            );
+            assert_eq!(constructed, replacement.into());
+        });
+    }
+
+    /// Call `hasOwnProperty` on a value as `callproperty` would, boxing it
+    /// first if it is a primitive.
+    fn has_own_property<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        value: Value<'gc>,
+        name: &'static str,
+    ) -> Value<'gc> {
+        let mut object = value.coerce_to_object(activation).unwrap();
+        object
+            .get_property(
+                object,
+                &QName::new(Namespace::as3_namespace(), "hasOwnProperty"),
+                activation,
+            )
+            .unwrap()
+            .coerce_to_object(activation)
+            .unwrap()
+            .call(Some(object), &[name.into()], activation, None)
+            .unwrap()
+    }
+
+    #[test]
+    fn number_primitives_see_their_prototype() {
+        with_avm2(|activation| {
+            for number in &[Value::Integer(5), Value::Unsigned(5), Value::Number(5.5)] {
+                for name in &["toString", "valueOf", "toLocaleString"] {
+                    assert_eq!(
+                        run_op(
+                            activation,
+                            Activation::op_in,
+                            &[(*name).into(), number.clone()]
+                        ),
+                        true.into(),
+                        "{:?} in {:?}",
+                        name,
+                        number
+                    );
+                    assert_eq!(
+                        has_own_property(activation, number.clone(), name),
+                        false.into(),
+                        "{:?}.hasOwnProperty({:?})",
+                        number,
+                        name
+                    );
+                }
+
+                assert_eq!(
+                    run_op(
+                        activation,
+                        Activation::op_in,
+                        &["missing".into(), number.clone()]
+                    ),
+                    false.into()
+                );
+            }
+        });
+    }
+
+    #[test]
+    fn null_and_undefined_receivers_throw_flash_errors() {
+        with_avm2(|activation| {
+            for (receiver, expected) in &[
+                (
+                    Value::Null,
+                    "TypeError: Error #1009: Cannot access a property or method of a null object reference.",
+                ),
+                (
+                    Value::Undefined,
+                    "TypeError: Error #1010: A term is undefined and has no properties.",
+                ),
+            ] {
+                activation.context.avm2.push(receiver.clone());
+                let error = activation.pop_receiver().unwrap_err();
+                assert_eq!(error.to_string(), *expected);
+            }
+
+            activation.context.avm2.push(5);
+            let boxed = activation.pop_receiver().unwrap();
+            assert_eq!(
+                boxed.value_of(activation.context.gc_context).unwrap(),
+                5.into()
+            );
+        });
+    }
+
+    /// A function whose `prototype` is `proto`.
+    fn constructor<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        proto: Object<'gc>,
+    ) -> Object<'gc> {
+        let fn_proto = activation.context.avm2.prototypes().function;
+        let mut function =
+            FunctionObject::from_builtin(activation.context.gc_context, noop, fn_proto);
+        function
+            .set_property(
+                function,
+                &QName::dynamic_name("prototype"),
+                proto.into(),
+                activation,
+            )
+            .unwrap();
+
+        function
+    }
+
+    fn instance_of<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        value: Value<'gc>,
+        type_object: Value<'gc>,
+    ) -> Result<Value<'gc>, Error> {
+        activation.context.avm2.push(value);
+        activation.context.avm2.push(type_object);
+        activation.op_instance_of()?;
+
+        Ok(activation.context.avm2.pop())
+    }
+
+    #[test]
+    fn instance_of_walks_function_prototypes() {
+        with_avm2(|activation| {
+            let mc = activation.context.gc_context;
+            let object_proto = activation.context.avm2.prototypes().object;
+
+            // `function Shape() {}` and `function Circle() {}`, with
+            // `Circle.prototype = new Shape()`.
+            let shape_proto = ScriptObject::object(mc, object_proto);
+            let circle_proto = ScriptObject::object(mc, shape_proto);
+            let shape = constructor(activation, shape_proto);
+            let circle = constructor(activation, circle_proto);
+            let unrelated = constructor(activation, ScriptObject::object(mc, object_proto));
+            let instance = ScriptObject::object(mc, circle_proto);
+
+            for (type_object, expected) in &[(circle, true), (shape, true), (unrelated, false)] {
+                assert_eq!(
+                    instance_of(activation, instance.into(), (*type_object).into()).unwrap(),
+                    (*expected).into()
+                );
+            }
+            assert_eq!(
+                instance_of(activation, Value::Null, shape.into()).unwrap(),
+                false.into()
+            );
+
+            for type_object in &[Value::Integer(5), instance.into()] {
+                let error =
+                    instance_of(activation, instance.into(), type_object.clone()).unwrap_err();
+                assert_eq!(
+                    error.to_string(),
+                    "TypeError: Error #1040: The right-hand side of instanceof must be a class or function."
+                );
+            }
+        });
+    }
+
+    #[test]
+    fn escape_opcodes_escape_for_their_context() {
+        with_avm2(|activation| {
+            let value: Value<'_> = "a & b < c > \"d\"\n'e'".into();
+
+            assert_eq!(
+                run_op(
+                    activation,
+                    Activation::op_esc_xelem,
+                    std::slice::from_ref(&value)
+                ),
+                "a &amp; b &lt; c &gt; \"d\"\n'e'".into()
+            );
+            assert_eq!(
+                run_op(activation, Activation::op_esc_xattr, &[value]),
+                "a &amp; b &lt; c > &quot;d&quot;&#xA;'e'".into()
+            );
+
+            // Other values are escaped as their string form.
+            assert_eq!(
+                run_op(activation, Activation::op_esc_xelem, &[Value::Integer(-5)]),
+                "-5".into()
+            );
+            assert_eq!(
+                run_op(activation, Activation::op_esc_xattr, &[Value::Null]),
+                "null".into()
+            );
+        });
+    }
+
+    #[test]
+    fn concatenation_leaves_earlier_strings_alone() {
+        with_avm2(|activation| {
+            let mut links = vec![Value::from("a")];
+            for piece in &["b", "c", "d"] {
+                let tip = links.last().cloned().unwrap();
+                links.push(run_op(
+                    activation,
+                    Activation::op_add,
+                    &[tip, (*piece).into()],
+                ));
+            }
+
+            // Build on a link that already has something built on it, both
+            // before and after it has been read.
+            let branched = run_op(
+                activation,
+                Activation::op_add,
+                &[links[1].clone(), "x".into()],
+            );
+            assert_eq!(links[2], "abc".into());
+            let rebranched = run_op(
+                activation,
+                Activation::op_add,
+                &[links[2].clone(), "y".into()],
+            );
+
+            assert_eq!(
+                links,
+                vec!["a".into(), "ab".into(), "abc".into(), "abcd".into()]
+            );
+            assert_eq!(branched, "abx".into());
+            assert_eq!(rebranched, "abcy".into());
+
+            // Append strings to themselves, read and unread.
+            let doubled = run_op(
+                activation,
+                Activation::op_add,
+                &[links[3].clone(), links[3].clone()],
+            );
+            let unread = run_op(
+                activation,
+                Activation::op_add,
+                &[branched.clone(), "!".into()],
+            );
+            let unread_doubled = run_op(
+                activation,
+                Activation::op_add,
+                &[unread.clone(), unread.clone()],
+            );
+
+            assert_eq!(doubled, "abcdabcd".into());
+            assert_eq!(unread_doubled, "abx!abx!".into());
+            assert_eq!(unread, "abx!".into());
+            assert_eq!(links[3], "abcd".into());
+        });
+    }
+
+    #[test]
+    fn long_concatenation_chains_flatten() {
+        with_avm2(|activation| {
+            let mut appended = Value::from("");
+            let mut prepended = Value::from("");
+            for i in 0..100_000 {
+                appended = run_op(activation, Activation::op_add, &[appended, (i % 10).into()]);
+                prepended = run_op(
+                    activation,
+                    Activation::op_add,
+                    &[(i % 10).into(), prepended],
+                );
+            }
+
+            let appended = appended.coerce_to_string(activation).unwrap();
+            let prepended = prepended.coerce_to_string(activation).unwrap();
+            assert_eq!(appended.len(), 100_000);
+            assert!(appended.starts_with("0123456789"));
+            assert_eq!(prepended.len(), 100_000);
+            assert!(prepended.starts_with("9876543210"));
+        });
+    }
+
+    /// A `nextNameIndex` trap that visits the indices 1, 4 and 9.
+    fn sparse_next_name_index<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        Ok(match args[0].coerce_to_u32(activation)? {
+            0 => 1.into(),
+            1 => 4.into(),
+            4 => 9.into(),
+            _ => 0.into(),
+        })
+    }
+
+    fn sparse_next_name<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let index = args[0].coerce_to_u32(activation)?;
+
+        Ok(AvmString::new(activation.context.gc_context, format!("key{}", index)).into())
+    }
+
+    fn sparse_next_value<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        Ok((args[0].coerce_to_u32(activation)? * 10).into())
+    }
+
+    #[test]
+    fn proxies_are_enumerated_through_their_traps() {
+        with_avm2(|activation| {
+            let mc = activation.context.gc_context;
+            let proxy_proto = activation.context.avm2.prototypes().proxy;
+
+            // A `Proxy` subclass that overrides every enumeration trap.
+            let proxy_class = activation
+                .avm2()
+                .global_domain()
+                .get_defined_value(
+                    activation,
+                    QName::new(Namespace::package("flash.utils"), "Proxy"),
+                )
+                .unwrap()
+                .coerce_to_object(activation)
+                .unwrap();
+            let class = Class::new(
+                QName::new(Namespace::public(), "SparseProxy"),
+                Some(QName::new(Namespace::package("flash.utils"), "Proxy").into()),
+                Method::from_builtin(noop),
+                Method::from_builtin(noop),
+                mc,
+            );
+            for (name, trap) in &[
+                ("nextNameIndex", sparse_next_name_index as NativeMethod<'_>),
+                ("nextName", sparse_next_name),
+                ("nextValue", sparse_next_value),
+            ] {
+                let mut trait_ = Trait::from_method(
+                    QName::new(Namespace::flash_proxy(), *name),
+                    Method::from_builtin(*trap),
+                );
+                trait_.set_attributes(TraitAttributes::OVERRIDE);
+                class.write(mc).define_instance_trait(trait_);
+            }
+            let (mut constr, _class_init) =
+                FunctionObject::from_class(activation, class, Some(proxy_class), None).unwrap();
+            let subclass_proto = constr
+                .get_property(
+                    constr,
+                    &QName::new(Namespace::public(), "prototype"),
+                    activation,
+                )
+                .unwrap()
+                .coerce_to_object(activation)
+                .unwrap();
+            let proxy = subclass_proto.construct(activation, &[]).unwrap();
+
+            let mut visited = vec![];
+            let mut index = Value::from(0);
+            loop {
+                index = run_op(
+                    activation,
+                    Activation::op_has_next,
+                    &[proxy.into(), index.clone()],
+                );
+                if index == 0.into() {
+                    break;
+                }
+
+                let name = run_op(
+                    activation,
+                    Activation::op_next_name,
+                    &[proxy.into(), index.clone()],
+                );
+                let value = run_op(
+                    activation,
+                    Activation::op_next_value,
+                    &[proxy.into(), index.clone()],
+                );
+                visited.push((
+                    name.coerce_to_string(activation).unwrap().to_string(),
+                    value,
+                ));
+            }
+
+            assert_eq!(
+                visited,
+                vec![
+                    ("key1".to_string(), 10.into()),
+                    ("key4".to_string(), 40.into()),
+                    ("key9".to_string(), 90.into()),
+                ]
+            );
+
+            // `Proxy` itself has no enumeration to offer.
+            let bare = ScriptObject::object(mc, proxy_proto);
+            activation.context.avm2.push(bare);
+            activation.context.avm2.push(0);
+            let error = activation.op_has_next().unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "IllegalOperationError: Error #2088: The Proxy class does not implement nextNameIndex. It must be overridden by a subclass."
+            );
+        });
+    }
+
+    #[test]
+    fn check_filter_only_accepts_xml_values() {
+        with_avm2(|activation| {
+            let mc = activation.context.gc_context;
+            let xml = ScriptObject::object(mc, activation.context.avm2.prototypes().xml);
+            let xml_list = ScriptObject::object(mc, activation.context.avm2.prototypes().xml_list);
+
+            for value in &[xml, xml_list] {
+                let checked = run_op(activation, Activation::op_check_filter, &[(*value).into()]);
+                assert!(matches!(checked, Value::Object(o) if Object::ptr_eq(o, *value)));
+            }
+
+            let object = ScriptObject::object(mc, activation.context.avm2.prototypes().object);
+            for (value, type_name) in &[
+                (object.into(), "Object"),
+                (Value::Integer(5), "int"),
+                ("<a/>".into(), "String"),
+                (Value::Null, "null"),
+            ] {
+                activation.context.avm2.push(value.clone());
+                let error = activation.op_check_filter().unwrap_err();
+                assert_eq!(
+                    error.to_string(),
+                    format!(
+                        "TypeError: Error #1123: Filter operator not supported on type {}.",
+                        type_name
+                    )
+                );
+            }
+        });
+    }
+
+    /// A `valueOf` that yields `this.time`, standing in for `Date`'s.
+    fn time_value_of<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let mut this = this.unwrap();
+        this.get_property(this, &QName::dynamic_name("time"), activation)
+    }
+
+    fn time_to_string<'gc>(
+        _activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        Ok("a moment".into())
+    }
+
+    /// An object whose `valueOf` is the millisecond timestamp `time`.
+    fn timestamp<'gc>(activation: &mut Activation<'_, 'gc, '_>, time: f64) -> Object<'gc> {
+        let mc = activation.context.gc_context;
+        let object_proto = activation.context.avm2.prototypes().object;
+        let fn_proto = activation.context.avm2.prototypes().function;
+        let mut object = ScriptObject::object(mc, object_proto);
+
+        for (name, value) in &[
+            (
+                "valueOf",
+                FunctionObject::from_builtin(mc, time_value_of, fn_proto).into(),
+            ),
+            (
+                "toString",
+                FunctionObject::from_builtin(mc, time_to_string, fn_proto).into(),
+            ),
+            ("time", Value::Number(time)),
+        ] {
+            object
+                .set_property(
+                    object,
+                    &QName::dynamic_name(*name),
+                    value.clone(),
+                    activation,
+                )
+                .unwrap();
+        }
+
+        object
+    }
+
+    #[test]
+    fn numeric_coercion_uses_value_of() {
+        with_avm2(|activation| {
+            let earlier = timestamp(activation, 1_600_000_000_000.0);
+            let later = timestamp(activation, 1_600_000_090_000.0);
+
+            assert_eq!(
+                Value::from(later).coerce_to_number(activation).unwrap(),
+                1_600_000_090_000.0
+            );
+            assert!(matches!(
+                run_op(activation, Activation::op_subtract, &[later.into(), earlier.into()]),
+                Value::Number(n) if n == 90_000.0
+            ));
+
+            // Adding a string concatenates, so coerces with `toString` instead.
+            let sum = run_op(activation, Activation::op_add, &[later.into(), "!".into()]);
+            assert_eq!(sum.coerce_to_string(activation).unwrap(), "a moment!");
+        });
+    }
+
+    #[test]
+    fn to_primitive_orders_value_of_and_to_string_by_hint() {
+        with_avm2(|activation| {
+            let moment = Value::from(timestamp(activation, 5.0));
+
+            assert_eq!(
+                moment.coerce_to_primitive(None, activation).unwrap(),
+                Value::Number(5.0)
+            );
+            assert_eq!(
+                moment
+                    .coerce_to_primitive(Some(Hint::Number), activation)
+                    .unwrap(),
+                Value::Number(5.0)
+            );
+            assert_eq!(
+                moment
+                    .coerce_to_primitive(Some(Hint::String), activation)
+                    .unwrap(),
+                "a moment".into()
+            );
+
+            // Adding to a string uses the string hint, but adding to anything
+            // else uses the object's default hint.
+            let sum = run_op(
+                activation,
+                Activation::op_add,
+                &["at ".into(), moment.clone()],
+            );
+            assert_eq!(sum.coerce_to_string(activation).unwrap(), "at a moment");
+            let sum = run_op(
+                activation,
+                Activation::op_add,
+                &[moment.clone(), moment.clone()],
+            );
+            assert!(matches!(sum, Value::Number(n) if n == 10.0));
+
+            // Comparisons always use the number hint.
+            assert_eq!(
+                moment.abstract_lt(&"6".into(), activation).unwrap(),
+                Some(true)
+            );
+        });
+    }
+
+    fn prototype_to_string<'gc>(
+        _activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        Ok("from the prototype".into())
+    }
+
+    fn trait_to_string<'gc>(
+        _activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        Ok("from the class".into())
+    }
+
+    /// Build a sealed class, optionally declaring a `toString` method, and
+    /// assign another `toString` to its prototype.
+    fn class_with_prototype_to_string<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        name: &'static str,
+        declares_to_string: bool,
+    ) -> Object<'gc> {
+        let class = Class::new(
+            QName::new(Namespace::public(), name),
+            Some(QName::new(Namespace::public(), "Object").into()),
+            Method::from_builtin(noop),
+            Method::from_builtin(noop),
+            activation.context.gc_context,
+        );
+        let mut write = class.write(activation.context.gc_context);
+        write.set_attributes(ClassAttributes::SEALED);
+        if declares_to_string {
+            write.define_instance_trait(Trait::from_method(
+                QName::new(Namespace::public(), "toString"),
+                Method::from_builtin(trait_to_string),
+            ));
+        }
+        drop(write);
+
+        let object_class = activation
+            .avm2()
+            .global_domain()
+            .get_defined_value(activation, QName::new(Namespace::public(), "Object"))
+            .unwrap()
+            .coerce_to_object(activation)
+            .unwrap();
+        let (mut constr, _class_init) =
+            FunctionObject::from_class(activation, class, Some(object_class), None).unwrap();
+        let mut proto = constr
+            .get_property(
+                constr,
+                &QName::new(Namespace::public(), "prototype"),
+                activation,
+            )
+            .unwrap()
+            .coerce_to_object(activation)
+            .unwrap();
+        let to_string = FunctionObject::from_builtin(
+            activation.context.gc_context,
+            prototype_to_string,
+            activation.avm2().prototypes().function,
+        );
+        proto
+            .set_property(
+                proto,
+                &QName::new(Namespace::public(), "toString"),
+                to_string.into(),
+                activation,
+            )
+            .unwrap();
+
+        proto.construct(activation, &[]).unwrap()
+    }
+
+    #[test]
+    fn prototype_to_string_is_used_by_coercion() {
+        with_avm2(|activation| {
+            let object = class_with_prototype_to_string(activation, "Assigned", false);
+            let sum = run_op(
+                activation,
+                Activation::op_add,
+                &["made ".into(), object.into()],
+            );
+            assert_eq!(
+                sum.coerce_to_string(activation).unwrap(),
+                "made from the prototype"
+            );
+
+            // A method declared by the class wins over the prototype.
+            let object = class_with_prototype_to_string(activation, "Declared", true);
+            let sum = run_op(
+                activation,
+                Activation::op_add,
+                &["made ".into(), object.into()],
+            );
+            assert_eq!(
+                sum.coerce_to_string(activation).unwrap(),
+                "made from the class"
+            );
+        });
+    }
+
+    /// An ABC file with two classes. `Base` initializes its `label` field to
+    /// `this.describe()` before calling its super constructor, then copies
+    /// `label` into `seen` as its constructor body's first statement. `Sub`
+    /// extends it and overrides `describe`.
+    fn field_initializer_abc() -> swf::avm2::types::AbcFile {
+        use std::marker::PhantomData;
+        use swf::avm2::types::{
+            AbcFile, Class as AbcClass, ConstantPool, Instance as AbcInstance, Method as AbcMethod,
+            MethodBody as AbcMethodBody, Multiname as AbcMultiname, Namespace as AbcNamespace,
+            Script as AbcScript, Trait as AbcTrait, TraitKind as AbcTraitKind,
+        };
+
+        let method = AbcMethod {
+            name: Index(0, PhantomData),
+            params: vec![],
+            return_type: Index(0, PhantomData),
+            needs_arguments_object: false,
+            needs_activation: false,
+            needs_rest: false,
+            needs_dxns: false,
+        };
+        let body = |method, code| AbcMethodBody {
+            method: Index(method, PhantomData),
+            max_stack: 3,
+            num_locals: 1,
+            init_scope_depth: 0,
+            max_scope_depth: 0,
+            code,
+            exceptions: vec![],
+            traits: vec![],
+        };
+        let qname = |name| AbcMultiname::QName {
+            namespace: Index(1, PhantomData),
+            name: Index(name, PhantomData),
+        };
+        let slot = |name, type_name| AbcTrait {
+            name: Index(name, PhantomData),
+            kind: AbcTraitKind::Slot {
+                slot_id: 0,
+                type_name: Index(type_name, PhantomData),
+                value: None,
+            },
+            metadata: vec![],
+            is_final: false,
+            is_override: false,
+        };
+        let describe = |method, is_override| AbcTrait {
+            name: Index(5, PhantomData),
+            kind: AbcTraitKind::Method {
+                disp_id: 0,
+                method: Index(method, PhantomData),
+            },
+            metadata: vec![],
+            is_final: false,
+            is_override,
+        };
+        let class = |name, slot_id| AbcTrait {
+            name: Index(name, PhantomData),
+            kind: AbcTraitKind::Class {
+                slot_id,
+                class: Index(slot_id - 1, PhantomData),
+            },
+            metadata: vec![],
+            is_final: false,
+            is_override: false,
+        };
+        let instance = |name, super_name, init_method, traits| AbcInstance {
+            name: Index(name, PhantomData),
+            super_name: Index(super_name, PhantomData),
+            is_sealed: true,
+            is_final: false,
+            is_interface: false,
+            protected_namespace: None,
+            interfaces: vec![],
+            init_method: Index(init_method, PhantomData),
+            traits,
+        };
+
+        AbcFile {
+            major_version: 46,
+            minor_version: 16,
+            constant_pool: ConstantPool {
+                ints: vec![],
+                uints: vec![],
+                doubles: vec![],
+                strings: [
+                    "", "Base", "Sub", "label", "seen", "describe", "base", "sub", "Object",
+                    "String", "count", "int",
+                ]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+                namespaces: vec![AbcNamespace::Package(Index(1, PhantomData))],
+                namespace_sets: vec![],
+                multinames: [2, 3, 4, 5, 6, 9, 10, 11, 12]
+                    .iter()
+                    .map(|name| qname(*name))
+                    .collect(),
+            },
+            methods: vec![method; 7],
+            metadata: vec![],
+            instances: vec![
+                instance(
+                    1,
+                    6,
+                    1,
+                    vec![slot(3, 7), slot(4, 0), slot(8, 9), describe(3, false)],
+                ),
+                instance(2, 1, 4, vec![describe(6, true)]),
+            ],
+            classes: vec![
+                AbcClass {
+                    init_method: Index(2, PhantomData),
+                    traits: vec![],
+                },
+                AbcClass {
+                    init_method: Index(5, PhantomData),
+                    traits: vec![],
+                },
+            ],
+            scripts: vec![AbcScript {
+                init_method: Index(0, PhantomData),
+                traits: vec![class(1, 1), class(2, 2)],
+            }],
+            method_bodies: vec![
+                // returnvoid
+                body(0, vec![0x47]),
+                // getlocal0; getlocal0; callproperty describe 0; initproperty label;
+                // getlocal0; constructsuper 0;
+                // getlocal0; getlocal0; getproperty label; setproperty seen;
+                // returnvoid
+                body(
+                    1,
+                    vec![
+                        0xD0, 0xD0, 0x46, 0x05, 0x00, 0x68, 0x03, 0xD0, 0x49, 0x00, 0xD0, 0xD0,
+                        0x66, 0x03, 0x61, 0x04, 0x47,
+                    ],
+                ),
+                body(2, vec![0x47]),
+                // pushstring "base"; returnvalue
+                body(3, vec![0x2C, 0x07, 0x48]),
+                // getlocal0; constructsuper 0; returnvoid
+                body(4, vec![0xD0, 0x49, 0x00, 0x47]),
+                body(5, vec![0x47]),
+                // pushstring "sub"; returnvalue
+                body(6, vec![0x2C, 0x08, 0x48]),
+            ],
+        }
+    }
+
+    /// Load an ABC file and get its first script's global object.
+    fn load_abc<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        abc: swf::avm2::types::AbcFile,
+    ) -> Result<Object<'gc>, Error> {
+        let domain = activation.avm2().global_domain();
+        let tunit =
+            TranslationUnit::from_abc(std::rc::Rc::new(abc), domain, activation.context.gc_context);
+        let mut script =
+            tunit.load_script(0, activation.context.avm2, activation.context.gc_context)?;
+
+        script.globals(&mut activation.context)
+    }
+
+    /// Load `field_initializer_abc` and get its script's global object.
+    fn load_field_initializer_abc<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Object<'gc> {
+        load_abc(activation, field_initializer_abc()).unwrap()
+    }
+
+    /// Construct one of the classes defined on `globals`.
+    fn construct_global_class<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        mut globals: Object<'gc>,
+        class_name: &'static str,
+    ) -> Object<'gc> {
+        let mut constr = globals
+            .get_property(
+                globals,
+                &QName::new(Namespace::public(), class_name),
+                activation,
+            )
+            .unwrap()
+            .coerce_to_object(activation)
+            .unwrap();
+        let proto = constr
+            .get_property(
+                constr,
+                &QName::new(Namespace::public(), "prototype"),
+                activation,
+            )
+            .unwrap()
+            .coerce_to_object(activation)
+            .unwrap();
+        let instance = proto.construct(activation, &[]).unwrap();
+
+        constr
+            .call(Some(instance), &[], activation, Some(proto))
+            .unwrap();
+
+        instance
+    }
+
+    #[test]
+    fn field_initializers_run_before_the_constructor_body() {
+        with_avm2(|activation| {
+            let globals = load_field_initializer_abc(activation);
+            let mut base = construct_global_class(activation, globals, "Base");
+            for (name, expected) in &[
+                ("label", Value::from("base")),
+                ("seen", "base".into()),
+                ("count", 0.into()),
+            ] {
+                let value = base
+                    .get_property(base, &QName::new(Namespace::public(), *name), activation)
+                    .unwrap();
+                assert_eq!(value, *expected, "{}", name);
+            }
+
+            // The initializer calls the subclass's override.
+            let mut sub = construct_global_class(activation, globals, "Sub");
+            let seen = sub
+                .get_property(sub, &QName::new(Namespace::public(), "seen"), activation)
+                .unwrap();
+            assert_eq!(seen, "sub".into());
+        });
+    }
+
+    #[test]
+    fn overrides_replace_the_inherited_method() {
+        with_avm2(|activation| {
+            let globals = load_field_initializer_abc(activation);
+            let describe = QName::new(Namespace::public(), "describe");
+            for (class_name, expected) in &[("Base", "base"), ("Sub", "sub")] {
+                let mut instance = construct_global_class(activation, globals, class_name);
+                let method = instance
+                    .get_property(instance, &describe, activation)
+                    .unwrap()
+                    .coerce_to_object(activation)
+                    .unwrap();
+                let result = method.call(Some(instance), &[], activation, None).unwrap();
+                assert_eq!(result, (*expected).into(), "{}", class_name);
+            }
+        });
+
+        with_avm2(|activation| {
+            // `Sub.describe` taking an `int` no longer matches `Base.describe`.
+            let mut abc = field_initializer_abc();
+            abc.methods[6].params.push(swf::avm2::types::MethodParam {
+                name: None,
+                kind: Index(9, std::marker::PhantomData),
+                default_value: None,
+            });
+            let error = load_abc(activation, abc)
+                .and_then(|mut globals| {
+                    globals.get_property(
+                        globals,
+                        &QName::new(Namespace::public(), "Sub"),
+                        activation,
+                    )
+                })
+                .unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "VerifyError: Error #1053: Illegal override of describe."
+            );
+        });
+    }
+
+    /// Enumerate `object`'s properties as `name=value` pairs, in the order
+    /// `for..in` visits them.
+    fn enumerate<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        mut object: Object<'gc>,
+    ) -> Vec<String> {
+        let mut pairs = vec![];
+        let mut index = 0;
+        while let Some(next) = object.get_next_enumerant(index) {
+            let name = object.get_enumerant_name(next).unwrap();
+            let value = object
+                .get_property(object, &name, activation)
+                .unwrap()
+                .coerce_to_string(activation)
+                .unwrap();
+
+            pairs.push(format!("{}={}", name.local_name(), value));
+            index = next;
+        }
+
+        pairs
+    }
+
+    #[test]
+    fn object_literals_enumerate_in_authored_order() {
+        with_avm2(|activation| {
+            let operands = [
+                "a".into(),
+                1.into(),
+                "b".into(),
+                2.into(),
+                "c".into(),
+                3.into(),
+            ];
+            let object = run_op(activation, |a| a.op_new_object(3), &operands)
+                .coerce_to_object(activation)
+                .unwrap();
+            assert_eq!(enumerate(activation, object), vec!["a=1", "b=2", "c=3"]);
+
+            let operands = [
+                "a".into(),
+                1.into(),
+                "b".into(),
+                2.into(),
+                "a".into(),
+                3.into(),
+            ];
+            let object = run_op(activation, |a| a.op_new_object(3), &operands)
+                .coerce_to_object(activation)
+                .unwrap();
+            assert_eq!(enumerate(activation, object), vec!["a=3", "b=2"]);
+        });
+    }
+
+    #[test]
+    fn conversion_opcodes_convert_distinctly() {
+        with_avm2(|activation| {
+            let number = run_op(activation, Activation::op_convert_d, &[(-1.5).into()]);
+            assert!(matches!(number, Value::Number(n) if n == -1.5));
+            let number = run_op(activation, Activation::op_convert_d, &[(-1).into()]);
+            assert!(matches!(number, Value::Number(n) if n == -1.0));
+
+            let int = run_op(activation, Activation::op_convert_i, &[(-1.5).into()]);
+            assert!(matches!(int, Value::Integer(-1)));
+            let int = run_op(activation, Activation::op_convert_i, &[(-1).into()]);
+            assert!(matches!(int, Value::Integer(-1)));
+            let int = run_op(activation, Activation::op_convert_i, &[4294967295.0.into()]);
+            assert!(matches!(int, Value::Integer(-1)));
+
+            let uint = run_op(activation, Activation::op_convert_u, &[(-1.5).into()]);
+            assert!(matches!(uint, Value::Unsigned(4294967295)));
+            let uint = run_op(activation, Activation::op_convert_u, &[(-1).into()]);
+            assert!(matches!(uint, Value::Unsigned(4294967295)));
+            let uint = run_op(activation, Activation::op_convert_u, &[2.75.into()]);
+            assert!(matches!(uint, Value::Unsigned(2)));
+        });
+    }
+
+    /// A method whose constant pool holds one each of `int`, `uint` and
+    /// `Number`.
+    fn constant_pool_method<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Gc<'gc, BytecodeMethod<'gc>> {
+        use swf::avm2::types::{AbcFile, ConstantPool, Method as AbcMethod};
+
+        let abc = AbcFile {
+            major_version: 46,
+            minor_version: 16,
+            constant_pool: ConstantPool {
+                ints: vec![-5],
+                uints: vec![4294967295],
+                doubles: vec![2.0],
+                strings: vec![],
+                namespaces: vec![],
+                namespace_sets: vec![],
+                multinames: vec![],
+            },
+            methods: vec![AbcMethod {
+                name: Index::new(0),
+                params: vec![],
+                return_type: Index::new(0),
+                needs_arguments_object: false,
+                needs_activation: false,
+                needs_rest: false,
+                needs_dxns: false,
+            }],
+            metadata: vec![],
+            instances: vec![],
+            classes: vec![],
+            scripts: vec![],
+            method_bodies: vec![],
+        };
+        let domain = activation.avm2().global_domain();
+        let tunit =
+            TranslationUnit::from_abc(std::rc::Rc::new(abc), domain, activation.context.gc_context);
+
+        BytecodeMethod::from_method_index(tunit, Index::new(0), activation.context.gc_context)
+            .unwrap()
+    }
+
+    #[test]
+    fn constant_opcodes_push_typed_values() {
+        with_avm2(|activation| {
+            let byte = run_op(activation, |a| a.op_push_byte(0xFF), &[]);
+            assert!(matches!(byte, Value::Integer(-1)));
+            let byte = run_op(activation, |a| a.op_push_byte(0x7F), &[]);
+            assert!(matches!(byte, Value::Integer(127)));
+            let short = run_op(activation, |a| a.op_push_short(-300), &[]);
+            assert!(matches!(short, Value::Integer(-300)));
+
+            let method = constant_pool_method(activation);
+            activation.op_push_int(method, Index::new(1)).unwrap();
+            let int = activation.context.avm2.pop();
+            assert!(matches!(int, Value::Integer(-5)));
+            activation.op_push_uint(method, Index::new(1)).unwrap();
+            let uint = activation.context.avm2.pop();
+            assert!(matches!(uint, Value::Unsigned(4294967295)));
+            activation.op_push_double(method, Index::new(1)).unwrap();
+            let double = activation.context.avm2.pop();
+            assert!(matches!(double, Value::Number(n) if n == 2.0));
+
+            let int_class = activation
+                .avm2()
+                .global_domain()
+                .get_defined_value(activation, QName::new(Namespace::public(), "int"))
+                .unwrap();
+            let is_int = run_op(
+                activation,
+                Activation::op_is_type_late,
+                &[Value::Integer(-1), int_class],
+            );
+            assert_eq!(is_int, true.into());
+        });
+    }
+
+    /// Run a `for..in` loop over `object` with `hasnext2`, yielding each
+    /// `name=value` pair it visits.
+    fn for_in<'gc>(activation: &mut Activation<'_, 'gc, '_>, object: Object<'gc>) -> Vec<String> {
+        activation.local_registers =
+            GcCell::allocate(activation.context.gc_context, RegisterSet::new(2));
+        activation
+            .set_local_register(0, object, activation.context.gc_context)
+            .unwrap();
+        activation
+            .set_local_register(1, 0, activation.context.gc_context)
+            .unwrap();
+
+        let mut pairs = vec![];
+        loop {
+            activation.op_has_next_2(0, 1).unwrap();
+            if !activation.context.avm2.pop().coerce_to_boolean() {
+                break;
+            }
+
+            let object = activation.local_register(0).unwrap();
+            let index = activation.local_register(1).unwrap();
+            let operands = [object, index];
+            let name = run_op(activation, Activation::op_next_name, &operands)
+                .coerce_to_string(activation)
+                .unwrap();
+            let value = run_op(activation, Activation::op_next_value, &operands)
+                .coerce_to_string(activation)
+                .unwrap();
+
+            pairs.push(format!("{}={}", name, value));
+        }
+
+        pairs
+    }
+
+    /// Run a `for..in` loop over `object` as in `for_in`, with `hasnext`.
+    fn for_in_with_has_next<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        object: Object<'gc>,
+    ) -> Vec<String> {
+        let mut pairs = vec![];
+        let mut index = Value::from(0);
+        loop {
+            index = run_op(activation, Activation::op_has_next, &[object.into(), index]);
+            if index == 0.into() {
+                break;
+            }
+
+            let operands = [object.into(), index.clone()];
+            let name = run_op(activation, Activation::op_next_name, &operands)
+                .coerce_to_string(activation)
+                .unwrap();
+            let value = run_op(activation, Activation::op_next_value, &operands)
+                .coerce_to_string(activation)
+                .unwrap();
+
+            pairs.push(format!("{}={}", name, value));
+        }
+
+        pairs
+    }
+
+    #[test]
+    fn for_in_visits_inherited_properties() {
+        with_avm2(|activation| {
+            let mut proto = ScriptObject::object(
+                activation.context.gc_context,
+                activation.context.avm2.prototypes().object,
+            );
+            let mut object = ScriptObject::object(activation.context.gc_context, proto);
+
+            for (name, value) in &[("shadowed", "proto"), ("inherited", "proto")] {
+                proto
+                    .set_property(
+                        proto,
+                        &QName::new(Namespace::public(), *name),
+                        (*value).into(),
+                        activation,
+                    )
+                    .unwrap();
+            }
+            for (name, value) in &[("own", "object"), ("shadowed", "object")] {
+                object
+                    .set_property(
+                        object,
+                        &QName::new(Namespace::public(), *name),
+                        (*value).into(),
+                        activation,
+                    )
+                    .unwrap();
+            }
+
+            assert_eq!(
+                for_in_with_has_next(activation, object),
+                vec!["own=object", "shadowed=object", "inherited=proto"]
+            );
+            assert_eq!(
+                for_in(activation, object),
+                vec!["own=object", "shadowed=object", "inherited=proto"]
+            );
+            assert_eq!(activation.local_register(0).unwrap(), Value::Null);
+        });
+    }
+
+    /// The values on the operand stack, bottom first.
+    fn stack_of<'gc>(activation: &Activation<'_, 'gc, '_>) -> Vec<Value<'gc>> {
+        activation.context.avm2.stack.clone()
+    }
+
+    #[test]
+    fn stack_opcodes_rearrange_the_top_values() {
+        with_avm2(|activation| {
+            activation.context.avm2.push(1);
+            activation.context.avm2.push(2);
+
+            activation.op_dup().unwrap();
+            assert_eq!(stack_of(activation), vec![1.into(), 2.into(), 2.into()]);
+
+            activation.op_pop().unwrap();
+            assert_eq!(stack_of(activation), vec![1.into(), 2.into()]);
+
+            activation.op_swap().unwrap();
+            assert_eq!(stack_of(activation), vec![2.into(), 1.into()]);
+
+            // Values left by the caller don't count towards a frame's stack.
+            let mut callee = Activation::from_nothing(activation.context.reborrow());
+            assert!(callee.op_dup().is_err());
+            assert!(callee.op_pop().is_err());
+            callee.context.avm2.push(3);
+            assert!(callee.op_swap().is_err());
+            callee.op_dup().unwrap();
+            callee.op_swap().unwrap();
+            assert_eq!(
+                stack_of(&callee),
+                vec![2.into(), 1.into(), 3.into(), 3.into()]
+            );
+        });
+    }
+
+    #[test]
+    fn scope_opcodes_nest() {
+        with_avm2(|activation| {
+            let outer = ScriptObject::bare_object(activation.context.gc_context);
+            let inner = ScriptObject::bare_object(activation.context.gc_context);
+
+            activation.context.avm2.push(outer);
+            activation.op_push_scope().unwrap();
+            activation.context.avm2.push(inner);
+            activation.op_push_scope().unwrap();
+            let scope = activation.scope().unwrap();
+            assert!(Object::ptr_eq(*scope.read().locals(), inner));
+
+            activation.op_pop_scope().unwrap();
+            let scope = activation.scope().unwrap();
+            assert!(Object::ptr_eq(*scope.read().locals(), outer));
+
+            activation.op_pop_scope().unwrap();
+            assert!(activation.scope().is_none());
+            assert!(activation.op_pop_scope().is_err());
+        });
+    }
+}
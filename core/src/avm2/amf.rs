@@ -0,0 +1,790 @@
+//! AMF3 serialization and deserialization of AVM2 values
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{ArrayObject, ByteArrayObject, Object, ScriptObject, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::TraitKind;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+
+/// The largest integer that AMF3 can store as an `integer` rather than a
+/// `double`.
+const AMF3_INT_MAX: i32 = 0x0FFF_FFFF;
+
+/// The smallest integer that AMF3 can store as an `integer` rather than a
+/// `double`.
+const AMF3_INT_MIN: i32 = -0x1000_0000;
+
+/// The shape of a serialized object.
+///
+/// Ruffle has no class alias registry yet, so every object is written with an
+/// empty class name and the traits are determined entirely by its members.
+#[derive(PartialEq)]
+struct Traits<'gc> {
+    /// The public slots of the object's class hierarchy, from the base class
+    /// down.
+    sealed: Vec<QName<'gc>>,
+
+    /// Whether or not the object's class allows dynamic properties.
+    is_dynamic: bool,
+}
+
+/// Writes AVM2 values as a single AMF3 message.
+///
+/// Strings, objects, and traits are written out in full once and referred to
+/// by index afterwards, so a single writer should be used per message.
+pub struct Amf3Writer<'gc> {
+    /// The encoded message.
+    bytes: Vec<u8>,
+
+    /// The string reference table.
+    strings: Vec<String>,
+
+    /// The object reference table.
+    objects: Vec<Object<'gc>>,
+
+    /// The traits reference table.
+    traits: Vec<Traits<'gc>>,
+}
+
+impl<'gc> Amf3Writer<'gc> {
+    /// Construct a writer for a new message.
+    pub fn new() -> Self {
+        Self {
+            bytes: Vec::new(),
+            strings: Vec::new(),
+            objects: Vec::new(),
+            traits: Vec::new(),
+        }
+    }
+
+    /// Finish the message and yield its bytes.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    /// Write a value, preceded by its type marker.
+    pub fn write_value(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        value: Value<'gc>,
+    ) -> Result<(), Error> {
+        match value {
+            Value::Undefined => self.bytes.push(0x00),
+            Value::Null => self.bytes.push(0x01),
+            Value::Bool(false) => self.bytes.push(0x02),
+            Value::Bool(true) => self.bytes.push(0x03),
+            Value::Integer(i) if (AMF3_INT_MIN..=AMF3_INT_MAX).contains(&i) => {
+                self.bytes.push(0x04);
+                self.write_u29(i as u32);
+            }
+            Value::Unsigned(u) if u <= AMF3_INT_MAX as u32 => {
+                self.bytes.push(0x04);
+                self.write_u29(u);
+            }
+            Value::Integer(i) => self.write_double(i as f64),
+            Value::Unsigned(u) => self.write_double(u as f64),
+            Value::Number(n) => self.write_double(n),
+            Value::String(s) => {
+                self.bytes.push(0x06);
+                self.write_string(&s);
+            }
+            Value::Object(o) => self.write_object(activation, o)?,
+        }
+
+        Ok(())
+    }
+
+    /// Write a variable-length 29-bit unsigned integer.
+    ///
+    /// Bits above the 29th are discarded, which is how negative integers are
+    /// stored.
+    fn write_u29(&mut self, value: u32) {
+        let value = value & 0x1FFF_FFFF;
+
+        if value < 0x80 {
+            self.bytes.push(value as u8);
+        } else if value < 0x4000 {
+            self.bytes.push((value >> 7 | 0x80) as u8);
+            self.bytes.push((value & 0x7F) as u8);
+        } else if value < 0x20_0000 {
+            self.bytes.push((value >> 14 | 0x80) as u8);
+            self.bytes.push((value >> 7 & 0x7F | 0x80) as u8);
+            self.bytes.push((value & 0x7F) as u8);
+        } else {
+            self.bytes.push((value >> 22 | 0x80) as u8);
+            self.bytes.push((value >> 15 & 0x7F | 0x80) as u8);
+            self.bytes.push((value >> 8 & 0x7F | 0x80) as u8);
+            self.bytes.push((value & 0xFF) as u8);
+        }
+    }
+
+    fn write_double(&mut self, value: f64) {
+        self.bytes.push(0x05);
+        self.bytes.extend_from_slice(&value.to_be_bytes());
+    }
+
+    /// Write a string without a type marker.
+    ///
+    /// The empty string is never entered into the reference table.
+    fn write_string(&mut self, string: &str) {
+        if string.is_empty() {
+            self.write_u29(0x01);
+            return;
+        }
+
+        if let Some(index) = self.strings.iter().position(|s| s == string) {
+            self.write_u29((index as u32) << 1);
+            return;
+        }
+
+        self.strings.push(string.to_string());
+        self.write_u29((string.len() as u32) << 1 | 1);
+        self.bytes.extend_from_slice(string.as_bytes());
+    }
+
+    /// Write a reference to an object that has already been written.
+    ///
+    /// Returns `false`, and enters the object into the reference table, if
+    /// the object has not been seen before; it must then be written in full.
+    fn write_object_reference(&mut self, object: Object<'gc>) -> bool {
+        if let Some(index) = self.objects.iter().position(|o| Object::ptr_eq(*o, object)) {
+            self.write_u29((index as u32) << 1);
+            return true;
+        }
+
+        self.objects.push(object);
+
+        false
+    }
+
+    fn write_object(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        mut object: Object<'gc>,
+    ) -> Result<(), Error> {
+        // Functions cannot be serialized.
+        if object.as_executable().is_some() {
+            self.bytes.push(0x00);
+            return Ok(());
+        }
+
+        let bytes = object.as_bytearray().map(|b| b.bytes().to_vec());
+        if let Some(bytes) = bytes {
+            self.bytes.push(0x0C);
+            if !self.write_object_reference(object) {
+                self.write_u29((bytes.len() as u32) << 1 | 1);
+                self.bytes.extend_from_slice(&bytes);
+            }
+
+            return Ok(());
+        }
+
+        let dense = object.as_array_storage().map(|a| {
+            (0..a.length())
+                .map(|i| a.get(i).unwrap_or(Value::Undefined))
+                .collect::<Vec<_>>()
+        });
+        if let Some(dense) = dense {
+            self.bytes.push(0x09);
+            if !self.write_object_reference(object) {
+                self.write_u29((dense.len() as u32) << 1 | 1);
+                self.write_dynamic_members(activation, object)?;
+
+                for value in dense {
+                    self.write_value(activation, value)?;
+                }
+            }
+
+            return Ok(());
+        }
+
+        self.bytes.push(0x0A);
+        if self.write_object_reference(object) {
+            return Ok(());
+        }
+
+        let traits = Self::traits_of(object);
+        let sealed = traits.sealed.clone();
+        let is_dynamic = traits.is_dynamic;
+
+        if let Some(index) = self.traits.iter().position(|t| *t == traits) {
+            self.write_u29((index as u32) << 2 | 0b01);
+        } else {
+            let dynamic_flag = if is_dynamic { 0b1000 } else { 0 };
+
+            self.write_u29((sealed.len() as u32) << 4 | dynamic_flag | 0b011);
+            self.write_string("");
+            for name in sealed.iter() {
+                self.write_string(&name.local_name());
+            }
+
+            self.traits.push(traits);
+        }
+
+        for name in sealed.iter() {
+            let value = object.get_property(object, name, activation)?;
+            self.write_value(activation, value)?;
+        }
+
+        if is_dynamic {
+            self.write_dynamic_members(activation, object)?;
+        }
+
+        Ok(())
+    }
+
+    /// Write an object's enumerable public dynamic properties, followed by
+    /// the empty string that terminates them.
+    ///
+    /// Properties in any other namespace hold the internal state of native
+    /// objects and are never serialized.
+    fn write_dynamic_members(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        mut object: Object<'gc>,
+    ) -> Result<(), Error> {
+        let names: Vec<QName<'gc>> = std::iter::successors(object.get_next_enumerant(0), |i| {
+            object.get_next_enumerant(*i)
+        })
+        .filter_map(|i| object.get_enumerant_name(i))
+        .filter(|name| name.namespace().is_public())
+        .collect();
+
+        for name in names {
+            let value = object.get_property(object, &name, activation)?;
+
+            self.write_string(&name.local_name());
+            self.write_value(activation, value)?;
+        }
+
+        self.write_string("");
+
+        Ok(())
+    }
+
+    /// Determine the traits of an object from its class hierarchy.
+    ///
+    /// Only public slots are sealed members; private traits never leave the
+    /// object.
+    fn traits_of(object: Object<'gc>) -> Traits<'gc> {
+        let mut classes = Vec::new();
+        let mut proto = object.proto();
+
+        while let Some(p) = proto {
+            if let Some(class) = p.as_class() {
+                classes.push(class);
+            }
+
+            proto = p.proto();
+        }
+
+        let is_dynamic = classes
+            .first()
+            .map(|class| !class.read().is_sealed())
+            .unwrap_or(true);
+
+        let mut sealed = Vec::new();
+        for class in classes.iter().rev() {
+            for instance_trait in class.read().instance_traits() {
+                let name = instance_trait.name();
+
+                if name.namespace().is_public()
+                    && matches!(instance_trait.kind(), TraitKind::Slot { .. })
+                    && !sealed.contains(name)
+                {
+                    sealed.push(name.clone());
+                }
+            }
+        }
+
+        Traits { sealed, is_dynamic }
+    }
+}
+
+impl<'gc> Default for Amf3Writer<'gc> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The error reported when a message ends partway through a value.
+pub const END_OF_MESSAGE: &str = "EOFError: Error #2030: End of file was encountered.";
+
+/// Reads AVM2 values back out of a single AMF3 message.
+///
+/// This understands everything that `Amf3Writer` produces. Objects are read
+/// back as plain `Object`s, since Ruffle has no class alias registry to find
+/// their original classes with.
+pub struct Amf3Reader<'a, 'gc> {
+    /// The encoded message.
+    bytes: &'a [u8],
+
+    /// How far into the message we have read.
+    position: usize,
+
+    /// The string reference table.
+    strings: Vec<String>,
+
+    /// The object reference table.
+    objects: Vec<Object<'gc>>,
+
+    /// The traits reference table, as each traits' sealed member names and
+    /// whether they allow dynamic members.
+    traits: Vec<(Vec<String>, bool)>,
+}
+
+impl<'a, 'gc> Amf3Reader<'a, 'gc> {
+    /// Construct a reader for a message.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            position: 0,
+            strings: Vec::new(),
+            objects: Vec::new(),
+            traits: Vec::new(),
+        }
+    }
+
+    /// How many bytes of the message have been read.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Read a value, preceded by its type marker.
+    pub fn read_value(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error> {
+        Ok(match self.read_u8()? {
+            0x00 => Value::Undefined,
+            0x01 => Value::Null,
+            0x02 => false.into(),
+            0x03 => true.into(),
+            0x04 => {
+                let value = self.read_u29()?;
+
+                // Sign-extend the 29-bit integer.
+                ((value << 3) as i32 >> 3).into()
+            }
+            0x05 => {
+                let mut bytes = [0; 8];
+                bytes.copy_from_slice(self.read_bytes(8)?);
+
+                f64::from_be_bytes(bytes).into()
+            }
+            0x06 => AvmString::new(activation.context.gc_context, self.read_string()?).into(),
+            0x09 => self.read_array(activation)?.into(),
+            0x0A => self.read_object(activation)?.into(),
+            0x0C => self.read_bytearray(activation)?.into(),
+            marker => return Err(format!("Unsupported AMF3 type marker {:#04X}", marker).into()),
+        })
+    }
+
+    fn read_u8(&mut self) -> Result<u8, Error> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    fn read_bytes(&mut self, length: usize) -> Result<&'a [u8], Error> {
+        let end = self
+            .position
+            .checked_add(length)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or(END_OF_MESSAGE)?;
+        let bytes = &self.bytes[self.position..end];
+        self.position = end;
+
+        Ok(bytes)
+    }
+
+    /// Read a variable-length 29-bit unsigned integer.
+    fn read_u29(&mut self) -> Result<u32, Error> {
+        let mut value = 0;
+
+        for _ in 0..3 {
+            let byte = self.read_u8()?;
+            value = value << 7 | u32::from(byte & 0x7F);
+
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        Ok(value << 8 | u32::from(self.read_u8()?))
+    }
+
+    /// Read a string without a type marker.
+    fn read_string(&mut self) -> Result<String, Error> {
+        let header = self.read_u29()?;
+        if header & 1 == 0 {
+            return self
+                .strings
+                .get((header >> 1) as usize)
+                .cloned()
+                .ok_or_else(|| "Invalid AMF3 string reference".into());
+        }
+
+        let length = (header >> 1) as usize;
+        let string = String::from_utf8_lossy(self.read_bytes(length)?).into_owned();
+        if !string.is_empty() {
+            self.strings.push(string.clone());
+        }
+
+        Ok(string)
+    }
+
+    /// Read the header of an object, array, or byte array.
+    ///
+    /// Yields either an object that has already been read, or the rest of
+    /// the header for an object that is written in full.
+    fn read_object_header(&mut self) -> Result<Result<Object<'gc>, u32>, Error> {
+        let header = self.read_u29()?;
+        if header & 1 == 0 {
+            return self
+                .objects
+                .get((header >> 1) as usize)
+                .copied()
+                .map(Ok)
+                .ok_or_else(|| "Invalid AMF3 object reference".into());
+        }
+
+        Ok(Err(header >> 1))
+    }
+
+    /// Read string-keyed members into an object until the empty string that
+    /// terminates them.
+    fn read_dynamic_members(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        mut object: Object<'gc>,
+    ) -> Result<(), Error> {
+        loop {
+            let name = self.read_string()?;
+            if name.is_empty() {
+                return Ok(());
+            }
+
+            let name = AvmString::new(activation.context.gc_context, name);
+            let value = self.read_value(activation)?;
+            object.set_property(
+                object,
+                &QName::new(Namespace::public(), name),
+                value,
+                activation,
+            )?;
+        }
+    }
+
+    fn read_array(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Object<'gc>, Error> {
+        let length = match self.read_object_header()? {
+            Ok(array) => return Ok(array),
+            Err(length) => length,
+        };
+
+        let array = ArrayObject::from_array(
+            ArrayStorage::new(0),
+            activation.avm2().prototypes().array,
+            activation.context.gc_context,
+        );
+        self.objects.push(array);
+        self.read_dynamic_members(activation, array)?;
+
+        for _ in 0..length {
+            let value = self.read_value(activation)?;
+            if let Some(mut storage) = array.as_array_storage_mut(activation.context.gc_context) {
+                storage.push(value);
+            }
+        }
+
+        Ok(array)
+    }
+
+    fn read_object(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Object<'gc>, Error> {
+        let header = match self.read_object_header()? {
+            Ok(object) => return Ok(object),
+            Err(header) => header,
+        };
+
+        let (sealed, is_dynamic) = if header & 1 == 0 {
+            self.traits
+                .get((header >> 1) as usize)
+                .cloned()
+                .ok_or("Invalid AMF3 traits reference")?
+        } else if header & 0b10 != 0 {
+            return Err("Externalizable AMF3 objects are not supported".into());
+        } else {
+            let is_dynamic = header & 0b100 != 0;
+            let _class_name = self.read_string()?;
+            let sealed = (0..header >> 3)
+                .map(|_| self.read_string())
+                .collect::<Result<Vec<_>, _>>()?;
+
+            self.traits.push((sealed.clone(), is_dynamic));
+
+            (sealed, is_dynamic)
+        };
+
+        let mut object = ScriptObject::object(
+            activation.context.gc_context,
+            activation.avm2().prototypes().object,
+        );
+        self.objects.push(object);
+
+        for name in sealed {
+            let name = AvmString::new(activation.context.gc_context, name);
+            let value = self.read_value(activation)?;
+            object.set_property(
+                object,
+                &QName::new(Namespace::public(), name),
+                value,
+                activation,
+            )?;
+        }
+
+        if is_dynamic {
+            self.read_dynamic_members(activation, object)?;
+        }
+
+        Ok(object)
+    }
+
+    fn read_bytearray(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Object<'gc>, Error> {
+        let length = match self.read_object_header()? {
+            Ok(bytearray) => return Ok(bytearray),
+            Err(length) => length as usize,
+        };
+
+        let bytes = self.read_bytes(length)?;
+        let bytearray = ByteArrayObject::construct(
+            activation.context.gc_context,
+            Some(activation.avm2().prototypes().bytearray),
+        );
+        if let Some(mut storage) = bytearray.as_bytearray_mut(activation.context.gc_context) {
+            storage.write_bytes(bytes);
+            storage.set_position(0);
+        }
+        self.objects.push(bytearray);
+
+        Ok(bytearray)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::array::ArrayStorage;
+    use crate::avm2::names::Namespace;
+    use crate::avm2::object::{ArrayObject, ScriptObject};
+    use crate::avm2::test_utils::with_avm2;
+
+    fn serialize<'gc>(activation: &mut Activation<'_, 'gc, '_>, value: Value<'gc>) -> Vec<u8> {
+        let mut writer = Amf3Writer::new();
+        writer.write_value(activation, value).unwrap();
+        writer.into_bytes()
+    }
+
+    /// Build `{visible: 1, b: true}` with an extra hidden `hidden` property,
+    /// and an internal property that script can't see at all.
+    fn object_with_hidden_properties<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Object<'gc> {
+        let mut object = ScriptObject::object(
+            activation.context.gc_context,
+            activation.avm2().prototypes().object,
+        );
+
+        for (name, value) in &[
+            ("visible", Value::Integer(1)),
+            ("hidden", "secret".into()),
+            ("b", true.into()),
+        ] {
+            object
+                .set_property(
+                    object,
+                    &QName::new(Namespace::public(), *name),
+                    value.clone(),
+                    activation,
+                )
+                .unwrap();
+        }
+        object
+            .set_property(
+                object,
+                &QName::new(Namespace::Private("ruffle".into()), "internal"),
+                "ruffle".into(),
+                activation,
+            )
+            .unwrap();
+        object
+            .set_local_property_is_enumerable(
+                activation.context.gc_context,
+                &QName::new(Namespace::public(), "hidden"),
+                false,
+            )
+            .unwrap();
+
+        object
+    }
+
+    #[test]
+    fn hidden_properties_are_not_serialized() {
+        with_avm2(|activation| {
+            let object = object_with_hidden_properties(activation);
+
+            assert_eq!(
+                serialize(activation, object.into()),
+                vec![
+                    0x0A, 0x0B, 0x01, // anonymous dynamic object, no sealed members
+                    0x0F, b'v', b'i', b's', b'i', b'b', b'l', b'e', 0x04, 0x01, // visible: 1
+                    0x03, b'b', 0x03, // b: true
+                    0x01, // end of dynamic members
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn private_traits_are_not_serialized() {
+        with_avm2(|activation| {
+            let name = QName::new(Namespace::package("flash.events"), "EventDispatcher");
+            let mut ctor = activation
+                .avm2()
+                .global_domain()
+                .get_defined_value(activation, name)
+                .unwrap()
+                .coerce_to_object(activation)
+                .unwrap();
+            let proto = ctor
+                .get_property(
+                    ctor,
+                    &QName::new(Namespace::public(), "prototype"),
+                    activation,
+                )
+                .unwrap()
+                .coerce_to_object(activation)
+                .unwrap();
+            let dispatcher = proto.construct(activation, &[]).unwrap();
+            ctor.call(Some(dispatcher), &[], activation, dispatcher.proto())
+                .unwrap();
+
+            // Sealed, with no public slots: the private dispatch list and
+            // target are left out entirely.
+            assert_eq!(
+                serialize(activation, dispatcher.into()),
+                vec![0x0A, 0x03, 0x01]
+            );
+        });
+    }
+
+    #[test]
+    fn repeated_objects_are_referenced() {
+        with_avm2(|activation| {
+            let first = object_with_hidden_properties(activation);
+            let second = object_with_hidden_properties(activation);
+            let array = ArrayObject::from_array(
+                ArrayStorage::from_args(&[first.into(), second.into(), first.into()]),
+                activation.avm2().prototypes().array,
+                activation.context.gc_context,
+            );
+
+            assert_eq!(
+                serialize(activation, array.into()),
+                vec![
+                    0x09, 0x07, 0x01, // dense array of three, no associative members
+                    0x0A, 0x0B, 0x01, // first object, written in full
+                    0x0F, b'v', b'i', b's', b'i', b'b', b'l', b'e', 0x04, 0x01, // visible: 1
+                    0x03, b'b', 0x03, // b: true
+                    0x01, // end of the first object's dynamic members
+                    0x0A, 0x01, // second object, reusing the first's traits
+                    0x00, 0x04, 0x01, // visible: 1, by string reference
+                    0x02, 0x03, // b: true, by string reference
+                    0x01, // end of the second object's dynamic members
+                    0x0A, 0x02, // first object again, by object reference
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn messages_read_back_as_copies() {
+        with_avm2(|activation| {
+            let object = object_with_hidden_properties(activation);
+            let bytearray = ByteArrayObject::construct(
+                activation.context.gc_context,
+                Some(activation.avm2().prototypes().bytearray),
+            );
+            bytearray
+                .as_bytearray_mut(activation.context.gc_context)
+                .unwrap()
+                .write_bytes(&[1, 2, 3]);
+            let array = ArrayObject::from_array(
+                ArrayStorage::from_args(&[
+                    object.into(),
+                    bytearray.into(),
+                    (-5).into(),
+                    0.5.into(),
+                    "visible".into(),
+                    object.into(),
+                ]),
+                activation.avm2().prototypes().array,
+                activation.context.gc_context,
+            );
+
+            let bytes = serialize(activation, array.into());
+            let copy = Amf3Reader::new(&bytes)
+                .read_value(activation)
+                .unwrap()
+                .coerce_to_object(activation)
+                .unwrap();
+            assert!(!Object::ptr_eq(copy, array));
+
+            let items: Vec<Value<'_>> = {
+                let storage = copy.as_array_storage().unwrap();
+                (0..storage.length())
+                    .map(|i| storage.get(i).unwrap())
+                    .collect()
+            };
+            assert_eq!(items.len(), 6);
+            assert_eq!(items[2], (-5).into());
+            assert_eq!(items[3], 0.5.into());
+            assert_eq!(items[4], "visible".into());
+
+            // The repeated object is read back as a single copy.
+            let mut object_copy = items[0].coerce_to_object(activation).unwrap();
+            assert!(!Object::ptr_eq(object_copy, object));
+            assert_eq!(items[5], object_copy.into());
+            assert_eq!(
+                object_copy
+                    .get_property(
+                        object_copy,
+                        &QName::new(Namespace::public(), "visible"),
+                        activation
+                    )
+                    .unwrap(),
+                1.into()
+            );
+
+            let bytearray_copy = items[1].coerce_to_object(activation).unwrap();
+            assert!(!Object::ptr_eq(bytearray_copy, bytearray));
+            assert_eq!(bytearray_copy.as_bytearray().unwrap().bytes(), &[1, 2, 3]);
+        });
+    }
+
+    #[test]
+    fn truncated_messages_are_rejected() {
+        with_avm2(|activation| {
+            let bytes = serialize(activation, "truncated".into());
+
+            assert!(Amf3Reader::new(&bytes[..4]).read_value(activation).is_err());
+        });
+    }
+}
@@ -147,6 +147,29 @@ impl<'gc> ArrayStorage<'gc> {
         self.storage.insert(0, Some(item))
     }
 
+    /// Insert a single value into this array, moving every value after it
+    /// up by one.
+    ///
+    /// Inserting past the end of the array appends the value instead.
+    pub fn insert(&mut self, index: usize, item: Value<'gc>) {
+        let index = index.min(self.storage.len());
+
+        self.storage.insert(index, Some(item))
+    }
+
+    /// Remove a single value from this array, moving every value after it
+    /// down by one.
+    ///
+    /// Yields `None` if the removed item was a hole, and does nothing if the
+    /// index is past the end of the array.
+    pub fn remove(&mut self, index: usize) -> Option<Value<'gc>> {
+        if index < self.storage.len() {
+            self.storage.remove(index)
+        } else {
+            None
+        }
+    }
+
     /// Iterate over array values.
     pub fn iter<'a>(
         &'a self,
@@ -170,3 +193,31 @@ impl<'gc> ArrayStorage<'gc> {
             .splice(range, replace_with.into_iter().map(Some))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn append_preserves_holes() {
+        let mut first = ArrayStorage::new(0);
+        first.push(1.into());
+        first.push_hole();
+        first.push_hole();
+
+        let mut second = ArrayStorage::new(2);
+        second.set(2, 3.into());
+
+        first.append(&second);
+        first.push(Value::Undefined);
+
+        assert_eq!(first.length(), 7);
+        assert_eq!(first.get(0), Some(1.into()));
+        assert_eq!(first.get(1), None);
+        assert_eq!(first.get(2), None);
+        assert_eq!(first.get(3), None);
+        assert_eq!(first.get(4), None);
+        assert_eq!(first.get(5), Some(3.into()));
+        assert_eq!(first.get(6), Some(Value::Undefined));
+    }
+}
@@ -0,0 +1,524 @@
+//! ByteArray support types
+
+use crate::avm2::Error;
+use encoding_rs::{Encoding, UTF_8};
+use flate2::Compression;
+use gc_arena::Collect;
+use std::io::{Read, Write};
+
+/// The byte order of multi-byte values in a `ByteArray`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Endian {
+    #[default]
+    Big,
+    Little,
+}
+
+impl Endian {
+    /// Look up a byte order by its `flash.utils.Endian` name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "bigEndian" => Some(Self::Big),
+            "littleEndian" => Some(Self::Little),
+            _ => None,
+        }
+    }
+
+    /// Get the `flash.utils.Endian` name of this byte order.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Big => "bigEndian",
+            Self::Little => "littleEndian",
+        }
+    }
+}
+
+/// The byte storage portion of a `ByteArray` object.
+///
+/// All reads and writes happen at the current `position`, which is advanced
+/// past whatever was read or written. Multi-byte values are read and written
+/// in the current `endian` byte order.
+#[derive(Clone, Collect, Debug, Default)]
+#[collect(require_static)]
+pub struct ByteArrayStorage {
+    /// The bytes stored in this array.
+    bytes: Vec<u8>,
+
+    /// The current read/write position.
+    position: usize,
+
+    /// The byte order of multi-byte values.
+    endian: Endian,
+}
+
+impl ByteArrayStorage {
+    /// Construct new, empty byte array storage.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the stored bytes.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Get the length of the stored bytes.
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Resize the storage, zero-filling any new bytes.
+    ///
+    /// If the current position lies past the new end, it is moved to the end.
+    pub fn set_length(&mut self, length: usize) {
+        self.bytes.resize(length, 0);
+        self.position = self.position.min(length);
+    }
+
+    /// Empty the storage, releasing its memory, and rewind the position.
+    pub fn clear(&mut self) {
+        self.bytes = Vec::new();
+        self.position = 0;
+    }
+
+    /// Replace the stored bytes with their raw DEFLATE compression.
+    ///
+    /// No zlib header or checksum is written. The position is left at the end
+    /// of the compressed data.
+    pub fn deflate(&mut self) {
+        let mut encoder = flate2::write::DeflateEncoder::new(Vec::new(), Compression::default());
+
+        // Writing into a `Vec` cannot fail.
+        encoder.write_all(&self.bytes).unwrap();
+        self.bytes = encoder.finish().unwrap();
+        self.position = self.bytes.len();
+    }
+
+    /// Replace the stored bytes with their raw DEFLATE decompression.
+    ///
+    /// The position is rewound to the start. If the bytes are not valid
+    /// DEFLATE data, the storage is left untouched.
+    pub fn inflate(&mut self) -> Result<(), Error> {
+        let mut decoder = flate2::bufread::DeflateDecoder::new(&self.bytes[..]);
+        let mut inflated = Vec::new();
+
+        decoder
+            .read_to_end(&mut inflated)
+            .map_err(|_| "IOError: Error #2058: There was an error decompressing the data.")?;
+
+        self.bytes = inflated;
+        self.position = 0;
+
+        Ok(())
+    }
+
+    /// Get the current read/write position.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Set the current read/write position.
+    ///
+    /// Positions past the end of the array are allowed; the array will be
+    /// extended the next time something is written.
+    pub fn set_position(&mut self, position: usize) {
+        self.position = position;
+    }
+
+    /// Get the byte order of multi-byte values.
+    pub fn endian(&self) -> Endian {
+        self.endian
+    }
+
+    /// Set the byte order of multi-byte values read or written from now on.
+    pub fn set_endian(&mut self, endian: Endian) {
+        self.endian = endian;
+    }
+
+    /// The number of bytes that can be read from the current position.
+    pub fn bytes_available(&self) -> usize {
+        self.bytes.len().saturating_sub(self.position)
+    }
+
+    /// Write bytes at the current position, extending the storage as needed.
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        let end = self.position + bytes.len();
+        if self.bytes.len() < end {
+            self.bytes.resize(end, 0);
+        }
+
+        self.bytes[self.position..end].copy_from_slice(bytes);
+        self.position = end;
+    }
+
+    /// Add bytes to the end of the storage without moving the current
+    /// position.
+    pub fn append(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    /// Drop every byte before the current position, which becomes zero.
+    pub fn discard_read(&mut self) {
+        let position = self.position.min(self.bytes.len());
+
+        self.bytes.drain(..position);
+        self.position = 0;
+    }
+
+    /// Read bytes from the current position.
+    ///
+    /// Attempting to read past the end of the array is an `EOFError`, and
+    /// leaves the position untouched.
+    pub fn read_bytes(&mut self, length: usize) -> Result<&[u8], Error> {
+        if self.bytes_available() < length {
+            return Err("EOFError: Error #2030: End of file was encountered.".into());
+        }
+
+        let start = self.position;
+        self.position += length;
+
+        Ok(&self.bytes[start..self.position])
+    }
+
+    /// Read exactly `N` bytes from the current position.
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.read_bytes(N)?);
+
+        Ok(array)
+    }
+
+    /// Read a 16-bit integer from the current position.
+    pub fn read_u16(&mut self) -> Result<u16, Error> {
+        let bytes = self.read_array()?;
+
+        Ok(match self.endian {
+            Endian::Big => u16::from_be_bytes(bytes),
+            Endian::Little => u16::from_le_bytes(bytes),
+        })
+    }
+
+    /// Read a 32-bit integer from the current position.
+    pub fn read_u32(&mut self) -> Result<u32, Error> {
+        let bytes = self.read_array()?;
+
+        Ok(match self.endian {
+            Endian::Big => u32::from_be_bytes(bytes),
+            Endian::Little => u32::from_le_bytes(bytes),
+        })
+    }
+
+    /// Read a single-precision float from the current position.
+    pub fn read_f32(&mut self) -> Result<f32, Error> {
+        Ok(f32::from_bits(self.read_u32()?))
+    }
+
+    /// Read a double-precision float from the current position.
+    pub fn read_f64(&mut self) -> Result<f64, Error> {
+        let bytes = self.read_array()?;
+
+        Ok(match self.endian {
+            Endian::Big => f64::from_be_bytes(bytes),
+            Endian::Little => f64::from_le_bytes(bytes),
+        })
+    }
+
+    /// Write a 16-bit integer at the current position.
+    pub fn write_u16(&mut self, value: u16) {
+        match self.endian {
+            Endian::Big => self.write_bytes(&value.to_be_bytes()),
+            Endian::Little => self.write_bytes(&value.to_le_bytes()),
+        }
+    }
+
+    /// Write a 32-bit integer at the current position.
+    pub fn write_u32(&mut self, value: u32) {
+        match self.endian {
+            Endian::Big => self.write_bytes(&value.to_be_bytes()),
+            Endian::Little => self.write_bytes(&value.to_le_bytes()),
+        }
+    }
+
+    /// Write a single-precision float at the current position.
+    pub fn write_f32(&mut self, value: f32) {
+        self.write_u32(value.to_bits());
+    }
+
+    /// Write a double-precision float at the current position.
+    pub fn write_f64(&mut self, value: f64) {
+        match self.endian {
+            Endian::Big => self.write_bytes(&value.to_be_bytes()),
+            Endian::Little => self.write_bytes(&value.to_le_bytes()),
+        }
+    }
+
+    /// Read `length` bytes at `offset` without moving the position, as the
+    /// domain memory opcodes do.
+    ///
+    /// Attempting to read past the end of the array is a `RangeError`.
+    pub fn read_at(&self, offset: usize, length: usize) -> Result<&[u8], Error> {
+        offset
+            .checked_add(length)
+            .and_then(|end| self.bytes.get(offset..end))
+            .ok_or_else(|| "RangeError: Error #1506: The specified range is invalid.".into())
+    }
+
+    /// Overwrite bytes at `offset` without moving the position, as the
+    /// domain memory opcodes do.
+    ///
+    /// Unlike `write_bytes`, this never extends the array: attempting to
+    /// write past the end is a `RangeError`.
+    pub fn write_at(&mut self, offset: usize, bytes: &[u8]) -> Result<(), Error> {
+        offset
+            .checked_add(bytes.len())
+            .and_then(|end| self.bytes.get_mut(offset..end))
+            .ok_or_else(|| "RangeError: Error #1506: The specified range is invalid.".into())
+            .map(|target| target.copy_from_slice(bytes))
+    }
+
+    /// Replace the int at `offset` with `new` if it is currently `expected`,
+    /// yielding the int that was there before.
+    ///
+    /// Only one worker runs at a time, so this can never be contended. The
+    /// position is left untouched.
+    pub fn compare_and_swap_int(
+        &mut self,
+        offset: usize,
+        expected: i32,
+        new: i32,
+    ) -> Result<i32, Error> {
+        let end = offset
+            .checked_add(4)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or("RangeError: Error #1506: The specified range is invalid.")?;
+
+        let mut old = [0; 4];
+        old.copy_from_slice(&self.bytes[offset..end]);
+        let old = match self.endian {
+            Endian::Big => i32::from_be_bytes(old),
+            Endian::Little => i32::from_le_bytes(old),
+        };
+
+        if old == expected {
+            let new = match self.endian {
+                Endian::Big => new.to_be_bytes(),
+                Endian::Little => new.to_le_bytes(),
+            };
+            self.bytes[offset..end].copy_from_slice(&new);
+        }
+
+        Ok(old)
+    }
+
+    /// Resize the storage to `new` bytes if it is currently `expected` bytes
+    /// long, yielding the length it had before.
+    pub fn compare_and_swap_length(&mut self, expected: usize, new: usize) -> usize {
+        let old = self.len();
+
+        if old == expected {
+            self.set_length(new);
+        }
+
+        old
+    }
+
+    /// Encode a string in the given charset and write it at the current
+    /// position.
+    ///
+    /// Unknown charsets are treated as UTF-8.
+    pub fn write_multibyte(&mut self, string: &str, charset: &str) {
+        let (encoded, _, _) = charset_encoding(charset).encode(string);
+
+        self.write_bytes(&encoded);
+    }
+
+    /// Read `length` bytes from the current position and decode them in the
+    /// given charset.
+    ///
+    /// Unknown charsets are treated as UTF-8.
+    pub fn read_multibyte(&mut self, length: usize, charset: &str) -> Result<String, Error> {
+        let encoding = charset_encoding(charset);
+        let bytes = self.read_bytes(length)?;
+        let (decoded, _, _) = encoding.decode(bytes);
+
+        Ok(decoded.into_owned())
+    }
+}
+
+/// Look up an encoding by its charset label, falling back to UTF-8 as Flash
+/// Player does.
+fn charset_encoding(charset: &str) -> &'static Encoding {
+    Encoding::for_label(charset.trim().as_bytes()).unwrap_or(UTF_8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multibyte_latin1_round_trip() {
+        let text = "Café à la crème, ñandú über ÿ";
+        let mut storage = ByteArrayStorage::new();
+
+        storage.write_multibyte(text, "iso-8859-1");
+        assert_eq!(storage.len(), text.chars().count());
+        assert_eq!(storage.bytes()[3], 0xE9);
+
+        storage.set_position(0);
+        let read = storage.read_multibyte(storage.len(), "iso-8859-1").unwrap();
+        assert_eq!(read, text);
+        assert_eq!(storage.bytes_available(), 0);
+    }
+
+    #[test]
+    fn multibyte_shift_jis() {
+        let mut storage = ByteArrayStorage::new();
+
+        storage.write_multibyte("日本", "shift-jis");
+        assert_eq!(storage.bytes(), &[0x93, 0xFA, 0x96, 0x7B]);
+
+        storage.set_position(0);
+        assert_eq!(storage.read_multibyte(4, "shift-jis").unwrap(), "日本");
+    }
+
+    #[test]
+    fn multibyte_unknown_charset_is_utf8() {
+        let mut storage = ByteArrayStorage::new();
+
+        storage.write_multibyte("é", "not-a-charset");
+        assert_eq!(storage.bytes(), "é".as_bytes());
+
+        storage.set_position(0);
+        assert_eq!(storage.read_multibyte(2, "bogus").unwrap(), "é");
+    }
+
+    #[test]
+    fn read_past_end_is_eof() {
+        let mut storage = ByteArrayStorage::new();
+
+        storage.write_bytes(&[1, 2]);
+        storage.set_position(1);
+        assert!(storage.read_multibyte(2, "utf-8").is_err());
+        assert_eq!(storage.position(), 1);
+    }
+
+    #[test]
+    fn append_and_discard_keep_unread_bytes() {
+        let mut storage = ByteArrayStorage::new();
+
+        storage.append(&[1, 2, 3]);
+        assert_eq!(storage.position(), 0);
+        assert_eq!(storage.read_bytes(2).unwrap(), &[1, 2]);
+
+        storage.discard_read();
+        storage.append(&[4]);
+        assert_eq!(storage.bytes(), &[3, 4]);
+        assert_eq!(storage.bytes_available(), 2);
+    }
+
+    #[test]
+    fn compare_and_swap_int_only_swaps_on_a_match() {
+        let mut storage = ByteArrayStorage::new();
+
+        storage.write_bytes(&[0, 0, 0, 0, 0, 0, 0, 7]);
+        assert_eq!(storage.compare_and_swap_int(4, 7, 9).unwrap(), 7);
+        assert_eq!(storage.bytes(), &[0, 0, 0, 0, 0, 0, 0, 9]);
+
+        assert_eq!(storage.compare_and_swap_int(4, 7, 11).unwrap(), 9);
+        assert_eq!(storage.bytes(), &[0, 0, 0, 0, 0, 0, 0, 9]);
+        assert_eq!(storage.position(), 8);
+    }
+
+    #[test]
+    fn compare_and_swap_int_out_of_range() {
+        let mut storage = ByteArrayStorage::new();
+
+        storage.write_bytes(&[0; 6]);
+        assert!(storage.compare_and_swap_int(4, 0, 1).is_err());
+        assert!(storage.compare_and_swap_int(usize::MAX, 0, 1).is_err());
+        assert_eq!(storage.bytes(), &[0; 6]);
+    }
+
+    #[test]
+    fn compare_and_swap_length_only_resizes_on_a_match() {
+        let mut storage = ByteArrayStorage::new();
+
+        storage.write_bytes(&[1, 2]);
+        assert_eq!(storage.compare_and_swap_length(3, 8), 2);
+        assert_eq!(storage.len(), 2);
+
+        assert_eq!(storage.compare_and_swap_length(2, 4), 2);
+        assert_eq!(storage.bytes(), &[1, 2, 0, 0]);
+    }
+
+    #[test]
+    fn endian_applies_to_later_values_only() {
+        let mut storage = ByteArrayStorage::new();
+
+        storage.write_u16(0x0102);
+        storage.write_u32(0x0304_0506);
+        storage.write_f64(1.0);
+        storage.set_endian(Endian::Little);
+        storage.write_u16(0x0102);
+        assert_eq!(
+            storage.bytes(),
+            &[1, 2, 3, 4, 5, 6, 0x3F, 0xF0, 0, 0, 0, 0, 0, 0, 2, 1]
+        );
+
+        // Reading big-endian values as little-endian swaps their bytes.
+        storage.set_position(0);
+        assert_eq!(storage.read_u16().unwrap(), 0x0201);
+        assert_eq!(storage.read_u32().unwrap(), 0x0605_0403);
+        assert_eq!(storage.read_f64().unwrap(), f64::from_bits(0xF03F));
+        assert_eq!(storage.read_u16().unwrap(), 0x0102);
+
+        storage.set_endian(Endian::Big);
+        storage.set_position(0);
+        assert_eq!(storage.read_u16().unwrap(), 0x0102);
+        assert_eq!(storage.read_u32().unwrap(), 0x0304_0506);
+        assert_eq!(storage.read_f64().unwrap(), 1.0);
+        assert_eq!(storage.read_u16().unwrap(), 0x0201);
+    }
+
+    #[test]
+    fn clear_empties_and_rewinds() {
+        let mut storage = ByteArrayStorage::new();
+
+        storage.write_bytes(&[1, 2, 3]);
+        storage.clear();
+        assert_eq!(storage.len(), 0);
+        assert_eq!(storage.position(), 0);
+
+        storage.write_bytes(&[4]);
+        assert_eq!(storage.bytes(), &[4]);
+    }
+
+    #[test]
+    fn deflate_inflate_round_trip() {
+        let text = b"raw deflate, raw deflate, raw deflate";
+        let mut storage = ByteArrayStorage::new();
+
+        storage.write_bytes(text);
+        storage.deflate();
+        assert_ne!(storage.bytes(), &text[..]);
+        assert_eq!(storage.position(), storage.len());
+
+        // Raw DEFLATE has no zlib header, so a zlib decoder rejects it.
+        let mut zlib = flate2::bufread::ZlibDecoder::new(storage.bytes());
+        assert!(zlib.read_to_end(&mut Vec::new()).is_err());
+
+        storage.inflate().unwrap();
+        assert_eq!(storage.bytes(), &text[..]);
+        assert_eq!(storage.position(), 0);
+    }
+
+    #[test]
+    fn inflate_rejects_data_that_is_not_deflated() {
+        let mut storage = ByteArrayStorage::new();
+
+        storage.write_bytes(&[0xFF; 8]);
+        assert!(storage.inflate().is_err());
+        assert_eq!(storage.bytes(), &[0xFF; 8]);
+        assert_eq!(storage.position(), 8);
+    }
+}
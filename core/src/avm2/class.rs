@@ -75,7 +75,13 @@ pub struct Class<'gc> {
 /// Find traits in a list of traits matching a name.
 ///
 /// This function also enforces final/override bits on the traits, and will
-/// raise `VerifyError`s as needed.
+/// raise `VerifyError`s as needed. An override must be the same kind of trait
+/// as the definition it replaces, with the same signature.
+///
+/// A getter never overrides a setter, nor the other way around. When a
+/// subclass overrides only one half of an inherited accessor, both halves
+/// end up in `known_traits`, and installing them in order pairs the new half
+/// with the inherited one.
 ///
 /// TODO: This is an O(n^2) algorithm, it sucks.
 fn do_trait_lookup<'gc>(
@@ -99,6 +105,23 @@ fn do_trait_lookup<'gc>(
                 if !trait_entry.is_override() {
                     return Err("Definition override is not marked as override".into());
                 }
+
+                let signature_matches = match (&trait_entry.kind(), &known_trait.kind()) {
+                    (TraitKind::Method { method, .. }, TraitKind::Method { method: base, .. })
+                    | (TraitKind::Getter { method, .. }, TraitKind::Getter { method: base, .. })
+                    | (TraitKind::Setter { method, .. }, TraitKind::Setter { method: base, .. }) => {
+                        method.signature_matches(base)
+                    }
+                    _ => false,
+                };
+
+                if !signature_matches {
+                    return Err(format!(
+                        "VerifyError: Error #1053: Illegal override of {}.",
+                        name.local_name()
+                    )
+                    .into());
+                }
             }
 
             known_traits.push(trait_entry.clone());
@@ -339,6 +362,11 @@ impl<'gc> Class<'gc> {
         self.instance_traits.push(my_trait);
     }
 
+    /// Get the traits this class defines on its instances.
+    pub fn instance_traits(&self) -> &[Trait<'gc>] {
+        &self.instance_traits
+    }
+
     /// Given a name, append instance traits matching the name to a list of
     /// known traits.
     ///
@@ -2,13 +2,16 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::names::{Multiname, QName};
-use crate::avm2::object::TObject;
+use crate::avm2::object::{Object, TObject};
 use crate::avm2::script::Script;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use gc_arena::{Collect, GcCell, MutationContext};
 use std::collections::HashMap;
 
+/// The smallest `ByteArray` that can be used as a domain's memory.
+pub const MIN_DOMAIN_MEMORY_LENGTH: usize = 1024;
+
 /// Represents a set of scripts and movies that share traits across different
 /// script-global scopes.
 #[derive(Copy, Clone, Debug, Collect)]
@@ -23,6 +26,9 @@ struct DomainData<'gc> {
 
     /// The parent domain.
     parent: Option<Domain<'gc>>,
+
+    /// The `ByteArray` that the domain memory opcodes read and write.
+    domain_memory: Option<Object<'gc>>,
 }
 
 impl<'gc> Domain<'gc> {
@@ -36,6 +42,7 @@ impl<'gc> Domain<'gc> {
             DomainData {
                 defs: HashMap::new(),
                 parent: None,
+                domain_memory: None,
             },
         ))
     }
@@ -47,6 +54,7 @@ impl<'gc> Domain<'gc> {
             DomainData {
                 defs: HashMap::new(),
                 parent: Some(parent),
+                domain_memory: None,
             },
         ))
     }
@@ -56,6 +64,16 @@ impl<'gc> Domain<'gc> {
         self.0.read().parent
     }
 
+    /// Get the `ByteArray` used as this domain's memory, if one was set.
+    pub fn domain_memory(self) -> Option<Object<'gc>> {
+        self.0.read().domain_memory
+    }
+
+    /// Set the `ByteArray` used as this domain's memory.
+    pub fn set_domain_memory(self, mc: MutationContext<'gc, '_>, memory: Option<Object<'gc>>) {
+        self.0.write(mc).domain_memory = memory;
+    }
+
     /// Determine if something has been defined within the current domain.
     pub fn has_definition(self, name: QName<'gc>) -> bool {
         let read = self.0.read();
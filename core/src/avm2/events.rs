@@ -1,11 +1,19 @@
 //! Core event structure
 
+use crate::avm2::activation::Activation;
+use crate::avm2::names::{Namespace, QName};
 use crate::avm2::object::{Object, TObject};
 use crate::avm2::string::AvmString;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::display_object::TDisplayObject;
 use gc_arena::Collect;
 use std::collections::{BTreeMap, HashMap};
 use std::hash::{Hash, Hasher};
 
+/// Namespace for the internal properties of `EventDispatcher` instances.
+pub const NS_EVENT_DISPATCHER: &str = "https://ruffle.rs/AS3/impl/EventDispatcher/";
+
 /// Which phase of event dispatch is currently occurring.
 #[derive(Copy, Clone, Collect, Debug, PartialEq, Eq)]
 #[collect(require_static)]
@@ -46,6 +54,92 @@ pub enum PropagationMode {
     StopImmediatePropagation,
 }
 
+/// Additional data carried by particular subclasses of `Event`.
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+pub enum EventData<'gc> {
+    /// A plain `Event` with no extra fields.
+    Empty,
+
+    /// A `TextEvent` and the text it carries.
+    Text { text: AvmString<'gc> },
+
+    /// A `DataEvent` and the data it carries, which doubles as its `text`.
+    Data { data: AvmString<'gc> },
+
+    /// An `ErrorEvent`, with its message and the number of the error.
+    Error { text: AvmString<'gc>, error_id: i32 },
+
+    /// A `NetStatusEvent` and the `info` object describing the status change.
+    NetStatus { info: Object<'gc> },
+
+    /// A `SyncEvent` and the list of changes to its shared object.
+    Sync { change_list: Option<Object<'gc>> },
+
+    /// A `ProgressEvent` and the load progress it reports.
+    Progress { bytes_loaded: f64, bytes_total: f64 },
+
+    /// An `HTTPStatusEvent`, with the status code of a response and, where
+    /// known, the URL and headers it came with.
+    HttpStatus {
+        status: i32,
+        response_url: Option<AvmString<'gc>>,
+        response_headers: Option<Object<'gc>>,
+    },
+
+    /// A `ContextMenuEvent`, with the object that was right-clicked and the
+    /// object whose menu is shown for it.
+    ContextMenu {
+        mouse_target: Option<Object<'gc>>,
+        context_menu_owner: Option<Object<'gc>>,
+    },
+
+    /// A `MouseEvent`, with where the mouse is and how far it moved since the
+    /// previous event.
+    ///
+    /// The movement is only tracked while the mouse is locked, and is zero
+    /// otherwise.
+    Mouse {
+        local_x: f64,
+        local_y: f64,
+        movement_x: f64,
+        movement_y: f64,
+    },
+
+    /// A `FullScreenEvent`, with whether the stage is now full screen and
+    /// whether keyboard input is allowed there.
+    FullScreen {
+        full_screen: bool,
+        interactive: bool,
+    },
+
+    /// A `TouchEvent`, with which touch point it is about and where that
+    /// point is.
+    Touch {
+        touch_point_id: i32,
+        is_primary_touch_point: bool,
+        local_x: f64,
+        local_y: f64,
+        stage_x: f64,
+        stage_y: f64,
+        pressure: f64,
+    },
+
+    /// A `GestureEvent`, with the phase of the gesture and where it is.
+    Gesture {
+        phase: Option<AvmString<'gc>>,
+        local_x: f64,
+        local_y: f64,
+    },
+
+    /// A `StageOrientationEvent`, with the orientations before and after the
+    /// stage turned.
+    StageOrientation {
+        before_orientation: Option<AvmString<'gc>>,
+        after_orientation: Option<AvmString<'gc>>,
+    },
+}
+
 /// Represents data fields of an event that can be fired on an object that
 /// implements `IEventDispatcher`.
 #[derive(Clone, Collect, Debug)]
@@ -76,6 +170,9 @@ pub struct Event<'gc> {
 
     /// The name of the event being triggered.
     event_type: AvmString<'gc>,
+
+    /// Fields specific to the class of event being triggered.
+    event_data: EventData<'gc>,
 }
 
 impl<'gc> Event<'gc> {
@@ -93,6 +190,7 @@ impl<'gc> Event<'gc> {
             event_phase: EventPhase::Bubbling,
             target: None,
             event_type: event_type.into(),
+            event_data: EventData::Empty,
         }
     }
 
@@ -174,6 +272,18 @@ impl<'gc> Event<'gc> {
     pub fn set_current_target(&mut self, current_target: Object<'gc>) {
         self.current_target = Some(current_target)
     }
+
+    pub fn event_data(&self) -> &EventData<'gc> {
+        &self.event_data
+    }
+
+    pub fn event_data_mut(&mut self) -> &mut EventData<'gc> {
+        &mut self.event_data
+    }
+
+    pub fn set_event_data(&mut self, event_data: EventData<'gc>) {
+        self.event_data = event_data;
+    }
 }
 
 /// A set of handlers organized by event type, priority, and order added.
@@ -338,3 +448,152 @@ impl<'gc> Hash for EventHandler<'gc> {
         self.handler.as_ptr().hash(state);
     }
 }
+
+/// Retrieve the parent of a given `EventDispatcher`.
+///
+/// `EventDispatcher` does not provide a generic way for it's subclasses to
+/// indicate ancestry. Instead, only specific event targets provide a hierarchy
+/// to traverse. If no hierarchy is available, this returns `None`, as if the
+/// target had no parent.
+pub fn parent_of(target: Object<'_>) -> Option<Object<'_>> {
+    if let Some(dobj) = target.as_display_object() {
+        if let Some(dparent) = dobj.parent() {
+            if let Value::Object(parent) = dparent.object2() {
+                return Some(parent);
+            }
+        }
+    }
+
+    None
+}
+
+/// Call all of the event handlers on a given target.
+///
+/// The `target` is the current target of the `event`. `event` must be a valid
+/// `EventObject`, or this function will panic. You must have already set the
+/// event's phase to match what targets you are dispatching to, or you will
+/// call the wrong handlers.
+pub fn dispatch_event_to_target<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut target: Object<'gc>,
+    event: Object<'gc>,
+) -> Result<(), Error> {
+    let dispatch_list = target
+        .get_property(
+            target,
+            &QName::new(Namespace::private(NS_EVENT_DISPATCHER), "dispatch_list"),
+            activation,
+        )?
+        .coerce_to_object(activation)?;
+
+    let mut evtmut = event.as_event_mut(activation.context.gc_context).unwrap();
+    let name = evtmut.event_type();
+    let use_capture = evtmut.phase() == EventPhase::Capturing;
+
+    evtmut.set_current_target(target);
+
+    drop(evtmut);
+
+    let handlers: Vec<Object<'gc>> = dispatch_list
+        .as_dispatch_mut(activation.context.gc_context)
+        .ok_or_else(|| Error::from("Internal dispatch list is missing during dispatch!"))?
+        .iter_event_handlers(name, use_capture)
+        .collect();
+
+    for handler in handlers.iter() {
+        if event
+            .as_event()
+            .unwrap()
+            .is_propagation_stopped_immediately()
+        {
+            break;
+        }
+
+        handler.call(
+            activation.global_scope().coerce_to_object(activation).ok(),
+            &[event.into()],
+            activation,
+            None,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Dispatch an event object on an `EventDispatcher`, running the capture,
+/// target, and bubbling phases in turn.
+///
+/// `event` must be a valid `EventObject`, or this function will panic. Yields
+/// `true` if the event's default action was not cancelled.
+pub fn dispatch_event<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+    event: Object<'gc>,
+) -> Result<bool, Error> {
+    let target = this
+        .get_property(
+            this,
+            &QName::new(Namespace::private(NS_EVENT_DISPATCHER), "target"),
+            activation,
+        )?
+        .coerce_to_object(activation)
+        .ok()
+        .unwrap_or(this);
+
+    let mut ancestor_list = Vec::new();
+    let mut parent = parent_of(target);
+    while let Some(par) = parent {
+        ancestor_list.push(par);
+        parent = parent_of(par);
+    }
+
+    // The stage is not the display parent of anything, but everything on the
+    // stage propagates up to it all the same.
+    let top = ancestor_list.last().copied().unwrap_or(target);
+    if let (Some(stage), Some(top)) = (activation.avm2().stage(), top.as_display_object()) {
+        if top.is_on_stage(&activation.context) {
+            ancestor_list.push(stage);
+        }
+    }
+
+    let mut evtmut = event.as_event_mut(activation.context.gc_context).unwrap();
+
+    evtmut.set_phase(EventPhase::Capturing);
+    evtmut.set_target(target);
+
+    drop(evtmut);
+
+    for ancestor in ancestor_list.iter().rev() {
+        if event.as_event().unwrap().is_propagation_stopped() {
+            break;
+        }
+
+        dispatch_event_to_target(activation, *ancestor, event)?;
+    }
+
+    event
+        .as_event_mut(activation.context.gc_context)
+        .unwrap()
+        .set_phase(EventPhase::AtTarget);
+
+    if !event.as_event().unwrap().is_propagation_stopped() {
+        dispatch_event_to_target(activation, target, event)?;
+    }
+
+    event
+        .as_event_mut(activation.context.gc_context)
+        .unwrap()
+        .set_phase(EventPhase::Bubbling);
+
+    if event.as_event().unwrap().is_bubbling() {
+        for ancestor in ancestor_list.iter() {
+            if event.as_event().unwrap().is_propagation_stopped() {
+                break;
+            }
+
+            dispatch_event_to_target(activation, *ancestor, event)?;
+        }
+    }
+
+    Ok(!event.as_event().unwrap().is_cancelled())
+}
@@ -25,6 +25,12 @@ pub struct BytecodeExecutable<'gc> {
     /// If `None`, then the receiver provided by the caller is used. A
     /// `Some` value indicates a bound executable.
     receiver: Option<Object<'gc>>,
+
+    /// Whether calls are checked against the method's parameter count.
+    ///
+    /// Function closures accept any number of arguments; missing ones are
+    /// `undefined` and extra ones are dropped.
+    checks_argument_count: bool,
 }
 
 /// Represents code that can be executed by some means.
@@ -64,6 +70,30 @@ impl<'gc> Executable<'gc> {
                     method,
                     scope,
                     receiver,
+                    checks_argument_count: true,
+                },
+            )),
+        }
+    }
+
+    /// Convert a method into the executable of a function closure.
+    ///
+    /// Unlike class and script methods, closures are never bound, and do not
+    /// check how many arguments they are called with.
+    pub fn from_closure(
+        method: Method<'gc>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+        mc: MutationContext<'gc, '_>,
+    ) -> Self {
+        match method {
+            Method::Native(nf) => Self::Native(nf, None),
+            Method::Entry(method) => Self::Action(Gc::allocate(
+                mc,
+                BytecodeExecutable {
+                    method,
+                    scope,
+                    receiver: None,
+                    checks_argument_count: false,
                 },
             )),
         }
@@ -100,6 +130,11 @@ impl<'gc> Executable<'gc> {
             }
             Executable::Action(bm) => {
                 let receiver = bm.receiver.or(unbound_reciever);
+                if bm.checks_argument_count {
+                    bm.method
+                        .check_argument_count(arguments.len(), activation.context.gc_context)?;
+                }
+
                 let mut activation = Activation::from_method(
                     activation.context.reborrow(),
                     bm.method,
@@ -23,6 +23,7 @@ mod flash;
 mod function;
 mod global_scope;
 mod int;
+mod json;
 mod math;
 mod namespace;
 mod number;
@@ -97,9 +98,42 @@ pub struct SystemPrototypes<'gc> {
     pub scene: Object<'gc>,
     pub application_domain: Object<'gc>,
     pub event: Object<'gc>,
+    pub text_event: Object<'gc>,
+    pub data_event: Object<'gc>,
+    pub error_event: Object<'gc>,
+    pub net_status_event: Object<'gc>,
+    pub sync_event: Object<'gc>,
+    pub progress_event: Object<'gc>,
+    pub http_status_event: Object<'gc>,
+    pub context_menu_event: Object<'gc>,
+    pub mouse_event: Object<'gc>,
+    pub full_screen_event: Object<'gc>,
+    pub touch_event: Object<'gc>,
+    pub gesture_event: Object<'gc>,
+    pub stage_orientation_event: Object<'gc>,
+    pub stage_3d: Object<'gc>,
     pub video: Object<'gc>,
     pub xml: Object<'gc>,
     pub xml_list: Object<'gc>,
+    pub bytearray: Object<'gc>,
+    pub proxy: Object<'gc>,
+    pub graphics: Object<'gc>,
+    pub loaderinfo: Object<'gc>,
+    pub matrix: Object<'gc>,
+    pub matrix3d: Object<'gc>,
+    pub perspective_projection: Object<'gc>,
+    pub point: Object<'gc>,
+    pub rectangle: Object<'gc>,
+    pub transform: Object<'gc>,
+    pub vector3d: Object<'gc>,
+    pub context_menu: Object<'gc>,
+    pub context_menu_item: Object<'gc>,
+    pub context_menu_built_in_items: Object<'gc>,
+    pub worker: Object<'gc>,
+    pub message_channel: Object<'gc>,
+    pub text_line: Object<'gc>,
+    pub morph_shape: Object<'gc>,
+    pub text_field: Object<'gc>,
 }
 
 impl<'gc> SystemPrototypes<'gc> {
@@ -133,9 +167,42 @@ impl<'gc> SystemPrototypes<'gc> {
             scene: empty,
             application_domain: empty,
             event: empty,
+            text_event: empty,
+            data_event: empty,
+            error_event: empty,
+            net_status_event: empty,
+            sync_event: empty,
+            progress_event: empty,
+            http_status_event: empty,
+            context_menu_event: empty,
+            mouse_event: empty,
+            full_screen_event: empty,
+            touch_event: empty,
+            gesture_event: empty,
+            stage_orientation_event: empty,
+            stage_3d: empty,
             video: empty,
             xml: empty,
             xml_list: empty,
+            bytearray: empty,
+            proxy: empty,
+            graphics: empty,
+            loaderinfo: empty,
+            matrix: empty,
+            matrix3d: empty,
+            perspective_projection: empty,
+            point: empty,
+            rectangle: empty,
+            transform: empty,
+            vector3d: empty,
+            context_menu: empty,
+            context_menu_item: empty,
+            context_menu_built_in_items: empty,
+            worker: empty,
+            message_channel: empty,
+            text_line: empty,
+            morph_shape: empty,
+            text_field: empty,
         }
     }
 }
@@ -407,6 +474,7 @@ pub fn load_player_globals<'gc>(
         domain,
         script,
     )?;
+    number::fill_proto(mc, sp.number, fn_proto);
     sp.int = class(
         activation,
         int::create_class(mc),
@@ -414,6 +482,7 @@ pub fn load_player_globals<'gc>(
         domain,
         script,
     )?;
+    number::fill_proto(mc, sp.int, fn_proto);
     sp.uint = class(
         activation,
         uint::create_class(mc),
@@ -421,6 +490,7 @@ pub fn load_player_globals<'gc>(
         domain,
         script,
     )?;
+    number::fill_proto(mc, sp.uint, fn_proto);
     sp.namespace = class(
         activation,
         namespace::create_class(mc),
@@ -438,7 +508,7 @@ pub fn load_player_globals<'gc>(
 
     // At this point we have to hide the fact that we had to create the player
     // globals scope *before* the `Object` class
-    gs.set_proto(mc, sp.global);
+    gs.set_proto(mc, sp.global)?;
 
     activation.context.avm2.system_prototypes = Some(sp);
 
@@ -457,6 +527,13 @@ pub fn load_player_globals<'gc>(
         domain,
         script,
     )?;
+    class(
+        activation,
+        json::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
 
     activation
         .context
@@ -500,6 +577,34 @@ pub fn load_player_globals<'gc>(
         domain,
         script,
     )?;
+    class(
+        activation,
+        flash::system::capabilities::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::system::imeconversionmode::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::system::loadercontext::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::system::system::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
 
     // package `flash.events`
     activation
@@ -522,40 +627,62 @@ pub fn load_player_globals<'gc>(
         domain,
         script,
     )?;
-    class(
+    let mut eventdispatcher_proto = class(
         activation,
         flash::events::eventdispatcher::create_class(mc),
         implicit_deriver,
         domain,
         script,
     )?;
-
-    // package `flash.display`
-    class(
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .text_event = class(
         activation,
-        flash::display::displayobject::create_class(mc),
-        stage_deriver,
+        flash::events::textevent::create_class(mc),
+        flash::events::event::event_deriver,
         domain,
         script,
     )?;
-    class(
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .data_event = class(
         activation,
-        flash::display::interactiveobject::create_class(mc),
-        implicit_deriver,
+        flash::events::dataevent::create_class(mc),
+        flash::events::event::event_deriver,
         domain,
         script,
     )?;
-    class(
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .net_status_event = class(
         activation,
-        flash::display::displayobjectcontainer::create_class(mc),
-        implicit_deriver,
+        flash::events::netstatusevent::create_class(mc),
+        flash::events::event::event_deriver,
         domain,
         script,
     )?;
-    class(
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .sync_event = class(
         activation,
-        flash::display::sprite::create_class(mc),
-        implicit_deriver,
+        flash::events::syncevent::create_class(mc),
+        flash::events::event::event_deriver,
         domain,
         script,
     )?;
@@ -565,10 +692,10 @@ pub fn load_player_globals<'gc>(
         .system_prototypes
         .as_mut()
         .unwrap()
-        .movieclip = class(
+        .progress_event = class(
         activation,
-        flash::display::movieclip::create_class(mc),
-        implicit_deriver,
+        flash::events::progressevent::create_class(mc),
+        flash::events::event::event_deriver,
         domain,
         script,
     )?;
@@ -578,9 +705,115 @@ pub fn load_player_globals<'gc>(
         .system_prototypes
         .as_mut()
         .unwrap()
-        .framelabel = class(
+        .http_status_event = class(
         activation,
-        flash::display::framelabel::create_class(mc),
+        flash::events::httpstatusevent::create_class(mc),
+        flash::events::event::event_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .context_menu_event = class(
+        activation,
+        flash::events::contextmenuevent::create_class(mc),
+        flash::events::event::event_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .mouse_event = class(
+        activation,
+        flash::events::mouseevent::create_class(mc),
+        flash::events::event::event_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .full_screen_event = class(
+        activation,
+        flash::events::fullscreenevent::create_class(mc),
+        flash::events::event::event_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .error_event = class(
+        activation,
+        flash::events::errorevent::create_class(mc),
+        flash::events::event::event_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .touch_event = class(
+        activation,
+        flash::events::touchevent::create_class(mc),
+        flash::events::event::event_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .gesture_event = class(
+        activation,
+        flash::events::gestureevent::create_class(mc),
+        flash::events::event::event_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .stage_orientation_event = class(
+        activation,
+        flash::events::stageorientationevent::create_class(mc),
+        flash::events::event::event_deriver,
+        domain,
+        script,
+    )?;
+
+    // package `flash.system`, continued: these need `EventDispatcher`
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .worker = class(
+        activation,
+        flash::system::worker::create_class(mc),
         implicit_deriver,
         domain,
         script,
@@ -591,28 +824,643 @@ pub fn load_player_globals<'gc>(
         .system_prototypes
         .as_mut()
         .unwrap()
-        .scene = class(
+        .message_channel = class(
         activation,
-        flash::display::scene::create_class(mc),
+        flash::system::messagechannel::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::system::messagechannelstate::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::system::workerdomain::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::system::workerstate::create_class(mc),
         implicit_deriver,
         domain,
         script,
     )?;
 
-    // package `flash.media`
+    // package `flash.display`
+    class(
+        activation,
+        flash::display::displayobject::create_class(mc),
+        stage_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::interactiveobject::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::displayobjectcontainer::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
     activation
         .context
         .avm2
         .system_prototypes
         .as_mut()
         .unwrap()
-        .video = class(
+        .graphics = class(
         activation,
-        flash::media::video::create_class(mc),
+        flash::display::graphics::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::sprite::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .morph_shape = class(
+        activation,
+        flash::display::morphshape::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    let stage_proto = class(
+        activation,
+        flash::display::stage::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    let stage = ScriptObject::object(mc, stage_proto);
+    // Content can't construct the stage, but it still needs to dispatch events.
+    eventdispatcher_proto
+        .get_property(
+            eventdispatcher_proto,
+            &QName::new(Namespace::public(), "constructor"),
+            activation,
+        )?
+        .coerce_to_object(activation)?
+        .call(Some(stage), &[], activation, Some(eventdispatcher_proto))?;
+    activation.context.avm2.stage = Some(stage);
+    class(
+        activation,
+        flash::display::stagedisplaystate::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::stageorientation::create_class(mc),
         implicit_deriver,
         domain,
         script,
     )?;
+    class(
+        activation,
+        flash::display::stagescalemode::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::stageaspectratio::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::focusdirection::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .movieclip = class(
+        activation,
+        flash::display::movieclip::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .framelabel = class(
+        activation,
+        flash::display::framelabel::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .scene = class(
+        activation,
+        flash::display::scene::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .loaderinfo = class(
+        activation,
+        flash::display::loaderinfo::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::loader::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::nativewindow::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .stage_3d = class(
+        activation,
+        flash::display::stage3d::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+
+    // package `flash.display3D`
+    class(
+        activation,
+        flash::display3d::context3d::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+
+    // package `flash.desktop`
+    class(
+        activation,
+        flash::desktop::nativeapplication::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+
+    // package `flash.accessibility`
+    class(
+        activation,
+        flash::accessibility::accessibility::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::accessibility::accessibilityproperties::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+
+    // package `flash.geom`
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .matrix = class(
+        activation,
+        flash::geom::matrix::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .matrix3d = class(
+        activation,
+        flash::geom::matrix3d::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .perspective_projection = class(
+        activation,
+        flash::geom::perspectiveprojection::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .point = class(
+        activation,
+        flash::geom::point::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .rectangle = class(
+        activation,
+        flash::geom::rectangle::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .transform = class(
+        activation,
+        flash::geom::transform::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::geom::utils3d::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .vector3d = class(
+        activation,
+        flash::geom::vector3d::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+
+    // package `flash.media`
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .video = class(
+        activation,
+        flash::media::video::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+
+    // package `flash.net`
+    class(
+        activation,
+        flash::net::netconnection::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::net::netstream::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::net::responder::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::net::sharedobject::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::net::urlstream::create_class(mc),
+        flash::utils::bytearray::bytearray_deriver,
+        domain,
+        script,
+    )?;
+    function(
+        mc,
+        "flash.net",
+        "sendToURL",
+        flash::net::send_to_url,
+        fn_proto,
+        domain,
+        script,
+    )?;
+
+    // package `flash.text`
+    class(
+        activation,
+        flash::text::antialiastype::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::text::gridfittype::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .text_field = class(
+        activation,
+        flash::text::textfield::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+
+    // package `flash.text.engine`
+    class(
+        activation,
+        flash::text::engine::fontdescription::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::text::engine::elementformat::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::text::engine::contentelement::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::text::engine::textelement::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::text::engine::textblock::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .text_line = class(
+        activation,
+        flash::text::engine::textline::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+
+    // package `flash.ui`
+    class(
+        activation,
+        flash::ui::keyboard::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::ui::mouse::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::ui::mousecursor::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::ui::multitouch::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::ui::multitouchinputmode::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .context_menu_built_in_items = class(
+        activation,
+        flash::ui::contextmenubuiltinitems::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .context_menu_item = class(
+        activation,
+        flash::ui::contextmenuitem::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .context_menu = class(
+        activation,
+        flash::ui::contextmenu::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+
+    // package `flash.utils`
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .bytearray = class(
+        activation,
+        flash::utils::bytearray::create_class(mc),
+        flash::utils::bytearray::bytearray_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::utils::endian::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .proxy = class(
+        activation,
+        flash::utils::proxy::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    function(
+        mc,
+        "flash.utils",
+        "setInterval",
+        flash::utils::set_interval,
+        fn_proto,
+        domain,
+        script,
+    )?;
+    function(
+        mc,
+        "flash.utils",
+        "setTimeout",
+        flash::utils::set_timeout,
+        fn_proto,
+        domain,
+        script,
+    )?;
+    function(
+        mc,
+        "flash.utils",
+        "clearInterval",
+        flash::utils::clear_interval,
+        fn_proto,
+        domain,
+        script,
+    )?;
+    function(
+        mc,
+        "flash.utils",
+        "clearTimeout",
+        flash::utils::clear_interval,
+        fn_proto,
+        domain,
+        script,
+    )?;
 
     Ok(())
 }
@@ -10,6 +10,7 @@ use crate::avm2::string::AvmString;
 use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
+use crate::string_utils;
 use bitflags::bitflags;
 use gc_arena::{GcCell, MutationContext};
 use std::cmp::{min, Ordering};
@@ -123,7 +124,14 @@ pub fn concat<'gc>(
         .unwrap_or_else(|| ArrayStorage::new(0));
 
     for arg in args {
-        if let Some(other_array) = arg.coerce_to_object(activation)?.as_array_storage() {
+        // Only arrays are spliced in, holes and all; anything else (including
+        // `null` and `undefined`) is appended as a single element.
+        let other_array = match arg {
+            Value::Object(other) => other.as_array_storage(),
+            _ => None,
+        };
+
+        if let Some(other_array) = other_array {
             base_array.append(&other_array);
         } else {
             base_array.push(arg.clone());
@@ -175,20 +183,21 @@ where
             let string_separator = separator.coerce_to_string(activation)?;
             let mut accum = Vec::with_capacity(array.length());
 
+            activation.context.avm2.join_stack.push(this);
+
+            let mut joined = Ok(());
             for (i, item) in array.iter().enumerate() {
-                let item = resolve_array_hole(activation, this, i, item)?;
+                joined = join_item(activation, this, i, item, &mut conv)
+                    .map(|item_string| accum.push(item_string));
 
-                if matches!(item, Value::Undefined) || matches!(item, Value::Null) {
-                    accum.push("".into());
-                } else {
-                    accum.push(
-                        conv(item, activation)?
-                            .coerce_to_string(activation)?
-                            .to_string(),
-                    );
+                if joined.is_err() {
+                    break;
                 }
             }
 
+            activation.context.avm2.join_stack.pop();
+            joined?;
+
             return Ok(AvmString::new(
                 activation.context.gc_context,
                 accum.join(&string_separator),
@@ -200,6 +209,40 @@ where
     Ok(Value::Undefined)
 }
 
+/// Stringify one item of an array being joined.
+///
+/// Empty items, and items that are arrays already being joined further up
+/// the stack, stringify as the empty string.
+fn join_item<'gc, 'a, 'ctxt, C>(
+    activation: &mut Activation<'a, 'gc, 'ctxt>,
+    this: Object<'gc>,
+    index: usize,
+    item: Option<Value<'gc>>,
+    conv: &mut C,
+) -> Result<String, Error>
+where
+    C: for<'b> FnMut(Value<'gc>, &'b mut Activation<'a, 'gc, 'ctxt>) -> Result<Value<'gc>, Error>,
+{
+    let item = resolve_array_hole(activation, this, index, item)?;
+
+    match item {
+        Value::Undefined | Value::Null => Ok("".into()),
+        Value::Object(object)
+            if activation
+                .context
+                .avm2
+                .join_stack
+                .iter()
+                .any(|joining| Object::ptr_eq(*joining, object)) =>
+        {
+            Ok("".into())
+        }
+        item => Ok(conv(item, activation)?
+            .coerce_to_string(activation)?
+            .to_string()),
+    }
+}
+
 /// Implements `Array.join`
 pub fn join<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
@@ -321,6 +364,37 @@ impl<'gc> ArrayIter<'gc> {
             None
         }
     }
+
+    /// Get the next item in the array, as in `next`, but yielding `None` in
+    /// place of the value of a hole.
+    ///
+    /// An index is a hole if neither the array nor its prototype chain has a
+    /// property for it.
+    fn next_or_hole(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Option<Result<(u32, Option<Value<'gc>>), Error>> {
+        if self.index < self.length {
+            let i = self.index;
+            let name = QName::new(
+                Namespace::public(),
+                AvmString::new(activation.context.gc_context, i.to_string()),
+            );
+
+            self.index += 1;
+
+            Some(match self.array_object.has_property(&name) {
+                Ok(true) => self
+                    .array_object
+                    .get_property(self.array_object, &name, activation)
+                    .map(|val| (i, Some(val))),
+                Ok(false) => Ok((i, None)),
+                Err(e) => Err(e),
+            })
+        } else {
+            None
+        }
+    }
 }
 
 /// Implements `Array.forEach`
@@ -359,6 +433,9 @@ pub fn for_each<'gc>(
 }
 
 /// Implements `Array.map`
+///
+/// Holes are copied into the result without calling the callback, so it
+/// keeps the length of the original array.
 pub fn map<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
@@ -379,8 +456,14 @@ pub fn map<'gc>(
         let mut new_array = ArrayStorage::new(0);
         let mut iter = ArrayIter::new(activation, this)?;
 
-        while let Some(r) = iter.next(activation) {
-            let (i, item) = r?;
+        while let Some(r) = iter.next_or_hole(activation) {
+            let (i, item) = match r? {
+                (i, Some(item)) => (i, item),
+                (_, None) => {
+                    new_array.push_hole();
+                    continue;
+                }
+            };
             let new_item = callback.call(
                 receiver,
                 &[item, i.into(), this.into()],
@@ -398,6 +481,8 @@ pub fn map<'gc>(
 }
 
 /// Implements `Array.filter`
+///
+/// Holes are skipped without calling the callback, so the result is dense.
 pub fn filter<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
@@ -418,8 +503,11 @@ pub fn filter<'gc>(
         let mut new_array = ArrayStorage::new(0);
         let mut iter = ArrayIter::new(activation, this)?;
 
-        while let Some(r) = iter.next(activation) {
-            let (i, item) = r?;
+        while let Some(r) = iter.next_or_hole(activation) {
+            let (i, item) = match r? {
+                (i, Some(item)) => (i, item),
+                (_, None) => continue,
+            };
             let is_allowed = callback
                 .call(
                     receiver,
@@ -537,7 +625,7 @@ pub fn index_of<'gc>(
 
             for (i, val) in array.iter().enumerate() {
                 let val = resolve_array_hole(activation, this, i, val)?;
-                if i >= from as usize && val == search_val {
+                if i >= from as usize && val.strict_eq(&search_val) {
                     return Ok(i.into());
                 }
             }
@@ -566,7 +654,7 @@ pub fn last_index_of<'gc>(
 
             for (i, val) in array.iter().enumerate().rev() {
                 let val = resolve_array_hole(activation, this, i, val)?;
-                if i <= from as usize && val == search_val {
+                if i <= from as usize && val.strict_eq(&search_val) {
                     return Ok(i.into());
                 }
             }
@@ -679,7 +767,69 @@ pub fn unshift<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `Array.insertAt`
+pub fn insert_at<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let array_length = this.as_array_storage().map(|a| a.length());
+
+        if let Some(array_length) = array_length {
+            let index = resolve_index(
+                activation,
+                args.get(0).cloned().unwrap_or(Value::Undefined),
+                array_length,
+            )?;
+            let element = args.get(1).cloned().unwrap_or(Value::Undefined);
+
+            if let Some(mut array) = this.as_array_storage_mut(activation.context.gc_context) {
+                array.insert(index, element);
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Array.removeAt`
+pub fn remove_at<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let array_length = this.as_array_storage().map(|a| a.length());
+
+        if let Some(array_length) = array_length {
+            let index = resolve_index(
+                activation,
+                args.get(0).cloned().unwrap_or(Value::Undefined),
+                array_length,
+            )?;
+            if index >= array_length {
+                return Ok(Value::Undefined);
+            }
+
+            let item = this.as_array_storage().unwrap().get(index);
+            let removed = resolve_array_hole(activation, this, index, item)?;
+
+            if let Some(mut array) = this.as_array_storage_mut(activation.context.gc_context) {
+                array.remove(index);
+            }
+
+            return Ok(removed);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Resolve a possibly-negative array index to something guaranteed to be positive.
+///
+/// Negative indices count back from the end of the array, stopping at its
+/// start.
 pub fn resolve_index<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     index: Value<'gc>,
@@ -688,7 +838,7 @@ pub fn resolve_index<'gc>(
     let index = index.coerce_to_i32(activation)?;
 
     Ok(if index < 0 {
-        (length as isize).saturating_add(index as isize) as usize
+        (length as isize).saturating_add(index as isize).max(0) as usize
     } else {
         index as usize
     })
@@ -890,18 +1040,25 @@ fn compare_string_case_sensitive<'gc>(
     let string_a = a.coerce_to_string(activation)?;
     let string_b = b.coerce_to_string(activation)?;
 
-    Ok(string_a.cmp(&string_b))
+    Ok(string_utils::swf_string_cmp(&string_a, &string_b))
 }
 
+/// Case-insensitive sorts use the host language's collation, so that accented
+/// letters sort next to their base letter rather than after `z`.
 fn compare_string_case_insensitive<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     a: Value<'gc>,
     b: Value<'gc>,
 ) -> Result<Ordering, Error> {
-    let string_a = a.coerce_to_string(activation)?.to_lowercase();
-    let string_b = b.coerce_to_string(activation)?.to_lowercase();
+    let string_a = a.coerce_to_string(activation)?;
+    let string_b = b.coerce_to_string(activation)?;
 
-    Ok(string_a.cmp(&string_b))
+    Ok(string_utils::swf_string_locale_cmp(
+        &string_a,
+        &string_b,
+        &activation.context.system.language,
+        false,
+    ))
 }
 
 fn compare_numeric<'gc>(
@@ -1329,6 +1486,16 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         Method::from_builtin(unshift),
     ));
 
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "insertAt"),
+        Method::from_builtin(insert_at),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "removeAt"),
+        Method::from_builtin(remove_at),
+    ));
+
     class.write(mc).define_instance_trait(Trait::from_method(
         QName::new(Namespace::as3_namespace(), "slice"),
         Method::from_builtin(slice),
@@ -1381,3 +1548,314 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
 
     class
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::globals::object;
+    use crate::avm2::method::NativeMethod;
+    use crate::avm2::object::FunctionObject;
+    use crate::avm2::test_utils::{trace_with_avm2, with_avm2};
+
+    fn array_of<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        items: &[Value<'gc>],
+    ) -> Object<'gc> {
+        build_array(activation, ArrayStorage::from_args(items))
+            .unwrap()
+            .coerce_to_object(activation)
+            .unwrap()
+    }
+
+    fn push<'gc>(activation: &mut Activation<'_, 'gc, '_>, array: Object<'gc>, item: Object<'gc>) {
+        array
+            .as_array_storage_mut(activation.context.gc_context)
+            .unwrap()
+            .push(item.into());
+    }
+
+    fn stringify<'gc>(activation: &mut Activation<'_, 'gc, '_>, array: Object<'gc>) -> String {
+        to_string(activation, Some(array), &[])
+            .unwrap()
+            .coerce_to_string(activation)
+            .unwrap()
+            .to_string()
+    }
+
+    /// An array of `1, <hole>, 2, 3, <hole>`.
+    fn sparse_array<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Object<'gc> {
+        let mut storage = ArrayStorage::new(0);
+        storage.push(1.into());
+        storage.push_hole();
+        storage.push(2.into());
+        storage.push(3.into());
+        storage.push_hole();
+
+        build_array(activation, storage)
+            .unwrap()
+            .coerce_to_object(activation)
+            .unwrap()
+    }
+
+    /// Call `method` on `array` with a callback that traces each index it is
+    /// called for and answers with `answer`.
+    fn call_with_callback<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        method: NativeMethod<'gc>,
+        array: Object<'gc>,
+        answer: NativeMethod<'gc>,
+    ) -> Object<'gc> {
+        let fn_proto = activation.context.avm2.prototypes().function;
+        let callback =
+            FunctionObject::from_builtin(activation.context.gc_context, answer, fn_proto);
+
+        method(activation, Some(array), &[callback.into()])
+            .unwrap()
+            .coerce_to_object(activation)
+            .unwrap()
+    }
+
+    fn tenfold<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let index = args[1].coerce_to_u32(activation)?;
+        activation.context.log.avm_trace(&format!("map {}", index));
+
+        Ok((args[0].coerce_to_number(activation)? * 10.0).into())
+    }
+
+    fn is_odd<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let index = args[1].coerce_to_u32(activation)?;
+        activation
+            .context
+            .log
+            .avm_trace(&format!("filter {}", index));
+
+        Ok((args[0].coerce_to_i32(activation)? % 2 == 1).into())
+    }
+
+    #[test]
+    fn as3_push_resolves_to_the_native_method() {
+        with_avm2(|activation| {
+            let mut array = array_of(activation, &[1.into()]);
+            let as3_push = QName::new(Namespace::as3_namespace(), "push");
+            let public_push = Multiname::from(QName::new(Namespace::public(), "push"));
+
+            // Only method calls fall back to the `AS3` namespace.
+            assert_eq!(array.resolve_multiname(&public_push).unwrap(), None);
+
+            for multiname in &[Multiname::from(as3_push.clone()), public_push] {
+                let name = array.resolve_method_multiname(multiname).unwrap();
+                assert_eq!(name, Some(as3_push.clone()));
+
+                let method = array
+                    .get_property(array, &as3_push, activation)
+                    .unwrap()
+                    .coerce_to_object(activation)
+                    .unwrap();
+                method
+                    .call(Some(array), &[2.into()], activation, None)
+                    .unwrap();
+            }
+
+            assert_eq!(stringify(activation, array), "1,2,2");
+        });
+    }
+
+    #[test]
+    fn map_preserves_holes() {
+        let traces = trace_with_avm2(|activation| {
+            let array = sparse_array(activation);
+            let mapped = call_with_callback(activation, map, array, tenfold);
+            let storage = mapped.as_array_storage().unwrap();
+
+            assert_eq!(storage.length(), 5);
+            assert_eq!(storage.get(0), Some(10.0.into()));
+            assert_eq!(storage.get(1), None);
+            assert_eq!(storage.get(2), Some(20.0.into()));
+            assert_eq!(storage.get(3), Some(30.0.into()));
+            assert_eq!(storage.get(4), None);
+        });
+
+        assert_eq!(traces, vec!["map 0", "map 2", "map 3"]);
+    }
+
+    #[test]
+    fn filter_compacts_its_result() {
+        let traces = trace_with_avm2(|activation| {
+            let array = sparse_array(activation);
+            let filtered = call_with_callback(activation, filter, array, is_odd);
+            let storage = filtered.as_array_storage().unwrap();
+
+            assert_eq!(storage.length(), 2);
+            assert_eq!(storage.get(0), Some(1.into()));
+            assert_eq!(storage.get(1), Some(3.into()));
+        });
+
+        assert_eq!(traces, vec!["filter 0", "filter 2", "filter 3"]);
+    }
+
+    #[test]
+    fn concat_splices_arrays_and_appends_everything_else() {
+        with_avm2(|activation| {
+            let array = array_of(activation, &[1.into()]);
+            let inner = array_of(activation, &[4.into()]);
+            let nested = array_of(activation, &[3.into(), inner.into()]);
+
+            let result = concat(
+                activation,
+                Some(array),
+                &["two".into(), Value::Null, nested.into()],
+            )
+            .unwrap()
+            .coerce_to_object(activation)
+            .unwrap();
+            let storage = result.as_array_storage().unwrap();
+
+            // Only the outer level of `nested` is spliced in; `inner` stays
+            // an array of its own.
+            assert_eq!(storage.length(), 5);
+            assert_eq!(storage.get(0), Some(1.into()));
+            assert_eq!(storage.get(1), Some("two".into()));
+            assert_eq!(storage.get(2), Some(Value::Null));
+            assert_eq!(storage.get(3), Some(3.into()));
+            assert_eq!(storage.get(4), Some(inner.into()));
+            drop(storage);
+
+            // `concat` leaves the array it was called on alone.
+            assert_eq!(stringify(activation, array), "1");
+        });
+    }
+
+    #[test]
+    fn insert_at_counts_negative_indices_from_the_end() {
+        with_avm2(|activation| {
+            let array = array_of(activation, &[1.into(), 2.into(), 3.into()]);
+
+            insert_at(activation, Some(array), &[1.into(), "a".into()]).unwrap();
+            assert_eq!(stringify(activation, array), "1,a,2,3");
+
+            insert_at(activation, Some(array), &[(-1).into(), "b".into()]).unwrap();
+            assert_eq!(stringify(activation, array), "1,a,2,b,3");
+
+            insert_at(activation, Some(array), &[(-10).into(), "c".into()]).unwrap();
+            assert_eq!(stringify(activation, array), "c,1,a,2,b,3");
+
+            insert_at(activation, Some(array), &[10.into(), "d".into()]).unwrap();
+            assert_eq!(stringify(activation, array), "c,1,a,2,b,3,d");
+        });
+    }
+
+    #[test]
+    fn remove_at_yields_the_removed_element() {
+        with_avm2(|activation| {
+            let array = array_of(activation, &[1.into(), 2.into(), 3.into(), 4.into()]);
+
+            let removed = remove_at(activation, Some(array), &[1.into()]).unwrap();
+            assert_eq!(removed, 2.into());
+            assert_eq!(stringify(activation, array), "1,3,4");
+
+            let removed = remove_at(activation, Some(array), &[(-1).into()]).unwrap();
+            assert_eq!(removed, 4.into());
+            assert_eq!(stringify(activation, array), "1,3");
+
+            let removed = remove_at(activation, Some(array), &[5.into()]).unwrap();
+            assert_eq!(removed, Value::Undefined);
+            assert_eq!(stringify(activation, array), "1,3");
+        });
+    }
+
+    #[test]
+    fn self_referential_array_to_string_terminates() {
+        with_avm2(|activation| {
+            let array = array_of(activation, &[1.into(), 2.into()]);
+            push(activation, array, array);
+
+            assert_eq!(stringify(activation, array), "1,2,");
+
+            let joined = join(activation, Some(array), &["-".into()])
+                .unwrap()
+                .coerce_to_string(activation)
+                .unwrap();
+            assert_eq!(joined.to_string(), "1-2-");
+
+            // The guard is released once the outermost join finishes.
+            assert!(activation.context.avm2.join_stack.is_empty());
+        });
+    }
+
+    #[test]
+    fn mutually_referential_arrays_to_string_terminate() {
+        with_avm2(|activation| {
+            let first = array_of(activation, &[1.into()]);
+            let second = array_of(activation, &[2.into()]);
+            push(activation, first, second);
+            push(activation, second, first);
+
+            assert_eq!(stringify(activation, first), "1,2,");
+            assert_eq!(stringify(activation, second), "2,1,");
+        });
+    }
+
+    #[test]
+    fn only_set_indices_and_dynamic_properties_are_enumerable() {
+        with_avm2(|activation| {
+            let mut array = sparse_array(activation);
+            array
+                .set_property(
+                    array,
+                    &QName::new(Namespace::public(), "extra"),
+                    "dynamic".into(),
+                    activation,
+                )
+                .unwrap();
+
+            for (name, expected) in &[
+                ("0", true),
+                ("1", false),
+                ("length", false),
+                ("extra", true),
+            ] {
+                assert_eq!(
+                    object::property_is_enumerable(activation, Some(array), &[(*name).into()])
+                        .unwrap(),
+                    (*expected).into(),
+                    "{}",
+                    name
+                );
+            }
+        });
+    }
+
+    #[test]
+    fn strings_sort_by_utf16_code_units() {
+        with_avm2(|activation| {
+            // The surrogates of U+1F600 sort before U+E000, but after U+D7FF.
+            let array = array_of(
+                activation,
+                &[
+                    "\u{E000}".into(),
+                    "\u{1F600}".into(),
+                    "\u{D7FF}".into(),
+                    "z".into(),
+                ],
+            );
+            sort(activation, Some(array), &[]).unwrap();
+            assert_eq!(
+                stringify(activation, array),
+                "z,\u{D7FF},\u{1F600},\u{E000}"
+            );
+
+            let less = Value::from("\u{1F600}")
+                .abstract_lt(&"\u{E000}".into(), activation)
+                .unwrap();
+            assert_eq!(less, Some(true));
+        });
+    }
+}
@@ -1,6 +1,14 @@
 //! `flash` namespace
 
+pub mod accessibility;
+pub mod desktop;
 pub mod display;
+pub mod display3d;
 pub mod events;
+pub mod geom;
 pub mod media;
+pub mod net;
 pub mod system;
+pub mod text;
+pub mod ui;
+pub mod utils;
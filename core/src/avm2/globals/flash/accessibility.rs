@@ -0,0 +1,5 @@
+//! `flash.accessibility` namespace
+
+#[allow(clippy::module_inception)]
+pub mod accessibility;
+pub mod accessibilityproperties;
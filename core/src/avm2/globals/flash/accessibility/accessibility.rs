@@ -0,0 +1,132 @@
+//! `flash.accessibility.Accessibility` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::Object;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.accessibility.Accessibility`'s instance constructor.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Err("ArgumentError: Error #2012: Accessibility class cannot be instantiated.".into())
+}
+
+/// Implements `flash.accessibility.Accessibility`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `active`'s getter.
+pub fn active<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(activation.context.ui.is_screen_reader_active().into())
+}
+
+/// Implements `Accessibility.updateProperties`.
+///
+/// Accessibility properties are not passed on to the host, so there is
+/// nothing to update.
+pub fn update_properties<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct `Accessibility`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.accessibility"), "Accessibility"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED | ClassAttributes::FINAL);
+
+    write.define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "active"),
+        Method::from_builtin(active),
+    ));
+    write.define_class_trait(Trait::from_method(
+        QName::new(Namespace::public(), "updateProperties"),
+        Method::from_builtin(update_properties),
+    ));
+
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::object::StageObject;
+    use crate::avm2::test_utils::{construct, get, set, with_avm2};
+    use crate::display_object::MovieClip;
+    use crate::tag_utils::{SwfMovie, SwfSlice};
+    use std::sync::Arc;
+
+    #[test]
+    fn accessibility_properties_are_kept_on_display_objects() {
+        with_avm2(|activation| {
+            let properties = construct(
+                activation,
+                "flash.accessibility",
+                "AccessibilityProperties",
+                &[],
+            );
+
+            assert_eq!(get(activation, properties, "name"), "".into());
+            assert_eq!(get(activation, properties, "silent"), false.into());
+            set(activation, properties, "name", "Play button".into());
+            set(activation, properties, "silent", true.into());
+
+            let movie = Arc::new(SwfMovie::empty(10));
+            let mc = activation.context.gc_context;
+            let clip = MovieClip::new(SwfSlice::empty(movie), mc);
+            let movieclip_proto = activation.context.avm2.prototypes().movieclip;
+            let clip: Object<'_> =
+                StageObject::for_display_object(mc, clip.into(), movieclip_proto).into();
+
+            assert_eq!(
+                get(activation, clip, "accessibilityProperties"),
+                Value::Null
+            );
+            set(
+                activation,
+                clip,
+                "accessibilityProperties",
+                properties.into(),
+            );
+            let read = get(activation, clip, "accessibilityProperties")
+                .coerce_to_object(activation)
+                .unwrap();
+            assert_eq!(get(activation, read, "name"), "Play button".into());
+            assert_eq!(get(activation, read, "silent"), true.into());
+
+            assert_eq!(active(activation, None, &[]).unwrap(), false.into());
+            assert_eq!(
+                update_properties(activation, None, &[]).unwrap(),
+                Value::Undefined
+            );
+        });
+    }
+}
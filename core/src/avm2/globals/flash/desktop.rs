@@ -0,0 +1,3 @@
+//! `flash.desktop` namespace
+
+pub mod nativeapplication;
@@ -0,0 +1,255 @@
+//! `flash.desktop.NativeApplication` builtin/prototype
+//!
+//! Ruffle never runs content as an AIR application, so the application
+//! object only reports defaults: it has no ID and no open windows.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::class::Class;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{ArrayObject, Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.desktop.NativeApplication`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.desktop.NativeApplication`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `NativeApplication.nativeApplication`.
+///
+/// The application object is created the first time it is asked for, and
+/// kept on the class from then on.
+pub fn native_application<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let name = QName::new(Namespace::Private("ruffle".into()), "nativeApplication");
+        let existing = this.get_property(this, &name, activation)?;
+        if let Value::Object(_) = existing {
+            return Ok(existing);
+        }
+
+        let mut proto = this
+            .get_property(
+                this,
+                &QName::new(Namespace::public(), "prototype"),
+                activation,
+            )?
+            .coerce_to_object(activation)?;
+        let application = proto.construct(activation, &[])?;
+        let constr = proto
+            .get_property(
+                proto,
+                &QName::new(Namespace::public(), "constructor"),
+                activation,
+            )?
+            .coerce_to_object(activation)?;
+
+        constr.call(Some(application), &[], activation, Some(proto))?;
+        this.set_property(this, &name, application.into(), activation)?;
+
+        return Ok(application.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NativeApplication.supportsMenu` and similar feature checks.
+pub fn unsupported_feature<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(false.into())
+}
+
+/// Implements `applicationID` and `publisherID`'s getters.
+pub fn empty_id<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok("".into())
+}
+
+/// Implements `activeWindow`'s getter.
+pub fn active_window<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Null)
+}
+
+/// Implements `openedWindows`'s getter.
+pub fn opened_windows<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let array_proto = activation.context.avm2.prototypes().array;
+
+    Ok(ArrayObject::from_array(
+        ArrayStorage::new(0),
+        array_proto,
+        activation.context.gc_context,
+    )
+    .into())
+}
+
+/// Implements `NativeApplication.activate` and `exit`, which have no
+/// application to act on.
+pub fn unsupported<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    log::warn!("NativeApplication: AIR applications are not supported");
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `NativeApplication`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.desktop"), "NativeApplication"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "nativeApplication"),
+        Method::from_builtin(native_application),
+    ));
+    for feature in &[
+        "supportsDefaultApplication",
+        "supportsDockIcon",
+        "supportsMenu",
+        "supportsStartAtLogin",
+        "supportsSystemTrayIcon",
+    ] {
+        write.define_class_trait(Trait::from_getter(
+            QName::new(Namespace::public(), *feature),
+            Method::from_builtin(unsupported_feature),
+        ));
+    }
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "applicationID"),
+        Method::from_builtin(empty_id),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "publisherID"),
+        Method::from_builtin(empty_id),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "activeWindow"),
+        Method::from_builtin(active_window),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "openedWindows"),
+        Method::from_builtin(opened_windows),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "activate"),
+        Method::from_builtin(unsupported),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "exit"),
+        Method::from_builtin(unsupported),
+    ));
+
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::test_utils::with_avm2;
+
+    #[test]
+    fn native_application_reads_defaults() {
+        with_avm2(|activation| {
+            let mut class = activation
+                .avm2()
+                .global_domain()
+                .get_defined_value(
+                    activation,
+                    QName::new(Namespace::package("flash.desktop"), "NativeApplication"),
+                )
+                .unwrap()
+                .coerce_to_object(activation)
+                .unwrap();
+            let name = QName::new(Namespace::public(), "nativeApplication");
+            let mut application = class
+                .get_property(class, &name, activation)
+                .unwrap()
+                .coerce_to_object(activation)
+                .unwrap();
+
+            // Every read yields the same application object.
+            let again = class.get_property(class, &name, activation).unwrap();
+            assert_eq!(again, Value::Object(application));
+
+            let mut get = |name: &'static str| {
+                application
+                    .get_property(
+                        application,
+                        &QName::new(Namespace::public(), name),
+                        activation,
+                    )
+                    .unwrap()
+            };
+            assert_eq!(get("applicationID"), Value::from(""));
+            assert_eq!(get("activeWindow"), Value::Null);
+
+            let mut windows = get("openedWindows").coerce_to_object(activation).unwrap();
+            let length = windows
+                .get_property(
+                    windows,
+                    &QName::new(Namespace::public(), "length"),
+                    activation,
+                )
+                .unwrap();
+            assert_eq!(length, Value::from(0));
+
+            let exit = application
+                .get_property(
+                    application,
+                    &QName::new(Namespace::public(), "exit"),
+                    activation,
+                )
+                .unwrap()
+                .coerce_to_object(activation)
+                .unwrap();
+            exit.call(Some(application), &[], activation, None).unwrap();
+        });
+    }
+}
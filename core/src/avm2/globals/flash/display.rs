@@ -2,8 +2,20 @@
 
 pub mod displayobject;
 pub mod displayobjectcontainer;
+pub mod focusdirection;
 pub mod framelabel;
+pub mod graphics;
 pub mod interactiveobject;
+pub mod loader;
+pub mod loaderinfo;
+pub mod morphshape;
 pub mod movieclip;
+pub mod nativewindow;
 pub mod scene;
 pub mod sprite;
+pub mod stage;
+pub mod stage3d;
+pub mod stageaspectratio;
+pub mod stagedisplaystate;
+pub mod stageorientation;
+pub mod stagescalemode;
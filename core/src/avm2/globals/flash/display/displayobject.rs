@@ -1,15 +1,19 @@
 //! `flash.display.DisplayObject` builtin/prototype
 
 use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
 use crate::avm2::class::Class;
+use crate::avm2::globals::flash::display::loaderinfo;
+use crate::avm2::globals::flash::geom::matrix3d::{self, Components3D};
+use crate::avm2::globals::flash::geom::{rectangle, transform};
 use crate::avm2::method::Method;
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::{Object, TObject};
+use crate::avm2::object::{ArrayObject, Object, TObject};
 use crate::avm2::string::AvmString;
 use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
-use crate::display_object::TDisplayObject;
+use crate::display_object::{DisplayObject, TDisplayObject};
 use crate::types::{Degrees, Percent};
 use gc_arena::{GcCell, MutationContext};
 use swf::Twips;
@@ -304,6 +308,188 @@ pub fn set_rotation<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Get the 3D components of a display object's transform.
+///
+/// Objects with only a 2D transform are flat, at a `z` of zero.
+fn components_3d<'gc>(
+    dobj: DisplayObject<'gc>,
+    gc_context: MutationContext<'gc, '_>,
+) -> Components3D {
+    match dobj.matrix_3d() {
+        Some(raw) => matrix3d::decompose(&raw),
+        None => Components3D {
+            translation: [dobj.x(), dobj.y(), 0.0],
+            rotation: [0.0, 0.0, f64::from(dobj.rotation(gc_context)).to_radians()],
+            scale: [
+                dobj.scale_x(gc_context).into_unit(),
+                dobj.scale_y(gc_context).into_unit(),
+                1.0,
+            ],
+        },
+    }
+}
+
+/// Rebuild a display object's transform from 3D components.
+///
+/// An object that is left flat, with no depth and no rotation out of the XY
+/// plane, goes back to having only a 2D transform. Otherwise it gets a 3D
+/// one, projected as `transform.matrix3D` is.
+fn set_components_3d<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    dobj: DisplayObject<'gc>,
+    components: &Components3D,
+) {
+    let gc_context = activation.context.gc_context;
+    let raw = matrix3d::recompose(components);
+    let [rotation_x, rotation_y, _] = components.rotation;
+
+    if components.translation[2] == 0.0 && rotation_x == 0.0 && rotation_y == 0.0 {
+        dobj.set_matrix_3d(gc_context, None);
+        dobj.set_matrix(gc_context, &matrix3d::to_2d(&raw));
+    } else {
+        dobj.set_matrix_3d(gc_context, Some(raw));
+        transform::project_matrix_3d(activation, dobj);
+    }
+
+    dobj.set_script_matrix(gc_context, None);
+    dobj.set_transformed_by_script(gc_context, true);
+}
+
+/// Implements `z`'s getter.
+pub fn z<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let components = components_3d(dobj, activation.context.gc_context);
+
+        return Ok(components.translation[2].into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `z`'s setter.
+pub fn set_z<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let new_z = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+        let mut components = components_3d(dobj, activation.context.gc_context);
+
+        components.translation[2] = new_z;
+        set_components_3d(activation, dobj, &components);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Read one of the rotations of a display object, in degrees.
+fn rotation_3d<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    axis: usize,
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let components = components_3d(dobj, activation.context.gc_context);
+
+        return Ok(components.rotation[axis].to_degrees().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Change one of the rotations of a display object, in degrees.
+fn set_rotation_3d<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+    axis: usize,
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let new_rotation = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+        let mut components = components_3d(dobj, activation.context.gc_context);
+
+        components.rotation[axis] = new_rotation.to_radians();
+        set_components_3d(activation, dobj, &components);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `rotationX`'s getter.
+pub fn rotation_x<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    rotation_3d(activation, this, 0)
+}
+
+/// Implements `rotationX`'s setter.
+pub fn set_rotation_x<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    set_rotation_3d(activation, this, args, 0)
+}
+
+/// Implements `rotationY`'s getter.
+pub fn rotation_y<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    rotation_3d(activation, this, 1)
+}
+
+/// Implements `rotationY`'s setter.
+pub fn set_rotation_y<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    set_rotation_3d(activation, this, args, 1)
+}
+
+/// Implements `rotationZ`'s getter.
+///
+/// For objects with only a 2D transform, this is the same as `rotation`.
+pub fn rotation_z<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    match this.and_then(|this| this.as_display_object()) {
+        Some(dobj) if dobj.matrix_3d().is_none() => rotation(activation, this, args),
+        _ => rotation_3d(activation, this, 2),
+    }
+}
+
+/// Implements `rotationZ`'s setter.
+pub fn set_rotation_z<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    match this.and_then(|this| this.as_display_object()) {
+        Some(dobj) if dobj.matrix_3d().is_none() => set_rotation(activation, this, args),
+        _ => set_rotation_3d(activation, this, args, 2),
+    }
+}
+
 /// Implements `name`'s getter.
 pub fn name<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
@@ -494,6 +680,352 @@ pub fn hit_test_object<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `stage`.
+pub fn stage<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        if dobj.is_on_stage(&activation.context) {
+            return Ok(activation
+                .context
+                .avm2
+                .stage()
+                .map(|stage| stage.into())
+                .unwrap_or(Value::Null));
+        }
+
+        return Ok(Value::Null);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `loaderInfo`.
+pub fn loader_info<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return loaderinfo::loader_info_for_display_object(activation, dobj);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `opaqueBackground`'s getter.
+pub fn opaque_background<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj
+            .opaque_background()
+            .map(|color| color.into())
+            .unwrap_or(Value::Null));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `opaqueBackground`'s setter.
+pub fn set_opaque_background<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let new_color = match args.get(0).cloned().unwrap_or(Value::Undefined) {
+            Value::Undefined | Value::Null => None,
+            value => Some(value.coerce_to_u32(activation)? & 0xFFFFFF),
+        };
+
+        dobj.set_opaque_background(activation.context.gc_context, new_color);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `scale9Grid`'s getter.
+pub fn scale_9_grid<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return match dobj.scale_9_grid() {
+            Some(grid) => Ok(rectangle::bounding_box_to_object(&grid, activation)?.into()),
+            None => Ok(Value::Null),
+        };
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `scale9Grid`'s setter.
+///
+/// The grid is copied; changing the `Rectangle` afterwards has no effect.
+/// Only the object's own vector graphics are sliced, not its children.
+pub fn set_scale_9_grid<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let grid = match args.get(0).cloned().unwrap_or(Value::Undefined) {
+            Value::Undefined | Value::Null => None,
+            value => Some(rectangle::object_to_bounding_box(
+                value.coerce_to_object(activation)?,
+                activation,
+            )?),
+        };
+
+        dobj.set_scale_9_grid(activation.context.gc_context, grid);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `transform`'s getter.
+pub fn transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(transform::transform_for_display_object(activation, dobj).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `transform`'s setter.
+///
+/// This copies the other transform's matrix and color transform; the two
+/// display objects are not linked afterwards.
+pub fn set_transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let source = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?
+            .as_display_object();
+
+        if let Some(source) = source {
+            let matrix = *source.matrix();
+            let color_transform = *source.color_transform();
+
+            dobj.set_matrix(activation.context.gc_context, &matrix);
+            dobj.set_color_transform(activation.context.gc_context, &color_transform);
+            dobj.set_transformed_by_script(activation.context.gc_context, true);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Copy a list of filters into a new array, cloning each filter in it.
+///
+/// Anything in the list without a `clone` method is copied as-is.
+fn clone_filters<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    filters: Value<'gc>,
+) -> Result<Object<'gc>, Error> {
+    let items: Vec<Value<'gc>> = match filters {
+        Value::Object(filters) => match filters.as_array_storage() {
+            Some(storage) => storage
+                .iter()
+                .map(|v| v.unwrap_or(Value::Undefined))
+                .collect(),
+            None => vec![],
+        },
+        _ => vec![],
+    };
+
+    let mut cloned = Vec::with_capacity(items.len());
+    for item in items {
+        if let Value::Object(mut filter) = item {
+            if let Value::Object(clone) =
+                filter.get_property(filter, &QName::dynamic_name("clone"), activation)?
+            {
+                cloned.push(clone.call(Some(filter), &[], activation, None)?);
+                continue;
+            }
+        }
+
+        cloned.push(item);
+    }
+
+    Ok(ArrayObject::from_array(
+        ArrayStorage::from_args(&cloned),
+        activation.context.avm2.prototypes().array,
+        activation.context.gc_context,
+    ))
+}
+
+/// Implements `filters`'s getter.
+///
+/// Every read returns a new array of copies of the filters, so changing it
+/// has no effect until it is assigned back to `filters`.
+pub fn filters<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        if this.as_display_object().is_some() {
+            let filters = this.get_property(
+                this,
+                &QName::new(Namespace::Private("ruffle".into()), "filters"),
+                activation,
+            )?;
+
+            return Ok(clone_filters(activation, filters)?.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `filters`'s setter.
+///
+/// Filters are stored but not yet drawn.
+pub fn set_filters<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        if this.as_display_object().is_some() {
+            let filters =
+                clone_filters(activation, args.get(0).cloned().unwrap_or(Value::Undefined))?;
+
+            this.set_property(
+                this,
+                &QName::new(Namespace::Private("ruffle".into()), "filters"),
+                filters.into(),
+                activation,
+            )?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `metaData`'s getter.
+pub fn meta_data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        if this.as_display_object().is_some() {
+            return match this.get_property(
+                this,
+                &QName::new(Namespace::Private("ruffle".into()), "metaData"),
+                activation,
+            )? {
+                Value::Undefined => Ok(Value::Null),
+                meta_data => Ok(meta_data),
+            };
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `metaData`'s setter.
+///
+/// Metadata authored into `PlaceObject4` tags is not yet decoded, so this is
+/// only ever what script assigned.
+pub fn set_meta_data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        if this.as_display_object().is_some() {
+            let meta_data = match args.get(0).cloned().unwrap_or(Value::Null) {
+                Value::Undefined | Value::Null => Value::Null,
+                meta_data => meta_data.coerce_to_object(activation)?.into(),
+            };
+
+            this.set_property(
+                this,
+                &QName::new(Namespace::Private("ruffle".into()), "metaData"),
+                meta_data,
+                activation,
+            )?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `accessibilityProperties`'s getter.
+pub fn accessibility_properties<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        if this.as_display_object().is_some() {
+            return match this.get_property(
+                this,
+                &QName::new(
+                    Namespace::Private("ruffle".into()),
+                    "accessibilityProperties",
+                ),
+                activation,
+            )? {
+                Value::Undefined => Ok(Value::Null),
+                properties => Ok(properties),
+            };
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `accessibilityProperties`'s setter.
+///
+/// The properties are kept for content to read back, but are not passed on to
+/// the host.
+pub fn set_accessibility_properties<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        if this.as_display_object().is_some() {
+            let properties = match args.get(0).cloned().unwrap_or(Value::Null) {
+                Value::Undefined | Value::Null => Value::Null,
+                properties => properties.coerce_to_object(activation)?.into(),
+            };
+
+            this.set_property(
+                this,
+                &QName::new(
+                    Namespace::Private("ruffle".into()),
+                    "accessibilityProperties",
+                ),
+                properties,
+                activation,
+            )?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Construct `DisplayObject`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
     let class = Class::new(
@@ -570,6 +1102,38 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         QName::new(Namespace::public(), "rotation"),
         Method::from_builtin(set_rotation),
     ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "z"),
+        Method::from_builtin(z),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "z"),
+        Method::from_builtin(set_z),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "rotationX"),
+        Method::from_builtin(rotation_x),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "rotationX"),
+        Method::from_builtin(set_rotation_x),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "rotationY"),
+        Method::from_builtin(rotation_y),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "rotationY"),
+        Method::from_builtin(set_rotation_y),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "rotationZ"),
+        Method::from_builtin(rotation_z),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "rotationZ"),
+        Method::from_builtin(set_rotation_z),
+    ));
     write.define_instance_trait(Trait::from_getter(
         QName::new(Namespace::public(), "name"),
         Method::from_builtin(name),
@@ -610,6 +1174,269 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         QName::new(Namespace::public(), "hitTestObject"),
         Method::from_builtin(hit_test_object),
     ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "stage"),
+        Method::from_builtin(stage),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "loaderInfo"),
+        Method::from_builtin(loader_info),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "opaqueBackground"),
+        Method::from_builtin(opaque_background),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "opaqueBackground"),
+        Method::from_builtin(set_opaque_background),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "scale9Grid"),
+        Method::from_builtin(scale_9_grid),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "scale9Grid"),
+        Method::from_builtin(set_scale_9_grid),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "transform"),
+        Method::from_builtin(transform),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "transform"),
+        Method::from_builtin(set_transform),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "filters"),
+        Method::from_builtin(filters),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "filters"),
+        Method::from_builtin(set_filters),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "metaData"),
+        Method::from_builtin(meta_data),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "metaData"),
+        Method::from_builtin(set_meta_data),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "accessibilityProperties"),
+        Method::from_builtin(accessibility_properties),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "accessibilityProperties"),
+        Method::from_builtin(set_accessibility_properties),
+    ));
 
     class
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::globals::flash::geom::perspectiveprojection;
+    use crate::avm2::object::{FunctionObject, ScriptObject, StageObject};
+    use crate::avm2::test_utils::with_avm2;
+    use crate::backend::render::BitmapHandle;
+    use crate::display_object::{Bitmap, MovieClip, TDisplayObjectContainer};
+    use crate::tag_utils::{SwfMovie, SwfSlice};
+    use std::sync::Arc;
+
+    /// A stand-in for `BlurFilter.clone`.
+    fn clone_blur<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let mut this = this.unwrap();
+        let blur_x = this
+            .get_property(this, &QName::dynamic_name("blurX"), activation)?
+            .coerce_to_number(activation)?;
+
+        Ok(blur_filter(activation, blur_x).into())
+    }
+
+    fn blur_filter<'gc>(activation: &mut Activation<'_, 'gc, '_>, blur_x: f64) -> Object<'gc> {
+        let mc = activation.context.gc_context;
+        let object_proto = activation.context.avm2.prototypes().object;
+        let fn_proto = activation.context.avm2.prototypes().function;
+        let mut filter = ScriptObject::object(mc, object_proto);
+        let clone = FunctionObject::from_builtin(mc, clone_blur, fn_proto);
+
+        filter
+            .set_property(
+                filter,
+                &QName::dynamic_name("clone"),
+                clone.into(),
+                activation,
+            )
+            .unwrap();
+        filter
+            .set_property(
+                filter,
+                &QName::dynamic_name("blurX"),
+                blur_x.into(),
+                activation,
+            )
+            .unwrap();
+
+        filter
+    }
+
+    fn first_blur_x<'gc>(activation: &mut Activation<'_, 'gc, '_>, this: Object<'gc>) -> f64 {
+        let list = filters(activation, Some(this), &[]).unwrap();
+        let first = list
+            .coerce_to_object(activation)
+            .unwrap()
+            .as_array_storage()
+            .unwrap()
+            .get(0);
+        let mut first = first.unwrap().coerce_to_object(activation).unwrap();
+
+        first
+            .get_property(first, &QName::dynamic_name("blurX"), activation)
+            .unwrap()
+            .coerce_to_number(activation)
+            .unwrap()
+    }
+
+    #[test]
+    fn filters_reads_are_copies() {
+        with_avm2(|activation| {
+            let movie = Arc::new(SwfMovie::empty(10));
+            let mc = activation.context.gc_context;
+            let clip = MovieClip::new(SwfSlice::empty(movie), mc);
+            let movieclip_proto = activation.context.avm2.prototypes().movieclip;
+            let this: Object<'_> =
+                StageObject::for_display_object(mc, clip.into(), movieclip_proto).into();
+
+            let empty = filters(activation, Some(this), &[])
+                .unwrap()
+                .coerce_to_object(activation)
+                .unwrap();
+            assert_eq!(empty.as_array_storage().unwrap().length(), 0);
+
+            let blur = blur_filter(activation, 4.0);
+            let list = ArrayObject::from_array(
+                ArrayStorage::from_args(&[blur.into()]),
+                activation.context.avm2.prototypes().array,
+                mc,
+            );
+            set_filters(activation, Some(this), &[list.into()]).unwrap();
+
+            // Neither the assigned nor the returned filters are live.
+            let read = filters(activation, Some(this), &[])
+                .unwrap()
+                .coerce_to_object(activation)
+                .unwrap();
+            let mut first = read
+                .as_array_storage()
+                .unwrap()
+                .get(0)
+                .unwrap()
+                .coerce_to_object(activation)
+                .unwrap();
+            first
+                .set_property(
+                    first,
+                    &QName::dynamic_name("blurX"),
+                    10.0.into(),
+                    activation,
+                )
+                .unwrap();
+            let mut blur = blur;
+            blur.set_property(blur, &QName::dynamic_name("blurX"), 20.0.into(), activation)
+                .unwrap();
+            read.as_array_storage_mut(mc).unwrap().set(1, Value::Null);
+            assert_eq!(first_blur_x(activation, this), 4.0);
+            assert_eq!(
+                filters(activation, Some(this), &[])
+                    .unwrap()
+                    .coerce_to_object(activation)
+                    .unwrap()
+                    .as_array_storage()
+                    .unwrap()
+                    .length(),
+                1
+            );
+
+            // Assigning the changed copy back applies it.
+            set_filters(activation, Some(this), &[read.into()]).unwrap();
+            assert_eq!(first_blur_x(activation, this), 10.0);
+        });
+    }
+
+    fn number<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        getter: crate::avm2::method::NativeMethod<'gc>,
+        this: Object<'gc>,
+    ) -> f64 {
+        getter(activation, Some(this), &[])
+            .unwrap()
+            .coerce_to_number(activation)
+            .unwrap()
+    }
+
+    #[test]
+    fn rotation_y_foreshortens_width() {
+        with_avm2(|activation| {
+            let movie = Arc::new(SwfMovie::empty(10));
+            let mc = activation.context.gc_context;
+            let parent = MovieClip::new(SwfSlice::empty(movie), mc);
+            let bitmap = Bitmap::new(&mut activation.context, 0, BitmapHandle(0), 20, 10);
+            parent.as_container().unwrap().insert_at_index(
+                &mut activation.context,
+                bitmap.into(),
+                0,
+            );
+            let movieclip_proto = activation.context.avm2.prototypes().movieclip;
+            let this: Object<'_> =
+                StageObject::for_display_object(mc, bitmap.into(), movieclip_proto).into();
+
+            set_x(activation, Some(this), &[30.0.into()]).unwrap();
+            set_rotation(activation, Some(this), &[10.0.into()]).unwrap();
+            assert_eq!(number(activation, z, this), 0.0);
+            assert_eq!(number(activation, rotation_y, this), 0.0);
+            assert!((number(activation, rotation_z, this) - 10.0).abs() < 1e-4);
+            set_rotation(activation, Some(this), &[0.0.into()]).unwrap();
+            assert_eq!(number(activation, width, this), 20.0);
+
+            // Turning the bitmap 60 degrees about Y shows it at half width.
+            set_rotation_y(activation, Some(this), &[60.0.into()]).unwrap();
+            assert!(bitmap.matrix_3d().is_some());
+            assert!((number(activation, rotation_y, this) - 60.0).abs() < 1e-9);
+            assert!((number(activation, width, this) - 10.0).abs() < 0.1);
+            assert_eq!(number(activation, x, this), 30.0);
+
+            // Under a perspective, pushing it away along Z shrinks it further.
+            let parent_transform =
+                transform::transform_for_display_object(activation, parent.into());
+            let projection = perspectiveprojection::perspective_projection_to_object(
+                &[perspectiveprojection::DEFAULT_FIELD_OF_VIEW, 0.0, 0.0],
+                activation,
+            )
+            .unwrap();
+            transform::set_perspective_projection(
+                activation,
+                Some(parent_transform),
+                &[projection.into()],
+            )
+            .unwrap();
+            set_z(activation, Some(this), &[200.0.into()]).unwrap();
+            assert_eq!(number(activation, z, this), 200.0);
+            let far_width = number(activation, width, this);
+            assert!(far_width > 0.0 && far_width < 9.9, "{}", far_width);
+            assert!((number(activation, rotation_y, this) - 60.0).abs() < 1e-9);
+
+            // Flattening it again makes it 2D.
+            set_z(activation, Some(this), &[0.0.into()]).unwrap();
+            set_rotation_y(activation, Some(this), &[0.0.into()]).unwrap();
+            assert!(bitmap.matrix_3d().is_none());
+            assert_eq!(number(activation, width, this), 20.0);
+            assert_eq!(number(activation, x, this), 30.0);
+        });
+    }
+}
@@ -2,12 +2,13 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::class::Class;
+use crate::avm2::events::Event;
 use crate::avm2::method::Method;
 use crate::avm2::names::{Namespace, QName};
 use crate::avm2::object::{Object, TObject};
 use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
-use crate::avm2::Error;
+use crate::avm2::{Avm2, Error};
 use crate::context::UpdateContext;
 use crate::display_object::{DisplayObject, Lists, TDisplayObject, TDisplayObjectContainer};
 use gc_arena::{GcCell, MutationContext};
@@ -117,6 +118,99 @@ fn add_child_to_displaylist<'gc>(
     }
 }
 
+/// Dispatch a non-bubbling event on a display object and then on each of its
+/// descendants, parents first.
+fn dispatch_to_subtree<'gc>(
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    dobj: DisplayObject<'gc>,
+    event_type: &'static str,
+) -> Result<(), Error> {
+    if let Value::Object(object) = dobj.object2() {
+        Avm2::dispatch_event(context, Event::new(event_type), object)?;
+    }
+
+    if let Some(ctr) = dobj.as_container() {
+        // Listeners may rearrange the children, so walk a snapshot of them.
+        let children: Vec<DisplayObject<'gc>> = ctr.iter_render_list().collect();
+        for child in children {
+            dispatch_to_subtree(context, child, event_type)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Dispatch the events for a child that was just added to a container.
+///
+/// `added` bubbles from the child itself. If the container is on the stage,
+/// the child and every one of its descendants then get `addedToStage`.
+fn dispatch_added_event<'gc>(
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    child: DisplayObject<'gc>,
+) -> Result<(), Error> {
+    if let Value::Object(object) = child.object2() {
+        let mut added = Event::new("added");
+        added.set_bubbles(true);
+        Avm2::dispatch_event(context, added, object)?;
+    }
+
+    if child.is_on_stage(context) {
+        dispatch_to_subtree(context, child, "addedToStage")?;
+    }
+
+    Ok(())
+}
+
+/// Dispatch the events for a child that is about to be removed from its
+/// container.
+///
+/// This mirrors `dispatch_added_event`: `removed` bubbles from the child, and
+/// if it is on the stage, its whole subtree gets `removedFromStage`.
+fn dispatch_removed_event<'gc>(
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    child: DisplayObject<'gc>,
+) -> Result<(), Error> {
+    if let Value::Object(object) = child.object2() {
+        let mut removed = Event::new("removed");
+        removed.set_bubbles(true);
+        Avm2::dispatch_event(context, removed, object)?;
+    }
+
+    if child.is_on_stage(context) {
+        dispatch_to_subtree(context, child, "removedFromStage")?;
+    }
+
+    Ok(())
+}
+
+/// Add the `child` to `parent`'s display list as a new child, dispatching the
+/// lifecycle events for leaving its old parent and joining the new one.
+///
+/// Moving a child within the same parent does not dispatch any events.
+fn add_child_with_events<'gc>(
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    parent: DisplayObject<'gc>,
+    child: DisplayObject<'gc>,
+    index: usize,
+) -> Result<(), Error> {
+    let old_parent = child.parent();
+    let is_new_parent = !old_parent
+        .map(|old_parent| DisplayObject::ptr_eq(old_parent, parent))
+        .unwrap_or(false);
+
+    if is_new_parent && old_parent.is_some() {
+        dispatch_removed_event(context, child)?;
+    }
+
+    add_child_to_displaylist(context, parent, child, index);
+
+    if is_new_parent {
+        dispatch_added_event(context, child)?;
+    }
+
+    Ok(())
+}
+
 /// Implements `DisplayObjectContainer.getChildAt`
 pub fn get_child_at<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
@@ -191,7 +285,7 @@ pub fn add_child<'gc>(
             let target_index = ctr.num_children();
 
             validate_add_operation(parent, child, target_index)?;
-            add_child_to_displaylist(&mut activation.context, parent, child, target_index);
+            add_child_with_events(&mut activation.context, parent, child, target_index)?;
 
             return Ok(child.object2());
         }
@@ -221,7 +315,7 @@ pub fn add_child_at<'gc>(
             .coerce_to_i32(activation)? as usize;
 
         validate_add_operation(parent, child, target_index)?;
-        add_child_to_displaylist(&mut activation.context, parent, child, target_index);
+        add_child_with_events(&mut activation.context, parent, child, target_index)?;
 
         return Ok(child.object2());
     }
@@ -245,6 +339,7 @@ pub fn remove_child<'gc>(
             .ok_or("ArgumentError: Child not a valid display object")?;
 
         validate_remove_operation(parent, child)?;
+        dispatch_removed_event(&mut activation.context, child)?;
         remove_child_from_displaylist(&mut activation.context, child);
 
         return Ok(child.object2());
@@ -315,10 +410,11 @@ pub fn get_child_index<'gc>(
                 .as_display_object();
 
             if let Some(target_child) = target_child {
-                for (i, child) in ctr.iter_render_list().enumerate() {
-                    if DisplayObject::ptr_eq(child, target_child) {
-                        return Ok(i.into());
-                    }
+                if let Some(index) = ctr
+                    .iter_render_list()
+                    .position(|child| DisplayObject::ptr_eq(child, target_child))
+                {
+                    return Ok(index.into());
                 }
             }
         }
@@ -352,6 +448,7 @@ pub fn remove_child_at<'gc>(
 
             let child = ctr.child_by_index(target_child as usize).unwrap();
 
+            dispatch_removed_event(&mut activation.context, child)?;
             ctr.remove_child(&mut activation.context, child, Lists::all());
 
             return Ok(child.object2());
@@ -362,6 +459,9 @@ pub fn remove_child_at<'gc>(
 }
 
 /// Implements `DisplayObjectContainer.removeChildren`
+///
+/// With no arguments, every child is removed; this is allowed even when
+/// there are none.
 pub fn remove_children<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
@@ -380,6 +480,10 @@ pub fn remove_children<'gc>(
                 .unwrap_or_else(|| i32::MAX.into())
                 .coerce_to_i32(activation)?;
 
+            if ctr.num_children() == 0 && from == 0 && to == i32::MAX {
+                return Ok(Value::Undefined);
+            }
+
             if from >= ctr.num_children() as i32 || from < 0 {
                 return Err(format!(
                     "RangeError: Starting position {} does not exist in the child list (valid range is 0 to {})",
@@ -402,9 +506,19 @@ pub fn remove_children<'gc>(
                 return Err(format!("RangeError: Range {} to {} is invalid", from, to).into());
             }
 
+            let range = from as usize..min(ctr.num_children(), to as usize + 1);
+            let removed: Vec<DisplayObject<'gc>> = ctr
+                .iter_render_list()
+                .skip(range.start)
+                .take(range.len())
+                .collect();
+            for child in removed {
+                dispatch_removed_event(&mut activation.context, child)?;
+            }
+
             ctr.remove_range(
                 &mut activation.context,
-                from as usize..min(ctr.num_children(), to as usize + 1),
+                range.start..min(ctr.num_children(), range.end),
             );
         }
     }
@@ -437,7 +551,13 @@ pub fn set_child_index<'gc>(
             return Err("ArgumentError: Given child is not a child of this display object".into());
         }
 
+        // Unlike adding a child, the index must be one the child can already
+        // be found at: moving past the last child is out of range.
         validate_add_operation(parent, child, target_index)?;
+        if target_index >= parent.as_container().unwrap().num_children() {
+            return Err("RangeError: Index position does not exist in the child list".into());
+        }
+
         add_child_to_displaylist(&mut activation.context, parent, child, target_index);
 
         return Ok(child.object2());
@@ -474,6 +594,10 @@ pub fn swap_children_at<'gc>(
                 return Err(format!("RangeError: Index {} is out of bounds", index1).into());
             }
 
+            if index0 == index1 {
+                return Ok(Value::Undefined);
+            }
+
             let child0 = ctr.child_by_index(index0 as usize).unwrap();
             let child1 = ctr.child_by_index(index1 as usize).unwrap();
 
@@ -519,6 +643,10 @@ pub fn swap_children<'gc>(
                 .position(|a| DisplayObject::ptr_eq(a, child1))
                 .ok_or("ArgumentError: Child is not a child of this display object")?;
 
+            if index0 == index1 {
+                return Ok(Value::Undefined);
+            }
+
             child0.set_placed_by_script(activation.context.gc_context, true);
             child1.set_placed_by_script(activation.context.gc_context, true);
 
@@ -571,6 +699,41 @@ pub fn are_inaccessible_objects_under_point<'gc>(
     Err("DisplayObjectContainer.areInaccessibleObjectsUnderPoint not yet implemented".into())
 }
 
+/// Implements `tabChildren`'s getter.
+pub fn tab_children<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.tab_children().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `tabChildren`'s setter.
+///
+/// Tab skips everything inside a container with `tabChildren` false, but its
+/// descendants can still be given focus by a script.
+pub fn set_tab_children<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let tab_children = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+
+        dobj.set_tab_children(activation.context.gc_context, tab_children);
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Construct `DisplayObjectContainer`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
     let class = Class::new(
@@ -650,6 +813,754 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         QName::new(Namespace::public(), "areInaccessibleObjectsUnderPoint"),
         Method::from_builtin(are_inaccessible_objects_under_point),
     ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "tabChildren"),
+        Method::from_builtin(tab_children),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "tabChildren"),
+        Method::from_builtin(set_tab_children),
+    ));
 
     class
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::globals::flash::display::{interactiveobject, stage};
+    use crate::avm2::method::NativeMethod;
+    use crate::avm2::object::{FunctionObject, StageObject};
+    use crate::avm2::test_utils::{trace_with_avm2, with_avm2, TestPlayer};
+    use crate::display_object::MovieClip;
+    use crate::tag_utils::{SwfMovie, SwfSlice};
+    use crate::vminterface::Instantiator;
+    use std::sync::Arc;
+    use swf::{
+        Compression, FileAttributes, Header, PlaceObject, PlaceObjectAction, Rectangle, Sprite,
+        Swf, Tag, Twips,
+    };
+
+    /// Any ActionScript 3 movie will do, so that clips get AVM2 objects.
+    const AS3_MOVIE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/swfs/avm2/add/test.swf");
+
+    const LIFECYCLE_EVENTS: &[&str] = &["added", "addedToStage", "removed", "removedFromStage"];
+
+    /// A listener that traces the event type, the clip it reached, and
+    /// whether that clip could see the stage.
+    fn trace_event<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let mut event = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let event_type = event
+            .get_property(event, &QName::new(Namespace::public(), "type"), activation)?
+            .coerce_to_string(activation)?;
+        let mut current_target = event
+            .get_property(
+                event,
+                &QName::new(Namespace::public(), "currentTarget"),
+                activation,
+            )?
+            .coerce_to_object(activation)?;
+        let name = current_target
+            .as_display_object()
+            .unwrap()
+            .name()
+            .to_string();
+        let stage = current_target.get_property(
+            current_target,
+            &QName::new(Namespace::public(), "stage"),
+            activation,
+        )?;
+
+        activation.context.log.avm_trace(&format!(
+            "{} {} stage={}",
+            event_type,
+            name,
+            !matches!(stage, Value::Null)
+        ));
+
+        Ok(Value::Undefined)
+    }
+
+    fn listening_clip<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        movie: &Arc<SwfMovie>,
+        name: &str,
+    ) -> Object<'gc> {
+        let clip: DisplayObject<'gc> = MovieClip::new(
+            SwfSlice::empty(movie.clone()),
+            activation.context.gc_context,
+        )
+        .into();
+        clip.post_instantiation(
+            &mut activation.context,
+            clip,
+            None,
+            Instantiator::Avm2,
+            false,
+        );
+        clip.set_name(activation.context.gc_context, name);
+
+        let mut object = clip.object2().coerce_to_object(activation).unwrap();
+        let add_event_listener = object
+            .get_property(
+                object,
+                &QName::new(Namespace::public(), "addEventListener"),
+                activation,
+            )
+            .unwrap()
+            .coerce_to_object(activation)
+            .unwrap();
+        for event_type in LIFECYCLE_EVENTS {
+            let listener = FunctionObject::from_builtin(
+                activation.context.gc_context,
+                trace_event,
+                activation.avm2().prototypes().function,
+            );
+            add_event_listener
+                .call(
+                    Some(object),
+                    &[(*event_type).into(), listener.into()],
+                    activation,
+                    None,
+                )
+                .unwrap();
+        }
+
+        object
+    }
+
+    #[test]
+    fn nested_subtree_lifecycle_events() {
+        let traces = trace_with_avm2(|activation| {
+            let movie = Arc::new(SwfMovie::from_path(AS3_MOVIE).unwrap());
+            let parent = listening_clip(activation, &movie, "parent");
+            let child = listening_clip(activation, &movie, "child");
+            let grandchild = listening_clip(activation, &movie, "grandchild");
+
+            // Assemble the subtree off the stage, where there's nothing to
+            // observe but `added`.
+            add_child(activation, Some(parent), &[child.into()]).unwrap();
+            add_child(activation, Some(child), &[grandchild.into()]).unwrap();
+            activation.context.log.avm_trace("--");
+
+            let root = *activation.context.levels.get(&0).unwrap();
+            let root_proto = activation.avm2().prototypes().movieclip;
+            let root = Object::from(StageObject::for_display_object(
+                activation.context.gc_context,
+                root,
+                root_proto,
+            ));
+            add_child(activation, Some(root), &[parent.into()]).unwrap();
+            activation.context.log.avm_trace("--");
+
+            remove_child(activation, Some(root), &[parent.into()]).unwrap();
+        });
+
+        assert_eq!(
+            traces,
+            vec![
+                "added child stage=false",
+                "added parent stage=false",
+                "added grandchild stage=false",
+                "added child stage=false",
+                "added parent stage=false",
+                "--",
+                "added parent stage=true",
+                "addedToStage parent stage=true",
+                "addedToStage child stage=true",
+                "addedToStage grandchild stage=true",
+                "--",
+                "removed parent stage=true",
+                "removedFromStage parent stage=true",
+                "removedFromStage child stage=true",
+                "removedFromStage grandchild stage=true",
+            ]
+        );
+    }
+
+    #[test]
+    fn remove_children_removes_a_range_or_everything() {
+        let traces = trace_with_avm2(|activation| {
+            let movie = Arc::new(SwfMovie::from_path(AS3_MOVIE).unwrap());
+            let parent = listening_clip(activation, &movie, "parent");
+            let children: Vec<Object<'_>> = ["a", "b", "c", "d"]
+                .iter()
+                .map(|name| listening_clip(activation, &movie, name))
+                .collect();
+            for child in &children {
+                add_child(activation, Some(parent), &[(*child).into()]).unwrap();
+            }
+
+            let root = *activation.context.levels.get(&0).unwrap();
+            let root_proto = activation.avm2().prototypes().movieclip;
+            let root = Object::from(StageObject::for_display_object(
+                activation.context.gc_context,
+                root,
+                root_proto,
+            ));
+            add_child(activation, Some(root), &[parent.into()]).unwrap();
+            activation.context.log.avm_trace("--");
+
+            remove_children(activation, Some(parent), &[1.into(), 2.into()]).unwrap();
+            assert_eq!(
+                num_children(activation, Some(parent), &[]).unwrap(),
+                2.into()
+            );
+            assert_eq!(
+                get_child_at(activation, Some(parent), &[1.into()]).unwrap(),
+                children[3].into()
+            );
+            activation.context.log.avm_trace("--");
+
+            for (from, to) in &[(1, 0), (-1, 1), (0, 2), (2, 2)] {
+                assert!(
+                    remove_children(activation, Some(parent), &[(*from).into(), (*to).into()])
+                        .is_err(),
+                    "{} to {} should be out of range",
+                    from,
+                    to
+                );
+            }
+
+            remove_children(activation, Some(parent), &[]).unwrap();
+            assert_eq!(
+                num_children(activation, Some(parent), &[]).unwrap(),
+                0.into()
+            );
+
+            // Clearing an empty container is fine.
+            remove_children(activation, Some(parent), &[]).unwrap();
+        });
+
+        let after_setup = traces.iter().position(|line| line == "--").unwrap();
+        assert_eq!(
+            &traces[after_setup + 1..],
+            &[
+                "removed b stage=true",
+                "removed parent stage=true",
+                "removedFromStage b stage=true",
+                "removed c stage=true",
+                "removed parent stage=true",
+                "removedFromStage c stage=true",
+                "--",
+                "removed a stage=true",
+                "removed parent stage=true",
+                "removedFromStage a stage=true",
+                "removed d stage=true",
+                "removed parent stage=true",
+                "removedFromStage d stage=true",
+            ]
+        );
+    }
+
+    #[test]
+    fn child_index_round_trips_after_reordering() {
+        trace_with_avm2(|activation| {
+            let movie = Arc::new(SwfMovie::from_path(AS3_MOVIE).unwrap());
+            let parent = listening_clip(activation, &movie, "parent");
+            let children: Vec<Object<'_>> = ["a", "b", "c"]
+                .iter()
+                .map(|name| listening_clip(activation, &movie, name))
+                .collect();
+            for child in &children {
+                add_child(activation, Some(parent), &[(*child).into()]).unwrap();
+            }
+
+            set_child_index(activation, Some(parent), &[children[2].into(), 0.into()]).unwrap();
+            set_child_index(activation, Some(parent), &[children[0].into(), 2.into()]).unwrap();
+
+            let order: Vec<Value<'_>> = (0..3)
+                .map(|i| get_child_at(activation, Some(parent), &[i.into()]).unwrap())
+                .collect();
+            assert_eq!(
+                order,
+                vec![children[2].into(), children[1].into(), children[0].into()]
+            );
+
+            for child in &children {
+                let index = get_child_index(activation, Some(parent), &[(*child).into()]).unwrap();
+                assert_eq!(
+                    get_child_at(activation, Some(parent), &[index]).unwrap(),
+                    (*child).into()
+                );
+            }
+
+            let stranger = listening_clip(activation, &movie, "stranger");
+            assert!(get_child_index(activation, Some(parent), &[stranger.into()]).is_err());
+            assert!(
+                set_child_index(activation, Some(parent), &[children[1].into(), 3.into()]).is_err()
+            );
+        });
+    }
+
+    /// Every child of `parent`, in `getChildAt` order.
+    fn child_order<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        parent: Object<'gc>,
+    ) -> Vec<Value<'gc>> {
+        let num_children = num_children(activation, Some(parent), &[])
+            .unwrap()
+            .coerce_to_u32(activation)
+            .unwrap();
+
+        (0..num_children)
+            .map(|i| get_child_at(activation, Some(parent), &[i.into()]).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn swap_children_at_reorders_get_child_at() {
+        trace_with_avm2(|activation| {
+            let movie = Arc::new(SwfMovie::from_path(AS3_MOVIE).unwrap());
+            let parent = listening_clip(activation, &movie, "parent");
+            let children: Vec<Object<'_>> = ["a", "b", "c"]
+                .iter()
+                .map(|name| listening_clip(activation, &movie, name))
+                .collect();
+            for child in &children {
+                add_child(activation, Some(parent), &[(*child).into()]).unwrap();
+            }
+
+            swap_children_at(activation, Some(parent), &[0.into(), 2.into()]).unwrap();
+            assert_eq!(
+                child_order(activation, parent),
+                vec![children[2].into(), children[1].into(), children[0].into()]
+            );
+
+            // Swapping a child with itself changes nothing.
+            swap_children_at(activation, Some(parent), &[1.into(), 1.into()]).unwrap();
+            assert_eq!(
+                child_order(activation, parent),
+                vec![children[2].into(), children[1].into(), children[0].into()]
+            );
+
+            swap_children_at(activation, Some(parent), &[1.into(), 0.into()]).unwrap();
+            assert_eq!(
+                child_order(activation, parent),
+                vec![children[1].into(), children[2].into(), children[0].into()]
+            );
+
+            assert!(swap_children_at(activation, Some(parent), &[0.into(), 3.into()]).is_err());
+            assert!(swap_children_at(activation, Some(parent), &[(-1).into(), 0.into()]).is_err());
+        });
+    }
+
+    /// A listener that removes the clip it reached from its parent.
+    fn remove_self<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let mut event = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let current_target = event
+            .get_property(
+                event,
+                &QName::new(Namespace::public(), "currentTarget"),
+                activation,
+            )?
+            .coerce_to_object(activation)?;
+
+        if let Some(dobj) = current_target.as_display_object() {
+            remove_child_from_displaylist(&mut activation.context, dobj);
+        }
+
+        Ok(Value::Undefined)
+    }
+
+    fn add_listener<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        mut object: Object<'gc>,
+        event_type: &'static str,
+        listener: NativeMethod<'gc>,
+    ) {
+        let add_event_listener = object
+            .get_property(
+                object,
+                &QName::new(Namespace::public(), "addEventListener"),
+                activation,
+            )
+            .unwrap()
+            .coerce_to_object(activation)
+            .unwrap();
+        let listener = FunctionObject::from_builtin(
+            activation.context.gc_context,
+            listener,
+            activation.avm2().prototypes().function,
+        );
+
+        add_event_listener
+            .call(
+                Some(object),
+                &[event_type.into(), listener.into()],
+                activation,
+                None,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn frame_events_skip_clips_off_the_display_list() {
+        let traces = trace_with_avm2(|activation| {
+            let movie = Arc::new(SwfMovie::from_path(AS3_MOVIE).unwrap());
+            let root = *activation.context.levels.get(&0).unwrap();
+            let root_proto = activation.avm2().prototypes().movieclip;
+            let root = Object::from(StageObject::for_display_object(
+                activation.context.gc_context,
+                root,
+                root_proto,
+            ));
+            let child = listening_clip(activation, &movie, "child");
+            let quitter = listening_clip(activation, &movie, "quitter");
+            for clip in &[child, quitter] {
+                add_listener(activation, *clip, "enterFrame", trace_event);
+                add_listener(activation, *clip, "exitFrame", trace_event);
+            }
+            add_listener(activation, quitter, "enterFrame", remove_self);
+
+            add_child(activation, Some(root), &[child.into()]).unwrap();
+            add_child(activation, Some(root), &[quitter.into()]).unwrap();
+            activation.context.log.avm_trace("--");
+
+            for _ in 0..3 {
+                Avm2::dispatch_frame_event(&mut activation.context, "enterFrame").unwrap();
+                Avm2::dispatch_frame_event(&mut activation.context, "exitFrame").unwrap();
+                activation.context.log.avm_trace("frame");
+
+                if remove_child(activation, Some(root), &[child.into()]).is_err() {
+                    add_child(activation, Some(root), &[child.into()]).unwrap();
+                }
+            }
+        });
+
+        let after_setup = traces.iter().position(|line| line == "--").unwrap();
+        let frame_events: Vec<&str> = traces[after_setup + 1..]
+            .iter()
+            .map(|line| line.as_str())
+            .filter(|line| !line.contains("removed") && !line.contains("added"))
+            .collect();
+        assert_eq!(
+            frame_events,
+            vec![
+                // The quitter leaves during `enterFrame`, so it misses
+                // `exitFrame`.
+                "enterFrame child stage=true",
+                "enterFrame quitter stage=true",
+                "exitFrame child stage=true",
+                "frame",
+                "frame",
+                "enterFrame child stage=true",
+                "exitFrame child stage=true",
+                "frame",
+            ]
+        );
+    }
+
+    /// A listener that traces the type of each event the stage gets.
+    fn trace_stage_event<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let mut event = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let event_type = event
+            .get_property(event, &QName::new(Namespace::public(), "type"), activation)?
+            .coerce_to_string(activation)?;
+
+        activation
+            .context
+            .log
+            .avm_trace(&format!("{} stage", event_type));
+
+        Ok(Value::Undefined)
+    }
+
+    #[test]
+    fn stage_gets_frame_events_first() {
+        let traces = trace_with_avm2(|activation| {
+            let movie = Arc::new(SwfMovie::from_path(AS3_MOVIE).unwrap());
+            let root = *activation.context.levels.get(&0).unwrap();
+            let root_proto = activation.avm2().prototypes().movieclip;
+            let root = Object::from(StageObject::for_display_object(
+                activation.context.gc_context,
+                root,
+                root_proto,
+            ));
+            let child = listening_clip(activation, &movie, "child");
+            add_child(activation, Some(root), &[child.into()]).unwrap();
+
+            let stage = activation.context.avm2.stage().unwrap();
+            add_listener(activation, stage, "enterFrame", trace_stage_event);
+            add_listener(activation, stage, "exitFrame", trace_stage_event);
+            add_listener(activation, child, "enterFrame", trace_event);
+            activation.context.log.avm_trace("--");
+
+            Avm2::dispatch_frame_event(&mut activation.context, "enterFrame").unwrap();
+            Avm2::dispatch_frame_event(&mut activation.context, "exitFrame").unwrap();
+        });
+
+        let after_setup = traces.iter().position(|line| line == "--").unwrap();
+        assert_eq!(
+            traces[after_setup + 1..].to_vec(),
+            vec![
+                "enterFrame stage",
+                "enterFrame child stage=true",
+                "exitFrame stage",
+            ]
+        );
+    }
+
+    /// A clip named `name` that Tab can focus if `focusable` is set.
+    fn tab_clip<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        movie: &Arc<SwfMovie>,
+        name: &str,
+        focusable: bool,
+    ) -> Object<'gc> {
+        let clip = MovieClip::new(
+            SwfSlice::empty(movie.clone()),
+            activation.context.gc_context,
+        );
+        clip.set_focusable(focusable, &mut activation.context);
+        let clip: DisplayObject<'gc> = clip.into();
+        clip.post_instantiation(
+            &mut activation.context,
+            clip,
+            None,
+            Instantiator::Avm2,
+            false,
+        );
+        clip.set_name(activation.context.gc_context, name);
+
+        clip.object2().coerce_to_object(activation).unwrap()
+    }
+
+    /// Press Tab, and return the name of the object that got focus.
+    fn press_tab(activation: &mut Activation<'_, '_, '_>) -> String {
+        let tracker = activation.context.focus_tracker;
+        tracker.cycle(&mut activation.context, false);
+
+        tracker.get().unwrap().name().to_string()
+    }
+
+    #[test]
+    fn tab_skips_subtrees_without_tab_children() {
+        with_avm2(|activation| {
+            let movie = Arc::new(SwfMovie::from_path(AS3_MOVIE).unwrap());
+            let root = *activation.context.levels.get(&0).unwrap();
+            let root_proto = activation.avm2().prototypes().movieclip;
+            let root = Object::from(StageObject::for_display_object(
+                activation.context.gc_context,
+                root,
+                root_proto,
+            ));
+            let first = tab_clip(activation, &movie, "first", true);
+            let group = tab_clip(activation, &movie, "group", false);
+            let hidden = tab_clip(activation, &movie, "hidden", true);
+            let last = tab_clip(activation, &movie, "last", true);
+            add_child(activation, Some(root), &[first.into()]).unwrap();
+            add_child(activation, Some(root), &[group.into()]).unwrap();
+            add_child(activation, Some(group), &[hidden.into()]).unwrap();
+            add_child(activation, Some(root), &[last.into()]).unwrap();
+
+            assert_eq!(press_tab(activation), "first");
+            assert_eq!(press_tab(activation), "hidden");
+
+            set_tab_children(activation, Some(group), &[false.into()]).unwrap();
+            assert_eq!(
+                tab_children(activation, Some(group), &[]).unwrap(),
+                false.into()
+            );
+            assert_eq!(press_tab(activation), "first");
+            assert_eq!(press_tab(activation), "last");
+            assert_eq!(press_tab(activation), "first");
+
+            // A script can still focus what Tab skips.
+            stage::set_focus(activation, None, &[hidden.into()]).unwrap();
+            assert_eq!(stage::focus(activation, None, &[]).unwrap(), hidden.into());
+        });
+    }
+
+    #[test]
+    fn tab_visits_explicit_indices_before_the_rest() {
+        with_avm2(|activation| {
+            let movie = Arc::new(SwfMovie::from_path(AS3_MOVIE).unwrap());
+            let root = *activation.context.levels.get(&0).unwrap();
+            let root_proto = activation.avm2().prototypes().movieclip;
+            let root = Object::from(StageObject::for_display_object(
+                activation.context.gc_context,
+                root,
+                root_proto,
+            ));
+            let mut clips = Vec::new();
+            for name in &["a", "b", "c", "d", "e"] {
+                let clip = tab_clip(activation, &movie, name, true);
+                add_child(activation, Some(root), &[clip.into()]).unwrap();
+                clips.push(clip);
+            }
+
+            interactiveobject::set_tab_index(activation, Some(clips[4]), &[1.into()]).unwrap();
+            interactiveobject::set_tab_index(activation, Some(clips[2]), &[2.into()]).unwrap();
+            interactiveobject::set_tab_index(activation, Some(clips[1]), &[2.into()]).unwrap();
+            assert_eq!(
+                interactiveobject::tab_index(activation, Some(clips[1]), &[]).unwrap(),
+                2.into()
+            );
+            assert_eq!(
+                interactiveobject::tab_index(activation, Some(clips[0]), &[]).unwrap(),
+                (-1).into()
+            );
+
+            // Explicit indices first, with ties in display order, then the
+            // rest in display order.
+            let order: Vec<String> = (0..6).map(|_| press_tab(activation)).collect();
+            assert_eq!(order, vec!["e", "b", "c", "a", "d", "e"]);
+
+            interactiveobject::set_tab_index(activation, Some(clips[2]), &[(-1).into()]).unwrap();
+            let order: Vec<String> = (0..5).map(|_| press_tab(activation)).collect();
+            assert_eq!(order, vec!["b", "a", "c", "d", "e"]);
+
+            assert!(
+                interactiveobject::set_tab_index(activation, Some(clips[0]), &[(-2).into()])
+                    .is_err()
+            );
+
+            stage::assign_focus(activation, None, &[clips[3].into(), "top".into()]).unwrap();
+            assert_eq!(press_tab(activation), "e");
+            assert!(
+                stage::assign_focus(activation, None, &[clips[3].into(), "up".into()]).is_err()
+            );
+        });
+    }
+
+    /// An ActionScript 3 movie whose timeline places an empty sprite at depth
+    /// 1 on its second frame, and places it again on its fourth.
+    fn timeline_children_movie() -> Arc<SwfMovie> {
+        let place = || {
+            Tag::PlaceObject(Box::new(PlaceObject {
+                version: 2,
+                action: PlaceObjectAction::Place(1),
+                depth: 1,
+                matrix: None,
+                color_transform: None,
+                ratio: None,
+                name: None,
+                clip_depth: None,
+                class_name: None,
+                filters: None,
+                background_color: None,
+                blend_mode: None,
+                clip_actions: None,
+                is_image: false,
+                is_bitmap_cached: None,
+                is_visible: None,
+                amf_data: None,
+            }))
+        };
+        let swf = Swf {
+            header: Header {
+                compression: Compression::None,
+                version: 10,
+                uncompressed_length: 0,
+                stage_size: Rectangle {
+                    x_min: Twips::new(0),
+                    x_max: Twips::from_pixels(100.0),
+                    y_min: Twips::new(0),
+                    y_max: Twips::from_pixels(100.0),
+                },
+                frame_rate: 24.0,
+                num_frames: 4,
+            },
+            tags: vec![
+                Tag::FileAttributes(FileAttributes {
+                    use_direct_blit: false,
+                    use_gpu: false,
+                    has_metadata: false,
+                    is_action_script_3: true,
+                    use_network_sandbox: false,
+                }),
+                Tag::DefineSprite(Sprite {
+                    id: 1,
+                    num_frames: 1,
+                    tags: vec![Tag::ShowFrame],
+                }),
+                Tag::ShowFrame,
+                place(),
+                Tag::ShowFrame,
+                Tag::ShowFrame,
+                place(),
+                Tag::ShowFrame,
+            ],
+        };
+
+        let mut data = Vec::new();
+        swf::write_swf(&swf, &mut data).unwrap();
+
+        Arc::new(SwfMovie::from_data(&data, None).unwrap())
+    }
+
+    /// The root as ActionScript sees it.
+    fn root_object<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Object<'gc> {
+        activation
+            .context
+            .levels
+            .get(&0)
+            .unwrap()
+            .object2()
+            .coerce_to_object(activation)
+            .unwrap()
+    }
+
+    /// Count the root's children as ActionScript sees them.
+    fn root_num_children(player: &TestPlayer) -> u32 {
+        player.update(|activation| {
+            let root = root_object(activation);
+            num_children(activation, Some(root), &[])
+                .unwrap()
+                .coerce_to_u32(activation)
+                .unwrap()
+        })
+    }
+
+    #[test]
+    fn timeline_children_are_visible_to_script_immediately() {
+        let player = TestPlayer::new();
+        player.lock().set_root_movie(timeline_children_movie());
+
+        player.lock().run_frame();
+        assert_eq!(root_num_children(&player), 0);
+
+        player.lock().run_frame();
+        assert_eq!(root_num_children(&player), 1);
+
+        // Script takes the timeline's child away, and the timeline doesn't
+        // bring it back until it places it again.
+        player.update(|activation| {
+            let root = root_object(activation);
+            let child = get_child_at(activation, Some(root), &[0.into()]).unwrap();
+            remove_child(activation, Some(root), &[child]).unwrap();
+            assert_eq!(num_children(activation, Some(root), &[]).unwrap(), 0.into());
+        });
+
+        player.lock().run_frame();
+        assert_eq!(root_num_children(&player), 0);
+
+        player.lock().run_frame();
+        assert_eq!(root_num_children(&player), 1);
+    }
+}
@@ -0,0 +1,542 @@
+//! `flash.display.Graphics` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::flash::geom::matrix::object_to_matrix;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::display_object::{MovieClip, TDisplayObject};
+use crate::drawing::{decode_path, decode_triangles, TriangleCulling};
+use crate::shape_utils::DrawCommand;
+use gc_arena::{GcCell, MutationContext};
+use swf::{
+    Color, FillStyle, Gradient, GradientInterpolation, GradientRecord, GradientSpread,
+    LineCapStyle, LineJoinStyle, LineStyle, Matrix, Twips,
+};
+
+/// Implements `flash.display.Graphics`'s instance constructor.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Err("ArgumentError: Error #2012: Graphics class cannot be instantiated.".into())
+}
+
+/// Implements `flash.display.Graphics`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Get the movie clip that a `Graphics` object draws into.
+fn drawing_target(this: Option<Object<'_>>) -> Option<MovieClip<'_>> {
+    this.and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_movie_clip())
+}
+
+/// Convert an AS3 color and `0.0`-`1.0` alpha into a `Color`.
+fn color_from_args<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    rgb: Option<&Value<'gc>>,
+    alpha: Option<&Value<'gc>>,
+) -> Result<Color, Error> {
+    let rgb = rgb
+        .cloned()
+        .unwrap_or_else(|| 0.into())
+        .coerce_to_u32(activation)?;
+    let alpha = alpha
+        .cloned()
+        .unwrap_or_else(|| 1.0.into())
+        .coerce_to_number(activation)?
+        .max(0.0)
+        .min(1.0);
+
+    Ok(Color::from_rgb(rgb, (alpha * 255.0) as u8))
+}
+
+/// Read the elements of an array argument.
+///
+/// `null` and `undefined` yield `None`; holes are read as `undefined`.
+fn array_arg<'gc>(value: Option<&Value<'gc>>) -> Result<Option<Vec<Value<'gc>>>, Error> {
+    match value.cloned().unwrap_or(Value::Undefined) {
+        Value::Undefined | Value::Null => Ok(None),
+        Value::Object(object) => {
+            let array = object
+                .as_array_storage()
+                .ok_or("TypeError: Error #1034: Type Coercion failed: expected an array")?;
+
+            Ok(Some(
+                array
+                    .iter()
+                    .map(|v| v.unwrap_or(Value::Undefined))
+                    .collect(),
+            ))
+        }
+        _ => Err("TypeError: Error #1034: Type Coercion failed: expected an array".into()),
+    }
+}
+
+/// Read the elements of an array argument as numbers.
+fn number_array_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Option<&Value<'gc>>,
+) -> Result<Option<Vec<f64>>, Error> {
+    if let Some(values) = array_arg(value)? {
+        let mut numbers = Vec::with_capacity(values.len());
+        for value in values {
+            numbers.push(value.coerce_to_number(activation)?);
+        }
+
+        return Ok(Some(numbers));
+    }
+
+    Ok(None)
+}
+
+/// Read the elements of an array argument as integers.
+fn int_array_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Option<&Value<'gc>>,
+) -> Result<Option<Vec<i32>>, Error> {
+    if let Some(values) = array_arg(value)? {
+        let mut ints = Vec::with_capacity(values.len());
+        for value in values {
+            ints.push(value.coerce_to_i32(activation)?);
+        }
+
+        return Ok(Some(ints));
+    }
+
+    Ok(None)
+}
+
+/// Convert the arguments of `beginGradientFill` or `lineGradientStyle`,
+/// starting from the gradient type, into a gradient fill.
+///
+/// Colors, alphas and ratios of different lengths yield `None`, as does
+/// leaving any of them out.
+fn gradient_from_args<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Option<FillStyle>, Error> {
+    let gradient_type = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+    let colors = array_arg(args.get(1))?;
+    let alphas = number_array_arg(activation, args.get(2))?;
+    let ratios = number_array_arg(activation, args.get(3))?;
+
+    let (colors, alphas, ratios) = match (colors, alphas, ratios) {
+        (Some(colors), Some(alphas), Some(ratios))
+            if colors.len() == alphas.len() && colors.len() == ratios.len() =>
+        {
+            (colors, alphas, ratios)
+        }
+        _ => return Ok(None),
+    };
+
+    let mut records = Vec::with_capacity(colors.len());
+    for ((color, alpha), ratio) in colors.iter().zip(alphas.iter()).zip(ratios.iter()) {
+        records.push(GradientRecord {
+            ratio: ratio.max(0.0).min(255.0) as u8,
+            color: color_from_args(activation, Some(color), Some(&(*alpha).into()))?,
+        });
+    }
+
+    let matrix = match args.get(4) {
+        Some(Value::Object(matrix)) => object_to_matrix(*matrix, activation)?,
+        _ => Matrix::identity(),
+    };
+    let spread = match args
+        .get(5)
+        .cloned()
+        .unwrap_or_else(|| "pad".into())
+        .coerce_to_string(activation)?
+        .as_str()
+    {
+        "reflect" => GradientSpread::Reflect,
+        "repeat" => GradientSpread::Repeat,
+        _ => GradientSpread::Pad,
+    };
+    let interpolation = match args
+        .get(6)
+        .cloned()
+        .unwrap_or_else(|| "rgb".into())
+        .coerce_to_string(activation)?
+        .as_str()
+    {
+        "linearRGB" => GradientInterpolation::LinearRgb,
+        _ => GradientInterpolation::Rgb,
+    };
+    let focal_point = args
+        .get(7)
+        .cloned()
+        .unwrap_or_else(|| 0.into())
+        .coerce_to_number(activation)?;
+
+    let gradient = Gradient {
+        matrix,
+        spread,
+        interpolation,
+        records,
+    };
+
+    match gradient_type.as_str() {
+        "linear" => Ok(Some(FillStyle::LinearGradient(gradient))),
+        "radial" if focal_point != 0.0 => Ok(Some(FillStyle::FocalGradient {
+            gradient,
+            focal_point: focal_point.max(-1.0).min(1.0) as f32,
+        })),
+        "radial" => Ok(Some(FillStyle::RadialGradient(gradient))),
+        _ => Err(
+            "ArgumentError: Error #2008: Parameter type must be one of the accepted values.".into(),
+        ),
+    }
+}
+
+/// Implements `Graphics.beginFill`.
+pub fn begin_fill<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mc) = drawing_target(this) {
+        let color = color_from_args(activation, args.get(0), args.get(1))?;
+
+        mc.set_fill_style(&mut activation.context, Some(FillStyle::Color(color)));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.beginGradientFill`.
+pub fn begin_gradient_fill<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mc) = drawing_target(this) {
+        let fill_style = gradient_from_args(activation, args)?;
+
+        mc.set_fill_style(&mut activation.context, fill_style);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.endFill`.
+pub fn end_fill<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mc) = drawing_target(this) {
+        mc.set_fill_style(&mut activation.context, None);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.lineStyle`.
+pub fn line_style<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mc) = drawing_target(this) {
+        let thickness = args
+            .get(0)
+            .cloned()
+            .unwrap_or_else(|| f64::NAN.into())
+            .coerce_to_number(activation)?;
+
+        if thickness.is_nan() {
+            mc.set_line_style(&mut activation.context, None);
+        } else {
+            let color = color_from_args(activation, args.get(1), args.get(2))?;
+
+            mc.set_line_style(
+                &mut activation.context,
+                Some(LineStyle {
+                    width: Twips::from_pixels(thickness.min(255.0).max(0.0)),
+                    color,
+                    start_cap: LineCapStyle::Round,
+                    end_cap: LineCapStyle::Round,
+                    join_style: LineJoinStyle::Round,
+                    fill_style: None,
+                    allow_scale_x: true,
+                    allow_scale_y: true,
+                    is_pixel_hinted: false,
+                    allow_close: false,
+                }),
+            );
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.lineGradientStyle`.
+///
+/// This strokes the rest of the current line with a gradient, keeping the
+/// width and other settings from `lineStyle`. Without a current line, this
+/// does nothing.
+pub fn line_gradient_style<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mc) = drawing_target(this) {
+        if let Some(fill_style) = gradient_from_args(activation, args)? {
+            mc.set_line_fill_style(&mut activation.context, fill_style);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.moveTo`.
+pub fn move_to<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mc) = drawing_target(this) {
+        let x = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+        let y = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+
+        mc.draw_command(
+            &mut activation.context,
+            DrawCommand::MoveTo {
+                x: Twips::from_pixels(x),
+                y: Twips::from_pixels(y),
+            },
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.lineTo`.
+pub fn line_to<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mc) = drawing_target(this) {
+        let x = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+        let y = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+
+        mc.draw_command(
+            &mut activation.context,
+            DrawCommand::LineTo {
+                x: Twips::from_pixels(x),
+                y: Twips::from_pixels(y),
+            },
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.curveTo`.
+pub fn curve_to<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mc) = drawing_target(this) {
+        let mut coords = [0.0; 4];
+        for (i, coord) in coords.iter_mut().enumerate() {
+            *coord = args
+                .get(i)
+                .cloned()
+                .unwrap_or(Value::Undefined)
+                .coerce_to_number(activation)?;
+        }
+
+        mc.draw_command(
+            &mut activation.context,
+            DrawCommand::CurveTo {
+                x1: Twips::from_pixels(coords[0]),
+                y1: Twips::from_pixels(coords[1]),
+                x2: Twips::from_pixels(coords[2]),
+                y2: Twips::from_pixels(coords[3]),
+            },
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.clear`.
+pub fn clear<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mc) = drawing_target(this) {
+        mc.clear(&mut activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.drawPath`.
+///
+/// The winding rule is currently ignored, as our fills always use the
+/// even-odd rule.
+pub fn draw_path<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mc) = drawing_target(this) {
+        let commands = int_array_arg(activation, args.get(0))?.unwrap_or_default();
+        let data = number_array_arg(activation, args.get(1))?.unwrap_or_default();
+
+        let draw_commands = decode_path(&commands, &data, mc.drawing_cursor())
+            .ok_or("ArgumentError: Error #2004: One of the parameters is invalid.")?;
+
+        for command in draw_commands {
+            mc.draw_command(&mut activation.context, command);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.drawTriangles`.
+///
+/// UV data is validated, but textured triangles are drawn with the current
+/// fill until bitmap fills are supported.
+pub fn draw_triangles<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mc) = drawing_target(this) {
+        let vertices = number_array_arg(activation, args.get(0))?.unwrap_or_default();
+        let indices = int_array_arg(activation, args.get(1))?;
+        let uvt_data = number_array_arg(activation, args.get(2))?;
+        let culling = match args
+            .get(3)
+            .cloned()
+            .unwrap_or_else(|| "none".into())
+            .coerce_to_string(activation)?
+            .as_str()
+        {
+            "none" => TriangleCulling::None,
+            "positive" => TriangleCulling::Positive,
+            "negative" => TriangleCulling::Negative,
+            _ => return Err(
+                "ArgumentError: Error #2008: Parameter culling must be one of the accepted values."
+                    .into(),
+            ),
+        };
+
+        if let Some(uvt_data) = uvt_data {
+            let vertex_count = vertices.len() / 2;
+            if uvt_data.len() != vertex_count * 2 && uvt_data.len() != vertex_count * 3 {
+                return Err("ArgumentError: Error #2004: One of the parameters is invalid.".into());
+            }
+        }
+
+        let draw_commands = decode_triangles(&vertices, indices.as_deref(), culling)
+            .ok_or("ArgumentError: Error #2004: One of the parameters is invalid.")?;
+
+        for command in draw_commands {
+            mc.draw_command(&mut activation.context, command);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Graphics`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.display"), "Graphics"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED | ClassAttributes::FINAL);
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "beginFill"),
+        Method::from_builtin(begin_fill),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "beginGradientFill"),
+        Method::from_builtin(begin_gradient_fill),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "endFill"),
+        Method::from_builtin(end_fill),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "lineStyle"),
+        Method::from_builtin(line_style),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "lineGradientStyle"),
+        Method::from_builtin(line_gradient_style),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "moveTo"),
+        Method::from_builtin(move_to),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "lineTo"),
+        Method::from_builtin(line_to),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "curveTo"),
+        Method::from_builtin(curve_to),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "clear"),
+        Method::from_builtin(clear),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "drawPath"),
+        Method::from_builtin(draw_path),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "drawTriangles"),
+        Method::from_builtin(draw_triangles),
+    ));
+
+    class
+}
@@ -4,9 +4,11 @@ use crate::avm2::activation::Activation;
 use crate::avm2::class::Class;
 use crate::avm2::method::Method;
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::Object;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
+use crate::display_object::TDisplayObject;
 use gc_arena::{GcCell, MutationContext};
 
 /// Implements `flash.display.InteractiveObject`'s instance constructor.
@@ -31,13 +33,167 @@ pub fn class_init<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `contextMenu`'s getter.
+pub fn context_menu<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.context_menu().map(Value::from).unwrap_or(Value::Null));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `contextMenu`'s setter.
+pub fn set_context_menu<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let menu = match args.get(0).cloned().unwrap_or(Value::Null) {
+            Value::Undefined | Value::Null => None,
+            Value::Object(menu)
+                if menu.has_prototype_in_chain(
+                    activation.context.avm2.prototypes().context_menu,
+                    false,
+                )? =>
+            {
+                Some(menu)
+            }
+            _ => {
+                return Err(
+                    "TypeError: Error #1034: Type Coercion failed: expected a ContextMenu".into(),
+                )
+            }
+        };
+
+        dobj.set_context_menu(activation.context.gc_context, menu);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `focusRect`'s getter.
+pub fn focus_rect<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.focus_rect().map(Value::from).unwrap_or(Value::Null));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `focusRect`'s setter.
+///
+/// `null` (or `undefined`) goes back to following `Stage.stageFocusRect`.
+pub fn set_focus_rect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let value = match args.get(0).cloned().unwrap_or(Value::Null) {
+            Value::Undefined | Value::Null => None,
+            value => Some(value.coerce_to_boolean()),
+        };
+
+        dobj.set_focus_rect(activation.context.gc_context, value);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `tabIndex`'s getter.
+pub fn tab_index<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj
+            .tab_index()
+            .map(|index| Value::Integer(index as i32))
+            .unwrap_or(Value::Integer(-1)));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `tabIndex`'s setter.
+///
+/// `-1` takes the object out of the explicit tab order, back to its place in
+/// the display list.
+pub fn set_tab_index<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let index = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)?;
+        let index = match index {
+            -1 => None,
+            index if index < 0 => {
+                return Err(format!(
+                    "RangeError: Error #2027: Parameter tabIndex must be a non-negative number; got {}.",
+                    index
+                )
+                .into())
+            }
+            index => Some(index as u32),
+        };
+
+        dobj.set_tab_index(activation.context.gc_context, index);
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Construct `InteractiveObject`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
-    Class::new(
+    let class = Class::new(
         QName::new(Namespace::package("flash.display"), "InteractiveObject"),
         Some(QName::new(Namespace::package("flash.display"), "DisplayObject").into()),
         Method::from_builtin(instance_init),
         Method::from_builtin(class_init),
         mc,
-    )
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "contextMenu"),
+        Method::from_builtin(context_menu),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "contextMenu"),
+        Method::from_builtin(set_context_menu),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "focusRect"),
+        Method::from_builtin(focus_rect),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "focusRect"),
+        Method::from_builtin(set_focus_rect),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "tabIndex"),
+        Method::from_builtin(tab_index),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "tabIndex"),
+        Method::from_builtin(set_tab_index),
+    ));
+
+    class
 }
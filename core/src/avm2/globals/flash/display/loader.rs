@@ -0,0 +1,302 @@
+//! `flash.display.Loader` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::domain::Domain;
+use crate::avm2::globals::flash::net::request_options;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::backend::navigator::RequestOptions;
+use crate::display_object::{MovieClip, TDisplayObject, TDisplayObjectContainer};
+use crate::tag_utils::{SwfMovie, SwfSlice};
+use gc_arena::{GcCell, MutationContext};
+use std::sync::Arc;
+
+/// Implements `flash.display.Loader`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+
+        if this.as_display_object().is_none() {
+            let movie = Arc::new(SwfMovie::empty(activation.context.swf.version()));
+            let new_do = MovieClip::new(SwfSlice::empty(movie), activation.context.gc_context);
+            new_do.set_avm2_object(activation.context.gc_context, this);
+
+            this.init_display_object(activation.context.gc_context, new_do.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.display.Loader`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Select the domain that a movie loaded with `context` runs its code in.
+///
+/// A `LoaderContext` with an `applicationDomain` loads the movie straight
+/// into that domain. Otherwise, the movie gets a child of the domain of the
+/// code calling `load`.
+fn domain_for_context<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    context: Value<'gc>,
+) -> Result<Domain<'gc>, Error> {
+    if let Value::Object(mut context) = context {
+        let application_domain = context.get_property(
+            context,
+            &QName::new(Namespace::public(), "applicationDomain"),
+            activation,
+        )?;
+
+        if let Some(domain) = match application_domain {
+            Value::Object(domain) => domain.as_application_domain(),
+            _ => None,
+        } {
+            return Ok(domain);
+        }
+    }
+
+    let caller_domain = activation
+        .scope()
+        .and_then(|scope| scope.read().globals().as_application_domain())
+        .unwrap_or_else(|| activation.avm2().global_domain());
+
+    Ok(Domain::movie_domain(
+        activation.context.gc_context,
+        caller_domain,
+    ))
+}
+
+/// Implements `Loader.load`.
+///
+/// The loaded movie replaces whatever this loader loaded before, and runs
+/// its code in the domain selected by the `LoaderContext` argument.
+pub fn load<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut loader) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_container())
+    {
+        let (url, options) = match args.get(0).cloned().unwrap_or(Value::Undefined) {
+            Value::Object(request) => request_options(activation, request)?,
+            Value::Undefined | Value::Null => {
+                return Err("TypeError: Error #2007: Parameter request must be non-null.".into())
+            }
+            url => (
+                url.coerce_to_string(activation)?.to_string(),
+                RequestOptions::get(),
+            ),
+        };
+        let domain = domain_for_context(activation, args.get(1).cloned().unwrap_or(Value::Null))?;
+
+        let movie = Arc::new(SwfMovie::empty(activation.context.swf.version()));
+        let content = MovieClip::new(SwfSlice::empty(movie), activation.context.gc_context);
+        loader.remove_range(&mut activation.context, ..);
+        loader.insert_at_index(&mut activation.context, content.into(), 0);
+
+        let fetch = activation.context.navigator.fetch(&url, options);
+        let process = activation.context.load_manager.load_movie_into_clip(
+            activation.context.player.clone().unwrap(),
+            content.into(),
+            fetch,
+            url,
+            None,
+            Some(domain),
+        );
+
+        activation.context.navigator.spawn_future(process);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `content`'s getter.
+pub fn content<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(loader) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_container())
+    {
+        let content = loader.child_by_index(0).map(|child| child.object2());
+
+        return Ok(match content {
+            Some(Value::Object(content)) => content.into(),
+            _ => Value::Null,
+        });
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Loader`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.display"), "Loader"),
+        Some(
+            QName::new(
+                Namespace::package("flash.display"),
+                "DisplayObjectContainer",
+            )
+            .into(),
+        ),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "load"),
+        Method::from_builtin(load),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "content"),
+        Method::from_builtin(content),
+    ));
+
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::object::{DomainObject, ScriptObject};
+    use crate::avm2::test_utils::{call, construct, get, park, parked, set, TestPlayer};
+
+    /// A movie that defines the public class `Test`.
+    const MOVIE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/swfs/avm2/hello_world");
+
+    fn test_class<'gc>() -> QName<'gc> {
+        QName::new(Namespace::public(), "Test")
+    }
+
+    /// Load the movie with a `LoaderContext` for `domain`, or with no
+    /// context at all, and run the load to completion.
+    fn load_into<F>(domain: F) -> TestPlayer
+    where
+        F: for<'gc> FnOnce(&mut Activation<'_, 'gc, '_>) -> Option<Domain<'gc>>,
+    {
+        let mut player = TestPlayer::with_base_path(MOVIE_DIR);
+
+        player.update(|activation| {
+            let context = match domain(activation) {
+                Some(domain) => {
+                    let domain = DomainObject::from_domain(
+                        activation.context.gc_context,
+                        Some(activation.avm2().prototypes().application_domain),
+                        domain,
+                    );
+                    park(activation, "domain", domain.into());
+
+                    construct(
+                        activation,
+                        "flash.system",
+                        "LoaderContext",
+                        &[false.into(), domain.into()],
+                    )
+                    .into()
+                }
+                None => Value::Null,
+            };
+            let request = ScriptObject::object(
+                activation.context.gc_context,
+                activation.avm2().prototypes().object,
+            );
+            set(activation, request, "url", "test.swf".into());
+
+            let loader = construct(activation, "flash.display", "Loader", &[]);
+            call(activation, loader, "load", &[request.into(), context]);
+            assert_eq!(get(activation, loader, "content"), Value::Null);
+            park(activation, "loader", loader.into());
+        });
+
+        player.block();
+        player
+    }
+
+    #[test]
+    fn shared_domain_exposes_the_loaded_classes() {
+        let player = load_into(|activation| Some(activation.avm2().global_domain()));
+
+        player.update(|activation| {
+            let loader = parked(activation, "loader")
+                .coerce_to_object(activation)
+                .unwrap();
+            assert!(matches!(
+                get(activation, loader, "content"),
+                Value::Object(_)
+            ));
+
+            // The loading movie can use the loaded movie's class directly.
+            let class = activation
+                .avm2()
+                .global_domain()
+                .get_defined_value(activation, test_class())
+                .unwrap()
+                .coerce_to_object(activation)
+                .unwrap();
+            assert!(class.as_class().is_some());
+        });
+    }
+
+    #[test]
+    fn requested_domain_keeps_the_loaded_classes() {
+        let player = load_into(|activation| {
+            let global = activation.avm2().global_domain();
+            Some(Domain::movie_domain(activation.context.gc_context, global))
+        });
+
+        player.update(|activation| {
+            let domain = parked(activation, "domain")
+                .coerce_to_object(activation)
+                .unwrap()
+                .as_application_domain()
+                .unwrap();
+            assert!(domain.has_definition(test_class()));
+            assert!(!activation
+                .avm2()
+                .global_domain()
+                .has_definition(test_class()));
+        });
+    }
+
+    #[test]
+    fn missing_context_loads_into_a_child_domain() {
+        let player = load_into(|_| None);
+
+        player.update(|activation| {
+            let loader = parked(activation, "loader")
+                .coerce_to_object(activation)
+                .unwrap();
+            assert!(matches!(
+                get(activation, loader, "content"),
+                Value::Object(_)
+            ));
+            assert!(!activation
+                .avm2()
+                .global_domain()
+                .has_definition(test_class()));
+        });
+    }
+}
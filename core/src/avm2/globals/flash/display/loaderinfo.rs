@@ -0,0 +1,461 @@
+//! `flash.display.LoaderInfo` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{ByteArrayObject, Object, ScriptObject, StageObject, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::display_object::{DisplayObject, TDisplayObject};
+use crate::tag_utils::SwfMovie;
+use gc_arena::{GcCell, MutationContext};
+use std::sync::Arc;
+
+/// Implements `flash.display.LoaderInfo`'s instance constructor.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Err("ArgumentError: Error #2012: LoaderInfo class cannot be instantiated.".into())
+}
+
+/// Implements `flash.display.LoaderInfo`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Find the root display object of the SWF that defined `dobj`.
+///
+/// This is the outermost ancestor of `dobj` that came from the same movie.
+/// Objects that aren't on any display list have no owning root, and thus
+/// yield `None`.
+fn owning_root<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    dobj: DisplayObject<'gc>,
+) -> Option<DisplayObject<'gc>> {
+    let movie = dobj.movie()?;
+    let is_level = |dobj| {
+        activation
+            .context
+            .levels
+            .values()
+            .any(|level| DisplayObject::ptr_eq(*level, dobj))
+    };
+
+    if dobj.parent().is_none() && !is_level(dobj) {
+        return None;
+    }
+
+    let mut root = dobj;
+    while let Some(parent) = root.parent() {
+        match parent.movie() {
+            Some(parent_movie) if Arc::ptr_eq(&parent_movie, &movie) => root = parent,
+            _ => break,
+        }
+    }
+
+    Some(root)
+}
+
+/// Get the `LoaderInfo` of the SWF that defined a display object.
+///
+/// The returned object is bound to the root display object of that SWF, or is
+/// `null` if the display object isn't on a display list yet.
+pub fn loader_info_for_display_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    dobj: DisplayObject<'gc>,
+) -> Result<Value<'gc>, Error> {
+    if let Some(root) = owning_root(activation, dobj) {
+        let loader_info_proto = activation.context.avm2.prototypes().loaderinfo;
+        let loader_info: Object<'gc> =
+            StageObject::for_display_object(activation.context.gc_context, root, loader_info_proto)
+                .into();
+
+        // `LoaderInfo` can't be constructed from script, but it still needs
+        // to be able to dispatch events, so run `EventDispatcher`'s
+        // constructor on it instead.
+        if let Some(mut dispatcher_proto) = loader_info_proto.proto() {
+            let dispatcher_constr = dispatcher_proto
+                .get_property(
+                    dispatcher_proto,
+                    &QName::new(Namespace::public(), "constructor"),
+                    activation,
+                )?
+                .coerce_to_object(activation)?;
+
+            dispatcher_constr.call(Some(loader_info), &[], activation, Some(dispatcher_proto))?;
+        }
+
+        return Ok(loader_info.into());
+    }
+
+    Ok(Value::Null)
+}
+
+/// Get the movie that a `LoaderInfo` describes.
+fn loaded_movie(this: Option<Object<'_>>) -> Option<Arc<SwfMovie>> {
+    this.and_then(|this| this.as_display_object())
+        .and_then(|root| root.movie())
+}
+
+/// Implements `content`'s getter.
+pub fn content<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(root) = this.and_then(|this| this.as_display_object()) {
+        return Ok(root.object2());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `url`'s getter.
+pub fn url<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(movie) = loaded_movie(this) {
+        return Ok(movie
+            .url()
+            .map(|url| AvmString::new(activation.context.gc_context, url).into())
+            .unwrap_or(Value::Null));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `parameters`'s getter.
+pub fn parameters<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(movie) = loaded_movie(this) {
+        let mut parameters = ScriptObject::object(
+            activation.context.gc_context,
+            activation.avm2().prototypes().object,
+        );
+
+        for (key, value) in movie.parameters().iter() {
+            let key = AvmString::new(activation.context.gc_context, key);
+            let value = AvmString::new(activation.context.gc_context, value);
+
+            parameters.set_property(
+                parameters,
+                &QName::new(Namespace::public(), key),
+                value.into(),
+                activation,
+            )?;
+        }
+
+        return Ok(parameters.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `swfVersion`'s getter.
+pub fn swf_version<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(movie) = loaded_movie(this) {
+        return Ok(movie.version().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `frameRate`'s getter.
+pub fn frame_rate<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(movie) = loaded_movie(this) {
+        return Ok(f64::from(movie.header().frame_rate).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `width`'s getter.
+pub fn width<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(movie) = loaded_movie(this) {
+        return Ok(movie.width().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `height`'s getter.
+pub fn height<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(movie) = loaded_movie(this) {
+        return Ok(movie.height().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `bytes`'s getter.
+///
+/// This is the SWF file with its body uncompressed.
+pub fn bytes<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(movie) = loaded_movie(this) {
+        let bytearray = ByteArrayObject::construct(
+            activation.context.gc_context,
+            Some(activation.avm2().prototypes().bytearray),
+        );
+
+        let header = swf::Header {
+            compression: swf::Compression::None,
+            ..movie.header().clone()
+        };
+        let mut data = Vec::new();
+        swf::write_swf_raw_tags(&header, movie.data(), &mut data)?;
+
+        if let Some(mut storage) = bytearray.as_bytearray_mut(activation.context.gc_context) {
+            storage.write_bytes(&data);
+            storage.set_position(0);
+        }
+
+        return Ok(bytearray.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `bytesTotal`'s getter.
+///
+/// Movies are only ever handed to display objects once fully loaded, so this
+/// is also the value of `bytesLoaded`.
+pub fn bytes_total<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(movie) = loaded_movie(this) {
+        return Ok(movie.header().uncompressed_length.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `LoaderInfo`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.display"), "LoaderInfo"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "content"),
+        Method::from_builtin(content),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "url"),
+        Method::from_builtin(url),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "parameters"),
+        Method::from_builtin(parameters),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "swfVersion"),
+        Method::from_builtin(swf_version),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "frameRate"),
+        Method::from_builtin(frame_rate),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "width"),
+        Method::from_builtin(width),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "height"),
+        Method::from_builtin(height),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "bytes"),
+        Method::from_builtin(bytes),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "bytesLoaded"),
+        Method::from_builtin(bytes_total),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "bytesTotal"),
+        Method::from_builtin(bytes_total),
+    ));
+
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::test_utils::with_avm2;
+    use crate::display_object::{MovieClip, TDisplayObjectContainer};
+    use crate::tag_utils::SwfSlice;
+    use crate::vminterface::Instantiator;
+
+    const ROOT_MOVIE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/swfs/avm2/add/test.swf");
+    const CHILD_MOVIE: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/swfs/avm2/array_concat/test.swf"
+    );
+
+    fn clip_from<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        movie: &Arc<SwfMovie>,
+        parent: Option<DisplayObject<'gc>>,
+    ) -> DisplayObject<'gc> {
+        let clip: DisplayObject<'gc> = MovieClip::new(
+            SwfSlice::empty(movie.clone()),
+            activation.context.gc_context,
+        )
+        .into();
+        clip.post_instantiation(
+            &mut activation.context,
+            clip,
+            None,
+            Instantiator::Avm2,
+            false,
+        );
+
+        if let Some(mut parent) = parent.and_then(|parent| parent.as_container()) {
+            let index = parent.num_children();
+            parent.insert_at_index(&mut activation.context, clip, index);
+        }
+
+        clip
+    }
+
+    fn loader_info_url<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        dobj: DisplayObject<'gc>,
+    ) -> String {
+        let loader_info = loader_info_for_display_object(activation, dobj)
+            .unwrap()
+            .coerce_to_object(activation)
+            .unwrap();
+
+        url(activation, Some(loader_info), &[])
+            .unwrap()
+            .coerce_to_string(activation)
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn child_swf_objects_have_their_own_loader_info() {
+        with_avm2(|activation| {
+            let root_movie = Arc::new(SwfMovie::from_path(ROOT_MOVIE).unwrap());
+            let child_movie = Arc::new(SwfMovie::from_path(CHILD_MOVIE).unwrap());
+            let stage_root = *activation.context.levels.get(&0).unwrap();
+
+            let root = clip_from(activation, &root_movie, Some(stage_root));
+            let root_object = clip_from(activation, &root_movie, Some(root));
+            let child_root = clip_from(activation, &child_movie, Some(root_object));
+            let child_object = clip_from(activation, &child_movie, Some(child_root));
+
+            let root_url = loader_info_url(activation, root_object);
+            let child_url = loader_info_url(activation, child_object);
+            assert!(root_url.ends_with("avm2/add/test.swf"), "{}", root_url);
+            assert!(
+                child_url.ends_with("avm2/array_concat/test.swf"),
+                "{}",
+                child_url
+            );
+
+            // Each `LoaderInfo` describes the root of the SWF that defined
+            // the object, not the root of the stage.
+            let loader_info = loader_info_for_display_object(activation, child_object)
+                .unwrap()
+                .coerce_to_object(activation)
+                .unwrap();
+            let content = content(activation, Some(loader_info), &[]).unwrap();
+            assert!(content.strict_eq(&child_root.object2()));
+
+            let orphan = clip_from(activation, &child_movie, None);
+            assert!(matches!(
+                loader_info_for_display_object(activation, orphan),
+                Ok(Value::Null)
+            ));
+        });
+    }
+
+    #[test]
+    fn loader_info_describes_the_swf_header() {
+        with_avm2(|activation| {
+            let movie = Arc::new(SwfMovie::from_path(ROOT_MOVIE).unwrap());
+            let stage_root = *activation.context.levels.get(&0).unwrap();
+            let root = clip_from(activation, &movie, Some(stage_root));
+            let loader_info = loader_info_for_display_object(activation, root)
+                .unwrap()
+                .coerce_to_object(activation)
+                .unwrap();
+
+            assert_eq!(
+                swf_version(activation, Some(loader_info), &[]).unwrap(),
+                movie.version().into()
+            );
+            assert_eq!(
+                width(activation, Some(loader_info), &[]).unwrap(),
+                movie.width().into()
+            );
+            assert_eq!(
+                height(activation, Some(loader_info), &[]).unwrap(),
+                movie.height().into()
+            );
+            assert_eq!(
+                frame_rate(activation, Some(loader_info), &[]).unwrap(),
+                f64::from(movie.header().frame_rate).into()
+            );
+            assert!(movie.width() > 0 && movie.height() > 0);
+
+            // `bytes` is an uncompressed copy of the whole file.
+            let bytes = bytes(activation, Some(loader_info), &[])
+                .unwrap()
+                .coerce_to_object(activation)
+                .unwrap();
+            let bytes = bytes.as_bytearray().unwrap().bytes().to_vec();
+            assert_eq!(&bytes[..3], b"FWS");
+            let copy = SwfMovie::from_data(&bytes, None).unwrap();
+            assert_eq!(copy.version(), movie.version());
+            assert_eq!(copy.width(), movie.width());
+            assert_eq!(copy.header().num_frames, movie.header().num_frames);
+            assert_eq!(copy.data(), movie.data());
+        });
+    }
+}
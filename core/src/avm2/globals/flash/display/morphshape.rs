@@ -0,0 +1,179 @@
+//! `flash.display.MorphShape` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.display.MorphShape`'s instance constructor.
+///
+/// Morph shapes only come from the timeline, which attaches their objects
+/// to them without running this.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if this.as_display_object().is_none() {
+            return Err(
+                "ArgumentError: Error #2012: MorphShape class cannot be instantiated.".into(),
+            );
+        }
+
+        activation.super_init(this, &[])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.display.MorphShape`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct `MorphShape`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.display"), "MorphShape"),
+        Some(QName::new(Namespace::package("flash.display"), "DisplayObject").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED | ClassAttributes::FINAL);
+
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::globals::flash::display::displayobject::{height, width};
+    use crate::avm2::test_utils::with_avm2;
+    use crate::display_object::{MorphShape, MorphShapeStatic, TDisplayObject};
+    use crate::tag_utils::SwfMovie;
+    use crate::vminterface::Instantiator;
+    use std::sync::Arc;
+    use swf::{Color, FillStyle, ShapeRecord, StyleChangeData, Twips};
+
+    const AVM2_MOVIE: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/swfs/avm2/movieclip_currentlabels/test.swf"
+    );
+
+    /// A square with its top left corner at the origin.
+    fn square(size: f64) -> swf::MorphShape {
+        let size = Twips::from_pixels(size);
+        let zero = Twips::new(0);
+
+        swf::MorphShape {
+            shape_bounds: swf::Rectangle {
+                x_min: zero,
+                x_max: size,
+                y_min: zero,
+                y_max: size,
+            },
+            edge_bounds: swf::Rectangle {
+                x_min: zero,
+                x_max: size,
+                y_min: zero,
+                y_max: size,
+            },
+            fill_styles: vec![FillStyle::Color(Color {
+                r: 255,
+                g: 0,
+                b: 0,
+                a: 255,
+            })],
+            line_styles: vec![],
+            shape: vec![
+                ShapeRecord::StyleChange(StyleChangeData {
+                    move_to: Some((zero, zero)),
+                    fill_style_0: None,
+                    fill_style_1: Some(1),
+                    line_style: None,
+                    new_styles: None,
+                }),
+                ShapeRecord::StraightEdge {
+                    delta_x: size,
+                    delta_y: zero,
+                },
+                ShapeRecord::StraightEdge {
+                    delta_x: zero,
+                    delta_y: size,
+                },
+                ShapeRecord::StraightEdge {
+                    delta_x: zero - size,
+                    delta_y: zero,
+                },
+                ShapeRecord::StraightEdge {
+                    delta_x: zero,
+                    delta_y: zero - size,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn bounds_follow_the_tween() {
+        with_avm2(|activation| {
+            let movie = Arc::new(SwfMovie::from_path(AVM2_MOVIE).unwrap());
+            let tag = swf::DefineMorphShape {
+                version: 1,
+                id: 1,
+                has_non_scaling_strokes: false,
+                has_scaling_strokes: false,
+                start: square(20.0),
+                end: square(40.0),
+            };
+            let mut morph_static =
+                MorphShapeStatic::from_swf_tag(&mut activation.context, &tag, movie);
+            morph_static.register_ratio(&mut activation.context, 32768);
+
+            let mut morph_shape = MorphShape::new(activation.context.gc_context, morph_static);
+            morph_shape.post_instantiation(
+                &mut activation.context,
+                morph_shape.into(),
+                None,
+                Instantiator::Movie,
+                false,
+            );
+            let this = morph_shape.object2().coerce_to_object(activation).unwrap();
+            assert!(this
+                .has_prototype_in_chain(activation.avm2().prototypes().morph_shape, false)
+                .unwrap());
+
+            // 16384 was never registered, so it is interpolated.
+            for (ratio, size) in &[(0, 20.0), (16384, 25.0), (32768, 30.0), (65535, 40.0)] {
+                morph_shape.set_ratio(activation.context.gc_context, *ratio);
+
+                let width = width(activation, Some(this), &[]).unwrap();
+                let height = height(activation, Some(this), &[]).unwrap();
+                assert_eq!(
+                    width.coerce_to_number(activation).unwrap(),
+                    *size,
+                    "width at ratio {}",
+                    ratio
+                );
+                assert_eq!(
+                    height.coerce_to_number(activation).unwrap(),
+                    *size,
+                    "height at ratio {}",
+                    ratio
+                );
+            }
+        });
+    }
+}
@@ -104,14 +104,8 @@ pub fn current_frame_label<'gc>(
         .and_then(|dobj| dobj.as_movie_clip())
     {
         return Ok(mc
-            .current_label()
-            .and_then(|(label, start_frame)| {
-                if start_frame < mc.current_frame() {
-                    None
-                } else {
-                    Some(AvmString::new(activation.context.gc_context, label).into())
-                }
-            })
+            .current_frame_label()
+            .map(|label| AvmString::new(activation.context.gc_context, label).into())
             .unwrap_or(Value::Null));
     }
 
@@ -628,3 +622,146 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
 
     class
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::domain::Domain;
+    use crate::avm2::method::NativeMethod;
+    use crate::avm2::object::StageObject;
+    use crate::avm2::test_utils::with_avm2;
+    use crate::vminterface::Instantiator;
+    use swf::{Compression, FrameLabel, Header, Rectangle, Swf, Tag, Twips};
+
+    const LABELED_MOVIE: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/swfs/avm2/movieclip_currentlabels/test.swf"
+    );
+
+    fn label_of<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Object<'gc>,
+        getter: NativeMethod<'gc>,
+    ) -> Option<String> {
+        match getter(activation, Some(this), &[]).unwrap() {
+            Value::Null => None,
+            label => Some(label.coerce_to_string(activation).unwrap().to_string()),
+        }
+    }
+
+    #[test]
+    fn frame_labels_across_labeled_and_unlabeled_frames() {
+        with_avm2(|activation| {
+            let movie = Arc::new(SwfMovie::from_path(LABELED_MOVIE).unwrap());
+            let domain = Domain::movie_domain(
+                activation.context.gc_context,
+                activation.context.avm2.global_domain(),
+            );
+            activation
+                .context
+                .library
+                .library_for_movie_mut(movie.clone())
+                .set_avm2_domain(domain);
+
+            let clip = MovieClip::from_movie(activation.context.gc_context, movie);
+            clip.preload(&mut activation.context, &mut Default::default());
+            clip.post_instantiation(
+                &mut activation.context,
+                clip.into(),
+                None,
+                Instantiator::Avm2,
+                false,
+            );
+            let this = clip.object2().coerce_to_object(activation).unwrap();
+
+            // Scene 1 labels frames 1 and 3; scene 2 labels its first frame.
+            for (frame, label, frame_label) in &[
+                (1, Some("frame1"), Some("frame1")),
+                (2, Some("frame1"), None),
+                (3, Some("frame3"), Some("frame3")),
+                (4, Some("frame4"), Some("frame4")),
+            ] {
+                clip.goto_frame(&mut activation.context, *frame, true);
+
+                assert_eq!(
+                    label_of(activation, this, current_label).as_deref(),
+                    *label,
+                    "currentLabel on frame {}",
+                    frame
+                );
+                assert_eq!(
+                    label_of(activation, this, current_frame_label).as_deref(),
+                    *frame_label,
+                    "currentFrameLabel on frame {}",
+                    frame
+                );
+            }
+
+            clip.goto_frame(&mut activation.context, 2, true);
+            next_scene(activation, Some(this), &[]).unwrap();
+            assert_eq!(clip.current_frame(), 4);
+
+            prev_scene(activation, Some(this), &[]).unwrap();
+            assert_eq!(clip.current_frame(), 1);
+            assert_eq!(
+                label_of(activation, this, current_frame_label).as_deref(),
+                Some("frame1")
+            );
+        });
+    }
+
+    /// A movie whose only frame has two labels, `second` tagged first.
+    fn shared_frame_movie() -> Arc<SwfMovie> {
+        let label = |label: &'static str| {
+            Tag::FrameLabel(FrameLabel {
+                label: label.into(),
+                is_anchor: false,
+            })
+        };
+        let swf = Swf {
+            header: Header {
+                compression: Compression::None,
+                version: 10,
+                uncompressed_length: 0,
+                stage_size: Rectangle {
+                    x_min: Twips::new(0),
+                    x_max: Twips::from_pixels(100.0),
+                    y_min: Twips::new(0),
+                    y_max: Twips::from_pixels(100.0),
+                },
+                frame_rate: 24.0,
+                num_frames: 1,
+            },
+            tags: vec![label("second"), label("first"), Tag::ShowFrame],
+        };
+
+        let mut data = Vec::new();
+        swf::write_swf(&swf, &mut data).unwrap();
+
+        Arc::new(SwfMovie::from_data(&data, None).unwrap())
+    }
+
+    #[test]
+    fn labels_sharing_a_frame_resolve_in_tag_order() {
+        with_avm2(|activation| {
+            let clip = MovieClip::from_movie(activation.context.gc_context, shared_frame_movie());
+            clip.preload(&mut activation.context, &mut Default::default());
+            clip.goto_frame(&mut activation.context, 1, true);
+            let this: Object<'_> = StageObject::for_display_object(
+                activation.context.gc_context,
+                clip.into(),
+                activation.avm2().prototypes().movieclip,
+            )
+            .into();
+
+            assert_eq!(
+                label_of(activation, this, current_label).as_deref(),
+                Some("second")
+            );
+            assert_eq!(
+                label_of(activation, this, current_frame_label).as_deref(),
+                Some("second")
+            );
+        });
+    }
+}
@@ -0,0 +1,272 @@
+//! `flash.display.NativeWindow` builtin/prototype
+//!
+//! Ruffle never hosts AIR windows, so this only exists for content that
+//! touches the class while starting up. Every window reports the player's
+//! stage area as its bounds, and windowing methods do nothing.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::flash::geom::rectangle;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::Object;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::prelude::*;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.display.NativeWindow`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.display.NativeWindow`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `NativeWindow.isSupported`.
+pub fn is_supported<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(false.into())
+}
+
+/// Implements `bounds`'s getter.
+pub fn bounds<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let (width, height) = activation.context.stage_size;
+    let bounds = BoundingBox {
+        x_min: Twips::new(0),
+        y_min: Twips::new(0),
+        x_max: width,
+        y_max: height,
+        valid: true,
+    };
+
+    Ok(rectangle::bounding_box_to_object(&bounds, activation)?.into())
+}
+
+/// Implements `width`'s getter.
+pub fn width<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(activation.context.stage_size.0.to_pixels().into())
+}
+
+/// Implements `height`'s getter.
+pub fn height<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(activation.context.stage_size.1.to_pixels().into())
+}
+
+/// Implements `x` and `y`'s getters.
+pub fn origin<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(0.into())
+}
+
+/// Implements `title`'s getter.
+pub fn title<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok("".into())
+}
+
+/// Implements `active` and `closed`'s getters.
+pub fn inactive<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(false.into())
+}
+
+/// Implements `visible`'s getter.
+pub fn visible<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(true.into())
+}
+
+/// Implements every setter and windowing method, none of which can do
+/// anything without a window.
+pub fn unsupported<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    log::warn!("NativeWindow: windowing is not supported");
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `NativeWindow`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.display"), "NativeWindow"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "isSupported"),
+        Method::from_builtin(is_supported),
+    ));
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "bounds"),
+        Method::from_builtin(bounds),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "width"),
+        Method::from_builtin(width),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "height"),
+        Method::from_builtin(height),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "x"),
+        Method::from_builtin(origin),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "y"),
+        Method::from_builtin(origin),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "title"),
+        Method::from_builtin(title),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "active"),
+        Method::from_builtin(inactive),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "closed"),
+        Method::from_builtin(inactive),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "visible"),
+        Method::from_builtin(visible),
+    ));
+
+    for setter in &["bounds", "width", "height", "x", "y", "title", "visible"] {
+        write.define_instance_trait(Trait::from_setter(
+            QName::new(Namespace::public(), *setter),
+            Method::from_builtin(unsupported),
+        ));
+    }
+
+    for method in &[
+        "activate",
+        "close",
+        "maximize",
+        "minimize",
+        "restore",
+        "orderToBack",
+        "orderToFront",
+        "startMove",
+        "startResize",
+    ] {
+        write.define_instance_trait(Trait::from_method(
+            QName::new(Namespace::public(), *method),
+            Method::from_builtin(unsupported),
+        ));
+    }
+
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::object::TObject;
+    use crate::avm2::test_utils::with_avm2;
+
+    #[test]
+    fn stage_has_no_native_window() {
+        with_avm2(|activation| {
+            let mut stage = activation.context.avm2.stage().unwrap();
+            let native_window = stage
+                .get_property(
+                    stage,
+                    &QName::new(Namespace::public(), "nativeWindow"),
+                    activation,
+                )
+                .unwrap();
+
+            assert_eq!(native_window, Value::Null);
+        });
+    }
+
+    #[test]
+    fn bounds_cover_the_stage() {
+        with_avm2(|activation| {
+            let (stage_width, stage_height) = activation.context.stage_size;
+            let mut bounds = bounds(activation, None, &[])
+                .unwrap()
+                .coerce_to_object(activation)
+                .unwrap();
+
+            for (name, expected) in &[
+                ("x", 0.0),
+                ("y", 0.0),
+                ("width", stage_width.to_pixels()),
+                ("height", stage_height.to_pixels()),
+            ] {
+                let value = bounds
+                    .get_property(bounds, &QName::new(Namespace::public(), *name), activation)
+                    .unwrap()
+                    .coerce_to_number(activation)
+                    .unwrap();
+
+                assert_eq!(value, *expected, "bounds.{}", name);
+            }
+
+            assert_eq!(
+                width(activation, None, &[]).unwrap(),
+                Value::from(stage_width.to_pixels())
+            );
+            assert_eq!(title(activation, None, &[]).unwrap(), Value::from(""));
+            assert_eq!(
+                unsupported(activation, None, &[]).unwrap(),
+                Value::Undefined
+            );
+        });
+    }
+}
@@ -4,9 +4,11 @@ use crate::avm2::activation::Activation;
 use crate::avm2::class::Class;
 use crate::avm2::method::Method;
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::Object;
+use crate::avm2::object::{Object, StageObject, TObject};
+use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
+use crate::display_object::TDisplayObject;
 use gc_arena::{GcCell, MutationContext};
 
 /// Implements `flash.display.Sprite`'s instance constructor.
@@ -31,9 +33,93 @@ pub fn class_init<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `graphics`.
+///
+/// Every read of `graphics` on the same sprite yields the same object.
+pub fn graphics<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mc) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_movie_clip())
+    {
+        if let Some(graphics) = mc.avm2_graphics() {
+            return Ok(graphics.into());
+        }
+
+        let graphics_proto = activation.context.avm2.prototypes().graphics;
+        let graphics: Object<'gc> = StageObject::for_display_object(
+            activation.context.gc_context,
+            mc.into(),
+            graphics_proto,
+        )
+        .into();
+        mc.set_avm2_graphics(activation.context.gc_context, graphics);
+
+        return Ok(graphics.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `hitArea`'s getter.
+pub fn hit_area<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mc) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_movie_clip())
+    {
+        return Ok(match mc.hit_area().map(|hit_area| hit_area.object2()) {
+            Some(Value::Object(hit_area)) => hit_area.into(),
+            _ => Value::Null,
+        });
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `hitArea`'s setter.
+///
+/// The hit area is used to hit-test this sprite even when it is not on the
+/// display list.
+pub fn set_hit_area<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mc) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_movie_clip())
+    {
+        let hit_area = match args.get(0).cloned().unwrap_or(Value::Null) {
+            Value::Null | Value::Undefined => None,
+            Value::Object(hit_area) => Some(
+                hit_area
+                    .as_display_object()
+                    .and_then(|dobj| dobj.as_movie_clip())
+                    .ok_or("TypeError: Error #1034: Type Coercion failed: expected a Sprite")?,
+            ),
+            _ => {
+                return Err(
+                    "TypeError: Error #1034: Type Coercion failed: expected a Sprite".into(),
+                )
+            }
+        };
+
+        mc.set_hit_area(&mut activation.context, hit_area);
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Construct `Sprite`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
-    Class::new(
+    let class = Class::new(
         QName::new(Namespace::package("flash.display"), "Sprite"),
         Some(
             QName::new(
@@ -45,5 +131,63 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         Method::from_builtin(instance_init),
         Method::from_builtin(class_init),
         mc,
-    )
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "graphics"),
+        Method::from_builtin(graphics),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "hitArea"),
+        Method::from_builtin(hit_area),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "hitArea"),
+        Method::from_builtin(set_hit_area),
+    ));
+
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::test_utils::{get, with_avm2};
+    use crate::display_object::MovieClip;
+    use crate::tag_utils::{SwfMovie, SwfSlice};
+    use std::sync::Arc;
+
+    fn sprite<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Object<'gc> {
+        let movie = Arc::new(SwfMovie::empty(activation.context.swf.version()));
+        let clip = MovieClip::new(SwfSlice::empty(movie), activation.context.gc_context);
+
+        StageObject::for_display_object(
+            activation.context.gc_context,
+            clip.into(),
+            activation.avm2().prototypes().movieclip,
+        )
+        .into()
+    }
+
+    #[test]
+    fn graphics_is_the_same_object_every_time() {
+        with_avm2(|activation| {
+            let sprite_object = sprite(activation);
+            let first = get(activation, sprite_object, "graphics")
+                .coerce_to_object(activation)
+                .unwrap();
+            let second = get(activation, sprite_object, "graphics")
+                .coerce_to_object(activation)
+                .unwrap();
+            assert!(Object::ptr_eq(first, second));
+
+            let other = sprite(activation);
+            let other = get(activation, other, "graphics")
+                .coerce_to_object(activation)
+                .unwrap();
+            assert!(!Object::ptr_eq(first, other));
+        });
+    }
 }
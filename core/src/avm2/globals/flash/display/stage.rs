@@ -0,0 +1,1289 @@
+//! `flash.display.Stage` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::flash::display::stage3d;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{ArrayObject, Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::{Avm2, Error};
+use crate::backend::ui::{StageAspectRatio, StageOrientation};
+use crate::config::StageScaleMode;
+use crate::display_object::TDisplayObject;
+use gc_arena::{GcCell, MutationContext};
+use swf::Color;
+
+/// Implements `flash.display.Stage`'s instance constructor.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Err("ArgumentError: Error #2012: Stage class cannot be instantiated.".into())
+}
+
+/// Implements `flash.display.Stage`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `color`'s getter.
+pub fn color<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let color = activation
+        .context
+        .background_color
+        .as_ref()
+        .map(|color| color.to_rgb())
+        .unwrap_or(0xFFFFFF);
+
+    Ok(color.into())
+}
+
+/// Implements `color`'s setter.
+pub fn set_color<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let color = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_u32(activation)?;
+
+    *activation.context.background_color = Some(Color::from_rgb(color, 0xFF));
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `stageWidth`'s getter.
+///
+/// An unscaled stage is as wide as the viewport. Otherwise, the stage is
+/// scaled to fit the viewport, so this is the width the movie was authored
+/// at.
+pub fn stage_width<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if *activation.context.scale_mode == StageScaleMode::NoScale {
+        return Ok(activation.context.viewport_size.0.into());
+    }
+
+    Ok(activation.context.stage_size.0.to_pixels().into())
+}
+
+/// Implements `stageHeight`'s getter.
+pub fn stage_height<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if *activation.context.scale_mode == StageScaleMode::NoScale {
+        return Ok(activation.context.viewport_size.1.into());
+    }
+
+    Ok(activation.context.stage_size.1.to_pixels().into())
+}
+
+/// Implements `scaleMode`'s getter.
+pub fn scale_mode<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let scale_mode = match *activation.context.scale_mode {
+        StageScaleMode::ShowAll => "showAll",
+        StageScaleMode::ExactFit => "exactFit",
+        StageScaleMode::NoBorder => "noBorder",
+        StageScaleMode::NoScale => "noScale",
+    };
+
+    Ok(scale_mode.into())
+}
+
+/// Implements `scaleMode`'s setter.
+pub fn set_scale_mode<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let scale_mode = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+
+    *activation.context.scale_mode = match scale_mode.as_str() {
+        "showAll" => StageScaleMode::ShowAll,
+        "exactFit" => StageScaleMode::ExactFit,
+        "noBorder" => StageScaleMode::NoBorder,
+        "noScale" => StageScaleMode::NoScale,
+        _ => return Err(
+            "ArgumentError: Error #2008: Parameter scaleMode must be one of the accepted values."
+                .into(),
+        ),
+    };
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `contentsScaleFactor`'s getter.
+///
+/// This is the host's device pixel ratio, e.g. 2 on most high-DPI displays.
+pub fn contents_scale_factor<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(activation.context.contents_scale_factor.into())
+}
+
+/// Implements `focus`'s getter.
+pub fn focus<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(activation
+        .context
+        .focus_tracker
+        .get()
+        .map(|focus| focus.object2())
+        .unwrap_or(Value::Null))
+}
+
+/// Implements `focus`'s setter.
+///
+/// A script may focus objects that Tab never reaches, such as those inside a
+/// container with `tabChildren` false.
+pub fn set_focus<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let focus = match args.get(0).cloned().unwrap_or(Value::Null) {
+        Value::Undefined | Value::Null => None,
+        Value::Object(object) if object.as_display_object().is_some() => object.as_display_object(),
+        _ => {
+            return Err(
+                "TypeError: Error #1034: Type Coercion failed: expected an InteractiveObject"
+                    .into(),
+            )
+        }
+    };
+
+    let tracker = activation.context.focus_tracker;
+    tracker.set(focus, &mut activation.context);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `assignFocus`.
+///
+/// This moves focus the way `focus`'s setter does. The direction only
+/// matters to AIR text fields, which Ruffle doesn't have.
+pub fn assign_focus<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let direction = args
+        .get(1)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+    if !matches!(direction.as_str(), "top" | "bottom" | "none") {
+        return Err(
+            "ArgumentError: Error #2008: Parameter direction must be one of the accepted values."
+                .into(),
+        );
+    }
+
+    set_focus(activation, this, &args[..args.len().min(1)])
+}
+
+/// Implements `nativeWindow`'s getter.
+///
+/// Only AIR applications have a window, and Ruffle never runs content as one.
+pub fn native_window<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Null)
+}
+
+/// Implements `allowsFullScreen`'s getter.
+///
+/// This is also `allowsFullScreenInteractive`'s getter: keyboard input is
+/// never held back from full screen content.
+pub fn allows_full_screen<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(activation.context.ui.allows_fullscreen().into())
+}
+
+/// Implements `displayState`'s getter.
+pub fn display_state<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let display_state = if !activation.context.ui.is_fullscreen() {
+        "normal"
+    } else if activation.context.avm2.is_full_screen_interactive() {
+        "fullScreenInteractive"
+    } else {
+        "fullScreen"
+    };
+
+    Ok(display_state.into())
+}
+
+/// Implements `displayState`'s setter.
+///
+/// A request the host turns down leaves the stage as it was, without a
+/// `fullScreen` event.
+pub fn set_display_state<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let display_state = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+    let (is_full, interactive) = match display_state.as_str() {
+        "normal" => (false, false),
+        "fullScreen" => (true, false),
+        "fullScreenInteractive" => (true, true),
+        _ => {
+            return Err(
+                "ArgumentError: Error #2008: Parameter displayState must be one of the accepted values."
+                    .into(),
+            )
+        }
+    };
+
+    if is_full == activation.context.ui.is_fullscreen()
+        && interactive == activation.context.avm2.is_full_screen_interactive()
+    {
+        return Ok(Value::Undefined);
+    }
+
+    if is_full && !activation.context.ui.allows_fullscreen() {
+        return Err("SecurityError: Error #2152: Full screen mode is not allowed.".into());
+    }
+
+    if is_full != activation.context.ui.is_fullscreen()
+        && !activation.context.ui.set_fullscreen(is_full)
+    {
+        if is_full {
+            return Err("SecurityError: Error #2152: Full screen mode is not allowed.".into());
+        }
+
+        return Ok(Value::Undefined);
+    }
+
+    Avm2::dispatch_full_screen(&mut activation.context, is_full, interactive)?;
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `mouseLock`'s getter.
+pub fn mouse_lock<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(activation.context.ui.is_mouse_locked().into())
+}
+
+/// Implements `mouseLock`'s setter.
+///
+/// The host may turn the request down, in which case the mouse stays as it
+/// was.
+pub fn set_mouse_lock<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let locked = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_boolean();
+
+    if locked != activation.context.ui.is_mouse_locked() {
+        activation.context.ui.set_mouse_locked(locked);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `orientation`'s getter.
+pub fn orientation<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(activation.context.ui.orientation().as_str().into())
+}
+
+/// Implements `deviceOrientation`'s getter.
+pub fn device_orientation<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(activation.context.ui.device_orientation().as_str().into())
+}
+
+/// Implements `supportsOrientationChange`'s getter.
+pub fn supports_orientation_change<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(activation.context.ui.supports_orientation_change().into())
+}
+
+/// Dispatch `orientationChange` if the stage is no longer turned `before`.
+fn dispatch_if_turned<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    before: StageOrientation,
+) -> Result<(), Error> {
+    let after = activation.context.ui.orientation();
+    if after != before {
+        Avm2::dispatch_orientation_change(&mut activation.context, before, after)?;
+    }
+
+    Ok(())
+}
+
+/// Implements `setOrientation`.
+///
+/// Hosts with a fixed orientation ignore the request.
+pub fn set_orientation<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let name = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+    let orientation = match StageOrientation::from_name(&name) {
+        Some(StageOrientation::Unknown) | None => {
+            return Err(
+                "ArgumentError: Error #2008: Parameter newOrientation must be one of the accepted values."
+                    .into(),
+            )
+        }
+        Some(orientation) => orientation,
+    };
+
+    let before = activation.context.ui.orientation();
+    if orientation != before {
+        activation.context.ui.set_orientation(orientation);
+        dispatch_if_turned(activation, before)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `setAspectRatio`.
+///
+/// Locking the aspect ratio may turn the stage, in which case it dispatches
+/// `orientationChange` as any other turn would.
+pub fn set_aspect_ratio<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let aspect_ratio = match args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?
+        .as_str()
+    {
+        "portrait" => StageAspectRatio::Portrait,
+        "landscape" => StageAspectRatio::Landscape,
+        "any" => StageAspectRatio::Any,
+        _ => {
+            return Err(
+                "ArgumentError: Error #2008: Parameter newAspectRatio must be one of the accepted values."
+                    .into(),
+            )
+        }
+    };
+
+    let before = activation.context.ui.orientation();
+    activation.context.ui.set_aspect_ratio(aspect_ratio);
+    dispatch_if_turned(activation, before)?;
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `showDefaultContextMenu`'s getter.
+pub fn show_default_context_menu<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(activation.context.avm2.show_default_context_menu().into())
+}
+
+/// Implements `showDefaultContextMenu`'s setter.
+///
+/// Hiding the default menu only removes the player's own items. Custom items
+/// of a `ContextMenu` are still shown.
+pub fn set_show_default_context_menu<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let show = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_boolean();
+
+    activation.context.avm2.set_show_default_context_menu(show);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `stageFocusRect`'s getter.
+pub fn stage_focus_rect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(activation.context.avm2.stage_focus_rect().into())
+}
+
+/// Implements `stageFocusRect`'s setter.
+///
+/// This only affects objects that leave their own `focusRect` as `null`.
+pub fn set_stage_focus_rect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let value = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_boolean();
+
+    activation.context.avm2.set_stage_focus_rect(value);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `wmodeGPU`'s getter.
+///
+/// This is true whenever the render backend draws with the GPU, as if the
+/// movie were embedded with `wmode="gpu"`.
+pub fn wmode_gpu<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(activation.context.renderer.is_hardware_accelerated().into())
+}
+
+/// Implements `stage3Ds`'s getter.
+///
+/// Flash Player offers four `Stage3D`s on desktop, which are created the first
+/// time they are asked for. This is an `Array`, as there is no `Vector` yet.
+pub fn stage_3ds<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let name = QName::new(Namespace::Private("ruffle".into()), "stage3Ds");
+        let existing = this.get_property(this, &name, activation)?;
+        if let Value::Object(_) = existing {
+            return Ok(existing);
+        }
+
+        let mut stage_3ds = Vec::with_capacity(4);
+        for _ in 0..4 {
+            stage_3ds.push(stage3d::create_stage_3d(activation)?.into());
+        }
+
+        let stage_3ds = ArrayObject::from_array(
+            ArrayStorage::from_args(&stage_3ds),
+            activation.context.avm2.prototypes().array,
+            activation.context.gc_context,
+        );
+        this.set_property(this, &name, stage_3ds.into(), activation)?;
+
+        return Ok(stage_3ds.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Stage`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.display"), "Stage"),
+        Some(
+            QName::new(
+                Namespace::package("flash.display"),
+                "DisplayObjectContainer",
+            )
+            .into(),
+        ),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "color"),
+        Method::from_builtin(color),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "color"),
+        Method::from_builtin(set_color),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "stageWidth"),
+        Method::from_builtin(stage_width),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "stageHeight"),
+        Method::from_builtin(stage_height),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "scaleMode"),
+        Method::from_builtin(scale_mode),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "scaleMode"),
+        Method::from_builtin(set_scale_mode),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "contentsScaleFactor"),
+        Method::from_builtin(contents_scale_factor),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "focus"),
+        Method::from_builtin(focus),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "focus"),
+        Method::from_builtin(set_focus),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "nativeWindow"),
+        Method::from_builtin(native_window),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "allowsFullScreen"),
+        Method::from_builtin(allows_full_screen),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "allowsFullScreenInteractive"),
+        Method::from_builtin(allows_full_screen),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "displayState"),
+        Method::from_builtin(display_state),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "displayState"),
+        Method::from_builtin(set_display_state),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "mouseLock"),
+        Method::from_builtin(mouse_lock),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "mouseLock"),
+        Method::from_builtin(set_mouse_lock),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "showDefaultContextMenu"),
+        Method::from_builtin(show_default_context_menu),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "showDefaultContextMenu"),
+        Method::from_builtin(set_show_default_context_menu),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "orientation"),
+        Method::from_builtin(orientation),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "deviceOrientation"),
+        Method::from_builtin(device_orientation),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "setOrientation"),
+        Method::from_builtin(set_orientation),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "setAspectRatio"),
+        Method::from_builtin(set_aspect_ratio),
+    ));
+    write.define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "supportsOrientationChange"),
+        Method::from_builtin(supports_orientation_change),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "assignFocus"),
+        Method::from_builtin(assign_focus),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "stageFocusRect"),
+        Method::from_builtin(stage_focus_rect),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "stageFocusRect"),
+        Method::from_builtin(set_stage_focus_rect),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "stage3Ds"),
+        Method::from_builtin(stage_3ds),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "wmodeGPU"),
+        Method::from_builtin(wmode_gpu),
+    ));
+
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::globals::flash::display::interactiveobject;
+    use crate::avm2::globals::flash::ui::multitouch;
+    use crate::avm2::method::NativeMethod;
+    use crate::avm2::object::StageObject;
+    use crate::avm2::test_utils::{
+        call, function, get, park, parked, set, RenderRecorder, TestPlayer, TestUi,
+    };
+    use crate::backend::render::BitmapHandle;
+    use crate::display_object::{
+        Bitmap as BitmapObject, DisplayObject, MovieClip, TDisplayObjectContainer,
+    };
+    use crate::events::PlayerEvent;
+    use crate::tag_utils::{SwfMovie, SwfSlice};
+    use crate::vminterface::Instantiator;
+    use std::sync::Arc;
+
+    /// A player on a host that can go full screen if `allowed` is set.
+    fn full_screen_player(allowed: bool) -> TestPlayer {
+        TestPlayer::with_ui(Box::new(TestUi {
+            allows_fullscreen: allowed,
+            ..Default::default()
+        }))
+    }
+
+    /// The first argument of a listener, as the event it was given.
+    fn event_arg<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        args: &[Value<'gc>],
+    ) -> Object<'gc> {
+        args.get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)
+            .unwrap()
+    }
+
+    /// A `fullScreen` listener that traces the flag of each event it gets.
+    fn trace_full_screen<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let event = event_arg(activation, args);
+        let full_screen = get(activation, event, "fullScreen").coerce_to_boolean();
+
+        activation
+            .context
+            .log
+            .avm_trace(&format!("fullScreen {}", full_screen));
+
+        Ok(Value::Undefined)
+    }
+
+    /// A listener that traces the type of each event it gets.
+    fn trace_type<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let event = event_arg(activation, args);
+        let event_type = get(activation, event, "type").coerce_to_string(activation)?;
+
+        activation.context.log.avm_trace(&event_type);
+
+        Ok(Value::Undefined)
+    }
+
+    /// A `resize` listener that traces the size the stage reports.
+    fn trace_stage_size<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let stage = activation.context.avm2.stage().unwrap();
+        let width = get(activation, stage, "stageWidth").coerce_to_number(activation)?;
+        let height = get(activation, stage, "stageHeight").coerce_to_number(activation)?;
+
+        activation
+            .context
+            .log
+            .avm_trace(&format!("resize {}x{}", width, height));
+
+        Ok(Value::Undefined)
+    }
+
+    /// Add `listener` to the stage for events of `event_type`.
+    fn add_stage_listener<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        event_type: &'static str,
+        listener: NativeMethod<'gc>,
+    ) -> Object<'gc> {
+        let stage = activation.context.avm2.stage().unwrap();
+        let listener = function(activation, listener);
+        call(
+            activation,
+            stage,
+            "addEventListener",
+            &[event_type.into(), listener.into()],
+        );
+
+        stage
+    }
+
+    /// Listen for `fullScreen` events on the stage.
+    fn listen<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Object<'gc> {
+        add_stage_listener(activation, "fullScreen", trace_full_screen)
+    }
+
+    #[test]
+    fn display_state_changes_dispatch_full_screen_events() {
+        let player = full_screen_player(true);
+
+        player.update(|activation| {
+            let stage = listen(activation);
+
+            assert_eq!(
+                allows_full_screen(activation, Some(stage), &[]).unwrap(),
+                true.into()
+            );
+            set_display_state(activation, Some(stage), &["fullScreen".into()]).unwrap();
+            assert_eq!(
+                display_state(activation, Some(stage), &[]).unwrap(),
+                "fullScreen".into()
+            );
+        });
+
+        // The host leaves full screen by itself, e.g. when Escape is pressed.
+        let mut locked = player.lock();
+        locked.ui_mut().downcast_mut::<TestUi>().unwrap().fullscreen = false;
+        locked.set_fullscreen_state(false);
+        drop(locked);
+
+        assert_eq!(player.traces(), vec!["fullScreen true", "fullScreen false"]);
+    }
+
+    #[test]
+    fn denied_full_screen_request_stays_normal() {
+        let player = full_screen_player(false);
+
+        player.update(|activation| {
+            let stage = listen(activation);
+
+            assert_eq!(
+                allows_full_screen(activation, Some(stage), &[]).unwrap(),
+                false.into()
+            );
+            assert!(set_display_state(activation, Some(stage), &["fullScreen".into()]).is_err());
+            assert_eq!(
+                display_state(activation, Some(stage), &[]).unwrap(),
+                "normal".into()
+            );
+        });
+
+        assert!(player.traces().is_empty());
+    }
+
+    #[test]
+    fn focus_changes_dispatch_paired_activate_events() {
+        let player = full_screen_player(false);
+
+        player.update(|activation| {
+            add_stage_listener(activation, "activate", trace_type);
+            add_stage_listener(activation, "deactivate", trace_type);
+        });
+
+        let mut locked = player.lock();
+        locked.set_focused(true);
+        locked.set_focused(false);
+        locked.set_focused(false);
+        locked.set_focused(true);
+        locked.set_focused(true);
+        locked.set_focused(false);
+        locked.set_focused(true);
+        drop(locked);
+
+        assert_eq!(
+            player.traces(),
+            vec!["deactivate", "activate", "deactivate", "activate"]
+        );
+    }
+
+    #[test]
+    fn resizes_within_a_frame_dispatch_one_resize() {
+        let player = full_screen_player(false);
+
+        player.update(|activation| {
+            let stage = add_stage_listener(activation, "resize", trace_stage_size);
+            set(activation, stage, "scaleMode", "noScale".into());
+        });
+
+        let mut locked = player.lock();
+        locked.set_viewport_dimensions(600, 420);
+        locked.set_viewport_dimensions(700, 480);
+        locked.set_viewport_dimensions(800, 600);
+        locked.run_frame();
+        assert_eq!(player.traces(), vec!["resize 800x600"]);
+        assert_eq!(locked.viewport_dimensions(), (800, 600));
+
+        // Nothing changed since the last frame.
+        locked.run_frame();
+        assert_eq!(player.traces().len(), 1);
+
+        // A resize that is undone within the frame isn't a resize at all.
+        locked.set_viewport_dimensions(1024, 768);
+        locked.set_viewport_dimensions(800, 600);
+        locked.run_frame();
+        assert_eq!(player.traces().len(), 1);
+    }
+
+    #[test]
+    fn scale_factor_changes_dispatch_resize() {
+        let player = full_screen_player(false);
+
+        player.update(|activation| {
+            add_stage_listener(activation, "resize", trace_type);
+        });
+
+        player.lock().set_contents_scale_factor(2.0);
+        player.lock().run_frame();
+        assert_eq!(player.traces(), vec!["resize"]);
+
+        player.lock().run_frame();
+        assert_eq!(player.traces().len(), 1);
+
+        player.update(|activation| {
+            let stage = activation.context.avm2.stage().unwrap();
+            assert_eq!(get(activation, stage, "contentsScaleFactor"), 2.0.into());
+        });
+    }
+
+    /// A `mouseMove` listener that traces the movement of each event it gets.
+    fn trace_movement<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let event = event_arg(activation, args);
+        let movement_x = get(activation, event, "movementX").coerce_to_number(activation)?;
+        let movement_y = get(activation, event, "movementY").coerce_to_number(activation)?;
+
+        activation
+            .context
+            .log
+            .avm_trace(&format!("moved {} {}", movement_x, movement_y));
+
+        Ok(Value::Undefined)
+    }
+
+    /// An `orientationChange` listener that traces the orientations each
+    /// event turns between.
+    fn trace_orientations<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let event = event_arg(activation, args);
+        let before = get(activation, event, "beforeOrientation").coerce_to_string(activation)?;
+        let after = get(activation, event, "afterOrientation").coerce_to_string(activation)?;
+
+        activation
+            .context
+            .log
+            .avm_trace(&format!("{} -> {}", before, after));
+
+        Ok(Value::Undefined)
+    }
+
+    #[test]
+    fn rotations_dispatch_orientation_change() {
+        let player = TestPlayer::with_ui(Box::new(TestUi {
+            orientation: Some(StageOrientation::Default),
+            ..Default::default()
+        }));
+
+        player.update(|activation| {
+            add_stage_listener(activation, "orientationChange", trace_orientations);
+        });
+
+        // The user turns the device.
+        let mut locked = player.lock();
+        locked
+            .ui_mut()
+            .downcast_mut::<TestUi>()
+            .unwrap()
+            .orientation = Some(StageOrientation::RotatedLeft);
+        locked.set_orientation_state(StageOrientation::Default, StageOrientation::RotatedLeft);
+        drop(locked);
+
+        player.update(|activation| {
+            let stage = activation.context.avm2.stage();
+            assert_eq!(
+                orientation(activation, stage, &[]).unwrap(),
+                "rotatedLeft".into()
+            );
+
+            set_orientation(activation, stage, &["upsideDown".into()]).unwrap();
+            assert_eq!(
+                orientation(activation, stage, &[]).unwrap(),
+                "upsideDown".into()
+            );
+        });
+
+        assert_eq!(
+            player.traces(),
+            vec!["default -> rotatedLeft", "rotatedLeft -> upsideDown"]
+        );
+    }
+
+    #[test]
+    fn fixed_orientation_ignores_requests() {
+        let player = full_screen_player(false);
+
+        player.update(|activation| {
+            let stage = add_stage_listener(activation, "orientationChange", trace_orientations);
+
+            set_orientation(activation, Some(stage), &["rotatedRight".into()]).unwrap();
+            set_aspect_ratio(activation, Some(stage), &["landscape".into()]).unwrap();
+            assert_eq!(
+                orientation(activation, Some(stage), &[]).unwrap(),
+                "unknown".into()
+            );
+            assert!(set_orientation(activation, Some(stage), &["sideways".into()]).is_err());
+        });
+
+        assert!(player.traces().is_empty());
+    }
+
+    #[test]
+    fn locked_mouse_reports_relative_movement() {
+        let player = full_screen_player(false);
+
+        player.update(|activation| {
+            add_stage_listener(activation, "mouseMove", trace_movement);
+        });
+
+        player
+            .lock()
+            .handle_event(PlayerEvent::MouseMove { x: 10.0, y: 10.0 });
+        player
+            .lock()
+            .handle_event(PlayerEvent::MouseMove { x: 15.0, y: 12.0 });
+
+        player.update(|activation| {
+            let stage = activation.context.avm2.stage();
+            set_mouse_lock(activation, stage, &[true.into()]).unwrap();
+            assert_eq!(mouse_lock(activation, stage, &[]).unwrap(), true.into());
+        });
+
+        player
+            .lock()
+            .handle_event(PlayerEvent::MouseMove { x: 12.0, y: 20.0 });
+
+        assert_eq!(
+            player.traces(),
+            vec!["moved 0 0", "moved 0 0", "moved -3 8"]
+        );
+    }
+
+    /// Any ActionScript 3 movie will do, so that clips get AVM2 objects.
+    const AS3_MOVIE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/swfs/avm2/add/test.swf");
+
+    /// A pointer event listener that traces where each event it gets is, and
+    /// which objects it reached.
+    fn trace_pointer<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        fn name_of<'gc>(activation: &mut Activation<'_, 'gc, '_>, object: Value<'gc>) -> String {
+            let object = object.coerce_to_object(activation).unwrap();
+            match object.as_display_object() {
+                Some(dobj) => dobj.name().to_string(),
+                None => "stage".to_string(),
+            }
+        }
+
+        let event = event_arg(activation, args);
+        let event_type = get(activation, event, "type").coerce_to_string(activation)?;
+        let current_target = get(activation, event, "currentTarget");
+        let current_target = name_of(activation, current_target);
+        let target = get(activation, event, "target");
+        let target = name_of(activation, target);
+        let local_x = get(activation, event, "localX").coerce_to_number(activation)?;
+        let local_y = get(activation, event, "localY").coerce_to_number(activation)?;
+
+        activation.context.log.avm_trace(&format!(
+            "{} on {} from {} at {} {}",
+            event_type, current_target, target, local_x, local_y
+        ));
+
+        Ok(Value::Undefined)
+    }
+
+    #[test]
+    fn pointer_events_target_the_object_under_them() {
+        let player = TestPlayer::new();
+
+        player.update(|activation| {
+            let movie = Arc::new(SwfMovie::from_path(AS3_MOVIE).unwrap());
+            let uc = &mut activation.context;
+            let clip: DisplayObject<'_> =
+                MovieClip::new(SwfSlice::empty(movie), uc.gc_context).into();
+            clip.post_instantiation(uc, clip, None, Instantiator::Avm2, false);
+            let bitmap = BitmapObject::new(uc, 0, BitmapHandle(0), 20, 10);
+            clip.as_container()
+                .unwrap()
+                .insert_at_index(uc, bitmap.into(), 0);
+            clip.set_name(uc.gc_context, "clip");
+            let mut root = uc.levels.get(&0).unwrap().as_container().unwrap();
+            root.insert_at_index(uc, clip, 0);
+
+            let this = clip.object2().coerce_to_object(activation).unwrap();
+            set(activation, this, "x", 100.into());
+
+            let listener = function(activation, trace_pointer);
+            for event_type in &["mouseMove", "touchBegin"] {
+                add_stage_listener(activation, event_type, trace_pointer);
+                call(
+                    activation,
+                    this,
+                    "addEventListener",
+                    &[(*event_type).into(), listener.into()],
+                );
+            }
+            multitouch::set_input_mode(activation, None, &["touchPoint".into()]).unwrap();
+
+            // Over the bitmap, which can't be a target itself.
+            Avm2::dispatch_mouse_move(&mut activation.context, (105.0, 5.0), (0.0, 0.0)).unwrap();
+            Avm2::dispatch_touch(&mut activation.context, "touchBegin", 1, (110.0, 2.0), 1.0)
+                .unwrap();
+
+            // Over nothing at all.
+            Avm2::dispatch_mouse_move(&mut activation.context, (50.0, 5.0), (0.0, 0.0)).unwrap();
+        });
+
+        assert_eq!(
+            player.traces(),
+            vec![
+                "mouseMove on clip from clip at 5 5",
+                "mouseMove on stage from clip at 5 5",
+                "touchBegin on clip from clip at 10 2",
+                "touchBegin on stage from clip at 10 2",
+                "mouseMove on stage from stage at 50 5",
+            ]
+        );
+    }
+
+    /// Render a frame, and count the rectangles of `color` drawn in it.
+    fn rects_of_color(player: &TestPlayer, color: u32) -> usize {
+        let mut locked = player.lock();
+        locked.render();
+
+        let renderer = locked
+            .renderer_mut()
+            .downcast_mut::<RenderRecorder>()
+            .unwrap();
+        let color = Color::from_rgb(color, 255);
+        let rects = renderer.rects.iter().filter(|rect| **rect == color).count();
+        renderer.rects.clear();
+
+        rects
+    }
+
+    /// Render a frame, and count the focus rectangle edges drawn in it.
+    fn focus_rect_edges(player: &TestPlayer) -> usize {
+        rects_of_color(player, 0xFFFF00)
+    }
+
+    /// Set `focusRect` on the focused object, with `None` as `null`, and set
+    /// `stageFocusRect`.
+    fn set_focus_rects(player: &TestPlayer, focus_rect: Option<bool>, stage_focus_rect: bool) {
+        player.update(|activation| {
+            let focused = activation.context.focus_tracker.get().unwrap();
+            let this: Object<'_> = StageObject::for_display_object(
+                activation.context.gc_context,
+                focused,
+                activation.context.avm2.prototypes().movieclip,
+            )
+            .into();
+            let focus_rect = focus_rect.map(Value::from).unwrap_or(Value::Null);
+            interactiveobject::set_focus_rect(activation, Some(this), &[focus_rect]).unwrap();
+
+            let stage = activation.context.avm2.stage();
+            set_stage_focus_rect(activation, stage, &[stage_focus_rect.into()]).unwrap();
+        });
+    }
+
+    #[test]
+    fn focus_rect_draws_around_the_focused_object() {
+        let player = TestPlayer::with_renderer(Box::new(RenderRecorder::default()));
+
+        player.update(|activation| {
+            let uc = &mut activation.context;
+            let mut clip = MovieClip::new(SwfSlice::empty(uc.swf.clone()), uc.gc_context);
+            let bitmap = BitmapObject::new(uc, 0, BitmapHandle(0), 20, 10);
+            clip.insert_at_index(uc, bitmap.into(), 0);
+
+            let tracker = uc.focus_tracker;
+            tracker.set(Some(clip.into()), uc);
+        });
+        assert_eq!(focus_rect_edges(&player), 4);
+
+        set_focus_rects(&player, Some(true), true);
+        assert_eq!(focus_rect_edges(&player), 4);
+
+        set_focus_rects(&player, Some(false), true);
+        assert_eq!(focus_rect_edges(&player), 0);
+
+        // Objects with a `null` focus rect follow the stage.
+        set_focus_rects(&player, None, false);
+        assert_eq!(focus_rect_edges(&player), 0);
+
+        set_focus_rects(&player, Some(true), false);
+        assert_eq!(focus_rect_edges(&player), 4);
+    }
+
+    #[test]
+    fn stage_color_and_opaque_background_are_drawn() {
+        let player = TestPlayer::with_renderer(Box::new(RenderRecorder::default()));
+
+        player.update(|activation| {
+            let stage = activation.context.avm2.stage();
+            assert_eq!(color(activation, stage, &[]).unwrap(), 0xFFFFFF.into());
+            set_color(activation, stage, &[0xFF33_6699u32.into()]).unwrap();
+            assert_eq!(color(activation, stage, &[]).unwrap(), 0x33_6699.into());
+
+            let uc = &mut activation.context;
+            let mut clip = MovieClip::new(SwfSlice::empty(uc.swf.clone()), uc.gc_context);
+            let bitmap = BitmapObject::new(uc, 0, BitmapHandle(0), 20, 10);
+            clip.insert_at_index(uc, bitmap.into(), 0);
+            let mut root = uc.levels.get(&0).unwrap().as_container().unwrap();
+            root.insert_at_index(uc, clip.into(), 0);
+
+            let this: Object<'_> = StageObject::for_display_object(
+                activation.context.gc_context,
+                clip.into(),
+                activation.context.avm2.prototypes().movieclip,
+            )
+            .into();
+            park(activation, "clip", this.into());
+            assert_eq!(get(activation, this, "opaqueBackground"), Value::Null);
+            set(activation, this, "opaqueBackground", 0x100_FF00.into());
+            assert_eq!(get(activation, this, "opaqueBackground"), 0xFF00.into());
+        });
+        assert_eq!(
+            player.lock().background_color(),
+            Some(Color::from_rgb(0x33_6699, 255))
+        );
+        assert_eq!(rects_of_color(&player, 0xFF00), 1);
+
+        player.update(|activation| {
+            let this = parked(activation, "clip")
+                .coerce_to_object(activation)
+                .unwrap();
+            set(activation, this, "opaqueBackground", Value::Null);
+        });
+        assert_eq!(rects_of_color(&player, 0xFF00), 0);
+    }
+
+    #[test]
+    fn only_unscaled_stages_report_the_viewport_size() {
+        let player = full_screen_player(false);
+
+        player.update(|activation| {
+            let stage = add_stage_listener(activation, "resize", trace_stage_size);
+            assert_eq!(get(activation, stage, "scaleMode"), "showAll".into());
+        });
+
+        let mut locked = player.lock();
+        locked.set_viewport_dimensions(1100, 800);
+        locked.run_frame();
+        let authored = (locked.movie_width(), locked.movie_height());
+        drop(locked);
+        assert_eq!(
+            player.traces(),
+            vec![format!("resize {}x{}", authored.0, authored.1)]
+        );
+
+        player.update(|activation| {
+            let stage = activation.context.avm2.stage().unwrap();
+            set(activation, stage, "scaleMode", "noScale".into());
+            assert_eq!(get(activation, stage, "stageWidth"), 1100.into());
+            assert_eq!(get(activation, stage, "stageHeight"), 800.into());
+            set(activation, stage, "scaleMode", "exactFit".into());
+            assert_eq!(get(activation, stage, "stageWidth"), authored.0.into());
+
+            let mut stage_object = stage;
+            let error = stage_object
+                .set_property(
+                    stage,
+                    &QName::new(Namespace::public(), "scaleMode"),
+                    "stretch".into(),
+                    activation,
+                )
+                .unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "ArgumentError: Error #2008: Parameter scaleMode must be one of the accepted values."
+            );
+            assert_eq!(get(activation, stage, "scaleMode"), "exactFit".into());
+        });
+    }
+}
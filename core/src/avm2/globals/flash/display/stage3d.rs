@@ -0,0 +1,370 @@
+//! `flash.display.Stage3D` builtin/prototype
+//!
+//! Ruffle cannot render Stage3D content, so every request for a `Context3D`
+//! fails with an `error` event. Content with a software fallback can then
+//! switch to it.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::events::{Event, EventData};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::{Avm2, Error};
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.display.Stage3D`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        for (name, value) in &[
+            ("x", Value::Number(0.0)),
+            ("y", Value::Number(0.0)),
+            ("visible", true.into()),
+        ] {
+            this.set_property(
+                this,
+                &QName::new(Namespace::ruffle_private(), *name),
+                value.clone(),
+                activation,
+            )?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.display.Stage3D`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct the `Stage3D`s listed in `Stage.stage3Ds`.
+pub fn create_stage_3d<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Object<'gc>, Error> {
+    let mut proto = activation.context.avm2.prototypes().stage_3d;
+    let stage_3d = proto.construct(activation, &[])?;
+    let constr = proto
+        .get_property(
+            proto,
+            &QName::new(Namespace::public(), "constructor"),
+            activation,
+        )?
+        .coerce_to_object(activation)?;
+
+    constr.call(Some(stage_3d), &[], activation, Some(proto))?;
+
+    Ok(stage_3d)
+}
+
+/// Implements `context3D`'s getter.
+///
+/// No context is ever created, so this is always `null`.
+pub fn context_3d<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Null)
+}
+
+/// Implements `requestContext3D` and `requestContext3DMatchingProfiles`.
+///
+/// The request fails straight away, with the error Flash Player reports when
+/// hardware acceleration is unavailable.
+pub fn request_context_3d<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let mut error = Event::new("error");
+        error.set_event_data(EventData::Error {
+            text: "Error #3702: Context3D not available.".into(),
+            error_id: 3702,
+        });
+
+        Avm2::dispatch_event(&mut activation.context, error, this)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Read one of the properties kept on a `Stage3D`.
+fn stored<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    name: &'static str,
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::ruffle_private(), name),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Change one of the properties kept on a `Stage3D`.
+fn store<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    name: &'static str,
+    value: Value<'gc>,
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        this.set_property(
+            this,
+            &QName::new(Namespace::ruffle_private(), name),
+            value,
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `x`'s getter.
+pub fn x<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    stored(activation, this, "x")
+}
+
+/// Implements `x`'s setter.
+pub fn set_x<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let x = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_number(activation)?;
+
+    store(activation, this, "x", x.into())
+}
+
+/// Implements `y`'s getter.
+pub fn y<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    stored(activation, this, "y")
+}
+
+/// Implements `y`'s setter.
+pub fn set_y<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let y = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_number(activation)?;
+
+    store(activation, this, "y", y.into())
+}
+
+/// Implements `visible`'s getter.
+pub fn visible<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    stored(activation, this, "visible")
+}
+
+/// Implements `visible`'s setter.
+pub fn set_visible<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let visible = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_boolean();
+
+    store(activation, this, "visible", visible.into())
+}
+
+/// Construct `Stage3D`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.display"), "Stage3D"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "context3D"),
+        Method::from_builtin(context_3d),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "requestContext3D"),
+        Method::from_builtin(request_context_3d),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "requestContext3DMatchingProfiles"),
+        Method::from_builtin(request_context_3d),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "x"),
+        Method::from_builtin(x),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "x"),
+        Method::from_builtin(set_x),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "y"),
+        Method::from_builtin(y),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "y"),
+        Method::from_builtin(set_y),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "visible"),
+        Method::from_builtin(visible),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "visible"),
+        Method::from_builtin(set_visible),
+    ));
+
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::globals::flash::display::stage;
+    use crate::avm2::object::FunctionObject;
+    use crate::avm2::test_utils::trace_with_avm2;
+
+    /// An `error` listener that traces the event's error and whether its
+    /// `Stage3D` has a context.
+    fn trace_error<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let mut event = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let mut line = vec![];
+        for name in &["type", "errorID", "text"] {
+            line.push(
+                event
+                    .get_property(event, &QName::new(Namespace::public(), *name), activation)?
+                    .coerce_to_string(activation)?
+                    .to_string(),
+            );
+        }
+
+        let mut target = event
+            .get_property(
+                event,
+                &QName::new(Namespace::public(), "target"),
+                activation,
+            )?
+            .coerce_to_object(activation)?;
+        let context_3d = target.get_property(
+            target,
+            &QName::new(Namespace::public(), "context3D"),
+            activation,
+        )?;
+        line.push(format!("{:?}", context_3d));
+
+        activation.context.log.avm_trace(&line.join(" / "));
+
+        Ok(Value::Undefined)
+    }
+
+    #[test]
+    fn requesting_a_context_dispatches_an_error() {
+        let log = trace_with_avm2(|activation| {
+            let stage = activation.context.avm2.stage();
+            let stage_3ds = stage::stage_3ds(activation, stage, &[])
+                .unwrap()
+                .coerce_to_object(activation)
+                .unwrap();
+            let mut stage_3d = stage_3ds
+                .as_array_storage()
+                .unwrap()
+                .get(0)
+                .unwrap()
+                .coerce_to_object(activation)
+                .unwrap();
+
+            // The same `Stage3D`s are listed every time.
+            let again = stage::stage_3ds(activation, stage, &[]).unwrap();
+            assert_eq!(again, stage_3ds.into());
+            assert_eq!(stage_3ds.as_array_storage().unwrap().length(), 4);
+
+            let listener = FunctionObject::from_builtin(
+                activation.context.gc_context,
+                trace_error,
+                activation.avm2().prototypes().function,
+            );
+            stage_3d
+                .get_property(
+                    stage_3d,
+                    &QName::new(Namespace::public(), "addEventListener"),
+                    activation,
+                )
+                .unwrap()
+                .coerce_to_object(activation)
+                .unwrap()
+                .call(
+                    Some(stage_3d),
+                    &["error".into(), listener.into()],
+                    activation,
+                    None,
+                )
+                .unwrap();
+
+            request_context_3d(activation, Some(stage_3d), &["auto".into()]).unwrap();
+            assert_eq!(
+                context_3d(activation, Some(stage_3d), &[]).unwrap(),
+                Value::Null
+            );
+        });
+
+        assert_eq!(
+            log,
+            vec!["error / 3702 / Error #3702: Context3D not available. / Null"]
+        );
+    }
+}
@@ -0,0 +1,3 @@
+//! `flash.display3D` namespace
+
+pub mod context3d;
@@ -1,5 +1,18 @@
 //! `flash.events` namespace
 
+pub mod contextmenuevent;
+pub mod dataevent;
+pub mod errorevent;
 pub mod event;
 pub mod eventdispatcher;
+pub mod fullscreenevent;
+pub mod gestureevent;
+pub mod httpstatusevent;
 pub mod ieventdispatcher;
+pub mod mouseevent;
+pub mod netstatusevent;
+pub mod progressevent;
+pub mod stageorientationevent;
+pub mod syncevent;
+pub mod textevent;
+pub mod touchevent;
@@ -0,0 +1,194 @@
+//! `flash.events.ContextMenuEvent` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::events::EventData;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.events.ContextMenuEvent`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, args.get(0..3).unwrap_or(args))?;
+
+        let mouse_target = match args.get(3) {
+            Some(Value::Object(mouse_target)) => Some(*mouse_target),
+            _ => None,
+        };
+        let context_menu_owner = match args.get(4) {
+            Some(Value::Object(context_menu_owner)) => Some(*context_menu_owner),
+            _ => None,
+        };
+
+        if let Some(mut evt) = this.as_event_mut(activation.context.gc_context) {
+            evt.set_event_data(EventData::ContextMenu {
+                mouse_target,
+                context_menu_owner,
+            });
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.events.ContextMenuEvent`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `mouseTarget`'s getter.
+pub fn mouse_target<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.unwrap().as_event() {
+        if let EventData::ContextMenu {
+            mouse_target: Some(mouse_target),
+            ..
+        } = evt.event_data()
+        {
+            return Ok((*mouse_target).into());
+        }
+
+        return Ok(Value::Null);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `mouseTarget`'s setter.
+pub fn set_mouse_target<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let value = match args.get(0) {
+        Some(Value::Object(value)) => Some(*value),
+        _ => None,
+    };
+
+    if let Some(mut evt) = this.unwrap().as_event_mut(activation.context.gc_context) {
+        if let EventData::ContextMenu { mouse_target, .. } = evt.event_data_mut() {
+            *mouse_target = value;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `contextMenuOwner`'s getter.
+pub fn context_menu_owner<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.unwrap().as_event() {
+        if let EventData::ContextMenu {
+            context_menu_owner: Some(context_menu_owner),
+            ..
+        } = evt.event_data()
+        {
+            return Ok((*context_menu_owner).into());
+        }
+
+        return Ok(Value::Null);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `contextMenuOwner`'s setter.
+pub fn set_context_menu_owner<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let value = match args.get(0) {
+        Some(Value::Object(value)) => Some(*value),
+        _ => None,
+    };
+
+    if let Some(mut evt) = this.unwrap().as_event_mut(activation.context.gc_context) {
+        if let EventData::ContextMenu {
+            context_menu_owner, ..
+        } = evt.event_data_mut()
+        {
+            *context_menu_owner = value;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `isMouseTargetInaccessible`'s getter.
+///
+/// Every object the player can pick is in the same security sandbox as the
+/// content, so the target is always accessible.
+pub fn is_mouse_target_inaccessible<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(false.into())
+}
+
+/// Construct `ContextMenuEvent`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.events"), "ContextMenuEvent"),
+        Some(QName::new(Namespace::package("flash.events"), "Event").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "mouseTarget"),
+        Method::from_builtin(mouse_target),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "mouseTarget"),
+        Method::from_builtin(set_mouse_target),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "contextMenuOwner"),
+        Method::from_builtin(context_menu_owner),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "contextMenuOwner"),
+        Method::from_builtin(set_context_menu_owner),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "isMouseTargetInaccessible"),
+        Method::from_builtin(is_mouse_target_inaccessible),
+    ));
+
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public(), "MENU_ITEM_SELECT"),
+        QName::new(Namespace::public(), "String").into(),
+        Some("menuItemSelect".into()),
+    ));
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public(), "MENU_SELECT"),
+        QName::new(Namespace::public(), "String").into(),
+        Some("menuSelect".into()),
+    ));
+
+    class
+}
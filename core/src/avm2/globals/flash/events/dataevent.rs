@@ -0,0 +1,130 @@
+//! `flash.events.DataEvent` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::events::EventData;
+use crate::avm2::globals::flash::events::textevent;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.events.DataEvent`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, args)?;
+
+        if let Some(mut evt) = this.as_event_mut(activation.context.gc_context) {
+            if let EventData::Text { text } = evt.event_data() {
+                let data = *text;
+                evt.set_event_data(EventData::Data { data });
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.events.DataEvent`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct `DataEvent`'s class.
+///
+/// The `data` property shares its storage with `TextEvent.text`, which keeps
+/// the event's data when set.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.events"), "DataEvent"),
+        Some(QName::new(Namespace::package("flash.events"), "TextEvent").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "data"),
+        Method::from_builtin(textevent::text),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "data"),
+        Method::from_builtin(textevent::set_text),
+    ));
+
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public(), "DATA"),
+        QName::new(Namespace::public(), "String").into(),
+        Some("data".into()),
+    ));
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public(), "UPLOAD_COMPLETE_DATA"),
+        QName::new(Namespace::public(), "String").into(),
+        Some("uploadCompleteData".into()),
+    ));
+
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::avm2::activation::Activation;
+    use crate::avm2::object::TObject;
+    use crate::avm2::test_utils::{call, class, construct, get, set, with_avm2};
+    use crate::avm2::value::Value;
+
+    fn is_data_event<'gc>(activation: &mut Activation<'_, 'gc, '_>, event: Value<'gc>) -> bool {
+        let event = event.coerce_to_object(activation).unwrap();
+        let data_event = class(activation, "flash.events", "DataEvent");
+        event.is_instance_of(activation, data_event, false).unwrap()
+    }
+
+    #[test]
+    fn clone_keeps_the_class_and_the_data() {
+        with_avm2(|activation| {
+            let event = construct(
+                activation,
+                "flash.events",
+                "DataEvent",
+                &["data".into(), false.into(), false.into(), "<xml/>".into()],
+            );
+            set(activation, event, "text", "<changed/>".into());
+
+            let clone = call(activation, event, "clone", &[]);
+            assert!(is_data_event(activation, clone.clone()));
+            let clone = clone.coerce_to_object(activation).unwrap();
+            assert_eq!(get(activation, clone, "data"), "<changed/>".into());
+            assert_eq!(get(activation, clone, "text"), "<changed/>".into());
+        });
+    }
+
+    #[test]
+    fn text_events_do_not_clone_into_data_events() {
+        with_avm2(|activation| {
+            let event = construct(
+                activation,
+                "flash.events",
+                "TextEvent",
+                &["link".into(), false.into(), false.into(), "hello".into()],
+            );
+
+            let clone = call(activation, event, "clone", &[]);
+            assert!(!is_data_event(activation, clone.clone()));
+            let clone = clone.coerce_to_object(activation).unwrap();
+            assert_eq!(get(activation, clone, "text"), "hello".into());
+        });
+    }
+}
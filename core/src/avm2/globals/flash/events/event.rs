@@ -2,6 +2,7 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::events::EventData;
 use crate::avm2::method::Method;
 use crate::avm2::names::{Namespace, QName};
 use crate::avm2::object::{EventObject, Object, TObject};
@@ -144,7 +145,24 @@ pub fn clone<'gc>(
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
     if let Some(evt) = this.unwrap().as_event() {
-        let evt_proto = activation.avm2().system_prototypes.as_ref().unwrap().event;
+        let evt_proto = match evt.event_data() {
+            EventData::Empty => activation.avm2().prototypes().event,
+            EventData::Text { .. } => activation.avm2().prototypes().text_event,
+            EventData::Data { .. } => activation.avm2().prototypes().data_event,
+            EventData::Error { .. } => activation.avm2().prototypes().error_event,
+            EventData::NetStatus { .. } => activation.avm2().prototypes().net_status_event,
+            EventData::Sync { .. } => activation.avm2().prototypes().sync_event,
+            EventData::Progress { .. } => activation.avm2().prototypes().progress_event,
+            EventData::HttpStatus { .. } => activation.avm2().prototypes().http_status_event,
+            EventData::ContextMenu { .. } => activation.avm2().prototypes().context_menu_event,
+            EventData::Mouse { .. } => activation.avm2().prototypes().mouse_event,
+            EventData::FullScreen { .. } => activation.avm2().prototypes().full_screen_event,
+            EventData::Touch { .. } => activation.avm2().prototypes().touch_event,
+            EventData::Gesture { .. } => activation.avm2().prototypes().gesture_event,
+            EventData::StageOrientation { .. } => {
+                activation.avm2().prototypes().stage_orientation_event
+            }
+        };
 
         return Ok(EventObject::from_event(
             activation.context.gc_context,
@@ -0,0 +1,117 @@
+//! `flash.events.FullScreenEvent` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::events::EventData;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.events.FullScreenEvent`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, args.get(0..3).unwrap_or(args))?;
+
+        let full_screen = args
+            .get(3)
+            .cloned()
+            .unwrap_or_else(|| false.into())
+            .coerce_to_boolean();
+        let interactive = args
+            .get(4)
+            .cloned()
+            .unwrap_or_else(|| false.into())
+            .coerce_to_boolean();
+
+        if let Some(mut evt) = this.as_event_mut(activation.context.gc_context) {
+            evt.set_event_data(EventData::FullScreen {
+                full_screen,
+                interactive,
+            });
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.events.FullScreenEvent`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `fullScreen`'s getter.
+pub fn full_screen<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.unwrap().as_event() {
+        if let EventData::FullScreen { full_screen, .. } = evt.event_data() {
+            return Ok((*full_screen).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `interactive`'s getter.
+pub fn interactive<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.unwrap().as_event() {
+        if let EventData::FullScreen { interactive, .. } = evt.event_data() {
+            return Ok((*interactive).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `FullScreenEvent`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.events"), "FullScreenEvent"),
+        Some(QName::new(Namespace::package("flash.events"), "Event").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "fullScreen"),
+        Method::from_builtin(full_screen),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "interactive"),
+        Method::from_builtin(interactive),
+    ));
+
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public(), "FULL_SCREEN"),
+        QName::new(Namespace::public(), "String").into(),
+        Some("fullScreen".into()),
+    ));
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public(), "FULL_SCREEN_INTERACTIVE_ACCEPTED"),
+        QName::new(Namespace::public(), "String").into(),
+        Some("fullScreenInteractiveAccepted".into()),
+    ));
+
+    class
+}
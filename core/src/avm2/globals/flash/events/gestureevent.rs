@@ -0,0 +1,143 @@
+//! `flash.events.GestureEvent` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::events::EventData;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.events.GestureEvent`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        // Unlike most events, gesture events bubble by default.
+        let event_type = args.get(0).cloned().unwrap_or(Value::Undefined);
+        let bubbles = args.get(1).cloned().unwrap_or(Value::Bool(true));
+        let cancelable = args.get(2).cloned().unwrap_or(Value::Bool(false));
+        activation.super_init(this, &[event_type, bubbles, cancelable])?;
+
+        let phase = match args.get(3).cloned().unwrap_or(Value::Null) {
+            Value::Undefined | Value::Null => None,
+            phase => Some(phase.coerce_to_string(activation)?),
+        };
+        let local_x = args
+            .get(4)
+            .cloned()
+            .unwrap_or(Value::Integer(0))
+            .coerce_to_number(activation)?;
+        let local_y = args
+            .get(5)
+            .cloned()
+            .unwrap_or(Value::Integer(0))
+            .coerce_to_number(activation)?;
+
+        if let Some(mut evt) = this.as_event_mut(activation.context.gc_context) {
+            evt.set_event_data(EventData::Gesture {
+                phase,
+                local_x,
+                local_y,
+            });
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.events.GestureEvent`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `phase`'s getter.
+pub fn phase<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.unwrap().as_event() {
+        if let EventData::Gesture { phase, .. } = evt.event_data() {
+            return Ok(phase.map(Value::from).unwrap_or(Value::Null));
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `localX`'s getter.
+pub fn local_x<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.unwrap().as_event() {
+        if let EventData::Gesture { local_x, .. } = evt.event_data() {
+            return Ok((*local_x).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `localY`'s getter.
+pub fn local_y<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.unwrap().as_event() {
+        if let EventData::Gesture { local_y, .. } = evt.event_data() {
+            return Ok((*local_y).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `GestureEvent`'s class.
+///
+/// Touches are never recognized as gestures, so these are only ever
+/// dispatched by script.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.events"), "GestureEvent"),
+        Some(QName::new(Namespace::package("flash.events"), "Event").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "phase"),
+        Method::from_builtin(phase),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "localX"),
+        Method::from_builtin(local_x),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "localY"),
+        Method::from_builtin(local_y),
+    ));
+
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public(), "GESTURE_TWO_FINGER_TAP"),
+        QName::new(Namespace::public(), "String").into(),
+        Some("gestureTwoFingerTap".into()),
+    ));
+
+    class
+}
@@ -0,0 +1,200 @@
+//! `flash.events.HTTPStatusEvent` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::events::EventData;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.events.HTTPStatusEvent`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, args.get(0..3).unwrap_or(args))?;
+
+        let status = args
+            .get(3)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_i32(activation)?;
+
+        if let Some(mut evt) = this.as_event_mut(activation.context.gc_context) {
+            evt.set_event_data(EventData::HttpStatus {
+                status,
+                response_url: None,
+                response_headers: None,
+            });
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.events.HTTPStatusEvent`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `status`'s getter.
+pub fn status<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.unwrap().as_event() {
+        if let EventData::HttpStatus { status, .. } = evt.event_data() {
+            return Ok((*status).into());
+        }
+
+        return Ok(0.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `responseURL`'s getter.
+pub fn response_url<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.unwrap().as_event() {
+        if let EventData::HttpStatus {
+            response_url: Some(response_url),
+            ..
+        } = evt.event_data()
+        {
+            return Ok((*response_url).into());
+        }
+
+        return Ok(Value::Null);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `responseURL`'s setter.
+pub fn set_response_url<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let value = match args.get(0).cloned().unwrap_or(Value::Null) {
+        Value::Undefined | Value::Null => None,
+        value => Some(value.coerce_to_string(activation)?),
+    };
+
+    if let Some(mut evt) = this.unwrap().as_event_mut(activation.context.gc_context) {
+        if let EventData::HttpStatus { response_url, .. } = evt.event_data_mut() {
+            *response_url = value;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `responseHeaders`'s getter.
+///
+/// Loaders don't report response headers, so this is `null` unless set by
+/// script.
+pub fn response_headers<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.unwrap().as_event() {
+        if let EventData::HttpStatus {
+            response_headers: Some(response_headers),
+            ..
+        } = evt.event_data()
+        {
+            return Ok((*response_headers).into());
+        }
+
+        return Ok(Value::Null);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `responseHeaders`'s setter.
+pub fn set_response_headers<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let value = match args.get(0).cloned().unwrap_or(Value::Null) {
+        Value::Undefined | Value::Null => None,
+        value => Some(value.coerce_to_object(activation)?),
+    };
+
+    if let Some(mut evt) = this.unwrap().as_event_mut(activation.context.gc_context) {
+        if let EventData::HttpStatus {
+            response_headers, ..
+        } = evt.event_data_mut()
+        {
+            *response_headers = value;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `HTTPStatusEvent`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.events"), "HTTPStatusEvent"),
+        Some(QName::new(Namespace::package("flash.events"), "Event").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "status"),
+        Method::from_builtin(status),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "responseURL"),
+        Method::from_builtin(response_url),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "responseURL"),
+        Method::from_builtin(set_response_url),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "responseHeaders"),
+        Method::from_builtin(response_headers),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "responseHeaders"),
+        Method::from_builtin(set_response_headers),
+    ));
+
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public(), "HTTP_STATUS"),
+        QName::new(Namespace::public(), "String").into(),
+        Some("httpStatus".into()),
+    ));
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public(), "HTTP_RESPONSE_STATUS"),
+        QName::new(Namespace::public(), "String").into(),
+        Some("httpResponseStatus".into()),
+    ));
+
+    class
+}
@@ -0,0 +1,169 @@
+//! `flash.events.MouseEvent` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::events::EventData;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.events.MouseEvent`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, args.get(0..3).unwrap_or(args))?;
+
+        let local_x = args
+            .get(3)
+            .cloned()
+            .unwrap_or(Value::Number(f64::NAN))
+            .coerce_to_number(activation)?;
+        let local_y = args
+            .get(4)
+            .cloned()
+            .unwrap_or(Value::Number(f64::NAN))
+            .coerce_to_number(activation)?;
+
+        if let Some(mut evt) = this.as_event_mut(activation.context.gc_context) {
+            evt.set_event_data(EventData::Mouse {
+                local_x,
+                local_y,
+                movement_x: 0.0,
+                movement_y: 0.0,
+            });
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.events.MouseEvent`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `localX`'s getter.
+pub fn local_x<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.unwrap().as_event() {
+        if let EventData::Mouse { local_x, .. } = evt.event_data() {
+            return Ok((*local_x).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `localY`'s getter.
+pub fn local_y<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.unwrap().as_event() {
+        if let EventData::Mouse { local_y, .. } = evt.event_data() {
+            return Ok((*local_y).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `movementX`'s getter.
+///
+/// This is only nonzero while the stage has locked the mouse.
+pub fn movement_x<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.unwrap().as_event() {
+        if let EventData::Mouse { movement_x, .. } = evt.event_data() {
+            return Ok((*movement_x).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `movementY`'s getter.
+pub fn movement_y<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.unwrap().as_event() {
+        if let EventData::Mouse { movement_y, .. } = evt.event_data() {
+            return Ok((*movement_y).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `MouseEvent`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.events"), "MouseEvent"),
+        Some(QName::new(Namespace::package("flash.events"), "Event").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "localX"),
+        Method::from_builtin(local_x),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "localY"),
+        Method::from_builtin(local_y),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "movementX"),
+        Method::from_builtin(movement_x),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "movementY"),
+        Method::from_builtin(movement_y),
+    ));
+
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public(), "CLICK"),
+        QName::new(Namespace::public(), "String").into(),
+        Some("click".into()),
+    ));
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public(), "MOUSE_DOWN"),
+        QName::new(Namespace::public(), "String").into(),
+        Some("mouseDown".into()),
+    ));
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public(), "MOUSE_MOVE"),
+        QName::new(Namespace::public(), "String").into(),
+        Some("mouseMove".into()),
+    ));
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public(), "MOUSE_UP"),
+        QName::new(Namespace::public(), "String").into(),
+        Some("mouseUp".into()),
+    ));
+
+    class
+}
@@ -0,0 +1,121 @@
+//! `flash.events.StageOrientationEvent` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::events::EventData;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.events.StageOrientationEvent`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, args.get(0..3).unwrap_or(args))?;
+
+        let before_orientation = match args.get(3).cloned().unwrap_or(Value::Null) {
+            Value::Undefined | Value::Null => None,
+            orientation => Some(orientation.coerce_to_string(activation)?),
+        };
+        let after_orientation = match args.get(4).cloned().unwrap_or(Value::Null) {
+            Value::Undefined | Value::Null => None,
+            orientation => Some(orientation.coerce_to_string(activation)?),
+        };
+
+        if let Some(mut evt) = this.as_event_mut(activation.context.gc_context) {
+            evt.set_event_data(EventData::StageOrientation {
+                before_orientation,
+                after_orientation,
+            });
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.events.StageOrientationEvent`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `beforeOrientation`'s getter.
+pub fn before_orientation<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.unwrap().as_event() {
+        if let EventData::StageOrientation {
+            before_orientation, ..
+        } = evt.event_data()
+        {
+            return Ok(before_orientation.map(Value::from).unwrap_or(Value::Null));
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `afterOrientation`'s getter.
+pub fn after_orientation<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.unwrap().as_event() {
+        if let EventData::StageOrientation {
+            after_orientation, ..
+        } = evt.event_data()
+        {
+            return Ok(after_orientation.map(Value::from).unwrap_or(Value::Null));
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `StageOrientationEvent`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.events"), "StageOrientationEvent"),
+        Some(QName::new(Namespace::package("flash.events"), "Event").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "beforeOrientation"),
+        Method::from_builtin(before_orientation),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "afterOrientation"),
+        Method::from_builtin(after_orientation),
+    ));
+
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public(), "ORIENTATION_CHANGE"),
+        QName::new(Namespace::public(), "String").into(),
+        Some("orientationChange".into()),
+    ));
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public(), "ORIENTATION_CHANGING"),
+        QName::new(Namespace::public(), "String").into(),
+        Some("orientationChanging".into()),
+    ));
+
+    class
+}
@@ -0,0 +1,110 @@
+//! `flash.events.SyncEvent` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::events::EventData;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.events.SyncEvent`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, args.get(0..3).unwrap_or(args))?;
+
+        if let Value::Object(change_list) = args.get(3).cloned().unwrap_or(Value::Null) {
+            if let Some(mut evt) = this.as_event_mut(activation.context.gc_context) {
+                evt.set_event_data(EventData::Sync {
+                    change_list: Some(change_list),
+                });
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.events.SyncEvent`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `changeList` property's getter
+pub fn change_list<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.unwrap().as_event() {
+        if let EventData::Sync {
+            change_list: Some(change_list),
+        } = evt.event_data()
+        {
+            return Ok((*change_list).into());
+        }
+
+        return Ok(Value::Null);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `changeList` property's setter
+pub fn set_change_list<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let change_list = match args.get(0).cloned().unwrap_or(Value::Null) {
+        Value::Undefined | Value::Null => None,
+        change_list => Some(change_list.coerce_to_object(activation)?),
+    };
+
+    if let Some(mut evt) = this.unwrap().as_event_mut(activation.context.gc_context) {
+        evt.set_event_data(EventData::Sync { change_list });
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `SyncEvent`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.events"), "SyncEvent"),
+        Some(QName::new(Namespace::package("flash.events"), "Event").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "changeList"),
+        Method::from_builtin(change_list),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "changeList"),
+        Method::from_builtin(set_change_list),
+    ));
+
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public(), "SYNC"),
+        QName::new(Namespace::public(), "String").into(),
+        Some("sync".into()),
+    ));
+
+    class
+}
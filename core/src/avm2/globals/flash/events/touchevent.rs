@@ -0,0 +1,355 @@
+//! `flash.events.TouchEvent` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::events::EventData;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.events.TouchEvent`'s instance constructor.
+///
+/// Events constructed by script have not been dispatched anywhere yet, so
+/// their stage coordinates are their local ones.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        // Unlike most events, touch events bubble by default.
+        let event_type = args.get(0).cloned().unwrap_or(Value::Undefined);
+        let bubbles = args.get(1).cloned().unwrap_or(Value::Bool(true));
+        let cancelable = args.get(2).cloned().unwrap_or(Value::Bool(false));
+        activation.super_init(this, &[event_type, bubbles, cancelable])?;
+
+        let touch_point_id = args
+            .get(3)
+            .cloned()
+            .unwrap_or(Value::Integer(0))
+            .coerce_to_i32(activation)?;
+        let is_primary_touch_point = args
+            .get(4)
+            .cloned()
+            .unwrap_or(Value::Bool(false))
+            .coerce_to_boolean();
+        let local_x = args
+            .get(5)
+            .cloned()
+            .unwrap_or(Value::Number(f64::NAN))
+            .coerce_to_number(activation)?;
+        let local_y = args
+            .get(6)
+            .cloned()
+            .unwrap_or(Value::Number(f64::NAN))
+            .coerce_to_number(activation)?;
+        let pressure = args
+            .get(9)
+            .cloned()
+            .unwrap_or(Value::Number(f64::NAN))
+            .coerce_to_number(activation)?;
+
+        if let Some(mut evt) = this.as_event_mut(activation.context.gc_context) {
+            evt.set_event_data(EventData::Touch {
+                touch_point_id,
+                is_primary_touch_point,
+                local_x,
+                local_y,
+                stage_x: local_x,
+                stage_y: local_y,
+                pressure,
+            });
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.events.TouchEvent`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `touchPointID`'s getter.
+pub fn touch_point_id<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.unwrap().as_event() {
+        if let EventData::Touch { touch_point_id, .. } = evt.event_data() {
+            return Ok((*touch_point_id).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `isPrimaryTouchPoint`'s getter.
+pub fn is_primary_touch_point<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.unwrap().as_event() {
+        if let EventData::Touch {
+            is_primary_touch_point,
+            ..
+        } = evt.event_data()
+        {
+            return Ok((*is_primary_touch_point).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `localX`'s getter.
+pub fn local_x<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.unwrap().as_event() {
+        if let EventData::Touch { local_x, .. } = evt.event_data() {
+            return Ok((*local_x).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `localY`'s getter.
+pub fn local_y<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.unwrap().as_event() {
+        if let EventData::Touch { local_y, .. } = evt.event_data() {
+            return Ok((*local_y).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `stageX`'s getter.
+pub fn stage_x<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.unwrap().as_event() {
+        if let EventData::Touch { stage_x, .. } = evt.event_data() {
+            return Ok((*stage_x).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `stageY`'s getter.
+pub fn stage_y<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.unwrap().as_event() {
+        if let EventData::Touch { stage_y, .. } = evt.event_data() {
+            return Ok((*stage_y).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `pressure`'s getter.
+///
+/// Hosts that can't sense pressure report 1 while a point is touching.
+pub fn pressure<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.unwrap().as_event() {
+        if let EventData::Touch { pressure, .. } = evt.event_data() {
+            return Ok((*pressure).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `TouchEvent`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.events"), "TouchEvent"),
+        Some(QName::new(Namespace::package("flash.events"), "Event").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "touchPointID"),
+        Method::from_builtin(touch_point_id),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "isPrimaryTouchPoint"),
+        Method::from_builtin(is_primary_touch_point),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "localX"),
+        Method::from_builtin(local_x),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "localY"),
+        Method::from_builtin(local_y),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "stageX"),
+        Method::from_builtin(stage_x),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "stageY"),
+        Method::from_builtin(stage_y),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "pressure"),
+        Method::from_builtin(pressure),
+    ));
+
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public(), "TOUCH_BEGIN"),
+        QName::new(Namespace::public(), "String").into(),
+        Some("touchBegin".into()),
+    ));
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public(), "TOUCH_END"),
+        QName::new(Namespace::public(), "String").into(),
+        Some("touchEnd".into()),
+    ));
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public(), "TOUCH_MOVE"),
+        QName::new(Namespace::public(), "String").into(),
+        Some("touchMove".into()),
+    ));
+
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::globals::flash::ui::multitouch;
+    use crate::avm2::object::FunctionObject;
+    use crate::avm2::test_utils::trace_with_avm2;
+    use crate::avm2::Avm2;
+
+    fn trace_touch<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let mut event = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let mut line = vec![];
+        for name in &[
+            "type",
+            "touchPointID",
+            "isPrimaryTouchPoint",
+            "stageX",
+            "stageY",
+            "pressure",
+            "bubbles",
+        ] {
+            line.push(
+                event
+                    .get_property(event, &QName::new(Namespace::public(), *name), activation)?
+                    .coerce_to_string(activation)?
+                    .to_string(),
+            );
+        }
+
+        activation.context.log.avm_trace(&line.join(" / "));
+
+        Ok(Value::Undefined)
+    }
+
+    #[test]
+    fn touches_dispatch_touch_events_on_the_stage() {
+        let log = trace_with_avm2(|activation| {
+            let mut stage = activation.context.avm2.stage().unwrap();
+            let listener = FunctionObject::from_builtin(
+                activation.context.gc_context,
+                trace_touch,
+                activation.avm2().prototypes().function,
+            );
+            let add_event_listener = stage
+                .get_property(
+                    stage,
+                    &QName::new(Namespace::public(), "addEventListener"),
+                    activation,
+                )
+                .unwrap()
+                .coerce_to_object(activation)
+                .unwrap();
+            for event_type in &["touchBegin", "touchMove", "touchEnd"] {
+                add_event_listener
+                    .call(
+                        Some(stage),
+                        &[(*event_type).into(), listener.into()],
+                        activation,
+                        None,
+                    )
+                    .unwrap();
+            }
+
+            // Touches are gestures until content asks for touch points.
+            Avm2::dispatch_touch(&mut activation.context, "touchBegin", 1, (5.0, 6.0), 1.0)
+                .unwrap();
+            Avm2::dispatch_touch(&mut activation.context, "touchEnd", 1, (5.0, 6.0), 0.0).unwrap();
+
+            multitouch::set_input_mode(activation, None, &["touchPoint".into()]).unwrap();
+            assert_eq!(
+                multitouch::input_mode(activation, None, &[]).unwrap(),
+                "touchPoint".into()
+            );
+
+            Avm2::dispatch_touch(&mut activation.context, "touchBegin", 7, (10.0, 20.0), 0.5)
+                .unwrap();
+            Avm2::dispatch_touch(&mut activation.context, "touchBegin", 8, (30.0, 40.0), 1.0)
+                .unwrap();
+            Avm2::dispatch_touch(&mut activation.context, "touchMove", 7, (11.0, 21.0), 0.5)
+                .unwrap();
+            Avm2::dispatch_touch(&mut activation.context, "touchEnd", 7, (11.0, 21.0), 0.0)
+                .unwrap();
+
+            assert!(multitouch::set_input_mode(activation, None, &["mouse".into()]).is_err());
+        });
+
+        assert_eq!(
+            log,
+            vec![
+                "touchBegin / 7 / true / 10 / 20 / 0.5 / true",
+                "touchBegin / 8 / false / 30 / 40 / 1 / true",
+                "touchMove / 7 / true / 11 / 21 / 0.5 / true",
+                "touchEnd / 7 / true / 11 / 21 / 0 / true",
+            ]
+        );
+    }
+}
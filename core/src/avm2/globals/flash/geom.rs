@@ -0,0 +1,10 @@
+//! `flash.geom` namespace
+
+pub mod matrix;
+pub mod matrix3d;
+pub mod perspectiveprojection;
+pub mod point;
+pub mod rectangle;
+pub mod transform;
+pub mod utils3d;
+pub mod vector3d;
@@ -0,0 +1,160 @@
+//! `flash.geom.Matrix` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+use swf::{Matrix, Twips};
+
+/// The names of a matrix's components, in constructor order.
+const COMPONENTS: [&str; 6] = ["a", "b", "c", "d", "tx", "ty"];
+
+/// Implements `flash.geom.Matrix`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        let identity: [f64; 6] = [1.0, 0.0, 0.0, 1.0, 0.0, 0.0];
+        for (i, (name, default)) in COMPONENTS.iter().zip(identity.iter()).enumerate() {
+            let value = args
+                .get(i)
+                .cloned()
+                .unwrap_or_else(|| (*default).into())
+                .coerce_to_number(activation)?;
+
+            this.set_property(
+                this,
+                &QName::new(Namespace::public(), *name),
+                value.into(),
+                activation,
+            )?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.geom.Matrix`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct a new `Matrix` object holding the given matrix.
+pub fn matrix_to_object<'gc>(
+    matrix: Matrix,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Object<'gc>, Error> {
+    components_to_object(matrix_to_components(&matrix), activation)
+}
+
+/// Construct a new `Matrix` object with the given components, in
+/// constructor order.
+pub fn components_to_object<'gc>(
+    components: [f64; 6],
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Object<'gc>, Error> {
+    let args: Vec<Value<'gc>> = components.iter().map(|c| (*c).into()).collect();
+    let mut proto = activation.context.avm2.prototypes().matrix;
+    let object = proto.construct(activation, &args)?;
+    let constr = proto
+        .get_property(
+            proto,
+            &QName::new(Namespace::public(), "constructor"),
+            activation,
+        )?
+        .coerce_to_object(activation)?;
+
+    constr.call(Some(object), &args, activation, Some(proto))?;
+
+    Ok(object)
+}
+
+/// Read the matrix held by a `Matrix` object.
+pub fn object_to_matrix<'gc>(
+    object: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Matrix, Error> {
+    Ok(components_to_matrix(&object_to_components(
+        object, activation,
+    )?))
+}
+
+/// Read the components of a `Matrix` object, in constructor order.
+pub fn object_to_components<'gc>(
+    mut object: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<[f64; 6], Error> {
+    let mut components = [0.0; 6];
+    for (component, name) in components.iter_mut().zip(COMPONENTS.iter()) {
+        *component = object
+            .get_property(object, &QName::new(Namespace::public(), *name), activation)?
+            .coerce_to_number(activation)?;
+    }
+
+    Ok(components)
+}
+
+/// Get the components of a matrix, in constructor order.
+pub fn matrix_to_components(matrix: &Matrix) -> [f64; 6] {
+    [
+        matrix.a.into(),
+        matrix.b.into(),
+        matrix.c.into(),
+        matrix.d.into(),
+        matrix.tx.to_pixels(),
+        matrix.ty.to_pixels(),
+    ]
+}
+
+/// Build a matrix from its components, in constructor order.
+///
+/// Display objects store their matrices with less precision than
+/// ActionScript numbers have, so this is lossy.
+pub fn components_to_matrix(components: &[f64; 6]) -> Matrix {
+    let [a, b, c, d, tx, ty] = *components;
+
+    Matrix {
+        a: a as f32,
+        b: b as f32,
+        c: c as f32,
+        d: d as f32,
+        tx: Twips::from_pixels(tx),
+        ty: Twips::from_pixels(ty),
+    }
+}
+
+/// Construct `Matrix`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.geom"), "Matrix"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    for name in COMPONENTS.iter() {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public(), *name),
+            QName::new(Namespace::public(), "Number").into(),
+            None,
+        ));
+    }
+
+    class
+}
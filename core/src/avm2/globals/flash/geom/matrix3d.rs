@@ -0,0 +1,555 @@
+//! `flash.geom.Matrix3D` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::class::Class;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{ArrayObject, Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+use swf::{Matrix, Twips};
+
+/// The values of a 3D matrix, in column-major order as in `rawData`.
+///
+/// The translation lives in elements 12 through 14.
+pub type RawMatrix3D = [f64; 16];
+
+/// The identity matrix.
+pub const IDENTITY: RawMatrix3D = [
+    1.0, 0.0, 0.0, 0.0, //
+    0.0, 1.0, 0.0, 0.0, //
+    0.0, 0.0, 1.0, 0.0, //
+    0.0, 0.0, 0.0, 1.0, //
+];
+
+/// Promote a 2D matrix to the 3D matrix that transforms the XY plane in the
+/// same way.
+pub fn from_2d(matrix: &Matrix) -> RawMatrix3D {
+    [
+        matrix.a.into(),
+        matrix.b.into(),
+        0.0,
+        0.0,
+        matrix.c.into(),
+        matrix.d.into(),
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        1.0,
+        0.0,
+        matrix.tx.to_pixels(),
+        matrix.ty.to_pixels(),
+        0.0,
+        1.0,
+    ]
+}
+
+/// Project a 3D matrix onto the XY plane, as the 2D renderer draws it.
+pub fn to_2d(raw: &RawMatrix3D) -> Matrix {
+    Matrix {
+        a: raw[0] as f32,
+        b: raw[1] as f32,
+        c: raw[4] as f32,
+        d: raw[5] as f32,
+        tx: Twips::from_pixels(raw[12]),
+        ty: Twips::from_pixels(raw[13]),
+    }
+}
+
+/// Multiply two 3D matrices, yielding the transform that applies `rhs`
+/// first and then `lhs`.
+pub fn multiply(lhs: &RawMatrix3D, rhs: &RawMatrix3D) -> RawMatrix3D {
+    let mut product = [0.0; 16];
+
+    for column in 0..4 {
+        for row in 0..4 {
+            product[column * 4 + row] =
+                (0..4).map(|k| lhs[k * 4 + row] * rhs[column * 4 + k]).sum();
+        }
+    }
+
+    product
+}
+
+/// The cofactors of a 3D matrix, transposed, and its determinant.
+fn adjugate(m: &RawMatrix3D) -> (RawMatrix3D, f64) {
+    let mut adj = [0.0; 16];
+
+    adj[0] = m[5] * m[10] * m[15] - m[5] * m[11] * m[14] - m[9] * m[6] * m[15]
+        + m[9] * m[7] * m[14]
+        + m[13] * m[6] * m[11]
+        - m[13] * m[7] * m[10];
+    adj[4] = -m[4] * m[10] * m[15] + m[4] * m[11] * m[14] + m[8] * m[6] * m[15]
+        - m[8] * m[7] * m[14]
+        - m[12] * m[6] * m[11]
+        + m[12] * m[7] * m[10];
+    adj[8] = m[4] * m[9] * m[15] - m[4] * m[11] * m[13] - m[8] * m[5] * m[15]
+        + m[8] * m[7] * m[13]
+        + m[12] * m[5] * m[11]
+        - m[12] * m[7] * m[9];
+    adj[12] = -m[4] * m[9] * m[14] + m[4] * m[10] * m[13] + m[8] * m[5] * m[14]
+        - m[8] * m[6] * m[13]
+        - m[12] * m[5] * m[10]
+        + m[12] * m[6] * m[9];
+    adj[1] = -m[1] * m[10] * m[15] + m[1] * m[11] * m[14] + m[9] * m[2] * m[15]
+        - m[9] * m[3] * m[14]
+        - m[13] * m[2] * m[11]
+        + m[13] * m[3] * m[10];
+    adj[5] = m[0] * m[10] * m[15] - m[0] * m[11] * m[14] - m[8] * m[2] * m[15]
+        + m[8] * m[3] * m[14]
+        + m[12] * m[2] * m[11]
+        - m[12] * m[3] * m[10];
+    adj[9] = -m[0] * m[9] * m[15] + m[0] * m[11] * m[13] + m[8] * m[1] * m[15]
+        - m[8] * m[3] * m[13]
+        - m[12] * m[1] * m[11]
+        + m[12] * m[3] * m[9];
+    adj[13] = m[0] * m[9] * m[14] - m[0] * m[10] * m[13] - m[8] * m[1] * m[14]
+        + m[8] * m[2] * m[13]
+        + m[12] * m[1] * m[10]
+        - m[12] * m[2] * m[9];
+    adj[2] = m[1] * m[6] * m[15] - m[1] * m[7] * m[14] - m[5] * m[2] * m[15]
+        + m[5] * m[3] * m[14]
+        + m[13] * m[2] * m[7]
+        - m[13] * m[3] * m[6];
+    adj[6] = -m[0] * m[6] * m[15] + m[0] * m[7] * m[14] + m[4] * m[2] * m[15]
+        - m[4] * m[3] * m[14]
+        - m[12] * m[2] * m[7]
+        + m[12] * m[3] * m[6];
+    adj[10] = m[0] * m[5] * m[15] - m[0] * m[7] * m[13] - m[4] * m[1] * m[15]
+        + m[4] * m[3] * m[13]
+        + m[12] * m[1] * m[7]
+        - m[12] * m[3] * m[5];
+    adj[14] = -m[0] * m[5] * m[14] + m[0] * m[6] * m[13] + m[4] * m[1] * m[14]
+        - m[4] * m[2] * m[13]
+        - m[12] * m[1] * m[6]
+        + m[12] * m[2] * m[5];
+    adj[3] = -m[1] * m[6] * m[11] + m[1] * m[7] * m[10] + m[5] * m[2] * m[11]
+        - m[5] * m[3] * m[10]
+        - m[9] * m[2] * m[7]
+        + m[9] * m[3] * m[6];
+    adj[7] = m[0] * m[6] * m[11] - m[0] * m[7] * m[10] - m[4] * m[2] * m[11]
+        + m[4] * m[3] * m[10]
+        + m[8] * m[2] * m[7]
+        - m[8] * m[3] * m[6];
+    adj[11] = -m[0] * m[5] * m[11] + m[0] * m[7] * m[9] + m[4] * m[1] * m[11]
+        - m[4] * m[3] * m[9]
+        - m[8] * m[1] * m[7]
+        + m[8] * m[3] * m[5];
+    adj[15] = m[0] * m[5] * m[10] - m[0] * m[6] * m[9] - m[4] * m[1] * m[10]
+        + m[4] * m[2] * m[9]
+        + m[8] * m[1] * m[6]
+        - m[8] * m[2] * m[5];
+
+    let determinant = m[0] * adj[0] + m[1] * adj[4] + m[2] * adj[8] + m[3] * adj[12];
+
+    (adj, determinant)
+}
+
+/// Invert a 3D matrix, unless it is singular.
+pub fn invert(raw: &RawMatrix3D) -> Option<RawMatrix3D> {
+    let (mut inverse, determinant) = adjugate(raw);
+
+    if determinant == 0.0 || !determinant.is_finite() {
+        return None;
+    }
+
+    for value in inverse.iter_mut() {
+        *value /= determinant;
+    }
+
+    Some(inverse)
+}
+
+/// The translation, rotation and scale that a 3D matrix is built from.
+///
+/// Rotations are in radians, about the X, Y and Z axes. They are applied
+/// after scaling, in that order, and before the translation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Components3D {
+    pub translation: [f64; 3],
+    pub rotation: [f64; 3],
+    pub scale: [f64; 3],
+}
+
+/// Build a 3D matrix from its components.
+pub fn recompose(components: &Components3D) -> RawMatrix3D {
+    let [rx, ry, rz] = components.rotation;
+    let (sa, ca) = rx.sin_cos();
+    let (sb, cb) = ry.sin_cos();
+    let (sc, cc) = rz.sin_cos();
+
+    // The rows of the rotation about Z, then Y, then X.
+    let rotation = [
+        [cc * cb, cc * sb * sa - sc * ca, cc * sb * ca + sc * sa],
+        [sc * cb, sc * sb * sa + cc * ca, sc * sb * ca - cc * sa],
+        [-sb, cb * sa, cb * ca],
+    ];
+
+    let mut raw = IDENTITY;
+    for column in 0..3 {
+        for row in 0..3 {
+            raw[column * 4 + row] = rotation[row][column] * components.scale[column];
+        }
+    }
+    raw[12..15].copy_from_slice(&components.translation);
+
+    raw
+}
+
+/// Split a 3D matrix into the components it could have been built from.
+///
+/// Scales are always positive. Any projection the matrix does is discarded.
+/// When the rotation about Y is a right angle, the rotations about X and Z
+/// can't be told apart, and all of it is taken to be about X.
+pub fn decompose(raw: &RawMatrix3D) -> Components3D {
+    let mut scale = [0.0; 3];
+    let mut rotation = [[0.0; 3]; 3];
+    for column in 0..3 {
+        let axis = &raw[column * 4..column * 4 + 3];
+        scale[column] = axis.iter().map(|v| v * v).sum::<f64>().sqrt();
+
+        for row in 0..3 {
+            rotation[row][column] = if scale[column] != 0.0 {
+                axis[row] / scale[column]
+            } else {
+                0.0
+            };
+        }
+    }
+
+    let sin_y = (-rotation[2][0]).clamp(-1.0, 1.0);
+    let ry = sin_y.asin();
+    let (rx, rz) = if ry.cos() > 1e-9 {
+        (
+            rotation[2][1].atan2(rotation[2][2]),
+            rotation[1][0].atan2(rotation[0][0]),
+        )
+    } else {
+        ((sin_y * rotation[0][1]).atan2(rotation[1][1]), 0.0)
+    };
+
+    Components3D {
+        translation: [raw[12], raw[13], raw[14]],
+        rotation: [rx, ry, rz],
+        scale,
+    }
+}
+
+/// Read the values of an array-like object as a 3D matrix.
+///
+/// Objects with fewer than 16 values are an `ArgumentError`.
+fn array_to_raw<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    array: Value<'gc>,
+) -> Result<RawMatrix3D, Error> {
+    let values: Vec<Value<'gc>> = match array {
+        Value::Object(object) => object
+            .as_array_storage()
+            .map(|storage| {
+                storage
+                    .iter()
+                    .map(|v| v.unwrap_or(Value::Undefined))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    };
+
+    if values.len() < 16 {
+        return Err("ArgumentError: Error #2004: One of the parameters is invalid.".into());
+    }
+
+    let mut raw = [0.0; 16];
+    for (element, value) in raw.iter_mut().zip(values) {
+        *element = value.coerce_to_number(activation)?;
+    }
+
+    Ok(raw)
+}
+
+/// Store a 3D matrix's values on a `Matrix3D` object.
+fn set_raw<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+    raw: &RawMatrix3D,
+) -> Result<(), Error> {
+    let values = raw.iter().map(|v| Some((*v).into())).collect();
+    let array = ArrayObject::from_array(
+        ArrayStorage::from_storage(values),
+        activation.context.avm2.prototypes().array,
+        activation.context.gc_context,
+    );
+
+    this.set_property(
+        this,
+        &QName::new(Namespace::Private("ruffle".into()), "rawData"),
+        array.into(),
+        activation,
+    )?;
+
+    Ok(())
+}
+
+/// Implements `flash.geom.Matrix3D`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+
+        let raw = match args.get(0).cloned().unwrap_or(Value::Null) {
+            Value::Undefined | Value::Null => IDENTITY,
+            values => array_to_raw(activation, values)?,
+        };
+
+        set_raw(activation, this, &raw)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.geom.Matrix3D`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct a new `Matrix3D` object holding the given matrix.
+pub fn matrix3d_to_object<'gc>(
+    raw: &RawMatrix3D,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Object<'gc>, Error> {
+    let mut proto = activation.context.avm2.prototypes().matrix3d;
+    let object = proto.construct(activation, &[])?;
+    let constr = proto
+        .get_property(
+            proto,
+            &QName::new(Namespace::public(), "constructor"),
+            activation,
+        )?
+        .coerce_to_object(activation)?;
+
+    constr.call(Some(object), &[], activation, Some(proto))?;
+    set_raw(activation, object, raw)?;
+
+    Ok(object)
+}
+
+/// Read the matrix held by a `Matrix3D` object.
+pub fn object_to_matrix3d<'gc>(
+    mut object: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<RawMatrix3D, Error> {
+    let values = object.get_property(
+        object,
+        &QName::new(Namespace::Private("ruffle".into()), "rawData"),
+        activation,
+    )?;
+
+    array_to_raw(activation, values)
+}
+
+/// Implements `rawData`'s getter.
+///
+/// This yields a copy: changing it does not change the matrix.
+pub fn raw_data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let raw = object_to_matrix3d(this, activation)?;
+        let values = raw.iter().map(|v| Some((*v).into())).collect();
+
+        return Ok(ArrayObject::from_array(
+            ArrayStorage::from_storage(values),
+            activation.context.avm2.prototypes().array,
+            activation.context.gc_context,
+        )
+        .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `rawData`'s setter.
+pub fn set_raw_data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let raw = array_to_raw(activation, args.get(0).cloned().unwrap_or(Value::Undefined))?;
+
+        set_raw(activation, this, &raw)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `determinant`'s getter.
+pub fn determinant<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let raw = object_to_matrix3d(this, activation)?;
+
+        return Ok(adjugate(&raw).1.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Matrix3D.identity`.
+pub fn identity<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        set_raw(activation, this, &IDENTITY)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Matrix3D.clone`.
+pub fn clone<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let raw = object_to_matrix3d(this, activation)?;
+
+        return Ok(matrix3d_to_object(&raw, activation)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Matrix3D.append`, which applies another matrix after this
+/// one.
+pub fn append<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let lhs = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let lhs = object_to_matrix3d(lhs, activation)?;
+        let raw = object_to_matrix3d(this, activation)?;
+
+        set_raw(activation, this, &multiply(&lhs, &raw))?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Matrix3D.prepend`, which applies another matrix before this
+/// one.
+pub fn prepend<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let rhs = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let rhs = object_to_matrix3d(rhs, activation)?;
+        let raw = object_to_matrix3d(this, activation)?;
+
+        set_raw(activation, this, &multiply(&raw, &rhs))?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Matrix3D.invert`.
+///
+/// A singular matrix is left unchanged, and `false` is returned.
+pub fn invert_matrix<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let raw = object_to_matrix3d(this, activation)?;
+
+        if let Some(inverse) = invert(&raw) {
+            set_raw(activation, this, &inverse)?;
+
+            return Ok(true.into());
+        }
+
+        return Ok(false.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Matrix3D`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.geom"), "Matrix3D"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "rawData"),
+        Method::from_builtin(raw_data),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "rawData"),
+        Method::from_builtin(set_raw_data),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "determinant"),
+        Method::from_builtin(determinant),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "identity"),
+        Method::from_builtin(identity),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "clone"),
+        Method::from_builtin(clone),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "append"),
+        Method::from_builtin(append),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "prepend"),
+        Method::from_builtin(prepend),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "invert"),
+        Method::from_builtin(invert_matrix),
+    ));
+
+    class
+}
@@ -0,0 +1,338 @@
+//! `flash.geom.PerspectiveProjection` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::flash::geom::matrix3d::RawMatrix3D;
+use crate::avm2::globals::flash::geom::point;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+use swf::{Matrix, Twips};
+
+/// A perspective, as its field of view in degrees followed by the X and Y of
+/// its projection center.
+pub type RawPerspectiveProjection = [f64; 3];
+
+/// The field of view of a new perspective, in degrees.
+pub const DEFAULT_FIELD_OF_VIEW: f64 = 55.0;
+
+/// The narrowest field of view, in degrees. Anything narrower would need an
+/// infinitely long focal length.
+const MIN_FIELD_OF_VIEW: f64 = 0.01;
+
+/// The widest field of view, in degrees. Anything wider would need a focal
+/// length of zero or less.
+const MAX_FIELD_OF_VIEW: f64 = 179.99;
+
+/// The names that a `PerspectiveProjection` object keeps its values under.
+const FIELDS: [&str; 3] = ["fieldOfView", "centerX", "centerY"];
+
+/// Keep a field of view strictly between 0 and 180 degrees.
+fn clamp_field_of_view(field_of_view: f64) -> f64 {
+    field_of_view.clamp(MIN_FIELD_OF_VIEW, MAX_FIELD_OF_VIEW)
+}
+
+/// The distance from the viewer to the XY plane that gives a field of view
+/// spanning `width` pixels of it.
+pub fn focal_length(field_of_view: f64, width: f64) -> f64 {
+    (width / 2.0) / (clamp_field_of_view(field_of_view).to_radians() / 2.0).tan()
+}
+
+/// The field of view that a focal length gives, when it spans `width`
+/// pixels of the XY plane.
+fn field_of_view(focal_length: f64, width: f64) -> f64 {
+    clamp_field_of_view(((width / 2.0) / focal_length).atan().to_degrees() * 2.0)
+}
+
+/// Project a 3D transform onto the XY plane through a perspective, as the
+/// 2D renderer draws it.
+///
+/// The transform is scaled about the projection center by how far its
+/// origin is from the viewer, so objects further along Z are drawn smaller
+/// and nearer to the center. Objects level with or behind the viewer are
+/// scaled down to nothing.
+pub fn project(
+    raw: &RawMatrix3D,
+    &[field_of_view, center_x, center_y]: &RawPerspectiveProjection,
+    width: f64,
+) -> Matrix {
+    let focal_length = focal_length(field_of_view, width);
+    let depth = focal_length + raw[14];
+    let factor = if depth > 0.0 {
+        focal_length / depth
+    } else {
+        0.0
+    };
+
+    Matrix {
+        a: (raw[0] * factor) as f32,
+        b: (raw[1] * factor) as f32,
+        c: (raw[4] * factor) as f32,
+        d: (raw[5] * factor) as f32,
+        tx: Twips::from_pixels(center_x + (raw[12] - center_x) * factor),
+        ty: Twips::from_pixels(center_y + (raw[13] - center_y) * factor),
+    }
+}
+
+/// The width of the stage, in pixels, which focal lengths are measured
+/// against.
+fn stage_width(activation: &mut Activation<'_, '_, '_>) -> f64 {
+    activation.context.stage_size.0.to_pixels()
+}
+
+/// Store a perspective's values on a `PerspectiveProjection` object.
+fn set_raw<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+    raw: &RawPerspectiveProjection,
+) -> Result<(), Error> {
+    for (name, value) in FIELDS.iter().zip(raw.iter()) {
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), *name),
+            (*value).into(),
+            activation,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Read the perspective held by a `PerspectiveProjection` object.
+pub fn object_to_perspective_projection<'gc>(
+    mut object: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<RawPerspectiveProjection, Error> {
+    let mut raw = [0.0; 3];
+    for (value, name) in raw.iter_mut().zip(FIELDS.iter()) {
+        *value = object
+            .get_property(
+                object,
+                &QName::new(Namespace::Private("ruffle".into()), *name),
+                activation,
+            )?
+            .coerce_to_number(activation)?;
+    }
+
+    Ok(raw)
+}
+
+/// Construct a new `PerspectiveProjection` object holding the given
+/// perspective.
+pub fn perspective_projection_to_object<'gc>(
+    raw: &RawPerspectiveProjection,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Object<'gc>, Error> {
+    let mut proto = activation.context.avm2.prototypes().perspective_projection;
+    let object = proto.construct(activation, &[])?;
+    let constr = proto
+        .get_property(
+            proto,
+            &QName::new(Namespace::public(), "constructor"),
+            activation,
+        )?
+        .coerce_to_object(activation)?;
+
+    constr.call(Some(object), &[], activation, Some(proto))?;
+    set_raw(activation, object, raw)?;
+
+    Ok(object)
+}
+
+/// Implements `flash.geom.PerspectiveProjection`'s instance constructor.
+///
+/// New perspectives look at the center of the stage.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+
+        let (width, height) = activation.context.stage_size;
+        set_raw(
+            activation,
+            this,
+            &[
+                DEFAULT_FIELD_OF_VIEW,
+                width.to_pixels() / 2.0,
+                height.to_pixels() / 2.0,
+            ],
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.geom.PerspectiveProjection`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `fieldOfView`'s getter.
+pub fn get_field_of_view<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let [field_of_view, _, _] = object_to_perspective_projection(this, activation)?;
+
+        return Ok(field_of_view.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `fieldOfView`'s setter.
+///
+/// Angles are kept strictly between 0 and 180 degrees, so that the focal
+/// length stays positive and finite.
+pub fn set_field_of_view<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let field_of_view = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+        let [_, center_x, center_y] = object_to_perspective_projection(this, activation)?;
+
+        set_raw(
+            activation,
+            this,
+            &[clamp_field_of_view(field_of_view), center_x, center_y],
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `focalLength`'s getter.
+///
+/// This follows from the field of view and the width of the stage.
+pub fn get_focal_length<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let [field_of_view, _, _] = object_to_perspective_projection(this, activation)?;
+
+        return Ok(focal_length(field_of_view, stage_width(activation)).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `focalLength`'s setter, which changes the field of view to
+/// match.
+pub fn set_focal_length<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let focal_length = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+        let [_, center_x, center_y] = object_to_perspective_projection(this, activation)?;
+        let field_of_view = field_of_view(focal_length, stage_width(activation));
+
+        set_raw(activation, this, &[field_of_view, center_x, center_y])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `projectionCenter`'s getter.
+///
+/// This yields a copy: changing it does not move the center.
+pub fn get_projection_center<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let [_, center_x, center_y] = object_to_perspective_projection(this, activation)?;
+
+        return Ok(point::point_to_object((center_x, center_y), activation)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `projectionCenter`'s setter.
+pub fn set_projection_center<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let center = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let (center_x, center_y) = point::object_to_point(center, activation)?;
+        let [field_of_view, _, _] = object_to_perspective_projection(this, activation)?;
+
+        set_raw(activation, this, &[field_of_view, center_x, center_y])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `PerspectiveProjection`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.geom"), "PerspectiveProjection"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "fieldOfView"),
+        Method::from_builtin(get_field_of_view),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "fieldOfView"),
+        Method::from_builtin(set_field_of_view),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "focalLength"),
+        Method::from_builtin(get_focal_length),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "focalLength"),
+        Method::from_builtin(set_focal_length),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "projectionCenter"),
+        Method::from_builtin(get_projection_center),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "projectionCenter"),
+        Method::from_builtin(set_projection_center),
+    ));
+
+    class
+}
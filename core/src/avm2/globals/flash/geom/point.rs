@@ -0,0 +1,110 @@
+//! `flash.geom.Point` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// The names of a point's coordinates, in constructor order.
+const COORDINATES: [&str; 2] = ["x", "y"];
+
+/// Implements `flash.geom.Point`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        for (i, name) in COORDINATES.iter().enumerate() {
+            let value = args
+                .get(i)
+                .cloned()
+                .unwrap_or_else(|| 0.0.into())
+                .coerce_to_number(activation)?;
+
+            this.set_property(
+                this,
+                &QName::new(Namespace::public(), *name),
+                value.into(),
+                activation,
+            )?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.geom.Point`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct a new `Point` object at the given coordinates.
+pub fn point_to_object<'gc>(
+    (x, y): (f64, f64),
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Object<'gc>, Error> {
+    let args = [x.into(), y.into()];
+    let mut proto = activation.context.avm2.prototypes().point;
+    let object = proto.construct(activation, &args)?;
+    let constr = proto
+        .get_property(
+            proto,
+            &QName::new(Namespace::public(), "constructor"),
+            activation,
+        )?
+        .coerce_to_object(activation)?;
+
+    constr.call(Some(object), &args, activation, Some(proto))?;
+
+    Ok(object)
+}
+
+/// Read the coordinates of a `Point` object.
+pub fn object_to_point<'gc>(
+    mut object: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<(f64, f64), Error> {
+    let x = object
+        .get_property(object, &QName::new(Namespace::public(), "x"), activation)?
+        .coerce_to_number(activation)?;
+    let y = object
+        .get_property(object, &QName::new(Namespace::public(), "y"), activation)?
+        .coerce_to_number(activation)?;
+
+    Ok((x, y))
+}
+
+/// Construct `Point`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.geom"), "Point"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    for name in COORDINATES.iter() {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public(), *name),
+            QName::new(Namespace::public(), "Number").into(),
+            None,
+        ));
+    }
+
+    class
+}
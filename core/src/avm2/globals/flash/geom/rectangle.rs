@@ -0,0 +1,130 @@
+//! `flash.geom.Rectangle` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::bounding_box::BoundingBox;
+use gc_arena::{GcCell, MutationContext};
+use swf::Twips;
+
+/// The names of a rectangle's components, in constructor order.
+const COMPONENTS: [&str; 4] = ["x", "y", "width", "height"];
+
+/// Implements `flash.geom.Rectangle`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        for (i, name) in COMPONENTS.iter().enumerate() {
+            let value = args
+                .get(i)
+                .cloned()
+                .unwrap_or_else(|| 0.0.into())
+                .coerce_to_number(activation)?;
+
+            this.set_property(
+                this,
+                &QName::new(Namespace::public(), *name),
+                value.into(),
+                activation,
+            )?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.geom.Rectangle`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct a new `Rectangle` object covering the given bounding box.
+///
+/// An invalid (empty) bounding box becomes an empty rectangle at the origin.
+pub fn bounding_box_to_object<'gc>(
+    bounds: &BoundingBox,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Object<'gc>, Error> {
+    let args = if bounds.valid {
+        [
+            bounds.x_min.to_pixels().into(),
+            bounds.y_min.to_pixels().into(),
+            bounds.width().to_pixels().into(),
+            bounds.height().to_pixels().into(),
+        ]
+    } else {
+        [0.0.into(), 0.0.into(), 0.0.into(), 0.0.into()]
+    };
+    let mut proto = activation.context.avm2.prototypes().rectangle;
+    let object = proto.construct(activation, &args)?;
+    let constr = proto
+        .get_property(
+            proto,
+            &QName::new(Namespace::public(), "constructor"),
+            activation,
+        )?
+        .coerce_to_object(activation)?;
+
+    constr.call(Some(object), &args, activation, Some(proto))?;
+
+    Ok(object)
+}
+
+/// Read the area covered by a `Rectangle` object.
+pub fn object_to_bounding_box<'gc>(
+    mut object: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<BoundingBox, Error> {
+    let mut components = [0.0; 4];
+    for (component, name) in components.iter_mut().zip(COMPONENTS.iter()) {
+        *component = object
+            .get_property(object, &QName::new(Namespace::public(), *name), activation)?
+            .coerce_to_number(activation)?;
+    }
+
+    let [x, y, width, height] = components;
+    Ok(BoundingBox {
+        x_min: Twips::from_pixels(x),
+        y_min: Twips::from_pixels(y),
+        x_max: Twips::from_pixels(x + width),
+        y_max: Twips::from_pixels(y + height),
+        valid: true,
+    })
+}
+
+/// Construct `Rectangle`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.geom"), "Rectangle"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    for name in COMPONENTS.iter() {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public(), *name),
+            QName::new(Namespace::public(), "Number").into(),
+            None,
+        ));
+    }
+
+    class
+}
@@ -0,0 +1,677 @@
+//! `flash.geom.Transform` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::flash::geom::matrix3d::{self, RawMatrix3D};
+use crate::avm2::globals::flash::geom::{matrix, perspectiveprojection, rectangle};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, StageObject, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::display_object::{DisplayObject, TDisplayObject, TDisplayObjectContainer};
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.geom.Transform`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.geom.Transform`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct a `Transform` object that reads and writes the transform of a
+/// given display object.
+pub fn transform_for_display_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    dobj: DisplayObject<'gc>,
+) -> Object<'gc> {
+    let transform_proto = activation.context.avm2.prototypes().transform;
+
+    StageObject::for_display_object(activation.context.gc_context, dobj, transform_proto).into()
+}
+
+/// Implements `matrix`'s getter.
+///
+/// Objects with a 3D transform have no 2D matrix, and yield `null`.
+///
+/// If the matrix is still the one that was last assigned, its components are
+/// returned exactly as they were set, so content that rewrites the matrix
+/// every frame doesn't drift. Otherwise they are taken from the matrix
+/// itself, which is also what `x`, `scaleX` and `rotation` derive from.
+pub fn matrix<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        if dobj.matrix_3d().is_some() {
+            return Ok(Value::Null);
+        }
+
+        let matrix = *dobj.matrix();
+        let components = dobj
+            .script_matrix()
+            .filter(|components| matrix::components_to_matrix(components) == matrix)
+            .unwrap_or_else(|| matrix::matrix_to_components(&matrix));
+
+        return Ok(matrix::components_to_object(components, activation)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `matrix`'s setter.
+///
+/// This replaces any 3D transform the object had.
+pub fn set_matrix<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let matrix = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let components = matrix::object_to_components(matrix, activation)?;
+
+        dobj.set_matrix_3d(activation.context.gc_context, None);
+        dobj.set_matrix(
+            activation.context.gc_context,
+            &matrix::components_to_matrix(&components),
+        );
+        dobj.set_script_matrix(activation.context.gc_context, Some(components));
+        dobj.set_transformed_by_script(activation.context.gc_context, true);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `matrix3D`'s getter.
+///
+/// Objects with only a 2D transform yield `null`.
+pub fn matrix_3d<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return match dobj.matrix_3d() {
+            Some(raw) => Ok(matrix3d::matrix3d_to_object(&raw, activation)?.into()),
+            None => Ok(Value::Null),
+        };
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `matrix3D`'s setter.
+///
+/// The object is drawn with the projection of the 3D transform onto the XY
+/// plane, through the nearest perspective set on it or an ancestor, which
+/// also replaces its 2D matrix. Without a perspective, Z is ignored. Setting `null` turns the object
+/// back into a 2D one, keeping that projection as its matrix.
+pub fn set_matrix_3d<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        match args.get(0).cloned().unwrap_or(Value::Null) {
+            Value::Undefined | Value::Null => {
+                dobj.set_matrix_3d(activation.context.gc_context, None);
+            }
+            matrix => {
+                let matrix = matrix.coerce_to_object(activation)?;
+                let raw = matrix3d::object_to_matrix3d(matrix, activation)?;
+
+                dobj.set_matrix_3d(activation.context.gc_context, Some(raw));
+                project_matrix_3d(activation, dobj);
+                dobj.set_script_matrix(activation.context.gc_context, None);
+            }
+        }
+
+        dobj.set_transformed_by_script(activation.context.gc_context, true);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Replace the 2D matrix of a display object with the projection of its 3D
+/// transform, if it has one.
+pub fn project_matrix_3d<'gc>(activation: &mut Activation<'_, 'gc, '_>, dobj: DisplayObject<'gc>) {
+    let raw = match dobj.matrix_3d() {
+        Some(raw) => raw,
+        None => return,
+    };
+
+    let mut node = Some(dobj);
+    let mut projection = None;
+    while let Some(current) = node {
+        projection = current.perspective_projection();
+        if projection.is_some() {
+            break;
+        }
+
+        node = current.parent();
+    }
+
+    let matrix = match projection {
+        Some(projection) => perspectiveprojection::project(
+            &raw,
+            &projection,
+            activation.context.stage_size.0.to_pixels(),
+        ),
+        None => matrix3d::to_2d(&raw),
+    };
+
+    dobj.set_matrix(activation.context.gc_context, &matrix);
+}
+
+/// Reproject the 3D transforms of a display object and everything under it.
+fn project_subtree<'gc>(activation: &mut Activation<'_, 'gc, '_>, dobj: DisplayObject<'gc>) {
+    project_matrix_3d(activation, dobj);
+
+    if let Some(container) = dobj.as_container() {
+        for child in container.iter_render_list() {
+            project_subtree(activation, child);
+        }
+    }
+}
+
+/// Implements `perspectiveProjection`'s getter.
+///
+/// Objects that were never given a perspective of their own yield `null`.
+pub fn perspective_projection<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return match dobj.perspective_projection() {
+            Some(raw) => Ok(perspectiveprojection::perspective_projection_to_object(
+                &raw, activation,
+            )?
+            .into()),
+            None => Ok(Value::Null),
+        };
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `perspectiveProjection`'s setter.
+///
+/// The perspective is copied, so later changes to the `PerspectiveProjection`
+/// object only take effect once it is assigned again. Every 3D transform in
+/// the object's subtree is reprojected.
+pub fn set_perspective_projection<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let raw = match args.get(0).cloned().unwrap_or(Value::Null) {
+            Value::Undefined | Value::Null => None,
+            projection => {
+                let projection = projection.coerce_to_object(activation)?;
+
+                Some(perspectiveprojection::object_to_perspective_projection(
+                    projection, activation,
+                )?)
+            }
+        };
+
+        dobj.set_perspective_projection(activation.context.gc_context, raw);
+        project_subtree(activation, dobj);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Get the 3D transform from a display object up to the root of its
+/// display tree.
+///
+/// Objects without a 3D transform contribute their 2D one.
+fn concatenated_matrix_3d(dobj: DisplayObject<'_>) -> RawMatrix3D {
+    let mut concatenated = matrix3d::IDENTITY;
+    let mut node = Some(dobj);
+
+    while let Some(current) = node {
+        let local = current
+            .matrix_3d()
+            .unwrap_or_else(|| matrix3d::from_2d(&current.matrix()));
+
+        concatenated = matrix3d::multiply(&local, &concatenated);
+        node = current.parent();
+    }
+
+    concatenated
+}
+
+/// Implements `Transform.getRelativeMatrix3D`.
+///
+/// This is the transform from the display object's space into that of
+/// `relativeTo`. If the latter can't be inverted, there is no such
+/// transform, and `null` is returned.
+pub fn get_relative_matrix_3d<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let relative_to = match args.get(0).cloned().unwrap_or(Value::Null) {
+            Value::Object(object) => object.as_display_object(),
+            _ => None,
+        };
+        let relative_to = relative_to.ok_or_else(|| {
+            Error::from("TypeError: Error #2007: Parameter relativeTo must be non-null.")
+        })?;
+
+        let to_global = concatenated_matrix_3d(dobj);
+        let from_global = match matrix3d::invert(&concatenated_matrix_3d(relative_to)) {
+            Some(from_global) => from_global,
+            None => return Ok(Value::Null),
+        };
+        let relative = matrix3d::multiply(&from_global, &to_global);
+
+        return Ok(matrix3d::matrix3d_to_object(&relative, activation)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `concatenatedMatrix`'s getter.
+///
+/// This is the product of every transform from the display object up to the
+/// root of its display tree. An object that isn't on the stage still has a
+/// concatenated matrix: that of its detached subtree, which for an object
+/// with no parent is just its own matrix.
+pub fn concatenated_matrix<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let matrix = dobj.local_to_global_matrix();
+
+        return Ok(matrix::matrix_to_object(matrix, activation)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `pixelBounds`'s getter.
+pub fn pixel_bounds<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let bounds = dobj.world_bounds();
+
+        return Ok(rectangle::bounding_box_to_object(&bounds, activation)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Transform`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.geom"), "Transform"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "matrix"),
+        Method::from_builtin(matrix),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "matrix"),
+        Method::from_builtin(set_matrix),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "matrix3D"),
+        Method::from_builtin(matrix_3d),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "matrix3D"),
+        Method::from_builtin(set_matrix_3d),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "perspectiveProjection"),
+        Method::from_builtin(perspective_projection),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "perspectiveProjection"),
+        Method::from_builtin(set_perspective_projection),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "getRelativeMatrix3D"),
+        Method::from_builtin(get_relative_matrix_3d),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "concatenatedMatrix"),
+        Method::from_builtin(concatenated_matrix),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "pixelBounds"),
+        Method::from_builtin(pixel_bounds),
+    ));
+
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::test_utils::with_avm2;
+    use crate::display_object::{MovieClip, TDisplayObjectContainer};
+    use crate::prelude::*;
+    use crate::tag_utils::{SwfMovie, SwfSlice};
+    use std::sync::Arc;
+
+    fn translate_and_scale(tx: f64, ty: f64, scale: f32) -> Matrix {
+        Matrix {
+            a: scale,
+            d: scale,
+            tx: Twips::from_pixels(tx),
+            ty: Twips::from_pixels(ty),
+            ..Default::default()
+        }
+    }
+
+    fn translate_and_scale_x(tx: f64, ty: f64, scale: f32) -> Matrix {
+        Matrix {
+            a: scale,
+            tx: Twips::from_pixels(tx),
+            ty: Twips::from_pixels(ty),
+            ..Default::default()
+        }
+    }
+
+    fn concatenated<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        dobj: DisplayObject<'gc>,
+    ) -> Matrix {
+        let transform = transform_for_display_object(activation, dobj);
+        let object = concatenated_matrix(activation, Some(transform), &[])
+            .unwrap()
+            .coerce_to_object(activation)
+            .unwrap();
+
+        matrix::object_to_matrix(object, activation).unwrap()
+    }
+
+    #[test]
+    fn nested_child_concatenates_ancestor_matrices() {
+        with_avm2(|activation| {
+            let movie = Arc::new(SwfMovie::empty(10));
+            let mc = activation.context.gc_context;
+            let parent = MovieClip::new(SwfSlice::empty(movie.clone()), mc);
+            let child = MovieClip::new(SwfSlice::empty(movie), mc);
+
+            let parent_matrix = translate_and_scale(10.0, 20.0, 2.0);
+            let child_matrix = translate_and_scale(5.0, -5.0, 0.5);
+            parent.set_matrix(mc, &parent_matrix);
+            child.set_matrix(mc, &child_matrix);
+
+            // A detached object's concatenated matrix is its own matrix.
+            assert_eq!(concatenated(activation, child.into()), child_matrix);
+
+            let mut parent_container = parent.as_container().unwrap();
+            parent_container.insert_at_index(&mut activation.context, child.into(), 0);
+
+            assert_eq!(
+                concatenated(activation, child.into()),
+                parent_matrix * child_matrix
+            );
+        });
+    }
+
+    #[test]
+    fn matrix_3d_round_trips_and_replaces_matrix() {
+        with_avm2(|activation| {
+            let movie = Arc::new(SwfMovie::empty(10));
+            let mc = activation.context.gc_context;
+            let parent = MovieClip::new(SwfSlice::empty(movie.clone()), mc);
+            let child = MovieClip::new(SwfSlice::empty(movie), mc);
+            let parent_transform = transform_for_display_object(activation, parent.into());
+            let child_transform = transform_for_display_object(activation, child.into());
+
+            let mut raw = matrix3d::IDENTITY;
+            raw[0] = 2.0;
+            raw[10] = 4.0;
+            raw[12] = 10.0;
+            raw[13] = -5.0;
+            raw[14] = 30.0;
+            let object = matrix3d::matrix3d_to_object(&raw, activation).unwrap();
+            set_matrix_3d(activation, Some(child_transform), &[object.into()]).unwrap();
+
+            let read_back = matrix_3d(activation, Some(child_transform), &[])
+                .unwrap()
+                .coerce_to_object(activation)
+                .unwrap();
+            assert_eq!(
+                matrix3d::object_to_matrix3d(read_back, activation).unwrap(),
+                raw
+            );
+
+            // The 2D matrix is hidden, but holds the projection that gets drawn.
+            assert_eq!(
+                matrix(activation, Some(child_transform), &[]).unwrap(),
+                Value::Null
+            );
+            assert_eq!(*child.matrix(), translate_and_scale_x(10.0, -5.0, 2.0));
+
+            // Relative to the parent, only the child's own transform remains.
+            let mut parent_container = parent.as_container().unwrap();
+            parent_container.insert_at_index(&mut activation.context, child.into(), 0);
+            parent.set_matrix(mc, &translate_and_scale(7.0, 3.0, 0.5));
+
+            let movieclip_proto = activation.context.avm2.prototypes().movieclip;
+            let parent_object = StageObject::for_display_object(mc, parent.into(), movieclip_proto);
+            let relative =
+                get_relative_matrix_3d(activation, Some(child_transform), &[parent_object.into()])
+                    .unwrap()
+                    .coerce_to_object(activation)
+                    .unwrap();
+            let relative = matrix3d::object_to_matrix3d(relative, activation).unwrap();
+            for (actual, expected) in relative.iter().zip(raw.iter()) {
+                assert!((actual - expected).abs() < 1e-9, "{:?}", relative);
+            }
+
+            // Setting a 2D matrix drops the 3D one.
+            let matrix_2d = translate_and_scale(1.0, 2.0, 3.0);
+            let object = matrix::matrix_to_object(matrix_2d, activation).unwrap();
+            set_matrix(activation, Some(child_transform), &[object.into()]).unwrap();
+            assert_eq!(
+                matrix_3d(activation, Some(child_transform), &[]).unwrap(),
+                Value::Null
+            );
+            assert!(child.matrix_3d().is_none());
+            assert_eq!(*child.matrix(), matrix_2d);
+
+            assert_eq!(
+                matrix_3d(activation, Some(parent_transform), &[]).unwrap(),
+                Value::Null
+            );
+        });
+    }
+
+    /// Give `transform` a perspective with the given field of view, looking
+    /// at `center`.
+    fn set_field_of_view<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        transform: Object<'gc>,
+        field_of_view: f64,
+        center: (f64, f64),
+    ) -> Object<'gc> {
+        let projection = perspectiveprojection::perspective_projection_to_object(
+            &[
+                perspectiveprojection::DEFAULT_FIELD_OF_VIEW,
+                center.0,
+                center.1,
+            ],
+            activation,
+        )
+        .unwrap();
+        perspectiveprojection::set_field_of_view(
+            activation,
+            Some(projection),
+            &[field_of_view.into()],
+        )
+        .unwrap();
+        set_perspective_projection(activation, Some(transform), &[projection.into()]).unwrap();
+
+        projection
+    }
+
+    #[test]
+    fn field_of_view_changes_projected_position() {
+        with_avm2(|activation| {
+            activation.context.stage_size = (Twips::from_pixels(400.0), Twips::from_pixels(300.0));
+
+            let movie = Arc::new(SwfMovie::empty(10));
+            let mc = activation.context.gc_context;
+            let parent = MovieClip::new(SwfSlice::empty(movie.clone()), mc);
+            let child = MovieClip::new(SwfSlice::empty(movie), mc);
+            let mut parent_container = parent.as_container().unwrap();
+            parent_container.insert_at_index(&mut activation.context, child.into(), 0);
+            let parent_transform = transform_for_display_object(activation, parent.into());
+            let child_transform = transform_for_display_object(activation, child.into());
+
+            assert_eq!(
+                perspective_projection(activation, Some(parent_transform), &[]).unwrap(),
+                Value::Null
+            );
+
+            // 100 pixels right of the center, and 100 pixels into the screen.
+            let mut raw = matrix3d::IDENTITY;
+            raw[12] = 300.0;
+            raw[13] = 150.0;
+            raw[14] = 100.0;
+            let object = matrix3d::matrix3d_to_object(&raw, activation).unwrap();
+            set_matrix_3d(activation, Some(child_transform), &[object.into()]).unwrap();
+            assert_eq!(child.matrix().tx, Twips::from_pixels(300.0));
+
+            let offset_from_center = |dobj: MovieClip<'_>| dobj.matrix().tx.to_pixels() - 200.0;
+
+            let projection = set_field_of_view(activation, parent_transform, 55.0, (200.0, 150.0));
+            let focal_length =
+                perspectiveprojection::get_focal_length(activation, Some(projection), &[])
+                    .unwrap()
+                    .coerce_to_number(activation)
+                    .unwrap();
+            let expected = 100.0 * focal_length / (focal_length + 100.0);
+            let narrow = offset_from_center(child);
+            assert!((narrow - expected).abs() < 0.05, "{}", narrow);
+            assert_eq!(child.matrix().ty, Twips::from_pixels(150.0));
+
+            // A wider view shortens the focal length, pulling the child
+            // further towards the center.
+            set_field_of_view(activation, parent_transform, 120.0, (200.0, 150.0));
+            let wide = offset_from_center(child);
+            assert!(wide > 0.0 && wide < narrow, "{} {}", wide, narrow);
+
+            // Right up against 180 degrees the focal length is tiny but still
+            // positive, so the child is drawn almost at the center.
+            let projection = set_field_of_view(activation, parent_transform, 180.0, (200.0, 150.0));
+            let field_of_view =
+                perspectiveprojection::get_field_of_view(activation, Some(projection), &[])
+                    .unwrap()
+                    .coerce_to_number(activation)
+                    .unwrap();
+            assert!(field_of_view < 180.0);
+            let focal_length =
+                perspectiveprojection::get_focal_length(activation, Some(projection), &[])
+                    .unwrap()
+                    .coerce_to_number(activation)
+                    .unwrap();
+            assert!(focal_length > 0.0 && focal_length.is_finite());
+            let degenerate = offset_from_center(child);
+            assert!(degenerate >= 0.0 && degenerate < 0.1, "{}", degenerate);
+
+            // Taking the perspective away projects orthographically again.
+            set_perspective_projection(activation, Some(parent_transform), &[Value::Null]).unwrap();
+            assert_eq!(child.matrix().tx, Twips::from_pixels(300.0));
+        });
+    }
+
+    fn read_components<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        transform: Object<'gc>,
+    ) -> [f64; 6] {
+        let object = matrix(activation, Some(transform), &[])
+            .unwrap()
+            .coerce_to_object(activation)
+            .unwrap();
+
+        matrix::object_to_components(object, activation).unwrap()
+    }
+
+    #[test]
+    fn sheared_matrix_round_trips_exactly() {
+        with_avm2(|activation| {
+            let movie = Arc::new(SwfMovie::empty(10));
+            let mc = activation.context.gc_context;
+            let clip = MovieClip::new(SwfSlice::empty(movie), mc);
+            let transform = transform_for_display_object(activation, clip.into());
+
+            // None of these are exact in the precision a display object keeps.
+            let components = [1.1, 0.3, -0.7, 0.9, 10.123, -3.3];
+            let object = matrix::components_to_object(components, activation).unwrap();
+            set_matrix(activation, Some(transform), &[object.into()]).unwrap();
+
+            assert_eq!(read_components(activation, transform), components);
+
+            // The decomposed properties come from the stored matrix, and
+            // reading them doesn't disturb it.
+            let stored = *clip.matrix();
+            let (a, b) = (f64::from(stored.a), f64::from(stored.b));
+            assert_eq!(clip.x(), 10.1);
+            assert_eq!(clip.scale_x(mc).into_unit(), (a * a + b * b).sqrt());
+            assert_eq!(f64::from(clip.rotation(mc)), f64::atan2(b, a).to_degrees());
+            assert_eq!(read_components(activation, transform), components);
+            assert_eq!(*clip.matrix(), stored);
+
+            // Once the matrix changes, its own components are returned, with
+            // the shear intact.
+            clip.set_x(mc, 20.0);
+            assert_eq!(
+                read_components(activation, transform),
+                [
+                    a,
+                    b,
+                    stored.c.into(),
+                    stored.d.into(),
+                    20.0,
+                    stored.ty.to_pixels()
+                ]
+            );
+        });
+    }
+}
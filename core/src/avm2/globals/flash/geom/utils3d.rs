@@ -0,0 +1,439 @@
+//! `flash.geom.Utils3D` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::flash::geom::matrix3d::{self, RawMatrix3D};
+use crate::avm2::globals::flash::geom::vector3d;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::Object;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// A 3D direction or position, without a `w` component.
+type Vec3 = [f64; 3];
+
+/// A rotation, stored as its three columns.
+type Rotation = [Vec3; 3];
+
+/// A rotation as a unit quaternion, stored as `[w, x, y, z]`.
+type Quaternion = [f64; 4];
+
+/// Implements `flash.geom.Utils3D`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.geom.Utils3D`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+fn sub(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn scale(a: Vec3, s: f64) -> Vec3 {
+    [a[0] * s, a[1] * s, a[2] * s]
+}
+
+fn dot(a: Vec3, b: Vec3) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: Vec3, b: Vec3) -> Vec3 {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+/// Scale a vector to unit length, unless it has no length to scale.
+fn normalize(a: Vec3) -> Option<Vec3> {
+    let length = dot(a, a).sqrt();
+    if length == 0.0 || !length.is_finite() {
+        return None;
+    }
+
+    Some(scale(a, 1.0 / length))
+}
+
+/// Build an orthonormal basis whose first axis is `forward`, and whose
+/// second axis is as close to `up` as it can be.
+///
+/// If `up` is parallel to `forward`, some other perpendicular axis is used.
+fn basis(forward: Vec3, up: Vec3) -> Rotation {
+    let up = normalize(sub(up, scale(forward, dot(up, forward))))
+        .or_else(|| normalize(cross(forward, [1.0, 0.0, 0.0])))
+        .or_else(|| normalize(cross(forward, [0.0, 1.0, 0.0])))
+        .unwrap_or([0.0, 0.0, 1.0]);
+
+    [forward, up, cross(forward, up)]
+}
+
+fn quaternion_from_rotation(m: &Rotation) -> Quaternion {
+    let at = |row: usize, column: usize| m[column][row];
+    let trace = at(0, 0) + at(1, 1) + at(2, 2);
+
+    if trace > 0.0 {
+        let s = (trace + 1.0).sqrt() * 2.0;
+        [
+            s / 4.0,
+            (at(2, 1) - at(1, 2)) / s,
+            (at(0, 2) - at(2, 0)) / s,
+            (at(1, 0) - at(0, 1)) / s,
+        ]
+    } else if at(0, 0) > at(1, 1) && at(0, 0) > at(2, 2) {
+        let s = (1.0 + at(0, 0) - at(1, 1) - at(2, 2)).sqrt() * 2.0;
+        [
+            (at(2, 1) - at(1, 2)) / s,
+            s / 4.0,
+            (at(0, 1) + at(1, 0)) / s,
+            (at(0, 2) + at(2, 0)) / s,
+        ]
+    } else if at(1, 1) > at(2, 2) {
+        let s = (1.0 + at(1, 1) - at(0, 0) - at(2, 2)).sqrt() * 2.0;
+        [
+            (at(0, 2) - at(2, 0)) / s,
+            (at(0, 1) + at(1, 0)) / s,
+            s / 4.0,
+            (at(1, 2) + at(2, 1)) / s,
+        ]
+    } else {
+        let s = (1.0 + at(2, 2) - at(0, 0) - at(1, 1)).sqrt() * 2.0;
+        [
+            (at(1, 0) - at(0, 1)) / s,
+            (at(0, 2) + at(2, 0)) / s,
+            (at(1, 2) + at(2, 1)) / s,
+            s / 4.0,
+        ]
+    }
+}
+
+fn rotation_from_quaternion(q: &Quaternion) -> Rotation {
+    let [w, x, y, z] = *q;
+
+    [
+        [
+            1.0 - 2.0 * (y * y + z * z),
+            2.0 * (x * y + z * w),
+            2.0 * (x * z - y * w),
+        ],
+        [
+            2.0 * (x * y - z * w),
+            1.0 - 2.0 * (x * x + z * z),
+            2.0 * (y * z + x * w),
+        ],
+        [
+            2.0 * (x * z + y * w),
+            2.0 * (y * z - x * w),
+            1.0 - 2.0 * (x * x + y * y),
+        ],
+    ]
+}
+
+/// Interpolate between two rotations along the shortest arc.
+fn slerp(a: &Quaternion, b: &Quaternion, t: f64) -> Quaternion {
+    let mut b = *b;
+    let mut cos = (0..4).map(|i| a[i] * b[i]).sum::<f64>();
+    if cos < 0.0 {
+        b.iter_mut().for_each(|v| *v = -*v);
+        cos = -cos;
+    }
+
+    let (wa, wb) = if cos > 0.9995 {
+        (1.0 - t, t)
+    } else {
+        let angle = cos.acos();
+        let sin = angle.sin();
+        (((1.0 - t) * angle).sin() / sin, (t * angle).sin() / sin)
+    };
+
+    let mut q = [0.0; 4];
+    for i in 0..4 {
+        q[i] = a[i] * wa + b[i] * wb;
+    }
+
+    let length = (0..4).map(|i| q[i] * q[i]).sum::<f64>().sqrt();
+    q.iter_mut().for_each(|v| *v /= length);
+    q
+}
+
+/// Read an optional `Vector3D` argument's direction.
+fn direction_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    arg: Option<&Value<'gc>>,
+    default: Vec3,
+) -> Result<Vec3, Error> {
+    match arg.cloned().unwrap_or(Value::Null) {
+        Value::Undefined | Value::Null => Ok(default),
+        value => {
+            let object = value.coerce_to_object(activation)?;
+            let [x, y, z, _] = vector3d::object_to_vector3d(object, activation)?;
+
+            Ok(normalize([x, y, z]).unwrap_or(default))
+        }
+    }
+}
+
+/// Implements `Utils3D.projectVector`.
+///
+/// The vector is transformed as a point, and then divided by the `w` that the
+/// projection gave it. Projections that give a `w` of zero would divide by
+/// zero, so the transformed point is returned without the divide instead.
+pub fn project_vector<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let matrix = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_object(activation)?;
+    let matrix = matrix3d::object_to_matrix3d(matrix, activation)?;
+    let vector = args
+        .get(1)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_object(activation)?;
+    let [x, y, z, _] = vector3d::object_to_vector3d(vector, activation)?;
+
+    let mut projected = [0.0; 4];
+    for (row, value) in projected.iter_mut().enumerate() {
+        *value = matrix[row] * x + matrix[4 + row] * y + matrix[8 + row] * z + matrix[12 + row];
+    }
+
+    let w = projected[3];
+    if w != 0.0 && w.is_finite() {
+        for value in projected.iter_mut().take(3) {
+            *value /= w;
+        }
+    }
+
+    Ok(vector3d::vector3d_to_object(&projected, activation)?.into())
+}
+
+/// Turn a matrix a fraction of the way towards facing `pos`.
+///
+/// The matrix's `at` axis is aimed at the point, with its `up` axis kept as
+/// near to the world's `up` direction as it can be. Its scale and position
+/// are kept as they are.
+fn point_towards_raw(
+    percent: f64,
+    raw: &RawMatrix3D,
+    pos: Vec3,
+    at: Vec3,
+    up: Vec3,
+) -> RawMatrix3D {
+    let translation = [raw[12], raw[13], raw[14]];
+    let columns = [
+        [raw[0], raw[1], raw[2]],
+        [raw[4], raw[5], raw[6]],
+        [raw[8], raw[9], raw[10]],
+    ];
+    let scales = [
+        dot(columns[0], columns[0]).sqrt(),
+        dot(columns[1], columns[1]).sqrt(),
+        dot(columns[2], columns[2]).sqrt(),
+    ];
+
+    let direction = match normalize(sub(pos, translation)) {
+        Some(direction) => direction,
+        None => return *raw,
+    };
+    if scales.iter().any(|s| *s == 0.0 || !s.is_finite()) {
+        return *raw;
+    }
+
+    let current = [
+        scale(columns[0], 1.0 / scales[0]),
+        scale(columns[1], 1.0 / scales[1]),
+        scale(columns[2], 1.0 / scales[2]),
+    ];
+
+    // The rotation that carries the local `at`/`up` basis onto the world one.
+    let local = basis(at, up);
+    let world = basis(direction, up);
+    let mut target = [[0.0; 3]; 3];
+    for (column, target_column) in target.iter_mut().enumerate() {
+        for (row, value) in target_column.iter_mut().enumerate() {
+            *value = (0..3).map(|k| world[k][row] * local[k][column]).sum();
+        }
+    }
+
+    let rotation = rotation_from_quaternion(&slerp(
+        &quaternion_from_rotation(&current),
+        &quaternion_from_rotation(&target),
+        percent,
+    ));
+
+    let mut result = *raw;
+    for (column, axis) in rotation.iter().enumerate() {
+        let axis = scale(*axis, scales[column]);
+        result[column * 4..column * 4 + 3].copy_from_slice(&axis);
+    }
+
+    result
+}
+
+/// Implements `Utils3D.pointTowards`.
+///
+/// `at` defaults to the negative Z axis and `up` to the negative Y axis, as
+/// in Flash Player.
+pub fn point_towards<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let percent = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_number(activation)?;
+    let matrix = args
+        .get(1)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_object(activation)?;
+    let matrix = matrix3d::object_to_matrix3d(matrix, activation)?;
+    let pos = args
+        .get(2)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_object(activation)?;
+    let [x, y, z, _] = vector3d::object_to_vector3d(pos, activation)?;
+    let at = direction_arg(activation, args.get(3), [0.0, 0.0, -1.0])?;
+    let up = direction_arg(activation, args.get(4), [0.0, -1.0, 0.0])?;
+
+    let result = point_towards_raw(percent, &matrix, [x, y, z], at, up);
+
+    Ok(matrix3d::matrix3d_to_object(&result, activation)?.into())
+}
+
+/// Construct `Utils3D`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.geom"), "Utils3D"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    write.define_class_trait(Trait::from_method(
+        QName::new(Namespace::public(), "projectVector"),
+        Method::from_builtin(project_vector),
+    ));
+    write.define_class_trait(Trait::from_method(
+        QName::new(Namespace::public(), "pointTowards"),
+        Method::from_builtin(point_towards),
+    ));
+
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::test_utils::with_avm2;
+
+    fn assert_close(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-9, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    fn project<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        matrix: &RawMatrix3D,
+        vector: &vector3d::RawVector3D,
+    ) -> vector3d::RawVector3D {
+        let matrix = matrix3d::matrix3d_to_object(matrix, activation).unwrap();
+        let vector = vector3d::vector3d_to_object(vector, activation).unwrap();
+        let projected = project_vector(activation, None, &[matrix.into(), vector.into()])
+            .unwrap()
+            .coerce_to_object(activation)
+            .unwrap();
+
+        vector3d::object_to_vector3d(projected, activation).unwrap()
+    }
+
+    #[test]
+    fn project_vector_divides_by_depth() {
+        with_avm2(|activation| {
+            // Doubles X and Y, and uses Z as the projection depth.
+            let mut projection = matrix3d::IDENTITY;
+            projection[0] = 2.0;
+            projection[5] = 2.0;
+            projection[11] = 1.0;
+            projection[15] = 0.0;
+
+            let projected = project(activation, &projection, &[2.0, 4.0, 8.0, 1.0]);
+            assert_close(&projected, &[0.5, 1.0, 1.0, 8.0]);
+
+            // A point at depth zero can't be divided through.
+            let projected = project(activation, &projection, &[2.0, 4.0, 0.0, 1.0]);
+            assert_close(&projected, &[4.0, 8.0, 0.0, 0.0]);
+        });
+    }
+
+    #[test]
+    fn point_towards_aims_the_at_axis() {
+        let mut raw = matrix3d::IDENTITY;
+        raw[12] = 5.0;
+
+        // Already facing the target, so nothing changes.
+        let facing = point_towards_raw(
+            1.0,
+            &raw,
+            [5.0, 0.0, -10.0],
+            [0.0, 0.0, -1.0],
+            [0.0, -1.0, 0.0],
+        );
+        assert_close(&facing, &raw);
+
+        let turned = point_towards_raw(
+            1.0,
+            &raw,
+            [15.0, 0.0, 0.0],
+            [0.0, 0.0, -1.0],
+            [0.0, -1.0, 0.0],
+        );
+        let new_at = [-turned[8], -turned[9], -turned[10]];
+        assert_close(&new_at, &[1.0, 0.0, 0.0]);
+        assert_close(&turned[12..16], &[5.0, 0.0, 0.0, 1.0]);
+
+        // Halfway there is a 45 degree turn.
+        let half = point_towards_raw(
+            0.5,
+            &raw,
+            [15.0, 0.0, 0.0],
+            [0.0, 0.0, -1.0],
+            [0.0, -1.0, 0.0],
+        );
+        let half_at = [-half[8], -half[9], -half[10]];
+        let diagonal = std::f64::consts::FRAC_1_SQRT_2;
+        assert_close(&half_at, &[diagonal, 0.0, -diagonal]);
+    }
+}
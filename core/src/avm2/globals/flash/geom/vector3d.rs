@@ -0,0 +1,113 @@
+//! `flash.geom.Vector3D` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// The names of a vector's components, in constructor order.
+const COMPONENTS: [&str; 4] = ["x", "y", "z", "w"];
+
+/// The components of a 3D vector, with `w` last.
+pub type RawVector3D = [f64; 4];
+
+/// Implements `flash.geom.Vector3D`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        for (i, name) in COMPONENTS.iter().enumerate() {
+            let value = args
+                .get(i)
+                .cloned()
+                .unwrap_or_else(|| 0.0.into())
+                .coerce_to_number(activation)?;
+
+            this.set_property(
+                this,
+                &QName::new(Namespace::public(), *name),
+                value.into(),
+                activation,
+            )?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.geom.Vector3D`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct a new `Vector3D` object with the given components.
+pub fn vector3d_to_object<'gc>(
+    raw: &RawVector3D,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Object<'gc>, Error> {
+    let args: Vec<Value<'gc>> = raw.iter().map(|v| (*v).into()).collect();
+    let mut proto = activation.context.avm2.prototypes().vector3d;
+    let object = proto.construct(activation, &args)?;
+    let constr = proto
+        .get_property(
+            proto,
+            &QName::new(Namespace::public(), "constructor"),
+            activation,
+        )?
+        .coerce_to_object(activation)?;
+
+    constr.call(Some(object), &args, activation, Some(proto))?;
+
+    Ok(object)
+}
+
+/// Read the components of a `Vector3D` object.
+pub fn object_to_vector3d<'gc>(
+    mut object: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<RawVector3D, Error> {
+    let mut raw = [0.0; 4];
+    for (component, name) in raw.iter_mut().zip(COMPONENTS.iter()) {
+        *component = object
+            .get_property(object, &QName::new(Namespace::public(), *name), activation)?
+            .coerce_to_number(activation)?;
+    }
+
+    Ok(raw)
+}
+
+/// Construct `Vector3D`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.geom"), "Vector3D"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    for name in COMPONENTS.iter() {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public(), *name),
+            QName::new(Namespace::public(), "Number").into(),
+            None,
+        ));
+    }
+
+    class
+}
@@ -4,19 +4,43 @@ use crate::avm2::activation::Activation;
 use crate::avm2::class::{Class, ClassAttributes};
 use crate::avm2::method::Method;
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::Object;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
+use crate::display_object::{TDisplayObject, Video};
 use gc_arena::{GcCell, MutationContext};
 
 /// Implements `flash.media.Video`'s instance constructor.
 pub fn instance_init<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
     if let Some(this) = this {
         activation.super_init(this, &[])?;
+
+        if this.as_display_object().is_none() {
+            let width = args
+                .get(0)
+                .cloned()
+                .unwrap_or_else(|| 320.into())
+                .coerce_to_i32(activation)?;
+            let height = args
+                .get(1)
+                .cloned()
+                .unwrap_or_else(|| 240.into())
+                .coerce_to_i32(activation)?;
+
+            let new_do = Video::new(
+                activation.context.gc_context,
+                width.clamp(0, u16::MAX.into()) as u16,
+                height.clamp(0, u16::MAX.into()) as u16,
+                Some(this),
+            );
+
+            this.init_display_object(activation.context.gc_context, new_do.into());
+        }
     }
 
     Ok(Value::Undefined)
@@ -31,11 +55,123 @@ pub fn class_init<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `Video.attachNetStream`.
+///
+/// Attaching `null` detaches the current stream and blanks the video.
+pub fn attach_net_stream<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(video) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_video())
+    {
+        let stream = match args.get(0).cloned().unwrap_or(Value::Null) {
+            Value::Undefined | Value::Null => None,
+            stream => Some(stream.coerce_to_object(activation)?),
+        };
+
+        video.attach_net_stream(activation.context.gc_context, stream);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Video.clear`.
+pub fn clear<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(video) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_video())
+    {
+        video.clear(activation.context.gc_context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `smoothing`'s getter.
+pub fn smoothing<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(video) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_video())
+    {
+        return Ok(video.smoothing().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `smoothing`'s setter.
+pub fn set_smoothing<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(video) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_video())
+    {
+        let smoothing = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+
+        video.set_smoothing(activation.context.gc_context, smoothing);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `videoWidth`'s getter.
+///
+/// This is the width of the frame being shown, which is zero until the
+/// attached stream has decoded one.
+pub fn video_width<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(video) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_video())
+    {
+        return Ok(video.frame_size().0.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `videoHeight`'s getter.
+pub fn video_height<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(video) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_video())
+    {
+        return Ok(video.frame_size().1.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Construct `Video`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
     let class = Class::new(
         QName::new(Namespace::package("flash.media"), "Video"),
-        Some(QName::new(Namespace::package("flash.media"), "DisplayObject").into()),
+        Some(QName::new(Namespace::package("flash.display"), "DisplayObject").into()),
         Method::from_builtin(instance_init),
         Method::from_builtin(class_init),
         mc,
@@ -45,5 +181,145 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
 
     write.set_attributes(ClassAttributes::SEALED);
 
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "attachNetStream"),
+        Method::from_builtin(attach_net_stream),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "clear"),
+        Method::from_builtin(clear),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "smoothing"),
+        Method::from_builtin(smoothing),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "smoothing"),
+        Method::from_builtin(set_smoothing),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "videoWidth"),
+        Method::from_builtin(video_width),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "videoHeight"),
+        Method::from_builtin(video_height),
+    ));
+
     class
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::globals::flash::display::displayobjectcontainer::add_child;
+    use crate::avm2::object::{ScriptObject, StageObject};
+    use crate::avm2::test_utils::with_avm2;
+
+    /// Hand a blank frame of `stream` to `video`, as a decoder would, if the
+    /// stream is the one attached to it.
+    fn present_frame<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        video: Object<'gc>,
+        stream: Object<'gc>,
+        width: u16,
+        height: u16,
+    ) {
+        let video = video.as_display_object().unwrap().as_video().unwrap();
+        if matches!(video.net_stream(), Some(attached) if Object::ptr_eq(attached, stream)) {
+            let rgba = vec![0; usize::from(width) * usize::from(height) * 4];
+            video
+                .show_frame(&mut activation.context, width, height, rgba)
+                .unwrap();
+        }
+    }
+
+    fn frame_size<'gc>(activation: &mut Activation<'_, 'gc, '_>, this: Object<'gc>) -> (i32, i32) {
+        let width = video_width(activation, Some(this), &[]).unwrap();
+        let height = video_height(activation, Some(this), &[]).unwrap();
+
+        (
+            width.coerce_to_i32(activation).unwrap(),
+            height.coerce_to_i32(activation).unwrap(),
+        )
+    }
+
+    fn construct_video<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        args: &[Value<'gc>],
+    ) -> Result<Object<'gc>, Error> {
+        let mut ctor = activation
+            .avm2()
+            .global_domain()
+            .get_defined_value(
+                activation,
+                QName::new(Namespace::package("flash.media"), "Video"),
+            )?
+            .coerce_to_object(activation)?;
+        let proto = ctor
+            .get_property(
+                ctor,
+                &QName::new(Namespace::public(), "prototype"),
+                activation,
+            )?
+            .coerce_to_object(activation)?;
+        let object = proto.construct(activation, args)?;
+        ctor.call(Some(object), args, activation, object.proto())?;
+
+        Ok(object)
+    }
+
+    #[test]
+    fn attached_stream_frames_set_the_video_size() {
+        with_avm2(|activation| {
+            let mc = activation.context.gc_context;
+            let root = *activation.context.levels.get(&0).unwrap();
+            let root = Object::from(StageObject::for_display_object(
+                mc,
+                root,
+                activation.avm2().prototypes().movieclip,
+            ));
+            let this = construct_video(activation, &[16.into(), 8.into()]).unwrap();
+            add_child(activation, Some(root), &[this.into()]).unwrap();
+
+            let video = this.as_display_object().unwrap();
+            assert_eq!(video.width(), 16.0);
+            assert_eq!(video.height(), 8.0);
+
+            // Nothing is attached yet, so the stream's frames go unseen.
+            let stream = ScriptObject::object(mc, activation.avm2().prototypes().object);
+            present_frame(activation, this, stream, 4, 2);
+            assert_eq!(frame_size(activation, this), (0, 0));
+
+            attach_net_stream(activation, Some(this), &[stream.into()]).unwrap();
+            present_frame(activation, this, stream, 4, 2);
+            assert_eq!(frame_size(activation, this), (4, 2));
+            present_frame(activation, this, stream, 6, 3);
+            assert_eq!(frame_size(activation, this), (6, 3));
+
+            // Frames are shown at the video's own size, whatever theirs is.
+            assert_eq!(video.width(), 16.0);
+
+            clear(activation, Some(this), &[]).unwrap();
+            assert_eq!(frame_size(activation, this), (0, 0));
+
+            present_frame(activation, this, stream, 4, 2);
+            attach_net_stream(activation, Some(this), &[Value::Null]).unwrap();
+            assert_eq!(frame_size(activation, this), (0, 0));
+
+            set_smoothing(activation, Some(this), &[true.into()]).unwrap();
+            assert_eq!(smoothing(activation, Some(this), &[]).unwrap(), true.into());
+        });
+    }
+
+    #[test]
+    fn out_of_range_sizes_are_clamped() {
+        with_avm2(|activation| {
+            let this = construct_video(activation, &[(-5).into(), 70000.into()]).unwrap();
+            let video = this.as_display_object().unwrap();
+
+            assert_eq!(video.width(), 0.0);
+            assert_eq!(video.height(), f64::from(u16::MAX));
+        });
+    }
+}
@@ -0,0 +1,197 @@
+//! `flash.net` namespace
+
+use crate::avm2::activation::Activation;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::backend::navigator::{NavigationMethod, RequestOptions};
+
+pub mod netconnection;
+pub mod netstream;
+pub mod responder;
+pub mod sharedobject;
+pub mod urlstream;
+
+/// Read the URL and request options out of a `URLRequest`, or anything else
+/// with the same properties.
+///
+/// The request's `data` is sent as the body of a `POST` or `PUT`, or appended
+/// to the URL's query for any other method.
+pub fn request_options<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut request: Object<'gc>,
+) -> Result<(String, RequestOptions), Error> {
+    let mut property = |name, activation: &mut Activation<'_, 'gc, '_>| {
+        request.get_property(request, &QName::new(Namespace::public(), name), activation)
+    };
+
+    let mut url = property("url", activation)?
+        .coerce_to_string(activation)?
+        .to_string();
+    let method = match property("method", activation)? {
+        Value::Undefined | Value::Null => NavigationMethod::Get,
+        method => NavigationMethod::from_url_request_method(&method.coerce_to_string(activation)?)
+            .unwrap_or(NavigationMethod::Get),
+    };
+    let data = match property("data", activation)? {
+        Value::Undefined | Value::Null => None,
+        Value::Object(data) if data.as_bytearray().is_some() => {
+            data.as_bytearray().map(|data| data.bytes().to_vec())
+        }
+        data => Some(data.coerce_to_string(activation)?.as_bytes().to_vec()),
+    };
+    let content_type = match property("contentType", activation)? {
+        Value::Undefined | Value::Null => "application/x-www-form-urlencoded".to_string(),
+        content_type => content_type.coerce_to_string(activation)?.to_string(),
+    };
+
+    if method.has_body() {
+        let body = data.map(|data| (data, content_type));
+        return Ok((url, RequestOptions::with_method(method, body)));
+    }
+
+    if let Some(data) = data.filter(|data| !data.is_empty()) {
+        url.push(if url.contains('?') { '&' } else { '?' });
+        url.push_str(&String::from_utf8_lossy(&data));
+    }
+
+    Ok((url, RequestOptions::with_method(method, None)))
+}
+
+/// Implements `flash.net.sendToURL`.
+///
+/// The request is made through the navigator and then forgotten: nothing
+/// waits for the response, and any error in making it is ignored.
+pub fn send_to_url<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let request = match args.get(0).cloned().unwrap_or(Value::Undefined) {
+        Value::Object(request) => request,
+        _ => return Err("TypeError: Error #2007: Parameter request must be non-null.".into()),
+    };
+    let (url, options) = request_options(activation, request)?;
+
+    let fetch = activation.context.navigator.fetch(&url, options);
+    activation
+        .context
+        .navigator
+        .spawn_future(Box::pin(async move {
+            if let Err(e) = fetch.await {
+                log::warn!("sendToURL request failed: {}", e);
+            }
+
+            Ok(())
+        }));
+
+    Ok(Value::Undefined)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::object::ScriptObject;
+    use crate::avm2::test_utils::{set, Fetch, TestNavigator, TestPlayer};
+
+    /// Call `sendToURL` with a request that has the given properties, and
+    /// return what the navigator was asked to fetch.
+    fn send(properties: &'static [(&'static str, &'static str)]) -> Vec<Fetch> {
+        let mut fetches = None;
+        let player = TestPlayer::with_navigator(|channel| {
+            let navigator = TestNavigator::new(channel);
+            fetches = Some(navigator.fetches());
+            Box::new(navigator)
+        });
+
+        player.update(|activation| {
+            let request = ScriptObject::object(
+                activation.context.gc_context,
+                activation.avm2().prototypes().object,
+            );
+            for (name, value) in properties {
+                set(activation, request, name, (*value).into());
+            }
+
+            assert_eq!(
+                send_to_url(activation, None, &[request.into()]).unwrap(),
+                Value::Undefined
+            );
+        });
+
+        fetches.unwrap().take()
+    }
+
+    #[test]
+    fn get_requests_carry_data_in_the_query() {
+        assert_eq!(
+            send(&[
+                ("url", "http://example.com/beacon?id=1"),
+                ("data", "event=load")
+            ]),
+            vec![(
+                "http://example.com/beacon?id=1&event=load".to_string(),
+                "GET",
+                None
+            )]
+        );
+    }
+
+    #[test]
+    fn post_requests_carry_data_in_the_body() {
+        assert_eq!(
+            send(&[
+                ("url", "http://example.com/log"),
+                ("method", "POST"),
+                ("data", "level=info&message=hi"),
+                ("contentType", "text/plain"),
+            ]),
+            vec![(
+                "http://example.com/log".to_string(),
+                "POST",
+                Some((b"level=info&message=hi".to_vec(), "text/plain".to_string()))
+            )]
+        );
+    }
+
+    #[test]
+    fn put_requests_carry_data_in_the_body() {
+        assert_eq!(
+            send(&[
+                ("url", "http://example.com/items/1"),
+                ("method", "PUT"),
+                ("data", "{\"name\":\"widget\"}"),
+                ("contentType", "application/json"),
+            ]),
+            vec![(
+                "http://example.com/items/1".to_string(),
+                "PUT",
+                Some((
+                    b"{\"name\":\"widget\"}".to_vec(),
+                    "application/json".to_string()
+                ))
+            )]
+        );
+    }
+
+    #[test]
+    fn delete_and_head_requests_carry_data_in_the_query() {
+        assert_eq!(
+            send(&[
+                ("url", "http://example.com/items/1"),
+                ("method", "DELETE"),
+                ("data", "force=1"),
+            ]),
+            vec![(
+                "http://example.com/items/1?force=1".to_string(),
+                "DELETE",
+                None
+            )]
+        );
+        assert_eq!(
+            send(&[("url", "http://example.com/items/1"), ("method", "HEAD")]),
+            vec![("http://example.com/items/1".to_string(), "HEAD", None)]
+        );
+    }
+}
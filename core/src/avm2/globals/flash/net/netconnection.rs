@@ -0,0 +1,462 @@
+//! `flash.net.NetConnection` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::remoting;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::{Avm2, Error};
+use crate::backend::navigator::RequestOptions;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.net.NetConnection`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "connected"),
+            false.into(),
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "uri"),
+            Value::Null,
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "callCount"),
+            0.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Whether a connection's URI names a Flash Remoting gateway, which is
+/// reached over HTTP.
+fn is_remoting_gateway(uri: &str) -> bool {
+    let uri = uri.to_ascii_lowercase();
+
+    uri.starts_with("http://") || uri.starts_with("https://")
+}
+
+/// Implements `flash.net.NetConnection`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetConnection.connect`.
+///
+/// Connecting to `null` selects progressive download over HTTP, which always
+/// succeeds. An HTTP URL selects a Flash Remoting gateway for `call`, which
+/// is only contacted once a call is made, so nothing is announced and the
+/// connection never reports itself as connected. Any other command names a
+/// Flash Media Server application, which we cannot talk to yet, so the
+/// connection fails.
+pub fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let (uri, connected, code, level) = match args.get(0).cloned().unwrap_or(Value::Null) {
+            Value::Null | Value::Undefined => (
+                "null".into(),
+                true,
+                "NetConnection.Connect.Success",
+                "status",
+            ),
+            command => {
+                let uri = command.coerce_to_string(activation)?;
+                if is_remoting_gateway(&uri) {
+                    for (name, value) in &[("connected", Value::from(false)), ("uri", uri.into())] {
+                        this.set_property(
+                            this,
+                            &QName::new(Namespace::Private("ruffle".into()), *name),
+                            value.clone(),
+                            activation,
+                        )?;
+                    }
+
+                    return Ok(Value::Undefined);
+                }
+
+                log::warn!(
+                    "NetConnection.connect: RTMP is not yet implemented ({})",
+                    uri
+                );
+
+                (uri, false, "NetConnection.Connect.Failed", "error")
+            }
+        };
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "connected"),
+            connected.into(),
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "uri"),
+            uri.into(),
+            activation,
+        )?;
+
+        Avm2::dispatch_net_status(&mut activation.context, this, code, level)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetConnection.call`.
+///
+/// The call is sent to the remoting gateway as an AMF message, and the reply
+/// is handed to `responder`, if there is one.
+pub fn call<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let uri = match this.get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "uri"),
+            activation,
+        )? {
+            Value::String(uri) if is_remoting_gateway(&uri) => uri,
+            _ => return Err("Error: Error #2126: NetConnection object must be connected.".into()),
+        };
+        let command = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let responder = match args.get(1).cloned().unwrap_or(Value::Null) {
+            Value::Undefined | Value::Null => None,
+            responder => Some(responder.coerce_to_object(activation)?),
+        };
+
+        // Each call is answered at its own response URI, `/1`, `/2`, and so
+        // on.
+        let call_count_name = QName::new(Namespace::Private("ruffle".into()), "callCount");
+        let call_count = this
+            .get_property(this, &call_count_name, activation)?
+            .coerce_to_u32(activation)?
+            + 1;
+        this.set_property(this, &call_count_name, call_count.into(), activation)?;
+        let response = format!("/{}", call_count);
+
+        let body = remoting::write_request(
+            activation,
+            &command,
+            &response,
+            args.get(2..).unwrap_or(&[]),
+        )?;
+        let options = RequestOptions::post(Some((body, "application/x-amf".to_string())));
+        let fetch = activation.context.navigator.fetch(&uri, options);
+        let process = activation.context.load_manager.load_remoting_response(
+            activation.context.player.clone().unwrap(),
+            this,
+            responder,
+            fetch,
+            response,
+        );
+
+        activation.context.navigator.spawn_future(process);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetConnection.close`.
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let was_connected = this
+            .get_property(
+                this,
+                &QName::new(Namespace::Private("ruffle".into()), "connected"),
+                activation,
+            )?
+            .coerce_to_boolean();
+
+        if was_connected {
+            this.set_property(
+                this,
+                &QName::new(Namespace::Private("ruffle".into()), "connected"),
+                false.into(),
+                activation,
+            )?;
+
+            Avm2::dispatch_net_status(
+                &mut activation.context,
+                this,
+                "NetConnection.Connect.Closed",
+                "status",
+            )?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `connected`'s getter.
+pub fn connected<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "connected"),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `uri`'s getter.
+pub fn uri<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "uri"),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `NetConnection`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.net"), "NetConnection"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "connect"),
+        Method::from_builtin(connect),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "call"),
+        Method::from_builtin(call),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "close"),
+        Method::from_builtin(close),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "connected"),
+        Method::from_builtin(connected),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "uri"),
+        Method::from_builtin(uri),
+    ));
+
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::test_utils::{construct, function, get, Fetch, TestNavigator, TestPlayer};
+
+    /// Read a string prefixed with its length out of an envelope.
+    fn read_utf8(bytes: &[u8], position: &mut usize) -> String {
+        let length = u16::from_be_bytes([bytes[*position], bytes[*position + 1]]) as usize;
+        let string = String::from_utf8(bytes[*position + 2..*position + 2 + length].to_vec());
+        *position += 2 + length;
+
+        string.unwrap()
+    }
+
+    /// Write a string prefixed with its length into an envelope.
+    fn write_utf8(bytes: &mut Vec<u8>, string: &str) {
+        bytes.extend_from_slice(&(string.len() as u16).to_be_bytes());
+        bytes.extend_from_slice(string.as_bytes());
+    }
+
+    /// A remoting gateway with two services: `math.add`, whose result is the
+    /// sum of its integer arguments, and `math.fail`, which always faults.
+    fn gateway_reply((url, method, body): &Fetch) -> Vec<u8> {
+        assert_eq!(url, "http://example.com/gateway");
+        assert_eq!(*method, "POST");
+        let (request, content_type) = body.as_ref().unwrap();
+        assert_eq!(content_type, "application/x-amf");
+
+        // Skip the version and the (empty) headers, and read the only
+        // message.
+        let mut position = 6;
+        let target = read_utf8(request, &mut position);
+        let response = read_utf8(request, &mut position);
+        let body = &request[position + 4..];
+        assert_eq!(body[0], 0x0A, "arguments are a strict array");
+
+        let mut reply = vec![0x00, 0x03, 0x00, 0x00, 0x00, 0x01];
+        let mut value = Vec::new();
+        let handler = if target == "math.add" {
+            // Every argument is a small AMF3 integer.
+            let count = u32::from_be_bytes([body[1], body[2], body[3], body[4]]) as usize;
+            let sum: f64 = (0..count)
+                .map(|i| {
+                    assert_eq!(&body[5 + i * 3..7 + i * 3], &[0x11, 0x04]);
+                    f64::from(body[7 + i * 3])
+                })
+                .sum();
+
+            value.push(0x00);
+            value.extend_from_slice(&sum.to_be_bytes());
+
+            "onResult"
+        } else {
+            value.push(0x03);
+            write_utf8(&mut value, "code");
+            value.push(0x02);
+            write_utf8(&mut value, "Server.Fault");
+            write_utf8(&mut value, "");
+            value.push(0x09);
+
+            "onStatus"
+        };
+
+        write_utf8(&mut reply, &format!("{}/{}", response, handler));
+        write_utf8(&mut reply, "null");
+        reply.extend_from_slice(&(value.len() as u32).to_be_bytes());
+        reply.extend_from_slice(&value);
+
+        reply
+    }
+
+    /// A `Responder` result function that traces the value it is given.
+    fn trace_result<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let value = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        activation
+            .context
+            .log
+            .avm_trace(&format!("result: {}", value));
+
+        Ok(Value::Undefined)
+    }
+
+    /// A `Responder` status function that traces the code of the fault.
+    fn trace_status<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let info = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let code = get(activation, info, "code").coerce_to_string(activation)?;
+
+        activation
+            .context
+            .log
+            .avm_trace(&format!("status: {}", code));
+
+        Ok(Value::Undefined)
+    }
+
+    #[test]
+    fn calls_are_answered_through_the_responder() {
+        let mut player = TestPlayer::with_navigator(|channel| {
+            Box::new(TestNavigator::with_responder(channel, gateway_reply))
+        });
+
+        player.update(|activation| {
+            let result = function(activation, trace_result);
+            let status = function(activation, trace_status);
+            let responder = construct(
+                activation,
+                "flash.net",
+                "Responder",
+                &[result.into(), status.into()],
+            );
+
+            let connection = construct(activation, "flash.net", "NetConnection", &[]);
+            assert!(call(
+                activation,
+                Some(connection),
+                &["math.add".into(), responder.into(), 2.into(), 3.into()],
+            )
+            .is_err());
+
+            connect(
+                activation,
+                Some(connection),
+                &["http://example.com/gateway".into()],
+            )
+            .unwrap();
+            assert_eq!(
+                connected(activation, Some(connection), &[]).unwrap(),
+                false.into()
+            );
+
+            call(
+                activation,
+                Some(connection),
+                &["math.add".into(), responder.into(), 2.into(), 3.into()],
+            )
+            .unwrap();
+            call(
+                activation,
+                Some(connection),
+                &["math.fail".into(), responder.into()],
+            )
+            .unwrap();
+        });
+
+        assert!(player.traces().is_empty());
+        player.block();
+
+        assert_eq!(
+            player.traces(),
+            vec!["result: 5".to_string(), "status: Server.Fault".to_string()]
+        );
+    }
+}
@@ -0,0 +1,680 @@
+//! `flash.net.NetStream` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::{Avm2, Error};
+use crate::backend::navigator::RequestOptions;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.net.NetStream`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        let connection = args.get(0).cloned().unwrap_or(Value::Undefined);
+        let is_connected = match connection {
+            Value::Object(mut connection) => connection
+                .get_property(
+                    connection,
+                    &QName::new(Namespace::ruffle_private(), "connected"),
+                    activation,
+                )?
+                .coerce_to_boolean(),
+            _ => false,
+        };
+
+        if !is_connected {
+            return Err(
+                "ArgumentError: Error #2126: NetConnection object must be connected.".into(),
+            );
+        }
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::ruffle_private(), "url"),
+            Value::Null,
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::ruffle_private(), "time"),
+            0.0.into(),
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::ruffle_private(), "paused"),
+            false.into(),
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::ruffle_private(), "bufferTime"),
+            0.1.into(),
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::ruffle_private(), "bytesLoaded"),
+            0.into(),
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::ruffle_private(), "bytesTotal"),
+            0.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.net.NetStream`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetStream.play`.
+///
+/// Only progressive download is supported: the media is fetched in full and
+/// `NetStream.Buffer.Full` fires once it arrives.
+pub fn play<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let url = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::ruffle_private(), "url"),
+            url.into(),
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::ruffle_private(), "time"),
+            0.0.into(),
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::ruffle_private(), "paused"),
+            false.into(),
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::ruffle_private(), "bytesLoaded"),
+            0.into(),
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::ruffle_private(), "bytesTotal"),
+            0.into(),
+            activation,
+        )?;
+
+        Avm2::dispatch_net_status(
+            &mut activation.context,
+            this,
+            "NetStream.Play.Start",
+            "status",
+        )?;
+
+        let fetch = activation
+            .context
+            .navigator
+            .fetch(&url, RequestOptions::get());
+        let process = activation.context.load_manager.load_media_into_net_stream(
+            activation.context.player.clone().unwrap(),
+            this,
+            fetch,
+            url.to_string(),
+        );
+
+        activation.context.navigator.spawn_future(process);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetStream.pause`.
+pub fn pause<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        set_paused(activation, this, true)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetStream.resume`.
+pub fn resume<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        set_paused(activation, this, false)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetStream.togglePause`.
+pub fn toggle_pause<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let is_paused = this
+            .get_property(
+                this,
+                &QName::new(Namespace::ruffle_private(), "paused"),
+                activation,
+            )?
+            .coerce_to_boolean();
+
+        set_paused(activation, this, !is_paused)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Pause or unpause a stream, notifying listeners if that changed anything.
+///
+/// Streams that are not playing anything cannot be paused.
+fn set_paused<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+    paused: bool,
+) -> Result<(), Error> {
+    let url = this.get_property(
+        this,
+        &QName::new(Namespace::ruffle_private(), "url"),
+        activation,
+    )?;
+    let was_paused = this
+        .get_property(
+            this,
+            &QName::new(Namespace::ruffle_private(), "paused"),
+            activation,
+        )?
+        .coerce_to_boolean();
+
+    if matches!(url, Value::Null) || was_paused == paused {
+        return Ok(());
+    }
+
+    this.set_property(
+        this,
+        &QName::new(Namespace::ruffle_private(), "paused"),
+        paused.into(),
+        activation,
+    )?;
+
+    let code = if paused {
+        "NetStream.Pause.Notify"
+    } else {
+        "NetStream.Unpause.Notify"
+    };
+    Avm2::dispatch_net_status(&mut activation.context, this, code, "status")?;
+
+    Ok(())
+}
+
+/// Implements `NetStream.seek`.
+pub fn seek<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let offset = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+        let url = this.get_property(
+            this,
+            &QName::new(Namespace::ruffle_private(), "url"),
+            activation,
+        )?;
+
+        if matches!(url, Value::Null) || offset.is_nan() {
+            Avm2::dispatch_net_status(
+                &mut activation.context,
+                this,
+                "NetStream.Seek.InvalidTime",
+                "error",
+            )?;
+
+            return Ok(Value::Undefined);
+        }
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::ruffle_private(), "time"),
+            offset.max(0.0).into(),
+            activation,
+        )?;
+
+        Avm2::dispatch_net_status(
+            &mut activation.context,
+            this,
+            "NetStream.Seek.Notify",
+            "status",
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetStream.close`.
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        // Clearing the URL also tells any in-flight load to discard its data.
+        this.set_property(
+            this,
+            &QName::new(Namespace::ruffle_private(), "url"),
+            Value::Null,
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::ruffle_private(), "time"),
+            0.0.into(),
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::ruffle_private(), "paused"),
+            false.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `time`'s getter.
+///
+/// Ruffle cannot decode streamed video yet, so the playhead only moves when
+/// the stream is seeked.
+pub fn time<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::ruffle_private(), "time"),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `bufferTime`'s getter.
+pub fn buffer_time<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::ruffle_private(), "bufferTime"),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `bufferTime`'s setter.
+pub fn set_buffer_time<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let buffer_time = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::ruffle_private(), "bufferTime"),
+            buffer_time.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `bufferLength`'s getter.
+///
+/// Progressive downloads are buffered in full, so the buffer is either empty
+/// or exactly as long as `bufferTime` asks for.
+pub fn buffer_length<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let bytes_loaded = this
+            .get_property(
+                this,
+                &QName::new(Namespace::ruffle_private(), "bytesLoaded"),
+                activation,
+            )?
+            .coerce_to_number(activation)?;
+        let bytes_total = this
+            .get_property(
+                this,
+                &QName::new(Namespace::ruffle_private(), "bytesTotal"),
+                activation,
+            )?
+            .coerce_to_number(activation)?;
+
+        if bytes_total > 0.0 && bytes_loaded >= bytes_total {
+            return buffer_time(activation, Some(this), &[]);
+        }
+
+        return Ok(0.0.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `bytesLoaded`'s getter.
+pub fn bytes_loaded<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::ruffle_private(), "bytesLoaded"),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `bytesTotal`'s getter.
+pub fn bytes_total<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::ruffle_private(), "bytesTotal"),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `NetStream`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.net"), "NetStream"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "play"),
+        Method::from_builtin(play),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "pause"),
+        Method::from_builtin(pause),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "resume"),
+        Method::from_builtin(resume),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "togglePause"),
+        Method::from_builtin(toggle_pause),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "seek"),
+        Method::from_builtin(seek),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "close"),
+        Method::from_builtin(close),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "time"),
+        Method::from_builtin(time),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "bufferTime"),
+        Method::from_builtin(buffer_time),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "bufferTime"),
+        Method::from_builtin(set_buffer_time),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "bufferLength"),
+        Method::from_builtin(buffer_length),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "bytesLoaded"),
+        Method::from_builtin(bytes_loaded),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "bytesTotal"),
+        Method::from_builtin(bytes_total),
+    ));
+
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::test_utils::{call, class, construct, function, get, TestPlayer};
+
+    /// Any file will do as a media stream, since it is never decoded.
+    const MEDIA_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/swfs/avm2/add");
+    const MEDIA_FILE: &str = "test.swf";
+
+    /// A `netStatus` listener that traces the code of each event it gets.
+    fn trace_status<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let event = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let info = get(activation, event, "info").coerce_to_object(activation)?;
+        let code = get(activation, info, "code").coerce_to_string(activation)?;
+
+        activation.context.log.avm_trace(&code);
+
+        Ok(Value::Undefined)
+    }
+
+    /// Construct a class in `flash.net`, and trace its `netStatus` events.
+    fn construct_traced<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        name: &'static str,
+        args: &[Value<'gc>],
+    ) -> Object<'gc> {
+        let object = construct(activation, "flash.net", name, args);
+        let listener = function(activation, trace_status);
+        call(
+            activation,
+            object,
+            "addEventListener",
+            &["netStatus".into(), listener.into()],
+        );
+
+        object
+    }
+
+    /// The global object that we park the stream on between updates.
+    fn globals<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Object<'gc> {
+        let name = QName::new(Namespace::package("flash.net"), "NetStream");
+        let (_, mut script) = activation
+            .avm2()
+            .global_domain()
+            .get_defining_script(&name.into())
+            .unwrap()
+            .unwrap();
+
+        script.globals(&mut activation.context).unwrap()
+    }
+
+    #[test]
+    fn progressive_play_lifecycle() {
+        let mut player = TestPlayer::with_base_path(MEDIA_DIR);
+
+        player.update(|activation| {
+            let connection = construct_traced(activation, "NetConnection", &[]);
+            call(activation, connection, "connect", &[Value::Null]);
+            assert_eq!(get(activation, connection, "connected"), true.into());
+
+            let stream = construct_traced(activation, "NetStream", &[connection.into()]);
+            call(activation, stream, "play", &[MEDIA_FILE.into()]);
+            assert_eq!(get(activation, stream, "bytesLoaded"), 0.into());
+            assert_eq!(get(activation, stream, "bufferLength"), 0.0.into());
+
+            let mut globals = globals(activation);
+            globals
+                .set_property(
+                    globals,
+                    &QName::new(Namespace::public(), "stream"),
+                    stream.into(),
+                    activation,
+                )
+                .unwrap();
+        });
+
+        player.poll();
+
+        let media_len = std::fs::read(format!("{}/{}", MEDIA_DIR, MEDIA_FILE))
+            .unwrap()
+            .len();
+
+        player.update(|activation| {
+            let globals = globals(activation);
+            let stream = get(activation, globals, "stream")
+                .coerce_to_object(activation)
+                .unwrap();
+
+            assert_eq!(
+                get(activation, stream, "bytesLoaded"),
+                (media_len as f64).into()
+            );
+            assert_eq!(
+                get(activation, stream, "bytesTotal"),
+                (media_len as f64).into()
+            );
+            assert_eq!(get(activation, stream, "bufferLength"), 0.1.into());
+
+            call(activation, stream, "pause", &[]);
+            call(activation, stream, "pause", &[]);
+            call(activation, stream, "seek", &[2.5.into()]);
+            assert_eq!(get(activation, stream, "time"), 2.5.into());
+            call(activation, stream, "togglePause", &[]);
+
+            call(activation, stream, "close", &[]);
+            call(activation, stream, "seek", &[1.0.into()]);
+            assert_eq!(get(activation, stream, "time"), 0.0.into());
+        });
+
+        assert_eq!(
+            player.traces(),
+            vec![
+                "NetConnection.Connect.Success",
+                "NetStream.Play.Start",
+                "NetStream.Buffer.Full",
+                "NetStream.Pause.Notify",
+                "NetStream.Seek.Notify",
+                "NetStream.Unpause.Notify",
+                "NetStream.Seek.InvalidTime",
+            ]
+        );
+    }
+
+    #[test]
+    fn rtmp_connections_fail() {
+        let player = TestPlayer::new();
+
+        player.update(|activation| {
+            let connection = construct_traced(activation, "NetConnection", &[]);
+            call(
+                activation,
+                connection,
+                "connect",
+                &["rtmp://example.com/app".into()],
+            );
+            assert_eq!(get(activation, connection, "connected"), false.into());
+
+            let class = class(activation, "flash.net", "NetStream");
+            let proto = get(activation, class, "prototype")
+                .coerce_to_object(activation)
+                .unwrap();
+            let stream = proto.construct(activation, &[connection.into()]).unwrap();
+            assert!(class
+                .call(Some(stream), &[connection.into()], activation, Some(proto))
+                .is_err());
+        });
+
+        assert_eq!(player.traces(), vec!["NetConnection.Connect.Failed"]);
+    }
+}
@@ -0,0 +1,550 @@
+//! `flash.net.SharedObject` builtin/prototype
+//!
+//! Only local shared objects are supported. Their data is stored the same way
+//! as AVM1's, so that movies of either kind can read each other's shared
+//! objects. There's no server to reject changes, so every change is accepted
+//! as soon as it is flushed, and announced with a `sync` event.
+
+use crate::avm1::globals::shared_object::full_name;
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::events::{Event, EventData};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{ArrayObject, Object, ScriptObject, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::{Avm2, Error};
+use gc_arena::{GcCell, MutationContext};
+use json::JsonValue;
+
+/// Implements `flash.net.SharedObject`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        let data = ScriptObject::object(
+            activation.context.gc_context,
+            activation.avm2().prototypes().object,
+        );
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "data"),
+            data.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.net.SharedObject`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Convert a value to the JSON it is stored as, or `None` if it isn't
+/// stored at all.
+///
+/// Objects that are already being serialized are left out, rather than
+/// recursing forever.
+fn serialize<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+    stack: &mut Vec<Object<'gc>>,
+) -> Result<Option<JsonValue>, Error> {
+    Ok(match value {
+        Value::Undefined => None,
+        Value::Null => Some(JsonValue::Null),
+        Value::Bool(b) => Some(b.into()),
+        Value::Number(_) | Value::Integer(_) | Value::Unsigned(_) => {
+            Some(value.coerce_to_number(activation)?.into())
+        }
+        Value::String(s) => Some(s.to_string().into()),
+        Value::Object(object) if object.as_executable().is_some() => None,
+        Value::Object(object) if stack.iter().any(|o| Object::ptr_eq(*o, object)) => None,
+        Value::Object(object) => {
+            stack.push(object);
+            let json = serialize_object(activation, object, stack);
+            stack.pop();
+
+            Some(json?)
+        }
+    })
+}
+
+/// Convert the public, enumerable properties of an object to JSON.
+///
+/// Arrays are marked as such, and keep their length, as AVM1 does.
+fn serialize_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut object: Object<'gc>,
+    stack: &mut Vec<Object<'gc>>,
+) -> Result<JsonValue, Error> {
+    let mut json = JsonValue::new_object();
+
+    if let Some(length) = object.as_array_storage().map(|storage| storage.length()) {
+        for i in 0..length {
+            let element = object
+                .as_array_storage()
+                .and_then(|storage| storage.get(i))
+                .unwrap_or(Value::Undefined);
+            if let Some(element) = serialize(activation, element, stack)? {
+                json[i.to_string()] = element;
+            }
+        }
+
+        json["__proto__"] = "Array".into();
+        json["length"] = length.into();
+    }
+
+    let mut index = 0;
+    while let Some(next) = object.get_next_enumerant(index) {
+        index = next;
+        let name = match object.get_enumerant_name(index) {
+            Some(name) if name.namespace().is_public() => name,
+            _ => continue,
+        };
+
+        let value = object.get_property(object, &name, activation)?;
+        if let Some(value) = serialize(activation, value, stack)? {
+            json[name.local_name().as_str()] = value;
+        }
+    }
+
+    Ok(json)
+}
+
+/// Convert stored JSON back into a value.
+fn deserialize<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    json: &JsonValue,
+) -> Result<Value<'gc>, Error> {
+    Ok(match json {
+        JsonValue::Null => Value::Null,
+        JsonValue::Short(s) => AvmString::new(activation.context.gc_context, s.as_str()).into(),
+        JsonValue::String(s) => AvmString::new(activation.context.gc_context, s.as_str()).into(),
+        JsonValue::Number(n) => f64::from(*n).into(),
+        JsonValue::Boolean(b) => (*b).into(),
+        JsonValue::Object(_) if json["__proto__"] == "Array" => {
+            let length = json["length"].as_usize().unwrap_or(0);
+            let mut array = ArrayObject::from_array(
+                ArrayStorage::new(length),
+                activation.avm2().prototypes().array,
+                activation.context.gc_context,
+            );
+            deserialize_members(activation, &mut array, json, &["__proto__", "length"])?;
+
+            array.into()
+        }
+        JsonValue::Object(_) => {
+            let mut object = ScriptObject::object(
+                activation.context.gc_context,
+                activation.avm2().prototypes().object,
+            );
+            deserialize_members(activation, &mut object, json, &[])?;
+
+            object.into()
+        }
+        JsonValue::Array(_) => Value::Undefined,
+    })
+}
+
+/// Set the properties of `object` from the members of a stored JSON object,
+/// except for those in `skip`.
+fn deserialize_members<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: &mut Object<'gc>,
+    json: &JsonValue,
+    skip: &[&str],
+) -> Result<(), Error> {
+    for (key, value) in json.entries() {
+        if skip.contains(&key) {
+            continue;
+        }
+
+        let value = deserialize(activation, value)?;
+        let name = QName::new(
+            Namespace::public(),
+            AvmString::new(activation.context.gc_context, key),
+        );
+        object.set_property(*object, &name, value, activation)?;
+    }
+
+    Ok(())
+}
+
+/// Get the private name that a shared object is stored under.
+fn name<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+) -> Result<String, Error> {
+    Ok(this
+        .get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "name"),
+            activation,
+        )?
+        .coerce_to_string(activation)?
+        .to_string())
+}
+
+/// Implements `SharedObject.getLocal`.
+///
+/// Asking for the same shared object twice yields the same object, which is
+/// kept on the class.
+pub fn get_local<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let mut this = if let Some(this) = this {
+        this
+    } else {
+        return Ok(Value::Undefined);
+    };
+
+    let name = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+    let local_path = match args.get(1).cloned().unwrap_or(Value::Null) {
+        Value::Undefined | Value::Null => None,
+        local_path => Some(local_path.coerce_to_string(activation)?),
+    };
+    let secure = args
+        .get(2)
+        .cloned()
+        .unwrap_or_else(|| false.into())
+        .coerce_to_boolean();
+
+    let full_name = full_name(
+        activation.context.swf.url(),
+        &name,
+        local_path.as_ref().map(|local_path| local_path.as_str()),
+        secure,
+    )
+    .ok_or("Error: Error #2134: Cannot create SharedObject.")?;
+
+    let cached_name = QName::new(
+        Namespace::Private("ruffle".into()),
+        AvmString::new(activation.context.gc_context, full_name.clone()),
+    );
+    let existing = this.get_property(this, &cached_name, activation)?;
+    if let Value::Object(_) = existing {
+        return Ok(existing);
+    }
+
+    let mut proto = this
+        .get_property(
+            this,
+            &QName::new(Namespace::public(), "prototype"),
+            activation,
+        )?
+        .coerce_to_object(activation)?;
+    let mut shared_object = proto.construct(activation, &[])?;
+    let constr = proto
+        .get_property(
+            proto,
+            &QName::new(Namespace::public(), "constructor"),
+            activation,
+        )?
+        .coerce_to_object(activation)?;
+
+    constr.call(Some(shared_object), &[], activation, Some(proto))?;
+    shared_object.set_property(
+        shared_object,
+        &QName::new(Namespace::Private("ruffle".into()), "name"),
+        AvmString::new(activation.context.gc_context, full_name.clone()).into(),
+        activation,
+    )?;
+
+    if let Some(saved) = activation.context.storage.get_string(&full_name) {
+        if let Ok(saved) = json::parse(&saved) {
+            if saved.is_object() {
+                let data = deserialize(activation, &saved)?;
+                shared_object.set_property(
+                    shared_object,
+                    &QName::new(Namespace::Private("ruffle".into()), "data"),
+                    data,
+                    activation,
+                )?;
+            }
+        }
+    }
+
+    this.set_property(this, &cached_name, shared_object.into(), activation)?;
+
+    Ok(shared_object.into())
+}
+
+/// Implements `data`'s getter.
+pub fn data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "data"),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `size`'s getter.
+///
+/// This is the size of the data as it was last flushed.
+pub fn size<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let name = name(activation, this)?;
+        let size = activation.context.storage.get_size(&name).unwrap_or(0);
+
+        return Ok((size as u32).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `SharedObject.flush`.
+///
+/// Every property that was added, changed or deleted since the last flush
+/// is listed in the `sync` event that follows, with a code of `success`: in
+/// local mode, all changes are local ones. Nothing is dispatched if nothing
+/// changed. `minDiskSpace` is ignored.
+pub fn flush<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let name = name(activation, this)?;
+        let data = data(activation, Some(this), &[])?.coerce_to_object(activation)?;
+        let mut stack = vec![data];
+        let json = serialize_object(activation, data, &mut stack)?;
+
+        let saved = activation
+            .context
+            .storage
+            .get_string(&name)
+            .and_then(|saved| json::parse(&saved).ok())
+            .filter(|saved| saved.is_object())
+            .unwrap_or_else(JsonValue::new_object);
+        let mut changed: Vec<String> = json
+            .entries()
+            .filter(|(key, value)| saved[*key] != **value)
+            .map(|(key, _)| key.to_string())
+            .collect();
+        changed.extend(
+            saved
+                .entries()
+                .filter(|(key, _)| !json.has_key(key))
+                .map(|(key, _)| key.to_string()),
+        );
+
+        if !activation.context.storage.put_string(&name, json.dump()) {
+            return Err("Error: Error #2130: Unable to flush SharedObject.".into());
+        }
+
+        if !changed.is_empty() {
+            let mut change_list = Vec::with_capacity(changed.len());
+            for key in changed {
+                let mut change = ScriptObject::object(
+                    activation.context.gc_context,
+                    activation.avm2().prototypes().object,
+                );
+                change.set_property(
+                    change,
+                    &QName::new(Namespace::public(), "code"),
+                    "success".into(),
+                    activation,
+                )?;
+                change.set_property(
+                    change,
+                    &QName::new(Namespace::public(), "name"),
+                    AvmString::new(activation.context.gc_context, key).into(),
+                    activation,
+                )?;
+                change_list.push(change.into());
+            }
+
+            let change_list = ArrayObject::from_array(
+                ArrayStorage::from_args(&change_list),
+                activation.avm2().prototypes().array,
+                activation.context.gc_context,
+            );
+            let mut event = Event::new("sync");
+            event.set_event_data(EventData::Sync {
+                change_list: Some(change_list),
+            });
+
+            Avm2::dispatch_event(&mut activation.context, event, this)?;
+        }
+
+        return Ok("flushed".into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `SharedObject.clear`.
+///
+/// This empties the data and deletes it from storage.
+pub fn clear<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let name = name(activation, this)?;
+        let data = ScriptObject::object(
+            activation.context.gc_context,
+            activation.avm2().prototypes().object,
+        );
+        let mut this = this;
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "data"),
+            data.into(),
+            activation,
+        )?;
+
+        activation.context.storage.remove_key(&name);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `SharedObject`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.net"), "SharedObject"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    write.define_class_trait(Trait::from_method(
+        QName::new(Namespace::public(), "getLocal"),
+        Method::from_builtin(get_local),
+    ));
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "data"),
+        Method::from_builtin(data),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "size"),
+        Method::from_builtin(size),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "flush"),
+        Method::from_builtin(flush),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "clear"),
+        Method::from_builtin(clear),
+    ));
+
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::test_utils::{call, class, function, get, set, trace_with_avm2};
+
+    /// A `sync` listener that traces each change as `code:name`.
+    fn trace_changes<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let event = args[0].coerce_to_object(activation)?;
+        let change_list = get(activation, event, "changeList").coerce_to_object(activation)?;
+        let changes = change_list
+            .as_array_storage()
+            .unwrap()
+            .iter()
+            .collect::<Vec<_>>();
+
+        for change in changes {
+            let change = change.unwrap().coerce_to_object(activation)?;
+            let mut field = |name| get(activation, change, name).coerce_to_string(activation);
+            let text = format!("{}:{}", field("code")?, field("name")?);
+            activation.context.log.avm_trace(&text);
+        }
+
+        Ok(Value::Undefined)
+    }
+
+    #[test]
+    fn flush_dispatches_sync_after_changes() {
+        let log = trace_with_avm2(|activation| {
+            let class = class(activation, "flash.net", "SharedObject");
+            let shared_object = call(activation, class, "getLocal", &["settings".into()])
+                .coerce_to_object(activation)
+                .unwrap();
+            let listener = function(activation, trace_changes);
+            call(
+                activation,
+                shared_object,
+                "addEventListener",
+                &["sync".into(), listener.into()],
+            );
+
+            let data = data(activation, Some(shared_object), &[])
+                .unwrap()
+                .coerce_to_object(activation)
+                .unwrap();
+            set(activation, data, "volume", 0.5.into());
+            set(activation, data, "muted", false.into());
+            assert_eq!(
+                call(activation, shared_object, "flush", &[]),
+                "flushed".into()
+            );
+
+            call(activation, shared_object, "flush", &[]);
+            activation.context.log.avm_trace("unchanged");
+
+            set(activation, data, "muted", true.into());
+            call(activation, shared_object, "flush", &[]);
+
+            let again = call(activation, class, "getLocal", &["settings".into()]);
+            assert_eq!(again, shared_object.into());
+        });
+
+        assert_eq!(
+            log,
+            vec![
+                "success:volume",
+                "success:muted",
+                "unchanged",
+                "success:muted"
+            ]
+        );
+    }
+}
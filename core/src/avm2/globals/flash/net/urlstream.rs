@@ -0,0 +1,626 @@
+//! `flash.net.URLStream` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::flash::net::request_options;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::backend::navigator::RequestOptions;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.net.URLStream`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "url"),
+            Value::Null,
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "bytesLoaded"),
+            0.into(),
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "endian"),
+            "bigEndian".into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.net.URLStream`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Get the URL a stream is currently downloading, if it is open.
+fn open_url<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+) -> Result<Option<AvmString<'gc>>, Error> {
+    match this.get_property(
+        this,
+        &QName::new(Namespace::Private("ruffle".into()), "url"),
+        activation,
+    )? {
+        Value::String(url) => Ok(Some(url)),
+        _ => Ok(None),
+    }
+}
+
+/// Empty a stream's buffer and point it at a new URL, or at nothing.
+fn reset<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+    url: Value<'gc>,
+) -> Result<(), Error> {
+    if let Some(mut buffer) = this.as_bytearray_mut(activation.context.gc_context) {
+        buffer.set_length(0);
+    }
+
+    this.set_property(
+        this,
+        &QName::new(Namespace::Private("ruffle".into()), "url"),
+        url,
+        activation,
+    )?;
+    this.set_property(
+        this,
+        &QName::new(Namespace::Private("ruffle".into()), "bytesLoaded"),
+        0.into(),
+        activation,
+    )?;
+
+    Ok(())
+}
+
+/// Implements `URLStream.load`.
+///
+/// The request may be a `URLRequest`, or anything else with the same
+/// properties; a plain string is taken to be the URL itself.
+pub fn load<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let (url, options) = match args.get(0).cloned().unwrap_or(Value::Undefined) {
+            Value::Object(request) => request_options(activation, request)?,
+            Value::Undefined | Value::Null => {
+                return Err("TypeError: Error #2007: Parameter request must be non-null.".into())
+            }
+            url => (
+                url.coerce_to_string(activation)?.to_string(),
+                RequestOptions::get(),
+            ),
+        };
+
+        reset(
+            activation,
+            this,
+            AvmString::new(activation.context.gc_context, url.clone()).into(),
+        )?;
+
+        let fetch = activation.context.navigator.fetch(&url, options);
+        let process = activation.context.load_manager.load_data_into_url_stream(
+            activation.context.player.clone().unwrap(),
+            this,
+            fetch,
+            url,
+        );
+
+        activation.context.navigator.spawn_future(process);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `URLStream.close`.
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if open_url(activation, this)?.is_none() {
+            return Err(
+                "IOError: Error #2029: This URLStream object does not have a stream opened.".into(),
+            );
+        }
+
+        reset(activation, this, Value::Null)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `connected`'s getter.
+pub fn connected<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(open_url(activation, this)?.is_some().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `bytesAvailable`'s getter.
+pub fn bytes_available<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(buffer) = this.as_bytearray() {
+            return Ok((buffer.bytes_available() as u32).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `endian`'s getter.
+pub fn endian<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "endian"),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `endian`'s setter.
+pub fn set_endian<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let endian = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        if endian != "bigEndian" && endian != "littleEndian" {
+            return Err(
+                "ArgumentError: Error #2008: Parameter type must be one of the accepted values."
+                    .into(),
+            );
+        }
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "endian"),
+            endian.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Read `length` bytes from a stream's buffer.
+///
+/// Reading more than is available is an `EOFError`, and consumes nothing.
+fn read<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    length: usize,
+) -> Result<Vec<u8>, Error> {
+    match this.as_bytearray_mut(activation.context.gc_context) {
+        Some(mut buffer) => Ok(buffer.read_bytes(length)?.to_vec()),
+        None => Err("EOFError: Error #2030: End of file was encountered.".into()),
+    }
+}
+
+/// Read a number's bytes from a stream's buffer, most significant byte
+/// first regardless of the stream's `endian`.
+fn read_number<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+    length: usize,
+) -> Result<Vec<u8>, Error> {
+    let endian = this
+        .get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "endian"),
+            activation,
+        )?
+        .coerce_to_string(activation)?;
+    let mut bytes = read(activation, this, length)?;
+
+    if endian == "littleEndian" {
+        bytes.reverse();
+    }
+
+    Ok(bytes)
+}
+
+/// Implements `URLStream.readBytes`.
+pub fn read_bytes<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let target = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let offset = args
+            .get(1)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_u32(activation)? as usize;
+        let mut length = args
+            .get(2)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_u32(activation)? as usize;
+
+        if length == 0 {
+            length = this
+                .as_bytearray()
+                .map(|buffer| buffer.bytes_available())
+                .unwrap_or(0);
+        }
+
+        let bytes = read(activation, this, length)?;
+
+        if let Some(mut buffer) = target.as_bytearray_mut(activation.context.gc_context) {
+            let position = buffer.position();
+
+            buffer.set_position(offset);
+            buffer.write_bytes(&bytes);
+            buffer.set_position(position);
+        };
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `URLStream.readBoolean`.
+pub fn read_boolean<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok((read(activation, this, 1)?[0] != 0).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `URLStream.readByte`.
+pub fn read_byte<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok((read(activation, this, 1)?[0] as i8 as i32).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `URLStream.readUnsignedByte`.
+pub fn read_unsigned_byte<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(read(activation, this, 1)?[0].into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `URLStream.readShort`.
+pub fn read_short<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let bytes = read_number(activation, this, 2)?;
+
+        return Ok((i16::from_be_bytes([bytes[0], bytes[1]]) as i32).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `URLStream.readUnsignedShort`.
+pub fn read_unsigned_short<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let bytes = read_number(activation, this, 2)?;
+
+        return Ok((u16::from_be_bytes([bytes[0], bytes[1]]) as u32).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `URLStream.readInt`.
+pub fn read_int<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let bytes = read_number(activation, this, 4)?;
+
+        return Ok(i32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `URLStream.readUnsignedInt`.
+pub fn read_unsigned_int<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let bytes = read_number(activation, this, 4)?;
+
+        return Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `URLStream.readFloat`.
+pub fn read_float<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let bytes = read_number(activation, this, 4)?;
+
+        return Ok((f32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f64).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `URLStream.readDouble`.
+pub fn read_double<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let bytes = read_number(activation, this, 8)?;
+        let mut double = [0; 8];
+        double.copy_from_slice(&bytes);
+
+        return Ok(f64::from_be_bytes(double).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `URLStream.readUTF`.
+pub fn read_utf<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let available = this
+            .as_bytearray()
+            .map(|buffer| buffer.bytes_available())
+            .unwrap_or(0);
+        let prefix = read_number(activation, this, 2)?;
+        let length = u16::from_be_bytes([prefix[0], prefix[1]]) as usize;
+
+        if available < length + 2 {
+            // Leave the length prefix unread, so the whole string can be read
+            // once it has arrived.
+            if let Some(mut buffer) = this.as_bytearray_mut(activation.context.gc_context) {
+                let position = buffer.position();
+                buffer.set_position(position - 2);
+            }
+
+            return Err("EOFError: Error #2030: End of file was encountered.".into());
+        }
+
+        let bytes = read(activation, this, length)?;
+
+        return Ok(AvmString::new(
+            activation.context.gc_context,
+            String::from_utf8_lossy(&bytes).into_owned(),
+        )
+        .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `URLStream.readUTFBytes`.
+pub fn read_utf_bytes<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let length = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_u32(activation)? as usize;
+        let bytes = read(activation, this, length)?;
+
+        return Ok(AvmString::new(
+            activation.context.gc_context,
+            String::from_utf8_lossy(&bytes).into_owned(),
+        )
+        .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `URLStream.readMultiByte`.
+pub fn read_multi_byte<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let length = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_u32(activation)? as usize;
+        let charset = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        if let Some(mut buffer) = this.as_bytearray_mut(activation.context.gc_context) {
+            let string = buffer.read_multibyte(length, &charset)?;
+
+            return Ok(AvmString::new(activation.context.gc_context, string).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `URLStream`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.net"), "URLStream"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "load"),
+        Method::from_builtin(load),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "close"),
+        Method::from_builtin(close),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "connected"),
+        Method::from_builtin(connected),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "bytesAvailable"),
+        Method::from_builtin(bytes_available),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "endian"),
+        Method::from_builtin(endian),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "endian"),
+        Method::from_builtin(set_endian),
+    ));
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "readBytes"),
+        Method::from_builtin(read_bytes),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "readBoolean"),
+        Method::from_builtin(read_boolean),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "readByte"),
+        Method::from_builtin(read_byte),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "readUnsignedByte"),
+        Method::from_builtin(read_unsigned_byte),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "readShort"),
+        Method::from_builtin(read_short),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "readUnsignedShort"),
+        Method::from_builtin(read_unsigned_short),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "readInt"),
+        Method::from_builtin(read_int),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "readUnsignedInt"),
+        Method::from_builtin(read_unsigned_int),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "readFloat"),
+        Method::from_builtin(read_float),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "readDouble"),
+        Method::from_builtin(read_double),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "readUTF"),
+        Method::from_builtin(read_utf),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "readUTFBytes"),
+        Method::from_builtin(read_utf_bytes),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "readMultiByte"),
+        Method::from_builtin(read_multi_byte),
+    ));
+
+    class
+}
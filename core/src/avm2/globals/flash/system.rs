@@ -1,3 +1,13 @@
 //! `flash.system` namespace
 
 pub mod application_domain;
+pub mod capabilities;
+pub mod imeconversionmode;
+pub mod loadercontext;
+pub mod messagechannel;
+pub mod messagechannelstate;
+#[allow(clippy::module_inception)]
+pub mod system;
+pub mod worker;
+pub mod workerdomain;
+pub mod workerstate;
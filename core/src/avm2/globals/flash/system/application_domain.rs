@@ -2,8 +2,9 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::class::Class;
+use crate::avm2::domain::MIN_DOMAIN_MEMORY_LENGTH;
 use crate::avm2::method::Method;
-use crate::avm2::names::{Namespace, QName};
+use crate::avm2::names::{Multiname, Namespace, QName};
 use crate::avm2::object::{DomainObject, Object, TObject};
 use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
@@ -118,6 +119,58 @@ pub fn has_definition<'gc>(
     Ok(Value::Undefined)
 }
 
+/// `domainMemory` property's getter
+pub fn domain_memory<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(appdomain) = this.and_then(|this| this.as_application_domain()) {
+        return Ok(appdomain
+            .domain_memory()
+            .map(Value::from)
+            .unwrap_or(Value::Null));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// `domainMemory` property's setter
+///
+/// Setting `null` goes back to a blank buffer of `MIN_DOMAIN_MEMORY_LENGTH`
+/// bytes, which the domain memory opcodes create the next time they run.
+pub fn set_domain_memory<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(appdomain) = this.and_then(|this| this.as_application_domain()) {
+        let memory = match args.get(0).cloned().unwrap_or(Value::Null) {
+            Value::Null | Value::Undefined => None,
+            Value::Object(memory) => {
+                let length = memory
+                    .as_bytearray()
+                    .map(|bytes| bytes.len())
+                    .ok_or("TypeError: Error #1034: Type Coercion failed: expected a ByteArray")?;
+                if length < MIN_DOMAIN_MEMORY_LENGTH {
+                    return Err("RangeError: Error #1504: End of file.".into());
+                }
+
+                Some(memory)
+            }
+            _ => {
+                return Err(
+                    "TypeError: Error #1034: Type Coercion failed: expected a ByteArray".into(),
+                )
+            }
+        };
+
+        appdomain.set_domain_memory(activation.context.gc_context, memory);
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Construct `ApplicationDomain`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
     let class = Class::new(
@@ -146,6 +199,20 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         QName::new(Namespace::public(), "hasDefinition"),
         Method::from_builtin(has_definition),
     ));
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public(), "MIN_DOMAIN_MEMORY_LENGTH"),
+        Multiname::from(QName::new(Namespace::public(), "uint")),
+        Some((MIN_DOMAIN_MEMORY_LENGTH as u32).into()),
+    ));
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "domainMemory"),
+        Method::from_builtin(domain_memory),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "domainMemory"),
+        Method::from_builtin(set_domain_memory),
+    ));
 
     class
 }
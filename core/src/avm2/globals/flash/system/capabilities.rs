@@ -0,0 +1,235 @@
+//! `flash.system.Capabilities` builtin/prototype
+
+use crate::avm1::globals::system::SystemCapabilities;
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::Object;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.system.Capabilities`'s instance constructor.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Err("ArgumentError: Error #2012: Capabilities class cannot be instantiated.".into())
+}
+
+/// Implements `flash.system.Capabilities`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+macro_rules! capabilities_getter {
+    ($func_name: ident, $capability: expr) => {
+        pub fn $func_name<'gc>(
+            activation: &mut Activation<'_, 'gc, '_>,
+            _this: Option<Object<'gc>>,
+            _args: &[Value<'gc>],
+        ) -> Result<Value<'gc>, Error> {
+            Ok(activation.context.system.has_capability($capability).into())
+        }
+    };
+}
+
+macro_rules! inverse_capabilities_getter {
+    ($func_name: ident, $capability: expr) => {
+        pub fn $func_name<'gc>(
+            activation: &mut Activation<'_, 'gc, '_>,
+            _this: Option<Object<'gc>>,
+            _args: &[Value<'gc>],
+        ) -> Result<Value<'gc>, Error> {
+            Ok((!activation.context.system.has_capability($capability)).into())
+        }
+    };
+}
+
+capabilities_getter!(has_accessibility, SystemCapabilities::ACCESSIBILITY);
+capabilities_getter!(has_audio, SystemCapabilities::AUDIO);
+capabilities_getter!(has_audio_encoder, SystemCapabilities::AUDIO_ENCODER);
+capabilities_getter!(has_embedded_video, SystemCapabilities::EMBEDDED_VIDEO);
+capabilities_getter!(has_ime, SystemCapabilities::IME);
+capabilities_getter!(has_mp3, SystemCapabilities::MP3);
+capabilities_getter!(has_printing, SystemCapabilities::PRINTING);
+capabilities_getter!(has_screen_broadcast, SystemCapabilities::SCREEN_BROADCAST);
+capabilities_getter!(has_screen_playback, SystemCapabilities::SCREEN_PLAYBACK);
+capabilities_getter!(has_streaming_audio, SystemCapabilities::STREAMING_AUDIO);
+capabilities_getter!(has_streaming_video, SystemCapabilities::STREAMING_VIDEO);
+capabilities_getter!(has_tls, SystemCapabilities::TLS);
+capabilities_getter!(has_video_encoder, SystemCapabilities::VIDEO_ENCODER);
+capabilities_getter!(is_debugger, SystemCapabilities::DEBUGGER);
+capabilities_getter!(is_embedded_in_acrobat, SystemCapabilities::ACROBAT_EMBEDDED);
+capabilities_getter!(
+    supports_32_bit_processes,
+    SystemCapabilities::PROCESS_32_BIT
+);
+capabilities_getter!(
+    supports_64_bit_processes,
+    SystemCapabilities::PROCESS_64_BIT
+);
+inverse_capabilities_getter!(av_hardware_disable, SystemCapabilities::AV_HARDWARE);
+inverse_capabilities_getter!(local_file_read_disable, SystemCapabilities::LOCAL_FILE_READ);
+
+/// Implements `serverString`'s getter.
+pub fn server_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let server_string = activation
+        .context
+        .system
+        .get_server_string(activation.context.player_version);
+
+    Ok(AvmString::new(activation.context.gc_context, server_string).into())
+}
+
+/// Construct `Capabilities`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.system"), "Capabilities"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED | ClassAttributes::FINAL);
+
+    let getters: &[(&str, NativeMethod<'gc>)] = &[
+        ("avHardwareDisable", av_hardware_disable),
+        ("hasAccessibility", has_accessibility),
+        ("hasAudio", has_audio),
+        ("hasAudioEncoder", has_audio_encoder),
+        ("hasEmbeddedVideo", has_embedded_video),
+        ("hasIME", has_ime),
+        ("hasMP3", has_mp3),
+        ("hasPrinting", has_printing),
+        ("hasScreenBroadcast", has_screen_broadcast),
+        ("hasScreenPlayback", has_screen_playback),
+        ("hasStreamingAudio", has_streaming_audio),
+        ("hasStreamingVideo", has_streaming_video),
+        ("hasTLS", has_tls),
+        ("hasVideoEncoder", has_video_encoder),
+        ("isDebugger", is_debugger),
+        ("isEmbeddedInAcrobat", is_embedded_in_acrobat),
+        ("localFileReadDisable", local_file_read_disable),
+        ("serverString", server_string),
+        ("supports32BitProcesses", supports_32_bit_processes),
+        ("supports64BitProcesses", supports_64_bit_processes),
+    ];
+
+    for (name, getter) in getters {
+        write.define_class_trait(Trait::from_getter(
+            QName::new(Namespace::public(), *name),
+            Method::from_builtin(*getter),
+        ));
+    }
+
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::test_utils::with_avm2;
+
+    #[test]
+    fn capabilities_reflect_the_player() {
+        with_avm2(|activation| {
+            assert_eq!(has_audio(activation, None, &[]).unwrap(), true.into());
+            assert_eq!(
+                has_streaming_audio(activation, None, &[]).unwrap(),
+                true.into()
+            );
+            assert_eq!(
+                supports_32_bit_processes(activation, None, &[]).unwrap(),
+                true.into()
+            );
+            assert_eq!(has_printing(activation, None, &[]).unwrap(), false.into());
+            assert_eq!(
+                is_embedded_in_acrobat(activation, None, &[]).unwrap(),
+                false.into()
+            );
+            assert_eq!(
+                av_hardware_disable(activation, None, &[]).unwrap(),
+                true.into()
+            );
+
+            activation
+                .context
+                .system
+                .capabilities
+                .set(SystemCapabilities::MP3, false);
+            assert_eq!(has_mp3(activation, None, &[]).unwrap(), false.into());
+            activation
+                .context
+                .system
+                .capabilities
+                .insert(SystemCapabilities::MP3);
+            assert_eq!(has_mp3(activation, None, &[]).unwrap(), true.into());
+        });
+    }
+
+    #[test]
+    fn server_string_encodes_each_capability() {
+        with_avm2(|activation| {
+            activation.context.player_version = 32;
+            activation
+                .context
+                .system
+                .capabilities
+                .insert(SystemCapabilities::ACCESSIBILITY);
+
+            let server_string = server_string(activation, None, &[])
+                .unwrap()
+                .coerce_to_string(activation)
+                .unwrap()
+                .to_string();
+            let pairs: Vec<&str> = server_string.split('&').collect();
+            let keys: Vec<&str> = pairs
+                .iter()
+                .map(|pair| pair.split('=').next().unwrap())
+                .collect();
+            assert_eq!(
+                keys,
+                vec![
+                    "A", "SA", "SV", "EV", "MP3", "AE", "VE", "ACC", "PR", "SP", "SB", "DEB", "V",
+                    "M", "R", "COL", "AR", "OS", "ARCH", "L", "IME", "PR32", "PR64", "PT", "AVD",
+                    "LFD", "WD", "TLS", "ML", "DP",
+                ]
+            );
+            for expected in &[
+                "A=t",
+                "SA=t",
+                "ACC=t",
+                "PR=f",
+                "DEB=f",
+                "V=LNX%2032%2C0%2C0%2C0",
+                "AR=1.0",
+                "PR32=t",
+                "AVD=t",
+                "TLS=t",
+            ] {
+                assert!(
+                    pairs.contains(expected),
+                    "{} in {}",
+                    expected,
+                    server_string
+                );
+            }
+        });
+    }
+}
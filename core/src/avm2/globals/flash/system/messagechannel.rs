@@ -0,0 +1,290 @@
+//! `flash.system.MessageChannel` builtin/prototype
+//!
+//! Workers share the player's thread, so a channel is a single object that
+//! both its sender and receiver hold. Messages are copied through AMF as
+//! they are sent; `channelMessage` is dispatched on the next frame, see
+//! `Avm2::deliver_channel_messages`.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::amf::{Amf3Reader, Amf3Writer};
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::events::Event;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{ArrayObject, Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::{Avm2, Error};
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.system.MessageChannel`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        let array_proto = activation.context.avm2.prototypes().array;
+        let messages = ArrayObject::from_array(
+            ArrayStorage::new(0),
+            array_proto,
+            activation.context.gc_context,
+        );
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "messages"),
+            messages.into(),
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "state"),
+            "open".into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.system.MessageChannel`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct a new channel for messages from `sender` to `receiver`.
+pub fn new_message_channel<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    sender: Object<'gc>,
+    receiver: Object<'gc>,
+) -> Result<Object<'gc>, Error> {
+    let mut proto = activation.context.avm2.prototypes().message_channel;
+    let mut channel = proto.construct(activation, &[])?;
+    let constr = proto
+        .get_property(
+            proto,
+            &QName::new(Namespace::public(), "constructor"),
+            activation,
+        )?
+        .coerce_to_object(activation)?;
+
+    constr.call(Some(channel), &[], activation, Some(proto))?;
+
+    channel.set_property(
+        channel,
+        &QName::new(Namespace::Private("ruffle".into()), "sender"),
+        sender.into(),
+        activation,
+    )?;
+    channel.set_property(
+        channel,
+        &QName::new(Namespace::Private("ruffle".into()), "receiver"),
+        receiver.into(),
+        activation,
+    )?;
+
+    Ok(channel)
+}
+
+/// Copy a value so that it can be handed to another worker.
+///
+/// Workers and channels are passed along as they are; anything else goes
+/// through AMF, as it would between threads. This includes `ByteArray`s,
+/// which are never actually shared between workers.
+pub fn marshal<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<Value<'gc>, Error> {
+    if let Value::Object(object) = value {
+        let worker_proto = activation.context.avm2.prototypes().worker;
+        let channel_proto = activation.context.avm2.prototypes().message_channel;
+
+        if object.has_prototype_in_chain(worker_proto, false)?
+            || object.has_prototype_in_chain(channel_proto, false)?
+        {
+            return Ok(value);
+        }
+    }
+
+    let mut writer = Amf3Writer::new();
+    writer.write_value(activation, value)?;
+    let bytes = writer.into_bytes();
+
+    Amf3Reader::new(&bytes).read_value(activation)
+}
+
+/// Get the queue of messages that have been sent but not received.
+fn messages<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+) -> Result<Object<'gc>, Error> {
+    this.get_property(
+        this,
+        &QName::new(Namespace::Private("ruffle".into()), "messages"),
+        activation,
+    )?
+    .coerce_to_object(activation)
+}
+
+/// Implements `state`'s getter.
+pub fn state<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "state"),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `messageAvailable`'s getter.
+pub fn message_available<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let messages = messages(activation, this)?;
+        let available = messages
+            .as_array_storage()
+            .map(|storage| storage.length() > 0)
+            .unwrap_or(false);
+
+        return Ok(available.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `MessageChannel.send`.
+///
+/// The message is queued no matter how many are already waiting: a sender
+/// can't be made to wait for the receiver on a single thread, so
+/// `queueLimit` is ignored.
+pub fn send<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if state(activation, Some(this), &[])? != "open".into() {
+            return Err("IllegalOperationError: The MessageChannel is closed.".into());
+        }
+
+        let message = marshal(activation, args.get(0).cloned().unwrap_or(Value::Undefined))?;
+        let messages = messages(activation, this)?;
+        if let Some(mut storage) = messages.as_array_storage_mut(activation.context.gc_context) {
+            storage.push(message);
+        }
+
+        let pending = &mut activation.context.avm2.pending_channels;
+        if !pending.iter().any(|channel| Object::ptr_eq(*channel, this)) {
+            pending.push(this);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `MessageChannel.receive`.
+///
+/// Waiting for a message would stop the sender from ever running, so when
+/// none is available this yields `null` even if `blockUntilReceived` is
+/// set.
+pub fn receive<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let messages = messages(activation, this)?;
+        if let Some(mut storage) = messages.as_array_storage_mut(activation.context.gc_context) {
+            if storage.length() > 0 {
+                return Ok(storage.shift());
+            }
+        }
+
+        return Ok(Value::Null);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `MessageChannel.close`.
+///
+/// Messages that were sent before the channel closed can still be
+/// received.
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        if state(activation, Some(this), &[])? == "closed".into() {
+            return Ok(Value::Undefined);
+        }
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "state"),
+            "closed".into(),
+            activation,
+        )?;
+
+        Avm2::dispatch_event(&mut activation.context, Event::new("channelState"), this)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `MessageChannel`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.system"), "MessageChannel"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED | ClassAttributes::FINAL);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "state"),
+        Method::from_builtin(state),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "messageAvailable"),
+        Method::from_builtin(message_available),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "send"),
+        Method::from_builtin(send),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "receive"),
+        Method::from_builtin(receive),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "close"),
+        Method::from_builtin(close),
+    ));
+
+    class
+}
@@ -0,0 +1,112 @@
+//! `flash.system.System` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::Object;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.system.System`'s instance constructor.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Err("ArgumentError: Error #2012: System class cannot be instantiated.".into())
+}
+
+/// Implements `flash.system.System`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `System.useCodePage`'s getter.
+pub fn use_code_page<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(activation.context.system.use_codepage.into())
+}
+
+/// Implements `System.useCodePage`'s setter.
+///
+/// This is shared with AVM1's `System.useCodePage`. It only affects text
+/// files that finish loading after it is set.
+pub fn set_use_code_page<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    activation.context.system.use_codepage = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_boolean();
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `System`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.system"), "System"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED | ClassAttributes::FINAL);
+
+    write.define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "useCodePage"),
+        Method::from_builtin(use_code_page),
+    ));
+    write.define_class_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "useCodePage"),
+        Method::from_builtin(set_use_code_page),
+    ));
+
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::test_utils::with_avm2;
+
+    #[test]
+    fn use_code_page_changes_text_decoding() {
+        with_avm2(|activation| {
+            let text = b"caf\xe9";
+            assert_eq!(activation.context.system.decode_text(text), "caf\u{fffd}");
+
+            set_use_code_page(activation, None, &[true.into()]).unwrap();
+            assert_eq!(use_code_page(activation, None, &[]).unwrap(), true.into());
+            assert_eq!(activation.context.system.decode_text(text), "café");
+
+            // A byte order mark still wins.
+            assert_eq!(
+                activation
+                    .context
+                    .system
+                    .decode_text(b"\xef\xbb\xbfcaf\xc3\xa9"),
+                "café"
+            );
+
+            set_use_code_page(activation, None, &[false.into()]).unwrap();
+            assert_eq!(activation.context.system.decode_text(text), "caf\u{fffd}");
+        });
+    }
+}
@@ -0,0 +1,638 @@
+//! `flash.system.Worker` builtin/prototype
+//!
+//! Ruffle runs workers cooperatively on the player's own thread. A started
+//! worker's movie is loaded into its own application domain, off the display
+//! list, and runs a frame every time the stage does.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::domain::Domain;
+use crate::avm2::events::Event;
+use crate::avm2::globals::flash::system::messagechannel::{marshal, new_message_channel};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{ByteArrayObject, Object, ScriptObject, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::{Avm2, Error};
+use crate::character::Character;
+use crate::display_object::{DisplayObject, MorphShape, MovieClip, TDisplayObject};
+use crate::tag_utils::SwfMovie;
+use crate::vminterface::Instantiator;
+use gc_arena::{GcCell, MutationContext};
+use std::sync::Arc;
+
+/// Implements `flash.system.Worker`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        let shared_properties = ScriptObject::object(
+            activation.context.gc_context,
+            activation.context.avm2.prototypes().object,
+        );
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "state"),
+            "new".into(),
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "isPrimordial"),
+            false.into(),
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "swf"),
+            Value::Null,
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "sharedProperties"),
+            shared_properties.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.system.Worker`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct a new worker that will run the given movie when started.
+pub fn new_worker<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    swf: &[u8],
+) -> Result<Object<'gc>, Error> {
+    let mut proto = activation.context.avm2.prototypes().worker;
+    let mut worker = proto.construct(activation, &[])?;
+    let constr = proto
+        .get_property(
+            proto,
+            &QName::new(Namespace::public(), "constructor"),
+            activation,
+        )?
+        .coerce_to_object(activation)?;
+
+    constr.call(Some(worker), &[], activation, Some(proto))?;
+
+    let bytearray = ByteArrayObject::construct(
+        activation.context.gc_context,
+        Some(activation.context.avm2.prototypes().bytearray),
+    );
+    if let Some(mut storage) = bytearray.as_bytearray_mut(activation.context.gc_context) {
+        storage.write_bytes(swf);
+    }
+
+    worker.set_property(
+        worker,
+        &QName::new(Namespace::Private("ruffle".into()), "swf"),
+        bytearray.into(),
+        activation,
+    )?;
+
+    Ok(worker)
+}
+
+/// Get the worker that the player's own movie runs in.
+pub fn primordial_worker<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Object<'gc>, Error> {
+    if let Some(worker) = activation.context.avm2.primordial_worker {
+        return Ok(worker);
+    }
+
+    let mut worker = new_worker(activation, &[])?;
+    worker.set_property(
+        worker,
+        &QName::new(Namespace::Private("ruffle".into()), "isPrimordial"),
+        true.into(),
+        activation,
+    )?;
+    worker.set_property(
+        worker,
+        &QName::new(Namespace::Private("ruffle".into()), "state"),
+        "running".into(),
+        activation,
+    )?;
+    worker.set_property(
+        worker,
+        &QName::new(Namespace::Private("ruffle".into()), "swf"),
+        Value::Null,
+        activation,
+    )?;
+    activation.context.avm2.primordial_worker = Some(worker);
+
+    Ok(worker)
+}
+
+/// Implements `Worker.current`.
+pub fn current<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(worker) = activation.context.avm2.current_worker {
+        return Ok(worker.into());
+    }
+
+    Ok(primordial_worker(activation)?.into())
+}
+
+/// Implements `Worker.isSupported`.
+pub fn is_supported<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(true.into())
+}
+
+/// Implements `isPrimordial`'s getter.
+pub fn is_primordial<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "isPrimordial"),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `state`'s getter.
+pub fn state<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "state"),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Change a worker's state and announce it with a `workerState` event.
+fn set_state<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+    state: &'static str,
+) -> Result<(), Error> {
+    this.set_property(
+        this,
+        &QName::new(Namespace::Private("ruffle".into()), "state"),
+        state.into(),
+        activation,
+    )?;
+
+    Avm2::dispatch_event(&mut activation.context, Event::new("workerState"), this)?;
+
+    Ok(())
+}
+
+/// Implements `Worker.createMessageChannel`.
+pub fn create_message_channel<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let worker_proto = activation.context.avm2.prototypes().worker;
+        let receiver = match args.get(0) {
+            Some(Value::Object(receiver))
+                if receiver.has_prototype_in_chain(worker_proto, false)? =>
+            {
+                *receiver
+            }
+            _ => {
+                return Err(
+                    "TypeError: Error #1034: Type Coercion failed: expected a Worker".into(),
+                )
+            }
+        };
+
+        return Ok(new_message_channel(activation, this, receiver)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Worker.setSharedProperty`.
+///
+/// The value is copied as it would be sent over a `MessageChannel`.
+pub fn set_shared_property<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let key = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let value = marshal(activation, args.get(1).cloned().unwrap_or(Value::Undefined))?;
+        let mut shared_properties = this
+            .get_property(
+                this,
+                &QName::new(Namespace::Private("ruffle".into()), "sharedProperties"),
+                activation,
+            )?
+            .coerce_to_object(activation)?;
+
+        shared_properties.set_property(
+            shared_properties,
+            &QName::new(Namespace::public(), key),
+            value,
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Worker.getSharedProperty`.
+pub fn get_shared_property<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let key = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let mut shared_properties = this
+            .get_property(
+                this,
+                &QName::new(Namespace::Private("ruffle".into()), "sharedProperties"),
+                activation,
+            )?
+            .coerce_to_object(activation)?;
+
+        return shared_properties.get_property(
+            shared_properties,
+            &QName::new(Namespace::public(), key),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Worker.start`.
+///
+/// The worker's movie is loaded right away, but its first frame runs along
+/// with the stage's next one. Starting a worker that has already been
+/// started does nothing.
+pub fn start<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        if state(activation, Some(this), &[])? != "new".into() {
+            return Ok(Value::Undefined);
+        }
+
+        let swf = this
+            .get_property(
+                this,
+                &QName::new(Namespace::Private("ruffle".into()), "swf"),
+                activation,
+            )?
+            .coerce_to_object(activation)?;
+        let bytes = swf
+            .as_bytearray()
+            .map(|bytearray| bytearray.bytes().to_vec())
+            .unwrap_or_default();
+
+        let movie = match SwfMovie::from_data(&bytes, None) {
+            Ok(movie) => Arc::new(movie),
+            Err(e) => {
+                log::warn!("Unable to start worker: {}", e);
+                set_state(activation, this, "terminated")?;
+
+                return Ok(Value::Undefined);
+            }
+        };
+
+        let domain = Domain::movie_domain(
+            activation.context.gc_context,
+            activation.context.avm2.global_domain(),
+        );
+        activation
+            .context
+            .library
+            .library_for_movie_mut(movie.clone())
+            .set_avm2_domain(domain);
+
+        let clip = MovieClip::from_movie(activation.context.gc_context, movie.clone());
+        let root: DisplayObject<'gc> = clip.into();
+        let previous = activation.context.avm2.set_current_worker(Some(this));
+        root.post_instantiation(
+            &mut activation.context,
+            root,
+            None,
+            Instantiator::Movie,
+            false,
+        );
+
+        let mut morph_shapes = fnv::FnvHashMap::default();
+        clip.preload(&mut activation.context, &mut morph_shapes);
+        for (id, static_data) in morph_shapes {
+            let morph_shape = MorphShape::new(activation.context.gc_context, static_data);
+            activation
+                .context
+                .library
+                .library_for_movie_mut(movie.clone())
+                .register_character(id, Character::MorphShape(morph_shape));
+        }
+        activation.context.avm2.set_current_worker(previous);
+        activation.context.avm2.workers.push((this, root));
+
+        set_state(activation, this, "running")?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Worker.terminate`.
+///
+/// The primordial worker can't be terminated, nor can a worker be
+/// terminated twice.
+pub fn terminate<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if is_primordial(activation, Some(this), &[])?.coerce_to_boolean()
+            || state(activation, Some(this), &[])? == "terminated".into()
+        {
+            return Ok(false.into());
+        }
+
+        activation
+            .context
+            .avm2
+            .workers
+            .retain(|(worker, _)| !Object::ptr_eq(*worker, this));
+        set_state(activation, this, "terminated")?;
+
+        return Ok(true.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Worker`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.system"), "Worker"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED | ClassAttributes::FINAL);
+
+    write.define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "current"),
+        Method::from_builtin(current),
+    ));
+    write.define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "isSupported"),
+        Method::from_builtin(is_supported),
+    ));
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "isPrimordial"),
+        Method::from_builtin(is_primordial),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "state"),
+        Method::from_builtin(state),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "createMessageChannel"),
+        Method::from_builtin(create_message_channel),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "setSharedProperty"),
+        Method::from_builtin(set_shared_property),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "getSharedProperty"),
+        Method::from_builtin(get_shared_property),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "start"),
+        Method::from_builtin(start),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "terminate"),
+        Method::from_builtin(terminate),
+    ));
+
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::array::ArrayStorage;
+    use crate::avm2::object::ArrayObject;
+    use crate::avm2::test_utils::{call, class, function, get, TestPlayer};
+
+    /// A movie that traces `Hello world!` once it runs.
+    const WORKER_SWF: &[u8] = include_bytes!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/swfs/avm2/hello_world/test.swf"
+    ));
+
+    /// Get the `current` instance of a class in `flash.system`.
+    fn current_of<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        name: &'static str,
+    ) -> Object<'gc> {
+        let class = class(activation, "flash.system", name);
+
+        get(activation, class, "current")
+            .coerce_to_object(activation)
+            .unwrap()
+    }
+
+    /// Runs in the worker: sends every message it gets straight back.
+    fn echo<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let worker = current_of(activation, "Worker");
+        assert_eq!(
+            is_primordial(activation, Some(worker), &[])?,
+            false.into(),
+            "messages to the worker are handled in the worker"
+        );
+
+        let event = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let to_worker = get(activation, event, "target").coerce_to_object(activation)?;
+        let message = call(activation, to_worker, "receive", &[]);
+        let from_worker = call(
+            activation,
+            worker,
+            "getSharedProperty",
+            &["fromWorker".into()],
+        )
+        .coerce_to_object(activation)?;
+        call(activation, from_worker, "send", &[message]);
+
+        Ok(Value::Undefined)
+    }
+
+    /// Runs in the primordial worker: traces the first element of every
+    /// reply.
+    fn trace_reply<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let event = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let from_worker = get(activation, event, "target").coerce_to_object(activation)?;
+        let reply = call(activation, from_worker, "receive", &[]).coerce_to_object(activation)?;
+        let text = get(activation, reply, "0").coerce_to_string(activation)?;
+
+        activation.context.log.avm_trace(&text);
+
+        Ok(Value::Undefined)
+    }
+
+    #[test]
+    fn worker_echoes_a_copy_of_the_message() {
+        let player = TestPlayer::new();
+
+        player.update(|activation| {
+            let swf = ByteArrayObject::construct(
+                activation.context.gc_context,
+                Some(activation.avm2().prototypes().bytearray),
+            );
+            swf.as_bytearray_mut(activation.context.gc_context)
+                .unwrap()
+                .write_bytes(WORKER_SWF);
+
+            let worker_domain = current_of(activation, "WorkerDomain");
+            let worker = call(activation, worker_domain, "createWorker", &[swf.into()])
+                .coerce_to_object(activation)
+                .unwrap();
+            let primordial = current_of(activation, "Worker");
+
+            let to_worker = call(
+                activation,
+                primordial,
+                "createMessageChannel",
+                &[worker.into()],
+            )
+            .coerce_to_object(activation)
+            .unwrap();
+            let from_worker = call(
+                activation,
+                worker,
+                "createMessageChannel",
+                &[primordial.into()],
+            )
+            .coerce_to_object(activation)
+            .unwrap();
+            call(
+                activation,
+                worker,
+                "setSharedProperty",
+                &["fromWorker".into(), from_worker.into()],
+            );
+
+            let echo = function(activation, echo);
+            let trace_reply = function(activation, trace_reply);
+            call(
+                activation,
+                to_worker,
+                "addEventListener",
+                &["channelMessage".into(), echo.into()],
+            );
+            call(
+                activation,
+                from_worker,
+                "addEventListener",
+                &["channelMessage".into(), trace_reply.into()],
+            );
+
+            call(activation, worker, "start", &[]);
+            assert_eq!(
+                state(activation, Some(worker), &[]).unwrap(),
+                "running".into()
+            );
+
+            let mut message = ArrayObject::from_array(
+                ArrayStorage::new(0),
+                activation.avm2().prototypes().array,
+                activation.context.gc_context,
+            );
+            let first = QName::new(Namespace::public(), "0");
+            message
+                .set_property(message, &first, "ping".into(), activation)
+                .unwrap();
+            call(activation, to_worker, "send", &[message.into()]);
+            message
+                .set_property(message, &first, "changed".into(), activation)
+                .unwrap();
+        });
+
+        player.lock().run_frame();
+        assert!(
+            !player.traces().contains(&"ping".to_string()),
+            "the reply is only sent once the worker has seen the message"
+        );
+
+        player.lock().run_frame();
+        assert_eq!(
+            player.traces(),
+            vec!["Hello world!".to_string(), "ping".to_string()]
+        );
+    }
+}
@@ -0,0 +1,168 @@
+//! `flash.system.WorkerDomain` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::flash::system::worker::{new_worker, primordial_worker};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{ArrayObject, Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.system.WorkerDomain`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.system.WorkerDomain`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `WorkerDomain.current`.
+///
+/// Every worker shares the one domain, which is created the first time it
+/// is asked for and kept on the class from then on.
+pub fn current<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let name = QName::new(Namespace::Private("ruffle".into()), "current");
+        let existing = this.get_property(this, &name, activation)?;
+        if let Value::Object(_) = existing {
+            return Ok(existing);
+        }
+
+        let mut proto = this
+            .get_property(
+                this,
+                &QName::new(Namespace::public(), "prototype"),
+                activation,
+            )?
+            .coerce_to_object(activation)?;
+        let domain = proto.construct(activation, &[])?;
+        let constr = proto
+            .get_property(
+                proto,
+                &QName::new(Namespace::public(), "constructor"),
+                activation,
+            )?
+            .coerce_to_object(activation)?;
+
+        constr.call(Some(domain), &[], activation, Some(proto))?;
+        this.set_property(this, &name, domain.into(), activation)?;
+
+        return Ok(domain.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `WorkerDomain.isSupported`.
+pub fn is_supported<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(true.into())
+}
+
+/// Implements `WorkerDomain.createWorker`.
+///
+/// The movie is copied, so later changes to the `ByteArray` don't affect
+/// the worker. Every worker gets the same privileges, so
+/// `giveAppPrivileges` is ignored.
+pub fn create_worker<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let swf = match args.get(0) {
+        Some(Value::Object(swf)) => swf.as_bytearray().map(|swf| swf.bytes().to_vec()),
+        _ => None,
+    }
+    .ok_or("TypeError: Error #1034: Type Coercion failed: expected a ByteArray")?;
+
+    Ok(new_worker(activation, &swf)?.into())
+}
+
+/// Implements `WorkerDomain.listWorkers`.
+///
+/// This lists the primordial worker, followed by every worker that is
+/// running.
+pub fn list_workers<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let mut workers = vec![primordial_worker(activation)?.into()];
+    workers.extend(
+        activation
+            .context
+            .avm2
+            .workers()
+            .into_iter()
+            .map(|(worker, _)| worker.into()),
+    );
+
+    let array_proto = activation.context.avm2.prototypes().array;
+
+    Ok(ArrayObject::from_array(
+        ArrayStorage::from_args(&workers),
+        array_proto,
+        activation.context.gc_context,
+    )
+    .into())
+}
+
+/// Construct `WorkerDomain`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.system"), "WorkerDomain"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED | ClassAttributes::FINAL);
+
+    write.define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "current"),
+        Method::from_builtin(current),
+    ));
+    write.define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "isSupported"),
+        Method::from_builtin(is_supported),
+    ));
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "createWorker"),
+        Method::from_builtin(create_worker),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "listWorkers"),
+        Method::from_builtin(list_workers),
+    ));
+
+    class
+}
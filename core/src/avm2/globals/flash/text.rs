@@ -0,0 +1,6 @@
+//! `flash.text` namespace
+
+pub mod antialiastype;
+pub mod engine;
+pub mod gridfittype;
+pub mod textfield;
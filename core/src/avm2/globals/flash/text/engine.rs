@@ -0,0 +1,11 @@
+//! `flash.text.engine` namespace
+//!
+//! Only enough of the Flash Text Engine is here to lay out plain text from
+//! left to right, in a single font per block.
+
+pub mod contentelement;
+pub mod elementformat;
+pub mod fontdescription;
+pub mod textblock;
+pub mod textelement;
+pub mod textline;
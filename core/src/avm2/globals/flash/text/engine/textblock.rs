@@ -0,0 +1,456 @@
+//! `flash.text.engine.TextBlock` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::flash::text::engine::textline::{
+    self, line_property, set_line_property, LineMetrics, MAX_LINE_WIDTH,
+};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::font::{EvalParameters, Font};
+use crate::html::TextFormat;
+use gc_arena::{GcCell, MutationContext};
+use swf::Twips;
+
+/// Implements `flash.text.engine.TextBlock`'s instance constructor.
+///
+/// Only `content` is kept; the arguments after it are ignored.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        let content = match args.get(0).cloned().unwrap_or(Value::Null) {
+            Value::Undefined | Value::Null => Value::Null,
+            content => content.coerce_to_object(activation)?.into(),
+        };
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), "content"),
+            content,
+            activation,
+        )?;
+        for name in &["firstLine", "lastLine"] {
+            this.set_property(
+                this,
+                &QName::new(Namespace::Private("ruffle".into()), *name),
+                Value::Null,
+                activation,
+            )?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.text.engine.TextBlock`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `firstLine`'s getter.
+pub fn first_line<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "firstLine"),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `lastLine`'s getter.
+pub fn last_line<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "lastLine"),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Get a public property of an object, treating `null` and `undefined`
+/// objects as having no properties.
+fn property<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: Value<'gc>,
+    name: &'static str,
+) -> Result<Value<'gc>, Error> {
+    match object {
+        Value::Object(mut object) => {
+            object.get_property(object, &QName::new(Namespace::public(), name), activation)
+        }
+        _ => Ok(Value::Undefined),
+    }
+}
+
+/// Get the text format an `ElementFormat` describes, along with the font it
+/// will be drawn in.
+///
+/// Embedded fonts are looked up by name, falling back to the device font as
+/// `TextField`s do.
+fn format_and_font<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    element_format: Value<'gc>,
+) -> Result<(TextFormat, Option<Font<'gc>>), Error> {
+    let font_description = property(activation, element_format.clone(), "fontDescription")?;
+
+    let font_name = match property(activation, font_description.clone(), "fontName")? {
+        Value::Undefined | Value::Null => "_serif".to_string(),
+        name => name.coerce_to_string(activation)?.to_string(),
+    };
+    let bold = property(activation, font_description.clone(), "fontWeight")? == "bold".into();
+    let italic = property(activation, font_description, "fontPosture")? == "italic".into();
+    let size = match property(activation, element_format.clone(), "fontSize")? {
+        Value::Undefined | Value::Null => 12.0,
+        size => size.coerce_to_number(activation)?,
+    };
+    let color = match property(activation, element_format, "color")? {
+        Value::Undefined | Value::Null => 0,
+        color => color.coerce_to_u32(activation)?,
+    };
+
+    let movie = activation.context.swf.clone();
+    let font = activation
+        .context
+        .library
+        .library_for_movie_mut(movie)
+        .get_font_by_name(&font_name, bold, italic)
+        .filter(|font| font.has_glyphs())
+        .or_else(|| activation.context.library.device_font());
+
+    let format = TextFormat {
+        font: Some(font_name),
+        size: Some(size),
+        color: Some(swf::Color {
+            r: (color >> 16) as u8,
+            g: (color >> 8) as u8,
+            b: color as u8,
+            a: 0xFF,
+        }),
+        bold: Some(bold),
+        italic: Some(italic),
+        ..Default::default()
+    };
+
+    Ok((format, font))
+}
+
+/// Find how much of `text` fits on a line `width` pixels wide.
+///
+/// Lines break after the last word that fits, keeping the spaces after it;
+/// a word too wide for a line of its own is broken between characters. This
+/// yields the length of the line in bytes, which is `0` if not even one
+/// character fits.
+fn fit_line(font: Font<'_>, text: &str, params: EvalParameters, width: f64) -> usize {
+    let end = match font.wrap_line(text, params, Twips::from_pixels(width), Twips::new(0), true) {
+        Some(end) => end,
+        None => return text.len(),
+    };
+
+    if end == 0 {
+        return 0;
+    }
+
+    end + text[end..].len() - text[end..].trim_start_matches(' ').len()
+}
+
+/// Implements `TextBlock.createTextLine`.
+///
+/// The line starts where `previousLine` ends, or at the start of the
+/// content. This yields `null` once all of the content has been laid out,
+/// or if not even one character fits in `width`, unless `fitSomething` is
+/// set. `lineOffset` is ignored, as tabs aren't laid out.
+pub fn create_text_line<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let previous_line = match args.get(0).cloned().unwrap_or(Value::Null) {
+            Value::Undefined | Value::Null => None,
+            previous_line => Some(previous_line.coerce_to_object(activation)?),
+        };
+        let width = args
+            .get(1)
+            .cloned()
+            .unwrap_or_else(|| MAX_LINE_WIDTH.into())
+            .coerce_to_number(activation)?;
+        let fit_something = args
+            .get(3)
+            .cloned()
+            .unwrap_or_else(|| false.into())
+            .coerce_to_boolean();
+
+        if !(0.0..=MAX_LINE_WIDTH).contains(&width) {
+            return Err("ArgumentError: Error #2004: One of the parameters is invalid.".into());
+        }
+
+        let content = this.get_property(
+            this,
+            &QName::new(Namespace::public(), "content"),
+            activation,
+        )?;
+        let text = match property(activation, content.clone(), "text")? {
+            Value::Undefined | Value::Null => String::new(),
+            text => text.coerce_to_string(activation)?.to_string(),
+        };
+
+        let begin_index = match previous_line {
+            Some(previous_line) => {
+                line_property(activation, previous_line, "textBlockBeginIndex")?
+                    .coerce_to_u32(activation)? as usize
+                    + line_property(activation, previous_line, "rawTextLength")?
+                        .coerce_to_u32(activation)? as usize
+            }
+            None => 0,
+        };
+        let remaining = match text.char_indices().nth(begin_index) {
+            Some((start, _)) => &text[start..],
+            None => return Ok(Value::Null),
+        };
+
+        let element_format = property(activation, content, "elementFormat")?;
+        let (format, font) = format_and_font(activation, element_format)?;
+        let font = match font {
+            Some(font) => font,
+            None => return Ok(Value::Null),
+        };
+        let height = Twips::from_pixels(format.size.unwrap_or(12.0));
+        let params = EvalParameters::from_parts(height, Twips::new(0), true);
+
+        let mut length = fit_line(font, remaining, params, width);
+        if length == 0 {
+            if !fit_something {
+                return Ok(Value::Null);
+            }
+
+            length = remaining.chars().next().map(char::len_utf8).unwrap_or(0);
+        }
+
+        let line_text = &remaining[..length];
+        let metrics = LineMetrics {
+            ascent: font.get_baseline_for_height(height).to_pixels(),
+            descent: font.get_descent_for_height(height).to_pixels(),
+            text_width: font.measure(line_text, params, false).0.to_pixels(),
+        };
+        let line =
+            textline::new_text_line(activation, this, begin_index, line_text, format, &metrics)?;
+
+        match previous_line {
+            Some(previous_line) => {
+                set_line_property(activation, previous_line, "nextLine", line.into())?;
+                set_line_property(activation, line, "previousLine", previous_line.into())?;
+            }
+            None => {
+                this.set_property(
+                    this,
+                    &QName::new(Namespace::Private("ruffle".into()), "firstLine"),
+                    line.into(),
+                    activation,
+                )?;
+            }
+        }
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "lastLine"),
+            line.into(),
+            activation,
+        )?;
+
+        return Ok(line.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `TextBlock`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.text.engine"), "TextBlock"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED | ClassAttributes::FINAL);
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "content"),
+        QName::new(Namespace::package("flash.text.engine"), "ContentElement").into(),
+        None,
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "firstLine"),
+        Method::from_builtin(first_line),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "lastLine"),
+        Method::from_builtin(last_line),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "createTextLine"),
+        Method::from_builtin(create_text_line),
+    ));
+
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::test_utils::{construct, get, with_avm2};
+    use crate::player::{Player, DEVICE_FONT_TAG};
+
+    /// Load the device font, which only happens when a movie is loaded.
+    fn load_device_font(activation: &mut Activation<'_, '_, '_>) {
+        let font = Player::load_device_font(
+            activation.context.gc_context,
+            DEVICE_FONT_TAG,
+            activation.context.renderer,
+        )
+        .unwrap();
+
+        activation.context.library.set_device_font(Some(font));
+    }
+
+    fn text_block<'gc>(activation: &mut Activation<'_, 'gc, '_>, text: &str) -> Object<'gc> {
+        load_device_font(activation);
+        let format = construct(
+            activation,
+            "flash.text.engine",
+            "ElementFormat",
+            &[Value::Null, 12.0.into()],
+        );
+        let text = crate::avm2::string::AvmString::new(activation.context.gc_context, text);
+        let element = construct(
+            activation,
+            "flash.text.engine",
+            "TextElement",
+            &[text.into(), format.into()],
+        );
+
+        construct(
+            activation,
+            "flash.text.engine",
+            "TextBlock",
+            &[element.into()],
+        )
+    }
+
+    fn create_line<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        block: Object<'gc>,
+        previous_line: Option<Object<'gc>>,
+        width: f64,
+    ) -> Option<Object<'gc>> {
+        let previous_line = previous_line.map(Value::from).unwrap_or(Value::Null);
+
+        match create_text_line(activation, Some(block), &[previous_line, width.into()]).unwrap() {
+            Value::Object(line) => Some(line),
+            _ => None,
+        }
+    }
+
+    /// Measure `text` in the device font at 12 pixels, as lines are laid out.
+    fn measure(activation: &mut Activation<'_, '_, '_>, text: &str) -> f64 {
+        let params = EvalParameters::from_parts(Twips::from_pixels(12.0), Twips::new(0), true);
+
+        activation
+            .context
+            .library
+            .device_font()
+            .unwrap()
+            .measure(text, params, false)
+            .0
+            .to_pixels()
+    }
+
+    #[test]
+    fn short_text_fits_on_one_line() {
+        with_avm2(|activation| {
+            let block = text_block(activation, "Hello world");
+            let line = create_line(activation, block, None, MAX_LINE_WIDTH).unwrap();
+
+            assert!(line.as_display_object().is_some());
+            assert_eq!(get(activation, line, "textBlockBeginIndex"), 0.into());
+            assert_eq!(get(activation, line, "rawTextLength"), 11.into());
+            assert_eq!(
+                get(activation, line, "textWidth"),
+                measure(activation, "Hello world").into()
+            );
+            assert_eq!(get(activation, block, "firstLine"), line.into());
+            assert_eq!(get(activation, block, "lastLine"), line.into());
+
+            assert!(create_line(activation, block, Some(line), MAX_LINE_WIDTH).is_none());
+        });
+    }
+
+    #[test]
+    fn text_breaks_onto_a_second_line() {
+        with_avm2(|activation| {
+            let block = text_block(activation, "Hello world");
+            let width = measure(activation, "Hello wo");
+
+            let first = create_line(activation, block, None, width).unwrap();
+            assert_eq!(get(activation, first, "rawTextLength"), 6.into());
+
+            let second = create_line(activation, block, Some(first), width).unwrap();
+            assert_eq!(get(activation, second, "textBlockBeginIndex"), 6.into());
+            assert_eq!(get(activation, second, "rawTextLength"), 5.into());
+            assert_eq!(get(activation, first, "nextLine"), second.into());
+            assert_eq!(get(activation, second, "previousLine"), first.into());
+            assert_eq!(get(activation, block, "firstLine"), first.into());
+            assert_eq!(get(activation, block, "lastLine"), second.into());
+
+            assert!(create_line(activation, block, Some(second), width).is_none());
+        });
+    }
+
+    #[test]
+    fn nothing_fits_in_a_too_narrow_line() {
+        with_avm2(|activation| {
+            let block = text_block(activation, "Hello");
+
+            assert!(create_line(activation, block, None, 1.0).is_none());
+
+            let line = create_text_line(
+                activation,
+                Some(block),
+                &[Value::Null, 1.0.into(), 0.0.into(), true.into()],
+            )
+            .unwrap()
+            .coerce_to_object(activation)
+            .unwrap();
+            assert_eq!(get(activation, line, "rawTextLength"), 1.into());
+        });
+    }
+}
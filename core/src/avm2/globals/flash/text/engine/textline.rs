@@ -0,0 +1,227 @@
+//! `flash.text.engine.TextLine` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, StageObject, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::display_object::{AutoSizeMode, EditText, MovieClip, TDisplayObjectContainer};
+use crate::html::TextFormat;
+use crate::tag_utils::SwfSlice;
+use gc_arena::{GcCell, MutationContext};
+
+/// The widest line that `TextBlock.createTextLine` will lay out.
+pub const MAX_LINE_WIDTH: f64 = 1_000_000.0;
+
+/// The space `EditText` leaves between its bounds and its text.
+const GUTTER: f64 = 2.0;
+
+/// Implements `flash.text.engine.TextLine`'s instance constructor.
+///
+/// Lines can only be made by `TextBlock.createTextLine`, which gives them
+/// their display object before they are constructed.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if this.as_display_object().is_none() {
+            return Err(
+                "ArgumentError: Error #2012: TextLine class cannot be instantiated.".into(),
+            );
+        }
+
+        activation.super_init(this, &[])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.text.engine.TextLine`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// The metrics of a laid out line, in pixels.
+pub struct LineMetrics {
+    pub ascent: f64,
+    pub descent: f64,
+    pub text_width: f64,
+}
+
+/// Construct a `TextLine` showing `text` in `format`, with its baseline at
+/// the origin.
+///
+/// The line holds the text of `text_block` from character `begin_index`. It
+/// isn't linked to any other line yet.
+pub fn new_text_line<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    text_block: Object<'gc>,
+    begin_index: usize,
+    text: &str,
+    format: TextFormat,
+    metrics: &LineMetrics,
+) -> Result<Object<'gc>, Error> {
+    let movie = activation.context.swf.clone();
+    let mut clip = MovieClip::new(
+        SwfSlice::empty(movie.clone()),
+        activation.context.gc_context,
+    );
+
+    let field = EditText::new(
+        &mut activation.context,
+        movie,
+        -GUTTER,
+        -GUTTER - metrics.ascent,
+        metrics.text_width + GUTTER * 2.0,
+        metrics.ascent + metrics.descent + GUTTER * 2.0,
+    );
+    field.set_selectable(false, &mut activation.context);
+    field.set_new_text_format(format, &mut activation.context);
+    field.set_text(text.to_string(), &mut activation.context)?;
+    field.set_autosize(AutoSizeMode::Left, &mut activation.context);
+    clip.insert_at_index(&mut activation.context, field.into(), 0);
+
+    let mut proto = activation.context.avm2.prototypes().text_line;
+    let constr = proto
+        .get_property(
+            proto,
+            &QName::new(Namespace::public(), "constructor"),
+            activation,
+        )?
+        .coerce_to_object(activation)?;
+    let line: Object<'gc> =
+        StageObject::for_display_object(activation.context.gc_context, clip.into(), proto).into();
+
+    constr.call(Some(line), &[], activation, Some(proto))?;
+    clip.set_avm2_object(activation.context.gc_context, line);
+
+    for (name, value) in &[
+        ("textBlock", text_block.into()),
+        ("textBlockBeginIndex", (begin_index as u32).into()),
+        ("rawTextLength", (text.chars().count() as u32).into()),
+        ("ascent", metrics.ascent.into()),
+        ("descent", metrics.descent.into()),
+        ("textWidth", metrics.text_width.into()),
+        ("textHeight", (metrics.ascent + metrics.descent).into()),
+        ("previousLine", Value::Null),
+        ("nextLine", Value::Null),
+    ] {
+        set_line_property(activation, line, name, value.clone())?;
+    }
+
+    Ok(line)
+}
+
+/// Set one of the read-only properties of a line.
+pub fn set_line_property<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut line: Object<'gc>,
+    name: &'static str,
+    value: Value<'gc>,
+) -> Result<(), Error> {
+    line.set_property(
+        line,
+        &QName::new(Namespace::Private("ruffle".into()), name),
+        value,
+        activation,
+    )
+}
+
+/// Get one of the read-only properties of a line.
+pub fn line_property<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut line: Object<'gc>,
+    name: &'static str,
+) -> Result<Value<'gc>, Error> {
+    line.get_property(
+        line,
+        &QName::new(Namespace::Private("ruffle".into()), name),
+        activation,
+    )
+}
+
+macro_rules! line_getters {
+    ($($name:ident => $property:expr),*) => {
+        $(
+            #[doc = concat!("Implements `", $property, "`'s getter.")]
+            pub fn $name<'gc>(
+                activation: &mut Activation<'_, 'gc, '_>,
+                this: Option<Object<'gc>>,
+                _args: &[Value<'gc>],
+            ) -> Result<Value<'gc>, Error> {
+                if let Some(this) = this {
+                    return line_property(activation, this, $property);
+                }
+
+                Ok(Value::Undefined)
+            }
+        )*
+    };
+}
+
+line_getters!(
+    text_block => "textBlock",
+    text_block_begin_index => "textBlockBeginIndex",
+    raw_text_length => "rawTextLength",
+    ascent => "ascent",
+    descent => "descent",
+    text_width => "textWidth",
+    text_height => "textHeight",
+    previous_line => "previousLine",
+    next_line => "nextLine"
+);
+
+/// Construct `TextLine`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.text.engine"), "TextLine"),
+        Some(
+            QName::new(
+                Namespace::package("flash.display"),
+                "DisplayObjectContainer",
+            )
+            .into(),
+        ),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED | ClassAttributes::FINAL);
+
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public(), "MAX_LINE_WIDTH"),
+        QName::new(Namespace::public(), "int").into(),
+        Some((MAX_LINE_WIDTH as i32).into()),
+    ));
+
+    for (name, getter) in &[
+        ("textBlock", text_block as NativeMethod<'gc>),
+        ("textBlockBeginIndex", text_block_begin_index),
+        ("rawTextLength", raw_text_length),
+        ("ascent", ascent),
+        ("descent", descent),
+        ("textWidth", text_width),
+        ("textHeight", text_height),
+        ("previousLine", previous_line),
+        ("nextLine", next_line),
+    ] {
+        write.define_instance_trait(Trait::from_getter(
+            QName::new(Namespace::public(), *name),
+            Method::from_builtin(*getter),
+        ));
+    }
+
+    class
+}
@@ -0,0 +1,665 @@
+//! `flash.text.TextField` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::display_object::TDisplayObject;
+use crate::font::TextGridFit;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.text.TextField`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.text.TextField`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `embedFonts`'s getter.
+pub fn embed_fonts<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(edit_text) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        return Ok((!edit_text.is_device_font()).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `embedFonts`'s setter.
+///
+/// Embedded fonts are only ever looked up in the movie's library. Text in a
+/// font that the movie doesn't embed is not rendered at all.
+pub fn set_embed_fonts<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(edit_text) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        let embed_fonts = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+
+        edit_text.set_is_device_font(&mut activation.context, !embed_fonts);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `htmlText`'s getter.
+pub fn html_text<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(edit_text) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        let html_text = edit_text.html_text(&mut activation.context)?;
+
+        return Ok(AvmString::new(activation.context.gc_context, html_text).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `htmlText`'s setter.
+///
+/// Images named by `<img>` tags are not loaded or displayed.
+pub fn set_html_text<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(edit_text) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        let html_text = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        edit_text.set_is_html(&mut activation.context, true);
+        edit_text.set_html_text(html_text.to_string(), &mut activation.context)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `antiAliasType`'s getter.
+pub fn anti_alias_type<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(edit_text) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        return Ok(if edit_text.render_settings().is_advanced() {
+            "advanced".into()
+        } else {
+            "normal".into()
+        });
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `antiAliasType`'s setter.
+///
+/// The advanced engine is only used for embedded fonts. Switching back to the
+/// normal engine keeps the other rendering hints, but they stop having any
+/// effect.
+pub fn set_anti_alias_type<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(edit_text) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        let anti_alias_type = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let is_advanced = match anti_alias_type.as_str() {
+            "advanced" => true,
+            "normal" => false,
+            _ => {
+                return Err("ArgumentError: Error #2008: Parameter antiAliasType must be one of the accepted values.".into())
+            }
+        };
+
+        let mut settings = edit_text.render_settings();
+        settings.is_advanced = is_advanced;
+        edit_text.set_render_settings(activation.context.gc_context, settings);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `gridFitType`'s getter.
+pub fn grid_fit_type<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(edit_text) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        return Ok(match edit_text.render_settings().grid_fit {
+            TextGridFit::None => "none".into(),
+            TextGridFit::Pixel => "pixel".into(),
+            TextGridFit::SubPixel => "subpixel".into(),
+        });
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `gridFitType`'s setter.
+///
+/// Only `pixel` changes how glyphs are drawn: it moves each glyph onto a
+/// whole pixel. Glyphs are drawn as vector outlines, so `subpixel` draws them
+/// as `none` does.
+pub fn set_grid_fit_type<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(edit_text) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        let grid_fit_type = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let grid_fit = match grid_fit_type.as_str() {
+            "none" => TextGridFit::None,
+            "pixel" => TextGridFit::Pixel,
+            "subpixel" => TextGridFit::SubPixel,
+            _ => {
+                return Err("ArgumentError: Error #2008: Parameter gridFitType must be one of the accepted values.".into())
+            }
+        };
+
+        let mut settings = edit_text.render_settings();
+        settings.grid_fit = grid_fit;
+        edit_text.set_render_settings(activation.context.gc_context, settings);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `sharpness`'s getter.
+pub fn sharpness<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(edit_text) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        return Ok(f64::from(edit_text.render_settings().sharpness).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `sharpness`'s setter.
+///
+/// Values are clamped to between -400 and 400. They are kept, but glyphs are
+/// drawn as vector outlines, so there are no rasterized edges to sharpen.
+pub fn set_sharpness<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(edit_text) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        let sharpness = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+
+        let mut settings = edit_text.render_settings();
+        settings.sharpness = sharpness.clamp(-400.0, 400.0) as f32;
+        edit_text.set_render_settings(activation.context.gc_context, settings);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `thickness`'s getter.
+pub fn thickness<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(edit_text) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        return Ok(f64::from(edit_text.render_settings().thickness).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `thickness`'s setter.
+///
+/// Values are clamped to between -200 and 200. They are kept, but glyphs are
+/// drawn as vector outlines, so there are no rasterized edges to thicken.
+pub fn set_thickness<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(edit_text) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        let thickness = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+
+        let mut settings = edit_text.render_settings();
+        settings.thickness = thickness.clamp(-200.0, 200.0) as f32;
+        edit_text.set_render_settings(activation.context.gc_context, settings);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `TextField`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.text"), "TextField"),
+        Some(QName::new(Namespace::package("flash.display"), "InteractiveObject").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "antiAliasType"),
+        Method::from_builtin(anti_alias_type),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "antiAliasType"),
+        Method::from_builtin(set_anti_alias_type),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "gridFitType"),
+        Method::from_builtin(grid_fit_type),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "gridFitType"),
+        Method::from_builtin(set_grid_fit_type),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "sharpness"),
+        Method::from_builtin(sharpness),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "sharpness"),
+        Method::from_builtin(set_sharpness),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "thickness"),
+        Method::from_builtin(thickness),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "thickness"),
+        Method::from_builtin(set_thickness),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "embedFonts"),
+        Method::from_builtin(embed_fonts),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "embedFonts"),
+        Method::from_builtin(set_embed_fonts),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "htmlText"),
+        Method::from_builtin(html_text),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "htmlText"),
+        Method::from_builtin(set_html_text),
+    ));
+
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::test_utils::{
+        call, class, function, get, set, trace_with_avm2, with_avm2, RenderRecorder,
+    };
+    use crate::bounding_box::BoundingBox;
+    use crate::character::Character;
+    use crate::context::RenderContext;
+    use crate::display_object::EditText;
+    use crate::font::Font;
+    use crate::player::{Player, DEVICE_FONT_TAG};
+    use crate::prelude::*;
+    use crate::tag_utils::SwfMovie;
+    use crate::transform::TransformStack;
+    use crate::vminterface::Instantiator;
+    use std::sync::Arc;
+    use swf::{Glyph, SwfStr};
+
+    const AVM2_MOVIE: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/swfs/avm2/movieclip_currentlabels/test.swf"
+    );
+
+    /// Embed a font called `Embedded` in the movie, with a blank `W` that is
+    /// two ems wide.
+    fn embed_font(activation: &mut Activation<'_, '_, '_>, movie: Arc<SwfMovie>) {
+        let tag = swf::Font {
+            version: 2,
+            id: 1,
+            name: SwfStr::from_str_with_encoding("Embedded", swf::UTF_8).unwrap(),
+            language: swf::Language::Unknown,
+            layout: None,
+            glyphs: vec![Glyph {
+                shape_records: vec![],
+                code: 'W' as u16,
+                advance: Some(2048),
+                bounds: None,
+            }],
+            is_small_text: false,
+            is_shift_jis: false,
+            is_ansi: false,
+            is_bold: false,
+            is_italic: false,
+        };
+        let font = Font::from_swf_tag(
+            activation.context.gc_context,
+            activation.context.renderer,
+            &tag,
+            swf::UTF_8,
+        )
+        .unwrap();
+
+        activation
+            .context
+            .library
+            .library_for_movie_mut(movie)
+            .register_character(1, Character::Font(font));
+    }
+
+    /// Create a text field showing `text` in the font called `font`.
+    fn text_field<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        movie: Arc<SwfMovie>,
+        font: &str,
+        text: &str,
+    ) -> (EditText<'gc>, Object<'gc>) {
+        let edit_text = EditText::new(&mut activation.context, movie, 0.0, 0.0, 400.0, 100.0);
+        edit_text.post_instantiation(
+            &mut activation.context,
+            edit_text.into(),
+            None,
+            Instantiator::Movie,
+            false,
+        );
+
+        let mut format = edit_text.new_text_format();
+        format.font = Some(font.to_string());
+        edit_text.set_new_text_format(format, &mut activation.context);
+        edit_text
+            .set_text(text.to_string(), &mut activation.context)
+            .unwrap();
+
+        let object = edit_text.object2().coerce_to_object(activation).unwrap();
+        (edit_text, object)
+    }
+
+    fn text_width<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        edit_text: EditText<'gc>,
+    ) -> Twips {
+        edit_text.measure_text(&mut activation.context).0
+    }
+
+    #[test]
+    fn embed_fonts_switches_between_embedded_and_device_glyphs() {
+        with_avm2(|activation| {
+            let device_font = Player::load_device_font(
+                activation.context.gc_context,
+                DEVICE_FONT_TAG,
+                activation.context.renderer,
+            )
+            .unwrap();
+            activation
+                .context
+                .library
+                .set_device_font(Some(device_font));
+
+            let movie = Arc::new(SwfMovie::from_path(AVM2_MOVIE).unwrap());
+            embed_font(activation, movie.clone());
+
+            let (edit_text, this) = text_field(activation, movie.clone(), "Embedded", "WWW");
+            assert_eq!(
+                embed_fonts(activation, Some(this), &[]).unwrap(),
+                false.into()
+            );
+            let device_width = text_width(activation, edit_text);
+            assert!(device_width > Twips::new(0));
+
+            // Three glyphs, each two ems of a 12 pixel font.
+            set_embed_fonts(activation, Some(this), &[true.into()]).unwrap();
+            assert_eq!(
+                embed_fonts(activation, Some(this), &[]).unwrap(),
+                true.into()
+            );
+            assert_eq!(text_width(activation, edit_text), Twips::from_pixels(72.0));
+
+            set_embed_fonts(activation, Some(this), &[false.into()]).unwrap();
+            assert_eq!(text_width(activation, edit_text), device_width);
+
+            // Without a matching embedded font, an embedding text field is
+            // blank rather than falling back to the device font.
+            let (edit_text, this) = text_field(activation, movie, "Missing", "WWW");
+            assert!(text_width(activation, edit_text) > Twips::new(0));
+            set_embed_fonts(activation, Some(this), &[true.into()]).unwrap();
+            assert_eq!(text_width(activation, edit_text), Twips::new(0));
+        });
+    }
+
+    /// Render a text field, and get the positions its glyphs are drawn at.
+    fn glyph_positions<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        edit_text: EditText<'gc>,
+    ) -> Vec<(Twips, Twips)> {
+        let mut recorder = RenderRecorder::default();
+        let mut transform_stack = TransformStack::new();
+        let mut context = RenderContext {
+            renderer: &mut recorder,
+            library: activation.context.library,
+            transform_stack: &mut transform_stack,
+            view_bounds: BoundingBox {
+                x_min: Twips::zero(),
+                y_min: Twips::zero(),
+                x_max: Twips::from_pixels(1000.0),
+                y_max: Twips::from_pixels(1000.0),
+                valid: true,
+            },
+            clip_depth_stack: vec![],
+            allow_mask: true,
+        };
+        edit_text.render(&mut context);
+
+        recorder.glyphs().iter().map(|m| (m.tx, m.ty)).collect()
+    }
+
+    fn is_whole_pixel(twips: Twips) -> bool {
+        twips.get() % Twips::TWIPS_PER_PIXEL as i32 == 0
+    }
+
+    #[test]
+    fn advanced_grid_fitting_moves_glyphs_onto_whole_pixels() {
+        with_avm2(|activation| {
+            let movie = Arc::new(SwfMovie::from_path(AVM2_MOVIE).unwrap());
+            embed_font(activation, movie.clone());
+
+            let (edit_text, this) = text_field(activation, movie, "Embedded", "WWW");
+            set_embed_fonts(activation, Some(this), &[true.into()]).unwrap();
+            edit_text.set_x(activation.context.gc_context, 10.3);
+            edit_text.set_y(activation.context.gc_context, 5.6);
+
+            let normal = glyph_positions(activation, edit_text);
+            assert_eq!(normal.len(), 3);
+            assert!(normal
+                .iter()
+                .all(|(x, y)| !is_whole_pixel(*x) && !is_whole_pixel(*y)));
+
+            // The normal engine ignores every rendering hint.
+            set_grid_fit_type(activation, Some(this), &["pixel".into()]).unwrap();
+            set_sharpness(activation, Some(this), &[300.into()]).unwrap();
+            set_thickness(activation, Some(this), &[(-500).into()]).unwrap();
+            assert_eq!(glyph_positions(activation, edit_text), normal);
+
+            set_anti_alias_type(activation, Some(this), &["advanced".into()]).unwrap();
+            assert_eq!(
+                anti_alias_type(activation, Some(this), &[]).unwrap(),
+                "advanced".into()
+            );
+            assert_eq!(
+                sharpness(activation, Some(this), &[]).unwrap(),
+                300.0.into()
+            );
+            assert_eq!(
+                thickness(activation, Some(this), &[]).unwrap(),
+                (-200.0).into()
+            );
+
+            let fitted = glyph_positions(activation, edit_text);
+            assert_eq!(fitted.len(), 3);
+            for ((x, y), (normal_x, normal_y)) in fitted.iter().zip(normal.iter()) {
+                assert!(is_whole_pixel(*x) && is_whole_pixel(*y), "{:?}", fitted);
+                assert!((x.get() - normal_x.get()).abs() <= 10);
+                assert!((y.get() - normal_y.get()).abs() <= 10);
+            }
+
+            set_grid_fit_type(activation, Some(this), &["subpixel".into()]).unwrap();
+            assert_eq!(glyph_positions(activation, edit_text), normal);
+
+            let error = set_anti_alias_type(activation, Some(this), &["sharp".into()]).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "ArgumentError: Error #2008: Parameter antiAliasType must be one of the accepted values."
+            );
+        });
+    }
+
+    fn trace_link<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let event = args[0].coerce_to_object(activation)?;
+        let text_event = class(activation, "flash.events", "TextEvent");
+        let is_text_event = event.is_instance_of(activation, text_event, false)?;
+        let text = get(activation, event, "text").coerce_to_string(activation)?;
+
+        activation
+            .context
+            .log
+            .avm_trace(&format!("{} {}", is_text_event, text));
+
+        Ok(Value::Undefined)
+    }
+
+    #[test]
+    fn clicking_an_event_link_dispatches_link() {
+        let log = trace_with_avm2(|activation| {
+            let device_font = Player::load_device_font(
+                activation.context.gc_context,
+                DEVICE_FONT_TAG,
+                activation.context.renderer,
+            )
+            .unwrap();
+            activation
+                .context
+                .library
+                .set_device_font(Some(device_font));
+
+            let movie = Arc::new(SwfMovie::from_path(AVM2_MOVIE).unwrap());
+            let (edit_text, this) = text_field(activation, movie, "Missing", "");
+            set(
+                activation,
+                this,
+                "htmlText",
+                "<a href=\"event:hello\">WWW</a>".into(),
+            );
+
+            let listener = function(activation, trace_link);
+            call(
+                activation,
+                this,
+                "addEventListener",
+                &["link".into(), listener.into()],
+            );
+
+            let index = edit_text
+                .screen_position_to_index((Twips::from_pixels(4.0), Twips::from_pixels(6.0)))
+                .unwrap();
+            edit_text.click_link(&mut activation.context, index);
+        });
+
+        assert_eq!(log, vec!["true hello"]);
+    }
+}
@@ -0,0 +1,10 @@
+//! `flash.ui` namespace
+
+pub mod contextmenu;
+pub mod contextmenubuiltinitems;
+pub mod contextmenuitem;
+pub mod keyboard;
+pub mod mouse;
+pub mod mousecursor;
+pub mod multitouch;
+pub mod multitouchinputmode;
@@ -0,0 +1,275 @@
+//! `flash.ui.ContextMenu` builtin/prototype
+//!
+//! The player reads a menu's `customItems` and `builtInItems` when the user
+//! right-clicks its owner; see `crate::context_menu`.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::flash::ui::contextmenubuiltinitems::{
+    new_built_in_items, BUILT_IN_ITEMS,
+};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{ArrayObject, Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.ui.ContextMenu`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        let built_in_items = new_built_in_items(activation)?;
+        let array_proto = activation.context.avm2.prototypes().array;
+        let custom_items = ArrayObject::from_array(
+            ArrayStorage::new(0),
+            array_proto,
+            activation.context.gc_context,
+        );
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "builtInItems"),
+            built_in_items.into(),
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "customItems"),
+            custom_items.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.ui.ContextMenu`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `ContextMenu.isSupported`.
+pub fn is_supported<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(true.into())
+}
+
+/// Implements `builtInItems`'s getter.
+pub fn built_in_items<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "builtInItems"),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `builtInItems`'s setter.
+pub fn set_built_in_items<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let built_in_items = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "builtInItems"),
+            built_in_items.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `customItems`'s getter.
+pub fn custom_items<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "customItems"),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `customItems`'s setter.
+pub fn set_custom_items<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let custom_items = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "customItems"),
+            custom_items.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ContextMenu.hideBuiltInItems`.
+///
+/// This hides every built-in item; the host's own items, such as
+/// "About Ruffle", are never affected.
+pub fn hide_built_in_items<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let mut built_in_items =
+            built_in_items(activation, Some(this), &[])?.coerce_to_object(activation)?;
+
+        for item in BUILT_IN_ITEMS {
+            built_in_items.set_property(
+                built_in_items,
+                &QName::new(Namespace::public(), *item),
+                false.into(),
+                activation,
+            )?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ContextMenu.clone`.
+///
+/// The copy has its own built-in item settings and item list, but shares the
+/// custom items themselves with this menu.
+pub fn clone<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let mut proto = activation.context.avm2.prototypes().context_menu;
+        let copy = proto.construct(activation, &[])?;
+        let constr = proto
+            .get_property(
+                proto,
+                &QName::new(Namespace::public(), "constructor"),
+                activation,
+            )?
+            .coerce_to_object(activation)?;
+
+        constr.call(Some(copy), &[], activation, Some(proto))?;
+
+        let mut built_in_items =
+            built_in_items(activation, Some(this), &[])?.coerce_to_object(activation)?;
+        let built_in_clone = built_in_items
+            .get_property(
+                built_in_items,
+                &QName::new(Namespace::public(), "clone"),
+                activation,
+            )?
+            .coerce_to_object(activation)?
+            .call(Some(built_in_items), &[], activation, None)?;
+        set_built_in_items(activation, Some(copy), &[built_in_clone])?;
+
+        let items = custom_items(activation, Some(this), &[])?.coerce_to_object(activation)?;
+        let items = items
+            .as_array_storage()
+            .map(|storage| storage.clone())
+            .unwrap_or_else(|| ArrayStorage::new(0));
+        let array_proto = activation.context.avm2.prototypes().array;
+        let items_clone =
+            ArrayObject::from_array(items, array_proto, activation.context.gc_context);
+        set_custom_items(activation, Some(copy), &[items_clone.into()])?;
+
+        return Ok(copy.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `ContextMenu`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.ui"), "ContextMenu"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED | ClassAttributes::FINAL);
+
+    write.define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "isSupported"),
+        Method::from_builtin(is_supported),
+    ));
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "builtInItems"),
+        Method::from_builtin(built_in_items),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "builtInItems"),
+        Method::from_builtin(set_built_in_items),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "customItems"),
+        Method::from_builtin(custom_items),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "customItems"),
+        Method::from_builtin(set_custom_items),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "hideBuiltInItems"),
+        Method::from_builtin(hide_built_in_items),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "clone"),
+        Method::from_builtin(clone),
+    ));
+
+    class
+}
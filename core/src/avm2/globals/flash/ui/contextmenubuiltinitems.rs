@@ -0,0 +1,121 @@
+//! `flash.ui.ContextMenuBuiltInItems` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// The name of every built-in item, each of which is a `Boolean` property
+/// that is `true` until content hides it.
+pub const BUILT_IN_ITEMS: &[&str] = &[
+    "forwardAndBack",
+    "loop",
+    "play",
+    "print",
+    "quality",
+    "rewind",
+    "save",
+    "zoom",
+];
+
+/// Implements `flash.ui.ContextMenuBuiltInItems`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.ui.ContextMenuBuiltInItems`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct a new `ContextMenuBuiltInItems` object with every item shown.
+pub fn new_built_in_items<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Object<'gc>, Error> {
+    let mut proto = activation
+        .context
+        .avm2
+        .prototypes()
+        .context_menu_built_in_items;
+    let object = proto.construct(activation, &[])?;
+    let constr = proto
+        .get_property(
+            proto,
+            &QName::new(Namespace::public(), "constructor"),
+            activation,
+        )?
+        .coerce_to_object(activation)?;
+
+    constr.call(Some(object), &[], activation, Some(proto))?;
+
+    Ok(object)
+}
+
+/// Implements `ContextMenuBuiltInItems.clone`.
+pub fn clone<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let mut copy = new_built_in_items(activation)?;
+
+        for item in BUILT_IN_ITEMS {
+            let name = QName::new(Namespace::public(), *item);
+            let shown = this.get_property(this, &name, activation)?;
+
+            copy.set_property(copy, &name, shown, activation)?;
+        }
+
+        return Ok(copy.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `ContextMenuBuiltInItems`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.ui"), "ContextMenuBuiltInItems"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED | ClassAttributes::FINAL);
+
+    for item in BUILT_IN_ITEMS {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public(), *item),
+            QName::new(Namespace::public(), "Boolean").into(),
+            Some(true.into()),
+        ));
+    }
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "clone"),
+        Method::from_builtin(clone),
+    ));
+
+    class
+}
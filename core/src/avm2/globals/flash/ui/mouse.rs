@@ -0,0 +1,166 @@
+//! `flash.ui.Mouse` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::flash::ui::mousecursor::{cursor_from_name, cursor_name};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::Object;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::backend::ui::MouseCursor;
+use crate::display_object::TDisplayObject;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.ui.Mouse`'s instance constructor.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Err("ArgumentError: Error #2012: Mouse class cannot be instantiated.".into())
+}
+
+/// Implements `flash.ui.Mouse`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `Mouse.hide`.
+pub fn hide<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    activation.context.ui.set_mouse_visible(false);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Mouse.show`.
+pub fn show<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    activation.context.ui.set_mouse_visible(true);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `cursor`'s getter.
+pub fn cursor<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(cursor_name(*activation.context.forced_mouse_cursor).into())
+}
+
+/// Implements `cursor`'s setter.
+pub fn set_cursor<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let name = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+    let forced_cursor = cursor_from_name(&name).ok_or(
+        "ArgumentError: Error #2008: Parameter cursor must be one of the accepted values.",
+    )?;
+
+    *activation.context.forced_mouse_cursor = forced_cursor;
+
+    // Going back to `auto` hands control back to whatever is being hovered.
+    let new_cursor = forced_cursor.unwrap_or_else(|| {
+        activation
+            .context
+            .mouse_hovered_object
+            .map(|hovered| hovered.mouse_cursor())
+            .unwrap_or(MouseCursor::Arrow)
+    });
+
+    if new_cursor != *activation.context.mouse_cursor {
+        *activation.context.mouse_cursor = new_cursor;
+        activation.context.ui.set_mouse_cursor(new_cursor);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Mouse`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.ui"), "Mouse"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED | ClassAttributes::FINAL);
+
+    write.define_class_trait(Trait::from_method(
+        QName::new(Namespace::public(), "hide"),
+        Method::from_builtin(hide),
+    ));
+    write.define_class_trait(Trait::from_method(
+        QName::new(Namespace::public(), "show"),
+        Method::from_builtin(show),
+    ));
+    write.define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "cursor"),
+        Method::from_builtin(cursor),
+    ));
+    write.define_class_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "cursor"),
+        Method::from_builtin(set_cursor),
+    ));
+
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::avm2::test_utils::{call, class, TestPlayer, TestUi};
+    use crate::events::PlayerEvent;
+    use crate::player::Player;
+
+    fn mouse_visible(player: &Player) -> bool {
+        player.ui().downcast_ref::<TestUi>().unwrap().mouse_visible
+    }
+
+    /// Call the static method `name` of `Mouse`.
+    fn call_mouse(player: &TestPlayer, name: &'static str) {
+        player.update(|activation| {
+            let mouse = class(activation, "flash.ui", "Mouse");
+            call(activation, mouse, name, &[]);
+        });
+    }
+
+    #[test]
+    fn mouse_visible_after_focus_lost() {
+        let player = TestPlayer::with_ui(Box::new(TestUi::default()));
+
+        call_mouse(&player, "hide");
+        assert!(!mouse_visible(&player.lock()));
+
+        call_mouse(&player, "show");
+        assert!(mouse_visible(&player.lock()));
+
+        // Losing focus always gives the system cursor back.
+        call_mouse(&player, "hide");
+        player.lock().handle_event(PlayerEvent::FocusLost);
+        assert!(mouse_visible(&player.lock()));
+    }
+}
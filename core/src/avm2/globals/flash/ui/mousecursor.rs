@@ -0,0 +1,93 @@
+//! `flash.ui.MouseCursor` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::Object;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::backend::ui::MouseCursor;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.ui.MouseCursor`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.ui.MouseCursor`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Get the AS3 name of a cursor, as used by `Mouse.cursor`.
+///
+/// `None` represents the `auto` cursor, where the player picks a cursor based
+/// on what is being hovered.
+pub fn cursor_name(cursor: Option<MouseCursor>) -> &'static str {
+    match cursor {
+        None => "auto",
+        Some(MouseCursor::Arrow) => "arrow",
+        Some(MouseCursor::Hand) => "button",
+        Some(MouseCursor::IBeam) => "ibeam",
+        Some(MouseCursor::Grab) => "hand",
+    }
+}
+
+/// Look up a cursor by its AS3 name.
+///
+/// The outer `None` indicates that the name is not a valid cursor.
+pub fn cursor_from_name(name: &str) -> Option<Option<MouseCursor>> {
+    match name {
+        "auto" => Some(None),
+        "arrow" => Some(Some(MouseCursor::Arrow)),
+        "button" => Some(Some(MouseCursor::Hand)),
+        "ibeam" => Some(Some(MouseCursor::IBeam)),
+        "hand" => Some(Some(MouseCursor::Grab)),
+        _ => None,
+    }
+}
+
+/// Construct `MouseCursor`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.ui"), "MouseCursor"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED | ClassAttributes::FINAL);
+
+    for (const_name, cursor) in &[
+        ("ARROW", Some(MouseCursor::Arrow)),
+        ("AUTO", None),
+        ("BUTTON", Some(MouseCursor::Hand)),
+        ("HAND", Some(MouseCursor::Grab)),
+        ("IBEAM", Some(MouseCursor::IBeam)),
+    ] {
+        write.define_class_trait(Trait::from_const(
+            QName::new(Namespace::public(), *const_name),
+            QName::new(Namespace::public(), "String").into(),
+            Some(cursor_name(*cursor).into()),
+        ));
+    }
+
+    class
+}
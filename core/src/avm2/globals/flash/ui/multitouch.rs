@@ -0,0 +1,174 @@
+//! `flash.ui.Multitouch` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::Object;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::{Error, MultitouchInputMode};
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.ui.Multitouch`'s instance constructor.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Err("ArgumentError: Error #2012: Multitouch class cannot be instantiated.".into())
+}
+
+/// Implements `flash.ui.Multitouch`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `inputMode`'s getter.
+pub fn input_mode<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(activation
+        .context
+        .avm2
+        .multitouch_input_mode()
+        .name()
+        .into())
+}
+
+/// Implements `inputMode`'s setter.
+pub fn set_input_mode<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let name = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+    let input_mode = MultitouchInputMode::from_name(&name).ok_or(
+        "ArgumentError: Error #2008: Parameter inputMode must be one of the accepted values.",
+    )?;
+
+    activation
+        .context
+        .avm2
+        .set_multitouch_input_mode(input_mode);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `supportsTouchEvents`'s getter.
+pub fn supports_touch_events<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(activation.context.ui.supports_touch_events().into())
+}
+
+/// Implements `maxTouchPoints`'s getter.
+pub fn max_touch_points<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(activation.context.ui.max_touch_points().into())
+}
+
+/// Implements `supportsGestureEvents`'s getter.
+///
+/// Touches are never recognized as gestures.
+pub fn supports_gesture_events<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(false.into())
+}
+
+/// Construct `Multitouch`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.ui"), "Multitouch"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED | ClassAttributes::FINAL);
+
+    write.define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "inputMode"),
+        Method::from_builtin(input_mode),
+    ));
+    write.define_class_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "inputMode"),
+        Method::from_builtin(set_input_mode),
+    ));
+    write.define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "supportsTouchEvents"),
+        Method::from_builtin(supports_touch_events),
+    ));
+    write.define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "supportsGestureEvents"),
+        Method::from_builtin(supports_gesture_events),
+    ));
+    write.define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "maxTouchPoints"),
+        Method::from_builtin(max_touch_points),
+    ));
+
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::globals::flash::display::stage;
+    use crate::avm2::test_utils::{with_avm2, with_avm2_ui, TestUi};
+
+    #[test]
+    fn capabilities_come_from_the_backends() {
+        // A host with a touch screen that tracks up to five touch points.
+        let ui = TestUi {
+            max_touch_points: 5,
+            ..Default::default()
+        };
+        with_avm2_ui(Box::new(ui), |activation| {
+            assert_eq!(max_touch_points(activation, None, &[]).unwrap(), 5.into());
+            assert_eq!(
+                supports_touch_events(activation, None, &[]).unwrap(),
+                true.into()
+            );
+            assert_eq!(
+                supports_gesture_events(activation, None, &[]).unwrap(),
+                false.into()
+            );
+        });
+
+        // The null backends stand in for a desktop without a touch screen,
+        // drawing in software.
+        with_avm2(|activation| {
+            assert_eq!(max_touch_points(activation, None, &[]).unwrap(), 0.into());
+            assert_eq!(
+                supports_touch_events(activation, None, &[]).unwrap(),
+                false.into()
+            );
+            assert_eq!(
+                stage::wmode_gpu(activation, None, &[]).unwrap(),
+                false.into()
+            );
+        });
+    }
+}
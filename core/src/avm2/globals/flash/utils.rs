@@ -0,0 +1,148 @@
+//! `flash.utils` namespace
+
+use crate::avm1::TimerCallback;
+use crate::avm2::activation::Activation;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+
+pub mod bytearray;
+pub mod endian;
+pub mod proxy;
+
+/// Register a timer that calls the closure in `args` after its delay,
+/// passing along every argument after the delay.
+fn add_timer<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    is_timeout: bool,
+) -> Result<Value<'gc>, Error> {
+    let closure = match args.get(0) {
+        Some(Value::Object(closure)) if closure.as_executable().is_some() => *closure,
+        _ => {
+            return Err("TypeError: Error #1034: Type Coercion failed: expected a Function".into())
+        }
+    };
+    let delay = args
+        .get(1)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_i32(activation)?;
+    let params = args.get(2..).unwrap_or_default().to_vec();
+
+    let id = activation.context.timers.add_timer(
+        TimerCallback::Avm2Callback { closure, params },
+        delay,
+        vec![],
+        is_timeout,
+    );
+
+    Ok(id.into())
+}
+
+/// Implements `flash.utils.setInterval`.
+pub fn set_interval<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    add_timer(activation, args, false)
+}
+
+/// Implements `flash.utils.setTimeout`.
+pub fn set_timeout<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    add_timer(activation, args, true)
+}
+
+/// Implements `flash.utils.clearInterval` and `flash.utils.clearTimeout`.
+///
+/// Intervals and timeouts share their IDs, so either one can clear the
+/// other.
+pub fn clear_interval<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let id = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_u32(activation)?;
+    activation.context.timers.remove(id as i32);
+
+    Ok(Value::Undefined)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm1::Timers;
+    use crate::avm2::names::{Namespace, QName};
+    use crate::avm2::object::{FunctionObject, ScriptObject};
+    use crate::avm2::test_utils::trace_with_avm2;
+
+    /// Trace the arguments given to a timer callback: a string, and an
+    /// object's `x`.
+    fn trace_args<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let label = args[0].coerce_to_string(activation)?;
+        let mut object = args[1].coerce_to_object(activation)?;
+        let x = object
+            .get_property(object, &QName::new(Namespace::public(), "x"), activation)?
+            .coerce_to_string(activation)?;
+        activation
+            .context
+            .log
+            .avm_trace(&format!("{} {} ({})", label, x, args.len()));
+
+        Ok(Value::Undefined)
+    }
+
+    #[test]
+    fn set_interval_forwards_arguments_by_reference() {
+        let traces = trace_with_avm2(|activation| {
+            let callback = FunctionObject::from_builtin(
+                activation.context.gc_context,
+                trace_args,
+                activation.avm2().prototypes().function,
+            );
+            let mut object = ScriptObject::object(
+                activation.context.gc_context,
+                activation.avm2().prototypes().object,
+            );
+            let x = QName::new(Namespace::public(), "x");
+            object
+                .set_property(object, &x, 1.into(), activation)
+                .unwrap();
+
+            let id = set_interval(
+                activation,
+                None,
+                &[callback.into(), 100.into(), "tick".into(), object.into()],
+            )
+            .unwrap();
+
+            object
+                .set_property(object, &x, 2.into(), activation)
+                .unwrap();
+            Timers::update_timers(&mut activation.context, 150.0);
+
+            object
+                .set_property(object, &x, 3.into(), activation)
+                .unwrap();
+            Timers::update_timers(&mut activation.context, 100.0);
+
+            clear_interval(activation, None, &[id]).unwrap();
+            Timers::update_timers(&mut activation.context, 100.0);
+        });
+
+        assert_eq!(traces, vec!["tick 2 (2)", "tick 3 (2)"]);
+    }
+}
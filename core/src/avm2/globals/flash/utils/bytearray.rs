@@ -0,0 +1,837 @@
+//! `flash.utils.ByteArray` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::amf::Amf3Writer;
+use crate::avm2::bytearray::Endian;
+use crate::avm2::class::Class;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{ByteArrayObject, Object, TObject};
+use crate::avm2::scope::Scope;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.utils.ByteArray`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.utils.ByteArray`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `length`'s getter.
+pub fn length<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(bytearray) = this.as_bytearray() {
+            return Ok((bytearray.len() as u32).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `length`'s setter.
+pub fn set_length<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let length = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_u32(activation)?;
+
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.set_length(length as usize);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `position`'s getter.
+pub fn position<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(bytearray) = this.as_bytearray() {
+            return Ok((bytearray.position() as u32).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `position`'s setter.
+pub fn set_position<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let position = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_u32(activation)?;
+
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.set_position(position as usize);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `bytesAvailable`'s getter.
+pub fn bytes_available<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(bytearray) = this.as_bytearray() {
+            return Ok((bytearray.bytes_available() as u32).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.clear`.
+pub fn clear<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.clear();
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.deflate`.
+pub fn deflate<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.deflate();
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.inflate`.
+pub fn inflate<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.inflate()?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeByte`.
+pub fn write_byte<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let byte = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)?;
+
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.write_bytes(&[byte as u8]);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readByte`.
+pub fn read_byte<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            let byte = bytearray.read_bytes(1)?[0];
+
+            return Ok((byte as i8 as i32).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readUnsignedByte`.
+pub fn read_unsigned_byte<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            let byte = bytearray.read_bytes(1)?[0];
+
+            return Ok(byte.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeShort`.
+pub fn write_short<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let value = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)?;
+
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.write_u16(value as u16);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readShort`.
+pub fn read_short<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            return Ok((bytearray.read_u16()? as i16 as i32).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readUnsignedShort`.
+pub fn read_unsigned_short<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            return Ok(bytearray.read_u16()?.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeInt`.
+pub fn write_int<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let value = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)?;
+
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.write_u32(value as u32);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeUnsignedInt`.
+pub fn write_unsigned_int<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let value = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_u32(activation)?;
+
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.write_u32(value);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readInt`.
+pub fn read_int<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            return Ok((bytearray.read_u32()? as i32).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readUnsignedInt`.
+pub fn read_unsigned_int<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            return Ok(bytearray.read_u32()?.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeFloat`.
+pub fn write_float<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let value = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.write_f32(value as f32);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readFloat`.
+pub fn read_float<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            return Ok(f64::from(bytearray.read_f32()?).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeDouble`.
+pub fn write_double<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let value = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.write_f64(value);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readDouble`.
+pub fn read_double<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            return Ok(bytearray.read_f64()?.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `endian`'s getter.
+pub fn endian<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(bytearray) = this.as_bytearray() {
+            return Ok(bytearray.endian().name().into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `endian`'s setter.
+///
+/// Only values read or written afterwards are affected.
+pub fn set_endian<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let name = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let endian = Endian::from_name(&name).ok_or(
+            "ArgumentError: Error #2008: Parameter type must be one of the accepted values.",
+        )?;
+
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.set_endian(endian);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.atomicCompareAndSwapIntAt`.
+pub fn atomic_compare_and_swap_int_at<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let offset = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)?;
+        let expected = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)?;
+        let new = args
+            .get(2)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)?;
+
+        if offset < 0 || offset % 4 != 0 {
+            return Err("ArgumentError: Error #2004: One of the parameters is invalid.".into());
+        }
+
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            let old = bytearray.compare_and_swap_int(offset as usize, expected, new)?;
+
+            return Ok(old.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.atomicCompareAndSwapLength`.
+pub fn atomic_compare_and_swap_length<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let expected = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)?;
+        let new = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)?;
+
+        if new < 0 {
+            return Err("RangeError: Error #1506: The specified range is invalid.".into());
+        }
+
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            let old = if expected < 0 {
+                bytearray.len()
+            } else {
+                bytearray.compare_and_swap_length(expected as usize, new as usize)
+            };
+
+            return Ok((old as u32).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeUTFBytes`.
+pub fn write_utf_bytes<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let string = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.write_bytes(string.as_bytes());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readUTFBytes`.
+pub fn read_utf_bytes<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let length = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_u32(activation)?;
+
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            let string = bytearray.read_multibyte(length as usize, "utf-8")?;
+
+            return Ok(AvmString::new(activation.context.gc_context, string).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeMultiByte`.
+pub fn write_multi_byte<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let string = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let charset = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.write_multibyte(&string, &charset);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readMultiByte`.
+pub fn read_multi_byte<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let length = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_u32(activation)?;
+        let charset = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            let string = bytearray.read_multibyte(length as usize, &charset)?;
+
+            return Ok(AvmString::new(activation.context.gc_context, string).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeObject`.
+///
+/// Values are always written as AMF3.
+pub fn write_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let value = args.get(0).cloned().unwrap_or(Value::Undefined);
+
+        let mut writer = Amf3Writer::new();
+        writer.write_value(activation, value)?;
+
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.write_bytes(&writer.into_bytes());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `objectEncoding`'s getter.
+pub fn object_encoding<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(3.into())
+}
+
+/// Implements `ByteArray.toString`.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(bytearray) = this.as_bytearray() {
+            let string = String::from_utf8_lossy(bytearray.bytes()).into_owned();
+
+            return Ok(AvmString::new(activation.context.gc_context, string).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Derive `ByteArrayObject` impls.
+pub fn bytearray_deriver<'gc>(
+    base_proto: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    class: GcCell<'gc, Class<'gc>>,
+    scope: Option<GcCell<'gc, Scope<'gc>>>,
+) -> Result<Object<'gc>, Error> {
+    ByteArrayObject::derive(base_proto, activation.context.gc_context, class, scope)
+}
+
+/// Construct `ByteArray`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.utils"), "ByteArray"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "length"),
+        Method::from_builtin(length),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "length"),
+        Method::from_builtin(set_length),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "position"),
+        Method::from_builtin(position),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "position"),
+        Method::from_builtin(set_position),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "bytesAvailable"),
+        Method::from_builtin(bytes_available),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "writeByte"),
+        Method::from_builtin(write_byte),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "readByte"),
+        Method::from_builtin(read_byte),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "readUnsignedByte"),
+        Method::from_builtin(read_unsigned_byte),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "writeShort"),
+        Method::from_builtin(write_short),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "readShort"),
+        Method::from_builtin(read_short),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "readUnsignedShort"),
+        Method::from_builtin(read_unsigned_short),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "writeInt"),
+        Method::from_builtin(write_int),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "writeUnsignedInt"),
+        Method::from_builtin(write_unsigned_int),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "readInt"),
+        Method::from_builtin(read_int),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "readUnsignedInt"),
+        Method::from_builtin(read_unsigned_int),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "writeFloat"),
+        Method::from_builtin(write_float),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "readFloat"),
+        Method::from_builtin(read_float),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "writeDouble"),
+        Method::from_builtin(write_double),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "readDouble"),
+        Method::from_builtin(read_double),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "endian"),
+        Method::from_builtin(endian),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "endian"),
+        Method::from_builtin(set_endian),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "writeUTFBytes"),
+        Method::from_builtin(write_utf_bytes),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "readUTFBytes"),
+        Method::from_builtin(read_utf_bytes),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "writeMultiByte"),
+        Method::from_builtin(write_multi_byte),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "readMultiByte"),
+        Method::from_builtin(read_multi_byte),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "writeObject"),
+        Method::from_builtin(write_object),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "atomicCompareAndSwapIntAt"),
+        Method::from_builtin(atomic_compare_and_swap_int_at),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "atomicCompareAndSwapLength"),
+        Method::from_builtin(atomic_compare_and_swap_length),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "objectEncoding"),
+        Method::from_builtin(object_encoding),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "clear"),
+        Method::from_builtin(clear),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "deflate"),
+        Method::from_builtin(deflate),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "inflate"),
+        Method::from_builtin(inflate),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "toString"),
+        Method::from_builtin(to_string),
+    ));
+
+    class
+}
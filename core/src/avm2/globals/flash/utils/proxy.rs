@@ -0,0 +1,91 @@
+//! `flash.utils.Proxy` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::Object;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.utils.Proxy`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.utils.Proxy`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `Proxy.nextNameIndex`, which subclasses must override to be
+/// enumerated.
+pub fn next_name_index<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Err("IllegalOperationError: Error #2088: The Proxy class does not implement nextNameIndex. It must be overridden by a subclass.".into())
+}
+
+/// Implements `Proxy.nextName`, which subclasses must override to be
+/// enumerated with `for..in`.
+pub fn next_name<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Err("IllegalOperationError: Error #2088: The Proxy class does not implement nextName. It must be overridden by a subclass.".into())
+}
+
+/// Implements `Proxy.nextValue`, which subclasses must override to be
+/// enumerated with `for each..in`.
+pub fn next_value<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Err("IllegalOperationError: Error #2088: The Proxy class does not implement nextValue. It must be overridden by a subclass.".into())
+}
+
+/// Construct `Proxy`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.utils"), "Proxy"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::flash_proxy(), "nextNameIndex"),
+        Method::from_builtin(next_name_index),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::flash_proxy(), "nextName"),
+        Method::from_builtin(next_name),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::flash_proxy(), "nextValue"),
+        Method::from_builtin(next_value),
+    ));
+
+    class
+}
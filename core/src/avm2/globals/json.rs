@@ -0,0 +1,483 @@
+//! `JSON` impl
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, ScriptObject, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+use std::fmt::Write;
+
+/// The most characters of indentation that `JSON.stringify` will use.
+const MAX_GAP_LENGTH: usize = 10;
+
+/// Implements `JSON`'s instance initializer.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Err("TypeError: Error #1076: JSON is not a constructor.".into())
+}
+
+/// Implements `JSON`'s class initializer.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// The state of a single `JSON.stringify` call.
+struct Serializer<'gc> {
+    /// The replacer function, called on every property before it is
+    /// serialized.
+    replacer: Option<Object<'gc>>,
+
+    /// The only names that are serialized on objects, if the replacer was an
+    /// array.
+    property_list: Option<Vec<AvmString<'gc>>>,
+
+    /// The indentation added at each level of nesting.
+    gap: String,
+
+    /// The indentation of the current level of nesting.
+    indent: String,
+
+    /// Every object that is currently being serialized, outermost first.
+    stack: Vec<Object<'gc>>,
+}
+
+impl<'gc> Serializer<'gc> {
+    /// Serialize the property `key` of `holder`, which holds `value`.
+    ///
+    /// Values with a `toJSON` method are replaced by whatever it returns
+    /// before anything else happens to them, including the replacer. If
+    /// the value should be left out entirely, this yields `None`.
+    fn serialize_property(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        holder: Object<'gc>,
+        key: AvmString<'gc>,
+        mut value: Value<'gc>,
+    ) -> Result<Option<String>, Error> {
+        if let Value::Object(mut object) = value {
+            let to_json = object.get_property(
+                object,
+                &QName::new(Namespace::public(), "toJSON"),
+                activation,
+            )?;
+            if let Value::Object(to_json) = to_json {
+                if to_json.as_executable().is_some() {
+                    value = to_json.call(Some(object), &[key.into()], activation, None)?;
+                }
+            }
+        }
+
+        if let Some(replacer) = self.replacer {
+            value = replacer.call(Some(holder), &[key.into(), value], activation, None)?;
+        }
+
+        if let Value::Object(Object::PrimitiveObject(primitive)) = value {
+            value = primitive.value_of(activation.context.gc_context)?;
+        }
+
+        Ok(match value {
+            Value::Undefined => None,
+            Value::Null => Some("null".to_string()),
+            Value::Bool(b) => Some(b.to_string()),
+            Value::String(s) => Some(quote(&s)),
+            Value::Number(n) if !n.is_finite() => Some("null".to_string()),
+            Value::Number(_) | Value::Integer(_) | Value::Unsigned(_) => {
+                Some(value.coerce_to_string(activation)?.to_string())
+            }
+            Value::Object(object) if object.as_executable().is_some() => None,
+            Value::Object(object) => Some(self.serialize_object(activation, object)?),
+        })
+    }
+
+    /// Serialize an array or any other object, which mustn't already be
+    /// being serialized.
+    fn serialize_object(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        object: Object<'gc>,
+    ) -> Result<String, Error> {
+        if self.stack.iter().any(|o| Object::ptr_eq(*o, object)) {
+            return Err(
+                "TypeError: Error #1129: Cyclic structure cannot be converted to JSON string."
+                    .into(),
+            );
+        }
+
+        self.stack.push(object);
+        let stepback = self.indent.clone();
+        self.indent.push_str(&self.gap);
+
+        let result = if object.as_array_storage().is_some() {
+            self.serialize_array(activation, object)
+        } else {
+            self.serialize_members(activation, object)
+        };
+
+        self.indent = stepback;
+        self.stack.pop();
+
+        result
+    }
+
+    /// Serialize the elements of an array. Elements that can't be
+    /// serialized become `null`.
+    fn serialize_array(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        array: Object<'gc>,
+    ) -> Result<String, Error> {
+        let length = array
+            .as_array_storage()
+            .map(|storage| storage.length())
+            .unwrap_or(0);
+        let mut elements = Vec::with_capacity(length);
+
+        for i in 0..length {
+            let value = array
+                .as_array_storage()
+                .and_then(|storage| storage.get(i))
+                .unwrap_or(Value::Undefined);
+            let key = AvmString::new(activation.context.gc_context, i.to_string());
+            let element = self.serialize_property(activation, array, key, value)?;
+
+            elements.push(element.unwrap_or_else(|| "null".to_string()));
+        }
+
+        Ok(self.join('[', elements, ']'))
+    }
+
+    /// Serialize the public, enumerable properties of an object, or only
+    /// those in the replacer's property list.
+    fn serialize_members(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        mut object: Object<'gc>,
+    ) -> Result<String, Error> {
+        let keys = if let Some(property_list) = &self.property_list {
+            property_list.clone()
+        } else {
+            let mut keys = Vec::new();
+            let mut index = 0;
+            while let Some(next) = object.get_next_enumerant(index) {
+                index = next;
+                if let Some(name) = object.get_enumerant_name(index) {
+                    if name.namespace().is_public() {
+                        keys.push(name.local_name());
+                    }
+                }
+            }
+
+            keys
+        };
+
+        let separator = if self.gap.is_empty() { ":" } else { ": " };
+        let mut members = Vec::with_capacity(keys.len());
+        for key in keys {
+            let value =
+                object.get_property(object, &QName::new(Namespace::public(), key), activation)?;
+            if let Some(member) = self.serialize_property(activation, object, key, value)? {
+                members.push(format!("{}{}{}", quote(&key), separator, member));
+            }
+        }
+
+        Ok(self.join('{', members, '}'))
+    }
+
+    /// Join the serialized members of an object or array, indenting them if
+    /// there is a gap. `self.indent` is the members' indentation.
+    fn join(&self, open: char, members: Vec<String>, close: char) -> String {
+        if members.is_empty() {
+            return format!("{}{}", open, close);
+        }
+
+        if self.gap.is_empty() {
+            return format!("{}{}{}", open, members.join(","), close);
+        }
+
+        let stepback = &self.indent[..self.indent.len() - self.gap.len()];
+        let separator = format!(",\n{}", self.indent);
+
+        format!(
+            "{}\n{}{}\n{}{}",
+            open,
+            self.indent,
+            members.join(&separator),
+            stepback,
+            close
+        )
+    }
+}
+
+/// Quote a string for JSON, escaping anything that can't appear in one
+/// as-is.
+fn quote(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\u{8}' => quoted.push_str("\\b"),
+            '\u{c}' => quoted.push_str("\\f"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c < ' ' => {
+                let _ = write!(quoted, "\\u{:04x}", c as u32);
+            }
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+
+    quoted
+}
+
+/// Implements `JSON.stringify`.
+///
+/// Only the dynamic properties of objects are serialized; the variables and
+/// getters declared by their classes are not.
+pub fn stringify<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let value = args.get(0).cloned().unwrap_or(Value::Undefined);
+
+    let (replacer, property_list) = match args.get(1).cloned().unwrap_or(Value::Null) {
+        Value::Null | Value::Undefined => (None, None),
+        Value::Object(replacer) if replacer.as_executable().is_some() => (Some(replacer), None),
+        Value::Object(replacer) if replacer.as_array_storage().is_some() => {
+            let names: Vec<Value<'gc>> = replacer
+                .as_array_storage()
+                .map(|storage| storage.iter().flatten().collect())
+                .unwrap_or_default();
+            let mut property_list: Vec<AvmString<'gc>> = Vec::with_capacity(names.len());
+            for name in names {
+                let name = match name {
+                    Value::String(_)
+                    | Value::Number(_)
+                    | Value::Integer(_)
+                    | Value::Unsigned(_) => name.coerce_to_string(activation)?,
+                    _ => continue,
+                };
+                if !property_list.contains(&name) {
+                    property_list.push(name);
+                }
+            }
+
+            (None, Some(property_list))
+        }
+        _ => {
+            return Err(
+                "TypeError: Error #4100: The replacer argument must be a function or an array."
+                    .into(),
+            )
+        }
+    };
+
+    let mut gap = match args.get(2).cloned().unwrap_or(Value::Null) {
+        Value::String(s) => s.to_string(),
+        space @ Value::Number(_) | space @ Value::Integer(_) | space @ Value::Unsigned(_) => {
+            let spaces = space
+                .coerce_to_number(activation)?
+                .max(0.0)
+                .min(MAX_GAP_LENGTH as f64);
+            " ".repeat(spaces as usize)
+        }
+        _ => String::new(),
+    };
+    if let Some((end, _)) = gap.char_indices().nth(MAX_GAP_LENGTH) {
+        gap.truncate(end);
+    }
+
+    let mut serializer = Serializer {
+        replacer,
+        property_list,
+        gap,
+        indent: String::new(),
+        stack: Vec::new(),
+    };
+
+    let mut wrapper = ScriptObject::object(
+        activation.context.gc_context,
+        activation.context.avm2.prototypes().object,
+    );
+    let key = AvmString::new(activation.context.gc_context, "");
+    wrapper.set_property(
+        wrapper,
+        &QName::new(Namespace::public(), key),
+        value.clone(),
+        activation,
+    )?;
+
+    Ok(
+        match serializer.serialize_property(activation, wrapper, key, value)? {
+            Some(json) => AvmString::new(activation.context.gc_context, json).into(),
+            None => Value::Undefined,
+        },
+    )
+}
+
+/// Construct `JSON`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::public(), "JSON"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+    write.set_attributes(ClassAttributes::FINAL | ClassAttributes::SEALED);
+
+    write.define_class_trait(Trait::from_method(
+        QName::new(Namespace::public(), "stringify"),
+        Method::from_builtin(stringify),
+    ));
+
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::array::ArrayStorage;
+    use crate::avm2::object::{ArrayObject, FunctionObject};
+    use crate::avm2::test_utils::with_avm2;
+
+    /// A `toJSON` method that replaces its object with one holding only its
+    /// `id` and the key it was found under.
+    fn summarize<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let mut this = this.unwrap();
+        let id = this.get_property(this, &QName::new(Namespace::public(), "id"), activation)?;
+        let summary = object(activation, &[("id", id), ("key", args[0].clone())]);
+
+        Ok(summary.into())
+    }
+
+    fn object<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        properties: &[(&'static str, Value<'gc>)],
+    ) -> Object<'gc> {
+        let mut object = ScriptObject::object(
+            activation.context.gc_context,
+            activation.avm2().prototypes().object,
+        );
+        for (name, value) in properties {
+            object
+                .set_property(
+                    object,
+                    &QName::new(Namespace::public(), *name),
+                    value.clone(),
+                    activation,
+                )
+                .unwrap();
+        }
+
+        object
+    }
+
+    fn stringify_args<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        args: &[Value<'gc>],
+    ) -> Result<String, Error> {
+        Ok(stringify(activation, None, args)?
+            .coerce_to_string(activation)?
+            .to_string())
+    }
+
+    #[test]
+    fn to_json_replaces_the_value_being_serialized() {
+        with_avm2(|activation| {
+            let to_json = FunctionObject::from_builtin(
+                activation.context.gc_context,
+                summarize,
+                activation.avm2().prototypes().function,
+            );
+            let item = object(
+                activation,
+                &[
+                    ("id", 7.into()),
+                    ("secret", "hidden".into()),
+                    ("toJSON", to_json.into()),
+                ],
+            );
+            let items = ArrayObject::from_array(
+                ArrayStorage::from_args(&[item.into()]),
+                activation.avm2().prototypes().array,
+                activation.context.gc_context,
+            );
+            let root = object(
+                activation,
+                &[("item", item.into()), ("items", items.into())],
+            );
+
+            assert_eq!(
+                stringify_args(activation, &[root.into()]).unwrap(),
+                r#"{"item":{"id":7,"key":"item"},"items":[{"id":7,"key":"0"}]}"#
+            );
+            assert_eq!(
+                stringify_args(activation, &[item.into()]).unwrap(),
+                r#"{"id":7,"key":""}"#
+            );
+        });
+    }
+
+    #[test]
+    fn stringify_indents_and_rejects_cycles() {
+        with_avm2(|activation| {
+            let empty = ArrayObject::from_array(
+                ArrayStorage::new(0),
+                activation.avm2().prototypes().array,
+                activation.context.gc_context,
+            );
+            let list = ArrayObject::from_array(
+                ArrayStorage::from_args(&[1.into(), Value::Undefined, "\"a\"\n".into()]),
+                activation.avm2().prototypes().array,
+                activation.context.gc_context,
+            );
+            let mut root = object(
+                activation,
+                &[("list", list.into()), ("empty", empty.into())],
+            );
+
+            assert_eq!(
+                stringify_args(activation, &[root.into(), Value::Null, 2.into()]).unwrap(),
+                "{\n  \"list\": [\n    1,\n    null,\n    \"\\\"a\\\"\\n\"\n  ],\n  \"empty\": []\n}"
+            );
+
+            root.set_property(
+                root,
+                &QName::new(Namespace::public(), "self"),
+                root.into(),
+                activation,
+            )
+            .unwrap();
+            assert_eq!(
+                stringify_args(activation, &[root.into()])
+                    .unwrap_err()
+                    .to_string(),
+                "TypeError: Error #1129: Cyclic structure cannot be converted to JSON string."
+            );
+        });
+    }
+}
@@ -101,14 +101,14 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
     math_wrap_std! {
         write,
         "abs" => f64::abs,
-        "acos" => f64::acos,
-        "asin" => f64::asin,
+        "acos" => acos,
+        "asin" => asin,
         "atan" => f64::atan,
         "ceil" => f64::ceil,
         "cos" => f64::cos,
-        "exp" => f64::exp,
+        "exp" => exp,
         "floor" => f64::floor,
-        "log" => f64::ln,
+        "log" => log,
         "sin" => f64::sin,
         "sqrt" => f64::sqrt,
         "tan" => f64::tan
@@ -127,6 +127,99 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
     class
 }
 
+/// Arc cosine, yielding `NaN` outside of `[-1, 1]`.
+fn acos(x: f64) -> f64 {
+    if x.is_nan() || x.abs() > 1.0 {
+        f64::NAN
+    } else {
+        x.acos()
+    }
+}
+
+/// Arc sine, yielding `NaN` outside of `[-1, 1]`.
+///
+/// The sign of a zero input is preserved.
+fn asin(x: f64) -> f64 {
+    if x.is_nan() || x.abs() > 1.0 {
+        f64::NAN
+    } else if x == 0.0 {
+        x
+    } else {
+        x.asin()
+    }
+}
+
+/// Exponential function, with `exp(-Infinity) == 0`.
+fn exp(x: f64) -> f64 {
+    if x.is_nan() {
+        f64::NAN
+    } else if x == f64::NEG_INFINITY {
+        0.0
+    } else if x == f64::INFINITY {
+        f64::INFINITY
+    } else {
+        x.exp()
+    }
+}
+
+/// Natural logarithm, with `log(±0) == -Infinity` and `NaN` for negatives.
+fn log(x: f64) -> f64 {
+    if x.is_nan() || x < 0.0 {
+        f64::NAN
+    } else if x == 0.0 {
+        f64::NEG_INFINITY
+    } else {
+        x.ln()
+    }
+}
+
+/// Two-argument arc tangent, following the ECMA-262 rules for zeroes and
+/// infinities.
+///
+/// A zero `y` keeps its sign; a zero `x` picks the quadrant by *its* sign, so
+/// `atan2(0, -0) == PI` while `atan2(-0, 0) == -0`.
+fn atan2_f64(y: f64, x: f64) -> f64 {
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    if y.is_nan() || x.is_nan() {
+        return f64::NAN;
+    }
+
+    if y == 0.0 {
+        return if x > 0.0 || (x == 0.0 && x.is_sign_positive()) {
+            y
+        } else {
+            PI.copysign(y)
+        };
+    }
+
+    if x == 0.0 {
+        return FRAC_PI_2.copysign(y);
+    }
+
+    if y.is_infinite() {
+        let angle = if x == f64::INFINITY {
+            FRAC_PI_4
+        } else if x == f64::NEG_INFINITY {
+            3.0 * FRAC_PI_4
+        } else {
+            FRAC_PI_2
+        };
+
+        return angle.copysign(y);
+    }
+
+    if x == f64::INFINITY {
+        return 0.0f64.copysign(y);
+    }
+
+    if x == f64::NEG_INFINITY {
+        return PI.copysign(y);
+    }
+
+    y.atan2(x)
+}
+
 fn round<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
@@ -155,7 +248,7 @@ fn atan2<'gc>(
         .get(1)
         .unwrap_or(&Value::Undefined)
         .coerce_to_number(activation)?;
-    Ok(f64::atan2(y, x).into())
+    Ok(atan2_f64(y, x).into())
 }
 
 fn max<'gc>(
@@ -208,10 +301,176 @@ fn pow<'gc>(
     Ok(f64::powf(n, p).into())
 }
 
+/// Implements `Math.random`.
+///
+/// The result is in `[0, 1)`, so `int(Math.random() * n)` never reaches
+/// `n`. All 53 bits of the mantissa are random.
 pub fn random<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
-    Ok(activation.context.rng.gen_range(0.0f64..1.0f64).into())
+    Ok(activation.context.rng.gen::<f64>().into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::test_utils::with_avm2;
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    /// Compare two numbers, treating `NaN`s as equal and distinguishing the
+    /// sign of zero.
+    fn assert_same(actual: f64, expected: f64, case: &str) {
+        if expected.is_nan() {
+            assert!(actual.is_nan(), "{}: expected NaN, got {}", case, actual);
+        } else {
+            assert_eq!(actual, expected, "{}", case);
+            assert_eq!(
+                actual.is_sign_negative(),
+                expected.is_sign_negative(),
+                "{}: sign mismatch",
+                case
+            );
+        }
+    }
+
+    #[test]
+    fn acos_edge_cases() {
+        for &(input, expected) in &[
+            (f64::NAN, f64::NAN),
+            (1.5, f64::NAN),
+            (-1.5, f64::NAN),
+            (f64::INFINITY, f64::NAN),
+            (1.0, 0.0),
+            (-1.0, PI),
+            (0.0, FRAC_PI_2),
+            (-0.0, FRAC_PI_2),
+        ] {
+            assert_same(acos(input), expected, &format!("acos({})", input));
+        }
+    }
+
+    #[test]
+    fn asin_edge_cases() {
+        for &(input, expected) in &[
+            (f64::NAN, f64::NAN),
+            (2.0, f64::NAN),
+            (-2.0, f64::NAN),
+            (f64::NEG_INFINITY, f64::NAN),
+            (0.0, 0.0),
+            (-0.0, -0.0),
+            (1.0, FRAC_PI_2),
+            (-1.0, -FRAC_PI_2),
+        ] {
+            assert_same(asin(input), expected, &format!("asin({})", input));
+        }
+    }
+
+    #[test]
+    fn exp_edge_cases() {
+        for &(input, expected) in &[
+            (f64::NAN, f64::NAN),
+            (f64::INFINITY, f64::INFINITY),
+            (f64::NEG_INFINITY, 0.0),
+            (0.0, 1.0),
+            (-0.0, 1.0),
+            (1.0, std::f64::consts::E),
+        ] {
+            assert_same(exp(input), expected, &format!("exp({})", input));
+        }
+    }
+
+    #[test]
+    fn log_edge_cases() {
+        for &(input, expected) in &[
+            (f64::NAN, f64::NAN),
+            (-1.0, f64::NAN),
+            (f64::NEG_INFINITY, f64::NAN),
+            (0.0, f64::NEG_INFINITY),
+            (-0.0, f64::NEG_INFINITY),
+            (1.0, 0.0),
+            (f64::INFINITY, f64::INFINITY),
+        ] {
+            assert_same(log(input), expected, &format!("log({})", input));
+        }
+    }
+
+    #[test]
+    fn atan2_edge_cases() {
+        for &(y, x, expected) in &[
+            (f64::NAN, 1.0, f64::NAN),
+            (1.0, f64::NAN, f64::NAN),
+            // Signed zeroes
+            (0.0, 0.0, 0.0),
+            (-0.0, 0.0, -0.0),
+            (0.0, -0.0, PI),
+            (-0.0, -0.0, -PI),
+            (0.0, 1.0, 0.0),
+            (-0.0, 1.0, -0.0),
+            (0.0, -1.0, PI),
+            (-0.0, -1.0, -PI),
+            (1.0, 0.0, FRAC_PI_2),
+            (1.0, -0.0, FRAC_PI_2),
+            (-1.0, 0.0, -FRAC_PI_2),
+            (-1.0, -0.0, -FRAC_PI_2),
+            // Quadrants
+            (1.0, 1.0, FRAC_PI_4),
+            (1.0, -1.0, 3.0 * FRAC_PI_4),
+            (-1.0, -1.0, -3.0 * FRAC_PI_4),
+            (-1.0, 1.0, -FRAC_PI_4),
+            // Infinities
+            (f64::INFINITY, f64::INFINITY, FRAC_PI_4),
+            (f64::INFINITY, f64::NEG_INFINITY, 3.0 * FRAC_PI_4),
+            (f64::NEG_INFINITY, f64::INFINITY, -FRAC_PI_4),
+            (f64::NEG_INFINITY, f64::NEG_INFINITY, -3.0 * FRAC_PI_4),
+            (f64::INFINITY, 1.0, FRAC_PI_2),
+            (f64::NEG_INFINITY, -1.0, -FRAC_PI_2),
+            (1.0, f64::INFINITY, 0.0),
+            (-1.0, f64::INFINITY, -0.0),
+            (1.0, f64::NEG_INFINITY, PI),
+            (-1.0, f64::NEG_INFINITY, -PI),
+        ] {
+            assert_same(atan2_f64(y, x), expected, &format!("atan2({}, {})", y, x));
+        }
+    }
+
+    #[test]
+    fn random_is_uniform_below_one() {
+        const SAMPLES: usize = 100_000;
+        const BUCKETS: usize = 10;
+
+        with_avm2(|activation| {
+            let mut counts = [0; BUCKETS];
+            let mut any_low_bits = false;
+
+            for _ in 0..SAMPLES {
+                let value = random(activation, None, &[])
+                    .unwrap()
+                    .coerce_to_number(activation)
+                    .unwrap();
+
+                assert!((0.0..1.0).contains(&value), "{} is out of range", value);
+
+                let index = (value * BUCKETS as f64) as usize;
+                assert!(index < BUCKETS, "{} indexed past the end", value);
+                counts[index] += 1;
+
+                // `value` is a multiple of 2^-53; check the last bit is used.
+                any_low_bits |= (value * (1u64 << 53) as f64) as u64 & 1 == 1;
+            }
+
+            let expected = SAMPLES / BUCKETS;
+            for (bucket, &count) in counts.iter().enumerate() {
+                assert!(
+                    count > expected * 9 / 10 && count < expected * 11 / 10,
+                    "bucket {} got {} of {} samples",
+                    bucket,
+                    count,
+                    SAMPLES
+                );
+            }
+            assert!(any_low_bits, "the lowest mantissa bit was never set");
+        });
+    }
 }
@@ -4,18 +4,32 @@ use crate::avm2::activation::Activation;
 use crate::avm2::class::Class;
 use crate::avm2::method::Method;
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::Object;
+use crate::avm2::object::{FunctionObject, Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use gc_arena::{GcCell, MutationContext};
 
 /// Implements `Number`'s instance initializer.
 pub fn instance_init<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
-    Err("Number constructor is a stub.".into())
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+
+        if let Some(mut value) = this.as_primitive_mut(activation.context.gc_context) {
+            *value = args
+                .get(0)
+                .unwrap_or(&Value::Number(0.0))
+                .coerce_to_number(activation)?
+                .into();
+        }
+    }
+
+    Ok(Value::Undefined)
 }
 
 /// Implements `Number`'s class initializer.
@@ -27,13 +41,212 @@ pub fn class_init<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Write out a number in any radix from 2 to 36.
+///
+/// Integers are converted exactly. Fractions are expanded one digit at a
+/// time until they run out, or until there are as many significant digits
+/// as a `Number` can hold, with the last digit truncated rather than
+/// rounded.
+pub fn number_to_radix_string(number: f64, radix: u32) -> String {
+    if number.is_nan() {
+        return "NaN".to_string();
+    }
+    if number.is_infinite() {
+        return if number < 0.0 {
+            "-Infinity"
+        } else {
+            "Infinity"
+        }
+        .to_string();
+    }
+
+    let radix_f = f64::from(radix);
+    let max_significant_digits = (53.0 / radix_f.log2()).ceil() as usize;
+    let magnitude = number.abs();
+
+    let mut integer = magnitude.trunc();
+    let mut digits = Vec::new();
+    while integer >= 1.0 {
+        let digit = integer % radix_f;
+        digits.push(std::char::from_digit(digit as u32, radix).unwrap());
+        integer = (integer - digit) / radix_f;
+    }
+    if digits.is_empty() {
+        digits.push('0');
+    }
+    if number < 0.0 && magnitude != 0.0 {
+        digits.push('-');
+    }
+
+    let mut string: String = digits.into_iter().rev().collect();
+    let mut significant_digits = if magnitude >= 1.0 {
+        string.trim_start_matches('-').len()
+    } else {
+        0
+    };
+
+    let mut fraction = magnitude.fract();
+    if fraction > 0.0 {
+        string.push('.');
+    }
+    while fraction > 0.0 && significant_digits < max_significant_digits {
+        fraction *= radix_f;
+        let digit = fraction.trunc();
+        fraction -= digit;
+
+        if digit > 0.0 || significant_digits > 0 {
+            significant_digits += 1;
+        }
+        string.push(std::char::from_digit(digit as u32, radix).unwrap());
+    }
+
+    string
+}
+
+/// Implements the `toString` method of `Number`, `int` and `uint`, which
+/// takes a radix.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let number = this.value_of(activation.context.gc_context)?;
+        let radix = match args.get(0).cloned().unwrap_or(Value::Undefined) {
+            Value::Undefined => 10,
+            radix => radix.coerce_to_i32(activation)?,
+        };
+        if !(2..=36).contains(&radix) {
+            return Err(format!(
+                "RangeError: Error #1003: The radix argument must be between 2 and 36; got {}.",
+                radix
+            )
+            .into());
+        }
+
+        if radix == 10 {
+            return Ok(number.coerce_to_string(activation)?.into());
+        }
+
+        let number = number.coerce_to_number(activation)?;
+        return Ok(AvmString::new(
+            activation.context.gc_context,
+            number_to_radix_string(number, radix as u32),
+        )
+        .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Install the public `toString` method on the prototype of `Number`, `int`
+/// or `uint`.
+///
+/// The class only defines `toString` in the `AS3` namespace. The public one
+/// belongs to the prototype, so that boxed numbers don't report it as their
+/// own property.
+pub fn fill_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    mut proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) {
+    proto.install_method(
+        gc_context,
+        QName::new(Namespace::public(), "toString"),
+        0,
+        FunctionObject::from_builtin(gc_context, to_string, fn_proto),
+    );
+}
+
 /// Construct `Number`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
-    Class::new(
+    let class = Class::new(
         QName::new(Namespace::public(), "Number"),
         Some(QName::new(Namespace::public(), "Object").into()),
         Method::from_builtin(instance_init),
         Method::from_builtin(class_init),
         mc,
-    )
+    );
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "toString"),
+        Method::from_builtin(to_string),
+    ));
+
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::object::PrimitiveObject;
+    use crate::avm2::test_utils::with_avm2;
+
+    fn to_string_in<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        number: Value<'gc>,
+        radix: i32,
+    ) -> Result<String, Error> {
+        let proto = activation.avm2().prototypes().number;
+        let this = PrimitiveObject::from_primitive(number, proto, activation.context.gc_context)?;
+
+        Ok(to_string(activation, Some(this), &[radix.into()])?
+            .coerce_to_string(activation)?
+            .to_string())
+    }
+
+    #[test]
+    fn integers_in_other_radixes() {
+        with_avm2(|activation| {
+            for (number, radix, expected) in &[
+                (Value::Integer(255), 2, "11111111"),
+                (Value::Integer(255), 16, "ff"),
+                (Value::Unsigned(u32::MAX), 16, "ffffffff"),
+                (Value::Integer(1295), 36, "zz"),
+                (Value::Integer(-255), 16, "-ff"),
+                (
+                    Value::Integer(i32::MIN),
+                    2,
+                    "-10000000000000000000000000000000",
+                ),
+                (Value::Number(0.0), 36, "0"),
+                (Value::Number(-0.0), 2, "0"),
+                (Value::Number(9007199254740993.0), 36, "2gosa7pa2gw"),
+            ] {
+                assert_eq!(
+                    to_string_in(activation, number.clone(), *radix).unwrap(),
+                    *expected,
+                    "{:?} in radix {}",
+                    number,
+                    radix
+                );
+            }
+        });
+    }
+
+    #[test]
+    fn fractions_and_invalid_radixes() {
+        assert_eq!(number_to_radix_string(0.5, 2), "0.1");
+        assert_eq!(number_to_radix_string(-255.5, 16), "-ff.8");
+        assert_eq!(
+            number_to_radix_string(0.1, 2),
+            "0.0001100110011001100110011001100110011001100110011001101"
+        );
+        assert_eq!(number_to_radix_string(f64::NAN, 16), "NaN");
+        assert_eq!(number_to_radix_string(f64::NEG_INFINITY, 2), "-Infinity");
+
+        with_avm2(|activation| {
+            for radix in &[1, 37] {
+                assert_eq!(
+                    to_string_in(activation, 10.into(), *radix)
+                        .unwrap_err()
+                        .to_string(),
+                    format!(
+                        "RangeError: Error #1003: The radix argument must be between 2 and 36; got {}.",
+                        radix
+                    )
+                );
+            }
+        });
+    }
 }
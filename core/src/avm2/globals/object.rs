@@ -70,7 +70,9 @@ pub fn has_own_property<'gc>(
     let name = name?.coerce_to_string(activation)?;
 
     if let Some(ns) = this.resolve_any(name)? {
-        if !ns.is_private() {
+        // `AS3` methods are fixed traits of the class, not properties of
+        // the object.
+        if !ns.is_private() && ns != Namespace::as3_namespace() {
             let qname = QName::new(ns, name);
             return Ok(this.has_own_property(&qname)?.into());
         }
@@ -116,6 +116,33 @@ fn char_code_at<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `String.localeCompare`
+fn locale_compare<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Value::String(s) = this.value_of(activation.context.gc_context)? {
+            let other = args
+                .get(0)
+                .cloned()
+                .unwrap_or(Value::Undefined)
+                .coerce_to_string(activation)?;
+            let order = string_utils::swf_string_locale_cmp(
+                &s,
+                &other,
+                &activation.context.system.language,
+                true,
+            );
+
+            return Ok((order as i32).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Construct `String`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
     let class = Class::new(
@@ -142,6 +169,10 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         QName::new(Namespace::as3_namespace(), "charCodeAt"),
         Method::from_builtin(char_code_at),
     ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "localeCompare"),
+        Method::from_builtin(locale_compare),
+    ));
 
     class
 }
@@ -2,14 +2,17 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::object::Object;
+use crate::avm2::property_cache::PropertyCache;
 use crate::avm2::script::TranslationUnit;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::collect::CollectWrapper;
-use gc_arena::{Collect, CollectionContext, Gc, MutationContext};
+use gc_arena::{Collect, CollectionContext, Gc, GcCell, MutationContext};
 use std::fmt;
 use std::rc::Rc;
-use swf::avm2::types::{AbcFile, Index, Method as AbcMethod, MethodBody as AbcMethodBody};
+use swf::avm2::types::{
+    AbcFile, Index, Method as AbcMethod, MethodBody as AbcMethodBody, Multiname as AbcMultiname,
+};
 
 /// Represents a function defined in Ruffle's code.
 ///
@@ -46,6 +49,9 @@ pub struct BytecodeMethod<'gc> {
 
     /// The ABC method body this function uses.
     pub abc_method_body: Option<u32>,
+
+    /// What the property accesses in this method's body last resolved to.
+    property_cache: GcCell<'gc, PropertyCache<'gc>>,
 }
 
 impl<'gc> BytecodeMethod<'gc> {
@@ -70,6 +76,7 @@ impl<'gc> BytecodeMethod<'gc> {
                             abc: CollectWrapper(txunit.abc()),
                             abc_method: abc_method.0,
                             abc_method_body: Some(index as u32),
+                            property_cache: GcCell::allocate(mc, PropertyCache::default()),
                         },
                     ));
                 }
@@ -83,6 +90,7 @@ impl<'gc> BytecodeMethod<'gc> {
                 abc: CollectWrapper(txunit.abc()),
                 abc_method: abc_method.0,
                 abc_method_body: None,
+                property_cache: GcCell::allocate(mc, PropertyCache::default()),
             },
         ))
     }
@@ -102,6 +110,44 @@ impl<'gc> BytecodeMethod<'gc> {
         &self.abc.0.methods.get(self.abc_method as usize).unwrap()
     }
 
+    /// Get the local name of a type in this method's signature.
+    ///
+    /// Returns `None` for the any type (`*`) and for types that are not
+    /// named by a `QName`.
+    fn type_name(&self, type_index: &Index<AbcMultiname>) -> Option<&str> {
+        let pool = &self.abc.0.constant_pool;
+        let name = match pool
+            .multinames
+            .get((type_index.0 as usize).checked_sub(1)?)?
+        {
+            AbcMultiname::QName { name, .. } | AbcMultiname::QNameA { name, .. } => name,
+            _ => return None,
+        };
+
+        pool.strings
+            .get((name.0 as usize).checked_sub(1)?)
+            .map(|s| s.as_str())
+    }
+
+    /// Determine if this method takes and returns the same types as another.
+    ///
+    /// Types are compared by local name.
+    pub fn signature_matches(&self, other: &Self) -> bool {
+        let method = self.method();
+        let other_method = other.method();
+
+        method.params.len() == other_method.params.len()
+            && method.needs_rest == other_method.needs_rest
+            && self.type_name(&method.return_type) == other.type_name(&other_method.return_type)
+            && method
+                .params
+                .iter()
+                .zip(other_method.params.iter())
+                .all(|(param, other_param)| {
+                    self.type_name(&param.kind) == other.type_name(&other_param.kind)
+                })
+    }
+
     /// Get a reference to the ABC method body entry this refers to.
     ///
     /// Some methods do not have bodies; this returns `None` in that case.
@@ -112,6 +158,53 @@ impl<'gc> BytecodeMethod<'gc> {
             None
         }
     }
+
+    /// Check that this method can be called with `num_arguments` arguments.
+    ///
+    /// Methods must be given all of their required parameters, and may only
+    /// be given more than they declare if they take `...rest` or use
+    /// `arguments`.
+    pub fn check_argument_count(
+        &self,
+        num_arguments: usize,
+        mc: MutationContext<'gc, '_>,
+    ) -> Result<(), Error> {
+        let method = self.method();
+        let num_declared = method.params.len();
+        let num_required = method
+            .params
+            .iter()
+            .filter(|param| param.default_value.is_none())
+            .count();
+        let takes_extra = method.needs_rest || method.needs_arguments_object;
+
+        let expected = if num_arguments < num_required {
+            num_required
+        } else if num_arguments > num_declared && !takes_extra {
+            num_declared
+        } else {
+            return Ok(());
+        };
+
+        let name = self
+            .txunit
+            .pool_string(method.name.0, mc)
+            .ok()
+            .filter(|name| !name.is_empty())
+            .map(|name| name.to_string())
+            .unwrap_or_else(|| "Function/<anonymous>".to_string());
+
+        Err(format!(
+            "ArgumentError: Error #1063: Argument count mismatch on {}(). Expected {}, got {}.",
+            name, expected, num_arguments
+        )
+        .into())
+    }
+
+    /// Get the inline caches of this method's property accesses.
+    pub fn property_cache(&self) -> GcCell<'gc, PropertyCache<'gc>> {
+        self.property_cache
+    }
 }
 
 /// An uninstantiated method that can either be natively implemented or sourced
@@ -176,4 +269,192 @@ impl<'gc> Method<'gc> {
             Method::Entry(bm) => Ok(bm),
         }
     }
+
+    /// Determine if this method can override another.
+    ///
+    /// Native methods carry no signature, and are compatible with anything.
+    pub fn signature_matches(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Method::Entry(method), Method::Entry(other)) => method.signature_matches(other),
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::avm2::activation::Activation;
+    use crate::avm2::names::{Namespace, QName};
+    use crate::avm2::object::{Object, TObject};
+    use crate::avm2::script::TranslationUnit;
+    use crate::avm2::test_utils::with_avm2;
+    use crate::avm2::value::Value;
+    use crate::avm2::Error;
+    use std::marker::PhantomData;
+    use std::rc::Rc;
+    use swf::avm2::types::{
+        AbcFile, ConstantPool, DefaultValue, Index, Method as AbcMethod,
+        MethodBody as AbcMethodBody, MethodParam, Multiname as AbcMultiname,
+        Namespace as AbcNamespace, Script as AbcScript, Trait as AbcTrait,
+        TraitKind as AbcTraitKind,
+    };
+
+    /// An ABC file with two functions: `pick(a, b = true)`, which yields `b`,
+    /// and `spread(a, ...rest)`, which yields `a`.
+    fn arguments_abc() -> AbcFile {
+        let param = |default_value| MethodParam {
+            name: None,
+            kind: Index(0, PhantomData),
+            default_value,
+        };
+        let method = |name, params, needs_rest| AbcMethod {
+            name: Index(name, PhantomData),
+            params,
+            return_type: Index(0, PhantomData),
+            needs_arguments_object: false,
+            needs_activation: false,
+            needs_rest,
+            needs_dxns: false,
+        };
+        let body = |method, num_locals, code| AbcMethodBody {
+            method: Index(method, PhantomData),
+            max_stack: 1,
+            num_locals,
+            init_scope_depth: 0,
+            max_scope_depth: 0,
+            code,
+            exceptions: vec![],
+            traits: vec![],
+        };
+        let function = |name, method| AbcTrait {
+            name: Index(name, PhantomData),
+            kind: AbcTraitKind::Method {
+                disp_id: 0,
+                method: Index(method, PhantomData),
+            },
+            metadata: vec![],
+            is_final: false,
+            is_override: false,
+        };
+
+        AbcFile {
+            major_version: 46,
+            minor_version: 16,
+            constant_pool: ConstantPool {
+                ints: vec![],
+                uints: vec![],
+                doubles: vec![],
+                strings: vec!["".to_string(), "pick".to_string(), "spread".to_string()],
+                namespaces: vec![AbcNamespace::Package(Index(1, PhantomData))],
+                namespace_sets: vec![],
+                multinames: vec![
+                    AbcMultiname::QName {
+                        namespace: Index(1, PhantomData),
+                        name: Index(2, PhantomData),
+                    },
+                    AbcMultiname::QName {
+                        namespace: Index(1, PhantomData),
+                        name: Index(3, PhantomData),
+                    },
+                ],
+            },
+            methods: vec![
+                method(0, vec![], false),
+                method(2, vec![param(None), param(Some(DefaultValue::True))], false),
+                method(3, vec![param(None)], true),
+            ],
+            metadata: vec![],
+            instances: vec![],
+            classes: vec![],
+            scripts: vec![AbcScript {
+                init_method: Index(0, PhantomData),
+                traits: vec![function(1, 1), function(2, 2)],
+            }],
+            method_bodies: vec![
+                // returnvoid
+                body(0, 1, vec![0x47]),
+                // getlocal2; returnvalue
+                body(1, 3, vec![0xD2, 0x48]),
+                // getlocal1; returnvalue
+                body(2, 3, vec![0xD1, 0x48]),
+            ],
+        }
+    }
+
+    /// Load `arguments_abc` and get its script's global object.
+    fn load<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Object<'gc> {
+        let domain = activation.avm2().global_domain();
+        let tunit = TranslationUnit::from_abc(
+            Rc::new(arguments_abc()),
+            domain,
+            activation.context.gc_context,
+        );
+        let mut script = tunit
+            .load_script(0, activation.context.avm2, activation.context.gc_context)
+            .unwrap();
+
+        script.globals(&mut activation.context).unwrap()
+    }
+
+    fn call<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        mut globals: Object<'gc>,
+        name: &'static str,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let function = globals
+            .get_property(globals, &QName::new(Namespace::public(), name), activation)
+            .unwrap()
+            .coerce_to_object(activation)
+            .unwrap();
+
+        function.call(None, args, activation, None)
+    }
+
+    #[test]
+    fn argument_counts_are_checked() {
+        with_avm2(|activation| {
+            let globals = load(activation);
+
+            // Missing optional parameters take their default.
+            assert_eq!(
+                call(activation, globals, "pick", &[1.into()]).unwrap(),
+                true.into()
+            );
+            assert_eq!(
+                call(activation, globals, "pick", &[1.into(), 2.into()]).unwrap(),
+                2.into()
+            );
+
+            let error = call(activation, globals, "pick", &[]).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "ArgumentError: Error #1063: Argument count mismatch on pick(). Expected 1, got 0."
+            );
+            let error =
+                call(activation, globals, "pick", &[1.into(), 2.into(), 3.into()]).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "ArgumentError: Error #1063: Argument count mismatch on pick(). Expected 2, got 3."
+            );
+
+            // Extra arguments go into `...rest`, but required ones are still
+            // required.
+            assert_eq!(
+                call(
+                    activation,
+                    globals,
+                    "spread",
+                    &[1.into(), 2.into(), 3.into()]
+                )
+                .unwrap(),
+                1.into()
+            );
+            let error = call(activation, globals, "spread", &[]).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "ArgumentError: Error #1063: Argument count mismatch on spread(). Expected 1, got 0."
+            );
+        });
+    }
 }
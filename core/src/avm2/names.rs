@@ -70,6 +70,16 @@ impl<'gc> Namespace<'gc> {
         Self::Namespace("http://adobe.com/AS3/2006/builtin".into())
     }
 
+    /// The `flash_proxy` namespace, which `Proxy`'s traps are defined in.
+    pub fn flash_proxy() -> Self {
+        Self::Namespace("http://www.adobe.com/2006/actionscript/flash/proxy".into())
+    }
+
+    /// The private namespace that native classes keep their hidden state in.
+    pub fn ruffle_private() -> Self {
+        Self::Private("ruffle".into())
+    }
+
     pub fn package(package_name: impl Into<AvmString<'gc>>) -> Self {
         Self::Package(package_name.into())
     }
@@ -324,6 +334,26 @@ impl<'gc> Multiname<'gc> {
         })
     }
 
+    /// Determine if a multiname in the ABC constant pool can be read without
+    /// popping any of its parts off the operand stack.
+    pub fn is_abc_multiname_static(
+        translation_unit: TranslationUnit<'gc>,
+        multiname_index: Index<AbcMultiname>,
+    ) -> bool {
+        let abc = translation_unit.abc();
+        let abc_multiname = (multiname_index.0 as usize)
+            .checked_sub(1)
+            .and_then(|actual_index| abc.constant_pool.multinames.get(actual_index));
+
+        matches!(
+            abc_multiname,
+            Some(AbcMultiname::QName { .. })
+                | Some(AbcMultiname::QNameA { .. })
+                | Some(AbcMultiname::Multiname { .. })
+                | Some(AbcMultiname::MultinameA { .. })
+        )
+    }
+
     /// Read a static multiname from the ABC constant pool
     ///
     /// This function prohibits the use of runtime-qualified and late-bound
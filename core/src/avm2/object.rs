@@ -2,6 +2,7 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::array::ArrayStorage;
+use crate::avm2::bytearray::ByteArrayStorage;
 use crate::avm2::class::Class;
 use crate::avm2::domain::Domain;
 use crate::avm2::events::{DispatchList, Event};
@@ -19,6 +20,7 @@ use std::cell::{Ref, RefMut};
 use std::fmt::Debug;
 
 mod array_object;
+mod bytearray_object;
 mod custom_object;
 mod dispatch_object;
 mod domain_object;
@@ -31,6 +33,7 @@ mod stage_object;
 mod xml_object;
 
 pub use crate::avm2::object::array_object::ArrayObject;
+pub use crate::avm2::object::bytearray_object::ByteArrayObject;
 pub use crate::avm2::object::dispatch_object::DispatchObject;
 pub use crate::avm2::object::domain_object::DomainObject;
 pub use crate::avm2::object::event_object::EventObject;
@@ -57,6 +60,7 @@ pub use crate::avm2::object::xml_object::XmlObject;
         EventObject(EventObject<'gc>),
         DispatchObject(DispatchObject<'gc>),
         XmlObject(XmlObject<'gc>),
+        ByteArrayObject(ByteArrayObject<'gc>),
     }
 )]
 pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy {
@@ -272,6 +276,36 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         Ok(None)
     }
 
+    /// Resolve the multiname of a method call into a single QName, if any of
+    /// the namespaces match.
+    ///
+    /// Built-in classes define some of their methods only in the `AS3`
+    /// namespace. Code compiled without `use namespace AS3` calls those by
+    /// their public name, so a public call that matches nothing else falls
+    /// back to the `AS3` method of the same name.
+    fn resolve_method_multiname(
+        self,
+        multiname: &Multiname<'gc>,
+    ) -> Result<Option<QName<'gc>>, Error> {
+        if let Some(name) = self.resolve_multiname(multiname)? {
+            return Ok(Some(name));
+        }
+
+        let as3 = Namespace::as3_namespace();
+        if multiname.namespace_set().any(|ns| ns.is_public())
+            && !multiname.namespace_set().any(|ns| *ns == as3)
+        {
+            if let Some(name) = multiname.local_name() {
+                let qname = QName::new(as3, name);
+                if self.has_property(&qname)? {
+                    return Ok(Some(qname));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
     /// Given a local name, find the namespace it resides in, if any.
     ///
     /// The `Namespace` must not be `Namespace::Any`, as this function exists
@@ -291,14 +325,25 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         -> Result<Option<Namespace<'gc>>, Error>;
 
     /// Indicates whether or not a property exists on an object.
+    ///
+    /// This checks the object's own traits and dynamic properties, followed
+    /// by every object on its prototype chain.
     fn has_property(self, name: &QName<'gc>) -> Result<bool, Error> {
         if self.has_own_property(name)? {
-            Ok(true)
-        } else if let Some(proto) = self.proto() {
-            Ok(proto.has_own_property(name)?)
-        } else {
-            Ok(false)
+            return Ok(true);
         }
+
+        let mut proto = self.proto();
+
+        while let Some(p) = proto {
+            if p.has_own_property(name)? {
+                return Ok(true);
+            }
+
+            proto = p.proto();
+        }
+
+        Ok(false)
     }
 
     /// Indicates whether or not a property or trait exists on an object and is
@@ -320,6 +365,10 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     /// object can have but has not yet instantiated.
     fn has_instantiated_property(self, name: &QName<'gc>) -> bool;
 
+    /// Retrieve the slot that an instantiated property of this object is
+    /// stored in, if it is a slot.
+    fn get_own_slot_id(self, name: &QName<'gc>) -> Option<u32>;
+
     /// Check if a particular object contains a virtual getter by the given
     /// name.
     fn has_own_virtual_getter(self, name: &QName<'gc>) -> bool;
@@ -352,19 +401,51 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     /// This method primarily exists so that the global scope that player
     /// globals loads into can be created before its superclasses are. It
     /// should be used sparingly, if at all.
-    fn set_proto(self, mc: MutationContext<'gc, '_>, proto: Object<'gc>);
+    ///
+    /// Attempting to set a proto that would make this object part of its own
+    /// prototype chain is an error, and leaves the existing proto in place.
+    fn set_proto(self, mc: MutationContext<'gc, '_>, proto: Object<'gc>) -> Result<(), Error>;
+
+    /// Check that using `proto` as this object's `__proto__` would not create
+    /// a cycle in the prototype chain.
+    fn validate_proto(&self, proto: Object<'gc>) -> Result<(), Error> {
+        let mut next = Some(proto);
+
+        while let Some(p) = next {
+            if p.as_ptr() == self.as_ptr() {
+                return Err("TypeError: Cannot set prototype, as it would create a cycle".into());
+            }
+
+            next = p.proto();
+        }
+
+        Ok(())
+    }
 
     /// Retrieve a given enumerable name by index.
     ///
-    /// Enumerants are listed by index, starting from zero. A value of `None`
-    /// indicates that no enumerant with that index, or any greater index,
-    /// exists. (In other words, it means stop.)
+    /// Enumerants are listed by index, starting from one. A value of `None`
+    /// indicates that no enumerant with that index exists; use
+    /// `get_next_enumerant` to find the next index that does.
     ///
     /// Objects are responsible for maintaining a consistently ordered and
     /// indexed list of enumerable names which can be queried by this
     /// mechanism.
     fn get_enumerant_name(&self, index: u32) -> Option<QName<'gc>>;
 
+    /// Retrieve the index of the first enumerant after `last_index`.
+    ///
+    /// Enumeration starts from an index of zero. A value of `None` means
+    /// there are no more enumerants. (In other words, it means stop.)
+    ///
+    /// Indices are skipped where names stopped being enumerable, so deleting
+    /// properties mid-enumeration doesn't disturb the names still to come.
+    fn get_next_enumerant(&self, last_index: u32) -> Option<u32> {
+        let index = last_index.checked_add(1)?;
+
+        self.get_enumerant_name(index).map(|_| index)
+    }
+
     /// Determine if a property is currently enumerable.
     ///
     /// Properties that do not exist are also not enumerable.
@@ -592,12 +673,11 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
             TraitKind::Function {
                 slot_id, function, ..
             } => {
-                let mut fobject = FunctionObject::from_method(
+                let mut fobject = FunctionObject::from_function(
                     activation.context.gc_context,
                     function.clone(),
                     scope,
                     fn_proto,
-                    None,
                 );
                 let es3_proto = ScriptObject::object(
                     activation.context.gc_context,
@@ -808,13 +888,13 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     /// This only yields `None` for bare objects.
     fn as_proto_class(&self) -> Option<GcCell<'gc, Class<'gc>>> {
         let mut class = self.as_class();
+        let mut proto = self.proto();
 
         while class.is_none() {
-            if let Some(proto) = self.proto() {
-                class = proto.as_class();
-            } else {
-                return None;
-            }
+            let this_proto = proto?;
+
+            class = this_proto.as_class();
+            proto = this_proto.proto();
         }
 
         class
@@ -843,6 +923,16 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         None
     }
 
+    /// Unwrap this object as byte array storage.
+    fn as_bytearray(&self) -> Option<Ref<ByteArrayStorage>> {
+        None
+    }
+
+    /// Unwrap this object as mutable byte array storage.
+    fn as_bytearray_mut(&self, _mc: MutationContext<'gc, '_>) -> Option<RefMut<ByteArrayStorage>> {
+        None
+    }
+
     /// Get this object's `DisplayObject`, if it has one.
     fn as_display_object(&self) -> Option<DisplayObject<'gc>> {
         None
@@ -892,3 +982,643 @@ impl<'gc> Object<'gc> {
         a.as_ptr() == b.as_ptr()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::array::ArrayStorage;
+    use crate::avm2::class::ClassAttributes;
+    use crate::avm2::method::{Method, NativeMethod};
+    use crate::avm2::object::FunctionObject;
+    use crate::avm2::test_utils::{construct, with_avm2};
+    use crate::avm2::traits::{Trait, TraitAttributes};
+    use gc_arena::{make_arena, rootless_arena, ArenaParameters};
+
+    #[derive(Collect)]
+    #[collect(no_drop)]
+    struct TestRoot<'gc> {
+        object: Object<'gc>,
+    }
+
+    make_arena!(TestArena, TestRoot);
+
+    fn enumerant_names(object: Object<'_>) -> Vec<String> {
+        std::iter::successors(object.get_next_enumerant(0), |i| {
+            object.get_next_enumerant(*i)
+        })
+        .map(|i| {
+            object
+                .get_enumerant_name(i)
+                .unwrap()
+                .local_name()
+                .to_string()
+        })
+        .collect()
+    }
+
+    #[test]
+    fn proto_cycles_are_rejected() {
+        rootless_arena(|mc| {
+            let a = ScriptObject::bare_object(mc);
+            let b = ScriptObject::object(mc, a);
+            let c = ScriptObject::object(mc, b);
+
+            assert!(a.set_proto(mc, c).is_err());
+            assert!(a.set_proto(mc, a).is_err());
+            assert!(a.proto().is_none());
+
+            let name = QName::new(Namespace::public(), "missing");
+            assert!(!c.has_property(&name).unwrap());
+        });
+    }
+
+    #[test]
+    fn has_property_covers_the_chain_own_properties_and_indices() {
+        with_avm2(|activation| {
+            let mc = activation.context.gc_context;
+
+            // `toString` lives on `Object.prototype`, two links up the chain.
+            let sprite = construct(activation, "flash.display", "Sprite", &[]);
+            let to_string = QName::new(Namespace::public(), "toString");
+            assert!(!sprite.has_own_property(&to_string).unwrap());
+            assert!(sprite.has_property(&to_string).unwrap());
+
+            let mut object = ScriptObject::object(mc, activation.avm2().prototypes().object);
+            let answer = QName::new(Namespace::public(), "answer");
+            assert!(!object.has_property(&answer).unwrap());
+            object
+                .install_dynamic_property(mc, answer.clone(), 42.into())
+                .unwrap();
+            assert!(object.has_property(&answer).unwrap());
+
+            let array = ArrayObject::from_array(
+                ArrayStorage::from_args(&[1.into(), 2.into(), 3.into()]),
+                activation.avm2().prototypes().array,
+                mc,
+            );
+            assert!(array
+                .has_property(&QName::new(Namespace::public(), "2"))
+                .unwrap());
+            assert!(!array
+                .has_property(&QName::new(Namespace::public(), "3"))
+                .unwrap());
+            assert!(array
+                .has_property(&QName::new(Namespace::public(), "length"))
+                .unwrap());
+        });
+    }
+
+    #[test]
+    fn deep_proto_chains_are_allowed() {
+        rootless_arena(|mc| {
+            let root = ScriptObject::bare_object(mc);
+            let mut leaf = root;
+
+            for _ in 0..1000 {
+                leaf = ScriptObject::object(mc, leaf);
+            }
+
+            let top = ScriptObject::bare_object(mc);
+            assert!(root.set_proto(mc, top).is_ok());
+            assert!(leaf.has_prototype_in_chain(top, false).unwrap());
+        });
+    }
+
+    #[test]
+    fn enumeration_order_survives_collection() {
+        let names = ["zeta", "alpha", "0", "mid", "10", "beta", "2"];
+
+        let mut arena = TestArena::new(ArenaParameters::default(), |mc| {
+            let mut object = ScriptObject::bare_object(mc);
+
+            for (i, name) in names.iter().enumerate() {
+                let qname = QName::new(Namespace::public(), *name);
+                object
+                    .install_dynamic_property(mc, qname.clone(), (i as i32).into())
+                    .unwrap();
+                object
+                    .set_local_property_is_enumerable(mc, &qname, true)
+                    .unwrap();
+            }
+
+            TestRoot { object }
+        });
+
+        let before = arena.mutate(|_mc, root| enumerant_names(root.object));
+        assert_eq!(before, names);
+
+        // Produce garbage, including objects with properties of their own, so
+        // that the collection has real work to do.
+        arena.mutate(|mc, _root| {
+            for i in 0..1000 {
+                let mut garbage = ScriptObject::bare_object(mc);
+                garbage
+                    .install_dynamic_property(
+                        mc,
+                        QName::new(Namespace::public(), "garbage"),
+                        i.into(),
+                    )
+                    .unwrap();
+            }
+        });
+        arena.collect_all();
+
+        let after = arena.mutate(|_mc, root| enumerant_names(root.object));
+        assert_eq!(before, after);
+    }
+
+    #[test]
+    fn deleted_properties_become_collectable() {
+        let mut arena = TestArena::new(ArenaParameters::default(), |mc| TestRoot {
+            object: ScriptObject::bare_object(mc),
+        });
+        arena.collect_all();
+        let empty = arena.total_allocated();
+
+        arena.mutate(|mc, root| {
+            let mut big = ScriptObject::bare_object(mc);
+            for i in 0..1000 {
+                let name = AvmString::new(mc, i.to_string());
+                big.install_dynamic_property(
+                    mc,
+                    QName::new(Namespace::public(), name),
+                    ScriptObject::bare_object(mc).into(),
+                )
+                .unwrap();
+            }
+
+            let mut object = root.object;
+            let name = QName::new(Namespace::public(), "big");
+            object
+                .install_dynamic_property(mc, name.clone(), big.into())
+                .unwrap();
+            object
+                .set_local_property_is_enumerable(mc, &name, true)
+                .unwrap();
+        });
+        arena.collect_all();
+        assert!(arena.total_allocated() > empty);
+
+        arena.mutate(|mc, root| {
+            let name = QName::new(Namespace::public(), "big");
+            assert!(root.object.delete_property(mc, &name));
+            assert!(enumerant_names(root.object).is_empty());
+        });
+        arena.collect_all();
+        assert_eq!(arena.total_allocated(), empty);
+    }
+
+    #[test]
+    fn default_to_string_names_the_most_derived_class() {
+        with_avm2(|activation| {
+            let object_proto = activation.avm2().prototypes().object;
+            let literal = ScriptObject::object(activation.context.gc_context, object_proto);
+            assert_eq!(
+                call_to_string(activation, literal),
+                "[object Object]".into()
+            );
+
+            for (package, name) in &[
+                ("flash.display", "Sprite"),
+                ("flash.display", "MovieClip"),
+                ("flash.ui", "ContextMenuItem"),
+            ] {
+                let mut class = activation
+                    .avm2()
+                    .global_domain()
+                    .get_defined_value(activation, QName::new(Namespace::package(*package), *name))
+                    .unwrap()
+                    .coerce_to_object(activation)
+                    .unwrap();
+                let proto = class
+                    .get_property(
+                        class,
+                        &QName::new(Namespace::public(), "prototype"),
+                        activation,
+                    )
+                    .unwrap()
+                    .coerce_to_object(activation)
+                    .unwrap();
+                let instance = proto.construct(activation, &[]).unwrap();
+                class
+                    .call(Some(instance), &[], activation, instance.proto())
+                    .unwrap();
+
+                let expected =
+                    AvmString::new(activation.context.gc_context, format!("[object {}]", name));
+                assert_eq!(call_to_string(activation, instance), expected.into());
+            }
+        });
+    }
+
+    #[test]
+    fn extracted_methods_are_the_same_object_each_time() {
+        with_avm2(|activation| {
+            let mut class = activation
+                .avm2()
+                .global_domain()
+                .get_defined_value(
+                    activation,
+                    QName::new(Namespace::package("flash.events"), "EventDispatcher"),
+                )
+                .unwrap()
+                .coerce_to_object(activation)
+                .unwrap();
+            let proto = class
+                .get_property(
+                    class,
+                    &QName::new(Namespace::public(), "prototype"),
+                    activation,
+                )
+                .unwrap()
+                .coerce_to_object(activation)
+                .unwrap();
+            let name = QName::new(Namespace::public(), "addEventListener");
+            let mut methods = Vec::new();
+            for _ in 0..2 {
+                let mut instance = proto.construct(activation, &[]).unwrap();
+                let first = instance.get_property(instance, &name, activation).unwrap();
+                let second = instance.get_property(instance, &name, activation).unwrap();
+                assert!(first.strict_eq(&second));
+
+                methods.push(first);
+            }
+
+            // Each dispatcher has its own bound method.
+            assert!(!methods[0].strict_eq(&methods[1]));
+        });
+    }
+
+    /// Call an object's `toString` method as content would.
+    fn call_to_string<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        mut object: Object<'gc>,
+    ) -> Value<'gc> {
+        object
+            .get_property(
+                object,
+                &QName::new(Namespace::public(), "toString"),
+                activation,
+            )
+            .unwrap()
+            .coerce_to_object(activation)
+            .unwrap()
+            .call(Some(object), &[], activation, None)
+            .unwrap()
+    }
+
+    fn empty_init<'gc>(
+        _activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        Ok(Value::Undefined)
+    }
+
+    /// Construct a class with a public `String` slot and method for each of
+    /// `traits`, yielding its constructor.
+    fn class_with_traits<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        name: &'static str,
+        base: Option<Object<'gc>>,
+        attributes: ClassAttributes,
+        traits: &[&'static str],
+    ) -> Object<'gc> {
+        let base = base.unwrap_or_else(|| {
+            activation
+                .avm2()
+                .global_domain()
+                .get_defined_value(activation, QName::new(Namespace::public(), "Object"))
+                .unwrap()
+                .coerce_to_object(activation)
+                .unwrap()
+        });
+        let base_name = base.as_class().unwrap().read().name().clone();
+
+        let mc = activation.context.gc_context;
+        let class = Class::new(
+            QName::new(Namespace::public(), name),
+            Some(base_name.into()),
+            Method::from_builtin(empty_init),
+            Method::from_builtin(empty_init),
+            mc,
+        );
+        class.write(mc).set_attributes(attributes);
+
+        for trait_name in traits {
+            class.write(mc).define_instance_trait(Trait::from_slot(
+                QName::new(Namespace::public(), *trait_name),
+                QName::new(Namespace::public(), "String").into(),
+                None,
+            ));
+            class.write(mc).define_instance_trait(Trait::from_method(
+                QName::new(
+                    Namespace::public(),
+                    AvmString::new(mc, format!("{}Method", trait_name)),
+                ),
+                Method::from_builtin(empty_init),
+            ));
+        }
+
+        FunctionObject::from_class(activation, class, Some(base), None)
+            .unwrap()
+            .0
+    }
+
+    /// Construct an instance of a class, and touch each of the given traits
+    /// so that they are installed on it.
+    fn instance_touching<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        mut class: Object<'gc>,
+        traits: &[&'static str],
+    ) -> Object<'gc> {
+        let proto = class
+            .get_property(
+                class,
+                &QName::new(Namespace::public(), "prototype"),
+                activation,
+            )
+            .unwrap()
+            .coerce_to_object(activation)
+            .unwrap();
+        let mut instance = proto.construct(activation, &[]).unwrap();
+        class
+            .call(Some(instance), &[], activation, Some(proto))
+            .unwrap();
+
+        for trait_name in traits {
+            let slot = QName::new(Namespace::public(), *trait_name);
+            instance
+                .set_property(instance, &slot, "set".into(), activation)
+                .unwrap();
+            instance.get_property(instance, &slot, activation).unwrap();
+
+            let method = QName::new(
+                Namespace::public(),
+                AvmString::new(
+                    activation.context.gc_context,
+                    format!("{}Method", trait_name),
+                ),
+            );
+            instance
+                .get_property(instance, &method, activation)
+                .unwrap()
+                .coerce_to_object(activation)
+                .unwrap()
+                .call(Some(instance), &[], activation, None)
+                .unwrap();
+        }
+
+        instance
+    }
+
+    #[test]
+    fn declared_traits_never_enumerate() {
+        with_avm2(|activation| {
+            let sealed = class_with_traits(
+                activation,
+                "Sealed",
+                None,
+                ClassAttributes::SEALED,
+                &["field"],
+            );
+            let instance = instance_touching(activation, sealed, &["field"]);
+
+            assert!(enumerant_names(instance).is_empty());
+        });
+    }
+
+    #[test]
+    fn dynamic_subclasses_enumerate_only_dynamic_properties() {
+        with_avm2(|activation| {
+            let base = class_with_traits(
+                activation,
+                "Base",
+                None,
+                ClassAttributes::empty(),
+                &["inherited"],
+            );
+            let derived = class_with_traits(
+                activation,
+                "Derived",
+                Some(base),
+                ClassAttributes::empty(),
+                &["declared"],
+            );
+            let mut instance = instance_touching(activation, derived, &["inherited", "declared"]);
+
+            assert!(enumerant_names(instance).is_empty());
+
+            instance
+                .set_property(
+                    instance,
+                    &QName::new(Namespace::public(), "added"),
+                    1.into(),
+                    activation,
+                )
+                .unwrap();
+
+            assert_eq!(enumerant_names(instance), vec!["added"]);
+
+            instance.delete_property(
+                activation.context.gc_context,
+                &QName::new(Namespace::public(), "added"),
+            );
+            assert!(enumerant_names(instance).is_empty());
+        });
+    }
+
+    #[test]
+    fn readded_properties_enumerate_last() {
+        with_avm2(|activation| {
+            let mc = activation.context.gc_context;
+            let mut object = ScriptObject::object(mc, activation.avm2().prototypes().object);
+            for name in &["a", "b", "c", "d"] {
+                object
+                    .set_property(object, &QName::dynamic_name(*name), 1.into(), activation)
+                    .unwrap();
+            }
+
+            object.delete_property(mc, &QName::dynamic_name("a"));
+            object
+                .set_property(object, &QName::dynamic_name("a"), 2.into(), activation)
+                .unwrap();
+            assert_eq!(enumerant_names(object), vec!["b", "c", "d", "a"]);
+
+            // Moving `c` to the end in the middle of a `for..in` neither
+            // skips `d` nor visits `b` again.
+            let mut visited = Vec::new();
+            let mut index = 0;
+            while let Some(next) = object.get_next_enumerant(index) {
+                index = next;
+                let name = object.get_enumerant_name(index).unwrap();
+                if name.local_name() == "c" && !visited.contains(&"c".to_string()) {
+                    object.delete_property(mc, &name);
+                    object
+                        .set_property(object, &name, 3.into(), activation)
+                        .unwrap();
+                }
+
+                visited.push(name.local_name().to_string());
+            }
+            assert_eq!(visited, vec!["b", "c", "d", "a", "c"]);
+            assert_eq!(enumerant_names(object), vec!["b", "d", "a", "c"]);
+        });
+    }
+
+    #[test]
+    fn deleting_most_properties_mid_enumeration_keeps_indices() {
+        with_avm2(|activation| {
+            let mc = activation.context.gc_context;
+            let mut object = ScriptObject::object(mc, activation.avm2().prototypes().object);
+            for name in &["a", "b", "c", "d", "e"] {
+                object
+                    .set_property(object, &QName::dynamic_name(*name), 1.into(), activation)
+                    .unwrap();
+            }
+
+            // At `c`, delete everything before it, then move `c` to the end.
+            let mut visited = Vec::new();
+            let mut index = 0;
+            while let Some(next) = object.get_next_enumerant(index) {
+                index = next;
+                let name = object.get_enumerant_name(index).unwrap();
+                if name.local_name() == "c" && !visited.contains(&"c".to_string()) {
+                    object.delete_property(mc, &QName::dynamic_name("a"));
+                    object.delete_property(mc, &QName::dynamic_name("b"));
+                    object.delete_property(mc, &name);
+                    object
+                        .set_property(object, &name, 2.into(), activation)
+                        .unwrap();
+                }
+
+                visited.push(name.local_name().to_string());
+            }
+
+            assert_eq!(visited, vec!["a", "b", "c", "d", "e", "c"]);
+            assert_eq!(enumerant_names(object), vec!["d", "e", "c"]);
+        });
+    }
+
+    /// A setter that stores its value in a private property.
+    fn store_value<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let mut this = this.unwrap();
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("test".into()), "stored"),
+            args[0].clone(),
+            activation,
+        )?;
+
+        Ok(Value::Undefined)
+    }
+
+    fn stored_value<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        mut this: Object<'gc>,
+    ) -> Result<Value<'gc>, Error> {
+        this.get_property(
+            this,
+            &QName::new(Namespace::Private("test".into()), "stored"),
+            activation,
+        )
+    }
+
+    fn base_value<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let stored = stored_value(activation, this.unwrap())?.coerce_to_string(activation)?;
+
+        Ok(AvmString::new(activation.context.gc_context, format!("base:{}", stored)).into())
+    }
+
+    fn derived_value<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let stored = stored_value(activation, this.unwrap())?.coerce_to_string(activation)?;
+
+        Ok(AvmString::new(activation.context.gc_context, format!("derived:{}", stored)).into())
+    }
+
+    /// Construct a class named `name` with the given `value` accessors.
+    fn class_with_accessors<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        name: &'static str,
+        base: Option<Object<'gc>>,
+        getter: Option<NativeMethod<'gc>>,
+        setter: Option<NativeMethod<'gc>>,
+    ) -> Object<'gc> {
+        let class = class_with_traits(activation, name, base, ClassAttributes::empty(), &[]);
+        let class_def = class.as_class().unwrap();
+        let mc = activation.context.gc_context;
+        let value = QName::new(Namespace::public(), "value");
+        let attributes = if base.is_some() {
+            TraitAttributes::OVERRIDE
+        } else {
+            TraitAttributes::empty()
+        };
+
+        if let Some(getter) = getter {
+            let mut getter = Trait::from_getter(value.clone(), Method::from_builtin(getter));
+            getter.set_attributes(attributes);
+            class_def.write(mc).define_instance_trait(getter);
+        }
+        if let Some(setter) = setter {
+            let mut setter = Trait::from_setter(value, Method::from_builtin(setter));
+            setter.set_attributes(attributes);
+            class_def.write(mc).define_instance_trait(setter);
+        }
+
+        class
+    }
+
+    #[test]
+    fn overriding_a_getter_keeps_the_inherited_setter() {
+        with_avm2(|activation| {
+            let value = QName::new(Namespace::public(), "value");
+            let base = class_with_accessors(
+                activation,
+                "Base",
+                None,
+                Some(base_value),
+                Some(store_value),
+            );
+            let derived =
+                class_with_accessors(activation, "Derived", Some(base), Some(derived_value), None);
+
+            let mut instance = instance_touching(activation, derived, &[]);
+            instance
+                .set_property(instance, &value, "x".into(), activation)
+                .unwrap();
+            assert_eq!(
+                instance.get_property(instance, &value, activation).unwrap(),
+                "derived:x".into()
+            );
+
+            // Overriding both halves replaces the pair.
+            let replaced = class_with_accessors(
+                activation,
+                "Replaced",
+                Some(base),
+                Some(derived_value),
+                Some(empty_init),
+            );
+            let mut instance = instance_touching(activation, replaced, &[]);
+            instance
+                .set_property(instance, &value, "x".into(), activation)
+                .unwrap();
+            assert_eq!(
+                instance.get_property(instance, &value, activation).unwrap(),
+                "derived:undefined".into()
+            );
+        });
+    }
+}
@@ -188,6 +188,38 @@ impl<'gc> TObject<'gc> for ArrayObject<'gc> {
         self.0.read().base.has_own_property(name)
     }
 
+    fn get_enumerant_name(&self, index: u32) -> Option<QName<'gc>> {
+        self.0.read().base.get_enumerant_name(index)
+    }
+
+    fn get_next_enumerant(&self, last_index: u32) -> Option<u32> {
+        self.0.read().base.get_next_enumerant(last_index)
+    }
+
+    /// Set indices are enumerable, while holes and the array's `length` are
+    /// not.
+    fn property_is_enumerable(&self, name: &QName<'gc>) -> bool {
+        if name.namespace().is_public() {
+            if let Ok(index) = name.local_name().parse::<usize>() {
+                return self.0.read().array.get(index).is_some();
+            }
+        }
+
+        self.0.read().base.property_is_enumerable(name)
+    }
+
+    fn set_local_property_is_enumerable(
+        &self,
+        mc: MutationContext<'gc, '_>,
+        name: &QName<'gc>,
+        is_enumerable: bool,
+    ) -> Result<(), Error> {
+        self.0
+            .write(mc)
+            .base
+            .set_local_property_is_enumerable(name, is_enumerable)
+    }
+
     fn resolve_any(self, local_name: AvmString<'gc>) -> Result<Option<Namespace<'gc>>, Error> {
         if let Ok(index) = local_name.parse::<usize>() {
             if self.0.read().array.get(index).is_some() {
@@ -91,6 +91,35 @@ macro_rules! impl_avm2_custom_object_properties {
     };
 }
 
+#[macro_export]
+macro_rules! impl_avm2_custom_object_enumeration {
+    ($field:ident) => {
+        fn get_enumerant_name(&self, index: u32) -> Option<QName<'gc>> {
+            self.0.read().$field.get_enumerant_name(index)
+        }
+
+        fn get_next_enumerant(&self, last_index: u32) -> Option<u32> {
+            self.0.read().$field.get_next_enumerant(last_index)
+        }
+
+        fn property_is_enumerable(&self, name: &QName<'gc>) -> bool {
+            self.0.read().$field.property_is_enumerable(name)
+        }
+
+        fn set_local_property_is_enumerable(
+            &self,
+            mc: MutationContext<'gc, '_>,
+            name: &QName<'gc>,
+            is_enumerable: bool,
+        ) -> Result<(), Error> {
+            self.0
+                .write(mc)
+                .$field
+                .set_local_property_is_enumerable(name, is_enumerable)
+        }
+    };
+}
+
 #[macro_export]
 macro_rules! impl_avm2_custom_object {
     ($field:ident) => {
@@ -148,6 +177,10 @@ macro_rules! impl_avm2_custom_object {
             self.0.read().$field.has_instantiated_property(name)
         }
 
+        fn get_own_slot_id(self, name: &QName<'gc>) -> Option<u32> {
+            self.0.read().$field.get_own_slot_id(name)
+        }
+
         fn has_own_virtual_getter(self, name: &QName<'gc>) -> bool {
             self.0.read().$field.has_own_virtual_getter(name)
         }
@@ -160,28 +193,11 @@ macro_rules! impl_avm2_custom_object {
             self.0.read().$field.proto()
         }
 
-        fn set_proto(self, mc: MutationContext<'gc, '_>, proto: Object<'gc>) {
-            self.0.write(mc).$field.set_proto(proto)
-        }
-
-        fn get_enumerant_name(&self, index: u32) -> Option<QName<'gc>> {
-            self.0.read().$field.get_enumerant_name(index)
-        }
-
-        fn property_is_enumerable(&self, name: &QName<'gc>) -> bool {
-            self.0.read().$field.property_is_enumerable(name)
-        }
+        fn set_proto(self, mc: MutationContext<'gc, '_>, proto: Object<'gc>) -> Result<(), Error> {
+            self.validate_proto(proto)?;
+            self.0.write(mc).$field.set_proto(proto);
 
-        fn set_local_property_is_enumerable(
-            &self,
-            mc: MutationContext<'gc, '_>,
-            name: &QName<'gc>,
-            is_enumerable: bool,
-        ) -> Result<(), Error> {
-            self.0
-                .write(mc)
-                .$field
-                .set_local_property_is_enumerable(name, is_enumerable)
+            Ok(())
         }
 
         fn as_ptr(&self) -> *const ObjectPtr {
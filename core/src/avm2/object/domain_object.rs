@@ -11,7 +11,10 @@ use crate::avm2::string::AvmString;
 use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
-use crate::{impl_avm2_custom_object, impl_avm2_custom_object_properties};
+use crate::{
+    impl_avm2_custom_object, impl_avm2_custom_object_enumeration,
+    impl_avm2_custom_object_properties,
+};
 use gc_arena::{Collect, GcCell, MutationContext};
 
 #[derive(Clone, Collect, Debug, Copy)]
@@ -59,6 +62,7 @@ impl<'gc> DomainObject<'gc> {
 impl<'gc> TObject<'gc> for DomainObject<'gc> {
     impl_avm2_custom_object!(base);
     impl_avm2_custom_object_properties!(base);
+    impl_avm2_custom_object_enumeration!(base);
 
     fn as_application_domain(&self) -> Option<Domain<'gc>> {
         Some(self.0.read().domain)
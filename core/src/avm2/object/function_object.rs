@@ -12,7 +12,10 @@ use crate::avm2::string::AvmString;
 use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
-use crate::{impl_avm2_custom_object, impl_avm2_custom_object_properties};
+use crate::{
+    impl_avm2_custom_object, impl_avm2_custom_object_enumeration,
+    impl_avm2_custom_object_properties,
+};
 use gc_arena::{Collect, GcCell, MutationContext};
 
 /// An Object which can be called to execute its function code.
@@ -109,7 +112,27 @@ impl<'gc> FunctionObject<'gc> {
                     .into()
                 });
 
-            derive(super_proto?, activation, class, scope)?
+            let class_proto = derive(super_proto?, activation, class, scope)?;
+
+            // Overrides are checked against the superclass now, rather than
+            // when the trait is first looked up.
+            let mut chain = Vec::new();
+            let mut proto = Some(class_proto);
+
+            while let Some(p) = proto {
+                chain.push(p);
+                proto = p.proto();
+            }
+
+            for trait_entry in class_read.instance_traits() {
+                let mut known_traits = Vec::new();
+
+                for proto in chain.iter().rev() {
+                    proto.get_provided_trait(trait_entry.name(), &mut known_traits)?;
+                }
+            }
+
+            class_proto
         } else {
             ScriptObject::bare_object(activation.context.gc_context)
         };
@@ -224,6 +247,26 @@ impl<'gc> FunctionObject<'gc> {
         .into()
     }
 
+    /// Construct a function closure from an ABC method and the current closure
+    /// scope.
+    pub fn from_function(
+        mc: MutationContext<'gc, '_>,
+        method: Method<'gc>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+        fn_proto: Object<'gc>,
+    ) -> Object<'gc> {
+        let exec = Some(Executable::from_closure(method, scope, mc));
+
+        FunctionObject(GcCell::allocate(
+            mc,
+            FunctionObjectData {
+                base: ScriptObjectData::base_new(Some(fn_proto), ScriptObjectClass::NoClass),
+                exec,
+            },
+        ))
+        .into()
+    }
+
     /// Construct a builtin function object from a Rust function.
     pub fn from_builtin(
         mc: MutationContext<'gc, '_>,
@@ -279,6 +322,7 @@ impl<'gc> FunctionObject<'gc> {
 impl<'gc> TObject<'gc> for FunctionObject<'gc> {
     impl_avm2_custom_object!(base);
     impl_avm2_custom_object_properties!(base);
+    impl_avm2_custom_object_enumeration!(base);
 
     fn to_string(&self, mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
         if let ScriptObjectClass::ClassConstructor(class, ..) = self.0.read().base.class() {
@@ -14,7 +14,7 @@ use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use gc_arena::{Collect, GcCell, MutationContext};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::Debug;
 
 /// Default implementation of `avm2::Object`.
@@ -67,7 +67,19 @@ pub struct ScriptObjectData<'gc> {
     class: ScriptObjectClass<'gc>,
 
     /// Enumeratable property names.
-    enumerants: Vec<QName<'gc>>,
+    ///
+    /// These are kept separately from `values` so that enumeration always
+    /// follows the order properties were added in. Tracing never reorders
+    /// this list, so a collection cannot change `for..in` order.
+    ///
+    /// Each name keeps the index it was given for as long as it stays
+    /// enumerable, and indices are never handed out twice. A `for..in` loop
+    /// in progress therefore still lines up with the names it has yet to
+    /// visit, however many names are removed or added along the way.
+    enumerants: BTreeMap<u32, QName<'gc>>,
+
+    /// The index the next enumerable name will be given.
+    next_enumerant: u32,
 
     /// Interfaces implemented by this object. (prototypes only)
     interfaces: Vec<Object<'gc>>,
@@ -203,6 +215,10 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         self.0.read().has_instantiated_property(name)
     }
 
+    fn get_own_slot_id(self, name: &QName<'gc>) -> Option<u32> {
+        self.0.read().get_own_slot_id(name)
+    }
+
     fn has_own_virtual_getter(self, name: &QName<'gc>) -> bool {
         self.0.read().has_own_virtual_getter(name)
     }
@@ -215,14 +231,21 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         self.0.read().proto
     }
 
-    fn set_proto(self, mc: MutationContext<'gc, '_>, proto: Object<'gc>) {
-        self.0.write(mc).set_proto(proto)
+    fn set_proto(self, mc: MutationContext<'gc, '_>, proto: Object<'gc>) -> Result<(), Error> {
+        self.validate_proto(proto)?;
+        self.0.write(mc).set_proto(proto);
+
+        Ok(())
     }
 
     fn get_enumerant_name(&self, index: u32) -> Option<QName<'gc>> {
         self.0.read().get_enumerant_name(index)
     }
 
+    fn get_next_enumerant(&self, last_index: u32) -> Option<u32> {
+        self.0.read().get_next_enumerant(last_index)
+    }
+
     fn property_is_enumerable(&self, name: &QName<'gc>) -> bool {
         self.0.read().property_is_enumerable(name)
     }
@@ -266,14 +289,6 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         ))
     }
 
-    fn to_string(&self, mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
-        if let Some(class) = self.as_proto_class() {
-            Ok(AvmString::new(mc, format!("[object {}]", class.read().name().local_name())).into())
-        } else {
-            Ok("[object Object]".into())
-        }
-    }
-
     fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
         Ok(Value::Object(Object::from(*self)))
     }
@@ -415,7 +430,8 @@ impl<'gc> ScriptObjectData<'gc> {
             methods: Vec::new(),
             proto,
             class: trait_source,
-            enumerants: Vec::new(),
+            enumerants: BTreeMap::new(),
+            next_enumerant: 1,
             interfaces: Vec::new(),
         }
     }
@@ -461,7 +477,11 @@ impl<'gc> ScriptObjectData<'gc> {
             prop.set(receiver, activation.base_proto().or(proto), value)
         } else {
             //TODO: Not all classes are dynamic like this
-            self.enumerants.push(name.clone());
+            // Traits are never enumerable, even if they were never installed
+            if !self.has_trait(name)? {
+                self.push_enumerant(name);
+            }
+
             self.values
                 .insert(name.clone(), Property::new_dynamic_property(value));
 
@@ -500,6 +520,11 @@ impl<'gc> ScriptObjectData<'gc> {
             .unwrap_or(true)
     }
 
+    /// Delete a dynamic property, if it can be deleted.
+    ///
+    /// The property is removed outright rather than being left behind as a
+    /// tombstone, so its value can be collected as soon as nothing else
+    /// refers to it. If it is added again, it is enumerated last.
     pub fn delete_property(&mut self, name: &QName<'gc>) -> bool {
         let can_delete = if let Some(prop) = self.values.get(name) {
             prop.can_delete()
@@ -509,6 +534,7 @@ impl<'gc> ScriptObjectData<'gc> {
 
         if can_delete {
             self.values.remove(name);
+            self.remove_enumerant(name);
         }
 
         can_delete
@@ -708,6 +734,10 @@ impl<'gc> ScriptObjectData<'gc> {
         self.values.get(name).is_some()
     }
 
+    pub fn get_own_slot_id(&self, name: &QName<'gc>) -> Option<u32> {
+        self.values.get(name).and_then(|prop| prop.slot_id())
+    }
+
     pub fn has_own_virtual_getter(&self, name: &QName<'gc>) -> bool {
         matches!(
             self.values.get(name),
@@ -734,16 +764,42 @@ impl<'gc> ScriptObjectData<'gc> {
         // NOTE: AVM2 object enumeration is one of the weakest parts of an
         // otherwise well-designed VM. Notably, because of the way they
         // implemented `hasnext` and `hasnext2`, all enumerants start from ONE.
-        // Hence why we have to `checked_sub` here in case some miscompiled
-        // code doesn't check for the zero index, which is actually a failure
-        // sentinel.
-        let true_index = (index as usize).checked_sub(1)?;
+        // Index zero, which is actually a failure sentinel, is never given
+        // out, in case some miscompiled code doesn't check for it.
+        self.enumerants.get(&index).cloned()
+    }
+
+    pub fn get_next_enumerant(&self, last_index: u32) -> Option<u32> {
+        let start = last_index.checked_add(1)?;
 
-        self.enumerants.get(true_index).cloned()
+        self.enumerants
+            .range(start..)
+            .next()
+            .map(|(index, _)| *index)
     }
 
     pub fn property_is_enumerable(&self, name: &QName<'gc>) -> bool {
-        self.enumerants.contains(name)
+        self.enumerant_index(name).is_some()
+    }
+
+    fn enumerant_index(&self, name: &QName<'gc>) -> Option<u32> {
+        self.enumerants
+            .iter()
+            .find(|(_, enumerant)| *enumerant == name)
+            .map(|(index, _)| *index)
+    }
+
+    /// Enumerate `name` after every other enumerant.
+    fn push_enumerant(&mut self, name: &QName<'gc>) {
+        self.enumerants.insert(self.next_enumerant, name.clone());
+        self.next_enumerant = self.next_enumerant.saturating_add(1);
+    }
+
+    /// Stop enumerating `name`.
+    fn remove_enumerant(&mut self, name: &QName<'gc>) {
+        if let Some(index) = self.enumerant_index(name) {
+            self.enumerants.remove(&index);
+        }
     }
 
     pub fn set_local_property_is_enumerable(
@@ -751,24 +807,15 @@ impl<'gc> ScriptObjectData<'gc> {
         name: &QName<'gc>,
         is_enumerable: bool,
     ) -> Result<(), Error> {
-        if is_enumerable && self.values.contains_key(name) && !self.enumerants.contains(name) {
+        if is_enumerable && self.values.contains_key(name) && !self.property_is_enumerable(name) {
             // Traits are never enumerable
             if self.has_trait(name)? {
                 return Ok(());
             }
 
-            self.enumerants.push(name.clone());
-        } else if !is_enumerable && self.enumerants.contains(name) {
-            let mut index = None;
-            for (i, other_name) in self.enumerants.iter().enumerate() {
-                if other_name == name {
-                    index = Some(i);
-                }
-            }
-
-            if let Some(index) = index {
-                self.enumerants.remove(index);
-            }
+            self.push_enumerant(name);
+        } else if !is_enumerable {
+            self.remove_enumerant(name);
         }
 
         Ok(())
@@ -204,6 +204,10 @@ impl<'gc> TObject<'gc> for StageObject<'gc> {
         self.0.read().base.has_instantiated_property(name)
     }
 
+    fn get_own_slot_id(self, name: &QName<'gc>) -> Option<u32> {
+        self.0.read().base.get_own_slot_id(name)
+    }
+
     fn has_own_virtual_getter(self, name: &QName<'gc>) -> bool {
         self.0.read().base.has_own_virtual_getter(name)
     }
@@ -216,14 +220,21 @@ impl<'gc> TObject<'gc> for StageObject<'gc> {
         self.0.read().base.proto()
     }
 
-    fn set_proto(self, mc: MutationContext<'gc, '_>, proto: Object<'gc>) {
-        self.0.write(mc).base.set_proto(proto)
+    fn set_proto(self, mc: MutationContext<'gc, '_>, proto: Object<'gc>) -> Result<(), Error> {
+        self.validate_proto(proto)?;
+        self.0.write(mc).base.set_proto(proto);
+
+        Ok(())
     }
 
     fn get_enumerant_name(&self, index: u32) -> Option<QName<'gc>> {
         self.0.read().base.get_enumerant_name(index)
     }
 
+    fn get_next_enumerant(&self, last_index: u32) -> Option<u32> {
+        self.0.read().base.get_next_enumerant(last_index)
+    }
+
     fn property_is_enumerable(&self, name: &QName<'gc>) -> bool {
         self.0.read().base.property_is_enumerable(name)
     }
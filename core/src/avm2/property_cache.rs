@@ -0,0 +1,368 @@
+//! Inline caches for property access instructions
+
+use crate::avm2::class::Class;
+use crate::avm2::names::QName;
+use crate::avm2::object::{Object, TObject};
+use fnv::FnvHashMap;
+use gc_arena::{Collect, GcCell};
+
+/// What a property access instruction's multiname resolved to, the last time
+/// it ran.
+#[derive(Collect, Clone, Debug)]
+#[collect(no_drop)]
+pub struct CachedProperty<'gc> {
+    /// The class of the receiver that the multiname was resolved on.
+    pub class: GcCell<'gc, Class<'gc>>,
+
+    /// The name that the multiname resolved to.
+    pub name: QName<'gc>,
+
+    /// The slot the property is stored in, if it is a slot.
+    pub slot_id: Option<u32>,
+
+    /// The prototype that provides the property's trait, for method calls.
+    pub base_proto: Option<Object<'gc>>,
+}
+
+/// The inline caches of every property access in a method body, keyed by the
+/// offset of the instruction.
+///
+/// Each instruction only remembers the class of the last receiver it saw. A
+/// receiver of any other class misses and replaces the entry, so a site that
+/// sees several classes is never handed another class's property.
+#[derive(Collect, Debug, Default)]
+#[collect(no_drop)]
+pub struct PropertyCache<'gc> {
+    entries: FnvHashMap<u64, CachedProperty<'gc>>,
+}
+
+impl<'gc> PropertyCache<'gc> {
+    /// Get the class that an entry for `receiver` would be made for.
+    ///
+    /// Only instances of sealed classes are cached: every instance of such a
+    /// class has the same traits, and can't grow properties of its own that
+    /// would change how a name resolves. Classes and prototypes are never
+    /// cached.
+    pub fn receiver_class(receiver: Object<'gc>) -> Option<GcCell<'gc, Class<'gc>>> {
+        if receiver.as_class().is_some() {
+            return None;
+        }
+
+        receiver
+            .as_proto_class()
+            .filter(|class| class.read().is_sealed())
+    }
+
+    /// Look up the entry for the instruction at `offset`, if it was made for
+    /// a receiver of `class`.
+    pub fn get(&self, offset: u64, class: GcCell<'gc, Class<'gc>>) -> Option<&CachedProperty<'gc>> {
+        self.entries
+            .get(&offset)
+            .filter(|entry| GcCell::ptr_eq(entry.class, class))
+    }
+
+    /// Remember what the instruction at `offset` resolved to.
+    ///
+    /// Names that resolved anywhere other than on the receiver itself are not
+    /// cached, as prototypes can change at any time.
+    pub fn insert(&mut self, offset: u64, receiver: Object<'gc>, entry: CachedProperty<'gc>) {
+        if receiver.has_instantiated_property(&entry.name) {
+            self.entries.insert(offset, entry);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::activation::Activation;
+    use crate::avm2::class::ClassAttributes;
+    use crate::avm2::method::Method;
+    use crate::avm2::names::Namespace;
+    use crate::avm2::object::FunctionObject;
+    use crate::avm2::script::TranslationUnit;
+    use crate::avm2::test_utils::with_avm2;
+    use crate::avm2::traits::Trait;
+    use crate::avm2::value::Value;
+    use crate::avm2::Error;
+    use std::marker::PhantomData;
+    use std::rc::Rc;
+    use std::time::Instant;
+    use swf::avm2::types::{
+        AbcFile, ConstantPool, Index, Method as AbcMethod, MethodBody as AbcMethodBody,
+        MethodParam, Multiname as AbcMultiname, Namespace as AbcNamespace, Script as AbcScript,
+        Trait as AbcTrait, TraitKind as AbcTraitKind,
+    };
+
+    /// An ABC file with a single function, `read(o)`, which yields `o.x`
+    /// through one `getproperty` instruction.
+    fn read_x_abc() -> AbcFile {
+        let method = |name, params| AbcMethod {
+            name: Index(name, PhantomData),
+            params,
+            return_type: Index(0, PhantomData),
+            needs_arguments_object: false,
+            needs_activation: false,
+            needs_rest: false,
+            needs_dxns: false,
+        };
+        let body = |method, num_locals, code| AbcMethodBody {
+            method: Index(method, PhantomData),
+            max_stack: 1,
+            num_locals,
+            init_scope_depth: 0,
+            max_scope_depth: 0,
+            code,
+            exceptions: vec![],
+            traits: vec![],
+        };
+
+        AbcFile {
+            major_version: 46,
+            minor_version: 16,
+            constant_pool: ConstantPool {
+                ints: vec![],
+                uints: vec![],
+                doubles: vec![],
+                strings: vec!["".to_string(), "x".to_string(), "read".to_string()],
+                namespaces: vec![AbcNamespace::Package(Index(1, PhantomData))],
+                namespace_sets: vec![],
+                multinames: vec![
+                    AbcMultiname::QName {
+                        namespace: Index(1, PhantomData),
+                        name: Index(2, PhantomData),
+                    },
+                    AbcMultiname::QName {
+                        namespace: Index(1, PhantomData),
+                        name: Index(3, PhantomData),
+                    },
+                ],
+            },
+            methods: vec![
+                method(0, vec![]),
+                method(
+                    3,
+                    vec![MethodParam {
+                        name: None,
+                        kind: Index(0, PhantomData),
+                        default_value: None,
+                    }],
+                ),
+            ],
+            metadata: vec![],
+            instances: vec![],
+            classes: vec![],
+            scripts: vec![AbcScript {
+                init_method: Index(0, PhantomData),
+                traits: vec![AbcTrait {
+                    name: Index(2, PhantomData),
+                    kind: AbcTraitKind::Method {
+                        disp_id: 0,
+                        method: Index(1, PhantomData),
+                    },
+                    metadata: vec![],
+                    is_final: false,
+                    is_override: false,
+                }],
+            }],
+            method_bodies: vec![
+                // returnvoid
+                body(0, 1, vec![0x47]),
+                // getlocal1; getproperty x; returnvalue
+                body(1, 2, vec![0xD1, 0x66, 0x01, 0x48]),
+            ],
+        }
+    }
+
+    /// Load `read_x_abc` and get its `read` function.
+    fn read_function<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Object<'gc> {
+        read_function_and_cache(activation).0
+    }
+
+    /// Load `read_x_abc` and get its `read` function, along with the inline
+    /// caches of its method body.
+    fn read_function_and_cache<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> (Object<'gc>, GcCell<'gc, PropertyCache<'gc>>) {
+        let domain = activation.avm2().global_domain();
+        let tunit =
+            TranslationUnit::from_abc(Rc::new(read_x_abc()), domain, activation.context.gc_context);
+        let mut script = tunit
+            .load_script(0, activation.context.avm2, activation.context.gc_context)
+            .unwrap();
+        let mut globals = script.globals(&mut activation.context).unwrap();
+
+        let read = globals
+            .get_property(
+                globals,
+                &QName::new(Namespace::public(), "read"),
+                activation,
+            )
+            .unwrap()
+            .coerce_to_object(activation)
+            .unwrap();
+        let cache = match tunit.load_method(1, activation.context.gc_context).unwrap() {
+            Method::Entry(method) => method.property_cache(),
+            Method::Native(_) => unreachable!("`read` is a bytecode method"),
+        };
+
+        (read, cache)
+    }
+
+    fn class_init<'gc>(
+        _activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        Ok(Value::Undefined)
+    }
+
+    /// Construct an instance of a sealed class with the given public slots,
+    /// in order. Each slot holds its class and slot name, e.g. `A.x`.
+    fn instance_with_slots<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        class_name: &'static str,
+        slots: &[&'static str],
+    ) -> Object<'gc> {
+        let mc = activation.context.gc_context;
+        let class = Class::new(
+            QName::new(Namespace::public(), class_name),
+            Some(QName::new(Namespace::public(), "Object").into()),
+            Method::from_builtin(class_init),
+            Method::from_builtin(class_init),
+            mc,
+        );
+        class
+            .write(mc)
+            .set_attributes(ClassAttributes::SEALED | ClassAttributes::FINAL);
+
+        for slot in slots {
+            let value = format!("{}.{}", class_name, slot);
+            class.write(mc).define_instance_trait(Trait::from_slot(
+                QName::new(Namespace::public(), *slot),
+                QName::new(Namespace::public(), "String").into(),
+                Some(crate::avm2::string::AvmString::new(mc, value).into()),
+            ));
+        }
+
+        let object_class = activation
+            .avm2()
+            .global_domain()
+            .get_defined_value(activation, QName::new(Namespace::public(), "Object"))
+            .unwrap()
+            .coerce_to_object(activation)
+            .unwrap();
+        let (constr, _cinit) =
+            FunctionObject::from_class(activation, class, Some(object_class), None).unwrap();
+        let mut constr = constr;
+        let proto = constr
+            .get_property(
+                constr,
+                &QName::new(Namespace::public(), "prototype"),
+                activation,
+            )
+            .unwrap()
+            .coerce_to_object(activation)
+            .unwrap();
+        let instance = proto.construct(activation, &[]).unwrap();
+        constr
+            .call(Some(instance), &[], activation, Some(proto))
+            .unwrap();
+
+        instance
+    }
+
+    fn read<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        read: Object<'gc>,
+        object: Object<'gc>,
+    ) -> String {
+        read.call(None, &[object.into()], activation, None)
+            .unwrap()
+            .coerce_to_string(activation)
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn polymorphic_receivers_get_their_own_slots() {
+        with_avm2(|activation| {
+            let read_x = read_function(activation);
+            let a = instance_with_slots(activation, "A", &["x", "y"]);
+            let b = instance_with_slots(activation, "B", &["y", "x"]);
+
+            assert_eq!(read(activation, read_x, a), "A.x");
+            assert_eq!(read(activation, read_x, a), "A.x");
+            assert_eq!(read(activation, read_x, b), "B.x");
+            assert_eq!(read(activation, read_x, a), "A.x");
+            assert_eq!(read(activation, read_x, b), "B.x");
+        });
+    }
+
+    #[test]
+    fn dynamic_receivers_are_never_cached() {
+        with_avm2(|activation| {
+            let read_x = read_function(activation);
+            let mut object = crate::avm2::object::ScriptObject::object(
+                activation.context.gc_context,
+                activation.avm2().prototypes().object,
+            );
+            let name = QName::new(Namespace::public(), "x");
+
+            assert!(PropertyCache::receiver_class(object).is_none());
+
+            object
+                .set_property(object, &name, "first".into(), activation)
+                .unwrap();
+            assert_eq!(read(activation, read_x, object), "first");
+
+            object
+                .set_property(object, &name, "second".into(), activation)
+                .unwrap();
+            assert_eq!(read(activation, read_x, object), "second");
+        });
+    }
+
+    /// Times a loop of property reads through one `getproperty` site, first
+    /// with its inline cache warm, then with the cache emptied before every
+    /// read so that each one takes the full `get_property` path.
+    ///
+    /// Run with `cargo test -p ruffle_core property_access_benchmark --
+    /// --ignored`, which logs both timings.
+    #[test]
+    #[ignore]
+    fn property_access_benchmark() {
+        const ITERATIONS: usize = 100_000;
+
+        let _ = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info"))
+            .format_timestamp(None)
+            .is_test(true)
+            .try_init();
+
+        with_avm2(|activation| {
+            let (read_x, cache) = read_function_and_cache(activation);
+            let a = instance_with_slots(activation, "A", &["x", "y"]);
+
+            let start = Instant::now();
+            for _ in 0..ITERATIONS {
+                read_x.call(None, &[a.into()], activation, None).unwrap();
+            }
+            let cached = start.elapsed();
+            assert_eq!(cache.read().entries.len(), 1);
+
+            let start = Instant::now();
+            for _ in 0..ITERATIONS {
+                *cache.write(activation.context.gc_context) = PropertyCache::default();
+                read_x.call(None, &[a.into()], activation, None).unwrap();
+            }
+            let uncached = start.elapsed();
+
+            log::info!(
+                "{} reads: {:?} cached, {:?} uncached",
+                ITERATIONS,
+                cached,
+                uncached
+            );
+        });
+    }
+}
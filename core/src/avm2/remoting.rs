@@ -0,0 +1,281 @@
+//! Flash Remoting messages, as sent by `NetConnection.call`
+//!
+//! A remoting message is an AMF0 envelope of headers and message bodies.
+//! Requests are always written with their arguments switched over to AMF3,
+//! as Flash Player does for connections whose `objectEncoding` is the
+//! default. Responses may use either encoding.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::amf::{Amf3Reader, Amf3Writer, END_OF_MESSAGE};
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{ArrayObject, Object, ScriptObject, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use std::convert::TryFrom;
+
+/// The version of the envelope, which announces that AMF3 values may appear
+/// in it.
+const ENVELOPE_VERSION: u16 = 3;
+
+/// Write a request to call `target` on the server with `args`.
+///
+/// The server sends its reply to `response`, which is how replies are matched
+/// up with their calls.
+pub fn write_request<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    target: &str,
+    response: &str,
+    args: &[Value<'gc>],
+) -> Result<Vec<u8>, Error> {
+    // Arguments are sent as a strict array, with each element switched over
+    // to AMF3 on its own.
+    let mut body = vec![0x0A];
+    body.extend_from_slice(&(args.len() as u32).to_be_bytes());
+    for arg in args {
+        let mut writer = Amf3Writer::new();
+        writer.write_value(activation, arg.clone())?;
+
+        body.push(0x11);
+        body.extend_from_slice(&writer.into_bytes());
+    }
+
+    let mut bytes = Vec::with_capacity(body.len() + target.len() + response.len() + 16);
+    bytes.extend_from_slice(&ENVELOPE_VERSION.to_be_bytes());
+    // No headers, and one message.
+    bytes.extend_from_slice(&0u16.to_be_bytes());
+    bytes.extend_from_slice(&1u16.to_be_bytes());
+    write_utf8(&mut bytes, target)?;
+    write_utf8(&mut bytes, response)?;
+    bytes.extend_from_slice(&(body.len() as u32).to_be_bytes());
+    bytes.extend_from_slice(&body);
+
+    Ok(bytes)
+}
+
+/// Write a string prefixed with its length, as the envelope stores them.
+fn write_utf8(bytes: &mut Vec<u8>, string: &str) -> Result<(), Error> {
+    let length = u16::try_from(string.len()).map_err(|_| "Remoting name is too long")?;
+    bytes.extend_from_slice(&length.to_be_bytes());
+    bytes.extend_from_slice(string.as_bytes());
+
+    Ok(())
+}
+
+/// Read the messages of a response, as each message's target and value.
+///
+/// Headers are read past and ignored.
+pub fn read_response<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    bytes: &[u8],
+) -> Result<Vec<(String, Value<'gc>)>, Error> {
+    let mut reader = Amf0Reader::new(bytes);
+
+    let _version = reader.read_u16()?;
+    for _ in 0..reader.read_u16()? {
+        let _name = reader.read_utf8()?;
+        let _must_understand = reader.read_u8()?;
+        let _length = reader.read_u32()?;
+        reader.read_value(activation)?;
+    }
+
+    let count = reader.read_u16()?;
+    let mut messages = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        let target = reader.read_utf8()?;
+        let _response = reader.read_utf8()?;
+        let _length = reader.read_u32()?;
+        let value = reader.read_value(activation)?;
+
+        messages.push((target, value));
+    }
+
+    Ok(messages)
+}
+
+/// Reads AMF0 values out of a remoting envelope.
+///
+/// Dates and XML documents are not understood.
+struct Amf0Reader<'a, 'gc> {
+    /// The encoded envelope.
+    bytes: &'a [u8],
+
+    /// How far into the envelope we have read.
+    position: usize,
+
+    /// The object reference table.
+    objects: Vec<Object<'gc>>,
+}
+
+impl<'a, 'gc> Amf0Reader<'a, 'gc> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            position: 0,
+            objects: Vec::new(),
+        }
+    }
+
+    fn read_bytes(&mut self, length: usize) -> Result<&'a [u8], Error> {
+        let end = self
+            .position
+            .checked_add(length)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or(END_OF_MESSAGE)?;
+        let bytes = &self.bytes[self.position..end];
+        self.position = end;
+
+        Ok(bytes)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, Error> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    fn read_u16(&mut self) -> Result<u16, Error> {
+        let mut bytes = [0; 2];
+        bytes.copy_from_slice(self.read_bytes(2)?);
+
+        Ok(u16::from_be_bytes(bytes))
+    }
+
+    fn read_u32(&mut self) -> Result<u32, Error> {
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(self.read_bytes(4)?);
+
+        Ok(u32::from_be_bytes(bytes))
+    }
+
+    /// Read a string prefixed with a 16-bit length.
+    fn read_utf8(&mut self) -> Result<String, Error> {
+        let length = self.read_u16()? as usize;
+
+        Ok(String::from_utf8_lossy(self.read_bytes(length)?).into_owned())
+    }
+
+    /// Read a value, preceded by its type marker.
+    fn read_value(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error> {
+        Ok(match self.read_u8()? {
+            0x00 => {
+                let mut bytes = [0; 8];
+                bytes.copy_from_slice(self.read_bytes(8)?);
+
+                f64::from_be_bytes(bytes).into()
+            }
+            0x01 => (self.read_u8()? != 0).into(),
+            0x02 => AvmString::new(activation.context.gc_context, self.read_utf8()?).into(),
+            0x03 => {
+                let object = ScriptObject::object(
+                    activation.context.gc_context,
+                    activation.avm2().prototypes().object,
+                );
+                self.objects.push(object);
+                self.read_members(activation, object)?;
+
+                object.into()
+            }
+            0x05 => Value::Null,
+            0x06 => Value::Undefined,
+            0x07 => {
+                let index = self.read_u16()? as usize;
+
+                self.objects
+                    .get(index)
+                    .copied()
+                    .ok_or("Invalid AMF0 object reference")?
+                    .into()
+            }
+            0x08 => {
+                let _count = self.read_u32()?;
+                let array = ArrayObject::from_array(
+                    ArrayStorage::new(0),
+                    activation.avm2().prototypes().array,
+                    activation.context.gc_context,
+                );
+                self.objects.push(array);
+                self.read_members(activation, array)?;
+
+                array.into()
+            }
+            0x0A => {
+                let length = self.read_u32()?;
+                let array = ArrayObject::from_array(
+                    ArrayStorage::new(0),
+                    activation.avm2().prototypes().array,
+                    activation.context.gc_context,
+                );
+                self.objects.push(array);
+
+                for _ in 0..length {
+                    let value = self.read_value(activation)?;
+                    if let Some(mut storage) =
+                        array.as_array_storage_mut(activation.context.gc_context)
+                    {
+                        storage.push(value);
+                    }
+                }
+
+                array.into()
+            }
+            0x0C => {
+                let length = self.read_u32()? as usize;
+                let string = String::from_utf8_lossy(self.read_bytes(length)?).into_owned();
+
+                AvmString::new(activation.context.gc_context, string).into()
+            }
+            0x10 => {
+                // Ruffle has no class alias registry, so typed objects are
+                // read as plain objects.
+                let _class_name = self.read_utf8()?;
+                let object = ScriptObject::object(
+                    activation.context.gc_context,
+                    activation.avm2().prototypes().object,
+                );
+                self.objects.push(object);
+                self.read_members(activation, object)?;
+
+                object.into()
+            }
+            0x11 => {
+                let mut reader = Amf3Reader::new(&self.bytes[self.position..]);
+                let value = reader.read_value(activation)?;
+                self.position += reader.position();
+
+                value
+            }
+            marker => return Err(format!("Unsupported AMF0 type marker {:#04X}", marker).into()),
+        })
+    }
+
+    /// Read named members into an object until the empty name and end marker
+    /// that terminate them.
+    fn read_members(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        mut object: Object<'gc>,
+    ) -> Result<(), Error> {
+        loop {
+            let name = self.read_utf8()?;
+            if name.is_empty() {
+                if self.read_u8()? != 0x09 {
+                    return Err("Expected the end of an AMF0 object".into());
+                }
+
+                return Ok(());
+            }
+
+            let name = AvmString::new(activation.context.gc_context, name);
+            let value = self.read_value(activation)?;
+            object.set_property(
+                object,
+                &QName::new(Namespace::public(), name),
+                value,
+                activation,
+            )?;
+        }
+    }
+}
@@ -0,0 +1,653 @@
+use crate::avm2::activation::Activation;
+use crate::avm2::method::NativeMethod;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{FunctionObject, Object, TObject};
+use crate::avm2::value::Value;
+use crate::backend::audio::NullAudioBackend;
+use crate::backend::locale::NullLocaleBackend;
+use crate::backend::log::LogBackend;
+use crate::backend::navigator::{
+    NavigationMethod, NavigatorBackend, NullExecutor, NullNavigatorBackend, OwnedFuture,
+    RequestOptions,
+};
+use crate::backend::render::{
+    Bitmap, BitmapHandle, BitmapInfo, MovieLibrary, NullRenderer, RenderBackend, ShapeHandle,
+    Transform,
+};
+use crate::backend::storage::MemoryStorageBackend;
+use crate::backend::ui::{MouseCursor, NullUiBackend, StageOrientation, UiBackend};
+use crate::backend::video::NullVideoBackend;
+use crate::context_menu::ContextMenuItem;
+use crate::events::KeyCode;
+use crate::loader::Error as LoaderError;
+use crate::player::Player;
+use crate::shape_utils::DistilledShape;
+use crate::Color;
+use indexmap::IndexMap;
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::path::Path;
+use std::rc::Rc;
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use swf::Matrix;
+use url::Url;
+
+/// A log backend that keeps every line passed to `trace`.
+struct TraceLog(Rc<RefCell<Vec<String>>>);
+
+impl LogBackend for TraceLog {
+    fn avm_trace(&self, message: &str) {
+        self.0.borrow_mut().push(message.to_string());
+    }
+}
+
+/// A renderer that keeps what it is asked to draw, and otherwise does what
+/// `NullRenderer` does.
+#[derive(Default)]
+pub struct RenderRecorder {
+    /// The handle and matrix of every shape drawn so far.
+    pub shapes: Vec<(ShapeHandle, Matrix)>,
+
+    /// The color of every rectangle drawn so far.
+    pub rects: Vec<Color>,
+}
+
+impl RenderRecorder {
+    /// The handle given to every shape that is not a glyph, such as a text
+    /// field's border, so that drawn glyphs can be told apart from them.
+    pub const DRAWING: ShapeHandle = ShapeHandle(usize::MAX);
+
+    /// The matrix of every glyph drawn so far.
+    pub fn glyphs(&self) -> Vec<Matrix> {
+        self.shapes
+            .iter()
+            .filter(|(handle, _)| handle.0 != Self::DRAWING.0)
+            .map(|(_, matrix)| *matrix)
+            .collect()
+    }
+}
+
+impl RenderBackend for RenderRecorder {
+    fn set_viewport_dimensions(&mut self, width: u32, height: u32) {
+        NullRenderer.set_viewport_dimensions(width, height)
+    }
+    fn register_shape(
+        &mut self,
+        _shape: DistilledShape,
+        _library: Option<&MovieLibrary<'_>>,
+    ) -> ShapeHandle {
+        Self::DRAWING
+    }
+    fn replace_shape(
+        &mut self,
+        shape: DistilledShape,
+        library: Option<&MovieLibrary<'_>>,
+        handle: ShapeHandle,
+    ) {
+        NullRenderer.replace_shape(shape, library, handle)
+    }
+    fn register_glyph_shape(&mut self, shape: &swf::Glyph) -> ShapeHandle {
+        NullRenderer.register_glyph_shape(shape)
+    }
+    fn register_bitmap_jpeg(
+        &mut self,
+        data: &[u8],
+        jpeg_tables: Option<&[u8]>,
+    ) -> Result<BitmapInfo, Box<dyn std::error::Error>> {
+        NullRenderer.register_bitmap_jpeg(data, jpeg_tables)
+    }
+    fn register_bitmap_jpeg_2(
+        &mut self,
+        data: &[u8],
+    ) -> Result<BitmapInfo, Box<dyn std::error::Error>> {
+        NullRenderer.register_bitmap_jpeg_2(data)
+    }
+    fn register_bitmap_jpeg_3(
+        &mut self,
+        data: &[u8],
+        alpha_data: &[u8],
+    ) -> Result<BitmapInfo, Box<dyn std::error::Error>> {
+        NullRenderer.register_bitmap_jpeg_3(data, alpha_data)
+    }
+    fn register_bitmap_png(
+        &mut self,
+        swf_tag: &swf::DefineBitsLossless,
+    ) -> Result<BitmapInfo, Box<dyn std::error::Error>> {
+        NullRenderer.register_bitmap_png(swf_tag)
+    }
+    fn begin_frame(&mut self, clear: Color) {
+        NullRenderer.begin_frame(clear)
+    }
+    fn end_frame(&mut self) {
+        NullRenderer.end_frame()
+    }
+    fn render_bitmap(&mut self, bitmap: BitmapHandle, transform: &Transform, smoothing: bool) {
+        NullRenderer.render_bitmap(bitmap, transform, smoothing)
+    }
+    fn render_shape(&mut self, shape: ShapeHandle, transform: &Transform) {
+        self.shapes.push((shape, transform.matrix));
+    }
+    fn draw_rect(&mut self, color: Color, _matrix: &Matrix) {
+        self.rects.push(color);
+    }
+    fn push_mask(&mut self) {
+        NullRenderer.push_mask()
+    }
+    fn activate_mask(&mut self) {
+        NullRenderer.activate_mask()
+    }
+    fn deactivate_mask(&mut self) {
+        NullRenderer.deactivate_mask()
+    }
+    fn pop_mask(&mut self) {
+        NullRenderer.pop_mask()
+    }
+    fn get_bitmap_pixels(&mut self, bitmap: BitmapHandle) -> Option<Bitmap> {
+        NullRenderer.get_bitmap_pixels(bitmap)
+    }
+    fn register_bitmap_raw(
+        &mut self,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Box<dyn std::error::Error>> {
+        NullRenderer.register_bitmap_raw(width, height, rgba)
+    }
+    fn update_texture(
+        &mut self,
+        bitmap: BitmapHandle,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Box<dyn std::error::Error>> {
+        NullRenderer.update_texture(bitmap, width, height, rgba)
+    }
+}
+
+/// A UI backend that does what `NullUiBackend` does, except where a test sets
+/// it up otherwise, and keeps what the player asks of it.
+pub struct TestUi {
+    /// Whether content may take the player full screen.
+    pub allows_fullscreen: bool,
+
+    /// Whether the player is full screen.
+    pub fullscreen: bool,
+
+    /// Whether the mouse is locked. Requests to lock it are always granted.
+    pub mouse_locked: bool,
+
+    /// Whether the player wants the cursor shown.
+    pub mouse_visible: bool,
+
+    /// The cursor the player last asked for.
+    pub cursor: MouseCursor,
+
+    /// The most touch points the host can track at once.
+    pub max_touch_points: u32,
+
+    /// Which way the stage is turned, or `None` if the host can't turn it.
+    pub orientation: Option<StageOrientation>,
+
+    /// The items of the last context menu the player showed.
+    pub menu_items: Vec<ContextMenuItem>,
+}
+
+impl Default for TestUi {
+    fn default() -> Self {
+        Self {
+            allows_fullscreen: false,
+            fullscreen: false,
+            mouse_locked: false,
+            mouse_visible: true,
+            cursor: MouseCursor::Arrow,
+            max_touch_points: 0,
+            orientation: None,
+            menu_items: Vec::new(),
+        }
+    }
+}
+
+impl UiBackend for TestUi {
+    fn is_key_down(&self, key: KeyCode) -> bool {
+        NullUiBackend::new().is_key_down(key)
+    }
+
+    fn last_key_code(&self) -> KeyCode {
+        NullUiBackend::new().last_key_code()
+    }
+
+    fn last_key_char(&self) -> Option<char> {
+        NullUiBackend::new().last_key_char()
+    }
+
+    fn mouse_visible(&self) -> bool {
+        self.mouse_visible
+    }
+
+    fn set_mouse_visible(&mut self, visible: bool) {
+        self.mouse_visible = visible;
+    }
+
+    fn set_mouse_cursor(&mut self, cursor: MouseCursor) {
+        self.cursor = cursor;
+    }
+
+    fn set_clipboard_content(&mut self, content: String) {
+        NullUiBackend::new().set_clipboard_content(content)
+    }
+
+    fn is_fullscreen(&self) -> bool {
+        self.fullscreen
+    }
+
+    fn allows_fullscreen(&self) -> bool {
+        self.allows_fullscreen
+    }
+
+    fn set_fullscreen(&mut self, is_full: bool) -> bool {
+        if self.allows_fullscreen {
+            self.fullscreen = is_full;
+        }
+
+        self.allows_fullscreen
+    }
+
+    fn is_mouse_locked(&self) -> bool {
+        self.mouse_locked
+    }
+
+    fn set_mouse_locked(&mut self, locked: bool) -> bool {
+        self.mouse_locked = locked;
+        true
+    }
+
+    fn max_touch_points(&self) -> u32 {
+        self.max_touch_points
+    }
+
+    fn orientation(&self) -> StageOrientation {
+        self.orientation.unwrap_or(StageOrientation::Unknown)
+    }
+
+    fn supports_orientation_change(&self) -> bool {
+        self.orientation.is_some()
+    }
+
+    fn set_orientation(&mut self, orientation: StageOrientation) -> bool {
+        match &mut self.orientation {
+            Some(current) => {
+                *current = orientation;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn show_context_menu(&mut self, items: &[ContextMenuItem]) {
+        self.menu_items = items.to_vec();
+    }
+
+    fn display_unsupported_message(&self) {
+        NullUiBackend::new().display_unsupported_message()
+    }
+
+    fn message(&self, message: &str) {
+        NullUiBackend::new().message(message)
+    }
+}
+
+/// A request made through a `TestNavigator`: its URL, method and body.
+pub type Fetch = (String, &'static str, Option<(Vec<u8>, String)>);
+
+/// What a `TestNavigator` answers a request with.
+type Responder = Box<dyn Fn(&Fetch) -> Vec<u8>>;
+
+/// A navigator that records the requests it is asked to make, answers each
+/// with whatever its responder returns for it, and otherwise does what
+/// `NullNavigatorBackend` does.
+pub struct TestNavigator {
+    inner: NullNavigatorBackend,
+    fetches: Rc<RefCell<Vec<Fetch>>>,
+    respond: Responder,
+}
+
+impl TestNavigator {
+    /// A navigator that answers every request with an empty body, and sends
+    /// the futures it spawns to `channel`.
+    pub fn new(channel: Sender<OwnedFuture<(), LoaderError>>) -> Self {
+        Self::with_responder(channel, |_| Vec::new())
+    }
+
+    /// A navigator that answers every request with what `respond` returns
+    /// for it.
+    pub fn with_responder<F>(channel: Sender<OwnedFuture<(), LoaderError>>, respond: F) -> Self
+    where
+        F: 'static + Fn(&Fetch) -> Vec<u8>,
+    {
+        Self {
+            inner: NullNavigatorBackend::with_base_path("", channel),
+            fetches: Rc::new(RefCell::new(Vec::new())),
+            respond: Box::new(respond),
+        }
+    }
+
+    /// The requests made so far, which keep coming in after the navigator
+    /// is given to a player.
+    pub fn fetches(&self) -> Rc<RefCell<Vec<Fetch>>> {
+        self.fetches.clone()
+    }
+}
+
+impl NavigatorBackend for TestNavigator {
+    fn navigate_to_url(
+        &self,
+        url: String,
+        window: Option<String>,
+        vars_method: Option<(NavigationMethod, IndexMap<String, String>)>,
+    ) {
+        self.inner.navigate_to_url(url, window, vars_method)
+    }
+
+    fn fetch(&self, url: &str, options: RequestOptions) -> OwnedFuture<Vec<u8>, LoaderError> {
+        let method = match options.method() {
+            NavigationMethod::Get => "GET",
+            NavigationMethod::Post => "POST",
+            NavigationMethod::Put => "PUT",
+            NavigationMethod::Delete => "DELETE",
+            NavigationMethod::Head => "HEAD",
+        };
+        let fetch = (url.to_string(), method, options.body().clone());
+        let response = (self.respond)(&fetch);
+        self.fetches.borrow_mut().push(fetch);
+
+        Box::pin(async move { Ok(response) })
+    }
+
+    fn time_since_launch(&mut self) -> Duration {
+        self.inner.time_since_launch()
+    }
+
+    fn spawn_future(&mut self, future: OwnedFuture<(), LoaderError>) {
+        self.inner.spawn_future(future)
+    }
+
+    fn resolve_relative_url<'a>(&mut self, url: &'a str) -> Cow<'a, str> {
+        self.inner.resolve_relative_url(url)
+    }
+
+    fn pre_process_url(&self, url: Url) -> Url {
+        self.inner.pre_process_url(url)
+    }
+}
+
+/// A player with null backends, for tests that span more than one update,
+/// or that need a backend of their own.
+///
+/// Futures spawned by the navigator are only run when the test asks for
+/// them with `poll` or `block`.
+pub struct TestPlayer {
+    player: Arc<Mutex<Player>>,
+    executor: NullExecutor,
+    traces: Rc<RefCell<Vec<String>>>,
+}
+
+impl TestPlayer {
+    /// Build a player with null backends throughout.
+    pub fn new() -> Self {
+        Self::with_backends(
+            Box::new(NullRenderer),
+            |_| Box::new(NullNavigatorBackend::new()),
+            Box::new(NullUiBackend::new()),
+        )
+    }
+
+    /// Build a player that uses `ui` as its UI backend.
+    pub fn with_ui(ui: Box<dyn UiBackend>) -> Self {
+        Self::with_backends(
+            Box::new(NullRenderer),
+            |_| Box::new(NullNavigatorBackend::new()),
+            ui,
+        )
+    }
+
+    /// Build a player that uses `renderer` as its render backend.
+    pub fn with_renderer(renderer: Box<dyn RenderBackend>) -> Self {
+        Self::with_backends(
+            renderer,
+            |_| Box::new(NullNavigatorBackend::new()),
+            Box::new(NullUiBackend::new()),
+        )
+    }
+
+    /// Build a player whose navigator fetches files relative to `path`.
+    pub fn with_base_path(path: impl AsRef<Path>) -> Self {
+        Self::with_navigator(|channel| {
+            Box::new(NullNavigatorBackend::with_base_path(path, channel))
+        })
+    }
+
+    /// Build a player with the navigator that `navigator` makes out of the
+    /// channel to the player's executor.
+    pub fn with_navigator<F>(navigator: F) -> Self
+    where
+        F: FnOnce(Sender<OwnedFuture<(), LoaderError>>) -> Box<dyn NavigatorBackend>,
+    {
+        Self::with_backends(
+            Box::new(NullRenderer),
+            navigator,
+            Box::new(NullUiBackend::new()),
+        )
+    }
+
+    fn with_backends<F>(
+        renderer: Box<dyn RenderBackend>,
+        navigator: F,
+        ui: Box<dyn UiBackend>,
+    ) -> Self
+    where
+        F: FnOnce(Sender<OwnedFuture<(), LoaderError>>) -> Box<dyn NavigatorBackend>,
+    {
+        let (executor, channel) = NullExecutor::new();
+        let traces = Rc::new(RefCell::new(Vec::new()));
+        let player = Player::new(
+            renderer,
+            Box::new(NullAudioBackend::new()),
+            navigator(channel),
+            Box::new(MemoryStorageBackend::default()),
+            Box::new(NullLocaleBackend::new()),
+            Box::new(NullVideoBackend::new()),
+            Box::new(TraceLog(traces.clone())),
+            ui,
+        )
+        .unwrap();
+
+        Self {
+            player,
+            executor,
+            traces,
+        }
+    }
+
+    /// Run one update of the player with an activation.
+    pub fn update<F, R>(&self, test: F) -> R
+    where
+        F: for<'gc> FnOnce(&mut Activation<'_, 'gc, '_>) -> R,
+    {
+        self.lock().update(|uc| {
+            let mut activation = Activation::from_nothing(uc.reborrow());
+            test(&mut activation)
+        })
+    }
+
+    /// Lock the player, to drive it directly.
+    pub fn lock(&self) -> MutexGuard<'_, Player> {
+        self.player.lock().unwrap()
+    }
+
+    /// Poll every future that was spawned so far once.
+    pub fn poll(&mut self) {
+        self.executor.poll_all().unwrap();
+    }
+
+    /// Run every future that was spawned so far to completion.
+    pub fn block(&mut self) {
+        self.executor.block_all().unwrap();
+    }
+
+    /// Every line traced so far.
+    pub fn traces(&self) -> Vec<String> {
+        self.traces.borrow().clone()
+    }
+}
+
+impl Default for TestPlayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Run a test with an activation on a fresh player that has loaded its AVM2
+/// globals.
+pub fn with_avm2<F>(test: F)
+where
+    F: for<'gc> FnOnce(&mut Activation<'_, 'gc, '_>),
+{
+    trace_with_avm2(test);
+}
+
+/// Run a test as in `with_avm2`, yielding every line it traced.
+pub fn trace_with_avm2<F>(test: F) -> Vec<String>
+where
+    F: for<'gc> FnOnce(&mut Activation<'_, 'gc, '_>),
+{
+    let player = TestPlayer::new();
+    player.update(test);
+
+    player.traces()
+}
+
+/// Run a test as in `with_avm2`, on a player that uses `ui` as its UI
+/// backend.
+pub fn with_avm2_ui<F>(ui: Box<dyn UiBackend>, test: F)
+where
+    F: for<'gc> FnOnce(&mut Activation<'_, 'gc, '_>),
+{
+    TestPlayer::with_ui(ui).update(test);
+}
+
+/// Get the class `name` out of `package` in the global domain.
+pub fn class<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    package: &'static str,
+    name: &'static str,
+) -> Object<'gc> {
+    activation
+        .avm2()
+        .global_domain()
+        .get_defined_value(activation, QName::new(Namespace::package(package), name))
+        .unwrap()
+        .coerce_to_object(activation)
+        .unwrap()
+}
+
+/// Construct an instance of the class `name` out of `package`, as `new`
+/// would.
+pub fn construct<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    package: &'static str,
+    name: &'static str,
+    args: &[Value<'gc>],
+) -> Object<'gc> {
+    let class = class(activation, package, name);
+    let proto = get(activation, class, "prototype")
+        .coerce_to_object(activation)
+        .unwrap();
+    let object = proto.construct(activation, args).unwrap();
+    class
+        .call(Some(object), args, activation, Some(proto))
+        .unwrap();
+
+    object
+}
+
+/// Get the public property `name` of `object`.
+pub fn get<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut object: Object<'gc>,
+    name: &'static str,
+) -> Value<'gc> {
+    object
+        .get_property(object, &QName::new(Namespace::public(), name), activation)
+        .unwrap()
+}
+
+/// Set the public property `name` of `object`.
+pub fn set<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut object: Object<'gc>,
+    name: &'static str,
+    value: Value<'gc>,
+) {
+    object
+        .set_property(
+            object,
+            &QName::new(Namespace::public(), name),
+            value,
+            activation,
+        )
+        .unwrap();
+}
+
+/// Call the public method `name` of `object`.
+pub fn call<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: Object<'gc>,
+    name: &'static str,
+    args: &[Value<'gc>],
+) -> Value<'gc> {
+    get(activation, object, name)
+        .coerce_to_object(activation)
+        .unwrap()
+        .call(Some(object), args, activation, None)
+        .unwrap()
+}
+
+/// The global object that values are parked on between updates.
+fn parking_lot<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Object<'gc> {
+    let name = QName::new(Namespace::public(), "Object");
+    let (_, mut script) = activation
+        .avm2()
+        .global_domain()
+        .get_defining_script(&name.into())
+        .unwrap()
+        .unwrap();
+
+    script.globals(&mut activation.context).unwrap()
+}
+
+/// Keep `value` around as `name`, for a later update to get it back with
+/// `parked`.
+pub fn park<'gc>(activation: &mut Activation<'_, 'gc, '_>, name: &'static str, value: Value<'gc>) {
+    let parking_lot = parking_lot(activation);
+    set(activation, parking_lot, name, value);
+}
+
+/// Get back the value an earlier update kept as `name` with `park`.
+pub fn parked<'gc>(activation: &mut Activation<'_, 'gc, '_>, name: &'static str) -> Value<'gc> {
+    let parking_lot = parking_lot(activation);
+    get(activation, parking_lot, name)
+}
+
+/// Wrap `method` in a function object, e.g. to use it as a listener.
+pub fn function<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    method: NativeMethod<'gc>,
+) -> Object<'gc> {
+    FunctionObject::from_builtin(
+        activation.context.gc_context,
+        method,
+        activation.avm2().prototypes().function,
+    )
+}
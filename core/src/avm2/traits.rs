@@ -2,7 +2,7 @@
 
 use crate::avm2::class::Class;
 use crate::avm2::method::Method;
-use crate::avm2::names::{Multiname, QName};
+use crate::avm2::names::{Multiname, Namespace, QName};
 use crate::avm2::script::TranslationUnit;
 use crate::avm2::value::{abc_default_value, Value};
 use crate::avm2::{Avm2, Error};
@@ -47,6 +47,24 @@ pub struct Trait<'gc> {
     kind: TraitKind<'gc>,
 }
 
+/// The value a slot of the given type holds before anything is assigned to it.
+///
+/// Numeric and `Boolean` slots start out as their zero value, untyped slots as
+/// `undefined`, and slots of any other type as `null`.
+fn default_value_for_type<'gc>(type_name: &Multiname<'gc>) -> Value<'gc> {
+    let is_public = type_name
+        .namespace_set()
+        .any(|ns| *ns == Namespace::public());
+    match type_name.local_name() {
+        None => Value::Undefined,
+        Some(name) if is_public && name == "int" => 0.into(),
+        Some(name) if is_public && name == "uint" => 0.into(),
+        Some(name) if is_public && name == "Number" => f64::NAN.into(),
+        Some(name) if is_public && name == "Boolean" => false.into(),
+        Some(_) => Value::Null,
+    }
+}
+
 fn trait_attribs_from_abc_traits(abc_trait: &AbcTrait) -> CollectWrapper<TraitAttributes> {
     let mut attributes = TraitAttributes::empty();
     attributes.set(TraitAttributes::FINAL, abc_trait.is_final);
@@ -192,18 +210,23 @@ impl<'gc> Trait<'gc> {
             } => Trait {
                 name,
                 attributes: trait_attribs_from_abc_traits(abc_trait),
-                kind: TraitKind::Slot {
-                    slot_id: *slot_id,
-                    type_name: if type_name.0 == 0 {
+                kind: {
+                    let type_name = if type_name.0 == 0 {
                         Multiname::any()
                     } else {
                         Multiname::from_abc_multiname_static(unit, type_name.clone(), mc)?
-                    },
-                    default_value: if let Some(dv) = value {
-                        Some(abc_default_value(unit, &dv, avm2, mc)?)
+                    };
+                    let default_value = if let Some(dv) = value {
+                        abc_default_value(unit, &dv, avm2, mc)?
                     } else {
-                        None
-                    },
+                        default_value_for_type(&type_name)
+                    };
+
+                    TraitKind::Slot {
+                        slot_id: *slot_id,
+                        type_name,
+                        default_value: Some(default_value),
+                    }
                 },
             },
             AbcTraitKind::Method { disp_id, method } => Trait {
@@ -253,18 +276,23 @@ impl<'gc> Trait<'gc> {
             } => Trait {
                 name,
                 attributes: trait_attribs_from_abc_traits(abc_trait),
-                kind: TraitKind::Const {
-                    slot_id: *slot_id,
-                    type_name: if type_name.0 == 0 {
+                kind: {
+                    let type_name = if type_name.0 == 0 {
                         Multiname::any()
                     } else {
                         Multiname::from_abc_multiname_static(unit, type_name.clone(), mc)?
-                    },
-                    default_value: if let Some(dv) = value {
-                        Some(abc_default_value(unit, &dv, avm2, mc)?)
+                    };
+                    let default_value = if let Some(dv) = value {
+                        abc_default_value(unit, &dv, avm2, mc)?
                     } else {
-                        None
-                    },
+                        default_value_for_type(&type_name)
+                    };
+
+                    TraitKind::Const {
+                        slot_id: *slot_id,
+                        type_name,
+                        default_value: Some(default_value),
+                    }
                 },
             },
         })
@@ -7,9 +7,12 @@ use crate::avm2::object::{NamespaceObject, Object, PrimitiveObject, TObject};
 use crate::avm2::script::TranslationUnit;
 use crate::avm2::string::AvmString;
 use crate::avm2::{Avm2, Error};
-use crate::ecma_conversions::{f64_to_wrapping_i32, f64_to_wrapping_u32};
+use crate::ecma_conversions::{f64_to_ecma_string, f64_to_wrapping_i32, f64_to_wrapping_u32};
+use crate::string_utils::swf_string_cmp;
 use gc_arena::{Collect, MutationContext};
+use std::borrow::Cow;
 use std::cell::Ref;
+use std::cmp::Ordering;
 use swf::avm2::types::{DefaultValue as AbcDefaultValue, Index};
 
 /// Indicate what kind of primitive coercion would be preferred when coercing
@@ -118,23 +121,7 @@ impl<'gc> From<usize> for Value<'gc> {
 
 impl PartialEq for Value<'_> {
     fn eq(&self, other: &Self) -> bool {
-        match (self, other) {
-            (Value::Undefined, Value::Undefined) => true,
-            (Value::Null, Value::Null) => true,
-            (Value::Bool(a), Value::Bool(b)) => a == b,
-            (Value::Number(a), Value::Number(b)) => a == b,
-            (Value::Number(a), Value::Unsigned(b)) => *a == *b as f64,
-            (Value::Number(a), Value::Integer(b)) => *a == *b as f64,
-            (Value::Unsigned(a), Value::Number(b)) => *a as f64 == *b,
-            (Value::Unsigned(a), Value::Unsigned(b)) => a == b,
-            (Value::Unsigned(a), Value::Integer(b)) => *a as i64 == *b as i64,
-            (Value::Integer(a), Value::Number(b)) => *a as f64 == *b,
-            (Value::Integer(a), Value::Unsigned(b)) => *a as i64 == *b as i64,
-            (Value::Integer(a), Value::Integer(b)) => a == b,
-            (Value::String(a), Value::String(b)) => a == b,
-            (Value::Object(a), Value::Object(b)) => Object::ptr_eq(*a, *b),
-            _ => false,
-        }
+        self.strict_eq(other)
     }
 }
 
@@ -246,6 +233,19 @@ impl<'gc> Value<'gc> {
         }
     }
 
+    /// Get the value as an `int`, if it is an `int` or a `uint` that fits in
+    /// one.
+    ///
+    /// Like `as_number`, this performs no coercion. It exists so that the
+    /// arithmetic opcodes can keep integer math in integers.
+    pub fn as_integer(&self) -> Option<i32> {
+        match self {
+            Value::Integer(i) => Some(*i),
+            Value::Unsigned(u) if *u <= i32::MAX as u32 => Some(*u as i32),
+            _ => None,
+        }
+    }
+
     /// Yields `true` if the given value is a primitive value.
     ///
     /// Note: Boxed primitive values are not considered primitive - it is
@@ -255,6 +255,42 @@ impl<'gc> Value<'gc> {
         !matches!(self, Value::Object(_))
     }
 
+    /// Get the name of this value's type, as reported by `typeof`.
+    ///
+    /// Boxed primitives, such as `new Number(5)`, are objects. Primitives
+    /// are only boxed while a method is called on them, so they keep their
+    /// own type name. Like in JavaScript, `null` is an object, but unlike
+    /// JavaScript, so are classes.
+    pub fn type_of(&self) -> &'static str {
+        match self {
+            Value::Undefined => "undefined",
+            Value::Null => "object",
+            Value::Bool(_) => "boolean",
+            Value::Number(_) | Value::Integer(_) | Value::Unsigned(_) => "number",
+            Value::Object(o) => {
+                // Objects constructed from a function or XML prototype share
+                // its representation, but are plain objects. Only instances
+                // of `Function` or `XML` themselves sit right below
+                // `Object.prototype`.
+                let is_instance = o
+                    .proto()
+                    .and_then(|p| p.proto())
+                    .and_then(|p| p.proto())
+                    .is_none();
+
+                match o {
+                    // Classes are objects even though they can be called.
+                    Object::FunctionObject(_) if is_instance && o.as_class().is_none() => {
+                        "function"
+                    }
+                    Object::XmlObject(_) if is_instance => "xml",
+                    _ => "object",
+                }
+            }
+            Value::String(_) => "string",
+        }
+    }
+
     /// Coerce the value to a boolean.
     ///
     /// Boolean coercion happens according to the rules specified in the ES4
@@ -444,32 +480,15 @@ impl<'gc> Value<'gc> {
         Ok(f64_to_wrapping_i32(self.coerce_to_number(activation)?))
     }
 
-    /// Minimum number of digits after which numbers are formatted as
-    /// exponential strings.
-    const MIN_DIGITS: f64 = -6.0;
-
-    /// Maximum number of digits before numbers are formatted as exponential
-    /// strings.
-    const MAX_DIGITS: f64 = 21.0;
-
-    /// Maximum number of significant digits renderable within coerced numbers.
-    ///
-    /// Any precision beyond this point will be discarded and replaced with
-    /// zeroes (for whole parts) or not rendered (for decimal parts).
-    const MAX_PRECISION: f64 = 15.0;
-
     /// Coerce the value to a String.
     ///
     /// This function returns the resulting String directly; or a TypeError if
     /// the value is an `Object` that cannot be converted to a primitive value.
     ///
     /// String conversions generally occur according to ECMA-262 3rd Edition's
-    /// ToString algorithm. The conversion of numbers to strings appears to be
-    /// somewhat underspecified; there are several formatting modes which
-    /// change at specific digit count cutoffs, but the spec allows
-    /// implementations to limit how much precision is displayed on coerced
-    /// numbers, even if that precision would result in rounding the whole part
-    /// of the number. (This is confusingly expressed in ECMA-262.)
+    /// ToString algorithm. Numbers are printed with the fewest digits that
+    /// round-trip, switching to exponential notation at the same cutoffs as
+    /// ECMA-262; see `f64_to_ecma_string`.
     ///
     /// TODO: The cutoffs change based on SWF/ABC version. Targeting FP10.3 in
     /// Animate CC 2020 significantly reduces them (towards zero).
@@ -482,35 +501,10 @@ impl<'gc> Value<'gc> {
             Value::Null => "null".into(),
             Value::Bool(true) => "true".into(),
             Value::Bool(false) => "false".into(),
-            Value::Number(n) if n.is_nan() => "NaN".into(),
-            Value::Number(n) if *n == 0.0 => "0".into(),
-            Value::Number(n) if *n < 0.0 => AvmString::new(
-                activation.context.gc_context,
-                format!("-{}", Value::Number(-n).coerce_to_string(activation)?),
-            ),
-            Value::Number(n) if n.is_infinite() => "Infinity".into(),
-            Value::Number(n) => {
-                let digits = n.log10().floor();
-
-                // TODO: This needs to limit precision in the resulting decimal
-                // output, not in binary.
-                let precision = (n * 10.0_f64.powf(Self::MAX_PRECISION - digits)).floor()
-                    / 10.0_f64.powf(Self::MAX_PRECISION - digits);
-
-                if digits < Self::MIN_DIGITS || digits >= Self::MAX_DIGITS {
-                    AvmString::new(
-                        activation.context.gc_context,
-                        format!(
-                            "{}e{}{}",
-                            precision / 10.0_f64.powf(digits),
-                            if digits < 0.0 { "-" } else { "+" },
-                            digits.abs()
-                        ),
-                    )
-                } else {
-                    AvmString::new(activation.context.gc_context, format!("{}", n))
-                }
-            }
+            Value::Number(n) => match f64_to_ecma_string(*n) {
+                Cow::Borrowed(s) => s.into(),
+                Cow::Owned(s) => AvmString::new(activation.context.gc_context, s),
+            },
             Value::Unsigned(u) => AvmString::new(activation.context.gc_context, format!("{}", u)),
             Value::Integer(i) => AvmString::new(activation.context.gc_context, format!("{}", i)),
             Value::String(s) => *s,
@@ -541,19 +535,20 @@ impl<'gc> Value<'gc> {
 
     /// Coerce the value to an Object.
     ///
-    /// TODO: In ECMA-262 3rd Edition, this would also box primitive values
-    /// into objects. Supposedly, ES4 removes primitive values entirely, and
-    /// the AVM2 Overview also implies that all this does is throw an error if
-    /// `undefined` or `null` are present. For the time being, this is what
-    /// that does. If we implement primitive boxing, then we should also box
-    /// them here, and this should change type to return `Object<'gc>`.
+    /// Primitive values are boxed. `null` and `undefined` have no object
+    /// form, and throw the `TypeError`s that Flash Player does: #1009 for
+    /// `null` and #1010 for `undefined`.
     pub fn coerce_to_object(
         &self,
         activation: &mut Activation<'_, 'gc, '_>,
     ) -> Result<Object<'gc>, Error> {
         match self {
-            Value::Undefined => return Err("TypeError: undefined is not an Object".into()),
-            Value::Null => return Err("TypeError: null is not an Object".into()),
+            Value::Undefined => {
+                return Err("TypeError: Error #1010: A term is undefined and has no properties.".into())
+            }
+            Value::Null => {
+                return Err("TypeError: Error #1009: Cannot access a property or method of a null object reference.".into())
+            }
             Value::Object(o) => return Ok(*o),
             _ => {}
         };
@@ -570,11 +565,41 @@ impl<'gc> Value<'gc> {
         PrimitiveObject::from_primitive(self.clone(), proto, activation.context.gc_context)
     }
 
+    /// Determine if two values are strictly equal to each other.
+    ///
+    /// This strict equality algorithm is intended to match ECMA-262 3rd
+    /// edition, section 11.9.6, and is shared by `===`, `!==`, `switch`, and
+    /// any builtins that search for values (such as `Array.indexOf`). Notably,
+    /// `NaN` is never strictly equal to anything, including itself, while
+    /// positive and negative zero are equal.
+    pub fn strict_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Value::Undefined, Value::Undefined) => true,
+            (Value::Null, Value::Null) => true,
+            (Value::Bool(a), Value::Bool(b)) => a == b,
+            (Value::Number(a), Value::Number(b)) => a == b,
+            (Value::Number(a), Value::Unsigned(b)) => *a == *b as f64,
+            (Value::Number(a), Value::Integer(b)) => *a == *b as f64,
+            (Value::Unsigned(a), Value::Number(b)) => *a as f64 == *b,
+            (Value::Unsigned(a), Value::Unsigned(b)) => a == b,
+            (Value::Unsigned(a), Value::Integer(b)) => *a as i64 == *b as i64,
+            (Value::Integer(a), Value::Number(b)) => *a as f64 == *b,
+            (Value::Integer(a), Value::Unsigned(b)) => *a as i64 == *b as i64,
+            (Value::Integer(a), Value::Integer(b)) => a == b,
+            (Value::String(a), Value::String(b)) => a == b,
+            (Value::Object(a), Value::Object(b)) => Object::ptr_eq(*a, *b),
+            _ => false,
+        }
+    }
+
     /// Determine if two values are abstractly equal to each other.
     ///
     /// This abstract equality algorithm is intended to match ECMA-262 3rd
     /// edition, section 11.9.3. Inequality is the direct opposite of equality,
     /// and this function always returns a boolean.
+    ///
+    /// Objects compared against primitives are coerced without a hint, so
+    /// that each object's `default_hint` decides how it is converted.
     pub fn abstract_eq(
         &self,
         other: &Value<'gc>,
@@ -642,8 +667,7 @@ impl<'gc> Value<'gc> {
             | (Value::Number(_), Value::Object(_))
             | (Value::Unsigned(_), Value::Object(_))
             | (Value::Integer(_), Value::Object(_)) => {
-                //TODO: Should this be `Hint::Number`, `Hint::String`, or no-hint?
-                let primitive_other = other.coerce_to_primitive(Some(Hint::Number), activation)?;
+                let primitive_other = other.coerce_to_primitive(None, activation)?;
 
                 self.abstract_eq(&primitive_other, activation)
             }
@@ -651,8 +675,7 @@ impl<'gc> Value<'gc> {
             | (Value::Object(_), Value::Number(_))
             | (Value::Object(_), Value::Unsigned(_))
             | (Value::Object(_), Value::Integer(_)) => {
-                //TODO: Should this be `Hint::Number`, `Hint::String`, or no-hint?
-                let primitive_self = self.coerce_to_primitive(Some(Hint::Number), activation)?;
+                let primitive_self = self.coerce_to_primitive(None, activation)?;
 
                 primitive_self.abstract_eq(other, activation)
             }
@@ -675,7 +698,7 @@ impl<'gc> Value<'gc> {
         let prim_other = other.coerce_to_primitive(Some(Hint::Number), activation)?;
 
         if let (Value::String(s), Value::String(o)) = (&prim_self, &prim_other) {
-            return Ok(Some(s.to_string().bytes().lt(o.to_string().bytes())));
+            return Ok(Some(swf_string_cmp(s, o) == Ordering::Less));
         }
 
         let num_self = prim_self.coerce_to_number(activation)?;
@@ -688,3 +711,360 @@ impl<'gc> Value<'gc> {
         Ok(Some(num_self < num_other))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::object::{FunctionObject, PrimitiveObject, ScriptObject};
+    use crate::avm2::test_utils::with_avm2;
+
+    #[test]
+    fn null_and_undefined_are_not_objects() {
+        with_avm2(|activation| {
+            let error = Value::Null.coerce_to_object(activation).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "TypeError: Error #1009: Cannot access a property or method of a null object reference."
+            );
+
+            let error = Value::Undefined.coerce_to_object(activation).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "TypeError: Error #1010: A term is undefined and has no properties."
+            );
+        });
+    }
+
+    #[test]
+    fn strict_eq_nan_is_never_equal() {
+        let nan = Value::Number(f64::NAN);
+
+        assert!(!nan.strict_eq(&nan));
+        assert!(!nan.strict_eq(&Value::Number(f64::NAN)));
+        assert!(!nan.strict_eq(&Value::Integer(0)));
+        assert!(!nan.strict_eq(&Value::Unsigned(0)));
+        assert!(!nan.strict_eq(&Value::Undefined));
+        assert!(!Value::Integer(0).strict_eq(&nan));
+        assert!(!Value::Unsigned(0).strict_eq(&nan));
+    }
+
+    #[test]
+    fn strict_eq_numbers() {
+        assert!(Value::Number(0.0).strict_eq(&Value::Number(-0.0)));
+        assert!(Value::Number(-0.0).strict_eq(&Value::Integer(0)));
+        assert!(Value::Number(5.0).strict_eq(&Value::Integer(5)));
+        assert!(Value::Unsigned(5).strict_eq(&Value::Integer(5)));
+        assert!(!Value::Unsigned(u32::MAX).strict_eq(&Value::Integer(-1)));
+        assert!(!Value::Number(1.0).strict_eq(&Value::Bool(true)));
+    }
+
+    #[test]
+    fn abstract_eq_truth_table() {
+        with_avm2(|activation| {
+            let mc = activation.context.gc_context;
+            let prototypes = activation.context.avm2.prototypes();
+            let boxed_five =
+                PrimitiveObject::from_primitive(5.into(), prototypes.number, mc).unwrap();
+            let boxed_string =
+                PrimitiveObject::from_primitive("abc".into(), prototypes.string, mc).unwrap();
+            let plain = ScriptObject::object(mc, prototypes.object);
+            let other_plain = ScriptObject::object(mc, prototypes.object);
+
+            let undefined = Value::Undefined;
+            let null = Value::Null;
+            let nan = Value::Number(f64::NAN);
+            let table: &[(Value<'_>, Value<'_>, bool)] = &[
+                // undefined and null only equal each other.
+                (undefined.clone(), undefined.clone(), true),
+                (null.clone(), null.clone(), true),
+                (undefined.clone(), null.clone(), true),
+                (null.clone(), undefined.clone(), true),
+                (undefined.clone(), 0.into(), false),
+                (null.clone(), 0.into(), false),
+                (null.clone(), "".into(), false),
+                (null.clone(), false.into(), false),
+                (undefined.clone(), plain.into(), false),
+                // Numbers of every representation compare by value.
+                (5.into(), Value::Number(5.0), true),
+                (Value::Unsigned(5), Value::Integer(5), true),
+                (Value::Number(0.0), Value::Number(-0.0), true),
+                (Value::Unsigned(u32::MAX), Value::Integer(-1), false),
+                (nan.clone(), nan.clone(), false),
+                (nan.clone(), Value::Integer(0), false),
+                // Strings compare by contents, or as numbers against numbers.
+                ("abc".into(), "abc".into(), true),
+                ("abc".into(), "abd".into(), false),
+                ("5".into(), 5.into(), true),
+                (5.into(), "5".into(), true),
+                (Value::Unsigned(16), "0x10".into(), true),
+                ("".into(), 0.into(), true),
+                ("abc".into(), nan.clone(), false),
+                ("1".into(), "1.0".into(), false),
+                // Booleans become numbers before comparing.
+                (true.into(), true.into(), true),
+                (true.into(), 1.into(), true),
+                (false.into(), "0".into(), true),
+                ("1".into(), true.into(), true),
+                (true.into(), "true".into(), false),
+                (Value::Integer(2), true.into(), false),
+                // Objects compare by identity against each other...
+                (plain.into(), plain.into(), true),
+                (plain.into(), other_plain.into(), false),
+                (boxed_five.into(), boxed_five.into(), true),
+                // ...and by their primitive value against primitives.
+                (boxed_five.into(), 5.into(), true),
+                (Value::Number(5.0), boxed_five.into(), true),
+                (boxed_five.into(), "5".into(), true),
+                (boxed_five.into(), 6.into(), false),
+                (boxed_string.into(), "abc".into(), true),
+                ("abc".into(), boxed_string.into(), true),
+                (plain.into(), "[object Object]".into(), true),
+                (true.into(), boxed_five.into(), false),
+                (boxed_five.into(), null.clone(), false),
+            ];
+
+            for (a, b, expected) in table {
+                assert_eq!(
+                    a.abstract_eq(b, activation).unwrap(),
+                    *expected,
+                    "{:?} == {:?}",
+                    a,
+                    b
+                );
+                assert_eq!(
+                    b.abstract_eq(a, activation).unwrap(),
+                    *expected,
+                    "{:?} == {:?}",
+                    b,
+                    a
+                );
+            }
+        });
+    }
+
+    #[test]
+    fn integer_coercion_table() {
+        with_avm2(|activation| {
+            // (input, ToUint32, ToInt32)
+            let table: &[(Value<'_>, u32, i32)] = &[
+                (Value::Number(0.0), 0, 0),
+                (Value::Number(-0.0), 0, 0),
+                (Value::Number(1.9), 1, 1),
+                (Value::Number(-1.9), 4294967295, -1),
+                (Value::Number(2147483647.0), 2147483647, 2147483647),
+                (Value::Number(2147483648.0), 2147483648, -2147483648),
+                (Value::Number(-2147483649.0), 2147483647, 2147483647),
+                (Value::Number(4294967295.0), 4294967295, -1),
+                (Value::Number(4294967296.0), 0, 0),
+                (Value::Number(4294967296.5), 0, 0),
+                (Value::Number(-4294967297.5), 4294967295, -1),
+                (Value::Number(1e20), 1661992960, 1661992960),
+                (Value::Number(f64::NAN), 0, 0),
+                (Value::Number(f64::INFINITY), 0, 0),
+                (Value::Number(f64::NEG_INFINITY), 0, 0),
+                (Value::Integer(-1), 4294967295, -1),
+                (Value::Unsigned(4294967295), 4294967295, -1),
+                ("4294967296.5".into(), 0, 0),
+                ("-1.5".into(), 4294967295, -1),
+                ("abc".into(), 0, 0),
+                (true.into(), 1, 1),
+                (Value::Undefined, 0, 0),
+                (Value::Null, 0, 0),
+            ];
+
+            for (value, unsigned, signed) in table {
+                assert_eq!(
+                    value.coerce_to_u32(activation).unwrap(),
+                    *unsigned,
+                    "ToUint32({:?})",
+                    value
+                );
+                assert_eq!(
+                    value.coerce_to_i32(activation).unwrap(),
+                    *signed,
+                    "ToInt32({:?})",
+                    value
+                );
+            }
+        });
+    }
+
+    /// Construct a wrapper object as `new` would, e.g. `new Number(5)`.
+    fn construct_wrapper<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        class_name: &'static str,
+        value: Value<'gc>,
+    ) -> Value<'gc> {
+        let mut class = activation
+            .avm2()
+            .global_domain()
+            .get_defined_value(activation, QName::new(Namespace::public(), class_name))
+            .unwrap()
+            .coerce_to_object(activation)
+            .unwrap();
+        let proto = class
+            .get_property(
+                class,
+                &QName::new(Namespace::public(), "prototype"),
+                activation,
+            )
+            .unwrap()
+            .coerce_to_object(activation)
+            .unwrap();
+        let args = [value];
+        let object = proto.construct(activation, &args).unwrap();
+        class
+            .call(Some(object), &args, activation, object.proto())
+            .unwrap();
+
+        object.into()
+    }
+
+    #[test]
+    fn type_of_matches_flash() {
+        with_avm2(|activation| {
+            let object_class = activation
+                .avm2()
+                .global_domain()
+                .get_defined_value(activation, QName::new(Namespace::public(), "Object"))
+                .unwrap();
+            let function = FunctionObject::from_builtin(
+                activation.context.gc_context,
+                |_, _, _| Ok(Value::Undefined),
+                activation.avm2().prototypes().function,
+            );
+            let xml_proto = activation.avm2().prototypes().xml;
+            let xml = xml_proto.construct(activation, &[]).unwrap();
+
+            let table: Vec<(Value<'_>, &str)> = vec![
+                (Value::Undefined, "undefined"),
+                (Value::Null, "object"),
+                (true.into(), "boolean"),
+                (Value::Integer(-1), "number"),
+                (Value::Unsigned(1), "number"),
+                (0.5.into(), "number"),
+                ("".into(), "string"),
+                (
+                    ScriptObject::object(
+                        activation.context.gc_context,
+                        activation.avm2().prototypes().object,
+                    )
+                    .into(),
+                    "object",
+                ),
+                (function.into(), "function"),
+                (object_class, "object"),
+                (xml.into(), "xml"),
+            ];
+
+            for (value, type_name) in table {
+                assert_eq!(value.type_of(), type_name, "typeof {:?}", value);
+            }
+        });
+    }
+
+    #[test]
+    fn wrappers_are_distinct_from_primitives() {
+        with_avm2(|activation| {
+            let table: &[(&str, Value<'_>, &str)] = &[
+                ("Number", 5.into(), "number"),
+                ("String", "abc".into(), "string"),
+                ("Boolean", true.into(), "boolean"),
+            ];
+
+            for (class_name, primitive, type_name) in table {
+                let wrapper = construct_wrapper(activation, class_name, primitive.clone());
+                let other_wrapper = construct_wrapper(activation, class_name, primitive.clone());
+
+                assert_eq!(primitive.type_of(), *type_name);
+                assert_eq!(wrapper.type_of(), "object", "typeof new {}", class_name);
+
+                assert!(primitive.strict_eq(&primitive.clone()));
+                assert!(wrapper.strict_eq(&wrapper));
+                assert!(
+                    !wrapper.strict_eq(primitive),
+                    "new {0}(x) === x",
+                    class_name
+                );
+                assert!(
+                    !primitive.strict_eq(&wrapper),
+                    "x === new {0}(x)",
+                    class_name
+                );
+                assert!(!wrapper.strict_eq(&other_wrapper));
+
+                assert!(
+                    wrapper.abstract_eq(primitive, activation).unwrap(),
+                    "new {0}(x) == x",
+                    class_name
+                );
+            }
+        });
+    }
+
+    #[test]
+    fn method_calls_on_primitives_keep_their_type() {
+        with_avm2(|activation| {
+            let five: Value<'_> = 5.into();
+            let boxed = five.coerce_to_object(activation).unwrap();
+
+            assert_eq!(boxed.value_of(activation.context.gc_context).unwrap(), five);
+            assert_eq!(five.type_of(), "number");
+            assert!(five.strict_eq(&5.into()));
+        });
+    }
+
+    #[test]
+    fn booleans_and_empty_values_print_exactly() {
+        with_avm2(|activation| {
+            for (value, expected) in &[
+                (Value::Bool(true), "true"),
+                (Value::Bool(false), "false"),
+                (Value::Null, "null"),
+                (Value::Undefined, "undefined"),
+            ] {
+                assert_eq!(
+                    value.coerce_to_string(activation).unwrap().to_string(),
+                    *expected
+                );
+            }
+
+            // `null` is only a string in a string context.
+            assert_eq!(Value::Null.coerce_to_number(activation).unwrap(), 0.0);
+        });
+    }
+
+    #[test]
+    fn numbers_print_in_shortest_form() {
+        with_avm2(|activation| {
+            for (number, expected) in &[
+                (0.1 + 0.2, "0.30000000000000004"),
+                (123.456, "123.456"),
+                (-2.5, "-2.5"),
+                (-0.0, "0"),
+                (9007199254740993.0, "9007199254740992"),
+                (1e20, "100000000000000000000"),
+                (123456789012345680000.0, "123456789012345680000"),
+                (1e21, "1e+21"),
+                (1.5e300, "1.5e+300"),
+                (f64::MAX, "1.79769313486231e+308"),
+                (1.0000000000000002e21, "1e+21"),
+                (0.000001, "0.000001"),
+                (0.0000015, "0.0000015"),
+                (1e-7, "1e-7"),
+                (-1.5e-7, "-1.5e-7"),
+                (5e-324, "5e-324"),
+                (1.2315987654321988e-8, "1.2315987654321988e-8"),
+                (f64::NEG_INFINITY, "-Infinity"),
+            ] {
+                assert_eq!(
+                    Value::Number(*number)
+                        .coerce_to_string(activation)
+                        .unwrap()
+                        .to_string(),
+                    *expected
+                );
+            }
+        });
+    }
+}
@@ -104,6 +104,14 @@ pub trait AudioBackend: Downcast {
     /// what the stage frame rate is. Otherwise, you are free to avoid
     /// implementing it.
     fn set_frame_rate(&mut self, _frame_rate: f64) {}
+
+    /// Whether this backend can play MP3 sounds.
+    ///
+    /// Backends that use Ruffle's own decoders can only do so when one of the
+    /// MP3 decoder features is enabled.
+    fn supports_mp3(&self) -> bool {
+        cfg!(any(feature = "minimp3", feature = "puremp3"))
+    }
 }
 
 impl_downcast!(AudioBackend);
@@ -76,6 +76,18 @@ pub enum NavigationMethod {
 
     /// Indicates that navigation should generate a POST request.
     Post,
+
+    /// Indicates that a fetch should generate a PUT request.
+    ///
+    /// This and the methods below can only be asked for by a `URLRequest`,
+    /// and never by a browser navigation.
+    Put,
+
+    /// Indicates that a fetch should generate a DELETE request.
+    Delete,
+
+    /// Indicates that a fetch should generate a HEAD request.
+    Head,
 }
 
 impl NavigationMethod {
@@ -95,6 +107,27 @@ impl NavigationMethod {
             _ => None,
         }
     }
+
+    /// Convert a `URLRequestMethod` into a NavigationMethod.
+    ///
+    /// Flash itself only allows `GET` and `POST` here, but content that
+    /// talks to a REST API may use the other methods too.
+    pub fn from_url_request_method(method: &str) -> Option<Self> {
+        match method {
+            "GET" => Some(Self::Get),
+            "POST" => Some(Self::Post),
+            "PUT" => Some(Self::Put),
+            "DELETE" => Some(Self::Delete),
+            "HEAD" => Some(Self::Head),
+            _ => None,
+        }
+    }
+
+    /// Whether requests made with this method send their data in the body,
+    /// rather than in the URL's query.
+    pub fn has_body(self) -> bool {
+        matches!(self, Self::Post | Self::Put)
+    }
 }
 
 /// Represents request options to be sent as part of a fetch.
@@ -126,6 +159,11 @@ impl RequestOptions {
         }
     }
 
+    /// Construct request options for a request with any method.
+    pub fn with_method(method: NavigationMethod, body: Option<(Vec<u8>, String)>) -> Self {
+        Self { method, body }
+    }
+
     /// Retrieve the navigation method for this request.
     pub fn method(&self) -> NavigationMethod {
         self.method
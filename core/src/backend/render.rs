@@ -60,6 +60,13 @@ pub trait RenderBackend: Downcast {
         height: u32,
         rgba: Vec<u8>,
     ) -> Result<BitmapHandle, Error>;
+
+    /// Whether this backend draws with the GPU.
+    ///
+    /// This is reported to content as `Stage.wmodeGPU`.
+    fn is_hardware_accelerated(&self) -> bool {
+        false
+    }
 }
 impl_downcast!(RenderBackend);
 
@@ -1,3 +1,4 @@
+use crate::context_menu::ContextMenuItem;
 use crate::events::KeyCode;
 use downcast_rs::Downcast;
 
@@ -8,6 +9,18 @@ pub trait UiBackend: Downcast {
 
     fn last_key_char(&self) -> Option<char>;
 
+    /// Whether Caps Lock is currently on.
+    ///
+    /// Backends that can't read the host's lock keys report them as off.
+    fn is_caps_lock_on(&self) -> bool {
+        false
+    }
+
+    /// Whether Num Lock is currently on.
+    fn is_num_lock_on(&self) -> bool {
+        false
+    }
+
     fn mouse_visible(&self) -> bool;
 
     fn set_mouse_visible(&mut self, visible: bool);
@@ -20,6 +33,94 @@ pub trait UiBackend: Downcast {
 
     fn is_fullscreen(&self) -> bool;
 
+    /// Whether the host lets content take the player full screen.
+    fn allows_fullscreen(&self) -> bool {
+        false
+    }
+
+    /// Enters or leaves full screen on behalf of content.
+    ///
+    /// Returns whether the host went along with the request. Hosts that
+    /// change the state themselves should tell the player with
+    /// `Player::set_fullscreen_state` instead.
+    fn set_fullscreen(&mut self, _is_full: bool) -> bool {
+        false
+    }
+
+    /// Whether the host has locked the mouse to the player.
+    fn is_mouse_locked(&self) -> bool {
+        false
+    }
+
+    /// Locks or unlocks the mouse on behalf of content.
+    ///
+    /// While the mouse is locked, the cursor is hidden and held in place, and
+    /// the host keeps sending `PlayerEvent::MouseMove` with the mouse's
+    /// position as if it were still free to move. Returns whether the host
+    /// went along with the request.
+    fn set_mouse_locked(&mut self, _locked: bool) -> bool {
+        false
+    }
+
+    /// The most touch points the host can track at once.
+    ///
+    /// Hosts without a touch screen report 0.
+    fn max_touch_points(&self) -> u32 {
+        0
+    }
+
+    /// Whether the host sends touch points with `PlayerEvent::TouchBegin` and
+    /// friends.
+    fn supports_touch_events(&self) -> bool {
+        self.max_touch_points() > 0
+    }
+
+    /// Which way the stage is turned.
+    ///
+    /// Hosts with a fixed orientation, such as desktops, report `Unknown`.
+    fn orientation(&self) -> StageOrientation {
+        StageOrientation::Unknown
+    }
+
+    /// Which way the device is turned, which may differ from the stage's
+    /// orientation when the stage keeps its own.
+    fn device_orientation(&self) -> StageOrientation {
+        self.orientation()
+    }
+
+    /// Whether the host can turn the stage on behalf of content.
+    fn supports_orientation_change(&self) -> bool {
+        false
+    }
+
+    /// Turns the stage on behalf of content.
+    ///
+    /// Returns whether the host went along with the request. Hosts that
+    /// turn the stage themselves should tell the player with
+    /// `Player::set_orientation_state` instead.
+    fn set_orientation(&mut self, _orientation: StageOrientation) -> bool {
+        false
+    }
+
+    /// Locks the stage to portrait or landscape orientations, or frees it.
+    ///
+    /// Returns whether the host went along with the request.
+    fn set_aspect_ratio(&mut self, _aspect_ratio: StageAspectRatio) -> bool {
+        false
+    }
+
+    /// Whether a screen reader is reading the player's content.
+    fn is_screen_reader_active(&self) -> bool {
+        false
+    }
+
+    /// Shows the player's context menu with the given items.
+    ///
+    /// The host reports the chosen item back through
+    /// `Player::run_context_menu_callback`. Backends that can't show a menu
+    /// leave this as a no-op.
+    fn show_context_menu(&mut self, _items: &[ContextMenuItem]) {}
+
     /// Displays a warning about unsupported content in Ruffle.
     /// The user can still click an "OK" or "run anyway" message to dismiss the warning.
     fn display_unsupported_message(&self);
@@ -49,6 +150,65 @@ pub enum MouseCursor {
     Grab,
 }
 
+/// Which way the stage or device is turned.
+/// Equivalent to AS3 `StageOrientation`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StageOrientation {
+    /// Upright, as the device is normally held.
+    Default,
+
+    /// Turned a quarter to the left.
+    RotatedLeft,
+
+    /// Turned a quarter to the right.
+    RotatedRight,
+
+    /// Turned halfway around.
+    UpsideDown,
+
+    /// Not known, e.g. when the device is lying flat.
+    Unknown,
+}
+
+impl StageOrientation {
+    /// The AS3 name of this orientation.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            StageOrientation::Default => "default",
+            StageOrientation::RotatedLeft => "rotatedLeft",
+            StageOrientation::RotatedRight => "rotatedRight",
+            StageOrientation::UpsideDown => "upsideDown",
+            StageOrientation::Unknown => "unknown",
+        }
+    }
+
+    /// Parse the AS3 name of an orientation.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "default" => Some(StageOrientation::Default),
+            "rotatedLeft" => Some(StageOrientation::RotatedLeft),
+            "rotatedRight" => Some(StageOrientation::RotatedRight),
+            "upsideDown" => Some(StageOrientation::UpsideDown),
+            "unknown" => Some(StageOrientation::Unknown),
+            _ => None,
+        }
+    }
+}
+
+/// Which orientations content lets the stage be turned to.
+/// Equivalent to AS3 `StageAspectRatio`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StageAspectRatio {
+    /// Taller than wide.
+    Portrait,
+
+    /// Wider than tall.
+    Landscape,
+
+    /// Whichever way the device is held.
+    Any,
+}
+
 /// UiBackend that does nothing.
 pub struct NullUiBackend {}
 
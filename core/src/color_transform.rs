@@ -37,6 +37,30 @@ impl ColorTransform {
             && self.b_add == 0.0
             && self.a_add == 0.0
     }
+
+    /// Apply this transform to a color.
+    ///
+    /// Each channel is clamped to the `u8` range after its multiplier and
+    /// offset are applied, so extreme transforms saturate rather than wrap.
+    pub fn apply(&self, color: &swf::Color) -> swf::Color {
+        swf::Color {
+            r: clamped_u8(f32::from(color.r) * self.r_mult + self.r_add * 255.0),
+            g: clamped_u8(f32::from(color.g) * self.g_mult + self.g_add * 255.0),
+            b: clamped_u8(f32::from(color.b) * self.b_mult + self.b_add * 255.0),
+            a: clamped_u8(f32::from(color.a) * self.a_mult + self.a_add * 255.0),
+        }
+    }
+}
+
+/// Convert an f32 to a u8, clamping all out-of-range values to the `u8` range.
+fn clamped_u8(v: f32) -> u8 {
+    if v < 0.0 {
+        0
+    } else if v > 255.0 {
+        255
+    } else {
+        v as u8
+    }
 }
 
 impl std::default::Default for ColorTransform {
@@ -86,3 +110,42 @@ impl std::ops::MulAssign for ColorTransform {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn color(r: u8, g: u8, b: u8, a: u8) -> swf::Color {
+        swf::Color { r, g, b, a }
+    }
+
+    #[test]
+    fn extreme_transforms_clamp_each_channel() {
+        let color_transform = ColorTransform {
+            r_mult: 4.0,
+            g_mult: -1.0,
+            b_mult: 1.0,
+            a_mult: 1.0,
+            r_add: 0.0,
+            g_add: 0.5,
+            b_add: -2.0,
+            a_add: 1.0,
+        };
+
+        assert_eq!(
+            color_transform.apply(&color(200, 100, 100, 0)),
+            color(255, 27, 0, 255)
+        );
+
+        // An alpha offset of 255 makes a transparent object fully opaque.
+        let opaque = ColorTransform {
+            a_add: 1.0,
+            ..Default::default()
+        };
+        assert_eq!(opaque.apply(&color(10, 20, 30, 0)), color(10, 20, 30, 255));
+        assert_eq!(
+            opaque.apply(&color(10, 20, 30, 128)),
+            color(10, 20, 30, 255)
+        );
+    }
+}
@@ -28,3 +28,34 @@ impl Default for Letterbox {
         Letterbox::Fullscreen
     }
 }
+
+/// Controls how the stage is fitted into the viewport, as set by content
+/// through `Stage.scaleMode`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename = "scaleMode"))]
+pub enum StageScaleMode {
+    /// The whole stage is shown at its aspect ratio, with margins around it
+    /// if the viewport's aspect ratio differs.
+    #[cfg_attr(feature = "serde", serde(rename = "showAll"))]
+    ShowAll,
+
+    /// The stage is stretched to fill the viewport exactly.
+    #[cfg_attr(feature = "serde", serde(rename = "exactFit"))]
+    ExactFit,
+
+    /// The stage fills the viewport at its aspect ratio, cropping whatever
+    /// falls outside of it.
+    #[cfg_attr(feature = "serde", serde(rename = "noBorder"))]
+    NoBorder,
+
+    /// The stage is not scaled at all, and is as big as the viewport.
+    #[cfg_attr(feature = "serde", serde(rename = "noScale"))]
+    NoScale,
+}
+
+impl Default for StageScaleMode {
+    fn default() -> Self {
+        StageScaleMode::ShowAll
+    }
+}
@@ -10,9 +10,10 @@ use crate::backend::{
     navigator::NavigatorBackend,
     render::RenderBackend,
     storage::StorageBackend,
-    ui::UiBackend,
+    ui::{MouseCursor, UiBackend},
     video::VideoBackend,
 };
+use crate::config::StageScaleMode;
 use crate::display_object::{EditText, MovieClip, SoundTransform};
 use crate::external::ExternalInterface;
 use crate::focus_tracker::FocusTracker;
@@ -101,12 +102,32 @@ pub struct UpdateContext<'a, 'gc, 'gc_context> {
     /// The location of the mouse when it was last over the player.
     pub mouse_position: &'a (Twips, Twips),
 
+    /// The mouse cursor icon currently being displayed.
+    pub mouse_cursor: &'a mut MouseCursor,
+
+    /// The mouse cursor icon requested by content via `Mouse.cursor`.
+    ///
+    /// If set, this overrides the cursor of whatever object is being hovered.
+    pub forced_mouse_cursor: &'a mut Option<MouseCursor>,
+
     /// The object being dragged via a `startDrag` action.
     pub drag_object: &'a mut Option<crate::player::DragObject<'gc>>,
 
+    /// The context menu the host is currently showing, if any.
+    pub current_context_menu: &'a mut Option<crate::context_menu::ContextMenuState<'gc>>,
+
     /// The dimensions of the stage.
     pub stage_size: (Twips, Twips),
 
+    /// The dimensions of the viewport, in viewport pixels.
+    pub viewport_size: (u32, u32),
+
+    /// How the stage is fitted into the viewport.
+    pub scale_mode: &'a mut StageScaleMode,
+
+    /// The number of device pixels per viewport pixel.
+    pub contents_scale_factor: f64,
+
     /// Weak reference to the player.
     ///
     /// Recipients of an update context may upgrade the reference to ensure
@@ -269,8 +290,14 @@ impl<'a, 'gc, 'gc_context> UpdateContext<'a, 'gc, 'gc_context> {
             levels: self.levels,
             mouse_hovered_object: self.mouse_hovered_object,
             mouse_position: self.mouse_position,
+            mouse_cursor: self.mouse_cursor,
+            forced_mouse_cursor: self.forced_mouse_cursor,
             drag_object: self.drag_object,
+            current_context_menu: self.current_context_menu,
             stage_size: self.stage_size,
+            viewport_size: self.viewport_size,
+            scale_mode: self.scale_mode,
+            contents_scale_factor: self.contents_scale_factor,
             player: self.player.clone(),
             load_manager: self.load_manager,
             system: self.system,
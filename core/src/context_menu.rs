@@ -0,0 +1,480 @@
+//! The context menu shown when the user right-clicks the player
+//!
+//! The menu is built from the built-in items the player can perform, and
+//! from the `ContextMenu` of the ActionScript 3 object under the mouse. The
+//! host displays it through `UiBackend::show_context_menu` and reports the
+//! chosen item back to the player.
+
+use crate::avm1::AvmString;
+use crate::avm2::{
+    Activation as Avm2Activation, Avm2, Error as Avm2Error, Event as Avm2Event,
+    EventData as Avm2EventData, Namespace, Object as Avm2Object, QName, TObject as _,
+    Value as Avm2Value,
+};
+use crate::context::UpdateContext;
+use crate::display_object::{DisplayObject, TDisplayObject, TDisplayObjectContainer};
+use crate::prelude::*;
+use gc_arena::Collect;
+
+/// An item of the context menu, as the host should display it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContextMenuItem {
+    /// The text of the item.
+    pub caption: String,
+
+    /// Whether the item can be chosen.
+    pub enabled: bool,
+
+    /// Whether the item is displayed with a check mark.
+    pub checked: bool,
+
+    /// Whether a separator is displayed above the item.
+    pub separator_before: bool,
+}
+
+/// What choosing an item of the context menu does.
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+pub enum ContextMenuCallback<'gc> {
+    /// Toggle whether the root movie is playing.
+    Play,
+
+    /// Go back to the first frame of the root movie, and stop there.
+    Rewind,
+
+    /// Step the root movie forward by a frame.
+    Forward,
+
+    /// Step the root movie back by a frame.
+    Back,
+
+    /// Dispatch `menuItemSelect` on an ActionScript 3 `ContextMenuItem`.
+    Avm2 {
+        item: Avm2Object<'gc>,
+        mouse_target: Option<Avm2Object<'gc>>,
+        context_menu_owner: Option<Avm2Object<'gc>>,
+    },
+}
+
+/// The context menu the host is showing, along with what each of its items
+/// does.
+#[derive(Collect, Default)]
+#[collect(no_drop)]
+pub struct ContextMenuState<'gc> {
+    #[collect(require_static)]
+    info: Vec<ContextMenuItem>,
+    callbacks: Vec<ContextMenuCallback<'gc>>,
+}
+
+impl<'gc> ContextMenuState<'gc> {
+    /// Construct an empty context menu.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an item to the bottom of the menu.
+    pub fn push(&mut self, item: ContextMenuItem, callback: ContextMenuCallback<'gc>) {
+        self.info.push(item);
+        self.callbacks.push(callback);
+    }
+
+    /// The items of the menu, from top to bottom.
+    pub fn info(&self) -> &[ContextMenuItem] {
+        &self.info
+    }
+
+    /// What choosing the item at `index` does.
+    ///
+    /// Disabled items, and indices past the end of the menu, do nothing.
+    pub fn callback(&self, index: usize) -> Option<&ContextMenuCallback<'gc>> {
+        match self.info.get(index) {
+            Some(item) if item.enabled => self.callbacks.get(index),
+            _ => None,
+        }
+    }
+}
+
+/// Find the topmost visible display object drawn at `point`.
+fn object_under_point<'gc>(
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    object: DisplayObject<'gc>,
+    point: (Twips, Twips),
+) -> Option<DisplayObject<'gc>> {
+    if !object.visible() {
+        return None;
+    }
+
+    if let Some(container) = object.as_container() {
+        for child in container.iter_render_list().rev() {
+            if let Some(hit) = object_under_point(context, child, point) {
+                return Some(hit);
+            }
+        }
+    }
+
+    if object.hit_test_shape(context, point) {
+        Some(object)
+    } else {
+        None
+    }
+}
+
+/// The ActionScript 3 object of a display object, if it has one.
+fn avm2_object(object: DisplayObject<'_>) -> Option<Avm2Object<'_>> {
+    match object.object2() {
+        Avm2Value::Object(object) => Some(object),
+        _ => None,
+    }
+}
+
+/// Read a public property of an ActionScript 3 object.
+fn get<'gc>(
+    activation: &mut Avm2Activation<'_, 'gc, '_>,
+    mut object: Avm2Object<'gc>,
+    name: &'static str,
+) -> Result<Avm2Value<'gc>, Avm2Error> {
+    object.get_property(object, &QName::new(Namespace::public(), name), activation)
+}
+
+/// Build the context menu for a right-click at the current mouse position.
+///
+/// The menu belongs to the object under the mouse, or to the closest of its
+/// ancestors that has a `contextMenu`. `menuSelect` is dispatched on that
+/// menu before its items are read, so that content can update them.
+pub fn build_context_menu<'gc>(
+    context: &mut UpdateContext<'_, 'gc, '_>,
+) -> Result<ContextMenuState<'gc>, Avm2Error> {
+    let mut state = ContextMenuState::new();
+    let root = match context.levels.get(&0) {
+        Some(root) => *root,
+        None => return Ok(state),
+    };
+
+    let point = *context.mouse_position;
+    let mouse_target = object_under_point(context, root, point).unwrap_or(root);
+    let mut owner = Some(mouse_target);
+    while let Some(object) = owner {
+        if object.context_menu().is_some() {
+            break;
+        }
+
+        owner = object.parent();
+    }
+
+    let mut show_play = true;
+    let mut show_rewind = true;
+    let mut show_forward_and_back = true;
+
+    if let Some((owner, menu)) = owner.and_then(|owner| Some((owner, owner.context_menu()?))) {
+        let mouse_target = avm2_object(mouse_target);
+        let context_menu_owner = avm2_object(owner);
+
+        let mut event = Avm2Event::new("menuSelect");
+        event.set_event_data(Avm2EventData::ContextMenu {
+            mouse_target,
+            context_menu_owner,
+        });
+        Avm2::dispatch_event(context, event, menu)?;
+
+        let mut activation = Avm2Activation::from_nothing(context.reborrow());
+        let mut custom_items =
+            get(&mut activation, menu, "customItems")?.coerce_to_object(&mut activation)?;
+        let length =
+            get(&mut activation, custom_items, "length")?.coerce_to_u32(&mut activation)?;
+
+        for i in 0..length {
+            let name = AvmString::new(activation.context.gc_context, i.to_string());
+            let item = match custom_items.get_property(
+                custom_items,
+                &QName::new(Namespace::public(), name),
+                &mut activation,
+            )? {
+                Avm2Value::Object(item) => item,
+                _ => continue,
+            };
+
+            if !get(&mut activation, item, "visible")?.coerce_to_boolean() {
+                continue;
+            }
+
+            let caption = get(&mut activation, item, "caption")?
+                .coerce_to_string(&mut activation)?
+                .to_string();
+            let enabled = get(&mut activation, item, "enabled")?.coerce_to_boolean();
+            let separator_before =
+                get(&mut activation, item, "separatorBefore")?.coerce_to_boolean();
+
+            state.push(
+                ContextMenuItem {
+                    caption,
+                    enabled,
+                    checked: false,
+                    separator_before,
+                },
+                ContextMenuCallback::Avm2 {
+                    item,
+                    mouse_target,
+                    context_menu_owner,
+                },
+            );
+        }
+
+        let built_in_items =
+            get(&mut activation, menu, "builtInItems")?.coerce_to_object(&mut activation)?;
+        show_play = get(&mut activation, built_in_items, "play")?.coerce_to_boolean();
+        show_rewind = get(&mut activation, built_in_items, "rewind")?.coerce_to_boolean();
+        show_forward_and_back =
+            get(&mut activation, built_in_items, "forwardAndBack")?.coerce_to_boolean();
+    }
+
+    // Flash Player only offers to control the playback of movies that have
+    // more than one frame, and only if content hasn't hidden its own items
+    // with `Stage.showDefaultContextMenu`.
+    let clip = match root.as_movie_clip() {
+        Some(clip) if clip.total_frames() > 1 && context.avm2.show_default_context_menu() => clip,
+        _ => return Ok(state),
+    };
+    let mut separator_before = !state.info().is_empty();
+    let mut push_built_in = |caption: &str, enabled: bool, checked: bool, callback| {
+        state.push(
+            ContextMenuItem {
+                caption: caption.to_string(),
+                enabled,
+                checked,
+                separator_before,
+            },
+            callback,
+        );
+        separator_before = false;
+    };
+
+    if show_play {
+        push_built_in("Play", true, clip.playing(), ContextMenuCallback::Play);
+    }
+    if show_rewind {
+        push_built_in(
+            "Rewind",
+            clip.current_frame() > 1,
+            false,
+            ContextMenuCallback::Rewind,
+        );
+    }
+    if show_forward_and_back {
+        push_built_in(
+            "Forward",
+            clip.current_frame() < clip.total_frames(),
+            false,
+            ContextMenuCallback::Forward,
+        );
+        push_built_in(
+            "Back",
+            clip.current_frame() > 1,
+            false,
+            ContextMenuCallback::Back,
+        );
+    }
+
+    Ok(state)
+}
+
+/// Perform the action of a chosen context menu item.
+pub fn run_context_menu_callback<'gc>(
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    callback: ContextMenuCallback<'gc>,
+) -> Result<(), Avm2Error> {
+    if let ContextMenuCallback::Avm2 {
+        item,
+        mouse_target,
+        context_menu_owner,
+    } = callback
+    {
+        let mut event = Avm2Event::new("menuItemSelect");
+        event.set_event_data(Avm2EventData::ContextMenu {
+            mouse_target,
+            context_menu_owner,
+        });
+        Avm2::dispatch_event(context, event, item)?;
+
+        return Ok(());
+    }
+
+    let clip = match context.levels.get(&0).and_then(|root| root.as_movie_clip()) {
+        Some(clip) => clip,
+        None => return Ok(()),
+    };
+
+    match callback {
+        ContextMenuCallback::Play if clip.playing() => clip.stop(context),
+        ContextMenuCallback::Play => clip.play(context),
+        ContextMenuCallback::Rewind => clip.goto_frame(context, 1, true),
+        ContextMenuCallback::Forward => clip.next_frame(context),
+        ContextMenuCallback::Back => clip.prev_frame(context),
+        ContextMenuCallback::Avm2 { .. } => {}
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::test_utils::{self, call, construct, function, TestPlayer, TestUi};
+    use crate::tag_utils::SwfMovie;
+    use std::sync::Arc;
+
+    /// The items of the last context menu `player` showed.
+    fn shown_items(player: &TestPlayer) -> Vec<ContextMenuItem> {
+        player
+            .lock()
+            .ui()
+            .downcast_ref::<TestUi>()
+            .unwrap()
+            .menu_items
+            .clone()
+    }
+
+    /// A listener that traces the type of each event it gets.
+    fn trace_type<'gc>(
+        activation: &mut Avm2Activation<'_, 'gc, '_>,
+        _this: Option<Avm2Object<'gc>>,
+        args: &[Avm2Value<'gc>],
+    ) -> Result<Avm2Value<'gc>, Avm2Error> {
+        let event = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Avm2Value::Undefined)
+            .coerce_to_object(activation)?;
+        let event_type = get(activation, event, "type")?.coerce_to_string(activation)?;
+
+        activation.context.log.avm_trace(&event_type);
+
+        Ok(Avm2Value::Undefined)
+    }
+
+    /// Put `item` first in the custom items of `menu`.
+    fn add_custom_item<'gc>(
+        activation: &mut Avm2Activation<'_, 'gc, '_>,
+        menu: Avm2Object<'gc>,
+        item: Avm2Object<'gc>,
+    ) {
+        let mut custom_items = test_utils::get(activation, menu, "customItems")
+            .coerce_to_object(activation)
+            .unwrap();
+        custom_items
+            .set_property(
+                custom_items,
+                &QName::new(Namespace::public(), "0"),
+                item.into(),
+                activation,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn custom_item_selection_dispatches_menu_item_select() {
+        let player = TestPlayer::with_ui(Box::new(TestUi::default()));
+
+        player.update(|activation| {
+            let menu = construct(activation, "flash.ui", "ContextMenu", &[]);
+            call(activation, menu, "hideBuiltInItems", &[]);
+            let built_in_items = test_utils::get(activation, menu, "builtInItems")
+                .coerce_to_object(activation)
+                .unwrap();
+            assert_eq!(
+                test_utils::get(activation, built_in_items, "play"),
+                false.into()
+            );
+
+            let item = construct(
+                activation,
+                "flash.ui",
+                "ContextMenuItem",
+                &["Custom".into()],
+            );
+            let listener = function(activation, trace_type);
+            call(
+                activation,
+                item,
+                "addEventListener",
+                &["menuItemSelect".into(), listener.into()],
+            );
+            add_custom_item(activation, menu, item);
+
+            let root = *activation.context.levels.get(&0).unwrap();
+            root.set_context_menu(activation.context.gc_context, Some(menu));
+        });
+
+        player.lock().show_context_menu();
+        assert_eq!(
+            shown_items(&player),
+            vec![ContextMenuItem {
+                caption: "Custom".to_string(),
+                enabled: true,
+                checked: false,
+                separator_before: false,
+            }]
+        );
+        assert!(player.traces().is_empty());
+
+        player.lock().run_context_menu_callback(0);
+        assert_eq!(player.traces(), vec!["menuItemSelect".to_string()]);
+    }
+
+    #[test]
+    fn hiding_the_default_menu_keeps_custom_items() {
+        let player = TestPlayer::with_ui(Box::new(TestUi::default()));
+
+        let movie = SwfMovie::from_path(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/swfs/avm2/movieclip_currentlabels/test.swf"
+        ))
+        .unwrap();
+        player.lock().set_root_movie(Arc::new(movie));
+
+        let set_show_default_context_menu = |player: &TestPlayer, show: bool| {
+            player.update(|activation| {
+                let stage = activation.context.avm2.stage().unwrap();
+                test_utils::set(activation, stage, "showDefaultContextMenu", show.into());
+            });
+        };
+        let captions = |player: &TestPlayer| -> Vec<String> {
+            shown_items(player)
+                .into_iter()
+                .map(|item| item.caption)
+                .collect()
+        };
+
+        player.lock().show_context_menu();
+        assert_eq!(captions(&player), vec!["Play", "Rewind", "Forward", "Back"]);
+
+        set_show_default_context_menu(&player, false);
+        player.lock().show_context_menu();
+        assert!(shown_items(&player).is_empty());
+
+        player.update(|activation| {
+            let menu = construct(activation, "flash.ui", "ContextMenu", &[]);
+            let item = construct(
+                activation,
+                "flash.ui",
+                "ContextMenuItem",
+                &["Custom".into()],
+            );
+            add_custom_item(activation, menu, item);
+
+            let root = *activation.context.levels.get(&0).unwrap();
+            root.set_context_menu(activation.context.gc_context, Some(menu));
+        });
+
+        player.lock().show_context_menu();
+        assert_eq!(captions(&player), vec!["Custom"]);
+
+        // Showing the default menu again brings the player's items back.
+        set_show_default_context_menu(&player, true);
+        player.lock().show_context_menu();
+        assert_eq!(
+            captions(&player),
+            vec!["Custom", "Play", "Rewind", "Forward", "Back"]
+        );
+        assert!(shown_items(&player)[1].separator_before);
+    }
+}
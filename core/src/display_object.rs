@@ -1,7 +1,7 @@
 use crate::avm1::{
     Error as Avm1Error, Object as Avm1Object, TObject as Avm1TObject, Value as Avm1Value,
 };
-use crate::avm2::{TObject as Avm2TObject, Value as Avm2Value};
+use crate::avm2::{Object as Avm2Object, TObject as Avm2TObject, Value as Avm2Value};
 use crate::context::{RenderContext, UpdateContext};
 use crate::player::NEWEST_PLAYER_VERSION;
 use crate::prelude::*;
@@ -79,6 +79,44 @@ pub struct DisplayObjectBase<'gc> {
     /// The display object we are currently masking.
     maskee: Option<DisplayObject<'gc>>,
 
+    /// The RGB color of the solid backdrop drawn behind this object's
+    /// bounds, if any.
+    opaque_background: Option<u32>,
+
+    /// The center slice of this object's nine-slice scaling grid, in its
+    /// own coordinates, if any.
+    scale_9_grid: Option<BoundingBox>,
+
+    /// The 3D transform assigned to this object by ActionScript, if any.
+    ///
+    /// Only its 2D projection, which is kept in `transform.matrix`, is
+    /// rendered.
+    #[collect(require_static)]
+    matrix_3d: Option<[f64; 16]>,
+
+    /// The components of the last `Matrix` assigned to `transform.matrix`
+    /// by ActionScript, before they were rounded to fit this object's own
+    /// matrix.
+    #[collect(require_static)]
+    script_matrix: Option<[f64; 6]>,
+
+    /// The perspective that 3D transforms of this object and its children
+    /// are projected with, as its field of view in degrees followed by the
+    /// X and Y of its projection center.
+    #[collect(require_static)]
+    perspective_projection: Option<[f64; 3]>,
+
+    /// The `ContextMenu` shown when this object is right-clicked, if any.
+    context_menu: Option<Avm2Object<'gc>>,
+
+    /// Whether a focus rectangle is drawn around this object while it has
+    /// focus. `None` defers to the stage's `stageFocusRect`.
+    focus_rect: Option<bool>,
+
+    /// Where this object comes in the tab order, if it was given a place
+    /// rather than following the display list.
+    tab_index: Option<u32>,
+
     /// Bit flags for various display object properites.
     flags: DisplayObjectFlags,
 }
@@ -100,6 +138,14 @@ impl<'gc> Default for DisplayObjectBase<'gc> {
             next_sibling: None,
             masker: None,
             maskee: None,
+            opaque_background: None,
+            scale_9_grid: None,
+            matrix_3d: None,
+            script_matrix: None,
+            perspective_projection: None,
+            context_menu: None,
+            focus_rect: None,
+            tab_index: None,
             sound_transform: Default::default(),
             flags: DisplayObjectFlags::VISIBLE,
         }
@@ -385,6 +431,81 @@ impl<'gc> DisplayObjectBase<'gc> {
         }
     }
 
+    fn tab_children(&self) -> bool {
+        !self
+            .flags
+            .contains(DisplayObjectFlags::TAB_CHILDREN_DISABLED)
+    }
+
+    fn set_tab_children(&mut self, value: bool) {
+        self.flags
+            .set(DisplayObjectFlags::TAB_CHILDREN_DISABLED, !value);
+    }
+
+    fn opaque_background(&self) -> Option<u32> {
+        self.opaque_background
+    }
+
+    fn set_opaque_background(&mut self, color: Option<u32>) {
+        self.opaque_background = color;
+    }
+
+    fn scale_9_grid(&self) -> Option<BoundingBox> {
+        self.scale_9_grid.clone()
+    }
+
+    fn set_scale_9_grid(&mut self, grid: Option<BoundingBox>) {
+        self.scale_9_grid = grid;
+    }
+
+    fn matrix_3d(&self) -> Option<[f64; 16]> {
+        self.matrix_3d
+    }
+
+    fn set_matrix_3d(&mut self, matrix: Option<[f64; 16]>) {
+        self.matrix_3d = matrix;
+    }
+
+    fn perspective_projection(&self) -> Option<[f64; 3]> {
+        self.perspective_projection
+    }
+
+    fn set_perspective_projection(&mut self, projection: Option<[f64; 3]>) {
+        self.perspective_projection = projection;
+    }
+
+    fn script_matrix(&self) -> Option<[f64; 6]> {
+        self.script_matrix
+    }
+
+    fn set_script_matrix(&mut self, components: Option<[f64; 6]>) {
+        self.script_matrix = components;
+    }
+
+    fn context_menu(&self) -> Option<Avm2Object<'gc>> {
+        self.context_menu
+    }
+
+    fn set_context_menu(&mut self, menu: Option<Avm2Object<'gc>>) {
+        self.context_menu = menu;
+    }
+
+    fn focus_rect(&self) -> Option<bool> {
+        self.focus_rect
+    }
+
+    fn set_focus_rect(&mut self, value: Option<bool>) {
+        self.focus_rect = value;
+    }
+
+    fn tab_index(&self) -> Option<u32> {
+        self.tab_index
+    }
+
+    fn set_tab_index(&mut self, value: Option<u32>) {
+        self.tab_index = value;
+    }
+
     fn lock_root(&self) -> bool {
         self.flags.contains(DisplayObjectFlags::LOCK_ROOT)
     }
@@ -807,6 +928,13 @@ pub trait TDisplayObject<'gc>:
     /// Returned by the `_visible`/`visible` ActionScript properties.
     fn set_visible(&self, context: MutationContext<'gc, '_>, value: bool);
 
+    /// Whether Tab can move focus into this object's children.
+    /// Returned by the `tabChildren` ActionScript property.
+    fn tab_children(&self) -> bool;
+
+    /// Sets whether Tab can move focus into this object's children.
+    fn set_tab_children(&self, context: MutationContext<'gc, '_>, value: bool);
+
     /// The sound transform for sounds played inside this display object.
     fn sound_transform(&self) -> Ref<SoundTransform>;
 
@@ -817,6 +945,80 @@ pub trait TDisplayObject<'gc>:
         sound_transform: SoundTransform,
     );
 
+    /// The RGB color of the backdrop drawn behind this display object, if any.
+    /// Returned by the `opaqueBackground` ActionScript property.
+    fn opaque_background(&self) -> Option<u32>;
+
+    /// Sets the RGB color of the backdrop drawn behind this display object.
+    /// `None` removes the backdrop.
+    fn set_opaque_background(&self, context: MutationContext<'gc, '_>, color: Option<u32>);
+
+    /// The center slice of this display object's nine-slice scaling grid, in
+    /// its own coordinates. Returned by the `scale9Grid` ActionScript property.
+    fn scale_9_grid(&self) -> Option<BoundingBox>;
+
+    /// Sets the nine-slice scaling grid of this display object.
+    /// `None` makes it scale normally.
+    fn set_scale_9_grid(&self, context: MutationContext<'gc, '_>, grid: Option<BoundingBox>);
+
+    /// The 3D transform of this display object, as the column-major values
+    /// of a `Matrix3D`. `None` if the object only has a 2D transform.
+    /// Returned by the `transform.matrix3D` ActionScript property.
+    fn matrix_3d(&self) -> Option<[f64; 16]>;
+
+    /// Sets the 3D transform of this display object. This does not touch the
+    /// 2D matrix, which callers should set to the projection of the 3D one.
+    /// `None` makes the object purely 2D again.
+    fn set_matrix_3d(&self, context: MutationContext<'gc, '_>, matrix: Option<[f64; 16]>);
+
+    /// The perspective this display object projects its own and its
+    /// children's 3D transforms with, as its field of view in degrees and
+    /// the X and Y of its projection center. `None` if it has none of its
+    /// own. Returned by the `transform.perspectiveProjection` ActionScript
+    /// property.
+    fn perspective_projection(&self) -> Option<[f64; 3]>;
+
+    /// Sets the perspective of this display object. This does not reproject
+    /// any 3D transforms.
+    fn set_perspective_projection(
+        &self,
+        context: MutationContext<'gc, '_>,
+        projection: Option<[f64; 3]>,
+    );
+
+    /// The exact components last assigned to `transform.matrix` by
+    /// ActionScript, in `Matrix` constructor order. These may no longer
+    /// describe the 2D matrix if it has been changed since.
+    fn script_matrix(&self) -> Option<[f64; 6]>;
+
+    /// Sets the exact components of a matrix assigned by ActionScript. This
+    /// does not touch the 2D matrix, which callers should set as well.
+    fn set_script_matrix(&self, context: MutationContext<'gc, '_>, components: Option<[f64; 6]>);
+
+    /// The `ContextMenu` assigned to this display object, if any.
+    /// Returned by the `contextMenu` ActionScript property.
+    fn context_menu(&self) -> Option<Avm2Object<'gc>>;
+
+    /// Sets the `ContextMenu` shown when this display object is
+    /// right-clicked. `None` falls back to the menu of its ancestors.
+    fn set_context_menu(&self, context: MutationContext<'gc, '_>, menu: Option<Avm2Object<'gc>>);
+
+    /// Whether a focus rectangle is drawn around this display object while
+    /// it has focus, or `None` to defer to the stage.
+    /// Returned by the `focusRect` ActionScript property.
+    fn focus_rect(&self) -> Option<bool>;
+
+    /// Sets whether a focus rectangle is drawn around this display object.
+    fn set_focus_rect(&self, context: MutationContext<'gc, '_>, value: Option<bool>);
+
+    /// This display object's explicit place in the tab order, if any.
+    /// Returned by the `tabIndex` ActionScript property.
+    fn tab_index(&self) -> Option<u32>;
+
+    /// Sets this display object's place in the tab order, or `None` to
+    /// follow the display list.
+    fn set_tab_index(&self, context: MutationContext<'gc, '_>, value: Option<u32>);
+
     /// Whether this display object is used as the _root of itself and its children.
     /// Returned by the `_lockroot` ActionScript property.
     fn lock_root(&self) -> bool;
@@ -896,6 +1098,22 @@ pub trait TDisplayObject<'gc>:
             context.allow_mask = true;
             context.renderer.activate_mask();
         }
+        if let Some(color) = self.opaque_background() {
+            let bounds = self.bounds();
+            if bounds.valid {
+                let backdrop = Matrix::create_box(
+                    (bounds.x_max - bounds.x_min).to_pixels() as f32,
+                    (bounds.y_max - bounds.y_min).to_pixels() as f32,
+                    0.0,
+                    bounds.x_min,
+                    bounds.y_min,
+                );
+                context.renderer.draw_rect(
+                    Color::from_rgb(color, 0xFF),
+                    &(context.transform_stack.transform().matrix * backdrop),
+                );
+            }
+        }
         self.render_self(context);
         if let Some(m) = mask {
             context.renderer.deactivate_mask();
@@ -1134,6 +1352,21 @@ pub trait TDisplayObject<'gc>:
         })
     }
 
+    /// Determine if this display object is currently part of the display list
+    /// of any level, and thus on the stage.
+    fn is_on_stage(&self, context: &UpdateContext<'_, 'gc, '_>) -> bool {
+        let mut ancestor: DisplayObject<'gc> = (*self).into();
+
+        while let Some(parent) = ancestor.parent() {
+            ancestor = parent;
+        }
+
+        context
+            .levels
+            .values()
+            .any(|level| DisplayObject::ptr_eq(*level, ancestor))
+    }
+
     /// Obtain the top-most parent of the display tree hierarchy, or some kind
     /// of an error.
     ///
@@ -1360,6 +1593,87 @@ macro_rules! impl_display_object_sansbounds {
         fn set_visible(&self, context: gc_arena::MutationContext<'gc, '_>, value: bool) {
             self.0.write(context).$field.set_visible(value);
         }
+        fn tab_children(&self) -> bool {
+            self.0.read().$field.tab_children()
+        }
+        fn set_tab_children(&self, context: gc_arena::MutationContext<'gc, '_>, value: bool) {
+            self.0.write(context).$field.set_tab_children(value);
+        }
+        fn opaque_background(&self) -> Option<u32> {
+            self.0.read().$field.opaque_background()
+        }
+        fn set_opaque_background(
+            &self,
+            context: gc_arena::MutationContext<'gc, '_>,
+            color: Option<u32>,
+        ) {
+            self.0.write(context).$field.set_opaque_background(color);
+        }
+        fn scale_9_grid(&self) -> Option<crate::bounding_box::BoundingBox> {
+            self.0.read().$field.scale_9_grid()
+        }
+        fn set_scale_9_grid(
+            &self,
+            context: gc_arena::MutationContext<'gc, '_>,
+            grid: Option<crate::bounding_box::BoundingBox>,
+        ) {
+            self.0.write(context).$field.set_scale_9_grid(grid);
+        }
+        fn matrix_3d(&self) -> Option<[f64; 16]> {
+            self.0.read().$field.matrix_3d()
+        }
+        fn set_matrix_3d(
+            &self,
+            context: gc_arena::MutationContext<'gc, '_>,
+            matrix: Option<[f64; 16]>,
+        ) {
+            self.0.write(context).$field.set_matrix_3d(matrix);
+        }
+        fn perspective_projection(&self) -> Option<[f64; 3]> {
+            self.0.read().$field.perspective_projection()
+        }
+        fn set_perspective_projection(
+            &self,
+            context: gc_arena::MutationContext<'gc, '_>,
+            projection: Option<[f64; 3]>,
+        ) {
+            self.0
+                .write(context)
+                .$field
+                .set_perspective_projection(projection);
+        }
+        fn script_matrix(&self) -> Option<[f64; 6]> {
+            self.0.read().$field.script_matrix()
+        }
+        fn set_script_matrix(
+            &self,
+            context: gc_arena::MutationContext<'gc, '_>,
+            components: Option<[f64; 6]>,
+        ) {
+            self.0.write(context).$field.set_script_matrix(components);
+        }
+        fn context_menu(&self) -> Option<crate::avm2::Object<'gc>> {
+            self.0.read().$field.context_menu()
+        }
+        fn set_context_menu(
+            &self,
+            context: gc_arena::MutationContext<'gc, '_>,
+            menu: Option<crate::avm2::Object<'gc>>,
+        ) {
+            self.0.write(context).$field.set_context_menu(menu);
+        }
+        fn focus_rect(&self) -> Option<bool> {
+            self.0.read().$field.focus_rect()
+        }
+        fn set_focus_rect(&self, context: gc_arena::MutationContext<'gc, '_>, value: Option<bool>) {
+            self.0.write(context).$field.set_focus_rect(value);
+        }
+        fn tab_index(&self) -> Option<u32> {
+            self.0.read().$field.tab_index()
+        }
+        fn set_tab_index(&self, context: gc_arena::MutationContext<'gc, '_>, value: Option<u32>) {
+            self.0.write(context).$field.set_tab_index(value);
+        }
         fn lock_root(&self) -> bool {
             self.0.read().$field.lock_root()
         }
@@ -1474,6 +1788,10 @@ bitflags! {
         /// Whether this object has `_lockroot` set to true, in which case
         /// it becomes the _root of itself and of any children
         const LOCK_ROOT                = 1 << 6;
+
+        /// Whether Tab skips over the children of this object
+        /// (`tabChildren` set to false).
+        const TAB_CHILDREN_DISABLED    = 1 << 7;
     }
 }
 
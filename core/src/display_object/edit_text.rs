@@ -2,6 +2,11 @@
 
 use crate::avm1::activation::{Activation, ActivationIdentifier};
 use crate::avm1::{Avm1, AvmString, Object, StageObject, TObject, Value};
+use crate::avm2::{
+    Activation as Avm2Activation, Avm2, Event as Avm2Event, EventData as Avm2EventData,
+    Namespace as Avm2Namespace, Object as Avm2Object, QName as Avm2QName,
+    StageObject as Avm2StageObject, TObject as Avm2TObject, Value as Avm2Value,
+};
 use crate::backend::ui::MouseCursor;
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::{DisplayObjectBase, TDisplayObject};
@@ -15,7 +20,7 @@ use crate::string_utils;
 use crate::tag_utils::SwfMovie;
 use crate::transform::Transform;
 use crate::types::{Degrees, Percent};
-use crate::vminterface::Instantiator;
+use crate::vminterface::{AvmObject, AvmType, Instantiator};
 use crate::xml::XmlDocument;
 use chrono::Utc;
 use gc_arena::{Collect, Gc, GcCell, MutationContext};
@@ -125,8 +130,9 @@ pub struct EditTextData<'gc> {
     /// The current intrinsic bounds of the text field.
     bounds: BoundingBox,
 
-    /// The AVM1 object handle
-    object: Option<Object<'gc>>,
+    /// The AVM1 or AVM2 object handle, depending on the movie that owns
+    /// this text field.
+    object: Option<AvmObject<'gc>>,
 
     /// The variable path that this text field is bound to (AVM1 only).
     variable: Option<String>,
@@ -327,7 +333,7 @@ impl<'gc> EditText<'gc> {
             has_border: false,
             was_static: false,
             is_html: false,
-            is_device_font: false,
+            is_device_font: true,
         };
 
         let text_field = Self::from_swf_tag(context, swf_movie, swf_tag);
@@ -391,13 +397,9 @@ impl<'gc> EditText<'gc> {
             let html_string = text.replace("<sbr>", "\n").replace("<br>", "\n");
             let document = XmlDocument::new(context.gc_context);
 
-            if let Err(err) =
-                document
-                    .as_node()
-                    .replace_with_str(context.gc_context, &html_string, false, false)
-            {
-                log::warn!("Parsing error when setting TextField.htmlText: {}", err);
-            }
+            document
+                .as_node()
+                .replace_with_html_str(context.gc_context, &html_string);
 
             self.set_html_tree(document, context);
         } else if let Err(err) = self.set_text(text, context) {
@@ -821,6 +823,8 @@ impl<'gc> EditText<'gc> {
 
         let edit_text = self.0.read();
         let selection = edit_text.selection;
+        let snaps_to_pixels =
+            edit_text.render_settings.snaps_to_pixels() && !edit_text.is_device_font;
 
         let caret = if let LayoutContent::Text { start, end, .. } = &lbox.content() {
             if let Some(selection) = selection {
@@ -897,10 +901,21 @@ impl<'gc> EditText<'gc> {
                         }
                     }
 
-                    // Render glyph.
-                    context
-                        .renderer
-                        .render_shape(glyph.shape_handle, context.transform_stack.transform());
+                    // Render glyph. Pixel grid fitting only moves the glyph's
+                    // origin onto a whole pixel of the stage.
+                    if snaps_to_pixels {
+                        let mut glyph_transform = context.transform_stack.transform().clone();
+                        let matrix = &mut glyph_transform.matrix;
+                        matrix.tx = Twips::from_pixels(matrix.tx.to_pixels().round());
+                        matrix.ty = Twips::from_pixels(matrix.ty.to_pixels().round());
+                        context
+                            .renderer
+                            .render_shape(glyph.shape_handle, &glyph_transform);
+                    } else {
+                        context
+                            .renderer
+                            .render_shape(glyph.shape_handle, context.transform_stack.transform());
+                    }
                     context.transform_stack.pop();
 
                     if let Some((caret_pos, length)) = caret {
@@ -1088,6 +1103,10 @@ impl<'gc> EditText<'gc> {
         }
     }
 
+    pub fn render_settings(self) -> TextRenderSettings {
+        self.0.read().render_settings.clone()
+    }
+
     pub fn set_render_settings(
         self,
         gc_context: MutationContext<'gc, '_>,
@@ -1143,6 +1162,67 @@ impl<'gc> EditText<'gc> {
         None
     }
 
+    /// Dispatch a `TextEvent` of the given type on this text field's AVM2
+    /// object, if it has one.
+    ///
+    /// Returns `false` if a listener cancelled the event.
+    fn dispatch_text_event(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        event_type: &'static str,
+        text: &str,
+        cancelable: bool,
+    ) -> bool {
+        if let Avm2Value::Object(target) = self.object2() {
+            let mut event = Avm2Event::new(event_type);
+            event.set_bubbles(true);
+            event.set_cancelable(cancelable);
+            event.set_event_data(Avm2EventData::Text {
+                text: AvmString::new(context.gc_context, text.to_string()),
+            });
+
+            match Avm2::dispatch_event(context, event, target) {
+                Ok(not_cancelled) => return not_cancelled,
+                Err(e) => log::error!("Got {} when dispatching {} event", e, event_type),
+            }
+        }
+
+        true
+    }
+
+    /// Follow the hyperlink at a given text position, if there is one.
+    ///
+    /// Links using the `event:` scheme dispatch `TextEvent.LINK` with the
+    /// rest of the URL as the event's text; all other links navigate.
+    pub fn click_link(self, context: &mut UpdateContext<'_, 'gc, '_>, position: usize) {
+        let link = self
+            .0
+            .read()
+            .text_spans
+            .iter_spans()
+            .find(|(start, end, _text, _span)| *start <= position && position < *end)
+            .filter(|(_start, _end, _text, span)| !span.url.is_empty())
+            .map(|(_start, _end, _text, span)| (span.url.clone(), span.target.clone()));
+
+        if let Some((url, target)) = link {
+            if let Some(payload) = url
+                .get(..6)
+                .filter(|scheme| scheme.eq_ignore_ascii_case("event:"))
+                .and_then(|_| url.get(6..))
+            {
+                self.dispatch_text_event(context, "link", payload, false);
+            } else {
+                let window = if target.is_empty() {
+                    None
+                } else {
+                    Some(target)
+                };
+
+                context.navigator.navigate_to_url(url, window, None);
+            }
+        }
+    }
+
     pub fn text_input(self, character: char, context: &mut UpdateContext<'_, 'gc, '_>) {
         if !self.0.read().is_editable {
             return;
@@ -1186,6 +1266,11 @@ impl<'gc> EditText<'gc> {
                     }
                 }
                 code if !(code as char).is_control() => {
+                    if !self.dispatch_text_event(context, "textInput", &character.to_string(), true)
+                    {
+                        return;
+                    }
+
                     self.replace_text(
                         selection.start(),
                         selection.end(),
@@ -1220,7 +1305,7 @@ impl<'gc> EditText<'gc> {
 
     fn initialize_as_broadcaster(&self, activation: &mut Activation<'_, 'gc, '_>) {
         let write = self.0.write(activation.context.gc_context);
-        if let Some(object) = write.object {
+        if let Some(object) = write.object.and_then(|o| o.as_avm1_object().ok()) {
             activation.context.avm1.broadcaster_functions().initialize(
                 activation.context.gc_context,
                 object,
@@ -1243,7 +1328,7 @@ impl<'gc> EditText<'gc> {
     }
 
     fn on_changed(&self, activation: &mut Activation<'_, 'gc, '_>) {
-        let object = self.0.read().object;
+        let object = self.0.read().object.and_then(|o| o.as_avm1_object().ok());
         if let Some(object) = object {
             let _ = object.call_method(
                 "broadcastMessage",
@@ -1284,15 +1369,28 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
         self.set_default_instance_name(context);
 
         let mut text = self.0.write(context.gc_context);
+        let movie = text.static_data.swf.clone();
+        let vm_type = context.library.library_for_movie_mut(movie).avm_type();
+        let mut avm2_object = None;
         if text.object.is_none() {
-            let object = StageObject::for_display_object(
-                context.gc_context,
-                display_object,
-                Some(context.avm1.prototypes().text_field),
-            )
-            .into();
-
-            text.object = Some(object);
+            if vm_type == AvmType::Avm2 {
+                let object: Avm2Object<'gc> = Avm2StageObject::for_display_object(
+                    context.gc_context,
+                    display_object,
+                    context.avm2.prototypes().text_field,
+                )
+                .into();
+                text.object = Some(object.into());
+                avm2_object = Some(object);
+            } else {
+                let object: Object<'gc> = StageObject::for_display_object(
+                    context.gc_context,
+                    display_object,
+                    Some(context.avm1.prototypes().text_field),
+                )
+                .into();
+                text.object = Some(object.into());
+            }
         }
 
         text.document = text
@@ -1307,21 +1405,45 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
         }
         drop(text);
 
-        Avm1::run_with_stack_frame_for_display_object(
-            (*self).into(),
-            context.swf.version(),
-            context,
-            |activation| {
-                // If this text field has a variable set, initialize text field binding.
-                if !self.try_bind_text_field_variable(activation, true) {
-                    activation.context.unbound_text_fields.push(*self);
-                }
-                // People can bind to properties of TextFields the same as other display objects.
-                self.bind_text_field_variables(activation);
+        // The AVM2 object still has to run its constructor, so that it can
+        // take event listeners.
+        if let Some(object) = avm2_object {
+            let mut activation = Avm2Activation::from_nothing(context.reborrow());
+            let mut proto = activation.context.avm2.prototypes().text_field;
+            let result = proto
+                .get_property(
+                    proto,
+                    &Avm2QName::new(Avm2Namespace::public(), "constructor"),
+                    &mut activation,
+                )
+                .and_then(|constructor| constructor.coerce_to_object(&mut activation))
+                .and_then(|constructor| {
+                    constructor.call(Some(object), &[], &mut activation, Some(proto))
+                });
+
+            if let Err(e) = result {
+                log::error!("Got {} when constructing AVM2 side of text field", e);
+            }
+        }
 
-                self.initialize_as_broadcaster(activation);
-            },
-        );
+        // Variable bindings and listeners only exist in AVM1.
+        if vm_type == AvmType::Avm1 {
+            Avm1::run_with_stack_frame_for_display_object(
+                (*self).into(),
+                context.swf.version(),
+                context,
+                |activation| {
+                    // If this text field has a variable set, initialize text field binding.
+                    if !self.try_bind_text_field_variable(activation, true) {
+                        activation.context.unbound_text_fields.push(*self);
+                    }
+                    // People can bind to properties of TextFields the same as other display objects.
+                    self.bind_text_field_variables(activation);
+
+                    self.initialize_as_broadcaster(activation);
+                },
+            );
+        }
 
         if run_frame {
             self.run_frame(context);
@@ -1332,10 +1454,20 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
         self.0
             .read()
             .object
+            .and_then(|o| o.as_avm1_object().ok())
             .map(Value::from)
             .unwrap_or(Value::Undefined)
     }
 
+    fn object2(&self) -> Avm2Value<'gc> {
+        self.0
+            .read()
+            .object
+            .and_then(|o| o.as_avm2_object().ok())
+            .map(Avm2Value::from)
+            .unwrap_or(Avm2Value::Undefined)
+    }
+
     fn self_bounds(&self) -> BoundingBox {
         self.0.read().bounds.clone()
     }
@@ -1560,11 +1692,10 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
             ClipEvent::Press => {
                 let tracker = context.focus_tracker;
                 tracker.set(Some((*self).into()), context);
-                if let Some(position) = self
-                    .screen_position_to_index(*context.mouse_position)
-                    .map(TextSelection::for_position)
-                {
-                    self.0.write(context.gc_context).selection = Some(position);
+                if let Some(index) = self.screen_position_to_index(*context.mouse_position) {
+                    self.0.write(context.gc_context).selection =
+                        Some(TextSelection::for_position(index));
+                    self.click_link(context, index);
                 } else {
                     self.0.write(context.gc_context).selection =
                         Some(TextSelection::for_position(self.text_length()));
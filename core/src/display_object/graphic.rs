@@ -1,10 +1,13 @@
 use crate::backend::render::ShapeHandle;
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::{DisplayObjectBase, TDisplayObject};
+use crate::drawing::Scale9Shape;
 use crate::prelude::*;
+use crate::shape_utils;
 use crate::tag_utils::SwfMovie;
 use crate::types::{Degrees, Percent};
 use gc_arena::{Collect, GcCell};
+use std::cell::RefCell;
 use std::sync::Arc;
 
 #[derive(Clone, Debug, Collect, Copy)]
@@ -16,6 +19,10 @@ pub struct Graphic<'gc>(GcCell<'gc, GraphicData<'gc>>);
 pub struct GraphicData<'gc> {
     base: DisplayObjectBase<'gc>,
     static_data: gc_arena::Gc<'gc, GraphicStatic>,
+
+    /// The shape last drawn for this graphic's scaling grid.
+    #[collect(require_static)]
+    scale_9_shape: RefCell<Option<Scale9Shape>>,
 }
 
 impl<'gc> Graphic<'gc> {
@@ -24,7 +31,7 @@ impl<'gc> Graphic<'gc> {
         swf_shape: swf::Shape,
         movie: Arc<SwfMovie>,
     ) -> Self {
-        let library = context.library.library_for_movie(movie);
+        let library = context.library.library_for_movie(movie.clone());
         let static_data = GraphicStatic {
             id: swf_shape.id,
             bounds: swf_shape.shape_bounds.clone().into(),
@@ -32,17 +39,52 @@ impl<'gc> Graphic<'gc> {
                 .renderer
                 .register_shape((&swf_shape).into(), library),
             shape: swf_shape,
+            movie,
         };
         Graphic(GcCell::allocate(
             context.gc_context,
             GraphicData {
                 base: Default::default(),
                 static_data: gc_arena::Gc::allocate(context.gc_context, static_data),
+                scale_9_shape: RefCell::new(None),
             },
         ))
     }
 }
 
+impl<'gc> Graphic<'gc> {
+    /// The shape to draw this graphic with under a scaling grid, warped for
+    /// the scale it is currently being drawn at.
+    fn scale_9_grid_handle(&self, context: &mut RenderContext, grid: BoundingBox) -> ShapeHandle {
+        let scale = shape_utils::matrix_scale(&context.transform_stack.transform().matrix);
+        let read = self.0.read();
+        let mut cache = read.scale_9_shape.borrow_mut();
+
+        match &*cache {
+            Some((handle, cached_scale, cached_grid))
+                if *cached_scale == scale && *cached_grid == grid =>
+            {
+                *handle
+            }
+            cached => {
+                let static_data = &read.static_data;
+                let shape =
+                    shape_utils::scale_9_grid_shape((&static_data.shape).into(), &grid, scale);
+                let library = context.library.library_for_movie(static_data.movie.clone());
+                let handle = if let Some((handle, _, _)) = cached {
+                    context.renderer.replace_shape(shape, library, *handle);
+                    *handle
+                } else {
+                    context.renderer.register_shape(shape, library)
+                };
+
+                *cache = Some((handle, scale, grid));
+                handle
+            }
+        }
+    }
+}
+
 impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
     impl_display_object!(base);
 
@@ -75,10 +117,14 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
             return;
         }
 
-        context.renderer.render_shape(
-            self.0.read().static_data.render_handle,
-            context.transform_stack.transform(),
-        );
+        let handle = match self.scale_9_grid() {
+            Some(grid) => self.scale_9_grid_handle(context, grid),
+            None => self.0.read().static_data.render_handle,
+        };
+
+        context
+            .renderer
+            .render_shape(handle, context.transform_stack.transform());
     }
 
     fn hit_test_shape(
@@ -107,4 +153,5 @@ struct GraphicStatic {
     shape: swf::Shape,
     render_handle: ShapeHandle,
     bounds: BoundingBox,
+    movie: Arc<SwfMovie>,
 }
@@ -1,9 +1,12 @@
+use crate::avm1::Object as Avm1Object;
+use crate::avm2::{Object as Avm2Object, StageObject as Avm2StageObject, Value as Avm2Value};
 use crate::backend::render::ShapeHandle;
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::{DisplayObjectBase, TDisplayObject};
 use crate::prelude::*;
 use crate::tag_utils::SwfMovie;
 use crate::types::{Degrees, Percent};
+use crate::vminterface::{AvmType, Instantiator};
 use gc_arena::{Collect, Gc, GcCell, MutationContext};
 use std::sync::Arc;
 use swf::Twips;
@@ -18,6 +21,9 @@ pub struct MorphShapeData<'gc> {
     base: DisplayObjectBase<'gc>,
     static_data: Gc<'gc, MorphShapeStatic>,
     ratio: u16,
+
+    /// The AVM2 object of a morph shape in an AVM2 movie.
+    object: Option<Avm2Object<'gc>>,
 }
 
 impl<'gc> MorphShape<'gc> {
@@ -31,6 +37,7 @@ impl<'gc> MorphShape<'gc> {
                 base: Default::default(),
                 static_data: Gc::allocate(gc_context, static_data),
                 ratio: 0,
+                object: None,
             },
         ))
     }
@@ -55,6 +62,38 @@ impl<'gc> TDisplayObject<'gc> for MorphShape<'gc> {
         Some(*self)
     }
 
+    fn post_instantiation(
+        &self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        display_object: DisplayObject<'gc>,
+        _init_object: Option<Avm1Object<'gc>>,
+        _instantiated_by: Instantiator,
+        run_frame: bool,
+    ) {
+        let movie = self.0.read().static_data.movie.clone();
+        let vm_type = context.library.library_for_movie_mut(movie).avm_type();
+        if vm_type == AvmType::Avm2 && self.0.read().object.is_none() {
+            let object = Avm2StageObject::for_display_object(
+                context.gc_context,
+                display_object,
+                context.avm2.prototypes().morph_shape,
+            );
+            self.0.write(context.gc_context).object = Some(object.into());
+        }
+
+        if run_frame {
+            self.run_frame(context);
+        }
+    }
+
+    fn object2(&self) -> Avm2Value<'gc> {
+        self.0
+            .read()
+            .object
+            .map(Avm2Value::from)
+            .unwrap_or(Avm2Value::Undefined)
+    }
+
     fn run_frame(&self, _context: &mut UpdateContext) {
         // Noop
     }
@@ -69,12 +108,30 @@ impl<'gc> TDisplayObject<'gc> for MorphShape<'gc> {
         }
     }
 
+    /// The bounds of the shape at the current ratio.
+    ///
+    /// Ratios that were never registered are interpolated between the
+    /// bounds of the start and end shapes instead.
     fn self_bounds(&self) -> BoundingBox {
-        // TODO: Use the bounds of the current ratio.
-        if let Some(frame) = self.0.read().static_data.frames.get(&self.ratio()) {
-            frame.bounds.clone()
-        } else {
-            BoundingBox::default()
+        let read = self.0.read();
+        let frames = &read.static_data.frames;
+        if let Some(frame) = frames.get(&read.ratio) {
+            return frame.bounds.clone();
+        }
+
+        match (frames.get(&0), frames.get(&65535)) {
+            (Some(start), Some(end)) if start.bounds.valid && end.bounds.valid => {
+                let b = f32::from(read.ratio) / 65535.0;
+                let a = 1.0 - b;
+                BoundingBox {
+                    x_min: lerp_twips(start.bounds.x_min, end.bounds.x_min, a, b),
+                    y_min: lerp_twips(start.bounds.y_min, end.bounds.y_min, a, b),
+                    x_max: lerp_twips(start.bounds.x_max, end.bounds.x_max, a, b),
+                    y_max: lerp_twips(start.bounds.y_max, end.bounds.y_max, a, b),
+                    valid: true,
+                }
+            }
+            _ => BoundingBox::default(),
         }
     }
 
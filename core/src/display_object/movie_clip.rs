@@ -28,6 +28,7 @@ use crate::tag_utils::{self, DecodeResult, SwfMovie, SwfSlice, SwfStream};
 use crate::types::{Degrees, Percent};
 use crate::vminterface::{AvmObject, AvmType, Instantiator};
 use gc_arena::{Collect, Gc, GcCell, MutationContext};
+use indexmap::IndexMap;
 use smallvec::SmallVec;
 use std::cell::{Ref, RefCell};
 use std::collections::HashMap;
@@ -68,6 +69,16 @@ pub struct MovieClipData<'gc> {
     has_focus: bool,
     enabled: bool,
     use_hand_cursor: bool,
+
+    /// The clip whose shape is hit-tested in place of this one's.
+    hit_area: Option<MovieClip<'gc>>,
+
+    /// Whether this clip is the `hit_area` of another clip, which makes it
+    /// ignore the mouse itself.
+    is_hit_area: bool,
+
+    /// The `Graphics` object that `graphics` hands out for this clip.
+    avm2_graphics: Option<Avm2Object<'gc>>,
 }
 
 impl<'gc> MovieClip<'gc> {
@@ -93,6 +104,9 @@ impl<'gc> MovieClip<'gc> {
                 has_focus: false,
                 enabled: true,
                 use_hand_cursor: true,
+                hit_area: None,
+                is_hit_area: false,
+                avm2_graphics: None,
             },
         ))
     }
@@ -126,6 +140,9 @@ impl<'gc> MovieClip<'gc> {
                 has_focus: false,
                 enabled: true,
                 use_hand_cursor: true,
+                hit_area: None,
+                is_hit_area: false,
+                avm2_graphics: None,
             },
         ))
     }
@@ -605,7 +622,6 @@ impl<'gc> MovieClip<'gc> {
         Ok(())
     }
 
-    #[allow(dead_code)]
     pub fn playing(self) -> bool {
         self.0.read().playing()
     }
@@ -797,6 +813,8 @@ impl<'gc> MovieClip<'gc> {
     }
 
     /// Yield the current frame label as a tuple of string and frame number.
+    ///
+    /// Of several labels on the same frame, the first in tag order wins.
     pub fn current_label(self) -> Option<(String, FrameNumber)> {
         let read = self.0.read();
         let current_frame = read.current_frame();
@@ -807,7 +825,7 @@ impl<'gc> MovieClip<'gc> {
                 continue;
             }
 
-            if best.map(|v| *frame >= v.1).unwrap_or(true) {
+            if best.map(|v| *frame > v.1).unwrap_or(true) {
                 best = Some((label, *frame));
             }
         }
@@ -815,6 +833,22 @@ impl<'gc> MovieClip<'gc> {
         best.map(|(s, fnum)| (s.to_string(), fnum))
     }
 
+    /// Return the label anchored exactly at the current frame, if any.
+    ///
+    /// Unlike `current_label`, this does not fall back to a label placed on
+    /// an earlier frame. Of several labels on the current frame, the first in
+    /// tag order wins.
+    pub fn current_frame_label(self) -> Option<String> {
+        let read = self.0.read();
+        let current_frame = read.current_frame();
+
+        read.static_data
+            .frame_labels
+            .iter()
+            .find(|(_label, frame)| **frame == current_frame)
+            .map(|(label, _frame)| label.to_string())
+    }
+
     /// Yield a list of labels and frame-numbers in the current scene.
     ///
     /// Labels are returned sorted by frame number.
@@ -978,11 +1012,25 @@ impl<'gc> MovieClip<'gc> {
         mc.drawing.set_line_style(style);
     }
 
+    pub fn set_line_fill_style(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        fill_style: FillStyle,
+    ) {
+        let mut mc = self.0.write(context.gc_context);
+        mc.drawing.set_line_fill_style(fill_style);
+    }
+
     pub fn draw_command(self, context: &mut UpdateContext<'_, 'gc, '_>, command: DrawCommand) {
         let mut mc = self.0.write(context.gc_context);
         mc.drawing.draw_command(command);
     }
 
+    /// The point that the next drawing command will start from.
+    pub fn drawing_cursor(self) -> (Twips, Twips) {
+        self.0.read().drawing.cursor()
+    }
+
     pub fn run_clip_event(
         self,
         context: &mut crate::context::UpdateContext<'_, 'gc, '_>,
@@ -1477,6 +1525,12 @@ impl<'gc> MovieClip<'gc> {
         }
     }
 
+    /// Associate this clip with an AVM2 object that was constructed for it
+    /// by native code, rather than by `post_instantiation`.
+    pub fn set_avm2_object(self, gc_context: MutationContext<'gc, '_>, object: Avm2Object<'gc>) {
+        self.0.write(gc_context).object = Some(object.into());
+    }
+
     pub fn register_frame_script(
         self,
         frame_id: FrameNumber,
@@ -1581,6 +1635,44 @@ impl<'gc> MovieClip<'gc> {
     ) {
         self.0.write(context.gc_context).use_hand_cursor = use_hand_cursor;
     }
+
+    /// The `Graphics` object that was handed out for this clip, if any.
+    pub fn avm2_graphics(self) -> Option<Avm2Object<'gc>> {
+        self.0.read().avm2_graphics
+    }
+
+    /// Keep `graphics` as the `Graphics` object handed out for this clip.
+    pub fn set_avm2_graphics(
+        self,
+        gc_context: MutationContext<'gc, '_>,
+        graphics: Avm2Object<'gc>,
+    ) {
+        self.0.write(gc_context).avm2_graphics = Some(graphics);
+    }
+
+    /// The clip that is hit-tested in place of this one, if any.
+    pub fn hit_area(self) -> Option<MovieClip<'gc>> {
+        self.0.read().hit_area
+    }
+
+    /// Designate a clip whose shape is hit-tested in place of this one.
+    ///
+    /// The hit area does not need to be on the display list. While it is
+    /// being used as one, it does not respond to the mouse itself.
+    pub fn set_hit_area(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        hit_area: Option<MovieClip<'gc>>,
+    ) {
+        let old_hit_area =
+            std::mem::replace(&mut self.0.write(context.gc_context).hit_area, hit_area);
+        if let Some(old_hit_area) = old_hit_area {
+            old_hit_area.0.write(context.gc_context).is_hit_area = false;
+        }
+        if let Some(hit_area) = hit_area {
+            hit_area.0.write(context.gc_context).is_hit_area = true;
+        }
+    }
 }
 
 impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
@@ -1630,7 +1722,14 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
     }
 
     fn render_self(&self, context: &mut RenderContext<'_, 'gc>) {
-        self.0.read().drawing.render(context);
+        match self.scale_9_grid() {
+            Some(grid) => self
+                .0
+                .read()
+                .drawing
+                .render_with_scale_9_grid(context, &grid),
+            None => self.0.read().drawing.render(context),
+        }
         self.render_children(context);
     }
 
@@ -1670,8 +1769,12 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
         self_node: DisplayObject<'gc>,
         point: (Twips, Twips),
     ) -> Option<DisplayObject<'gc>> {
-        if self.visible() {
-            if self.world_bounds().contains(point) {
+        if self.visible() && !self.0.read().is_hit_area {
+            let hit_area = self.hit_area();
+
+            // A hit area may lie anywhere, so only clips without one can be
+            // culled by their own bounds.
+            if hit_area.is_some() || self.world_bounds().contains(point) {
                 // This movieclip operates in "button mode" if it has a mouse handler,
                 // either via on(..) or via property mc.onRelease, etc.
                 let is_button_mode = {
@@ -1690,7 +1793,12 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
                     }
                 };
 
-                if is_button_mode && self.hit_test_shape(context, point) {
+                let is_hit = match hit_area {
+                    Some(hit_area) => hit_area.hit_test_shape(context, point),
+                    None => self.hit_test_shape(context, point),
+                };
+
+                if is_button_mode && is_hit {
                     return Some(self_node);
                 }
             }
@@ -2773,8 +2881,7 @@ impl<'gc, 'a> MovieClipData<'gc> {
             .label
             .to_str_lossy(reader.encoding())
             .to_ascii_lowercase();
-        if let std::collections::hash_map::Entry::Vacant(v) = static_data.frame_labels.entry(label)
-        {
+        if let indexmap::map::Entry::Vacant(v) = static_data.frame_labels.entry(label) {
             v.insert(cur_frame);
         } else {
             log::warn!("Movie clip {}: Duplicated frame label", self.id());
@@ -3055,7 +3162,8 @@ impl Default for Scene {
 struct MovieClipStatic {
     id: CharacterId,
     swf: SwfSlice,
-    frame_labels: HashMap<String, FrameNumber>,
+    /// Frame labels, in the order their tags appear in.
+    frame_labels: IndexMap<String, FrameNumber>,
     scene_labels: HashMap<String, Scene>,
     audio_stream_info: Option<swf::SoundStreamHead>,
     audio_stream_handle: Option<SoundHandle>,
@@ -3075,7 +3183,7 @@ impl MovieClipStatic {
             id,
             swf,
             total_frames,
-            frame_labels: HashMap::new(),
+            frame_labels: IndexMap::new(),
             scene_labels: HashMap::new(),
             audio_stream_info: None,
             audio_stream_handle: None,
@@ -1,8 +1,8 @@
 //! Video player display object
 
 use crate::avm1::{Object as Avm1Object, StageObject as Avm1StageObject};
-use crate::avm2::{Object as Avm2Object, StageObject as Avm2StageObject};
-use crate::backend::render::BitmapHandle;
+use crate::avm2::{Object as Avm2Object, StageObject as Avm2StageObject, Value as Avm2Value};
+use crate::backend::render::BitmapInfo;
 use crate::backend::video::{EncodedFrame, VideoStreamHandle};
 use crate::bounding_box::BoundingBox;
 use crate::collect::CollectWrapper;
@@ -40,7 +40,13 @@ pub struct VideoData<'gc> {
     stream: VideoStream,
 
     /// The last decoded frame in the video stream.
-    decoded_frame: Option<(u32, CollectWrapper<BitmapHandle>)>,
+    decoded_frame: Option<(u32, CollectWrapper<BitmapInfo>)>,
+
+    /// The `NetStream` whose frames this video shows, if any.
+    net_stream: Option<Avm2Object<'gc>>,
+
+    /// Whether frames are smoothed when the video is scaled.
+    smoothing: bool,
 
     /// AVM representation of this video player.
     object: Option<AvmObject<'gc>>,
@@ -85,6 +91,16 @@ pub enum VideoSource {
         /// to reconstruct a reference to the embedded bitstream.
         frames: BTreeMap<u32, (usize, usize)>,
     },
+
+    /// A video constructed by content, which shows the frames of whatever
+    /// `NetStream` is attached to it.
+    NetStream {
+        /// The width the video is displayed at, before any scaling.
+        width: u16,
+
+        /// The height the video is displayed at, before any scaling.
+        height: u16,
+    },
 }
 
 impl<'gc> Video<'gc> {
@@ -110,12 +126,123 @@ impl<'gc> Video<'gc> {
                 source,
                 stream: VideoStream::Uninstantiated(0),
                 decoded_frame: None,
+                net_stream: None,
+                smoothing: false,
                 object: None,
                 keyframes: BTreeSet::new(),
             },
         ))
     }
 
+    /// Construct a Video object for content to attach a `NetStream` to.
+    pub fn new(
+        mc: MutationContext<'gc, '_>,
+        width: u16,
+        height: u16,
+        object: Option<Avm2Object<'gc>>,
+    ) -> Self {
+        let source = GcCell::allocate(mc, VideoSource::NetStream { width, height });
+
+        Video(GcCell::allocate(
+            mc,
+            VideoData {
+                base: Default::default(),
+                source,
+                stream: VideoStream::Uninstantiated(0),
+                decoded_frame: None,
+                net_stream: None,
+                smoothing: false,
+                object: object.map(AvmObject::from),
+                keyframes: BTreeSet::new(),
+            },
+        ))
+    }
+
+    /// The `NetStream` attached to this video, if any.
+    pub fn net_stream(self) -> Option<Avm2Object<'gc>> {
+        self.0.read().net_stream
+    }
+
+    /// Attach a `NetStream` to this video, or detach it with `None`.
+    ///
+    /// The frame from any previous stream stops being shown.
+    pub fn attach_net_stream(self, mc: MutationContext<'gc, '_>, stream: Option<Avm2Object<'gc>>) {
+        let mut write = self.0.write(mc);
+        let is_same = match (write.net_stream, stream) {
+            (Some(old), Some(new)) => Avm2Object::ptr_eq(old, new),
+            (old, new) => old.is_none() && new.is_none(),
+        };
+
+        if !is_same {
+            write.net_stream = stream;
+            write.decoded_frame = None;
+        }
+    }
+
+    /// Show a decoded frame from the attached `NetStream`.
+    ///
+    /// The frame is uploaded as RGBA pixels. Frames that are the same size as
+    /// the one before reuse its texture.
+    pub fn show_frame(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        width: u16,
+        height: u16,
+        rgba: Vec<u8>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let last = self
+            .0
+            .read()
+            .decoded_frame
+            .as_ref()
+            .map(|(id, f)| (*id, f.0));
+        let handle = match last {
+            Some((_, last)) if last.width == width && last.height == height => context
+                .renderer
+                .update_texture(last.handle, width.into(), height.into(), rgba)?,
+            _ => context
+                .renderer
+                .register_bitmap_raw(width.into(), height.into(), rgba)?,
+        };
+
+        let frame_id = last.map(|(id, _)| id + 1).unwrap_or(0);
+        self.0.write(context.gc_context).decoded_frame = Some((
+            frame_id,
+            CollectWrapper(BitmapInfo {
+                handle,
+                width,
+                height,
+            }),
+        ));
+
+        Ok(())
+    }
+
+    /// Stop showing the current frame, leaving the video blank until the next
+    /// one arrives.
+    pub fn clear(self, mc: MutationContext<'gc, '_>) {
+        self.0.write(mc).decoded_frame = None;
+    }
+
+    /// The size of the frame being shown, or zero if there is none.
+    pub fn frame_size(self) -> (u16, u16) {
+        self.0
+            .read()
+            .decoded_frame
+            .as_ref()
+            .map(|(_, frame)| (frame.0.width, frame.0.height))
+            .unwrap_or((0, 0))
+    }
+
+    /// Whether frames are smoothed when the video is scaled.
+    pub fn smoothing(self) -> bool {
+        self.0.read().smoothing
+    }
+
+    pub fn set_smoothing(self, mc: MutationContext<'gc, '_>, smoothing: bool) {
+        self.0.write(mc).smoothing = smoothing;
+    }
+
     /// Preload frame data from an SWF.
     ///
     /// This function yields an error if this video player is not playing an
@@ -145,6 +272,9 @@ impl<'gc> Video<'gc> {
                     log::warn!("Invalid bitstream subslice on frame {}", tag.frame_num);
                 }
             }
+            VideoSource::NetStream { .. } => {
+                log::warn!("Attempted to preload SWF frame into a NetStream video");
+            }
         }
     }
 
@@ -168,6 +298,7 @@ impl<'gc> Video<'gc> {
 
         let num_frames = match &*read.source.read() {
             VideoSource::Swf { streamdef, .. } => Some(streamdef.num_frames),
+            VideoSource::NetStream { .. } => None,
         };
 
         if let Some(num_frames) = num_frames {
@@ -248,7 +379,6 @@ impl<'gc> Video<'gc> {
                     context
                         .video
                         .decode_video_stream_frame(*stream, encframe, context.renderer)
-                        .map(|bi| bi.handle)
                 }
                 None => {
                     if let Some((_old_id, old_frame)) = &read.decoded_frame {
@@ -261,6 +391,7 @@ impl<'gc> Video<'gc> {
                     }
                 }
             },
+            VideoSource::NetStream { .. } => Err("Attempted to seek a NetStream video".into()),
         };
 
         drop(read);
@@ -292,6 +423,7 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
     ) {
         let mut write = self.0.write(context.gc_context);
 
+        // Frames from a `NetStream` are decoded by the stream, not the video.
         let (stream, movie, keyframes) = match &*write.source.read() {
             VideoSource::Swf {
                 streamdef,
@@ -338,6 +470,7 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
 
                 (stream, movie.clone(), keyframes)
             }
+            VideoSource::NetStream { .. } => return,
         };
 
         let starting_seek = if let VideoStream::Uninstantiated(seek_to) = write.stream {
@@ -382,9 +515,19 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
         }
     }
 
+    fn object2(&self) -> Avm2Value<'gc> {
+        self.0
+            .read()
+            .object
+            .and_then(|o| o.as_avm2_object().ok())
+            .map(Avm2Value::from)
+            .unwrap_or(Avm2Value::Undefined)
+    }
+
     fn id(&self) -> CharacterId {
         match (*self.0.read().source.read()).borrow() {
             VideoSource::Swf { streamdef, .. } => streamdef.id,
+            VideoSource::NetStream { .. } => 0,
         }
     }
 
@@ -396,6 +539,10 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
                 bounding_box.set_width(Twips::from_pixels(streamdef.width as f64));
                 bounding_box.set_height(Twips::from_pixels(streamdef.height as f64));
             }
+            VideoSource::NetStream { width, height } => {
+                bounding_box.set_width(Twips::from_pixels(*width as f64));
+                bounding_box.set_height(Twips::from_pixels(*height as f64));
+            }
         }
 
         bounding_box
@@ -409,11 +556,14 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
 
         context.transform_stack.push(&*self.transform());
 
-        if let Some((_frame_id, ref bitmap)) = self.0.read().decoded_frame {
-            context
-                .renderer
-                .render_bitmap(bitmap.0, context.transform_stack.transform(), false);
-        } else {
+        let read = self.0.read();
+        if let Some((_frame_id, ref bitmap)) = read.decoded_frame {
+            context.renderer.render_bitmap(
+                bitmap.0.handle,
+                context.transform_stack.transform(),
+                read.smoothing,
+            );
+        } else if let VideoSource::Swf { .. } = &*read.source.read() {
             log::warn!("Video has no decoded frame to render.");
         }
 
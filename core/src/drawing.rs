@@ -1,15 +1,20 @@
 use crate::backend::render::ShapeHandle;
 use crate::bounding_box::BoundingBox;
 use crate::context::RenderContext;
-use crate::shape_utils::{DistilledShape, DrawCommand, DrawPath};
+use crate::shape_utils::{self, DistilledShape, DrawCommand, DrawPath};
 use gc_arena::Collect;
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use swf::{FillStyle, LineStyle, Twips};
 
+/// A shape warped for a nine-slice scaling grid, along with the scale and the
+/// grid that it was warped for.
+pub type Scale9Shape = (ShapeHandle, (f64, f64), BoundingBox);
+
 #[derive(Clone, Debug, Collect)]
 #[collect(require_static)]
 pub struct Drawing {
     render_handle: Cell<Option<ShapeHandle>>,
+    scale_9_shape: RefCell<Option<Scale9Shape>>,
     shape_bounds: BoundingBox,
     edge_bounds: BoundingBox,
     dirty: Cell<bool>,
@@ -24,6 +29,7 @@ impl Drawing {
     pub fn new() -> Self {
         Self {
             render_handle: Cell::new(None),
+            scale_9_shape: RefCell::new(None),
             shape_bounds: BoundingBox::default(),
             edge_bounds: BoundingBox::default(),
             dirty: Cell::new(false),
@@ -82,6 +88,21 @@ impl Drawing {
         self.dirty.set(true);
     }
 
+    /// Stroke the rest of the current line with a fill, such as a gradient,
+    /// instead of its color.
+    ///
+    /// This does nothing if there is no current line.
+    pub fn set_line_fill_style(&mut self, fill_style: FillStyle) {
+        if let Some((style, _)) = &self.current_line {
+            let style = LineStyle {
+                fill_style: Some(fill_style),
+                ..style.clone()
+            };
+
+            self.set_line_style(Some(style));
+        }
+    }
+
     pub fn draw_command(&mut self, command: DrawCommand) {
         let mut include_last = false;
         let stroke_width = if let Some((style, _)) = &self.current_line {
@@ -136,58 +157,73 @@ impl Drawing {
         self.dirty.set(true);
     }
 
-    pub fn render(&self, context: &mut RenderContext) {
-        if self.dirty.get() {
-            self.dirty.set(false);
-            let mut paths = Vec::new();
+    /// Build the shape that this drawing renders as.
+    fn distilled_shape(&self) -> DistilledShape<'_> {
+        let mut paths = Vec::new();
 
-            for (style, commands) in &self.fills {
-                paths.push(DrawPath::Fill {
-                    style,
-                    commands: commands.to_owned(),
-                })
-            }
+        for (style, commands) in &self.fills {
+            paths.push(DrawPath::Fill {
+                style,
+                commands: commands.to_owned(),
+            })
+        }
 
-            // TODO: If the current_fill is not closed, we should automatically close current_line
+        // TODO: If the current_fill is not closed, we should automatically close current_line
 
-            if let Some((style, commands)) = &self.current_fill {
-                paths.push(DrawPath::Fill {
-                    style,
-                    commands: commands.to_owned(),
-                })
-            }
+        if let Some((style, commands)) = &self.current_fill {
+            paths.push(DrawPath::Fill {
+                style,
+                commands: commands.to_owned(),
+            })
+        }
 
-            for (style, commands) in &self.lines {
-                paths.push(DrawPath::Stroke {
-                    style,
-                    commands: commands.to_owned(),
-                    is_closed: false, // TODO: Determine this
-                })
-            }
+        for (style, commands) in &self.lines {
+            paths.push(DrawPath::Stroke {
+                style,
+                commands: commands.to_owned(),
+                is_closed: false, // TODO: Determine this
+            })
+        }
 
-            if let Some((style, commands)) = &self.current_line {
-                paths.push(DrawPath::Stroke {
-                    style,
-                    commands: commands.to_owned(),
-                    is_closed: false, // TODO: Determine this
-                })
-            }
+        if let Some((style, commands)) = &self.current_line {
+            paths.push(DrawPath::Stroke {
+                style,
+                commands: commands.to_owned(),
+                is_closed: false, // TODO: Determine this
+            })
+        }
 
-            let shape = DistilledShape {
-                paths,
-                shape_bounds: self.shape_bounds.clone(),
-                edge_bounds: self.edge_bounds.clone(),
-                id: 0,
-            };
+        DistilledShape {
+            paths,
+            shape_bounds: self.shape_bounds.clone(),
+            edge_bounds: self.edge_bounds.clone(),
+            id: 0,
+        }
+    }
 
-            if let Some(handle) = self.render_handle.get() {
-                context.renderer.replace_shape(shape, None, handle);
-            } else {
-                self.render_handle
-                    .set(Some(context.renderer.register_shape(shape, None)));
-            }
+    /// Register or replace the shape of this drawing if it has changed since
+    /// it was last rendered. Returns whether it had changed.
+    fn update_render_handle(&self, context: &mut RenderContext) -> bool {
+        if !self.dirty.get() {
+            return false;
         }
 
+        self.dirty.set(false);
+        let shape = self.distilled_shape();
+
+        if let Some(handle) = self.render_handle.get() {
+            context.renderer.replace_shape(shape, None, handle);
+        } else {
+            self.render_handle
+                .set(Some(context.renderer.register_shape(shape, None)));
+        }
+
+        true
+    }
+
+    pub fn render(&self, context: &mut RenderContext) {
+        self.update_render_handle(context);
+
         if let Some(handle) = self.render_handle.get() {
             context
                 .renderer
@@ -195,6 +231,46 @@ impl Drawing {
         }
     }
 
+    /// Render this drawing, warped to honour a nine-slice scaling grid at
+    /// the scale it is currently being drawn at.
+    pub fn render_with_scale_9_grid(&self, context: &mut RenderContext, grid: &BoundingBox) {
+        let changed = self.update_render_handle(context);
+        if self.render_handle.get().is_none() {
+            return;
+        }
+
+        let scale = shape_utils::matrix_scale(&context.transform_stack.transform().matrix);
+        let mut cache = self.scale_9_shape.borrow_mut();
+        let handle = match &*cache {
+            Some((handle, cached_scale, cached_grid))
+                if !changed && *cached_scale == scale && cached_grid == grid =>
+            {
+                *handle
+            }
+            cached => {
+                let shape = shape_utils::scale_9_grid_shape(self.distilled_shape(), grid, scale);
+                let handle = if let Some((handle, _, _)) = cached {
+                    context.renderer.replace_shape(shape, None, *handle);
+                    *handle
+                } else {
+                    context.renderer.register_shape(shape, None)
+                };
+
+                *cache = Some((handle, scale, grid.clone()));
+                handle
+            }
+        };
+
+        context
+            .renderer
+            .render_shape(handle, context.transform_stack.transform());
+    }
+
+    /// The point that the next drawing command will start from.
+    pub fn cursor(&self) -> (Twips, Twips) {
+        self.cursor
+    }
+
     pub fn self_bounds(&self) -> BoundingBox {
         self.shape_bounds.clone()
     }
@@ -227,6 +303,232 @@ impl Drawing {
     }
 }
 
+/// Which triangles `Graphics.drawTriangles` should skip drawing, based on the
+/// direction they are wound in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TriangleCulling {
+    /// Draw every triangle.
+    None,
+
+    /// Skip triangles wound clockwise on screen.
+    Positive,
+
+    /// Skip triangles wound counter-clockwise on screen.
+    Negative,
+}
+
+/// Decode the `commands` and `data` of a `Graphics.drawPath` call into draw
+/// commands.
+///
+/// Command values are those of `GraphicsPathCommand`. Cubic curves are
+/// approximated with quadratic curves, starting from `cursor`, the current
+/// drawing position.
+///
+/// Returns `None` if `data` does not hold exactly as many coordinates as the
+/// commands consume, or if an unknown command is given.
+pub fn decode_path(
+    commands: &[i32],
+    data: &[f64],
+    mut cursor: (Twips, Twips),
+) -> Option<Vec<DrawCommand>> {
+    fn next_point(data: &mut impl Iterator<Item = Twips>) -> Option<(Twips, Twips)> {
+        Some((data.next()?, data.next()?))
+    }
+
+    let mut data = data.iter().map(|v| Twips::from_pixels(*v));
+    let mut draw_commands = Vec::with_capacity(commands.len());
+
+    for command in commands {
+        let draw_command = match command {
+            // NO_OP
+            0 => continue,
+
+            // MOVE_TO
+            1 => {
+                let (x, y) = next_point(&mut data)?;
+                DrawCommand::MoveTo { x, y }
+            }
+
+            // LINE_TO
+            2 => {
+                let (x, y) = next_point(&mut data)?;
+                DrawCommand::LineTo { x, y }
+            }
+
+            // CURVE_TO
+            3 => {
+                let (x1, y1) = next_point(&mut data)?;
+                let (x2, y2) = next_point(&mut data)?;
+                DrawCommand::CurveTo { x1, y1, x2, y2 }
+            }
+
+            // WIDE_MOVE_TO
+            4 => {
+                next_point(&mut data)?;
+                let (x, y) = next_point(&mut data)?;
+                DrawCommand::MoveTo { x, y }
+            }
+
+            // WIDE_LINE_TO
+            5 => {
+                next_point(&mut data)?;
+                let (x, y) = next_point(&mut data)?;
+                DrawCommand::LineTo { x, y }
+            }
+
+            // CUBIC_CURVE_TO
+            6 => {
+                let control1 = next_point(&mut data)?;
+                let control2 = next_point(&mut data)?;
+                let end = next_point(&mut data)?;
+                draw_commands.extend(approximate_cubic(cursor, control1, control2, end));
+                cursor = end;
+                continue;
+            }
+
+            _ => return None,
+        };
+
+        cursor = draw_command.end_point();
+        draw_commands.push(draw_command);
+    }
+
+    if data.next().is_some() {
+        return None;
+    }
+
+    Some(draw_commands)
+}
+
+/// How many quadratic curves stand in for each cubic curve of a path.
+const CUBIC_SUBDIVISIONS: u32 = 4;
+
+/// Approximate the cubic curve from `start` to `end`, with the control points
+/// `control1` and `control2`, with `CUBIC_SUBDIVISIONS` quadratic curves.
+///
+/// Each quadratic curve shares its end points with a piece of the cubic
+/// curve, and its control point is the midpoint of the two control points
+/// that the piece would have as a cubic curve of its own.
+fn approximate_cubic(
+    start: (Twips, Twips),
+    control1: (Twips, Twips),
+    control2: (Twips, Twips),
+    end: (Twips, Twips),
+) -> Vec<DrawCommand> {
+    let axis = |get: fn((Twips, Twips)) -> Twips| {
+        [
+            get(start).to_pixels(),
+            get(control1).to_pixels(),
+            get(control2).to_pixels(),
+            get(end).to_pixels(),
+        ]
+    };
+    let (xs, ys) = (axis(|p| p.0), axis(|p| p.1));
+
+    let position = |[p0, p1, p2, p3]: [f64; 4], t: f64| {
+        let u = 1.0 - t;
+        u * u * u * p0 + 3.0 * u * u * t * p1 + 3.0 * u * t * t * p2 + t * t * t * p3
+    };
+    let slope = |[p0, p1, p2, p3]: [f64; 4], t: f64| {
+        let u = 1.0 - t;
+        3.0 * u * u * (p1 - p0) + 6.0 * u * t * (p2 - p1) + 3.0 * t * t * (p3 - p2)
+    };
+    let control = |axis: [f64; 4], t0: f64, t1: f64| {
+        let midpoint = (position(axis, t0) + position(axis, t1)) / 2.0;
+        Twips::from_pixels(midpoint + (t1 - t0) * (slope(axis, t0) - slope(axis, t1)) / 4.0)
+    };
+
+    let step = 1.0 / f64::from(CUBIC_SUBDIVISIONS);
+    (1..=CUBIC_SUBDIVISIONS)
+        .map(|i| {
+            let (t0, t1) = (f64::from(i - 1) * step, f64::from(i) * step);
+            let (x2, y2) = if i == CUBIC_SUBDIVISIONS {
+                end
+            } else {
+                (
+                    Twips::from_pixels(position(xs, t1)),
+                    Twips::from_pixels(position(ys, t1)),
+                )
+            };
+
+            DrawCommand::CurveTo {
+                x1: control(xs, t0, t1),
+                y1: control(ys, t0, t1),
+                x2,
+                y2,
+            }
+        })
+        .collect()
+}
+
+/// Decode the `vertices` and `indices` of a `Graphics.drawTriangles` call
+/// into draw commands tracing each triangle.
+///
+/// Without `indices`, every three vertices form a triangle.
+///
+/// Returns `None` if the vertices or indices do not describe whole
+/// triangles, or if an index is out of range.
+pub fn decode_triangles(
+    vertices: &[f64],
+    indices: Option<&[i32]>,
+    culling: TriangleCulling,
+) -> Option<Vec<DrawCommand>> {
+    if vertices.len() % 2 != 0 {
+        return None;
+    }
+
+    let vertex_count = vertices.len() / 2;
+    let indices: Vec<usize> = match indices {
+        Some(indices) => indices
+            .iter()
+            .map(|i| {
+                if *i >= 0 && (*i as usize) < vertex_count {
+                    Some(*i as usize)
+                } else {
+                    None
+                }
+            })
+            .collect::<Option<_>>()?,
+        None => (0..vertex_count).collect(),
+    };
+
+    if indices.len() % 3 != 0 {
+        return None;
+    }
+
+    let vertex = |i: usize| (vertices[i * 2], vertices[i * 2 + 1]);
+    let mut draw_commands = Vec::with_capacity(indices.len() / 3 * 4);
+
+    for triangle in indices.chunks_exact(3) {
+        let (ax, ay) = vertex(triangle[0]);
+        let (bx, by) = vertex(triangle[1]);
+        let (cx, cy) = vertex(triangle[2]);
+        let winding = (bx - ax) * (cy - ay) - (by - ay) * (cx - ax);
+
+        let culled = match culling {
+            TriangleCulling::None => false,
+            TriangleCulling::Positive => winding > 0.0,
+            TriangleCulling::Negative => winding < 0.0,
+        };
+        if culled {
+            continue;
+        }
+
+        draw_commands.push(DrawCommand::MoveTo {
+            x: Twips::from_pixels(ax),
+            y: Twips::from_pixels(ay),
+        });
+        for &(x, y) in &[(bx, by), (cx, cy), (ax, ay)] {
+            draw_commands.push(DrawCommand::LineTo {
+                x: Twips::from_pixels(x),
+                y: Twips::from_pixels(y),
+            });
+        }
+    }
+
+    Some(draw_commands)
+}
+
 fn stretch_bounding_box(
     bounding_box: &mut BoundingBox,
     command: &DrawCommand,
@@ -250,3 +552,266 @@ fn stretch_bounding_box(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(x: f64, y: f64) -> (Twips, Twips) {
+        (Twips::from_pixels(x), Twips::from_pixels(y))
+    }
+
+    #[test]
+    fn decode_path_matches_move_and_line() {
+        let commands = [1, 2, 2, 0, 2];
+        let data = [10.0, 10.0, 50.0, 10.0, 50.0, 40.0, 10.0, 10.0];
+        let path = decode_path(&commands, &data, point(0.0, 0.0)).unwrap();
+
+        let mut expected = vec![DrawCommand::MoveTo {
+            x: Twips::from_pixels(10.0),
+            y: Twips::from_pixels(10.0),
+        }];
+        for &(x, y) in &[(50.0, 10.0), (50.0, 40.0), (10.0, 10.0)] {
+            expected.push(DrawCommand::LineTo {
+                x: Twips::from_pixels(x),
+                y: Twips::from_pixels(y),
+            });
+        }
+        assert_eq!(path, expected);
+
+        let mut batched = Drawing::new();
+        let mut manual = Drawing::new();
+        batched.set_fill_style(Some(FillStyle::Color(swf::Color::from_rgb(0xFF0000, 255))));
+        manual.set_fill_style(Some(FillStyle::Color(swf::Color::from_rgb(0xFF0000, 255))));
+        for command in path {
+            batched.draw_command(command);
+        }
+        for command in expected {
+            manual.draw_command(command);
+        }
+        assert_eq!(batched.self_bounds(), manual.self_bounds());
+        assert_eq!(batched.cursor(), manual.cursor());
+    }
+
+    #[test]
+    fn decode_path_wide_and_curve_commands() {
+        let commands = [4, 5, 3];
+        let data = [0.0, 0.0, 1.0, 2.0, 0.0, 0.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
+        let path = decode_path(&commands, &data, point(0.0, 0.0)).unwrap();
+
+        assert_eq!(
+            path,
+            vec![
+                DrawCommand::MoveTo {
+                    x: Twips::from_pixels(1.0),
+                    y: Twips::from_pixels(2.0),
+                },
+                DrawCommand::LineTo {
+                    x: Twips::from_pixels(3.0),
+                    y: Twips::from_pixels(4.0),
+                },
+                DrawCommand::CurveTo {
+                    x1: Twips::from_pixels(5.0),
+                    y1: Twips::from_pixels(6.0),
+                    x2: Twips::from_pixels(7.0),
+                    y2: Twips::from_pixels(8.0),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn decode_path_subdivides_cubic_curves() {
+        // A cubic curve that is really the quadratic curve through (10, 20),
+        // so every piece of it must lie on that quadratic curve as well.
+        let path = decode_path(
+            &[6],
+            &[20.0 / 3.0, 40.0 / 3.0, 40.0 / 3.0, 40.0 / 3.0, 20.0, 0.0],
+            point(0.0, 0.0),
+        )
+        .unwrap();
+        let quadratic = |t: f64| point(20.0 * t, 40.0 * t * (1.0 - t));
+
+        assert_eq!(path.len(), CUBIC_SUBDIVISIONS as usize);
+        for (i, command) in path.iter().enumerate() {
+            let t = (i + 1) as f64 / f64::from(CUBIC_SUBDIVISIONS);
+            let (x, y) = command.end_point();
+            let (expected_x, expected_y) = quadratic(t);
+            assert!((x - expected_x).get().abs() <= 1);
+            assert!((y - expected_y).get().abs() <= 1);
+        }
+        assert_eq!(path.last().unwrap().end_point(), point(20.0, 0.0));
+
+        // An S-shaped curve bends both ways, which a single quadratic curve
+        // cannot do.
+        let path = decode_path(
+            &[1, 6],
+            &[0.0, 0.0, 10.0, 10.0, 20.0, -10.0, 30.0, 0.0],
+            point(0.0, 0.0),
+        )
+        .unwrap();
+        let controls: Vec<_> = path[1..]
+            .iter()
+            .map(|command| match command {
+                DrawCommand::CurveTo { y1, .. } => y1.get(),
+                _ => panic!("expected a curve, got {:?}", command),
+            })
+            .collect();
+        assert!(controls.first().unwrap() > &0);
+        assert!(controls.last().unwrap() < &0);
+    }
+
+    #[test]
+    fn decode_path_rejects_mismatched_data() {
+        let origin = point(0.0, 0.0);
+
+        assert_eq!(decode_path(&[1, 2], &[0.0, 0.0, 1.0], origin), None);
+        assert_eq!(decode_path(&[1], &[0.0, 0.0, 1.0, 1.0], origin), None);
+        assert_eq!(decode_path(&[9], &[], origin), None);
+        assert_eq!(decode_path(&[], &[], origin), Some(vec![]));
+    }
+
+    #[test]
+    fn decode_triangles_with_indices_and_culling() {
+        // A unit square split into two triangles wound in opposite directions.
+        let vertices = [0.0, 0.0, 10.0, 0.0, 10.0, 10.0, 0.0, 10.0];
+        let indices = [0, 1, 2, 0, 3, 2];
+
+        let all = decode_triangles(&vertices, Some(&indices), TriangleCulling::None).unwrap();
+        assert_eq!(all.len(), 8);
+        assert_eq!(
+            all[4],
+            DrawCommand::MoveTo {
+                x: Twips::zero(),
+                y: Twips::zero(),
+            }
+        );
+        assert_eq!(
+            all[7],
+            DrawCommand::LineTo {
+                x: Twips::zero(),
+                y: Twips::zero(),
+            }
+        );
+
+        let positive =
+            decode_triangles(&vertices, Some(&indices), TriangleCulling::Positive).unwrap();
+        let negative =
+            decode_triangles(&vertices, Some(&indices), TriangleCulling::Negative).unwrap();
+        assert_eq!(positive.len(), 4);
+        assert_eq!(negative.len(), 4);
+        assert_ne!(positive, negative);
+
+        assert_eq!(
+            decode_triangles(&vertices, Some(&[0, 1, 4]), TriangleCulling::None),
+            None
+        );
+        assert_eq!(
+            decode_triangles(&vertices, None, TriangleCulling::None),
+            None
+        );
+        assert_eq!(
+            decode_triangles(&vertices[..6], None, TriangleCulling::None)
+                .unwrap()
+                .len(),
+            4
+        );
+    }
+
+    fn solid_line(width: f64, rgb: u32) -> LineStyle {
+        LineStyle {
+            width: Twips::from_pixels(width),
+            color: swf::Color::from_rgb(rgb, 255),
+            start_cap: swf::LineCapStyle::Round,
+            end_cap: swf::LineCapStyle::Round,
+            join_style: swf::LineJoinStyle::Round,
+            fill_style: None,
+            allow_scale_x: true,
+            allow_scale_y: true,
+            is_pixel_hinted: false,
+            allow_close: false,
+        }
+    }
+
+    #[test]
+    fn line_gradient_strokes_the_rest_of_the_line() {
+        let red_to_blue = FillStyle::LinearGradient(swf::Gradient {
+            matrix: swf::Matrix::identity(),
+            spread: swf::GradientSpread::Pad,
+            interpolation: swf::GradientInterpolation::Rgb,
+            records: vec![
+                swf::GradientRecord {
+                    ratio: 0,
+                    color: swf::Color::from_rgb(0xFF0000, 255),
+                },
+                swf::GradientRecord {
+                    ratio: 128,
+                    color: swf::Color::from_rgb(0x00FF00, 255),
+                },
+                swf::GradientRecord {
+                    ratio: 255,
+                    color: swf::Color::from_rgb(0x0000FF, 255),
+                },
+            ],
+        });
+
+        let mut drawing = Drawing::new();
+        drawing.set_line_style(Some(solid_line(4.0, 0x000000)));
+        drawing.draw_command(DrawCommand::LineTo {
+            x: Twips::from_pixels(10.0),
+            y: Twips::zero(),
+        });
+        drawing.set_line_fill_style(red_to_blue.clone());
+        drawing.draw_command(DrawCommand::LineTo {
+            x: Twips::from_pixels(100.0),
+            y: Twips::zero(),
+        });
+
+        let shape = drawing.distilled_shape();
+        let strokes: Vec<_> = shape
+            .paths
+            .iter()
+            .map(|path| match path {
+                DrawPath::Stroke {
+                    style, commands, ..
+                } => (*style, commands.clone()),
+                DrawPath::Fill { .. } => panic!("Nothing was filled"),
+            })
+            .collect();
+
+        assert_eq!(strokes.len(), 2);
+        assert_eq!(strokes[0].0, &solid_line(4.0, 0x000000));
+        assert_eq!(
+            strokes[1].0,
+            &LineStyle {
+                fill_style: Some(red_to_blue),
+                ..solid_line(4.0, 0x000000)
+            }
+        );
+        assert_eq!(
+            strokes[1].1,
+            vec![
+                DrawCommand::MoveTo {
+                    x: Twips::from_pixels(10.0),
+                    y: Twips::zero(),
+                },
+                DrawCommand::LineTo {
+                    x: Twips::from_pixels(100.0),
+                    y: Twips::zero(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn line_gradient_without_a_line_does_nothing() {
+        let mut drawing = Drawing::new();
+        drawing.set_line_fill_style(FillStyle::Color(swf::Color::from_rgb(0xFF0000, 255)));
+        drawing.draw_command(DrawCommand::LineTo {
+            x: Twips::from_pixels(10.0),
+            y: Twips::zero(),
+        });
+
+        assert!(drawing.distilled_shape().paths.is_empty());
+    }
+}
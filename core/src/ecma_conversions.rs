@@ -28,6 +28,71 @@ pub fn f64_to_string(n: f64) -> Cow<'static, str> {
     }
 }
 
+/// Converts an `f64` to a String as ECMA-262's `Number::toString` does,
+/// which is how AVM2 prints numbers.
+///
+/// This yields the fewest digits that still round-trip to the same number.
+/// Numbers of at least 1e21, or below 1e-6, are written in exponential
+/// notation, and integers below that are written out in full.
+///
+/// Flash Player differs from ECMA-262 in one way: large numbers in
+/// exponential notation keep at most 15 significant digits, and the rest are
+/// truncated.
+pub fn f64_to_ecma_string(n: f64) -> Cow<'static, str> {
+    if n.is_nan() {
+        return Cow::Borrowed("NaN");
+    } else if n == 0.0 {
+        return Cow::Borrowed("0");
+    } else if n == f64::INFINITY {
+        return Cow::Borrowed("Infinity");
+    } else if n == f64::NEG_INFINITY {
+        return Cow::Borrowed("-Infinity");
+    }
+
+    // Rust's exponential formatting already finds the shortest round-trip
+    // digits, e.g. `1.2345e-7`; we only need to lay them out.
+    let formatted = format!("{:e}", n.abs());
+    let (mantissa, exponent) = formatted.split_at(formatted.find('e').unwrap());
+    let exponent: i32 = exponent[1..].parse().unwrap();
+    let mut digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+    let sign = if n < 0.0 { "-" } else { "" };
+
+    Cow::Owned(if !(-6..21).contains(&exponent) {
+        if exponent > 0 {
+            digits.truncate(15);
+            digits.truncate(digits.trim_end_matches('0').len());
+        }
+
+        let (first, rest) = digits.split_at(1);
+        let point = if rest.is_empty() { "" } else { "." };
+        let exponent_sign = if exponent < 0 { "-" } else { "+" };
+
+        format!(
+            "{}{}{}{}e{}{}",
+            sign,
+            first,
+            point,
+            rest,
+            exponent_sign,
+            exponent.abs()
+        )
+    } else if exponent >= 0 {
+        let point = exponent as usize + 1;
+        if digits.len() <= point {
+            format!("{}{}{}", sign, digits, "0".repeat(point - digits.len()))
+        } else {
+            format!("{}{}.{}", sign, &digits[..point], &digits[point..])
+        }
+    } else {
+        format!(
+            "{}0.{}{}",
+            sign,
+            "0".repeat((-exponent - 1) as usize),
+            digits
+        )
+    })
+}
+
 /// Converts an `f64` to an `u16` with ECMAScript `ToUInt16` wrapping behavior.
 /// The value will be wrapped modulo 2^16.
 pub fn f64_to_wrapping_u16(n: f64) -> u16 {
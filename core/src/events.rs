@@ -2,14 +2,57 @@ use num_enum::{IntoPrimitive, TryFromPrimitive};
 
 #[derive(Debug)]
 pub enum PlayerEvent {
-    KeyDown { key_code: KeyCode },
-    KeyUp { key_code: KeyCode },
-    MouseMove { x: f64, y: f64 },
-    MouseUp { x: f64, y: f64 },
-    MouseDown { x: f64, y: f64 },
+    KeyDown {
+        key_code: KeyCode,
+    },
+    KeyUp {
+        key_code: KeyCode,
+    },
+    MouseMove {
+        x: f64,
+        y: f64,
+    },
+    MouseUp {
+        x: f64,
+        y: f64,
+    },
+    MouseDown {
+        x: f64,
+        y: f64,
+    },
     MouseLeft,
-    MouseWheel { delta: MouseWheelDelta },
-    TextInput { codepoint: char },
+    MouseWheel {
+        delta: MouseWheelDelta,
+    },
+    TextInput {
+        codepoint: char,
+    },
+    FocusLost,
+
+    /// A finger or stylus touched the player, in the same coordinates as the
+    /// mouse events.
+    ///
+    /// `id` tells touch points apart for as long as they are down, and
+    /// `pressure` runs from 0 to 1. Hosts should still send mouse events for
+    /// the primary touch point.
+    TouchBegin {
+        id: i32,
+        x: f64,
+        y: f64,
+        pressure: f64,
+    },
+    TouchMove {
+        id: i32,
+        x: f64,
+        y: f64,
+        pressure: f64,
+    },
+    TouchEnd {
+        id: i32,
+        x: f64,
+        y: f64,
+        pressure: f64,
+    },
 }
 
 /// The distance scrolled by the mouse wheel.
@@ -142,6 +185,7 @@ impl ClipEvent {
 pub enum KeyCode {
     Unknown = 0,
     Backspace = 8,
+    Tab = 9,
     Return = 13,
     Shift = 16,
     Control = 17,
@@ -366,6 +410,7 @@ pub fn key_code_to_button_key_code(key_code: KeyCode) -> Option<ButtonKeyCode> {
         KeyCode::Insert => ButtonKeyCode::Insert,
         KeyCode::Delete => ButtonKeyCode::Delete,
         KeyCode::Backspace => ButtonKeyCode::Backspace,
+        KeyCode::Tab => ButtonKeyCode::Tab,
         KeyCode::Return => ButtonKeyCode::Return,
         KeyCode::Up => ButtonKeyCode::Up,
         KeyCode::Down => ButtonKeyCode::Down,
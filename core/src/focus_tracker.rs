@@ -1,7 +1,13 @@
 use crate::avm1::{Avm1, Value};
-use crate::context::UpdateContext;
-pub use crate::display_object::{DisplayObject, TDisplayObject};
+use crate::context::{RenderContext, UpdateContext};
+pub use crate::display_object::{DisplayObject, TDisplayObject, TDisplayObjectContainer};
+use crate::prelude::*;
 use gc_arena::{Collect, GcCell, MutationContext};
+use swf::Matrix;
+
+/// How far the focus rectangle extends outside of the focused object, in
+/// pixels.
+const FOCUS_RECT_THICKNESS: f64 = 2.0;
 
 #[derive(Clone, Copy, Collect, Debug)]
 #[collect(no_drop)]
@@ -56,4 +62,129 @@ impl<'gc> FocusTracker<'gc> {
             ],
         );
     }
+
+    /// Move focus to the next object in the tab order, or the previous one if
+    /// `reverse` is set, wrapping around at either end.
+    ///
+    /// Focus that a script gave to an object outside of the tab order moves
+    /// to the first (or last) object in it.
+    pub fn cycle(&self, context: &mut UpdateContext<'_, 'gc, '_>, reverse: bool) {
+        let order = tab_order(context);
+        if order.is_empty() {
+            return;
+        }
+
+        let current = self.get().and_then(|focused| {
+            order
+                .iter()
+                .position(|o| DisplayObject::ptr_eq(*o, focused))
+        });
+        let next = match (current, reverse) {
+            (Some(i), false) => (i + 1) % order.len(),
+            (Some(i), true) => (i + order.len() - 1) % order.len(),
+            (None, false) => 0,
+            (None, true) => order.len() - 1,
+        };
+
+        self.set(Some(order[next]), context);
+    }
+
+    /// Draw a yellow rectangle around the focused object.
+    ///
+    /// Objects whose `focusRect` is unset get one only if `stage_focus_rect`
+    /// is set.
+    pub fn render_highlight(&self, context: &mut RenderContext<'_, 'gc>, stage_focus_rect: bool) {
+        let focused = match self.get() {
+            Some(focused) => focused,
+            None => return,
+        };
+        if !focused.focus_rect().unwrap_or(stage_focus_rect) {
+            return;
+        }
+
+        let bounds = focused.world_bounds();
+        if !bounds.valid {
+            return;
+        }
+
+        let thickness = Twips::from_pixels(FOCUS_RECT_THICKNESS);
+        let width = (bounds.x_max - bounds.x_min + thickness * 2).to_pixels() as f32;
+        let height = (bounds.y_max - bounds.y_min).to_pixels() as f32;
+        let edges = [
+            Matrix::create_box(
+                width,
+                FOCUS_RECT_THICKNESS as f32,
+                0.0,
+                bounds.x_min - thickness,
+                bounds.y_min - thickness,
+            ),
+            Matrix::create_box(
+                width,
+                FOCUS_RECT_THICKNESS as f32,
+                0.0,
+                bounds.x_min - thickness,
+                bounds.y_max,
+            ),
+            Matrix::create_box(
+                FOCUS_RECT_THICKNESS as f32,
+                height,
+                0.0,
+                bounds.x_min - thickness,
+                bounds.y_min,
+            ),
+            Matrix::create_box(
+                FOCUS_RECT_THICKNESS as f32,
+                height,
+                0.0,
+                bounds.x_max,
+                bounds.y_min,
+            ),
+        ];
+
+        let matrix = context.transform_stack.transform().matrix;
+        for edge in edges.iter() {
+            context
+                .renderer
+                .draw_rect(Color::from_rgb(0xFFFF00, 255), &(matrix * *edge));
+        }
+    }
+}
+
+/// The focusable objects that Tab moves between.
+///
+/// Objects with a `tabIndex` come first, in index order, followed by the rest
+/// in display list order. Invisible objects are left out, as is everything
+/// inside a container whose `tabChildren` is false.
+pub fn tab_order<'gc>(context: &UpdateContext<'_, 'gc, '_>) -> Vec<DisplayObject<'gc>> {
+    let mut order = Vec::new();
+    for level in context.levels.values() {
+        fill_tab_order(*level, &mut order);
+    }
+
+    // The sort is stable, so objects sharing an index keep their display
+    // list order.
+    order.sort_by_key(|object| {
+        let tab_index = object.tab_index();
+        (tab_index.is_none(), tab_index)
+    });
+
+    order
+}
+
+fn fill_tab_order<'gc>(object: DisplayObject<'gc>, order: &mut Vec<DisplayObject<'gc>>) {
+    if !object.visible() {
+        return;
+    }
+
+    if object.is_focusable() {
+        order.push(object);
+    }
+
+    if object.tab_children() {
+        if let Some(container) = object.as_container() {
+            for child in container.iter_render_list() {
+                fill_tab_order(child, order);
+            }
+        }
+    }
 }
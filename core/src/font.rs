@@ -34,8 +34,7 @@ pub struct EvalParameters {
 
 impl EvalParameters {
     /// Construct eval parameters from their individual parts.
-    #[allow(dead_code)]
-    fn from_parts(height: Twips, letter_spacing: Twips, kerning: bool) -> Self {
+    pub fn from_parts(height: Twips, letter_spacing: Twips, kerning: bool) -> Self {
         Self {
             height,
             letter_spacing,
@@ -204,6 +203,14 @@ impl<'gc> Font<'gc> {
         Twips::new((self.0.ascent as f32 * scale) as i32)
     }
 
+    /// Get the distance from the baseline to the bottom of the glyph at a
+    /// given height.
+    pub fn get_descent_for_height(&self, height: Twips) -> Twips {
+        let scale = height.get() as f32 / self.scale();
+
+        Twips::new((self.0.descent as f32 * scale) as i32)
+    }
+
     /// Returns whether this font contains kerning information.
     pub fn has_kerning_info(&self) -> bool {
         !self.0.kerning_pairs.is_empty()
@@ -436,51 +443,67 @@ impl FontDescriptor {
     }
 }
 
-/// The text rendering engine that a text field should use.
-/// This is controlled by the "Anti-alias" setting in the Flash IDE.
+/// The text rendering engine that a text field should use, and how it should
+/// rasterize glyphs.
+///
+/// The engine is controlled by the "Anti-alias" setting in the Flash IDE.
 /// Using "Anti-alias for readibility" switches to the "Advanced" text
-/// rendering engine.
+/// rendering engine, whose parameters are set via the CSMTextSettings SWF
+/// tag or ActionScript. The parameters are kept even while the standard
+/// engine is in use, but only the advanced engine looks at them.
 #[derive(Debug, PartialEq, Clone, Collect)]
 #[collect(require_static)]
-pub enum TextRenderSettings {
-    /// This text should render with the standard rendering engine.
-    /// Set via "Anti-alias for animation" in the Flash IDE.
-    Default,
-
-    /// This text should render with the advanced rendering engine.
-    /// Set via "Anti-alias for readibility" in the Flash IDE.
-    /// The parameters are set via the CSMTextSettings SWF tag.
-    /// Ruffle does not support this currently, but this also affects
-    /// hit-testing behavior.
-    Advanced {
-        grid_fit: TextGridFit,
-        thickness: f32,
-        sharpness: f32,
-    },
+pub struct TextRenderSettings {
+    /// Whether this text renders with the advanced rendering engine.
+    /// This also affects hit-testing behavior.
+    pub is_advanced: bool,
+
+    /// How the advanced engine fits glyphs to the pixel grid.
+    pub grid_fit: TextGridFit,
+
+    /// How much the advanced engine thickens glyph edges, from -200 to 200.
+    ///
+    /// Ruffle draws glyphs as vector outlines, and does not support this
+    /// currently.
+    pub thickness: f32,
+
+    /// How much the advanced engine sharpens glyph edges, from -400 to 400.
+    ///
+    /// Ruffle draws glyphs as vector outlines, and does not support this
+    /// currently.
+    pub sharpness: f32,
 }
 
 impl TextRenderSettings {
     pub fn is_advanced(&self) -> bool {
-        matches!(self, TextRenderSettings::Advanced { .. })
+        self.is_advanced
+    }
+
+    /// Whether glyphs should be moved onto whole pixels before they are
+    /// drawn.
+    pub fn snaps_to_pixels(&self) -> bool {
+        self.is_advanced && self.grid_fit == TextGridFit::Pixel
     }
 }
 
 impl Default for TextRenderSettings {
     fn default() -> Self {
-        TextRenderSettings::Default
+        TextRenderSettings {
+            is_advanced: false,
+            grid_fit: TextGridFit::Pixel,
+            thickness: 0.0,
+            sharpness: 0.0,
+        }
     }
 }
 
 impl From<swf::CsmTextSettings> for TextRenderSettings {
     fn from(settings: swf::CsmTextSettings) -> Self {
-        if settings.use_advanced_rendering {
-            TextRenderSettings::Advanced {
-                grid_fit: settings.grid_fit,
-                thickness: settings.thickness,
-                sharpness: settings.sharpness,
-            }
-        } else {
-            TextRenderSettings::Default
+        TextRenderSettings {
+            is_advanced: settings.use_advanced_rendering,
+            grid_fit: settings.grid_fit,
+            thickness: settings.thickness,
+            sharpness: settings.sharpness,
         }
     }
 }
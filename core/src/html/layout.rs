@@ -402,11 +402,16 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
         // If this text field is set to use device fonts, fallback to using our embedded Noto Sans.
         // Note that the SWF can still contain a DefineFont tag with no glyphs/layout info in this case (see #451).
         // In an ideal world, device fonts would search for a matching font on the system and render it in some way.
-        if let Some(font) = library
-            .get_font_by_name(&span.font, span.bold, span.italic)
-            .filter(|f| !is_device_font && f.has_glyphs())
-            .or_else(|| context.library.device_font())
-        {
+        // Text fields that embed their fonts never fall back: without a matching embedded font, nothing renders.
+        let font = if is_device_font {
+            context.library.device_font()
+        } else {
+            library
+                .get_font_by_name(&span.font, span.bold, span.italic)
+                .filter(|f| f.has_glyphs())
+        };
+
+        if let Some(font) = font {
             self.font = Some(font);
             return self.font;
         }
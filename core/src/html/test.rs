@@ -2,6 +2,8 @@
 
 use crate::html::dimensions::{BoxBounds, Position, Size};
 use crate::html::text_format::{FormatSpans, TextFormat, TextSpan};
+use crate::xml::XmlDocument;
+use gc_arena::rootless_arena;
 use swf::{Rectangle, Twips};
 
 #[test]
@@ -863,3 +865,49 @@ fn formatspans_replace_text_degenerate() {
     assert_eq!((0, 1), fs.get_span_boundaries(0, 5));
     assert_eq!((1, 2), fs.get_span_boundaries(5, 9));
 }
+
+/// Lower a string of text field HTML into spans of `(text, url, underline)`.
+fn lower_html(html: &str) -> Vec<(String, String, bool)> {
+    rootless_arena(|mc| {
+        let document = XmlDocument::new(mc);
+        document.as_node().replace_with_html_str(mc, html);
+
+        let mut fs = FormatSpans::new();
+        fs.lower_from_html(document);
+
+        fs.iter_spans()
+            .map(|(_, _, text, span)| (text.to_string(), span.url.clone(), span.underline))
+            .collect()
+    })
+}
+
+#[test]
+fn formatspans_lower_from_html_links() {
+    assert_eq!(
+        lower_html(r#"<a href="event:go"><u>link</u></a> plain"#),
+        vec![
+            ("link".to_string(), "event:go".to_string(), true),
+            (" plain".to_string(), "".to_string(), false),
+        ]
+    );
+}
+
+#[test]
+fn formatspans_lower_from_html_malformed() {
+    // Mismatched end tags close everything opened since their start tag, and
+    // stray end tags and images contribute no text.
+    assert_eq!(
+        lower_html(r#"<u>under<a href="x">both</u> tail</i><img src="pic.png">!"#),
+        vec![
+            ("under".to_string(), "".to_string(), true),
+            ("both".to_string(), "x".to_string(), true),
+            (" tail!".to_string(), "".to_string(), false),
+        ]
+    );
+
+    // Unclosed tags run to the end of the text.
+    assert_eq!(
+        lower_html("<li><u>item"),
+        vec![("item\n".to_string(), "".to_string(), true)]
+    );
+}
@@ -24,6 +24,7 @@ mod character;
 mod collect;
 pub mod color_transform;
 pub mod context;
+pub mod context_menu;
 mod drawing;
 mod ecma_conversions;
 pub mod events;
@@ -2,16 +2,19 @@
 
 use crate::avm1::activation::{Activation, ActivationIdentifier};
 use crate::avm1::{Avm1, AvmString, Object, TObject, Value};
-use crate::avm2::Domain as Avm2Domain;
+use crate::avm2::{
+    Activation as Avm2Activation, Avm2, Domain as Avm2Domain, Event as Avm2Event,
+    EventData as Avm2EventData, Namespace as Avm2Namespace, Object as Avm2Object,
+    QName as Avm2QName, TObject as _, Value as Avm2Value,
+};
 use crate::backend::navigator::OwnedFuture;
-use crate::context::{ActionQueue, ActionType};
+use crate::context::{ActionQueue, ActionType, UpdateContext};
 use crate::display_object::{DisplayObject, MorphShape, TDisplayObject};
 use crate::player::{Player, NEWEST_PLAYER_VERSION};
 use crate::property_map::PropertyMap;
 use crate::tag_utils::SwfMovie;
 use crate::vminterface::Instantiator;
 use crate::xml::XmlNode;
-use encoding_rs::UTF_8;
 use gc_arena::{Collect, CollectionContext, MutationContext};
 use generational_arena::{Arena, Index};
 use std::string::FromUtf8Error;
@@ -41,9 +44,21 @@ pub enum Error {
     #[error("Non-XML loader spawned as XML loader")]
     NotXmlLoader,
 
+    #[error("Non-NetStream loader spawned as NetStream loader")]
+    NotNetStreamLoader,
+
+    #[error("Non-URLStream loader spawned as URLStream loader")]
+    NotUrlStreamLoader,
+
+    #[error("Non-remoting loader spawned as remoting loader")]
+    NotRemotingLoader,
+
     #[error("Could not fetch movie {0}")]
     FetchError(String),
 
+    #[error("HTTP status {1} when fetching {0}")]
+    HttpNotOk(String, u16),
+
     #[error("Invalid SWF")]
     InvalidSwf(#[from] crate::tag_utils::Error),
 
@@ -60,6 +75,9 @@ pub enum Error {
     // the GC arena). We're losing info here. How do we fix that?
     #[error("Error running avm1 script: {0}")]
     Avm1Error(String),
+
+    #[error("Error running avm2 script: {0}")]
+    Avm2Error(String),
 }
 
 pub type FormLoadHandler<'gc> =
@@ -140,6 +158,10 @@ impl<'gc> LoadManager<'gc> {
 
     /// Kick off a movie clip load.
     ///
+    /// The loaded movie's AVM2 code will be loaded into `avm2_domain`, such as
+    /// one selected by a `LoaderContext`. If not provided, the movie gets a
+    /// fresh domain of its own.
+    ///
     /// Returns the loader's async process, which you will need to spawn.
     pub fn load_movie_into_clip(
         &mut self,
@@ -148,11 +170,13 @@ impl<'gc> LoadManager<'gc> {
         fetch: OwnedFuture<Vec<u8>, Error>,
         url: String,
         target_broadcaster: Option<Object<'gc>>,
+        avm2_domain: Option<Avm2Domain<'gc>>,
     ) -> OwnedFuture<(), Error> {
         let loader = Loader::Movie {
             self_handle: None,
             target_clip,
             target_broadcaster,
+            avm2_domain,
             loader_status: LoaderStatus::Pending,
         };
         let handle = self.add_loader(loader);
@@ -250,6 +274,77 @@ impl<'gc> LoadManager<'gc> {
 
         loader.xml_loader(player, fetch)
     }
+
+    /// Kick off a progressive media download into an AVM2 `NetStream`.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn load_media_into_net_stream(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        target_stream: Avm2Object<'gc>,
+        fetch: OwnedFuture<Vec<u8>, Error>,
+        url: String,
+    ) -> OwnedFuture<(), Error> {
+        let loader = Loader::NetStream {
+            self_handle: None,
+            target_stream,
+            url,
+        };
+        let handle = self.add_loader(loader);
+
+        let loader = self.get_loader_mut(handle).unwrap();
+        loader.introduce_loader_handle(handle);
+
+        loader.net_stream_loader(player, fetch)
+    }
+
+    /// Kick off a binary download into an AVM2 `URLStream`.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn load_data_into_url_stream(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        target_stream: Avm2Object<'gc>,
+        fetch: OwnedFuture<Vec<u8>, Error>,
+        url: String,
+    ) -> OwnedFuture<(), Error> {
+        let loader = Loader::UrlStream {
+            self_handle: None,
+            target_stream,
+            url,
+        };
+        let handle = self.add_loader(loader);
+
+        let loader = self.get_loader_mut(handle).unwrap();
+        loader.introduce_loader_handle(handle);
+
+        loader.url_stream_loader(player, fetch)
+    }
+
+    /// Kick off a Flash Remoting call made with `NetConnection.call`.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn load_remoting_response(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        connection: Avm2Object<'gc>,
+        responder: Option<Avm2Object<'gc>>,
+        fetch: OwnedFuture<Vec<u8>, Error>,
+        response: String,
+    ) -> OwnedFuture<(), Error> {
+        let loader = Loader::Remoting {
+            self_handle: None,
+            connection,
+            responder,
+            response,
+        };
+        let handle = self.add_loader(loader);
+
+        let loader = self.get_loader_mut(handle).unwrap();
+        loader.introduce_loader_handle(handle);
+
+        loader.remoting_loader(player, fetch)
+    }
 }
 
 impl<'gc> Default for LoadManager<'gc> {
@@ -294,6 +389,12 @@ pub enum Loader<'gc> {
         /// into.
         target_broadcaster: Option<Object<'gc>>,
 
+        /// The AVM2 application domain to load the movie's code into.
+        ///
+        /// If `None`, the movie is given a new domain parented to the global
+        /// domain.
+        avm2_domain: Option<Avm2Domain<'gc>>,
+
         /// Indicates the completion status of this loader.
         ///
         /// This flag exists to prevent a situation in which loading a movie
@@ -342,6 +443,57 @@ pub enum Loader<'gc> {
         /// The target node whose contents will be replaced with the parsed XML.
         target_node: XmlNode<'gc>,
     },
+
+    /// Loader that is downloading media for an AVM2 `NetStream`.
+    NetStream {
+        /// The handle to refer to this loader instance.
+        #[collect(require_static)]
+        self_handle: Option<Handle>,
+
+        /// The stream that requested the media.
+        target_stream: Avm2Object<'gc>,
+
+        /// The URL the stream was asked to play.
+        ///
+        /// If the stream has since been closed or told to play something
+        /// else, the downloaded data is discarded.
+        #[collect(require_static)]
+        url: String,
+    },
+
+    /// Loader that is downloading binary data for an AVM2 `URLStream`.
+    UrlStream {
+        /// The handle to refer to this loader instance.
+        #[collect(require_static)]
+        self_handle: Option<Handle>,
+
+        /// The stream that requested the data.
+        target_stream: Avm2Object<'gc>,
+
+        /// The URL the stream was asked to load.
+        ///
+        /// If the stream has since been closed or told to load something
+        /// else, the downloaded data is discarded.
+        #[collect(require_static)]
+        url: String,
+    },
+
+    /// Loader that is waiting for the reply to an AVM2 `NetConnection.call`.
+    Remoting {
+        /// The handle to refer to this loader instance.
+        #[collect(require_static)]
+        self_handle: Option<Handle>,
+
+        /// The connection the call was made on.
+        connection: Avm2Object<'gc>,
+
+        /// The `Responder` to hand the reply to, if the call has one.
+        responder: Option<Avm2Object<'gc>>,
+
+        /// The response URI that the reply to this call is addressed to.
+        #[collect(require_static)]
+        response: String,
+    },
 }
 
 impl<'gc> Loader<'gc> {
@@ -356,6 +508,9 @@ impl<'gc> Loader<'gc> {
             Loader::Form { self_handle, .. } => *self_handle = Some(handle),
             Loader::LoadVars { self_handle, .. } => *self_handle = Some(handle),
             Loader::Xml { self_handle, .. } => *self_handle = Some(handle),
+            Loader::NetStream { self_handle, .. } => *self_handle = Some(handle),
+            Loader::UrlStream { self_handle, .. } => *self_handle = Some(handle),
+            Loader::Remoting { self_handle, .. } => *self_handle = Some(handle),
         }
     }
 
@@ -472,8 +627,14 @@ impl<'gc> Loader<'gc> {
                     .lock()
                     .expect("Could not lock player!!")
                     .update(|uc| {
-                        let domain =
-                            Avm2Domain::movie_domain(uc.gc_context, uc.avm2.global_domain());
+                        let domain = match uc.load_manager.get_loader(handle) {
+                            Some(Loader::Movie { avm2_domain, .. }) => *avm2_domain,
+                            None => return Err(Error::Cancelled),
+                            _ => unreachable!(),
+                        }
+                        .unwrap_or_else(|| {
+                            Avm2Domain::movie_domain(uc.gc_context, uc.avm2.global_domain())
+                        });
                         uc.library
                             .library_for_movie_mut(movie.clone())
                             .set_avm2_domain(domain);
@@ -672,8 +833,10 @@ impl<'gc> Loader<'gc> {
                 match data {
                     Ok(data) => {
                         // Fire the onData method with the loaded string.
-                        let string_data =
-                            AvmString::new(activation.context.gc_context, UTF_8.decode(&data).0);
+                        let string_data = AvmString::new(
+                            activation.context.gc_context,
+                            activation.context.system.decode_text(&data),
+                        );
                         let _ = that.call_method("onData", &[string_data.into()], &mut activation);
                     }
                     Err(_) => {
@@ -758,10 +921,9 @@ impl<'gc> Loader<'gc> {
         Box::pin(async move {
             let data = fetch.await;
             if let Ok(data) = data {
-                let xmlstring = String::from_utf8(data)?;
-
                 player.lock().expect("Could not lock player!!").update(
                     |uc| -> Result<(), Error> {
+                        let xmlstring = uc.system.decode_text(&data);
                         let (mut node, active_clip) = match uc.load_manager.get_loader(handle) {
                             Some(Loader::Xml {
                                 target_node,
@@ -837,4 +999,651 @@ impl<'gc> Loader<'gc> {
             Ok(())
         })
     }
+
+    pub fn net_stream_loader(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        fetch: OwnedFuture<Vec<u8>, Error>,
+    ) -> OwnedFuture<(), Error> {
+        let handle = match self {
+            Loader::NetStream { self_handle, .. } => {
+                self_handle.expect("Loader not self-introduced")
+            }
+            _ => return Box::pin(async { Err(Error::NotNetStreamLoader) }),
+        };
+
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(async move {
+            let data = fetch.await;
+
+            player
+                .lock()
+                .expect("Could not lock player!!")
+                .update(|uc| -> Result<(), Error> {
+                    let (mut stream, url) = match uc.load_manager.get_loader(handle) {
+                        Some(Loader::NetStream {
+                            target_stream, url, ..
+                        }) => (*target_stream, url.clone()),
+                        None => return Err(Error::Cancelled),
+                        _ => return Err(Error::NotNetStreamLoader),
+                    };
+
+                    let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+                    let current_url = stream
+                        .get_property(
+                            stream,
+                            &Avm2QName::new(Avm2Namespace::Private("ruffle".into()), "url"),
+                            &mut activation,
+                        )
+                        .map_err(|e| Error::Avm2Error(e.to_string()))?;
+
+                    if !matches!(current_url, Avm2Value::String(s) if s.as_str() == url) {
+                        return Err(Error::Cancelled);
+                    }
+
+                    let (code, level) = match data {
+                        Ok(data) => {
+                            for name in &["bytesLoaded", "bytesTotal"] {
+                                stream
+                                    .set_property(
+                                        stream,
+                                        &Avm2QName::new(
+                                            Avm2Namespace::Private("ruffle".into()),
+                                            *name,
+                                        ),
+                                        (data.len() as f64).into(),
+                                        &mut activation,
+                                    )
+                                    .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                            }
+
+                            ("NetStream.Buffer.Full", "status")
+                        }
+                        Err(_) => ("NetStream.Play.StreamNotFound", "error"),
+                    };
+
+                    Avm2::dispatch_net_status(&mut activation.context, stream, code, level)
+                        .map_err(|e| Error::Avm2Error(e.to_string()))?;
+
+                    Ok(())
+                })
+        })
+    }
+
+    /// Construct a future for the given URLStream loader.
+    ///
+    /// The downloaded data is handed to the stream in chunks of
+    /// `URL_STREAM_CHUNK_SIZE`, each announced with a `progress` event, so
+    /// that the stream can be read while the rest of the data is pending.
+    pub fn url_stream_loader(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        fetch: OwnedFuture<Vec<u8>, Error>,
+    ) -> OwnedFuture<(), Error> {
+        let handle = match self {
+            Loader::UrlStream { self_handle, .. } => {
+                self_handle.expect("Loader not self-introduced")
+            }
+            _ => return Box::pin(async { Err(Error::NotUrlStreamLoader) }),
+        };
+
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(async move {
+            let data = fetch.await;
+            let status = http_status(&data);
+
+            player
+                .lock()
+                .expect("Could not lock player!!")
+                .update(|uc| url_stream_http_status(uc, handle, status))?;
+
+            let data = match data {
+                Ok(data) => data,
+                Err(_) => {
+                    return player
+                        .lock()
+                        .expect("Could not lock player!!")
+                        .update(|uc| url_stream_failed(uc, handle));
+                }
+            };
+
+            player
+                .lock()
+                .expect("Could not lock player!!")
+                .update(|uc| url_stream_opened(uc, handle))?;
+
+            for chunk in data.chunks(URL_STREAM_CHUNK_SIZE) {
+                player
+                    .lock()
+                    .expect("Could not lock player!!")
+                    .update(|uc| url_stream_progress(uc, handle, chunk, data.len()))?;
+            }
+
+            player
+                .lock()
+                .expect("Could not lock player!!")
+                .update(|uc| url_stream_completed(uc, handle))
+        })
+    }
+
+    /// Construct a future for the given remoting loader.
+    ///
+    /// A call that can't reach the gateway is announced on its connection
+    /// with a `NetConnection.Call.Failed` status.
+    pub fn remoting_loader(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        fetch: OwnedFuture<Vec<u8>, Error>,
+    ) -> OwnedFuture<(), Error> {
+        let handle = match self {
+            Loader::Remoting { self_handle, .. } => {
+                self_handle.expect("Loader not self-introduced")
+            }
+            _ => return Box::pin(async { Err(Error::NotRemotingLoader) }),
+        };
+
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(async move {
+            let data = fetch.await;
+
+            player
+                .lock()
+                .expect("Could not lock player!!")
+                .update(|uc| -> Result<(), Error> {
+                    let (connection, responder, response) = match uc.load_manager.get_loader(handle)
+                    {
+                        Some(Loader::Remoting {
+                            connection,
+                            responder,
+                            response,
+                            ..
+                        }) => (*connection, *responder, response.clone()),
+                        None => return Err(Error::Cancelled),
+                        _ => return Err(Error::NotRemotingLoader),
+                    };
+
+                    match (data, responder) {
+                        (Ok(data), Some(responder)) => Avm2::dispatch_remoting_response(
+                            uc, connection, responder, &response, &data,
+                        )
+                        .map_err(|e| Error::Avm2Error(e.to_string()))?,
+                        (Ok(_), None) => {}
+                        (Err(_), _) => {
+                            Avm2::dispatch_net_status(
+                                uc,
+                                connection,
+                                "NetConnection.Call.Failed",
+                                "error",
+                            )
+                            .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                        }
+                    }
+
+                    Ok(())
+                })
+        })
+    }
+}
+
+/// The most data a `URLStream` loader hands to its stream at once.
+const URL_STREAM_CHUNK_SIZE: usize = 64 * 1024;
+
+/// The HTTP status code to report for the outcome of a fetch.
+///
+/// A fetch that failed without any response, such as one that couldn't
+/// connect, reports a status of 0 as Flash Player does.
+fn http_status(result: &Result<Vec<u8>, Error>) -> i32 {
+    match result {
+        Ok(_) => 200,
+        Err(Error::HttpNotOk(_, status)) => i32::from(*status),
+        Err(_) => 0,
+    }
+}
+
+/// Get the stream a URLStream loader is downloading into.
+///
+/// Yields `Error::Cancelled` if the stream has since been closed or told to
+/// load something else.
+fn url_stream_target<'gc>(
+    uc: &mut UpdateContext<'_, 'gc, '_>,
+    handle: Handle,
+) -> Result<Avm2Object<'gc>, Error> {
+    let (mut stream, url) = match uc.load_manager.get_loader(handle) {
+        Some(Loader::UrlStream {
+            target_stream, url, ..
+        }) => (*target_stream, url.clone()),
+        None => return Err(Error::Cancelled),
+        _ => return Err(Error::NotUrlStreamLoader),
+    };
+
+    let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+    let current_url = stream
+        .get_property(
+            stream,
+            &Avm2QName::new(Avm2Namespace::Private("ruffle".into()), "url"),
+            &mut activation,
+        )
+        .map_err(|e| Error::Avm2Error(e.to_string()))?;
+
+    if !matches!(current_url, Avm2Value::String(s) if s.as_str() == url) {
+        return Err(Error::Cancelled);
+    }
+
+    Ok(stream)
+}
+
+/// Announce the HTTP status of a URLStream's download with an `httpStatus`
+/// event.
+///
+/// This is sent for successful downloads too, before any of their data.
+fn url_stream_http_status(
+    uc: &mut UpdateContext<'_, '_, '_>,
+    handle: Handle,
+    status: i32,
+) -> Result<(), Error> {
+    let stream = url_stream_target(uc, handle)?;
+    let response_url = match uc.load_manager.get_loader(handle) {
+        Some(Loader::UrlStream { url, .. }) => AvmString::new(uc.gc_context, url.clone()),
+        _ => return Err(Error::NotUrlStreamLoader),
+    };
+
+    let mut event = Avm2Event::new("httpStatus");
+    event.set_event_data(Avm2EventData::HttpStatus {
+        status,
+        response_url: Some(response_url),
+        response_headers: None,
+    });
+    Avm2::dispatch_event(uc, event, stream).map_err(|e| Error::Avm2Error(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Announce that a URLStream's download has started.
+fn url_stream_opened(uc: &mut UpdateContext<'_, '_, '_>, handle: Handle) -> Result<(), Error> {
+    let stream = url_stream_target(uc, handle)?;
+
+    Avm2::dispatch_event(uc, Avm2Event::new("open"), stream)
+        .map_err(|e| Error::Avm2Error(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Buffer a chunk of a URLStream's download and announce it with a
+/// `progress` event.
+fn url_stream_progress(
+    uc: &mut UpdateContext<'_, '_, '_>,
+    handle: Handle,
+    chunk: &[u8],
+    bytes_total: usize,
+) -> Result<(), Error> {
+    let mut stream = url_stream_target(uc, handle)?;
+
+    if let Some(mut buffer) = stream.as_bytearray_mut(uc.gc_context) {
+        // Bytes that have been read are dropped, so that a long download
+        // doesn't have to be held in memory all at once.
+        buffer.discard_read();
+        buffer.append(chunk);
+    }
+
+    let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+    let bytes_loaded_name = Avm2QName::new(Avm2Namespace::Private("ruffle".into()), "bytesLoaded");
+    let bytes_loaded = stream
+        .get_property(stream, &bytes_loaded_name, &mut activation)
+        .and_then(|v| v.coerce_to_number(&mut activation))
+        .map_err(|e| Error::Avm2Error(e.to_string()))?
+        + chunk.len() as f64;
+    stream
+        .set_property(
+            stream,
+            &bytes_loaded_name,
+            bytes_loaded.into(),
+            &mut activation,
+        )
+        .map_err(|e| Error::Avm2Error(e.to_string()))?;
+
+    let mut event = Avm2Event::new("progress");
+    event.set_event_data(Avm2EventData::Progress {
+        bytes_loaded,
+        bytes_total: bytes_total as f64,
+    });
+    Avm2::dispatch_event(&mut activation.context, event, stream)
+        .map_err(|e| Error::Avm2Error(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Announce that a URLStream's download has finished.
+fn url_stream_completed(uc: &mut UpdateContext<'_, '_, '_>, handle: Handle) -> Result<(), Error> {
+    let stream = url_stream_target(uc, handle)?;
+
+    Avm2::dispatch_event(uc, Avm2Event::new("complete"), stream)
+        .map_err(|e| Error::Avm2Error(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Close a URLStream whose download failed, and announce it with an
+/// `ioError` event.
+fn url_stream_failed(uc: &mut UpdateContext<'_, '_, '_>, handle: Handle) -> Result<(), Error> {
+    let mut stream = url_stream_target(uc, handle)?;
+    let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+
+    stream
+        .set_property(
+            stream,
+            &Avm2QName::new(Avm2Namespace::Private("ruffle".into()), "url"),
+            Avm2Value::Null,
+            &mut activation,
+        )
+        .map_err(|e| Error::Avm2Error(e.to_string()))?;
+
+    Avm2::dispatch_event(&mut activation.context, Avm2Event::new("ioError"), stream)
+        .map_err(|e| Error::Avm2Error(e.to_string()))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::test_utils::trace_with_avm2;
+    use crate::avm2::{Error as Avm2Error, FunctionObject};
+
+    fn call_method<'gc>(
+        activation: &mut Avm2Activation<'_, 'gc, '_>,
+        mut object: Avm2Object<'gc>,
+        name: &'static str,
+        args: &[Avm2Value<'gc>],
+    ) -> Result<Avm2Value<'gc>, Avm2Error> {
+        let method = object
+            .get_property(
+                object,
+                &Avm2QName::new(Avm2Namespace::public(), name),
+                activation,
+            )?
+            .coerce_to_object(activation)?;
+
+        method.call(Some(object), args, activation, None)
+    }
+
+    fn get<'gc>(
+        activation: &mut Avm2Activation<'_, 'gc, '_>,
+        mut object: Avm2Object<'gc>,
+        name: &'static str,
+    ) -> Result<f64, Avm2Error> {
+        object
+            .get_property(
+                object,
+                &Avm2QName::new(Avm2Namespace::public(), name),
+                activation,
+            )?
+            .coerce_to_number(activation)
+    }
+
+    /// A listener that traces each event, and reads every whole `int` that
+    /// has arrived so far.
+    fn read_ints<'gc>(
+        activation: &mut Avm2Activation<'_, 'gc, '_>,
+        _this: Option<Avm2Object<'gc>>,
+        args: &[Avm2Value<'gc>],
+    ) -> Result<Avm2Value<'gc>, Avm2Error> {
+        let mut event = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Avm2Value::Undefined)
+            .coerce_to_object(activation)?;
+        let event_type = event
+            .get_property(
+                event,
+                &Avm2QName::new(Avm2Namespace::public(), "type"),
+                activation,
+            )?
+            .coerce_to_string(activation)?;
+        let stream = event
+            .get_property(
+                event,
+                &Avm2QName::new(Avm2Namespace::public(), "target"),
+                activation,
+            )?
+            .coerce_to_object(activation)?;
+
+        if event_type == "progress" {
+            let loaded = get(activation, event, "bytesLoaded")?;
+            let total = get(activation, event, "bytesTotal")?;
+            activation
+                .context
+                .log
+                .avm_trace(&format!("progress {}/{}", loaded, total));
+        } else {
+            activation.context.log.avm_trace(&event_type);
+        }
+
+        while get(activation, stream, "bytesAvailable")? >= 4.0 {
+            let value =
+                call_method(activation, stream, "readInt", &[])?.coerce_to_i32(activation)?;
+            activation
+                .context
+                .log
+                .avm_trace(&format!("readInt {}", value));
+        }
+
+        if get(activation, stream, "bytesAvailable")? > 0.0 {
+            let error = call_method(activation, stream, "readInt", &[]).unwrap_err();
+            let available = get(activation, stream, "bytesAvailable")?;
+            activation
+                .context
+                .log
+                .avm_trace(&format!("{} ({} available)", error, available));
+        }
+
+        Ok(Avm2Value::Undefined)
+    }
+
+    /// Construct a `URLStream` that calls `listener` for each of the given
+    /// event types.
+    fn url_stream<'gc>(
+        activation: &mut Avm2Activation<'_, 'gc, '_>,
+        event_types: &[&'static str],
+        listener: Avm2Object<'gc>,
+    ) -> Avm2Object<'gc> {
+        let mut class = activation
+            .avm2()
+            .global_domain()
+            .get_defined_value(
+                activation,
+                Avm2QName::new(Avm2Namespace::package("flash.net"), "URLStream"),
+            )
+            .unwrap()
+            .coerce_to_object(activation)
+            .unwrap();
+        let proto = class
+            .get_property(
+                class,
+                &Avm2QName::new(Avm2Namespace::public(), "prototype"),
+                activation,
+            )
+            .unwrap()
+            .coerce_to_object(activation)
+            .unwrap();
+        let stream = proto.construct(activation, &[]).unwrap();
+        class
+            .call(Some(stream), &[], activation, stream.proto())
+            .unwrap();
+
+        for event_type in event_types {
+            call_method(
+                activation,
+                stream,
+                "addEventListener",
+                &[(*event_type).into(), listener.into()],
+            )
+            .unwrap();
+        }
+
+        stream
+    }
+
+    /// Start `stream` loading `url`, and add a mock download for it.
+    ///
+    /// The null navigator never runs the fetch this starts, so the mock
+    /// download for the same URL stands in for it.
+    fn load_mock<'gc>(
+        activation: &mut Avm2Activation<'_, 'gc, '_>,
+        stream: Avm2Object<'gc>,
+        url: &'static str,
+    ) -> Handle {
+        call_method(activation, stream, "load", &[url.into()]).unwrap();
+
+        activation
+            .context
+            .load_manager
+            .add_loader(Loader::UrlStream {
+                self_handle: None,
+                target_stream: stream,
+                url: url.to_string(),
+            })
+    }
+
+    /// A listener that traces the type of each event, along with the status
+    /// and response URL of `httpStatus` events.
+    fn trace_http_status<'gc>(
+        activation: &mut Avm2Activation<'_, 'gc, '_>,
+        _this: Option<Avm2Object<'gc>>,
+        args: &[Avm2Value<'gc>],
+    ) -> Result<Avm2Value<'gc>, Avm2Error> {
+        let mut event = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Avm2Value::Undefined)
+            .coerce_to_object(activation)?;
+        let event_type = event
+            .get_property(
+                event,
+                &Avm2QName::new(Avm2Namespace::public(), "type"),
+                activation,
+            )?
+            .coerce_to_string(activation)?;
+
+        if event_type == "httpStatus" {
+            let status = get(activation, event, "status")?;
+            let response_url = event
+                .get_property(
+                    event,
+                    &Avm2QName::new(Avm2Namespace::public(), "responseURL"),
+                    activation,
+                )?
+                .coerce_to_string(activation)?;
+            activation
+                .context
+                .log
+                .avm_trace(&format!("httpStatus {} {}", status, response_url));
+        } else {
+            activation.context.log.avm_trace(&event_type);
+        }
+
+        Ok(Avm2Value::Undefined)
+    }
+
+    #[test]
+    fn url_stream_reads_chunks_as_they_arrive() {
+        let traces = trace_with_avm2(|activation| {
+            let listener = FunctionObject::from_builtin(
+                activation.context.gc_context,
+                read_ints,
+                activation.avm2().prototypes().function,
+            );
+            let mut stream = url_stream(activation, &["open", "progress", "complete"], listener);
+            let handle = load_mock(activation, stream, "mock.bin");
+
+            let data = [0, 0, 0, 1, 0, 0, 0, 2, 0xFF, 0xFF, 0xFF, 0xFD];
+            url_stream_opened(&mut activation.context, handle).unwrap();
+            for chunk in data.chunks(6) {
+                url_stream_progress(&mut activation.context, handle, chunk, data.len()).unwrap();
+            }
+            url_stream_completed(&mut activation.context, handle).unwrap();
+
+            let connected = stream
+                .get_property(
+                    stream,
+                    &Avm2QName::new(Avm2Namespace::public(), "connected"),
+                    activation,
+                )
+                .unwrap();
+            assert!(matches!(connected, Avm2Value::Bool(true)));
+
+            // Once closed, the stream ignores the rest of the download.
+            call_method(activation, stream, "close", &[]).unwrap();
+            assert!(matches!(
+                url_stream_progress(&mut activation.context, handle, &data, data.len()),
+                Err(Error::Cancelled)
+            ));
+        });
+
+        assert_eq!(
+            traces,
+            vec![
+                "open",
+                "progress 6/12",
+                "readInt 1",
+                "EOFError: Error #2030: End of file was encountered. (2 available)",
+                "progress 12/12",
+                "readInt 2",
+                "readInt -3",
+                "complete",
+            ]
+        );
+    }
+
+    #[test]
+    fn url_stream_reports_http_status() {
+        let traces = trace_with_avm2(|activation| {
+            let event_types = ["httpStatus", "open", "complete", "ioError"];
+            let listener = FunctionObject::from_builtin(
+                activation.context.gc_context,
+                trace_http_status,
+                activation.avm2().prototypes().function,
+            );
+            let stream = url_stream(activation, &event_types, listener);
+
+            let handle = load_mock(activation, stream, "found.bin");
+            url_stream_http_status(&mut activation.context, handle, http_status(&Ok(vec![])))
+                .unwrap();
+            url_stream_opened(&mut activation.context, handle).unwrap();
+            url_stream_completed(&mut activation.context, handle).unwrap();
+
+            let missing = Err(Error::HttpNotOk("missing.bin".to_string(), 404));
+            let handle = load_mock(activation, stream, "missing.bin");
+            url_stream_http_status(&mut activation.context, handle, http_status(&missing)).unwrap();
+            url_stream_failed(&mut activation.context, handle).unwrap();
+
+            // A download that never got a response has no status.
+            let unreachable = Err(Error::NetworkUnavailable);
+            let handle = load_mock(activation, stream, "offline.bin");
+            url_stream_http_status(&mut activation.context, handle, http_status(&unreachable))
+                .unwrap();
+            url_stream_failed(&mut activation.context, handle).unwrap();
+        });
+
+        assert_eq!(
+            traces,
+            vec![
+                "httpStatus 200 found.bin",
+                "open",
+                "complete",
+                "httpStatus 404 missing.bin",
+                "ioError",
+                "httpStatus 0 offline.bin",
+                "ioError",
+            ]
+        );
+    }
 }
@@ -1,6 +1,6 @@
 use crate::avm1::activation::{Activation, ActivationIdentifier};
 use crate::avm1::debug::VariableDumper;
-use crate::avm1::globals::system::SystemProperties;
+use crate::avm1::globals::system::{SystemCapabilities, SystemProperties};
 use crate::avm1::object::Object;
 use crate::avm1::property::Attribute;
 use crate::avm1::{Avm1, AvmString, ScriptObject, TObject, Timers, Value};
@@ -12,11 +12,12 @@ use crate::backend::{
     navigator::{NavigatorBackend, RequestOptions},
     render::RenderBackend,
     storage::StorageBackend,
-    ui::{MouseCursor, UiBackend},
+    ui::{MouseCursor, StageOrientation, UiBackend},
     video::VideoBackend,
 };
-use crate::config::Letterbox;
+use crate::config::{Letterbox, StageScaleMode};
 use crate::context::{ActionQueue, ActionType, RenderContext, UpdateContext};
+use crate::context_menu::{self, ContextMenuState};
 use crate::display_object::{EditText, MorphShape, MovieClip};
 use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult, KeyCode, PlayerEvent};
 use crate::external::Value as ExternalValue;
@@ -66,6 +67,9 @@ struct GcRootData<'gc> {
     /// The object being dragged via a `startDrag` action.
     drag_object: Option<DragObject<'gc>>,
 
+    /// The context menu the host is currently showing, if any.
+    current_context_menu: Option<ContextMenuState<'gc>>,
+
     /// Interpreter state for AVM1 code.
     avm1: Avm1<'gc>,
 
@@ -109,6 +113,7 @@ impl<'gc> GcRootData<'gc> {
         &mut Avm1<'gc>,
         &mut Avm2<'gc>,
         &mut Option<DragObject<'gc>>,
+        &mut Option<ContextMenuState<'gc>>,
         &mut LoadManager<'gc>,
         &mut HashMap<String, Object<'gc>>,
         &mut Vec<EditText<'gc>>,
@@ -123,6 +128,7 @@ impl<'gc> GcRootData<'gc> {
             &mut self.avm1,
             &mut self.avm2,
             &mut self.drag_object,
+            &mut self.current_context_menu,
             &mut self.load_manager,
             &mut self.shared_objects,
             &mut self.unbound_text_fields,
@@ -198,16 +204,35 @@ pub struct Player {
 
     viewport_width: u32,
     viewport_height: u32,
+
+    /// The viewport size that content was last told about with a `resize`
+    /// event.
+    announced_viewport: (u32, u32),
+
+    /// The ratio of device pixels to viewport pixels, as reported by the
+    /// host.
+    contents_scale_factor: f64,
+
+    /// The scale factor that content was last told about with a `resize`
+    /// event.
+    announced_scale_factor: f64,
+
     movie_width: u32,
     movie_height: u32,
     letterbox: Letterbox,
 
+    /// How the stage is fitted into the viewport.
+    scale_mode: StageScaleMode,
+
     mouse_pos: (Twips, Twips),
     is_mouse_down: bool,
 
     /// The current mouse cursor icon.
     mouse_cursor: MouseCursor,
 
+    /// The mouse cursor icon forced by content via `Mouse.cursor`, if any.
+    forced_mouse_cursor: Option<MouseCursor>,
+
     system: SystemProperties,
 
     /// The current instance ID. Used to generate default `instanceN` names.
@@ -276,6 +301,7 @@ impl Player {
                         levels: BTreeMap::new(),
                         mouse_hovered_object: None,
                         drag_object: None,
+                        current_context_menu: None,
                         avm1: Avm1::new(gc_context, NEWEST_PLAYER_VERSION),
                         avm2: Avm2::new(gc_context),
                         action_queue: ActionQueue::new(),
@@ -299,11 +325,16 @@ impl Player {
             movie_height,
             viewport_width: movie_width,
             viewport_height: movie_height,
+            announced_viewport: (movie_width, movie_height),
+            contents_scale_factor: 1.0,
+            announced_scale_factor: 1.0,
             letterbox: Letterbox::Fullscreen,
+            scale_mode: StageScaleMode::default(),
 
             mouse_pos: (Twips::new(0), Twips::new(0)),
             is_mouse_down: false,
             mouse_cursor: MouseCursor::Arrow,
+            forced_mouse_cursor: None,
 
             renderer,
             audio,
@@ -338,6 +369,11 @@ impl Player {
 
         player.build_matrices();
         player.audio.set_frame_rate(frame_rate);
+        let supports_mp3 = player.audio.supports_mp3();
+        player
+            .system
+            .capabilities
+            .set(SystemCapabilities::MP3, supports_mp3);
         let player_box = Arc::new(Mutex::new(player));
         let mut player_lock = player_box.lock().unwrap();
         player_lock.self_reference = Some(Arc::downgrade(&player_box));
@@ -430,7 +466,7 @@ impl Player {
             let version_string = activation
                 .context
                 .system
-                .get_version_string(activation.context.avm1);
+                .get_version_string(activation.context.player_version);
             object.define_value(
                 activation.context.gc_context,
                 "$version",
@@ -586,9 +622,20 @@ impl Player {
         self.letterbox = letterbox
     }
 
+    pub fn scale_mode(&self) -> StageScaleMode {
+        self.scale_mode
+    }
+
+    pub fn set_scale_mode(&mut self, scale_mode: StageScaleMode) {
+        self.scale_mode = scale_mode;
+        self.build_matrices();
+    }
+
+    /// Only a stage that is shown whole has margins to letterbox.
     fn should_letterbox(&self) -> bool {
-        self.letterbox == Letterbox::On
-            || (self.letterbox == Letterbox::Fullscreen && self.ui.is_fullscreen())
+        self.scale_mode == StageScaleMode::ShowAll
+            && (self.letterbox == Letterbox::On
+                || (self.letterbox == Letterbox::Fullscreen && self.ui.is_fullscreen()))
     }
 
     pub fn warn_on_unsupported_content(&self) -> bool {
@@ -617,6 +664,111 @@ impl Player {
         self.build_matrices();
     }
 
+    pub fn contents_scale_factor(&self) -> f64 {
+        self.contents_scale_factor
+    }
+
+    /// Set the number of device pixels per viewport pixel, e.g. the window's
+    /// device pixel ratio on a high-DPI display.
+    ///
+    /// Content sees this as `Stage.contentsScaleFactor`, and is told about a
+    /// change with a `resize` event on the next frame.
+    pub fn set_contents_scale_factor(&mut self, contents_scale_factor: f64) {
+        self.contents_scale_factor = contents_scale_factor;
+    }
+
+    /// Dispatch `resize` on the stage if the viewport has changed size or
+    /// scale factor since content was last told.
+    ///
+    /// Hosts may resize the viewport many times a frame, e.g. while a window
+    /// is being dragged, so this only happens once per frame, with the size
+    /// the viewport ended up with. A viewport that changes and changes back
+    /// within a frame dispatches nothing.
+    fn dispatch_resize(&mut self) {
+        let viewport = (self.viewport_width, self.viewport_height);
+        if self.announced_viewport == viewport
+            && self.announced_scale_factor == self.contents_scale_factor
+        {
+            return;
+        }
+
+        self.announced_viewport = viewport;
+        self.announced_scale_factor = self.contents_scale_factor;
+        self.update(|context| {
+            if let Err(e) = Avm2::dispatch_resize(context) {
+                log::error!("Unhandled AVM2 exception in resize handler: {}", e);
+            }
+        });
+    }
+
+    /// Build the context menu for a right-click at the current mouse
+    /// position, and ask the host to show it.
+    ///
+    /// The host reports the chosen item with `run_context_menu_callback`.
+    pub fn show_context_menu(&mut self) {
+        let items = self.update(|context| {
+            let menu = context_menu::build_context_menu(context).unwrap_or_else(|e| {
+                log::error!("Unhandled AVM2 exception in context menu: {}", e);
+                ContextMenuState::new()
+            });
+            let items = menu.info().to_vec();
+
+            *context.current_context_menu = Some(menu);
+
+            items
+        });
+
+        self.ui.show_context_menu(&items);
+    }
+
+    /// Perform the action of the item at `index` of the context menu last
+    /// shown by `show_context_menu`.
+    pub fn run_context_menu_callback(&mut self, index: usize) {
+        self.update(|context| {
+            let callback = context
+                .current_context_menu
+                .take()
+                .and_then(|menu| menu.callback(index).cloned());
+
+            if let Some(callback) = callback {
+                if let Err(e) = context_menu::run_context_menu_callback(context, callback) {
+                    log::error!("Unhandled AVM2 exception in context menu: {}", e);
+                }
+            }
+        });
+    }
+
+    /// Let content know that the host has entered or left full screen.
+    pub fn set_fullscreen_state(&mut self, is_full: bool) {
+        self.update(|context| {
+            if let Err(e) = Avm2::dispatch_full_screen(context, is_full, false) {
+                log::error!("Unhandled AVM2 exception in fullScreen handler: {}", e);
+            }
+        });
+    }
+
+    /// Let content know that the host has turned the stage from one
+    /// orientation to another.
+    pub fn set_orientation_state(&mut self, before: StageOrientation, after: StageOrientation) {
+        self.update(|context| {
+            if let Err(e) = Avm2::dispatch_orientation_change(context, before, after) {
+                log::error!(
+                    "Unhandled AVM2 exception in orientationChange handler: {}",
+                    e
+                );
+            }
+        });
+    }
+
+    /// Let content know that the host's window has gained or lost focus.
+    pub fn set_focused(&mut self, is_focused: bool) {
+        self.update(|context| {
+            if let Err(e) = Avm2::dispatch_focus(context, is_focused) {
+                log::error!("Unhandled AVM2 exception in focus handler: {}", e);
+            }
+        });
+    }
+
     pub fn handle_event(&mut self, event: PlayerEvent) {
         let mut needs_render = self.needs_render;
 
@@ -680,16 +832,63 @@ impl Player {
             }
         }
 
+        // The system cursor is always given back when the player loses focus,
+        // even if content has hidden it.
+        if let PlayerEvent::FocusLost = event {
+            self.ui.set_mouse_visible(true);
+        }
+
         // Update mouse position from mouse events.
         if let PlayerEvent::MouseMove { x, y }
         | PlayerEvent::MouseDown { x, y }
         | PlayerEvent::MouseUp { x, y } = event
         {
+            let last_pos = self.mouse_pos;
             self.mouse_pos =
                 self.inverse_view_matrix * (Twips::from_pixels(x), Twips::from_pixels(y));
             if self.update_roll_over() {
                 needs_render = true;
             }
+
+            // Content that locked the mouse sees how far it moved, rather
+            // than where the cursor is.
+            if let PlayerEvent::MouseMove { .. } = event {
+                let position = (self.mouse_pos.0.to_pixels(), self.mouse_pos.1.to_pixels());
+                let movement = if self.ui.is_mouse_locked() {
+                    (
+                        (self.mouse_pos.0 - last_pos.0).to_pixels(),
+                        (self.mouse_pos.1 - last_pos.1).to_pixels(),
+                    )
+                } else {
+                    (0.0, 0.0)
+                };
+
+                self.mutate_with_update_context(|context| {
+                    if let Err(e) = Avm2::dispatch_mouse_move(context, position, movement) {
+                        log::error!("Unhandled AVM2 exception in mouseMove handler: {}", e);
+                    }
+                });
+            }
+        }
+
+        if let PlayerEvent::TouchBegin { id, x, y, pressure }
+        | PlayerEvent::TouchMove { id, x, y, pressure }
+        | PlayerEvent::TouchEnd { id, x, y, pressure } = event
+        {
+            let event_type = match event {
+                PlayerEvent::TouchBegin { .. } => "touchBegin",
+                PlayerEvent::TouchMove { .. } => "touchMove",
+                _ => "touchEnd",
+            };
+            let position =
+                self.inverse_view_matrix * (Twips::from_pixels(x), Twips::from_pixels(y));
+            let position = (position.0.to_pixels(), position.1.to_pixels());
+
+            self.mutate_with_update_context(|context| {
+                if let Err(e) = Avm2::dispatch_touch(context, event_type, id, position, pressure) {
+                    log::error!("Unhandled AVM2 exception in {} handler: {}", event_type, e);
+                }
+            });
         }
 
         // Propagate button events.
@@ -736,6 +935,18 @@ impl Player {
             });
         }
 
+        // Tab moves focus through the tab order, and Shift+Tab moves it back.
+        if let PlayerEvent::KeyDown {
+            key_code: KeyCode::Tab,
+        } = event
+        {
+            let reverse = self.ui.is_key_down(KeyCode::Shift);
+            self.mutate_with_update_context(|context| {
+                let tracker = context.focus_tracker;
+                tracker.cycle(context, reverse);
+            });
+        }
+
         // Propagate clip events.
         self.mutate_with_update_context(|context| {
             let (clip_event, listener) = match event {
@@ -897,6 +1108,11 @@ impl Player {
             }
         });
 
+        // Content may override the cursor of the hovered object.
+        if let Some(forced_cursor) = self.forced_mouse_cursor {
+            new_cursor = forced_cursor;
+        }
+
         // Update mouse cursor if it has changed.
         if new_cursor != self.mouse_cursor {
             self.mouse_cursor = new_cursor;
@@ -936,6 +1152,8 @@ impl Player {
     }
 
     pub fn run_frame(&mut self) {
+        self.dispatch_resize();
+
         self.update(|update_context| {
             // TODO: In what order are levels run?
             // NOTE: We have to copy all the layer pointers into a separate list
@@ -943,10 +1161,22 @@ impl Player {
             // want to run frames on
             let levels: Vec<_> = update_context.levels.values().copied().collect();
 
+            if let Err(e) = Avm2::dispatch_frame_event(update_context, "enterFrame") {
+                log::error!("Unhandled AVM2 exception in enterFrame handler: {}", e);
+            }
+
             for level in levels {
                 level.run_frame(update_context);
             }
 
+            Self::run_actions(update_context);
+
+            if let Err(e) = Avm2::dispatch_frame_event(update_context, "exitFrame") {
+                log::error!("Unhandled AVM2 exception in exitFrame handler: {}", e);
+            }
+
+            Self::run_workers(update_context);
+
             update_context.update_sounds();
         });
         self.needs_render = true;
@@ -981,6 +1211,10 @@ impl Player {
             for (_depth, level) in root_data.levels.iter() {
                 level.render(&mut render_context);
             }
+
+            root_data
+                .focus_tracker
+                .render_highlight(&mut render_context, root_data.avm2.stage_focus_rect());
         });
         transform_stack.pop();
 
@@ -1043,6 +1277,19 @@ impl Player {
         &self.locale
     }
 
+    /// Run a frame of every started `Worker`, then announce the messages
+    /// they and the stage have sent each other.
+    fn run_workers<'gc>(context: &mut UpdateContext<'_, 'gc, '_>) {
+        for (worker, root) in context.avm2.workers() {
+            let previous = context.avm2.set_current_worker(Some(worker));
+            root.run_frame(context);
+            Self::run_actions(context);
+            context.avm2.set_current_worker(previous);
+        }
+
+        Avm2::deliver_channel_messages(context);
+    }
+
     fn run_actions<'gc>(context: &mut UpdateContext<'_, 'gc, '_>) {
         // Note that actions can queue further actions, so a while loop is necessary here.
         while let Some(actions) = context.action_queue.pop_action() {
@@ -1163,18 +1410,31 @@ impl Player {
             (self.viewport_width as f32, self.viewport_height as f32);
         let movie_aspect = movie_width / movie_height;
         let viewport_aspect = viewport_width / viewport_height;
-        let (scale, margin_width, margin_height) = if viewport_aspect > movie_aspect {
-            let scale = viewport_height / movie_height;
-            (scale, (viewport_width - movie_width * scale) / 2.0, 0.0)
-        } else {
-            let scale = viewport_width / movie_width;
-            (scale, 0.0, (viewport_height - movie_height * scale) / 2.0)
+        let (scale_x, scale_y) = match self.scale_mode {
+            StageScaleMode::ShowAll if viewport_aspect > movie_aspect => (
+                viewport_height / movie_height,
+                viewport_height / movie_height,
+            ),
+            StageScaleMode::ShowAll => (viewport_width / movie_width, viewport_width / movie_width),
+            StageScaleMode::ExactFit => {
+                (viewport_width / movie_width, viewport_height / movie_height)
+            }
+            StageScaleMode::NoBorder if viewport_aspect > movie_aspect => {
+                (viewport_width / movie_width, viewport_width / movie_width)
+            }
+            StageScaleMode::NoBorder => (
+                viewport_height / movie_height,
+                viewport_height / movie_height,
+            ),
+            StageScaleMode::NoScale => (1.0, 1.0),
         };
+        let margin_width = (viewport_width - movie_width * scale_x) / 2.0;
+        let margin_height = (viewport_height - movie_height * scale_y) / 2.0;
         self.view_matrix = Matrix {
-            a: scale,
+            a: scale_x,
             b: 0.0,
             c: 0.0,
-            d: scale,
+            d: scale_y,
             tx: Twips::from_pixels(margin_width.into()),
             ty: Twips::from_pixels(margin_height.into()),
         };
@@ -1192,8 +1452,8 @@ impl Player {
             }
         } else {
             // No letterbox: full visible stage area
-            let margin_width = f64::from(margin_width / scale);
-            let margin_height = f64::from(margin_height / scale);
+            let margin_width = f64::from(margin_width / scale_x);
+            let margin_height = f64::from(margin_height / scale_y);
             BoundingBox {
                 x_min: Twips::from_pixels(-margin_width),
                 y_min: Twips::from_pixels(-margin_height),
@@ -1210,6 +1470,8 @@ impl Player {
     where
         F: for<'a, 'gc> FnOnce(&mut UpdateContext<'a, 'gc, '_>) -> R,
     {
+        let old_scale_mode = self.scale_mode;
+
         // We have to do this piecewise borrowing of fields before the closure to avoid
         // completely borrowing `self`.
         let (
@@ -1222,8 +1484,13 @@ impl Player {
             ui,
             rng,
             mouse_position,
+            mouse_cursor,
+            forced_mouse_cursor,
             stage_width,
             stage_height,
+            viewport_size,
+            scale_mode,
+            contents_scale_factor,
             player,
             system_properties,
             instance_counter,
@@ -1245,8 +1512,13 @@ impl Player {
             self.ui.deref_mut(),
             &mut self.rng,
             &self.mouse_pos,
+            &mut self.mouse_cursor,
+            &mut self.forced_mouse_cursor,
             Twips::from_pixels(self.movie_width.into()),
             Twips::from_pixels(self.movie_height.into()),
+            (self.viewport_width, self.viewport_height),
+            &mut self.scale_mode,
+            self.contents_scale_factor,
             self.self_reference.clone(),
             &mut self.system,
             &mut self.instance_counter,
@@ -1260,7 +1532,7 @@ impl Player {
             &mut self.time_offset,
         );
 
-        self.gc_arena.mutate(|gc_context, gc_root| {
+        let ret = self.gc_arena.mutate(|gc_context, gc_root| {
             let mut root_data = gc_root.0.write(gc_context);
             let mouse_hovered_object = root_data.mouse_hovered_object;
             let focus_tracker = root_data.focus_tracker;
@@ -1271,6 +1543,7 @@ impl Player {
                 avm1,
                 avm2,
                 drag_object,
+                current_context_menu,
                 load_manager,
                 shared_objects,
                 unbound_text_fields,
@@ -1294,8 +1567,14 @@ impl Player {
                 levels,
                 mouse_hovered_object,
                 mouse_position,
+                mouse_cursor,
+                forced_mouse_cursor,
                 drag_object,
+                current_context_menu,
                 stage_size: (stage_width, stage_height),
+                viewport_size,
+                scale_mode,
+                contents_scale_factor,
                 player,
                 load_manager,
                 system: system_properties,
@@ -1331,7 +1610,14 @@ impl Player {
             root_data.mouse_hovered_object = update_context.mouse_hovered_object;
 
             ret
-        })
+        });
+
+        // Content may have changed how the stage fits into the viewport.
+        if self.scale_mode != old_scale_mode {
+            self.build_matrices();
+        }
+
+        ret
     }
 
     /// Loads font data from the given buffer.
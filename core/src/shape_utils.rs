@@ -127,6 +127,132 @@ impl DrawCommand {
     }
 }
 
+/// The horizontal and vertical scale that a matrix draws at, ignoring any
+/// rotation or skew.
+pub fn matrix_scale(matrix: &Matrix) -> (f64, f64) {
+    let (a, b, c, d) = (
+        f64::from(matrix.a),
+        f64::from(matrix.b),
+        f64::from(matrix.c),
+        f64::from(matrix.d),
+    );
+    ((a * a + b * b).sqrt(), (c * c + d * d).sqrt())
+}
+
+/// Maps coordinates along one axis of a shape drawn with a scaling grid.
+#[derive(Debug, Copy, Clone)]
+struct Scale9Axis {
+    min: f64,
+    grid_min: f64,
+    grid_max: f64,
+    max: f64,
+    scale: f64,
+}
+
+impl Scale9Axis {
+    /// Returns `None` if the grid doesn't lie within the bounds, or if the
+    /// end slices wouldn't fit at `scale`.
+    fn new(min: Twips, grid_min: Twips, grid_max: Twips, max: Twips, scale: f64) -> Option<Self> {
+        let axis = Self {
+            min: f64::from(min.get()),
+            grid_min: f64::from(grid_min.get()),
+            grid_max: f64::from(grid_max.get()),
+            max: f64::from(max.get()),
+            scale,
+        };
+
+        if !(scale > 0.0 && scale.is_finite())
+            || axis.grid_min < axis.min
+            || axis.grid_max > axis.max
+            || axis.grid_min >= axis.grid_max
+            || (axis.grid_min - axis.min) + (axis.max - axis.grid_max)
+                > (axis.max - axis.min) * scale
+        {
+            return None;
+        }
+
+        Some(axis)
+    }
+
+    fn map(&self, value: Twips) -> Twips {
+        let value = f64::from(value.get());
+        let inner_min = self.min + (self.grid_min - self.min) / self.scale;
+        let inner_max = self.max - (self.max - self.grid_max) / self.scale;
+
+        let mapped = if value <= self.grid_min {
+            self.min + (value - self.min) / self.scale
+        } else if value >= self.grid_max {
+            self.max - (self.max - value) / self.scale
+        } else {
+            inner_min
+                + (value - self.grid_min) * (inner_max - inner_min)
+                    / (self.grid_max - self.grid_min)
+        };
+
+        Twips::new(mapped.round() as i32)
+    }
+}
+
+/// Warp a shape so that drawing it at `scale` honours a scaling grid.
+///
+/// `grid` is the center slice of the shape, in its own coordinates. Once the
+/// shape is drawn at `scale`, its corners keep their unscaled size, its edges
+/// only stretch along their length, and the center absorbs the rest.
+///
+/// Along any axis where the grid doesn't fit inside the shape's bounds, or the
+/// shape is too small to fit its unscaled edges, the shape scales normally.
+pub fn scale_9_grid_shape<'a>(
+    mut shape: DistilledShape<'a>,
+    grid: &BoundingBox,
+    scale: (f64, f64),
+) -> DistilledShape<'a> {
+    let bounds = &shape.shape_bounds;
+    if !bounds.valid || !grid.valid {
+        return shape;
+    }
+
+    let x_axis = Scale9Axis::new(bounds.x_min, grid.x_min, grid.x_max, bounds.x_max, scale.0);
+    let y_axis = Scale9Axis::new(bounds.y_min, grid.y_min, grid.y_max, bounds.y_max, scale.1);
+    if x_axis.is_none() && y_axis.is_none() {
+        return shape;
+    }
+
+    let map_x = |x: &mut Twips| {
+        if let Some(axis) = &x_axis {
+            *x = axis.map(*x);
+        }
+    };
+    let map_y = |y: &mut Twips| {
+        if let Some(axis) = &y_axis {
+            *y = axis.map(*y);
+        }
+    };
+
+    for path in &mut shape.paths {
+        let commands = match path {
+            DrawPath::Stroke { commands, .. } => commands,
+            DrawPath::Fill { commands, .. } => commands,
+        };
+
+        for command in commands {
+            match command {
+                DrawCommand::MoveTo { x, y } | DrawCommand::LineTo { x, y } => {
+                    map_x(x);
+                    map_y(y);
+                }
+                DrawCommand::CurveTo { x1, y1, x2, y2 } => {
+                    map_x(x1);
+                    map_y(y1);
+                    map_x(x2);
+                    map_y(y2);
+                }
+            }
+        }
+    }
+
+    shape
+}
+
 #[derive(Debug, Copy, Clone)]
 struct Point {
     x: Twips,
@@ -595,6 +721,98 @@ mod tests {
         }
     }
 
+    /// A fill through the given points, in pixels.
+    fn polyline_shape(points: &[(f64, f64)]) -> DistilledShape<'static> {
+        let mut commands: Vec<_> = points
+            .iter()
+            .map(|&(x, y)| DrawCommand::LineTo {
+                x: Twips::from_pixels(x),
+                y: Twips::from_pixels(y),
+            })
+            .collect();
+        commands[0] = DrawCommand::MoveTo {
+            x: Twips::from_pixels(points[0].0),
+            y: Twips::from_pixels(points[0].1),
+        };
+
+        let bounds = BoundingBox {
+            x_min: Twips::from_pixels(0.0),
+            y_min: Twips::from_pixels(0.0),
+            x_max: Twips::from_pixels(100.0),
+            y_max: Twips::from_pixels(100.0),
+            valid: true,
+        };
+        DistilledShape {
+            paths: vec![DrawPath::Fill {
+                style: &FILL_STYLES[0],
+                commands,
+            }],
+            shape_bounds: bounds.clone(),
+            edge_bounds: bounds,
+            id: 1,
+        }
+    }
+
+    /// Where each point of a shape ends up once drawn with `matrix`, in
+    /// pixels.
+    fn drawn_points(shape: &DistilledShape, matrix: Matrix) -> Vec<(f64, f64)> {
+        match &shape.paths[0] {
+            DrawPath::Fill { commands, .. } => commands
+                .iter()
+                .map(|command| {
+                    let (x, y) = matrix * command.end_point();
+                    (x.to_pixels(), y.to_pixels())
+                })
+                .collect(),
+            DrawPath::Stroke { .. } => unreachable!(),
+        }
+    }
+
+    fn grid(x_min: f64, y_min: f64, x_max: f64, y_max: f64) -> BoundingBox {
+        BoundingBox {
+            x_min: Twips::from_pixels(x_min),
+            y_min: Twips::from_pixels(y_min),
+            x_max: Twips::from_pixels(x_max),
+            y_max: Twips::from_pixels(y_max),
+            valid: true,
+        }
+    }
+
+    /// Drawn at four times the width and twice the height, the 10 pixel
+    /// corners of a nine-slice shape keep their size.
+    #[test]
+    fn scale_9_grid_keeps_corner_size() {
+        let points = [(0.0, 0.0), (10.0, 10.0), (90.0, 90.0), (100.0, 100.0)];
+        let matrix = Matrix::scale(4.0, 2.0);
+        let shape = scale_9_grid_shape(
+            polyline_shape(&points),
+            &grid(10.0, 10.0, 90.0, 90.0),
+            matrix_scale(&matrix),
+        );
+
+        assert_eq!(
+            drawn_points(&shape, matrix),
+            vec![(0.0, 0.0), (10.0, 10.0), (390.0, 190.0), (400.0, 200.0)]
+        );
+    }
+
+    /// A grid that reaches past the shape's bounds is ignored.
+    #[test]
+    fn oversized_scale_9_grid_scales_normally() {
+        let points = [(0.0, 0.0), (10.0, 10.0), (90.0, 90.0), (100.0, 100.0)];
+        let matrix = Matrix::scale(4.0, 2.0);
+        let shape = scale_9_grid_shape(
+            polyline_shape(&points),
+            &grid(-10.0, -10.0, 110.0, 110.0),
+            matrix_scale(&matrix),
+        );
+
+        assert_eq!(
+            drawn_points(&shape, matrix),
+            vec![(0.0, 0.0), (40.0, 20.0), (360.0, 180.0), (400.0, 200.0)]
+        );
+    }
+
     /// A simple solid square.
     #[test]
     fn basic_shape() {
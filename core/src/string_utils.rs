@@ -1,4 +1,5 @@
 ///! Utilities for operating on strings in SWF files.
+use crate::avm1::globals::system::Language;
 
 /// Gets the position of the previous char
 /// `pos` must already lie on a char boundary
@@ -95,6 +96,16 @@ pub fn swf_string_eq_ignore_case(a: &str, b: &str) -> bool {
         .eq(b.chars().map(swf_char_to_lowercase))
 }
 
+/// Compares two strings by their UTF-16 code units, as done by the Flash
+/// Player.
+///
+/// This differs from Rust's ordering of `str`, which is by code point, for
+/// characters outside the Basic Multilingual Plane: their surrogates sort
+/// before the characters from U+E000 to U+FFFF.
+pub fn swf_string_cmp(a: &str, b: &str) -> std::cmp::Ordering {
+    a.encode_utf16().cmp(b.encode_utf16())
+}
+
 /// Compares two strings, ignoring case as done by the Flash Player.
 /// Note that the case mapping is different than Rust's case mapping.
 pub fn swf_string_cmp_ignore_case(a: &str, b: &str) -> std::cmp::Ordering {
@@ -103,6 +114,84 @@ pub fn swf_string_cmp_ignore_case(a: &str, b: &str) -> std::cmp::Ordering {
         .cmp(b.chars().map(swf_char_to_lowercase))
 }
 
+/// Compares two strings in the collation order of the given host language,
+/// as used by `String.localeCompare`.
+///
+/// This is a simplified Unicode collation with three strengths: letters are
+/// first compared without accents or case, then by accent (unaccented before
+/// accented), and finally by case (lowercase before uppercase). The last step
+/// is skipped when `case_sensitive` is false. Nordic languages sort their
+/// extra letters after `z` instead of folding them onto their base letter.
+pub fn swf_string_locale_cmp(
+    a: &str,
+    b: &str,
+    language: &Language,
+    case_sensitive: bool,
+) -> std::cmp::Ordering {
+    let primary = |s: &str| {
+        s.chars()
+            .map(|c| collation_primary_weight(swf_char_to_lowercase(c), language))
+            .collect::<Vec<_>>()
+    };
+    let secondary = |s: &str| s.chars().map(swf_char_to_lowercase).collect::<Vec<_>>();
+    // Lowercase letters sort before their uppercase forms.
+    let tertiary = |s: &str| {
+        s.chars()
+            .map(|c| swf_char_to_lowercase(c) != c)
+            .collect::<Vec<_>>()
+    };
+
+    let order = primary(a)
+        .cmp(&primary(b))
+        .then_with(|| secondary(a).cmp(&secondary(b)));
+
+    if case_sensitive {
+        order.then_with(|| tertiary(a).cmp(&tertiary(b)))
+    } else {
+        order
+    }
+}
+
+/// The primary collation weight of a lowercase char: accented Latin letters
+/// share the weight of their base letter, except where `language` treats them
+/// as letters of their own.
+fn collation_primary_weight(c: char, language: &Language) -> u32 {
+    let tailored = match language {
+        Language::Swedish | Language::Finnish => ['å', 'ä', 'ö'].iter().position(|&l| l == c),
+        Language::Danish | Language::Norwegian => ['æ', 'ø', 'å'].iter().position(|&l| l == c),
+        _ => None,
+    };
+
+    if let Some(position) = tailored {
+        return u32::from('z') + 1 + position as u32;
+    }
+
+    let base = match c {
+        'à'..='å' | 'ā' | 'ă' | 'ą' => 'a',
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => 'c',
+        'ď' | 'đ' => 'd',
+        'è'..='ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => 'e',
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => 'g',
+        'ĥ' | 'ħ' => 'h',
+        'ì'..='ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => 'i',
+        'ĵ' => 'j',
+        'ķ' => 'k',
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => 'l',
+        'ñ' | 'ń' | 'ņ' | 'ň' => 'n',
+        'ò'..='ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => 'o',
+        'ŕ' | 'ŗ' | 'ř' => 'r',
+        'ś' | 'ŝ' | 'ş' | 'š' => 's',
+        'ţ' | 'ť' | 'ŧ' => 't',
+        'ù'..='ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => 'u',
+        'ŵ' => 'w',
+        'ý' | 'ÿ' | 'ŷ' => 'y',
+        'ź' | 'ż' | 'ž' => 'z',
+        c => c,
+    };
+
+    u32::from(base)
+}
+
 static UPPERCASE_TABLE: &[(u16, u16)] = &[
     (97, 65),
     (98, 66),
@@ -1556,3 +1645,61 @@ static LOWERCASE_TABLE: &[(u16, u16)] = &[
     (65337, 65369),
     (65338, 65370),
 ];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn locale_sorted(language: Language, case_sensitive: bool, words: &[&str]) -> Vec<String> {
+        let mut words: Vec<String> = words.iter().map(|w| w.to_string()).collect();
+        words.sort_by(|a, b| swf_string_locale_cmp(a, b, &language, case_sensitive));
+        words
+    }
+
+    #[test]
+    fn locale_cmp_orders_accents_and_case() {
+        // Accents are ignored until the base letters tie, unaccented words
+        // come first, and lowercase comes before uppercase.
+        assert_eq!(
+            locale_sorted(
+                Language::French,
+                true,
+                &["cote", "Côte", "côté", "côte", "Cote", "coté", "Bz", "ça"]
+            ),
+            vec!["Bz", "ça", "cote", "Cote", "coté", "côte", "Côte", "côté"]
+        );
+        assert_eq!(
+            swf_string_locale_cmp("résumé", "RESUME", &Language::English, true),
+            std::cmp::Ordering::Greater
+        );
+    }
+
+    #[test]
+    fn locale_cmp_ignores_case_when_insensitive() {
+        assert_eq!(
+            swf_string_locale_cmp("Éclair", "éclair", &Language::French, false),
+            std::cmp::Ordering::Equal
+        );
+        assert_eq!(
+            locale_sorted(
+                Language::English,
+                false,
+                &["zebra", "Émile", "apple", "Eve"]
+            ),
+            vec!["apple", "Émile", "Eve", "zebra"]
+        );
+    }
+
+    #[test]
+    fn locale_cmp_tailors_nordic_letters() {
+        let words = ["ödla", "zon", "åsna", "orm"];
+        assert_eq!(
+            locale_sorted(Language::Swedish, true, &words),
+            vec!["orm", "zon", "åsna", "ödla"]
+        );
+        assert_eq!(
+            locale_sorted(Language::German, true, &words),
+            vec!["åsna", "ödla", "orm", "zon"]
+        );
+    }
+}
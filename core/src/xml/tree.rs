@@ -228,8 +228,31 @@ impl<'gc> XmlNode<'gc> {
         data: &str,
         process_entity: bool,
         ignore_white: bool,
+    ) -> Result<(), Error> {
+        self.parse_str(mc, data, process_entity, ignore_white, false)
+    }
+
+    /// Replace the contents of this node with the result of parsing a string
+    /// of text field HTML.
+    ///
+    /// This is as lenient as Flash's text field parser: end tags close
+    /// everything opened since their matching start tag, end tags without one
+    /// are ignored, `<img>` never has contents, and anything after malformed
+    /// markup is dropped instead of failing the whole parse.
+    pub fn replace_with_html_str(&mut self, mc: MutationContext<'gc, '_>, data: &str) {
+        let _ = self.parse_str(mc, data, false, false, true);
+    }
+
+    fn parse_str(
+        &mut self,
+        mc: MutationContext<'gc, '_>,
+        data: &str,
+        process_entity: bool,
+        ignore_white: bool,
+        lenient: bool,
     ) -> Result<(), Error> {
         let mut parser = Reader::from_str(data);
+        parser.check_end_names(!lenient);
         let mut buf = Vec::new();
         let document = self.document();
         let mut open_tags: Vec<XmlNode<'gc>> = Vec::new();
@@ -237,11 +260,33 @@ impl<'gc> XmlNode<'gc> {
         document.clear_parse_error(mc);
 
         loop {
-            let event = document.log_parse_result(mc, parser.read_event(&mut buf))?;
+            let event = match document.log_parse_result(mc, parser.read_event(&mut buf)) {
+                Ok(event) => event,
+                Err(_) if lenient => break,
+                Err(err) => return Err(err.into()),
+            };
 
             document.process_event(mc, &event)?;
 
             match event {
+                Event::Start(bs) if lenient && bs.name().eq_ignore_ascii_case(b"img") => {
+                    let child = XmlNode::from_start_event(mc, bs, document)?;
+                    self.add_child_to_tree(mc, &mut open_tags, child)?;
+                }
+                Event::End(be) if lenient => {
+                    let name = be.name();
+                    let open = open_tags.iter().rposition(|tag| {
+                        tag.tag_name()
+                            .map(|tag_name| {
+                                tag_name.node_name().as_bytes().eq_ignore_ascii_case(name)
+                            })
+                            .unwrap_or(false)
+                    });
+
+                    if let Some(open) = open {
+                        open_tags.truncate(open);
+                    }
+                }
                 Event::Start(bs) => {
                     let child = XmlNode::from_start_event(mc, bs, document)?;
                     self.document().update_idmap(mc, child);
@@ -35,7 +35,8 @@ use std::io::Read;
 use std::rc::Rc;
 use winit::dpi::{LogicalSize, PhysicalPosition};
 use winit::event::{
-    ElementState, KeyboardInput, MouseButton, MouseScrollDelta, VirtualKeyCode, WindowEvent,
+    DeviceEvent, ElementState, KeyboardInput, MouseButton, MouseScrollDelta, VirtualKeyCode,
+    WindowEvent,
 };
 use winit::event_loop::{ControlFlow, EventLoop};
 use winit::window::{Fullscreen, Icon, WindowBuilder};
@@ -254,6 +255,7 @@ fn run_player(opt: Opt) -> Result<(), Box<dyn std::error::Error>> {
         player.set_is_playing(true); // Desktop player will auto-play.
         player.set_letterbox(Letterbox::On);
         player.set_viewport_dimensions(viewport_size.width, viewport_size.height);
+        player.set_contents_scale_factor(window.scale_factor());
     }
 
     let mut mouse_pos = PhysicalPosition::new(0.0, 0.0);
@@ -307,7 +309,16 @@ fn run_player(opt: Opt) -> Result<(), Box<dyn std::error::Error>> {
                             .set_viewport_dimensions(size.width, size.height);
                         window.request_redraw();
                     }
-                    WindowEvent::CursorMoved { position, .. } => {
+                    WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+                        player
+                            .lock()
+                            .unwrap()
+                            .set_contents_scale_factor(scale_factor);
+                    }
+                    // A locked mouse moves by the device's raw motion instead.
+                    WindowEvent::CursorMoved { position, .. }
+                        if !player.lock().unwrap().ui().is_mouse_locked() =>
+                    {
                         let mut player_lock = player.lock().unwrap();
                         mouse_pos = position;
                         let event = ruffle_core::PlayerEvent::MouseMove {
@@ -361,6 +372,13 @@ fn run_player(opt: Opt) -> Result<(), Box<dyn std::error::Error>> {
                             window.request_redraw();
                         }
                     }
+                    WindowEvent::Focused(focused) => {
+                        let mut player_lock = player.lock().unwrap();
+                        if !focused {
+                            player_lock.handle_event(ruffle_core::PlayerEvent::FocusLost);
+                        }
+                        player_lock.set_focused(focused);
+                    }
                     WindowEvent::CloseRequested => *control_flow = ControlFlow::Exit,
                     WindowEvent::KeyboardInput {
                         input:
@@ -377,6 +395,10 @@ fn run_player(opt: Opt) -> Result<(), Box<dyn std::error::Error>> {
                                 None => Some(Fullscreen::Borderless(None)),
                                 Some(_) => None,
                             });
+                            player
+                                .lock()
+                                .unwrap()
+                                .set_fullscreen_state(window.fullscreen().is_some());
                         }
                         fullscreen_down = true;
                     }
@@ -399,8 +421,9 @@ fn run_player(opt: Opt) -> Result<(), Box<dyn std::error::Error>> {
                                 ..
                             },
                         ..
-                    } => {
+                    } if window.fullscreen().is_some() => {
                         window.set_fullscreen(None);
+                        player.lock().unwrap().set_fullscreen_state(false);
                     }
                     WindowEvent::KeyboardInput { .. } | WindowEvent::ReceivedCharacter(_) => {
                         let mut player_lock = player.lock().unwrap();
@@ -418,6 +441,23 @@ fn run_player(opt: Opt) -> Result<(), Box<dyn std::error::Error>> {
                     }
                     _ => (),
                 },
+                winit::event::Event::DeviceEvent {
+                    event: DeviceEvent::MouseMotion { delta },
+                    ..
+                } => {
+                    let mut player_lock = player.lock().unwrap();
+                    if player_lock.ui().is_mouse_locked() {
+                        mouse_pos.x += delta.0;
+                        mouse_pos.y += delta.1;
+                        player_lock.handle_event(ruffle_core::PlayerEvent::MouseMove {
+                            x: mouse_pos.x,
+                            y: mouse_pos.y,
+                        });
+                        if player_lock.needs_render() {
+                            window.request_redraw();
+                        }
+                    }
+                }
                 winit::event::Event::UserEvent(RuffleEvent::TaskPoll) => executor
                     .lock()
                     .expect("active executor reference")
@@ -136,6 +136,9 @@ impl NavigatorBackend for ExternalNavigatorBackend {
                 let request = match options.method() {
                     NavigationMethod::Get => Request::get(processed_url.to_string()),
                     NavigationMethod::Post => Request::post(processed_url.to_string()),
+                    NavigationMethod::Put => Request::put(processed_url.to_string()),
+                    NavigationMethod::Delete => Request::delete(processed_url.to_string()),
+                    NavigationMethod::Head => Request::head(processed_url.to_string()),
                 };
 
                 let (body_data, _) = options.body().clone().unwrap_or_default();
@@ -148,6 +151,11 @@ impl NavigatorBackend for ExternalNavigatorBackend {
                     .await
                     .map_err(|e| Error::FetchError(e.to_string()))?;
 
+                let status = response.status();
+                if !status.is_success() {
+                    return Err(Error::HttpNotOk(processed_url.to_string(), status.as_u16()));
+                }
+
                 let mut buffer = vec![];
                 response
                     .copy_to(&mut buffer)
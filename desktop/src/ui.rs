@@ -5,12 +5,13 @@ use std::collections::HashSet;
 use std::rc::Rc;
 use tinyfiledialogs::{message_box_ok, MessageBoxIcon};
 use winit::event::{ElementState, ModifiersState, VirtualKeyCode, WindowEvent};
-use winit::window::Window;
+use winit::window::{Fullscreen, Window};
 
 pub struct DesktopUiBackend {
     window: Rc<Window>,
     keys_down: HashSet<VirtualKeyCode>,
     cursor_visible: bool,
+    mouse_locked: bool,
     last_key: KeyCode,
     last_char: Option<char>,
     clipboard: ClipboardContext,
@@ -22,6 +23,7 @@ impl DesktopUiBackend {
             window,
             keys_down: HashSet::new(),
             cursor_visible: true,
+            mouse_locked: false,
             last_key: KeyCode::Unknown,
             last_char: None,
             clipboard: ClipboardProvider::new().unwrap(),
@@ -82,6 +84,7 @@ impl UiBackend for DesktopUiBackend {
         match key {
             KeyCode::Unknown => false,
             KeyCode::Backspace => self.keys_down.contains(&VirtualKeyCode::Back),
+            KeyCode::Tab => self.keys_down.contains(&VirtualKeyCode::Tab),
             KeyCode::Return => self.keys_down.contains(&VirtualKeyCode::Return),
             KeyCode::Shift => {
                 self.keys_down.contains(&VirtualKeyCode::LShift)
@@ -200,7 +203,7 @@ impl UiBackend for DesktopUiBackend {
     }
 
     fn set_mouse_visible(&mut self, visible: bool) {
-        self.window.set_cursor_visible(visible);
+        self.window.set_cursor_visible(visible && !self.mouse_locked);
         self.cursor_visible = visible;
     }
 
@@ -223,6 +226,35 @@ impl UiBackend for DesktopUiBackend {
         self.window.fullscreen().is_some()
     }
 
+    fn allows_fullscreen(&self) -> bool {
+        true
+    }
+
+    fn set_fullscreen(&mut self, is_full: bool) -> bool {
+        self.window.set_fullscreen(if is_full {
+            Some(Fullscreen::Borderless(None))
+        } else {
+            None
+        });
+
+        true
+    }
+
+    fn is_mouse_locked(&self) -> bool {
+        self.mouse_locked
+    }
+
+    fn set_mouse_locked(&mut self, locked: bool) -> bool {
+        if self.window.set_cursor_grab(locked).is_err() {
+            return false;
+        }
+
+        self.window
+            .set_cursor_visible(self.cursor_visible && !locked);
+        self.mouse_locked = locked;
+        true
+    }
+
     fn display_unsupported_message(&self) {
         message_box_ok(
             "Ruffle - Unsupported content",
@@ -241,6 +273,7 @@ impl UiBackend for DesktopUiBackend {
 fn winit_to_ruffle_key_code(key_code: VirtualKeyCode) -> Option<KeyCode> {
     Some(match key_code {
         VirtualKeyCode::Back => KeyCode::Backspace,
+        VirtualKeyCode::Tab => KeyCode::Tab,
         VirtualKeyCode::Return => KeyCode::Return,
         VirtualKeyCode::LShift | VirtualKeyCode::RShift => KeyCode::Shift,
         VirtualKeyCode::LControl | VirtualKeyCode::RControl => KeyCode::Control,
@@ -243,10 +243,32 @@ impl ShapeTessellator {
                     commands,
                     is_closed,
                 } => {
-                    let color = ((style.color.a as u32) << 24)
-                        | ((style.color.b as u32) << 16)
-                        | ((style.color.g as u32) << 8)
-                        | (style.color.r as u32);
+                    // Strokes with a gradient fill are drawn in white, and then
+                    // colored by the gradient as fills are.
+                    let gradient = match &style.fill_style {
+                        Some(FillStyle::LinearGradient(gradient)) => {
+                            Some((GradientType::Linear, gradient, 0.0))
+                        }
+                        Some(FillStyle::RadialGradient(gradient)) => {
+                            Some((GradientType::Radial, gradient, 0.0))
+                        }
+                        Some(FillStyle::FocalGradient {
+                            gradient,
+                            focal_point,
+                        }) => Some((GradientType::Focal, gradient, *focal_point)),
+                        _ => None,
+                    };
+
+                    let color = if gradient.is_some() {
+                        flush_draw(DrawType::Color, &mut mesh, &mut lyon_mesh);
+
+                        0xffff_ffff
+                    } else {
+                        ((style.color.a as u32) << 24)
+                            | ((style.color.b as u32) << 16)
+                            | ((style.color.g as u32) << 8)
+                            | (style.color.r as u32)
+                    };
 
                     let mut buffers_builder =
                         BuffersBuilder::new(&mut lyon_mesh, RuffleVertexCtor { color });
@@ -295,6 +317,33 @@ impl ShapeTessellator {
                         log::error!("Tessellation failure: {:?}", e);
                         continue;
                     }
+
+                    if let Some((gradient_type, gradient, focal_point)) = gradient {
+                        let mut colors: Vec<[f32; 4]> = Vec::with_capacity(8);
+                        let mut ratios: Vec<f32> = Vec::with_capacity(8);
+                        for record in &gradient.records {
+                            colors.push([
+                                f32::from(record.color.r) / 255.0,
+                                f32::from(record.color.g) / 255.0,
+                                f32::from(record.color.b) / 255.0,
+                                f32::from(record.color.a) / 255.0,
+                            ]);
+                            ratios.push(f32::from(record.ratio) / 255.0);
+                        }
+
+                        let gradient = Gradient {
+                            gradient_type,
+                            ratios,
+                            colors,
+                            num_colors: gradient.records.len() as u32,
+                            matrix: swf_to_gl_matrix(gradient.matrix),
+                            repeat_mode: gradient.spread,
+                            focal_point,
+                            interpolation: gradient.interpolation,
+                        };
+
+                        flush_draw(DrawType::Gradient(gradient), &mut mesh, &mut lyon_mesh);
+                    }
                 }
             }
         }
@@ -22,9 +22,11 @@ void main() {
     // Unmultiply alpha before apply color transform.
     if( color.a > 0.0 ) {
         color.rgb /= color.a;
-        color = mult_color * color + add_color;
-        color.rgb *= color.a;
     }
 
+    // Clamp before premultiplying again, so that large offsets saturate.
+    color = clamp(mult_color * color + add_color, 0.0, 1.0);
+    color.rgb *= color.a;
+
     gl_FragColor = color;
 }
\ No newline at end of file
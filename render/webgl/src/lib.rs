@@ -758,6 +758,10 @@ impl WebGlRenderBackend {
 }
 
 impl RenderBackend for WebGlRenderBackend {
+    fn is_hardware_accelerated(&self) -> bool {
+        true
+    }
+
     fn set_viewport_dimensions(&mut self, width: u32, height: u32) {
         self.view_width = width as i32;
         self.view_height = height as i32;
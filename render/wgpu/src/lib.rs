@@ -592,12 +592,37 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
                     commands,
                     is_closed,
                 } => {
-                    let color = [
-                        f32::from(style.color.r) / 255.0,
-                        f32::from(style.color.g) / 255.0,
-                        f32::from(style.color.b) / 255.0,
-                        f32::from(style.color.a) / 255.0,
-                    ];
+                    // Strokes with a gradient fill are drawn in white, and then
+                    // colored by the gradient as fills are.
+                    let gradient = match &style.fill_style {
+                        Some(FillStyle::LinearGradient(gradient)) => Some((0, gradient, 0.0)),
+                        Some(FillStyle::RadialGradient(gradient)) => Some((1, gradient, 0.0)),
+                        Some(FillStyle::FocalGradient {
+                            gradient,
+                            focal_point,
+                        }) => Some((2, gradient, *focal_point)),
+                        _ => None,
+                    };
+
+                    let color = if gradient.is_some() {
+                        flush_draw(
+                            shape.id,
+                            IncompleteDrawType::Color,
+                            &mut draws,
+                            &mut lyon_mesh,
+                            &self.descriptors.device,
+                            &self.descriptors.pipelines,
+                        );
+
+                        [1.0, 1.0, 1.0, 1.0]
+                    } else {
+                        [
+                            f32::from(style.color.r) / 255.0,
+                            f32::from(style.color.g) / 255.0,
+                            f32::from(style.color.b) / 255.0,
+                            f32::from(style.color.a) / 255.0,
+                        ]
+                    };
 
                     let mut buffers_builder =
                         BuffersBuilder::new(&mut lyon_mesh, RuffleVertexCtor { color });
@@ -646,6 +671,24 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
                         log::error!("Tessellation failure: {:?}", e);
                         continue;
                     }
+
+                    if let Some((gradient_type, gradient, focal_point)) = gradient {
+                        let uniforms =
+                            swf_gradient_to_uniforms(gradient_type, gradient, focal_point);
+                        let matrix = swf_to_gl_matrix(gradient.matrix);
+
+                        flush_draw(
+                            shape.id,
+                            IncompleteDrawType::Gradient {
+                                texture_transform: matrix,
+                                gradient: uniforms,
+                            },
+                            &mut draws,
+                            &mut lyon_mesh,
+                            &self.descriptors.device,
+                            &self.descriptors.pipelines,
+                        );
+                    }
                 }
             }
         }
@@ -772,6 +815,10 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
 }
 
 impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
+    fn is_hardware_accelerated(&self) -> bool {
+        true
+    }
+
     fn set_viewport_dimensions(&mut self, width: u32, height: u32) {
         // Avoid panics from creating 0-sized framebuffers.
         let width = std::cmp::max(width, 1);
@@ -91,6 +91,11 @@ pub enum OpCode {
     Label = 0x09,
     LessEquals = 0xae,
     LessThan = 0xad,
+    Lf32 = 0x38,
+    Lf64 = 0x39,
+    Li16 = 0x36,
+    Li32 = 0x37,
+    Li8 = 0x35,
     LookupSwitch = 0x1b,
     LShift = 0xa5,
     Modulo = 0xa4,
@@ -136,6 +141,11 @@ pub enum OpCode {
     SetProperty = 0x61,
     SetSlot = 0x6d,
     SetSuper = 0x05,
+    Sf32 = 0x3d,
+    Sf64 = 0x3e,
+    Si16 = 0x3b,
+    Si32 = 0x3c,
+    Si8 = 0x3a,
     StrictEquals = 0xac,
     Subtract = 0xa1,
     SubtractI = 0xc6,
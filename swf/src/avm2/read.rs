@@ -281,10 +281,14 @@ impl<R: Read> Reader<R> {
         let flags = self.read_u8()?;
 
         if flags & 0x08 != 0 {
+            // Optional parameters are always the last ones.
             let num_optional_params = self.read_u30()? as usize;
-            #[allow(clippy::needless_range_loop)]
+            let first_optional_param = num_params.saturating_sub(num_optional_params);
             for i in 0..num_optional_params {
-                params[i].default_value = Some(self.read_constant_value()?);
+                let default_value = self.read_constant_value()?;
+                if let Some(param) = params.get_mut(first_optional_param + i) {
+                    param.default_value = Some(default_value);
+                }
             }
         }
 
@@ -749,6 +753,11 @@ impl<R: Read> Reader<R> {
             OpCode::Label => Op::Label,
             OpCode::LessEquals => Op::LessEquals,
             OpCode::LessThan => Op::LessThan,
+            OpCode::Lf32 => Op::Lf32,
+            OpCode::Lf64 => Op::Lf64,
+            OpCode::Li16 => Op::Li16,
+            OpCode::Li32 => Op::Li32,
+            OpCode::Li8 => Op::Li8,
             OpCode::LookupSwitch => Op::LookupSwitch {
                 default_offset: self.read_i24()?,
                 case_offsets: {
@@ -838,6 +847,11 @@ impl<R: Read> Reader<R> {
             OpCode::SetSuper => Op::SetSuper {
                 index: self.read_index()?,
             },
+            OpCode::Sf32 => Op::Sf32,
+            OpCode::Sf64 => Op::Sf64,
+            OpCode::Si16 => Op::Si16,
+            OpCode::Si32 => Op::Si32,
+            OpCode::Si8 => Op::Si8,
             OpCode::StrictEquals => Op::StrictEquals,
             OpCode::Subtract => Op::Subtract,
             OpCode::SubtractI => Op::SubtractI,
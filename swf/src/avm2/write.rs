@@ -890,6 +890,11 @@ impl<W: Write> Writer<W> {
             Op::Label => self.write_opcode(OpCode::Label)?,
             Op::LessEquals => self.write_opcode(OpCode::LessEquals)?,
             Op::LessThan => self.write_opcode(OpCode::LessThan)?,
+            Op::Lf32 => self.write_opcode(OpCode::Lf32)?,
+            Op::Lf64 => self.write_opcode(OpCode::Lf64)?,
+            Op::Li16 => self.write_opcode(OpCode::Li16)?,
+            Op::Li32 => self.write_opcode(OpCode::Li32)?,
+            Op::Li8 => self.write_opcode(OpCode::Li8)?,
             Op::LookupSwitch {
                 default_offset,
                 ref case_offsets,
@@ -998,6 +1003,11 @@ impl<W: Write> Writer<W> {
                 self.write_opcode(OpCode::SetSuper)?;
                 self.write_index(index)?;
             }
+            Op::Sf32 => self.write_opcode(OpCode::Sf32)?,
+            Op::Sf64 => self.write_opcode(OpCode::Sf64)?,
+            Op::Si16 => self.write_opcode(OpCode::Si16)?,
+            Op::Si32 => self.write_opcode(OpCode::Si32)?,
+            Op::Si8 => self.write_opcode(OpCode::Si8)?,
             Op::StrictEquals => self.write_opcode(OpCode::StrictEquals)?,
             Op::Subtract => self.write_opcode(OpCode::Subtract)?,
             Op::SubtractI => self.write_opcode(OpCode::SubtractI)?,
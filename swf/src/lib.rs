@@ -39,4 +39,4 @@ pub use read::{decompress_swf, parse_swf};
 pub use string::*;
 pub use tag_code::TagCode;
 pub use types::*;
-pub use write::write_swf;
+pub use write::{write_swf, write_swf_raw_tags};
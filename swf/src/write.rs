@@ -38,34 +38,42 @@ use std::io::{self, Write};
 /// let output = Vec::new();
 /// swf::write_swf(&swf, output).unwrap();
 /// ```
-pub fn write_swf<W: Write>(swf: &Swf, mut output: W) -> Result<()> {
-    let signature = match swf.header.compression {
+pub fn write_swf<W: Write>(swf: &Swf, output: W) -> Result<()> {
+    // Write main timeline tag list.
+    let mut tags = Vec::new();
+    Writer::new(&mut tags, swf.header.version).write_tag_list(&swf.tags)?;
+
+    write_swf_raw_tags(&swf.header, &tags, output)
+}
+
+/// Writes a SWF file to an output stream from a header and its already
+/// encoded tags, such as the data returned by `decompress_swf`.
+pub fn write_swf_raw_tags<W: Write>(header: &Header, tags: &[u8], mut output: W) -> Result<()> {
+    let signature = match header.compression {
         Compression::None => b"FWS",
         Compression::Zlib => b"CWS",
         Compression::Lzma => b"ZWS",
     };
     output.write_all(&signature[..])?;
-    output.write_u8(swf.header.version)?;
+    output.write_u8(header.version)?;
 
     // Write SWF body.
     let mut swf_body = Vec::new();
     {
-        let mut writer = Writer::new(&mut swf_body, swf.header.version);
-
-        writer.write_rectangle(&swf.header.stage_size)?;
-        writer.write_fixed8(swf.header.frame_rate)?;
-        writer.write_u16(swf.header.num_frames)?;
+        let mut writer = Writer::new(&mut swf_body, header.version);
 
-        // Write main timeline tag list.
-        writer.write_tag_list(&swf.tags)?;
+        writer.write_rectangle(&header.stage_size)?;
+        writer.write_fixed8(header.frame_rate)?;
+        writer.write_u16(header.num_frames)?;
     }
+    swf_body.extend_from_slice(tags);
 
     // Write SWF header.
     // Uncompressed SWF length.
     output.write_u32::<LittleEndian>(swf_body.len() as u32 + 8)?;
 
     // Compress SWF body.
-    match swf.header.compression {
+    match header.compression {
         Compression::None => {
             output.write_all(&swf_body)?;
         }
@@ -797,6 +797,11 @@ impl AudioBackend for WebAudioBackend {
         self.frame_rate = frame_rate
     }
 
+    fn supports_mp3(&self) -> bool {
+        // MP3 sounds are decoded by the browser.
+        true
+    }
+
     fn register_sound(&mut self, sound: &swf::Sound) -> Result<SoundHandle, Error> {
         // Slice off latency seek for MP3 data.
         let (skip_sample_frames, data) = if sound.format.compression == AudioCompression::Mp3 {
@@ -545,6 +545,12 @@ impl Ruffle {
                                 y: f64::from(js_event.offset_y()) * instance.device_pixel_ratio,
                             };
                             instance.core.lock().unwrap().handle_event(event);
+                            if let Some((id, x, y, pressure)) =
+                                touch_point(&js_event, instance.device_pixel_ratio)
+                            {
+                                let event = PlayerEvent::TouchMove { id, x, y, pressure };
+                                instance.core.lock().unwrap().handle_event(event);
+                            }
                             if instance.has_focus {
                                 js_event.prevent_default();
                             }
@@ -583,6 +589,12 @@ impl Ruffle {
                                     y: f64::from(js_event.offset_y()) * device_pixel_ratio,
                                 };
                                 instance.borrow().core.lock().unwrap().handle_event(event);
+                                if let Some((id, x, y, pressure)) =
+                                    touch_point(&js_event, device_pixel_ratio)
+                                {
+                                    let event = PlayerEvent::TouchBegin { id, x, y, pressure };
+                                    instance.borrow().core.lock().unwrap().handle_event(event);
+                                }
                             }
 
                             js_event.prevent_default();
@@ -674,6 +686,12 @@ impl Ruffle {
                                     y: f64::from(js_event.offset_y()) * instance.device_pixel_ratio,
                                 };
                                 instance.core.lock().unwrap().handle_event(event);
+                                if let Some((id, x, y, pressure)) =
+                                    touch_point(&js_event, instance.device_pixel_ratio)
+                                {
+                                    let event = PlayerEvent::TouchEnd { id, x, y, pressure };
+                                    instance.core.lock().unwrap().handle_event(event);
+                                }
                             }
 
                             if instance.has_focus {
@@ -890,6 +908,7 @@ impl Ruffle {
                     canvas.set_height(viewport_height);
 
                     core_lock.set_viewport_dimensions(viewport_width, viewport_height);
+                    core_lock.set_contents_scale_factor(device_pixel_ratio);
                     core_lock
                         .renderer_mut()
                         .set_viewport_dimensions(viewport_width, viewport_height);
@@ -1002,6 +1021,22 @@ impl ExternalInterfaceProvider for JavascriptInterface {
     }
 }
 
+/// Get the ID, position and pressure of a touch screen's pointer event.
+///
+/// Pointer events from a mouse or pen yield `None`.
+fn touch_point(js_event: &PointerEvent, device_pixel_ratio: f64) -> Option<(i32, f64, f64, f64)> {
+    if js_event.pointer_type() != "touch" {
+        return None;
+    }
+
+    Some((
+        js_event.pointer_id(),
+        f64::from(js_event.offset_x()) * device_pixel_ratio,
+        f64::from(js_event.offset_y()) * device_pixel_ratio,
+        f64::from(js_event.pressure()),
+    ))
+}
+
 fn js_to_external_value(js: &JsValue) -> ExternalValue {
     if let Some(value) = js.as_f64() {
         ExternalValue::Number(value)
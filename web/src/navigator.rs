@@ -110,9 +110,11 @@ impl NavigatorBackend for WebNavigatorBackend {
 
                     let _ = form.set_attribute(
                         "method",
+                        // Forms can only GET or POST, and navigation never
+                        // asks for anything else.
                         match navmethod {
                             NavigationMethod::Get => "get",
-                            NavigationMethod::Post => "post",
+                            _ => "post",
                         },
                     );
 
@@ -167,6 +169,9 @@ impl NavigatorBackend for WebNavigatorBackend {
             init.method(match options.method() {
                 NavigationMethod::Get => "GET",
                 NavigationMethod::Post => "POST",
+                NavigationMethod::Put => "PUT",
+                NavigationMethod::Delete => "DELETE",
+                NavigationMethod::Head => "HEAD",
             });
 
             if let Some((data, mime)) = options.body() {
@@ -205,6 +210,10 @@ impl NavigatorBackend for WebNavigatorBackend {
             }
 
             let resp: Response = fetchval.unwrap().dyn_into().unwrap();
+            if !resp.ok() {
+                return Err(Error::HttpNotOk(url, resp.status()));
+            }
+
             let data: ArrayBuffer = JsFuture::from(resp.array_buffer().unwrap())
                 .await
                 .unwrap()
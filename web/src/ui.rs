@@ -15,6 +15,8 @@ pub struct WebUiBackend {
     cursor: MouseCursor,
     last_key: KeyCode,
     last_char: Option<char>,
+    caps_lock: bool,
+    num_lock: bool,
 }
 
 impl WebUiBackend {
@@ -27,6 +29,8 @@ impl WebUiBackend {
             cursor: MouseCursor::Arrow,
             last_key: KeyCode::Unknown,
             last_char: None,
+            caps_lock: false,
+            num_lock: false,
         }
     }
 
@@ -36,6 +40,7 @@ impl WebUiBackend {
         self.last_key = web_to_ruffle_key_code(&code).unwrap_or(KeyCode::Unknown);
         self.keys_down.insert(code);
         self.last_char = web_key_to_codepoint(&event.key());
+        self.update_lock_state(event);
     }
 
     /// Register a key release for a given code string.
@@ -44,6 +49,14 @@ impl WebUiBackend {
         self.last_key = web_to_ruffle_key_code(&code).unwrap_or(KeyCode::Unknown);
         self.keys_down.remove(&code);
         self.last_char = web_key_to_codepoint(&event.key());
+        self.update_lock_state(event);
+    }
+
+    /// The browser only tells us the state of the lock keys with each key
+    /// event, so remember it from the last one.
+    fn update_lock_state(&mut self, event: &KeyboardEvent) {
+        self.caps_lock = event.get_modifier_state("CapsLock");
+        self.num_lock = event.get_modifier_state("NumLock");
     }
 
     fn update_mouse_cursor(&self) {
@@ -69,6 +82,7 @@ impl UiBackend for WebUiBackend {
         match key {
             KeyCode::Unknown => false,
             KeyCode::Backspace => self.keys_down.contains("Backspace"),
+            KeyCode::Tab => self.keys_down.contains("Tab"),
             KeyCode::Return => self.keys_down.contains("Enter"),
             KeyCode::Shift => {
                 self.keys_down.contains("ShiftLeft") || self.keys_down.contains("ShiftRight")
@@ -179,6 +193,14 @@ impl UiBackend for WebUiBackend {
         self.last_char
     }
 
+    fn is_caps_lock_on(&self) -> bool {
+        self.caps_lock
+    }
+
+    fn is_num_lock_on(&self) -> bool {
+        self.num_lock
+    }
+
     fn mouse_visible(&self) -> bool {
         self.cursor_visible
     }
@@ -201,6 +223,12 @@ impl UiBackend for WebUiBackend {
         self.js_player.is_fullscreen()
     }
 
+    fn max_touch_points(&self) -> u32 {
+        web_sys::window()
+            .map(|window| window.navigator().max_touch_points().max(0) as u32)
+            .unwrap_or(0)
+    }
+
     fn display_unsupported_message(&self) {
         self.js_player.display_unsupported_message()
     }
@@ -215,6 +243,7 @@ impl UiBackend for WebUiBackend {
 pub fn web_to_ruffle_key_code(key_code: &str) -> Option<KeyCode> {
     Some(match key_code {
         "Backspace" => KeyCode::Backspace,
+        "Tab" => KeyCode::Tab,
         "Enter" => KeyCode::Return,
         "ShiftLeft" | "ShiftRight" => KeyCode::Shift,
         "ControlLeft" | "ControlRight" => KeyCode::Control,